@@ -4,13 +4,24 @@
 //! Provides 1:1 mapping to Rust API.
 
 use pyo3::prelude::*;
+use pyo3::types::PyDict;
 
 use ::zengeld_canvas::api::{
-    Chart as RustChart, ChartConfig as RustChartConfig, PrimitiveConfig, SignalConfig,
+    Chart as RustChart, ChartConfig as RustChartConfig, ChartRenderer, GapConfig, GapThreshold,
+    HtfOverlayStyle, InsetRect, InsetSeries, PrimitiveConfig, SignalConfig, ThemeConfig,
+    TickSeries, VbpConfig,
 };
 use ::zengeld_canvas::core::Bar;
-use ::zengeld_canvas::model::Indicator;
-use ::zengeld_canvas::{RuntimeTheme, Theme, UITheme, Viewport};
+use ::zengeld_canvas::model::{
+    BandStyle, Indicator, IndicatorParams, IndicatorRange, PivotLabels, ScaleGroup, Source,
+    Strategy,
+};
+use ::zengeld_canvas::primitives::PropertyValue;
+use ::zengeld_canvas::render::SvgPrecision;
+use ::zengeld_canvas::{
+    BandDeviation, CompositeSpec, DivergenceType, FibLabelMode, RuntimeTheme, Theme, UITheme,
+    Viewport,
+};
 
 // =============================================================================
 // Bar - OHLCV data point
@@ -105,6 +116,24 @@ impl PyChart {
     fn put_inner(&mut self, chart: RustChart) {
         self.inner = Some(chart);
     }
+    /// Price source of an indicator
+    fn parse_source(source: &str) -> PyResult<Source> {
+        source
+            .parse()
+            .map_err(|_| pyo3::exceptions::PyValueError::new_err("unknown price source"))
+    }
+    /// Add a primitive whose point count came from the caller
+    fn add_checked(&mut self, primitive: PrimitiveConfig) -> PyResult<()> {
+        self.inner
+            .as_ref()
+            .expect("Chart already consumed")
+            .primitive_registry()
+            .validate(&primitive.type_id, primitive.points.len())
+            .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))?;
+        let chart = self.take_inner().primitive(primitive);
+        self.put_inner(chart);
+        Ok(())
+    }
 }
 
 #[pymethods]
@@ -117,6 +146,15 @@ impl PyChart {
         }
     }
 
+    /// Chart from a complete config: a ChartConfig, a dict or a JSON string.
+    /// Fields left out keep their defaults; errors name the offending field
+    #[staticmethod]
+    fn from_config(config: &Bound<'_, PyAny>) -> PyResult<Self> {
+        Ok(Self {
+            inner: Some(RustChart::from_config(config_from_py(config)?)),
+        })
+    }
+
     // =========================================================================
     // Configuration
     // =========================================================================
@@ -127,6 +165,12 @@ impl PyChart {
         self.put_inner(chart);
     }
 
+    /// Set independent horizontal and vertical pixel ratios (e.g. for print)
+    fn dpr_xy(&mut self, dpr_x: f64, dpr_y: f64) {
+        let chart = self.take_inner().dpr_xy(dpr_x, dpr_y);
+        self.put_inner(chart);
+    }
+
     /// Set OHLCV bar data
     fn bars(&mut self, bars: Vec<PyBar>) {
         let rust_bars: Vec<Bar> = bars.iter().map(|b| b.to_rust()).collect();
@@ -134,6 +178,49 @@ impl PyChart {
         self.put_inner(chart);
     }
 
+    /// Sort bars by timestamp and merge duplicates: "keep_last" or "combine"
+    #[pyo3(signature = (sort=true, merge="keep_last"))]
+    fn sort_bars(&mut self, sort: bool, merge: &str) -> PyResult<()> {
+        let Ok(merge) = merge.parse() else {
+            return Err(pyo3::exceptions::PyValueError::new_err(
+                "unknown merge mode",
+            ));
+        };
+        let chart = self.take_inner().duplicate_merge(merge).sort_bars(sort);
+        self.put_inner(chart);
+        Ok(())
+    }
+
+    /// Empty space after the last bar, in bars
+    fn right_offset(&mut self, bars: f64) {
+        let chart = self.take_inner().right_offset(bars);
+        self.put_inner(chart);
+    }
+
+    /// Bar placement: "indexed" (one slot per bar) or "proportional" (by
+    /// timestamp, so weekends and holidays show as gaps)
+    fn time_spacing(&mut self, mode: &str) -> PyResult<()> {
+        let Ok(mode) = mode.parse() else {
+            return Err(pyo3::exceptions::PyValueError::new_err(
+                "unknown time spacing",
+            ));
+        };
+        let chart = self.take_inner().time_spacing(mode);
+        self.put_inner(chart);
+        Ok(())
+    }
+
+    /// Bar replay: draw bars only up to index `last`, optionally keeping the
+    /// full data set's price scale
+    #[pyo3(signature = (last, lock_scale=false))]
+    fn replay_to(&mut self, last: usize, lock_scale: bool) {
+        let chart = self
+            .take_inner()
+            .replay_to(last)
+            .replay_lock_scale(lock_scale);
+        self.put_inner(chart);
+    }
+
     // =========================================================================
     // Series Types (12 total)
     // =========================================================================
@@ -156,9 +243,96 @@ impl PyChart {
         self.put_inner(chart);
     }
 
+    /// Fill the area chart down to `base` instead of the pane bottom,
+    /// optionally with `below_color` under it (call after `area`)
+    #[pyo3(signature = (base, below_color=None))]
+    fn area_base(&mut self, base: f64, below_color: Option<&str>) {
+        let mut chart = self.take_inner().area_base(base);
+        if let Some(color) = below_color {
+            chart = chart.area_below_color(color);
+        }
+        self.put_inner(chart);
+    }
+
+    /// Baseline chart split into up/down zones at `value` (may be negative)
+    fn baseline(&mut self, value: f64) {
+        let chart = self.take_inner().baseline(value);
+        self.put_inner(chart);
+    }
+
+    /// Heikin Ashi chart
+    fn heikin_ashi(&mut self) {
+        let chart = self.take_inner().heikin_ashi();
+        self.put_inner(chart);
+    }
+
+    /// Renko chart: the bars become bricks of `box_size`, time-labelled by
+    /// the bars that completed them
+    fn renko(&mut self, box_size: f64) {
+        let chart = self.take_inner().renko(box_size);
+        self.put_inner(chart);
+    }
+
+    /// Line-break chart turning after `lines` lines
+    #[pyo3(signature = (lines=3))]
+    fn line_break(&mut self, lines: usize) {
+        let chart = self.take_inner().line_break(lines);
+        self.put_inner(chart);
+    }
+
+    /// Heikin Ashi wick range: "smoothed" or "original"
+    fn ha_wick_source(&mut self, source: &str) -> PyResult<()> {
+        let Ok(source) = source.parse() else {
+            return Err(pyo3::exceptions::PyValueError::new_err(
+                "unknown wick source",
+            ));
+        };
+        let chart = self.take_inner().ha_wick_source(source);
+        self.put_inner(chart);
+        Ok(())
+    }
+
+    /// Candle wicks as a fraction of the body width, never under 1px
+    fn wick_width_ratio(&mut self, ratio: f64) {
+        let chart = self.take_inner().wick_width_ratio(ratio);
+        self.put_inner(chart);
+    }
+
+    /// Candle bodies `pixels` wide at any zoom, narrowed where they would overlap
+    fn candle_width_pixels(&mut self, pixels: f64) {
+        let chart = self.take_inner().candle_width_pixels(pixels);
+        self.put_inner(chart);
+    }
+
+    /// Past `bars_per_pixel`, draw candles as "high_low" lines or "cull_wicks"
+    #[pyo3(signature = (bars_per_pixel, mode="high_low"))]
+    fn dense_candles(&mut self, bars_per_pixel: f64, mode: &str) -> PyResult<()> {
+        let Ok(mode) = mode.parse() else {
+            return Err(pyo3::exceptions::PyValueError::new_err(
+                "unknown dense candle mode",
+            ));
+        };
+        let chart = self.take_inner().dense_candles(bars_per_pixel, mode);
+        self.put_inner(chart);
+        Ok(())
+    }
+
+    /// OHLC bar chart; `tick_length` in pixels (None = half the bar width)
+    #[pyo3(signature = (tick_length=None, show_open_tick=true, show_close_tick=true))]
+    fn ohlc_bars(&mut self, tick_length: Option<f64>, show_open_tick: bool, show_close_tick: bool) {
+        let mut chart = self
+            .take_inner()
+            .ohlc_bars()
+            .bar_ticks(show_open_tick, show_close_tick);
+        if let Some(length) = tick_length {
+            chart = chart.bar_tick_length(length);
+        }
+        self.put_inner(chart);
+    }
+
     // Note: Additional series types require extending the Rust Chart builder
     // The following are placeholders for when Rust API is extended:
-    // hollow_candlestick, heikin_ashi, bar, hlc_area, step_line,
+    // hollow_candlestick, hlc_area, step_line,
     // line_with_markers, baseline, histogram, columns
 
     // =========================================================================
@@ -183,6 +357,514 @@ impl PyChart {
         self.put_inner(chart);
     }
 
+    /// Set per-bar colors (None keeps the theme color)
+    fn bar_colors(&mut self, colors: Vec<Option<String>>) {
+        let chart = self.take_inner().bar_colors(colors);
+        self.put_inner(chart);
+    }
+
+    /// Show/hide the price axis on the right
+    fn price_scale(&mut self, show: bool) {
+        let chart = self.take_inner().price_scale(show);
+        self.put_inner(chart);
+    }
+
+    /// Size the price axis to its widest label instead of a fixed width
+    fn price_scale_fit(&mut self, fit: bool) {
+        let chart = self.take_inner().price_scale_fit(fit);
+        self.put_inner(chart);
+    }
+
+    /// Show/hide the time axis at the bottom
+    fn time_scale(&mut self, show: bool) {
+        let chart = self.take_inner().time_scale(show);
+        self.put_inner(chart);
+    }
+
+    /// Sparkline mode: a bare line filling the canvas, no axes or grid
+    fn sparkline(&mut self) {
+        let chart = self.take_inner().sparkline();
+        self.put_inner(chart);
+    }
+
+    /// Show each subpane's indicator name and latest values
+    fn pane_titles(&mut self, show: bool) {
+        let chart = self.take_inner().pane_titles(show);
+        self.put_inner(chart);
+    }
+
+    /// Corner of the subpane titles: "top_left", "top_right", "bottom_left"
+    /// or "bottom_right"
+    fn legend_position(&mut self, position: &str) -> PyResult<()> {
+        let Ok(position) = position.parse() else {
+            return Err(pyo3::exceptions::PyValueError::new_err(
+                "unknown legend position",
+            ));
+        };
+        let chart = self.take_inner().legend_position(position);
+        self.put_inner(chart);
+        Ok(())
+    }
+
+    /// Size of the scales, grid and signal glyphs: "compact", "normal" or
+    /// "comfortable"
+    fn density(&mut self, density: &str) -> PyResult<()> {
+        let Ok(density) = density.parse() else {
+            return Err(pyo3::exceptions::PyValueError::new_err("unknown density"));
+        };
+        let chart = self.take_inner().density(density);
+        self.put_inner(chart);
+        Ok(())
+    }
+
+    /// Symbol or title used in the text description
+    fn title(&mut self, title: &str) {
+        let chart = self.take_inner().title(title);
+        self.put_inner(chart);
+    }
+
+    /// Embed the text description in the SVG as <title>/<desc>
+    fn embed_description(&mut self, embed: bool) {
+        let chart = self.take_inner().embed_description(embed);
+        self.put_inner(chart);
+    }
+
+    /// Force a time label pattern such as "%H:%M"
+    fn time_format(&mut self, pattern: &str) {
+        let chart = self.take_inner().time_format(pattern);
+        self.put_inner(chart);
+    }
+
+    /// Unit of bar timestamps: "s", "ms" or "auto" (ms above 1e12)
+    fn timestamp_unit(&mut self, unit: &str) -> PyResult<()> {
+        let Ok(unit) = unit.parse() else {
+            return Err(pyo3::exceptions::PyValueError::new_err(
+                "unknown timestamp unit",
+            ));
+        };
+        let chart = self.take_inner().timestamp_unit(unit);
+        self.put_inner(chart);
+        Ok(())
+    }
+
+    /// Highlight the hovered bar, or clear the highlight with None
+    #[pyo3(signature = (index=None))]
+    fn hover_bar(&mut self, index: Option<usize>) {
+        let chart = self.take_inner().hover_bar(index);
+        self.put_inner(chart);
+    }
+
+    /// Show a dashed line and axis tag at the last close
+    fn last_price_line(&mut self, show: bool) {
+        let chart = self.take_inner().last_price_line(show);
+        self.put_inner(chart);
+    }
+
+    /// Never squeeze bars narrower than `spacing` pixels; earlier bars that
+    /// do not fit are left out
+    fn min_bar_spacing(&mut self, spacing: f64) -> PyResult<()> {
+        if !(spacing.is_finite() && spacing > 0.0) {
+            return Err(pyo3::exceptions::PyValueError::new_err(
+                "spacing must be a positive number",
+            ));
+        }
+        let chart = self.take_inner().min_bar_spacing(spacing);
+        self.put_inner(chart);
+        Ok(())
+    }
+
+    /// Show a scrollbar when `min_bar_spacing` leaves bars out
+    fn scroll_indicator(&mut self, show: bool) {
+        let chart = self.take_inner().scroll_indicator(show);
+        self.put_inner(chart);
+    }
+
+    /// Decimals of SVG coordinates for axes/grid, series, curves and
+    /// everything else (e.g. 0, 1, 2, 2 for compact output)
+    #[pyo3(signature = (axis=2, series=2, curves=2, other=2))]
+    fn svg_precision(&mut self, axis: u8, series: u8, curves: u8, other: u8) {
+        let precision = SvgPrecision {
+            axis,
+            series,
+            curves,
+            other,
+        };
+        let chart = self.take_inner().svg_precision(precision);
+        self.put_inner(chart);
+    }
+
+    /// Mark bars that open more than `threshold` (percent, or price distance
+    /// with `percent=False`) away from the previous close
+    #[pyo3(signature = (threshold, percent=true, shade=true, marker=false))]
+    fn show_gaps(&mut self, threshold: f64, percent: bool, shade: bool, marker: bool) {
+        let threshold = if percent {
+            GapThreshold::Percent(threshold)
+        } else {
+            GapThreshold::Absolute(threshold)
+        };
+        let gaps = GapConfig::new(threshold)
+            .with_shade(shade)
+            .with_marker(marker);
+        let chart = self.take_inner().show_gaps(gaps);
+        self.put_inner(chart);
+    }
+
+    /// Draw higher-timeframe candles (e.g. 14400 for 4h) behind the main series
+    #[pyo3(signature = (interval_secs, filled=false, show_wicks=true))]
+    fn htf_overlay(&mut self, interval_secs: i64, filled: bool, show_wicks: bool) {
+        let style = if filled {
+            HtfOverlayStyle::filled()
+        } else {
+            HtfOverlayStyle::outline()
+        };
+        let chart = self
+            .take_inner()
+            .htf_overlay(interval_secs, style.with_wicks(show_wicks));
+        self.put_inner(chart);
+    }
+
+    /// Draw a volume-by-price histogram along the right edge of the main pane
+    ///
+    /// `width` is the fullest bucket's length as a fraction of the pane.
+    #[pyo3(signature = (buckets=24, width=0.3, source="hlc3", poc=false, split=false, color=None))]
+    fn volume_by_price(
+        &mut self,
+        buckets: usize,
+        width: f64,
+        source: &str,
+        poc: bool,
+        split: bool,
+        color: Option<&str>,
+    ) -> PyResult<()> {
+        let mut config = VbpConfig::new(buckets, width).with_source(Self::parse_source(source)?);
+        config.show_poc = poc;
+        config.split_up_down = split;
+        config.color = color.map(str::to_string);
+        let chart = self.take_inner().volume_by_price(config);
+        self.put_inner(chart);
+        Ok(())
+    }
+
+    /// Pin a mini line chart of `bars` closes in a box of the main pane
+    ///
+    /// `x`, `y`, `width` and `height` are fractions of the pane.
+    #[pyo3(signature = (bars, x, y, width, height, color=None, label=None))]
+    #[allow(clippy::too_many_arguments)]
+    fn add_inset(
+        &mut self,
+        bars: Vec<PyBar>,
+        x: f64,
+        y: f64,
+        width: f64,
+        height: f64,
+        color: Option<&str>,
+        label: Option<&str>,
+    ) {
+        let rust_bars: Vec<Bar> = bars.iter().map(|b| b.to_rust()).collect();
+        let mut inset = InsetSeries::new(&rust_bars, InsetRect::new(x, y, width, height));
+        if let Some(color) = color {
+            inset = inset.with_color(color);
+        }
+        if let Some(label) = label {
+            inset = inset.with_label(label);
+        }
+        let chart = self.take_inner().inset(inset);
+        self.put_inner(chart);
+    }
+
+    /// Plot raw trade ticks as a line or area: in a pane under the bars, or
+    /// as the main series when no bars are set
+    ///
+    /// `timestamps` and `prices` are parallel lists or numpy arrays. Ticks are
+    /// downsampled to four points per pixel column when drawn.
+    #[pyo3(signature = (timestamps, prices, style="line", color=None))]
+    fn ticks(
+        &mut self,
+        timestamps: Vec<i64>,
+        prices: Vec<f64>,
+        style: &str,
+        color: Option<&str>,
+    ) -> PyResult<()> {
+        if timestamps.len() != prices.len() {
+            return Err(pyo3::exceptions::PyValueError::new_err(
+                "timestamps and prices differ in length",
+            ));
+        }
+        let Ok(style) = style.parse() else {
+            return Err(pyo3::exceptions::PyValueError::new_err(
+                "unknown tick style",
+            ));
+        };
+        let ticks: Vec<(i64, f64)> = timestamps.into_iter().zip(prices).collect();
+        let mut series = TickSeries::new(&ticks).with_style(style);
+        if let Some(color) = color {
+            series = series.with_color(color);
+        }
+        let chart = self.take_inner().tick_series(series);
+        self.put_inner(chart);
+        Ok(())
+    }
+
+    /// Smooth the lines of indicator `id` with an SMA of `period` bars
+    fn smoothing(&mut self, id: &str, period: usize) {
+        let chart = self.take_inner().smoothing(id, period);
+        self.put_inner(chart);
+    }
+
+    /// Put indicator `id` in a legend group
+    fn indicator_group(&mut self, id: &str, group: &str) {
+        let chart = self.take_inner().indicator_group(id, group);
+        self.put_inner(chart);
+    }
+
+    /// Scale the named vectors of subpane indicator `id` by their own
+    /// range: "auto", "symmetric", "price", or "fixed" from `min` to `max`;
+    /// `axis` labels it in a column right of the price scale
+    #[pyo3(signature = (id, group, vectors, range="auto", min=None, max=None, axis=false))]
+    #[allow(clippy::too_many_arguments)]
+    fn scale_group(
+        &mut self,
+        id: &str,
+        group: &str,
+        vectors: Vec<String>,
+        range: &str,
+        min: Option<f64>,
+        max: Option<f64>,
+        axis: bool,
+    ) -> PyResult<()> {
+        let range = match (range, min, max) {
+            ("auto", ..) => IndicatorRange::auto(),
+            ("symmetric", ..) => IndicatorRange::symmetric(),
+            ("price", ..) => IndicatorRange::price(),
+            ("fixed", Some(min), Some(max)) if min < max => IndicatorRange::fixed(min, max),
+            ("fixed", ..) => {
+                return Err(pyo3::exceptions::PyValueError::new_err(
+                    "a fixed range needs min below max",
+                ));
+            }
+            (other, ..) => {
+                return Err(pyo3::exceptions::PyValueError::new_err(format!(
+                    "unknown range '{}'",
+                    other
+                )));
+            }
+        };
+        let mut scale_group = ScaleGroup::new(group, range);
+        scale_group.axis = axis;
+        let names: Vec<&str> = vectors.iter().map(String::as_str).collect();
+        let chart = self.take_inner().scale_group(id, scale_group, &names);
+        self.put_inner(chart);
+        Ok(())
+    }
+
+    /// Show or hide every indicator in a group
+    fn set_group_visible(&mut self, group: &str, visible: bool) {
+        self.inner
+            .as_mut()
+            .expect("Chart already consumed")
+            .set_group_visible(group, visible);
+    }
+
+    /// Toggle one indicator as a legend click would; a hidden one keeps a
+    /// greyed legend row
+    fn set_indicator_visible(&mut self, id: &str, visible: bool) {
+        self.inner
+            .as_mut()
+            .expect("Chart already consumed")
+            .set_indicator_visible(id, visible);
+    }
+
+    /// Show or hide the main series
+    fn set_series_visible(&mut self, visible: bool) {
+        self.inner
+            .as_mut()
+            .expect("Chart already consumed")
+            .set_series_visible(visible);
+    }
+
+    /// Legend of overlay indicators, grouped by indicator group
+    #[pyo3(signature = (show=true))]
+    fn legend(&mut self, show: bool) {
+        let chart = self.take_inner().legend(show);
+        self.put_inner(chart);
+    }
+
+    /// Set the instrument tick size (e.g. 0.25); price ticks snap to its
+    /// multiples and prices use its decimal precision
+    fn tick_size(&mut self, tick_size: f64) -> PyResult<()> {
+        if !(tick_size.is_finite() && tick_size > 0.0) {
+            return Err(pyo3::exceptions::PyValueError::new_err(
+                "tick size must be positive",
+            ));
+        }
+        let chart = self.take_inner().tick_size(tick_size);
+        self.put_inner(chart);
+        Ok(())
+    }
+
+    /// Message shown while there are no bars (empty string for none)
+    fn empty_message(&mut self, message: &str) {
+        let chart = self.take_inner().empty_message(message);
+        self.put_inner(chart);
+    }
+
+    /// Draw placeholder candles while there are no bars; `animate` pulses
+    /// them with SVG animation
+    #[pyo3(signature = (bar_count=40, animate=false))]
+    fn skeleton(&mut self, bar_count: usize, animate: bool) {
+        let chart = self.take_inner().skeleton(bar_count, animate);
+        self.put_inner(chart);
+    }
+
+    /// Overlay each "year" or "day" of a source ("open", "high", "low",
+    /// "close") as separate lines on a shared in-period axis
+    #[pyo3(signature = (period="year", source="close"))]
+    fn seasonal(&mut self, period: &str, source: &str) -> PyResult<()> {
+        let (Ok(period), Ok(source)) = (period.parse(), source.parse()) else {
+            return Err(pyo3::exceptions::PyValueError::new_err(
+                "unknown period or source",
+            ));
+        };
+        let chart = self.take_inner().seasonal(period, source);
+        self.put_inner(chart);
+        Ok(())
+    }
+
+    /// Register a custom drawing tool from a composite spec JSON
+    /// (type_id, display_name, kind, points, ops)
+    fn register_primitive(&mut self, json: &str) -> PyResult<()> {
+        let spec = CompositeSpec::from_json(json)
+            .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))?;
+        self.inner
+            .as_mut()
+            .expect("Chart already consumed")
+            .register_composite(spec)
+            .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))
+    }
+
+    /// Add any registered primitive by type ID, including custom ones;
+    /// give it an `id` to be able to select it
+    #[pyo3(signature = (type_id, points, color=None, id=None))]
+    fn add_primitive(
+        &mut self,
+        type_id: &str,
+        points: Vec<(f64, f64)>,
+        color: Option<&str>,
+        id: Option<&str>,
+    ) -> PyResult<()> {
+        let mut primitive = PrimitiveConfig::new(type_id, points);
+        if let Some(color) = color {
+            primitive = primitive.with_color(color);
+        }
+        if let Some(id) = id {
+            primitive = primitive.with_id(id);
+        }
+        self.add_checked(primitive)
+    }
+
+    /// Divergence from a `(bar, price)` pivot to a `(bar, value)` pivot on
+    /// the subpane of `indicator_id`; `kind` is "regular_bullish",
+    /// "regular_bearish", "hidden_bullish" or "hidden_bearish"
+    #[pyo3(signature = (price_pivot, indicator_pivot, indicator_id, kind="regular_bullish"))]
+    fn indicator_divergence(
+        &mut self,
+        price_pivot: (f64, f64),
+        indicator_pivot: (f64, f64),
+        indicator_id: &str,
+        kind: &str,
+    ) -> PyResult<()> {
+        let kind: DivergenceType = kind
+            .parse()
+            .map_err(|_| pyo3::exceptions::PyValueError::new_err("unknown divergence kind"))?;
+        let primitive =
+            PrimitiveConfig::indicator_divergence(price_pivot, indicator_pivot, indicator_id, kind);
+        let chart = self.take_inner().primitive(primitive);
+        self.put_inner(chart);
+        Ok(())
+    }
+
+    /// Draw the primitive with `id` selected (halo and control-point handles)
+    #[pyo3(signature = (id, selected=true))]
+    fn set_primitive_selected(&mut self, id: &str, selected: bool) {
+        self.inner
+            .as_mut()
+            .expect("Chart already consumed")
+            .set_primitive_selected(id, selected);
+    }
+
+    /// Show or hide the primitive with `id`, keeping it on the chart
+    #[pyo3(signature = (id, visible=true))]
+    fn set_primitive_visibility(&mut self, id: &str, visible: bool) {
+        self.inner
+            .as_mut()
+            .expect("Chart already consumed")
+            .set_primitive_visibility(id, visible);
+    }
+
+    /// Lock the primitive with `id` against hit-testing and selection
+    #[pyo3(signature = (id, locked=true))]
+    fn set_primitive_locked(&mut self, id: &str, locked: bool) {
+        self.inner
+            .as_mut()
+            .expect("Chart already consumed")
+            .set_primitive_locked(id, locked);
+    }
+
+    /// Let the primitive with `id` stretch the automatic price range
+    #[pyo3(signature = (id, autoscale=true))]
+    fn set_primitive_autoscale(&mut self, id: &str, autoscale: bool) {
+        self.inner
+            .as_mut()
+            .expect("Chart already consumed")
+            .set_primitive_autoscale(id, autoscale);
+    }
+
+    /// Shift primitive points by bars and price, all primitives or those
+    /// in `ids`; returns how many moved
+    #[pyo3(signature = (bar_delta, price_delta=0.0, ids=None))]
+    fn translate_primitives(
+        &mut self,
+        bar_delta: f64,
+        price_delta: f64,
+        ids: Option<Vec<String>>,
+    ) -> usize {
+        self.inner
+            .as_mut()
+            .expect("Chart already consumed")
+            .translate_primitives(bar_delta, price_delta, ids.as_deref())
+    }
+
+    /// Scale primitive points about `origin` by `factor`, both
+    /// (bar, price), e.g. scale_primitives((0, 0), (1, 0.25)) after a 4:1
+    /// split; returns how many changed
+    #[pyo3(signature = (origin, factor, ids=None))]
+    fn scale_primitives(
+        &mut self,
+        origin: (f64, f64),
+        factor: (f64, f64),
+        ids: Option<Vec<String>>,
+    ) -> usize {
+        self.inner
+            .as_mut()
+            .expect("Chart already consumed")
+            .scale_primitives(origin, factor, ids.as_deref())
+    }
+
+    /// Index of the topmost unlocked, visible primitive at pixel `(x, y)`
+    #[pyo3(signature = (x, y, tolerance=4.0))]
+    fn hit_test(&self, x: f64, y: f64, tolerance: f64) -> Option<usize> {
+        self.inner.as_ref()?.hit_test(x, y, tolerance)
+    }
+
+    /// Attach a strategy described as JSON (indicators, signals, primitives, theme)
+    fn strategy_json(&mut self, json: &str) -> PyResult<()> {
+        let strategy = Strategy::from_json(json)
+            .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))?;
+        let chart = self.take_inner().strategy(strategy);
+        self.put_inner(chart);
+        Ok(())
+    }
+
     /// Apply dark theme
     fn dark_theme(&mut self) {
         let chart = self
@@ -201,20 +883,45 @@ impl PyChart {
         self.put_inner(chart);
     }
 
+    /// Apply cyberpunk theme (neon colors with a glow)
+    fn cyberpunk_theme(&mut self) {
+        let chart = self.take_inner().theme(ThemeConfig::cyberpunk());
+        self.put_inner(chart);
+    }
+
+    /// Apply a RuntimeTheme wholesale: background, grid, scale text,
+    /// candle bodies, wicks and borders, and glow
+    fn apply_theme(&mut self, theme: &PyRuntimeTheme) {
+        let chart = self.take_inner().theme(&theme.inner);
+        self.put_inner(chart);
+    }
+
+    /// Glow blur radius around series and drawings (0 = off)
+    fn glow(&mut self, radius: f64) {
+        let chart = self.take_inner().glow(radius);
+        self.put_inner(chart);
+    }
+
     // =========================================================================
     // Moving Average Indicators (9 types)
     // =========================================================================
 
-    /// Simple Moving Average
-    fn sma(&mut self, period: usize, color: &str) {
-        let chart = self.take_inner().sma(period, color);
+    /// Simple Moving Average of a price source: "close", "open", "high", "low", "hl2", "hlc3" or "ohlc4"
+    #[pyo3(signature = (period, color, source="close"))]
+    fn sma(&mut self, period: usize, color: &str, source: &str) -> PyResult<()> {
+        let source = Self::parse_source(source)?;
+        let chart = self.take_inner().sma_on(period, color, source);
         self.put_inner(chart);
+        Ok(())
     }
 
-    /// Exponential Moving Average
-    fn ema(&mut self, period: usize, color: &str) {
-        let chart = self.take_inner().ema(period, color);
+    /// Exponential Moving Average of a price source
+    #[pyo3(signature = (period, color, source="close"))]
+    fn ema(&mut self, period: usize, color: &str, source: &str) -> PyResult<()> {
+        let source = Self::parse_source(source)?;
+        let chart = self.take_inner().ema_on(period, color, source);
         self.put_inner(chart);
+        Ok(())
     }
 
     /// Weighted Moving Average
@@ -285,9 +992,33 @@ impl PyChart {
     // Band Indicators (5 types)
     // =========================================================================
 
-    /// Bollinger Bands
-    fn bollinger(&mut self, period: usize, multiplier: f64) {
-        let chart = self.take_inner().bollinger(period, multiplier);
+    /// Bollinger Bands of a price source
+    #[pyo3(signature = (period, multiplier, source="close"))]
+    fn bollinger(&mut self, period: usize, multiplier: f64, source: &str) -> PyResult<()> {
+        let source = Self::parse_source(source)?;
+        let chart = self.take_inner().bollinger_on(period, multiplier, source);
+        self.put_inner(chart);
+        Ok(())
+    }
+
+    /// Bollinger Bands with the middle line and fill toggled, and dots
+    /// on bars closing outside the bands
+    #[pyo3(signature = (period, multiplier, show_middle=true, fill=true, outside_markers=false))]
+    fn bollinger_styled(
+        &mut self,
+        period: usize,
+        multiplier: f64,
+        show_middle: bool,
+        fill: bool,
+        outside_markers: bool,
+    ) {
+        let mut style = BandStyle::bollinger()
+            .show_middle(show_middle)
+            .outside_markers(outside_markers);
+        style.fill = fill;
+        let chart = self
+            .take_inner()
+            .bollinger_styled(period, multiplier, &style);
         self.put_inner(chart);
     }
 
@@ -327,16 +1058,22 @@ impl PyChart {
     // Oscillators / Momentum Indicators
     // =========================================================================
 
-    /// Relative Strength Index
-    fn rsi(&mut self, period: usize) {
-        let chart = self.take_inner().rsi(period);
+    /// Relative Strength Index of a price source
+    #[pyo3(signature = (period, source="close"))]
+    fn rsi(&mut self, period: usize, source: &str) -> PyResult<()> {
+        let source = Self::parse_source(source)?;
+        let chart = self.take_inner().rsi_on(period, source);
         self.put_inner(chart);
+        Ok(())
     }
 
-    /// MACD
-    fn macd(&mut self, fast: usize, slow: usize, signal: usize) {
-        let chart = self.take_inner().macd(fast, slow, signal);
+    /// MACD of a price source
+    #[pyo3(signature = (fast, slow, signal, source="close"))]
+    fn macd(&mut self, fast: usize, slow: usize, signal: usize, source: &str) -> PyResult<()> {
+        let source = Self::parse_source(source)?;
+        let chart = self.take_inner().macd_on(fast, slow, signal, source);
         self.put_inner(chart);
+        Ok(())
     }
 
     /// MACD with default settings (12, 26, 9)
@@ -511,9 +1248,7 @@ impl PyChart {
 
     /// Average Directional Index
     fn adx(&mut self, period: usize) {
-        let id = format!("adx_{}", period);
-        let indicator = Indicator::adx(&id, period as u32);
-        let chart = self.take_inner().indicator(indicator);
+        let chart = self.take_inner().adx(period);
         self.put_inner(chart);
     }
 
@@ -557,11 +1292,16 @@ impl PyChart {
         self.put_inner(chart);
     }
 
-    /// ZigZag
-    fn zigzag(&mut self, deviation: f64) {
-        let indicator = Indicator::zigzag("zigzag", deviation);
-        let chart = self.take_inner().indicator(indicator);
+    /// ZigZag between swing highs and lows reversing by `deviation` percent,
+    /// pivots labeled "none", "price" or "change"
+    #[pyo3(signature = (deviation, labels="none"))]
+    fn zigzag(&mut self, deviation: f64, labels: &str) -> PyResult<()> {
+        let labels: PivotLabels = labels
+            .parse()
+            .map_err(|_| pyo3::exceptions::PyValueError::new_err("unknown pivot labels"))?;
+        let chart = self.take_inner().zigzag_labeled(deviation, labels);
         self.put_inner(chart);
+        Ok(())
     }
 
     /// TRIX
@@ -579,18 +1319,19 @@ impl PyChart {
         self.put_inner(chart);
     }
 
-    /// Parabolic SAR
-    fn psar(&mut self) {
-        let indicator = Indicator::psar("psar");
-        let chart = self.take_inner().indicator(indicator);
+    /// Parabolic SAR dots colored by trend
+    #[pyo3(signature = (step=0.02, max_step=0.2))]
+    fn psar(&mut self, step: f64, max_step: f64) {
+        let chart = self.take_inner().psar(step, max_step);
         self.put_inner(chart);
     }
 
-    /// Supertrend
-    fn supertrend(&mut self, period: usize, multiplier: f64) {
-        let id = format!("supertrend_{}", period);
-        let indicator = Indicator::supertrend(&id, period as u32, multiplier);
-        let chart = self.take_inner().indicator(indicator);
+    /// Supertrend stepped line colored by trend, optionally filled to the close
+    #[pyo3(signature = (period, multiplier, fill=false))]
+    fn supertrend(&mut self, period: usize, multiplier: f64, fill: bool) {
+        let chart = self
+            .take_inner()
+            .supertrend_with_fill(period, multiplier, fill);
         self.put_inner(chart);
     }
 
@@ -611,25 +1352,30 @@ impl PyChart {
         self.put_inner(chart);
     }
 
+    /// Volume with sell volume at the base and buy volume stacked on top,
+    /// per bar
+    ///
+    /// Bars missing either side fall back to the plain volume bar.
+    fn volume_breakdown(&mut self, buy: Vec<f64>, sell: Vec<f64>) {
+        let chart = self.take_inner().volume_breakdown(&buy, &sell);
+        self.put_inner(chart);
+    }
+
     /// On Balance Volume
     fn obv(&mut self) {
-        let indicator = Indicator::obv("obv");
-        let chart = self.take_inner().indicator(indicator);
+        let chart = self.take_inner().obv();
         self.put_inner(chart);
     }
 
     /// Accumulation/Distribution Line
     fn ad_line(&mut self) {
-        let indicator = Indicator::ad_line("ad");
-        let chart = self.take_inner().indicator(indicator);
+        let chart = self.take_inner().ad_line();
         self.put_inner(chart);
     }
 
     /// Chaikin Money Flow
     fn cmf(&mut self, period: usize) {
-        let id = format!("cmf_{}", period);
-        let indicator = Indicator::cmf(&id, period as u32);
-        let chart = self.take_inner().indicator(indicator);
+        let chart = self.take_inner().cmf(period);
         self.put_inner(chart);
     }
 
@@ -679,9 +1425,7 @@ impl PyChart {
 
     /// Money Flow Index
     fn mfi(&mut self, period: usize) {
-        let id = format!("mfi_{}", period);
-        let indicator = Indicator::mfi(&id, period as u32);
-        let chart = self.take_inner().indicator(indicator);
+        let chart = self.take_inner().mfi(period);
         self.put_inner(chart);
     }
 
@@ -840,24 +1584,42 @@ impl PyChart {
         self.put_inner(chart);
     }
 
-    /// Take profit signal
-    #[pyo3(signature = (bar_index, price, label=None))]
-    fn take_profit_signal(&mut self, bar_index: usize, price: f64, label: Option<String>) {
+    /// Take profit signal, optionally with a short level line and price label
+    #[pyo3(signature = (bar_index, price, label=None, level_line=false))]
+    fn take_profit_signal(
+        &mut self,
+        bar_index: usize,
+        price: f64,
+        label: Option<String>,
+        level_line: bool,
+    ) {
         let mut signal = SignalConfig::take_profit(bar_index, price);
         if let Some(l) = label {
             signal = signal.with_label(&l);
         }
+        if level_line {
+            signal = signal.with_level_line();
+        }
         let chart = self.take_inner().signal(signal);
         self.put_inner(chart);
     }
 
-    /// Stop loss signal
-    #[pyo3(signature = (bar_index, price, label=None))]
-    fn stop_loss_signal(&mut self, bar_index: usize, price: f64, label: Option<String>) {
+    /// Stop loss signal, optionally with a short level line and price label
+    #[pyo3(signature = (bar_index, price, label=None, level_line=false))]
+    fn stop_loss_signal(
+        &mut self,
+        bar_index: usize,
+        price: f64,
+        label: Option<String>,
+        level_line: bool,
+    ) {
         let mut signal = SignalConfig::stop_loss(bar_index, price);
         if let Some(l) = label {
             signal = signal.with_label(&l);
         }
+        if level_line {
+            signal = signal.with_level_line();
+        }
         let chart = self.take_inner().signal(signal);
         self.put_inner(chart);
     }
@@ -888,6 +1650,13 @@ impl PyChart {
         self.put_inner(chart);
     }
 
+    /// Vertical line at a timestamp, which may lie after the last bar
+    fn vertical_line_at(&mut self, timestamp: i64) {
+        let primitive = PrimitiveConfig::vertical_line_at(timestamp);
+        let chart = self.take_inner().primitive(primitive);
+        self.put_inner(chart);
+    }
+
     /// Trend line between two points
     fn trend_line(&mut self, start: (f64, f64), end: (f64, f64)) {
         let primitive = PrimitiveConfig::trend_line(start, end);
@@ -948,11 +1717,25 @@ impl PyChart {
         self.put_inner(chart);
     }
 
-    /// Regression trend channel
-    fn regression_trend(&mut self, p1: (f64, f64), p2: (f64, f64)) {
-        let primitive = PrimitiveConfig::regression_trend(p1, p2);
+    /// Regression trend channel fitted to the bars between `p1` and `p2`
+    ///
+    /// Bands sit `multiplier` deviations ("std_dev" or "std_error") from the
+    /// regression line.
+    #[pyo3(signature = (p1, p2, multiplier=2.0, deviation="std_dev"))]
+    fn regression_trend(
+        &mut self,
+        p1: (f64, f64),
+        p2: (f64, f64),
+        multiplier: f64,
+        deviation: &str,
+    ) -> PyResult<()> {
+        let deviation: BandDeviation = deviation
+            .parse()
+            .map_err(|_| pyo3::exceptions::PyValueError::new_err("unknown band deviation"))?;
+        let primitive = PrimitiveConfig::regression_trend(p1, p2).with_band(multiplier, deviation);
         let chart = self.take_inner().primitive(primitive);
         self.put_inner(chart);
+        Ok(())
     }
 
     /// Flat top/bottom channel
@@ -963,10 +1746,8 @@ impl PyChart {
     }
 
     /// Disjoint channel
-    fn disjoint_channel(&mut self, points: Vec<(f64, f64)>) {
-        let primitive = PrimitiveConfig::disjoint_channel(points);
-        let chart = self.take_inner().primitive(primitive);
-        self.put_inner(chart);
+    fn disjoint_channel(&mut self, points: Vec<(f64, f64)>) -> PyResult<()> {
+        self.add_checked(PrimitiveConfig::disjoint_channel(points))
     }
 
     // =========================================================================
@@ -1009,17 +1790,13 @@ impl PyChart {
     }
 
     /// Polyline
-    fn polyline(&mut self, points: Vec<(f64, f64)>) {
-        let primitive = PrimitiveConfig::polyline(points);
-        let chart = self.take_inner().primitive(primitive);
-        self.put_inner(chart);
+    fn polyline(&mut self, points: Vec<(f64, f64)>) -> PyResult<()> {
+        self.add_checked(PrimitiveConfig::polyline(points))
     }
 
     /// Path (closed polygon)
-    fn path(&mut self, points: Vec<(f64, f64)>) {
-        let primitive = PrimitiveConfig::path(points);
-        let chart = self.take_inner().primitive(primitive);
-        self.put_inner(chart);
+    fn path(&mut self, points: Vec<(f64, f64)>) -> PyResult<()> {
+        self.add_checked(PrimitiveConfig::path(points))
     }
 
     /// Rotated rectangle
@@ -1030,17 +1807,13 @@ impl PyChart {
     }
 
     /// Curve
-    fn curve(&mut self, points: Vec<(f64, f64)>) {
-        let primitive = PrimitiveConfig::curve(points);
-        let chart = self.take_inner().primitive(primitive);
-        self.put_inner(chart);
+    fn curve(&mut self, points: Vec<(f64, f64)>) -> PyResult<()> {
+        self.add_checked(PrimitiveConfig::curve(points))
     }
 
     /// Double curve
-    fn double_curve(&mut self, points: Vec<(f64, f64)>) {
-        let primitive = PrimitiveConfig::double_curve(points);
-        let chart = self.take_inner().primitive(primitive);
-        self.put_inner(chart);
+    fn double_curve(&mut self, points: Vec<(f64, f64)>) -> PyResult<()> {
+        self.add_checked(PrimitiveConfig::double_curve(points))
     }
 
     // =========================================================================
@@ -1048,10 +1821,41 @@ impl PyChart {
     // =========================================================================
 
     /// Fibonacci retracement
-    fn fib_retracement(&mut self, p1: (f64, f64), p2: (f64, f64)) {
-        let primitive = PrimitiveConfig::fib_retracement(p1, p2);
+    ///
+    /// `labels` ("ratio", "ratio_price" or "ratio_percent") labels each
+    /// level, overriding the chart-wide fib_labels.
+    #[pyo3(signature = (p1, p2, labels=None))]
+    fn fib_retracement(
+        &mut self,
+        p1: (f64, f64),
+        p2: (f64, f64),
+        labels: Option<&str>,
+    ) -> PyResult<()> {
+        let mut primitive = PrimitiveConfig::fib_retracement(p1, p2);
+        if let Some(labels) = labels {
+            let Ok(mode) = labels.parse::<FibLabelMode>() else {
+                return Err(pyo3::exceptions::PyValueError::new_err(
+                    "labels must be ratio, ratio_price or ratio_percent",
+                ));
+            };
+            primitive = primitive.with_fib_labels(mode);
+        }
         let chart = self.take_inner().primitive(primitive);
         self.put_inner(chart);
+        Ok(())
+    }
+
+    /// Label the levels of every Fibonacci drawing: "ratio", "ratio_price"
+    /// (0.618  123.45) or "ratio_percent" (0.618  61.8%)
+    fn fib_labels(&mut self, mode: &str) -> PyResult<()> {
+        let Ok(mode) = mode.parse::<FibLabelMode>() else {
+            return Err(pyo3::exceptions::PyValueError::new_err(
+                "mode must be ratio, ratio_price or ratio_percent",
+            ));
+        };
+        let chart = self.take_inner().fib_labels(mode);
+        self.put_inner(chart);
+        Ok(())
     }
 
     /// Fibonacci extension
@@ -1193,31 +1997,23 @@ impl PyChart {
     // =========================================================================
 
     /// XABCD Pattern
-    fn xabcd_pattern(&mut self, points: Vec<(f64, f64)>) {
-        let primitive = PrimitiveConfig::xabcd_pattern(points);
-        let chart = self.take_inner().primitive(primitive);
-        self.put_inner(chart);
+    fn xabcd_pattern(&mut self, points: Vec<(f64, f64)>) -> PyResult<()> {
+        self.add_checked(PrimitiveConfig::xabcd_pattern(points))
     }
 
     /// Cypher Pattern
-    fn cypher_pattern(&mut self, points: Vec<(f64, f64)>) {
-        let primitive = PrimitiveConfig::cypher_pattern(points);
-        let chart = self.take_inner().primitive(primitive);
-        self.put_inner(chart);
+    fn cypher_pattern(&mut self, points: Vec<(f64, f64)>) -> PyResult<()> {
+        self.add_checked(PrimitiveConfig::cypher_pattern(points))
     }
 
     /// Head and Shoulders
-    fn head_shoulders(&mut self, points: Vec<(f64, f64)>) {
-        let primitive = PrimitiveConfig::head_shoulders(points);
-        let chart = self.take_inner().primitive(primitive);
-        self.put_inner(chart);
+    fn head_shoulders(&mut self, points: Vec<(f64, f64)>) -> PyResult<()> {
+        self.add_checked(PrimitiveConfig::head_shoulders(points))
     }
 
     /// ABCD Pattern
-    fn abcd_pattern(&mut self, points: Vec<(f64, f64)>) {
-        let primitive = PrimitiveConfig::abcd_pattern(points);
-        let chart = self.take_inner().primitive(primitive);
-        self.put_inner(chart);
+    fn abcd_pattern(&mut self, points: Vec<(f64, f64)>) -> PyResult<()> {
+        self.add_checked(PrimitiveConfig::abcd_pattern(points))
     }
 
     /// Triangle Pattern
@@ -1228,10 +2024,8 @@ impl PyChart {
     }
 
     /// Three Drives
-    fn three_drives(&mut self, points: Vec<(f64, f64)>) {
-        let primitive = PrimitiveConfig::three_drives(points);
-        let chart = self.take_inner().primitive(primitive);
-        self.put_inner(chart);
+    fn three_drives(&mut self, points: Vec<(f64, f64)>) -> PyResult<()> {
+        self.add_checked(PrimitiveConfig::three_drives(points))
     }
 
     // =========================================================================
@@ -1239,38 +2033,28 @@ impl PyChart {
     // =========================================================================
 
     /// Elliott Impulse Wave
-    fn elliott_impulse(&mut self, points: Vec<(f64, f64)>) {
-        let primitive = PrimitiveConfig::elliott_impulse(points);
-        let chart = self.take_inner().primitive(primitive);
-        self.put_inner(chart);
+    fn elliott_impulse(&mut self, points: Vec<(f64, f64)>) -> PyResult<()> {
+        self.add_checked(PrimitiveConfig::elliott_impulse(points))
     }
 
     /// Elliott Correction Wave
-    fn elliott_correction(&mut self, points: Vec<(f64, f64)>) {
-        let primitive = PrimitiveConfig::elliott_correction(points);
-        let chart = self.take_inner().primitive(primitive);
-        self.put_inner(chart);
+    fn elliott_correction(&mut self, points: Vec<(f64, f64)>) -> PyResult<()> {
+        self.add_checked(PrimitiveConfig::elliott_correction(points))
     }
 
     /// Elliott Triangle
-    fn elliott_triangle(&mut self, points: Vec<(f64, f64)>) {
-        let primitive = PrimitiveConfig::elliott_triangle(points);
-        let chart = self.take_inner().primitive(primitive);
-        self.put_inner(chart);
+    fn elliott_triangle(&mut self, points: Vec<(f64, f64)>) -> PyResult<()> {
+        self.add_checked(PrimitiveConfig::elliott_triangle(points))
     }
 
     /// Elliott Double Combo
-    fn elliott_double_combo(&mut self, points: Vec<(f64, f64)>) {
-        let primitive = PrimitiveConfig::elliott_double_combo(points);
-        let chart = self.take_inner().primitive(primitive);
-        self.put_inner(chart);
+    fn elliott_double_combo(&mut self, points: Vec<(f64, f64)>) -> PyResult<()> {
+        self.add_checked(PrimitiveConfig::elliott_double_combo(points))
     }
 
     /// Elliott Triple Combo
-    fn elliott_triple_combo(&mut self, points: Vec<(f64, f64)>) {
-        let primitive = PrimitiveConfig::elliott_triple_combo(points);
-        let chart = self.take_inner().primitive(primitive);
-        self.put_inner(chart);
+    fn elliott_triple_combo(&mut self, points: Vec<(f64, f64)>) -> PyResult<()> {
+        self.add_checked(PrimitiveConfig::elliott_triple_combo(points))
     }
 
     // =========================================================================
@@ -1436,6 +2220,40 @@ impl PyChart {
         self.put_inner(chart);
     }
 
+    /// Forecast path: one median, upper and lower value per bar after
+    /// `start_bar`, drawn as a dashed median in a shaded cone
+    #[pyo3(signature = (start_bar, median, upper, lower, color=None, fill_color=None, fill_opacity=None, id=None))]
+    #[allow(clippy::too_many_arguments)]
+    fn forecast_path(
+        &mut self,
+        start_bar: f64,
+        median: Vec<f64>,
+        upper: Vec<f64>,
+        lower: Vec<f64>,
+        color: Option<&str>,
+        fill_color: Option<&str>,
+        fill_opacity: Option<f64>,
+        id: Option<&str>,
+    ) -> PyResult<()> {
+        if median.len() != upper.len() || median.len() != lower.len() {
+            return Err(pyo3::exceptions::PyValueError::new_err(
+                "median, upper and lower must have the same length",
+            ));
+        }
+        let mut primitive = PrimitiveConfig::forecast_path(start_bar, &median, &upper, &lower);
+        if let Some(color) = color {
+            primitive = primitive.with_color(color);
+        }
+        if let Some(id) = id {
+            primitive = primitive.with_id(id);
+        }
+        primitive.fill_color = fill_color.map(str::to_string);
+        primitive.fill_opacity = fill_opacity;
+        let chart = self.take_inner().primitive(primitive);
+        self.put_inner(chart);
+        Ok(())
+    }
+
     /// Bars pattern
     fn bars_pattern(&mut self, p1: (f64, f64), p2: (f64, f64)) {
         let primitive = PrimitiveConfig::bars_pattern(p1, p2);
@@ -1512,23 +2330,276 @@ impl PyChart {
     // =========================================================================
 
     /// Brush
-    fn brush(&mut self, points: Vec<(f64, f64)>) {
-        let primitive = PrimitiveConfig::brush(points);
-        let chart = self.take_inner().primitive(primitive);
-        self.put_inner(chart);
+    fn brush(&mut self, points: Vec<(f64, f64)>) -> PyResult<()> {
+        self.add_checked(PrimitiveConfig::brush(points))
     }
 
     /// Highlighter
-    fn highlighter(&mut self, points: Vec<(f64, f64)>) {
-        let primitive = PrimitiveConfig::highlighter(points);
-        let chart = self.take_inner().primitive(primitive);
-        self.put_inner(chart);
+    fn highlighter(&mut self, points: Vec<(f64, f64)>) -> PyResult<()> {
+        self.add_checked(PrimitiveConfig::highlighter(points))
     }
 
     // =========================================================================
     // Rendering
     // =========================================================================
 
+    /// Computed indicator vectors as a list of (name, values)
+    ///
+    /// Each values list converts directly with numpy.asarray. Returns None for
+    /// an unknown id and an empty list before bars are set.
+    fn indicator_values(&self, id: &str) -> Option<Vec<(String, Vec<f64>)>> {
+        let vectors = self.inner.as_ref()?.indicator_values(id)?;
+        Some(
+            vectors
+                .iter()
+                .map(|v| (v.name.clone(), v.values.clone()))
+                .collect(),
+        )
+    }
+
+    /// Labeled indicator values at a bar as a list of (label, value)
+    ///
+    /// Returns None for an unknown id; missing values are NaN.
+    fn indicator_values_at(&self, id: &str, bar_index: usize) -> Option<Vec<(String, f64)>> {
+        self.inner
+            .as_ref()
+            .and_then(|c| c.indicator_values_at(id, bar_index))
+    }
+
+    /// Drawing tools available on this chart (type_id, name, category,
+    /// min_points, max_points, supports_text, has_levels)
+    fn list_primitives<'py>(&self, py: Python<'py>) -> PyResult<Vec<Bound<'py, PyDict>>> {
+        let Some(chart) = self.inner.as_ref() else {
+            return Ok(Vec::new());
+        };
+        chart
+            .list_primitives()
+            .into_iter()
+            .map(|info| {
+                let dict = PyDict::new(py);
+                dict.set_item("type_id", info.type_id)?;
+                dict.set_item("name", info.name)?;
+                dict.set_item("category", format!("{:?}", info.category))?;
+                dict.set_item("min_points", info.min_points)?;
+                dict.set_item("max_points", info.max_points)?;
+                dict.set_item("supports_text", info.supports_text)?;
+                dict.set_item("has_levels", info.has_levels)?;
+                Ok(dict)
+            })
+            .collect()
+    }
+
+    /// Settings of an indicator as a list of dicts (id, name, category,
+    /// value): inputs such as period and source, vector colors, levels and
+    /// visibility. Returns None for an unknown id.
+    fn indicator_properties<'py>(
+        &self,
+        py: Python<'py>,
+        id: &str,
+    ) -> PyResult<Option<Vec<Bound<'py, PyDict>>>> {
+        let Some(properties) = self.inner.as_ref().and_then(|c| c.indicator_properties(id)) else {
+            return Ok(None);
+        };
+        properties
+            .into_iter()
+            .map(|property| {
+                let dict = PyDict::new(py);
+                dict.set_item("id", property.id)?;
+                dict.set_item("name", property.name)?;
+                dict.set_item("category", format!("{:?}", property.category))?;
+                match property.value {
+                    PropertyValue::Number(n) => dict.set_item("value", n)?,
+                    PropertyValue::Integer(n) => dict.set_item("value", n)?,
+                    PropertyValue::Boolean(b) => dict.set_item("value", b)?,
+                    PropertyValue::Color(s)
+                    | PropertyValue::String(s)
+                    | PropertyValue::LineStyle(s) => dict.set_item("value", s)?,
+                    _ => dict.set_item("value", py.None())?,
+                }
+                Ok(dict)
+            })
+            .collect::<PyResult<_>>()
+            .map(Some)
+    }
+
+    /// Change one indicator setting by its property id, e.g.
+    /// update_indicator("sma_20", "period", 50); new inputs recompute the
+    /// indicator from the chart's bars
+    fn update_indicator(&mut self, id: &str, name: &str, value: &Bound<'_, PyAny>) -> PyResult<()> {
+        let value = if let Ok(b) = value.extract::<bool>() {
+            PropertyValue::Boolean(b)
+        } else if let Ok(n) = value.extract::<i32>() {
+            PropertyValue::Integer(n)
+        } else if let Ok(n) = value.extract::<f64>() {
+            PropertyValue::Number(n)
+        } else if let Ok(s) = value.extract::<String>() {
+            PropertyValue::String(s)
+        } else {
+            return Err(pyo3::exceptions::PyValueError::new_err(
+                "Indicator property values are bool, int, float or str",
+            ));
+        };
+        self.inner
+            .as_mut()
+            .expect("Chart already consumed")
+            .update_indicator(id, name, value)
+            .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))
+    }
+
+    /// Render to SVG along with a stats dict: bars, commands (count per
+    /// command type), command_count, svg_bytes, timings (seconds per
+    /// phase: layout, series, indicators, primitives, serialization) and
+    /// warnings (see validate)
+    fn render_svg_with_stats<'py>(
+        &self,
+        py: Python<'py>,
+    ) -> PyResult<(String, Bound<'py, PyDict>)> {
+        let (svg, stats) = self
+            .inner
+            .as_ref()
+            .map(|c| c.render_svg_with_stats())
+            .unwrap_or_default();
+
+        let dict = PyDict::new(py);
+        dict.set_item("bars", stats.bars)?;
+        dict.set_item("commands", &stats.commands)?;
+        dict.set_item("command_count", stats.command_count())?;
+        dict.set_item("svg_bytes", stats.svg_bytes)?;
+        let t = stats.timings;
+        let timings = PyDict::new(py);
+        timings.set_item("layout", t.layout.as_secs_f64())?;
+        timings.set_item("series", t.series.as_secs_f64())?;
+        timings.set_item("indicators", t.indicators.as_secs_f64())?;
+        timings.set_item("primitives", t.primitives.as_secs_f64())?;
+        timings.set_item("serialization", t.serialization.as_secs_f64())?;
+        timings.set_item("total", t.total().as_secs_f64())?;
+        dict.set_item("timings", timings)?;
+        let warnings: Vec<String> = stats.warnings.iter().map(|w| w.to_string()).collect();
+        dict.set_item("warnings", warnings)?;
+        Ok((svg, dict))
+    }
+
+    /// Configuration problems that render silently wrong (indicators
+    /// without values, duplicate ids, oversized subpanes, bad colors,
+    /// out-of-range bar indices) as messages
+    fn validate(&self) -> Vec<String> {
+        self.inner
+            .as_ref()
+            .map(|c| c.validate().iter().map(|w| w.to_string()).collect())
+            .unwrap_or_default()
+    }
+
+    /// Tooltip payload for a bar as a dict (time, OHLCV strings, indicator
+    /// values with labels and colors, signals); None when out of range
+    fn bar_info<'py>(&self, py: Python<'py>, index: usize) -> PyResult<Option<Bound<'py, PyDict>>> {
+        let Some(info) = self.inner.as_ref().and_then(|c| c.bar_info(index)) else {
+            return Ok(None);
+        };
+
+        let dict = PyDict::new(py);
+        dict.set_item("index", info.index)?;
+        dict.set_item("timestamp", info.timestamp)?;
+        dict.set_item("time", info.time)?;
+        dict.set_item("open", info.open)?;
+        dict.set_item("high", info.high)?;
+        dict.set_item("low", info.low)?;
+        dict.set_item("close", info.close)?;
+        dict.set_item("volume", info.volume)?;
+
+        let mut indicators = Vec::with_capacity(info.indicators.len());
+        for indicator in info.indicators {
+            let values = indicator
+                .values
+                .into_iter()
+                .map(|v| {
+                    let value = PyDict::new(py);
+                    value.set_item("label", v.label)?;
+                    value.set_item("value", v.value)?;
+                    value.set_item("text", v.text)?;
+                    value.set_item("color", v.color)?;
+                    Ok(value)
+                })
+                .collect::<PyResult<Vec<_>>>()?;
+            let item = PyDict::new(py);
+            item.set_item("id", indicator.id)?;
+            item.set_item("name", indicator.name)?;
+            item.set_item("values", values)?;
+            indicators.push(item);
+        }
+        dict.set_item("indicators", indicators)?;
+
+        let signals = info
+            .signals
+            .into_iter()
+            .map(|s| {
+                let signal = PyDict::new(py);
+                signal.set_item("signal_type", s.signal_type)?;
+                signal.set_item("price", s.price)?;
+                signal.set_item("text", s.text)?;
+                Ok(signal)
+            })
+            .collect::<PyResult<Vec<_>>>()?;
+        dict.set_item("signals", signals)?;
+
+        Ok(Some(dict))
+    }
+
+    /// Legend contents as a dict (bar OHLCV, series name and type, overlay
+    /// indicators in legend order with their values and colors) at the
+    /// hovered bar or the last one; None without bars
+    fn legend_data<'py>(&self, py: Python<'py>) -> PyResult<Option<Bound<'py, PyDict>>> {
+        let Some(legend) = self.inner.as_ref().and_then(|c| c.legend_data()) else {
+            return Ok(None);
+        };
+
+        let dict = PyDict::new(py);
+        dict.set_item("index", legend.index)?;
+        dict.set_item("timestamp", legend.timestamp)?;
+        dict.set_item("open", legend.open)?;
+        dict.set_item("high", legend.high)?;
+        dict.set_item("low", legend.low)?;
+        dict.set_item("close", legend.close)?;
+        dict.set_item("volume", legend.volume)?;
+        dict.set_item("prev_close", legend.prev_close)?;
+        dict.set_item("change", legend.change())?;
+        dict.set_item("change_percent", legend.change_percent())?;
+        dict.set_item("series_name", legend.series_name)?;
+        dict.set_item("series_type", legend.series_type.as_str())?;
+
+        let mut indicators = Vec::with_capacity(legend.indicators.len());
+        for indicator in legend.indicators {
+            let values = indicator
+                .values
+                .into_iter()
+                .map(|v| {
+                    let value = PyDict::new(py);
+                    value.set_item("label", v.label)?;
+                    value.set_item("value", v.value)?;
+                    value.set_item("color", v.color)?;
+                    Ok(value)
+                })
+                .collect::<PyResult<Vec<_>>>()?;
+            let item = PyDict::new(py);
+            item.set_item("id", indicator.id)?;
+            item.set_item("name", indicator.name)?;
+            item.set_item("group", indicator.group)?;
+            item.set_item("visible", indicator.visible)?;
+            item.set_item("values", values)?;
+            indicators.push(item);
+        }
+        dict.set_item("indicators", indicators)?;
+
+        Ok(Some(dict))
+    }
+
+    /// Plain-text summary of the chart for screen readers and alt text
+    fn describe(&self) -> String {
+        self.inner
+            .as_ref()
+            .map(|c| c.describe())
+            .unwrap_or_default()
+    }
+
     /// Render chart to SVG string
     fn render_svg(&self) -> String {
         self.inner
@@ -1536,6 +2607,30 @@ impl PyChart {
             .map(|c| c.render_svg())
             .unwrap_or_default()
     }
+
+    /// One SVG per replay step, drawing bars up to each index in
+    /// `start..end`, e.g. for a GIF of a backtest
+    #[pyo3(signature = (start, end, lock_scale=true))]
+    fn render_frames(&mut self, start: usize, end: usize, lock_scale: bool) -> Vec<String> {
+        let chart = self.take_inner().replay_lock_scale(lock_scale);
+        let frames = chart.render_frames(start..end);
+        self.put_inner(chart);
+        frames
+    }
+
+    /// Parts of the SVG that changed since the last call, as (group_id,
+    /// markup) pairs: swap the inner markup of the `<g id=group_id>`
+    /// node, or replace the whole document when group_id is None (always
+    /// the case on the first call)
+    fn render_dirty(&mut self) -> Vec<(Option<String>, String)> {
+        self.inner
+            .as_mut()
+            .map(|c| c.render_dirty())
+            .unwrap_or_default()
+            .into_iter()
+            .map(|f| (f.id, f.svg))
+            .collect()
+    }
 }
 
 // =============================================================================
@@ -1579,6 +2674,18 @@ impl PyViewport {
     fn scroll_to_start(&mut self) {
         self.inner.scroll_to_start();
     }
+    fn scroll_by(&mut self, bars: f64) {
+        self.inner.scroll_by(bars);
+    }
+    fn scroll_offset(&self) -> f64 {
+        self.inner.scroll_offset()
+    }
+    fn set_scroll_offset(&mut self, offset: f64) {
+        self.inner.set_scroll_offset(offset);
+    }
+    fn set_right_offset(&mut self, bars: f64) {
+        self.inner.set_right_offset(bars);
+    }
 }
 
 /// Legacy simple theme (for backwards compatibility)
@@ -1810,7 +2917,7 @@ impl PyRuntimeTheme {
         }
     }
 
-    /// Deserialize from JSON string
+    /// Deserialize from JSON string, migrating themes saved by older versions
     #[staticmethod]
     fn from_json(json: &str) -> Option<Self> {
         RuntimeTheme::from_json(json).map(|inner| Self { inner })
@@ -1832,8 +2939,17 @@ impl PyRuntimeTheme {
         RuntimeTheme::PRESETS.to_vec()
     }
 
+    /// Schema version written by to_json
+    #[classattr]
+    const CURRENT_SCHEMA_VERSION: u32 = RuntimeTheme::CURRENT_SCHEMA_VERSION;
+
     // === Basic properties ===
 
+    #[getter]
+    fn schema_version(&self) -> u32 {
+        self.inner.schema_version
+    }
+
     #[getter]
     fn name(&self) -> &str {
         &self.inner.name
@@ -2001,20 +3117,250 @@ impl PyRuntimeTheme {
     }
 }
 
+/// Complete chart configuration, built field by field or read from a
+/// dict or JSON, and rendered directly with `render(bars)`
 #[pyclass(name = "ChartConfig")]
+#[derive(Clone)]
 pub struct PyChartConfig {
-    #[allow(dead_code)]
     inner: RustChartConfig,
 }
 
+/// Read a config from a ChartConfig, a JSON string or a dict; errors name
+/// the offending field, e.g. "indicators[1].placement: unknown variant ..."
+fn config_from_py(value: &Bound<'_, PyAny>) -> PyResult<RustChartConfig> {
+    if let Ok(config) = value.extract::<PyChartConfig>() {
+        return Ok(config.inner);
+    }
+    let json = match value.extract::<String>() {
+        Ok(json) => json,
+        Err(_) => value
+            .py()
+            .import("json")?
+            .call_method1("dumps", (value,))?
+            .extract()?,
+    };
+    RustChartConfig::from_json(&json)
+        .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))
+}
+
 #[pymethods]
 impl PyChartConfig {
     #[new]
-    fn new() -> Self {
+    #[pyo3(signature = (width=800, height=600))]
+    fn new(width: u32, height: u32) -> Self {
         Self {
-            inner: RustChartConfig::default(),
+            inner: RustChart::new(width, height).build().0,
         }
     }
+
+    /// Read a config from a dict or JSON string, with defaults for the
+    /// fields it leaves out
+    #[staticmethod]
+    fn from_dict(config: &Bound<'_, PyAny>) -> PyResult<Self> {
+        Ok(Self {
+            inner: config_from_py(config)?,
+        })
+    }
+
+    /// Serialize to a JSON string, readable back with from_dict
+    fn to_json(&self) -> String {
+        self.inner.to_json()
+    }
+
+    #[getter]
+    fn width(&self) -> u32 {
+        self.inner.width
+    }
+
+    #[setter]
+    fn set_width(&mut self, width: u32) {
+        self.inner.width = width;
+    }
+
+    #[getter]
+    fn height(&self) -> u32 {
+        self.inner.height
+    }
+
+    #[setter]
+    fn set_height(&mut self, height: u32) {
+        self.inner.height = height;
+    }
+
+    #[getter]
+    fn dpr(&self) -> f64 {
+        self.inner.dpr
+    }
+
+    #[setter]
+    fn set_dpr(&mut self, dpr: f64) {
+        self.inner.dpr = dpr;
+    }
+
+    /// Main series type, e.g. "candlestick", "line", "heikin_ashi"
+    #[getter]
+    fn series_type(&self) -> &str {
+        self.inner.series.series_type.as_str()
+    }
+
+    #[setter]
+    fn set_series_type(&mut self, series_type: &str) -> PyResult<()> {
+        self.inner.series.series_type = series_type
+            .parse()
+            .map_err(|_| pyo3::exceptions::PyValueError::new_err("unknown series type"))?;
+        Ok(())
+    }
+
+    #[getter]
+    fn background(&self) -> &str {
+        &self.inner.theme.background
+    }
+
+    #[setter]
+    fn set_background(&mut self, color: String) {
+        self.inner.theme.background = color;
+    }
+
+    #[getter]
+    fn text_color(&self) -> &str {
+        &self.inner.theme.text_color
+    }
+
+    #[setter]
+    fn set_text_color(&mut self, color: String) {
+        self.inner.theme.text_color = color;
+    }
+
+    #[getter]
+    fn grid_color(&self) -> &str {
+        &self.inner.theme.grid_color
+    }
+
+    #[setter]
+    fn set_grid_color(&mut self, color: String) {
+        self.inner.theme.grid_color = color;
+    }
+
+    #[getter]
+    fn show_grid(&self) -> bool {
+        self.inner.theme.show_grid
+    }
+
+    #[setter]
+    fn set_show_grid(&mut self, show: bool) {
+        self.inner.theme.show_grid = show;
+    }
+
+    #[getter]
+    fn up_color(&self) -> &str {
+        &self.inner.theme.up_color
+    }
+
+    #[setter]
+    fn set_up_color(&mut self, color: String) {
+        self.inner.theme.up_color = color;
+    }
+
+    #[getter]
+    fn down_color(&self) -> &str {
+        &self.inner.theme.down_color
+    }
+
+    #[setter]
+    fn set_down_color(&mut self, color: String) {
+        self.inner.theme.down_color = color;
+    }
+
+    #[getter]
+    fn border_color(&self) -> &str {
+        &self.inner.theme.border_color
+    }
+
+    #[setter]
+    fn set_border_color(&mut self, color: String) {
+        self.inner.theme.border_color = color;
+    }
+
+    /// Add a built-in indicator by kind ("sma", "ema", "wma", "hma",
+    /// "bollinger", "keltner", "donchian", "zigzag", "psar", "supertrend",
+    /// "rsi", "stochastic", "cci", "williams_r", "atr", "adx", "macd", "obv",
+    /// "ad", "cmf", "mfi") with its standard inputs, overridden by keyword, e.g.
+    /// add_indicator("macd", fast=8, source="hl2");
+    /// values are computed from the bars when rendered
+    #[pyo3(signature = (kind, id=None, **inputs))]
+    fn add_indicator(
+        &mut self,
+        kind: &str,
+        id: Option<&str>,
+        inputs: Option<&Bound<'_, PyDict>>,
+    ) -> PyResult<()> {
+        let mut params: IndicatorParams = kind.parse().map_err(|_| {
+            pyo3::exceptions::PyValueError::new_err(format!("unknown indicator kind '{}'", kind))
+        })?;
+        for (name, value) in inputs.into_iter().flatten() {
+            let name: String = name.extract()?;
+            let known = if name == "source" {
+                let source: Source = value
+                    .extract::<String>()?
+                    .parse()
+                    .map_err(|_| pyo3::exceptions::PyValueError::new_err("unknown source"))?;
+                params.set_source(source)
+            } else {
+                params.set_input(&name, value.extract()?)
+            };
+            if !known {
+                return Err(pyo3::exceptions::PyValueError::new_err(format!(
+                    "{} has no input '{}'",
+                    kind, name
+                )));
+            }
+        }
+        self.inner
+            .indicators
+            .push(Indicator::from_params(id.unwrap_or(kind), params));
+        Ok(())
+    }
+
+    /// Add a drawing primitive by type id with its (bar, price) points
+    #[pyo3(signature = (type_id, points, color=None, id=None))]
+    fn add_primitive(
+        &mut self,
+        type_id: &str,
+        points: Vec<(f64, f64)>,
+        color: Option<&str>,
+        id: Option<&str>,
+    ) -> PyResult<()> {
+        self.inner
+            .primitive_registry()
+            .validate(type_id, points.len())
+            .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))?;
+        let mut primitive = PrimitiveConfig::new(type_id, points);
+        if let Some(color) = color {
+            primitive = primitive.with_color(color);
+        }
+        if let Some(id) = id {
+            primitive = primitive.with_id(id);
+        }
+        self.inner.primitives.push(primitive);
+        Ok(())
+    }
+
+    /// Add a signal marker: kind is "buy", "sell", "entry", "exit", ...
+    #[pyo3(signature = (kind, bar_index, price, label=None))]
+    fn add_signal(&mut self, kind: &str, bar_index: usize, price: f64, label: Option<&str>) {
+        let signal_type = kind.parse().unwrap_or_default();
+        let mut signal = SignalConfig::new(signal_type, bar_index, price);
+        if let Some(label) = label {
+            signal = signal.with_label(label);
+        }
+        self.inner.signals.push(signal);
+    }
+
+    /// Render this config over `bars` to an SVG string
+    fn render(&self, bars: Vec<PyBar>) -> String {
+        let bars: Vec<Bar> = bars.iter().map(|b| b.to_rust()).collect();
+        ChartRenderer::new(&self.inner, &bars).render_svg()
+    }
 }
 
 // =============================================================================
@@ -19,6 +19,7 @@ from zengeld_canvas import (
     version,
     Bar,
     Chart,
+    ChartConfig,
     UITheme,
     RuntimeTheme,
     Viewport,
@@ -154,10 +155,10 @@ def main():
     chart = Chart(800, 400)
     chart.bars(bars)
     chart.candlesticks()
-    chart.background(runtime.background)
-    chart.colors(runtime.candle_up_body, runtime.candle_down_body)
+    chart.apply_theme(runtime)
     chart.sma(20, "#ffff00")
     svg = chart.render_svg()
+    assert all(c in svg for c in ("#1a0a2e", "#00ffff", "#ff00ff"))
     save_svg(svg, f"{output_dir}/05_runtime_custom.svg")
 
     # =========================================================================
@@ -237,6 +238,41 @@ def main():
     svg = chart.render_svg()
     save_svg(svg, f"{output_dir}/11_signals.svg")
 
+    # 12. Chart from Config (two subpanes, no builder calls)
+    print("12. Chart from Config")
+    chart = Chart.from_config({
+        "width": 1000,
+        "height": 600,
+        "theme": {"background": "#0b0e14"},
+        "indicators": [
+            {"params": {"kind": "sma", "period": 20}},
+            {"params": {"kind": "rsi", "period": 14}},
+            {"params": {"kind": "macd", "fast": 12, "slow": 26, "signal": 9}},
+        ],
+        "signals": [{"signal_type": "Buy", "bar_index": 40, "price": bars[40].low - 2.0}],
+    })
+    chart.bars(bars)
+    svg = chart.render_svg()
+    assert "RSI 14" in svg and "MACD (12,26,9)" in svg
+    save_svg(svg, f"{output_dir}/12_from_config.svg")
+
+    config = ChartConfig(1000, 600)
+    config.background = "#0b0e14"
+    config.series_type = "heikin_ashi"
+    config.add_indicator("rsi", period=7)
+    config.add_indicator("macd", fast=8, source="hl2")
+    config.add_primitive("horizontal_line", [(0, bars[0].close)])
+    config.add_signal("sell", 60, bars[60].high + 2.0, "Short")
+    svg = config.render(bars)
+    assert "RSI 7" in svg and "MACD (8,26,9) hl2" in svg
+    assert ChartConfig.from_dict(config.to_json()).series_type == "heikin_ashi"
+
+    try:
+        Chart.from_config({"indicators": [{"params": {"kind": "rsi", "period": 14}, "placement": "nowhere"}]})
+        raise AssertionError("invalid config accepted")
+    except ValueError as e:
+        assert str(e).startswith("indicators[0].placement:"), e
+
     # =========================================================================
     # API Verification
     # =========================================================================
@@ -276,7 +312,7 @@ def main():
 
     print(f"\n[OK] All charts generated in '{output_dir}/'\n")
     print("Generated files:")
-    for i in range(1, 12):
+    for i in range(1, 13):
         prefix = "0" if i < 10 else ""
         print(f"  - {prefix}{i}_*.svg")
 
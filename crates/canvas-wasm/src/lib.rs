@@ -25,11 +25,21 @@
 
 use wasm_bindgen::prelude::*;
 use zengeld_canvas::api::{
-    Chart as RustChart, ChartConfig as RustChartConfig, PrimitiveConfig, SignalConfig,
+    Chart as RustChart, ChartConfig as RustChartConfig, ChartRenderer, GapConfig, GapThreshold,
+    HtfOverlayStyle, InsetRect, InsetSeries, PrimitiveConfig, SignalConfig, ThemeConfig,
+    TickSeries, VbpConfig,
 };
 use zengeld_canvas::core::Bar;
-use zengeld_canvas::model::Indicator;
-use zengeld_canvas::{RuntimeTheme, Theme, UITheme, Viewport};
+use zengeld_canvas::model::{
+    BandStyle, Indicator, IndicatorParams, IndicatorRange, PivotLabels, ScaleGroup, Source,
+    Strategy,
+};
+use zengeld_canvas::primitives::PropertyValue;
+use zengeld_canvas::render::SvgPrecision;
+use zengeld_canvas::{
+    BandDeviation, CompositeSpec, DivergenceType, FibLabelMode, RuntimeTheme, Theme, UITheme,
+    Viewport,
+};
 
 // =============================================================================
 // JsBar - OHLCV data point
@@ -106,6 +116,24 @@ impl Chart {
     fn put_inner(&mut self, chart: RustChart) {
         self.inner = Some(chart);
     }
+    /// Price source of an indicator, close when omitted
+    fn parse_source(source: Option<String>) -> Result<Source, JsValue> {
+        source
+            .map_or(Ok(Source::Close), |s| s.parse())
+            .map_err(|_| JsValue::from_str("unknown price source"))
+    }
+    /// Add a primitive whose point count came from the caller
+    fn add_checked(&mut self, primitive: PrimitiveConfig) -> Result<(), JsValue> {
+        self.inner
+            .as_ref()
+            .expect("Chart already consumed")
+            .primitive_registry()
+            .validate(&primitive.type_id, primitive.points.len())
+            .map_err(|e| JsValue::from_str(&e.to_string()))?;
+        let chart = self.take_inner().primitive(primitive);
+        self.put_inner(chart);
+        Ok(())
+    }
 }
 
 #[wasm_bindgen]
@@ -118,6 +146,15 @@ impl Chart {
         }
     }
 
+    /// Chart from a complete config: a plain object or JSON string. Fields
+    /// left out keep their defaults; errors name the offending field
+    #[wasm_bindgen(js_name = fromConfig)]
+    pub fn from_config(config: JsValue) -> Result<Chart, JsValue> {
+        Ok(Self {
+            inner: Some(RustChart::from_config(config_from_js(&config)?)),
+        })
+    }
+
     // =========================================================================
     // Configuration
     // =========================================================================
@@ -129,6 +166,13 @@ impl Chart {
         self.put_inner(chart);
     }
 
+    /// Set independent horizontal and vertical pixel ratios (e.g. for print)
+    #[wasm_bindgen(js_name = setDprXY)]
+    pub fn set_dpr_xy(&mut self, dpr_x: f64, dpr_y: f64) {
+        let chart = self.take_inner().dpr_xy(dpr_x, dpr_y);
+        self.put_inner(chart);
+    }
+
     /// Set OHLCV bar data
     #[wasm_bindgen(js_name = setBars)]
     pub fn set_bars(&mut self, bars: Vec<JsBar>) {
@@ -137,6 +181,47 @@ impl Chart {
         self.put_inner(chart);
     }
 
+    /// Sort bars by timestamp and merge duplicates: "keep_last" or "combine"
+    #[wasm_bindgen(js_name = setSortBars)]
+    pub fn set_sort_bars(&mut self, sort: bool, merge: &str) -> Result<(), JsValue> {
+        let Ok(merge) = merge.parse() else {
+            return Err(JsValue::from_str("unknown merge mode"));
+        };
+        let chart = self.take_inner().duplicate_merge(merge).sort_bars(sort);
+        self.put_inner(chart);
+        Ok(())
+    }
+
+    /// Empty space after the last bar, in bars
+    #[wasm_bindgen(js_name = setRightOffset)]
+    pub fn set_right_offset(&mut self, bars: f64) {
+        let chart = self.take_inner().right_offset(bars);
+        self.put_inner(chart);
+    }
+
+    /// Bar placement: "indexed" (one slot per bar) or "proportional" (by
+    /// timestamp, so weekends and holidays show as gaps)
+    #[wasm_bindgen(js_name = setTimeSpacing)]
+    pub fn set_time_spacing(&mut self, mode: &str) -> Result<(), JsValue> {
+        let Ok(mode) = mode.parse() else {
+            return Err(JsValue::from_str("unknown time spacing"));
+        };
+        let chart = self.take_inner().time_spacing(mode);
+        self.put_inner(chart);
+        Ok(())
+    }
+
+    /// Bar replay: draw bars only up to index `last`, optionally keeping the
+    /// full data set's price scale
+    #[wasm_bindgen(js_name = setReplayTo)]
+    pub fn set_replay_to(&mut self, last: usize, lock_scale: bool) {
+        let chart = self
+            .take_inner()
+            .replay_to(last)
+            .replay_lock_scale(lock_scale);
+        self.put_inner(chart);
+    }
+
     // =========================================================================
     // Series Types
     // =========================================================================
@@ -162,6 +247,101 @@ impl Chart {
         self.put_inner(chart);
     }
 
+    /// Fill the area chart down to `base` instead of the pane bottom,
+    /// optionally with `below_color` under it (call after `area`)
+    #[wasm_bindgen(js_name = setAreaBase)]
+    pub fn set_area_base(&mut self, base: f64, below_color: Option<String>) {
+        let mut chart = self.take_inner().area_base(base);
+        if let Some(color) = below_color {
+            chart = chart.area_below_color(&color);
+        }
+        self.put_inner(chart);
+    }
+
+    /// Baseline chart split into up/down zones at `value` (may be negative)
+    #[wasm_bindgen]
+    pub fn baseline(&mut self, value: f64) {
+        let chart = self.take_inner().baseline(value);
+        self.put_inner(chart);
+    }
+
+    /// Heikin Ashi chart
+    #[wasm_bindgen(js_name = heikinAshi)]
+    pub fn heikin_ashi(&mut self) {
+        let chart = self.take_inner().heikin_ashi();
+        self.put_inner(chart);
+    }
+
+    /// Renko chart: the bars become bricks of `box_size`, time-labelled by
+    /// the bars that completed them
+    #[wasm_bindgen]
+    pub fn renko(&mut self, box_size: f64) {
+        let chart = self.take_inner().renko(box_size);
+        self.put_inner(chart);
+    }
+
+    /// Line-break chart turning after `lines` lines (3 for three-line break)
+    #[wasm_bindgen(js_name = lineBreak)]
+    pub fn line_break(&mut self, lines: usize) {
+        let chart = self.take_inner().line_break(lines);
+        self.put_inner(chart);
+    }
+
+    /// Heikin Ashi wick range: "smoothed" or "original"
+    #[wasm_bindgen(js_name = setHaWickSource)]
+    pub fn set_ha_wick_source(&mut self, source: &str) -> Result<(), JsValue> {
+        let Ok(source) = source.parse() else {
+            return Err(JsValue::from_str("unknown wick source"));
+        };
+        let chart = self.take_inner().ha_wick_source(source);
+        self.put_inner(chart);
+        Ok(())
+    }
+
+    /// Candle wicks as a fraction of the body width, never under 1px
+    #[wasm_bindgen(js_name = setWickWidthRatio)]
+    pub fn set_wick_width_ratio(&mut self, ratio: f64) {
+        let chart = self.take_inner().wick_width_ratio(ratio);
+        self.put_inner(chart);
+    }
+
+    /// Candle bodies `pixels` wide at any zoom, narrowed where they would overlap
+    #[wasm_bindgen(js_name = setCandleWidthPixels)]
+    pub fn set_candle_width_pixels(&mut self, pixels: f64) {
+        let chart = self.take_inner().candle_width_pixels(pixels);
+        self.put_inner(chart);
+    }
+
+    /// Past `barsPerPixel`, draw candles as "high_low" lines or "cull_wicks"
+    #[wasm_bindgen(js_name = setDenseCandles)]
+    pub fn set_dense_candles(&mut self, bars_per_pixel: f64, mode: &str) -> Result<(), JsValue> {
+        let Ok(mode) = mode.parse() else {
+            return Err(JsValue::from_str("unknown dense candle mode"));
+        };
+        let chart = self.take_inner().dense_candles(bars_per_pixel, mode);
+        self.put_inner(chart);
+        Ok(())
+    }
+
+    /// OHLC bar chart; `tick_length` in pixels (unset = half the bar width),
+    /// open and close ticks shown unless set to false
+    #[wasm_bindgen(js_name = ohlcBars)]
+    pub fn ohlc_bars(
+        &mut self,
+        tick_length: Option<f64>,
+        show_open_tick: Option<bool>,
+        show_close_tick: Option<bool>,
+    ) {
+        let mut chart = self.take_inner().ohlc_bars().bar_ticks(
+            show_open_tick.unwrap_or(true),
+            show_close_tick.unwrap_or(true),
+        );
+        if let Some(length) = tick_length {
+            chart = chart.bar_tick_length(length);
+        }
+        self.put_inner(chart);
+    }
+
     // =========================================================================
     // Theme & Styling
     // =========================================================================
@@ -187,6 +367,545 @@ impl Chart {
         self.put_inner(chart);
     }
 
+    /// Set per-bar colors (empty string keeps the theme color)
+    #[wasm_bindgen(js_name = setBarColors)]
+    pub fn set_bar_colors(&mut self, colors: Vec<String>) {
+        let colors = colors
+            .into_iter()
+            .map(|c| if c.is_empty() { None } else { Some(c) })
+            .collect();
+        let chart = self.take_inner().bar_colors(colors);
+        self.put_inner(chart);
+    }
+
+    /// Show/hide the price axis on the right
+    #[wasm_bindgen(js_name = setPriceScale)]
+    pub fn set_price_scale(&mut self, show: bool) {
+        let chart = self.take_inner().price_scale(show);
+        self.put_inner(chart);
+    }
+
+    /// Size the price axis to its widest label instead of a fixed width
+    #[wasm_bindgen(js_name = setPriceScaleFit)]
+    pub fn set_price_scale_fit(&mut self, fit: bool) {
+        let chart = self.take_inner().price_scale_fit(fit);
+        self.put_inner(chart);
+    }
+
+    /// Show/hide the time axis at the bottom
+    #[wasm_bindgen(js_name = setTimeScale)]
+    pub fn set_time_scale(&mut self, show: bool) {
+        let chart = self.take_inner().time_scale(show);
+        self.put_inner(chart);
+    }
+
+    /// Sparkline mode: a bare line filling the canvas, no axes or grid
+    #[wasm_bindgen(js_name = sparkline)]
+    pub fn sparkline(&mut self) {
+        let chart = self.take_inner().sparkline();
+        self.put_inner(chart);
+    }
+
+    /// Show each subpane's indicator name and latest values
+    #[wasm_bindgen(js_name = setPaneTitles)]
+    pub fn set_pane_titles(&mut self, show: bool) {
+        let chart = self.take_inner().pane_titles(show);
+        self.put_inner(chart);
+    }
+
+    /// Corner of the subpane titles: "top_left", "top_right", "bottom_left"
+    /// or "bottom_right"
+    #[wasm_bindgen(js_name = setLegendPosition)]
+    pub fn set_legend_position(&mut self, position: &str) -> Result<(), JsValue> {
+        let Ok(position) = position.parse() else {
+            return Err(JsValue::from_str("unknown legend position"));
+        };
+        let chart = self.take_inner().legend_position(position);
+        self.put_inner(chart);
+        Ok(())
+    }
+
+    /// Size of the scales, grid and signal glyphs: "compact", "normal" or
+    /// "comfortable"
+    #[wasm_bindgen(js_name = setDensity)]
+    pub fn set_density(&mut self, density: &str) -> Result<(), JsValue> {
+        let Ok(density) = density.parse() else {
+            return Err(JsValue::from_str("unknown density"));
+        };
+        let chart = self.take_inner().density(density);
+        self.put_inner(chart);
+        Ok(())
+    }
+
+    /// Symbol or title used in the text description
+    #[wasm_bindgen(js_name = setTitle)]
+    pub fn set_title(&mut self, title: &str) {
+        let chart = self.take_inner().title(title);
+        self.put_inner(chart);
+    }
+
+    /// Embed the text description in the SVG as `<title>`/`<desc>`
+    #[wasm_bindgen(js_name = setEmbedDescription)]
+    pub fn set_embed_description(&mut self, embed: bool) {
+        let chart = self.take_inner().embed_description(embed);
+        self.put_inner(chart);
+    }
+
+    /// Force a time label pattern such as "%H:%M"
+    #[wasm_bindgen(js_name = setTimeFormat)]
+    pub fn set_time_format(&mut self, pattern: &str) {
+        let chart = self.take_inner().time_format(pattern);
+        self.put_inner(chart);
+    }
+
+    /// Unit of bar timestamps: "s", "ms" or "auto" (ms above 1e12)
+    #[wasm_bindgen(js_name = setTimestampUnit)]
+    pub fn set_timestamp_unit(&mut self, unit: &str) -> Result<(), JsValue> {
+        let Ok(unit) = unit.parse() else {
+            return Err(JsValue::from_str("unknown timestamp unit"));
+        };
+        let chart = self.take_inner().timestamp_unit(unit);
+        self.put_inner(chart);
+        Ok(())
+    }
+
+    /// Highlight the hovered bar, or clear the highlight with `undefined`
+    #[wasm_bindgen(js_name = setHoverBar)]
+    pub fn set_hover_bar(&mut self, index: Option<usize>) {
+        let chart = self.take_inner().hover_bar(index);
+        self.put_inner(chart);
+    }
+
+    /// Show a dashed line and axis tag at the last close
+    #[wasm_bindgen(js_name = setLastPriceLine)]
+    pub fn set_last_price_line(&mut self, show: bool) {
+        let chart = self.take_inner().last_price_line(show);
+        self.put_inner(chart);
+    }
+
+    /// Never squeeze bars narrower than `spacing` pixels; earlier bars that
+    /// do not fit are left out
+    #[wasm_bindgen(js_name = setMinBarSpacing)]
+    pub fn set_min_bar_spacing(&mut self, spacing: f64) -> Result<(), JsValue> {
+        if !(spacing.is_finite() && spacing > 0.0) {
+            return Err(JsValue::from_str("spacing must be a positive number"));
+        }
+        let chart = self.take_inner().min_bar_spacing(spacing);
+        self.put_inner(chart);
+        Ok(())
+    }
+
+    /// Show a scrollbar when `setMinBarSpacing` leaves bars out
+    #[wasm_bindgen(js_name = setScrollIndicator)]
+    pub fn set_scroll_indicator(&mut self, show: bool) {
+        let chart = self.take_inner().scroll_indicator(show);
+        self.put_inner(chart);
+    }
+
+    /// Decimals of SVG coordinates for axes/grid, series, curves and
+    /// everything else (e.g. 0, 1, 2, 2 for compact output)
+    #[wasm_bindgen(js_name = setSvgPrecision)]
+    pub fn set_svg_precision(&mut self, axis: u8, series: u8, curves: u8, other: u8) {
+        let precision = SvgPrecision {
+            axis,
+            series,
+            curves,
+            other,
+        };
+        let chart = self.take_inner().svg_precision(precision);
+        self.put_inner(chart);
+    }
+
+    /// Mark bars that open more than `threshold` (percent, or price distance
+    /// when `percent` is false) away from the previous close
+    #[wasm_bindgen(js_name = setShowGaps)]
+    pub fn set_show_gaps(&mut self, threshold: f64, percent: bool, shade: bool, marker: bool) {
+        let threshold = if percent {
+            GapThreshold::Percent(threshold)
+        } else {
+            GapThreshold::Absolute(threshold)
+        };
+        let gaps = GapConfig::new(threshold)
+            .with_shade(shade)
+            .with_marker(marker);
+        let chart = self.take_inner().show_gaps(gaps);
+        self.put_inner(chart);
+    }
+
+    /// Draw higher-timeframe candles (e.g. 14400 for 4h) behind the main series
+    #[wasm_bindgen(js_name = setHtfOverlay)]
+    pub fn set_htf_overlay(&mut self, interval_secs: i64, filled: bool, show_wicks: bool) {
+        let style = if filled {
+            HtfOverlayStyle::filled()
+        } else {
+            HtfOverlayStyle::outline()
+        };
+        let chart = self
+            .take_inner()
+            .htf_overlay(interval_secs, style.with_wicks(show_wicks));
+        self.put_inner(chart);
+    }
+
+    /// Draw a volume-by-price histogram along the right edge of the main pane
+    ///
+    /// `width` is the fullest bucket's length as a fraction of the pane;
+    /// the source defaults to hlc3.
+    #[wasm_bindgen(js_name = setVolumeByPrice)]
+    #[allow(clippy::too_many_arguments)]
+    pub fn set_volume_by_price(
+        &mut self,
+        buckets: Option<usize>,
+        width: Option<f64>,
+        source: Option<String>,
+        poc: Option<bool>,
+        split: Option<bool>,
+        color: Option<String>,
+    ) -> Result<(), JsValue> {
+        let source = Self::parse_source(Some(source.unwrap_or_else(|| "hlc3".to_string())))?;
+        let defaults = VbpConfig::default();
+        let config = VbpConfig {
+            buckets: buckets.unwrap_or(defaults.buckets),
+            width_ratio: width.unwrap_or(defaults.width_ratio),
+            source,
+            show_poc: poc.unwrap_or(false),
+            split_up_down: split.unwrap_or(false),
+            color,
+        };
+        let chart = self.take_inner().volume_by_price(config);
+        self.put_inner(chart);
+        Ok(())
+    }
+
+    /// Pin a mini line chart of `bars` closes in a box of the main pane
+    ///
+    /// `x`, `y`, `width` and `height` are fractions of the pane.
+    #[wasm_bindgen(js_name = addInset)]
+    #[allow(clippy::too_many_arguments)]
+    pub fn add_inset(
+        &mut self,
+        bars: Vec<JsBar>,
+        x: f64,
+        y: f64,
+        width: f64,
+        height: f64,
+        color: Option<String>,
+        label: Option<String>,
+    ) {
+        let rust_bars: Vec<Bar> = bars.iter().map(|b| b.inner).collect();
+        let mut inset = InsetSeries::new(&rust_bars, InsetRect::new(x, y, width, height));
+        if let Some(color) = &color {
+            inset = inset.with_color(color);
+        }
+        if let Some(label) = &label {
+            inset = inset.with_label(label);
+        }
+        let chart = self.take_inner().inset(inset);
+        self.put_inner(chart);
+    }
+
+    /// Plot raw trade ticks as a line or area (`style` "line" or "area"): in
+    /// a pane under the bars, or as the main series when no bars are set
+    ///
+    /// `timestamps` and `prices` are parallel Float64Arrays. Ticks are
+    /// downsampled to four points per pixel column when drawn.
+    #[wasm_bindgen(js_name = setTicks)]
+    pub fn set_ticks(
+        &mut self,
+        timestamps: Vec<f64>,
+        prices: Vec<f64>,
+        style: Option<String>,
+        color: Option<String>,
+    ) -> Result<(), JsValue> {
+        if timestamps.len() != prices.len() {
+            return Err(JsValue::from_str("timestamps and prices differ in length"));
+        }
+        let Ok(style) = style.as_deref().unwrap_or("line").parse() else {
+            return Err(JsValue::from_str("unknown tick style"));
+        };
+        let ticks: Vec<(i64, f64)> = timestamps
+            .iter()
+            .zip(prices)
+            .map(|(&ts, price)| (ts as i64, price))
+            .collect();
+        let mut series = TickSeries::new(&ticks).with_style(style);
+        if let Some(color) = &color {
+            series = series.with_color(color);
+        }
+        let chart = self.take_inner().tick_series(series);
+        self.put_inner(chart);
+        Ok(())
+    }
+
+    /// Smooth the lines of indicator `id` with an SMA of `period` bars
+    #[wasm_bindgen(js_name = setSmoothing)]
+    pub fn set_smoothing(&mut self, id: &str, period: usize) {
+        let chart = self.take_inner().smoothing(id, period);
+        self.put_inner(chart);
+    }
+
+    /// Put indicator `id` in a legend group
+    #[wasm_bindgen(js_name = setIndicatorGroup)]
+    pub fn set_indicator_group(&mut self, id: &str, group: &str) {
+        let chart = self.take_inner().indicator_group(id, group);
+        self.put_inner(chart);
+    }
+
+    /// Scale the named vectors of subpane indicator `id` by their own
+    /// range: "auto", "symmetric", "price", or "fixed" from `min` to `max`;
+    /// `axis` labels it in a column right of the price scale
+    #[wasm_bindgen(js_name = scaleGroup)]
+    #[allow(clippy::too_many_arguments)]
+    pub fn scale_group(
+        &mut self,
+        id: &str,
+        group: &str,
+        vectors: Vec<String>,
+        range: &str,
+        min: Option<f64>,
+        max: Option<f64>,
+        axis: bool,
+    ) -> Result<(), JsValue> {
+        let range = match (range, min, max) {
+            ("auto", ..) => IndicatorRange::auto(),
+            ("symmetric", ..) => IndicatorRange::symmetric(),
+            ("price", ..) => IndicatorRange::price(),
+            ("fixed", Some(min), Some(max)) if min < max => IndicatorRange::fixed(min, max),
+            ("fixed", ..) => return Err(JsValue::from_str("a fixed range needs min below max")),
+            (other, ..) => return Err(JsValue::from_str(&format!("unknown range '{}'", other))),
+        };
+        let mut scale_group = ScaleGroup::new(group, range);
+        scale_group.axis = axis;
+        let names: Vec<&str> = vectors.iter().map(String::as_str).collect();
+        let chart = self.take_inner().scale_group(id, scale_group, &names);
+        self.put_inner(chart);
+        Ok(())
+    }
+
+    /// Show or hide every indicator in a group
+    #[wasm_bindgen(js_name = setGroupVisible)]
+    pub fn set_group_visible(&mut self, group: &str, visible: bool) {
+        self.inner
+            .as_mut()
+            .expect("Chart already consumed")
+            .set_group_visible(group, visible);
+    }
+
+    /// Toggle one indicator as a legend click would; a hidden one keeps a
+    /// greyed legend row
+    #[wasm_bindgen(js_name = setIndicatorVisible)]
+    pub fn set_indicator_visible(&mut self, id: &str, visible: bool) {
+        self.inner
+            .as_mut()
+            .expect("Chart already consumed")
+            .set_indicator_visible(id, visible);
+    }
+
+    /// Show or hide the main series
+    #[wasm_bindgen(js_name = setSeriesVisible)]
+    pub fn set_series_visible(&mut self, visible: bool) {
+        self.inner
+            .as_mut()
+            .expect("Chart already consumed")
+            .set_series_visible(visible);
+    }
+
+    /// Legend of overlay indicators, grouped by indicator group
+    #[wasm_bindgen(js_name = setLegend)]
+    pub fn set_legend(&mut self, show: bool) {
+        let chart = self.take_inner().legend(show);
+        self.put_inner(chart);
+    }
+
+    /// Set the instrument tick size (e.g. 0.25); price ticks snap to its
+    /// multiples and prices use its decimal precision
+    #[wasm_bindgen(js_name = setTickSize)]
+    pub fn set_tick_size(&mut self, tick_size: f64) -> Result<(), JsValue> {
+        if !(tick_size.is_finite() && tick_size > 0.0) {
+            return Err(JsValue::from_str("tick size must be positive"));
+        }
+        let chart = self.take_inner().tick_size(tick_size);
+        self.put_inner(chart);
+        Ok(())
+    }
+
+    /// Message shown while there are no bars (empty string for none)
+    #[wasm_bindgen(js_name = setEmptyMessage)]
+    pub fn set_empty_message(&mut self, message: &str) {
+        let chart = self.take_inner().empty_message(message);
+        self.put_inner(chart);
+    }
+
+    /// Draw placeholder candles while there are no bars; `animate` pulses
+    /// them with SVG animation
+    #[wasm_bindgen(js_name = setSkeleton)]
+    pub fn set_skeleton(&mut self, bar_count: usize, animate: bool) {
+        let chart = self.take_inner().skeleton(bar_count, animate);
+        self.put_inner(chart);
+    }
+
+    /// Overlay each "year" or "day" of a source ("open", "high", "low",
+    /// "close") as separate lines on a shared in-period axis
+    #[wasm_bindgen(js_name = setSeasonal)]
+    pub fn set_seasonal(&mut self, period: &str, source: &str) -> Result<(), JsValue> {
+        let (Ok(period), Ok(source)) = (period.parse(), source.parse()) else {
+            return Err(JsValue::from_str("unknown period or source"));
+        };
+        let chart = self.take_inner().seasonal(period, source);
+        self.put_inner(chart);
+        Ok(())
+    }
+
+    /// Register a custom drawing tool from a composite spec JSON
+    /// (type_id, display_name, kind, points, ops)
+    #[wasm_bindgen(js_name = registerPrimitive)]
+    pub fn register_primitive(&mut self, json: &str) -> Result<(), JsValue> {
+        let spec = CompositeSpec::from_json(json).map_err(|e| JsValue::from_str(&e.to_string()))?;
+        self.inner
+            .as_mut()
+            .expect("Chart already consumed")
+            .register_composite(spec)
+            .map_err(|e| JsValue::from_str(&e.to_string()))
+    }
+
+    /// Add any registered primitive by type ID, including custom ones;
+    /// `points` is flat `[bar, price, bar, price, ...]`. Give it an `id`
+    /// to be able to select it.
+    #[wasm_bindgen(js_name = addPrimitive)]
+    pub fn add_primitive(
+        &mut self,
+        type_id: &str,
+        points: Vec<f64>,
+        color: Option<String>,
+        id: Option<String>,
+    ) -> Result<(), JsValue> {
+        let pts: Vec<(f64, f64)> = points.chunks(2).map(|c| (c[0], c[1])).collect();
+        let mut primitive = PrimitiveConfig::new(type_id, pts);
+        if let Some(color) = color {
+            primitive = primitive.with_color(&color);
+        }
+        if let Some(id) = id {
+            primitive = primitive.with_id(&id);
+        }
+        self.add_checked(primitive)
+    }
+
+    /// Divergence from a price pivot to a pivot on the subpane of
+    /// `indicator_id`; `kind` is "regular_bullish" (default),
+    /// "regular_bearish", "hidden_bullish" or "hidden_bearish"
+    #[wasm_bindgen(js_name = indicatorDivergence)]
+    #[allow(clippy::too_many_arguments)]
+    pub fn indicator_divergence(
+        &mut self,
+        price_bar: f64,
+        price: f64,
+        indicator_bar: f64,
+        value: f64,
+        indicator_id: &str,
+        kind: Option<String>,
+    ) -> Result<(), JsValue> {
+        let kind = kind
+            .map_or(Ok(DivergenceType::RegularBullish), |k| k.parse())
+            .map_err(|_| JsValue::from_str("unknown divergence kind"))?;
+        let primitive = PrimitiveConfig::indicator_divergence(
+            (price_bar, price),
+            (indicator_bar, value),
+            indicator_id,
+            kind,
+        );
+        let chart = self.take_inner().primitive(primitive);
+        self.put_inner(chart);
+        Ok(())
+    }
+
+    /// Draw the primitive with `id` selected (halo and control-point handles)
+    #[wasm_bindgen(js_name = setPrimitiveSelected)]
+    pub fn set_primitive_selected(&mut self, id: &str, selected: bool) {
+        self.inner
+            .as_mut()
+            .expect("Chart already consumed")
+            .set_primitive_selected(id, selected);
+    }
+
+    /// Show or hide the primitive with `id`, keeping it on the chart
+    #[wasm_bindgen(js_name = setPrimitiveVisibility)]
+    pub fn set_primitive_visibility(&mut self, id: &str, visible: bool) {
+        self.inner
+            .as_mut()
+            .expect("Chart already consumed")
+            .set_primitive_visibility(id, visible);
+    }
+
+    /// Lock the primitive with `id` against hit-testing and selection
+    #[wasm_bindgen(js_name = setPrimitiveLocked)]
+    pub fn set_primitive_locked(&mut self, id: &str, locked: bool) {
+        self.inner
+            .as_mut()
+            .expect("Chart already consumed")
+            .set_primitive_locked(id, locked);
+    }
+
+    /// Let the primitive with `id` stretch the automatic price range
+    #[wasm_bindgen(js_name = setPrimitiveAutoscale)]
+    pub fn set_primitive_autoscale(&mut self, id: &str, autoscale: bool) {
+        self.inner
+            .as_mut()
+            .expect("Chart already consumed")
+            .set_primitive_autoscale(id, autoscale);
+    }
+
+    /// Shift primitive points by bars and price, all primitives or those
+    /// in `ids`; returns how many moved
+    #[wasm_bindgen(js_name = translatePrimitives)]
+    pub fn translate_primitives(
+        &mut self,
+        bar_delta: f64,
+        price_delta: f64,
+        ids: Option<Vec<String>>,
+    ) -> usize {
+        self.inner
+            .as_mut()
+            .expect("Chart already consumed")
+            .translate_primitives(bar_delta, price_delta, ids.as_deref())
+    }
+
+    /// Scale primitive points about `(originBar, originPrice)` by
+    /// `(barFactor, priceFactor)`, e.g. a price factor of 0.25 about 0
+    /// after a 4:1 split; returns how many changed
+    #[wasm_bindgen(js_name = scalePrimitives)]
+    pub fn scale_primitives(
+        &mut self,
+        origin_bar: f64,
+        origin_price: f64,
+        bar_factor: f64,
+        price_factor: f64,
+        ids: Option<Vec<String>>,
+    ) -> usize {
+        self.inner
+            .as_mut()
+            .expect("Chart already consumed")
+            .scale_primitives(
+                (origin_bar, origin_price),
+                (bar_factor, price_factor),
+                ids.as_deref(),
+            )
+    }
+
+    /// Index of the topmost unlocked, visible primitive at pixel `(x, y)`,
+    /// or `undefined`
+    #[wasm_bindgen(js_name = hitTest)]
+    pub fn hit_test(&self, x: f64, y: f64, tolerance: f64) -> Option<usize> {
+        self.inner.as_ref()?.hit_test(x, y, tolerance)
+    }
+
+    /// Attach a strategy described as JSON (indicators, signals, primitives, theme)
+    #[wasm_bindgen(js_name = setStrategyJson)]
+    pub fn set_strategy_json(&mut self, json: &str) -> Result<(), JsValue> {
+        let strategy = Strategy::from_json(json).map_err(|e| JsValue::from_str(&e.to_string()))?;
+        let chart = self.take_inner().strategy(strategy);
+        self.put_inner(chart);
+        Ok(())
+    }
+
     /// Apply dark theme
     #[wasm_bindgen(js_name = darkTheme)]
     pub fn dark_theme(&mut self) {
@@ -207,22 +926,58 @@ impl Chart {
         self.put_inner(chart);
     }
 
+    /// Apply cyberpunk theme (neon colors with a glow)
+    #[wasm_bindgen(js_name = cyberpunkTheme)]
+    pub fn cyberpunk_theme(&mut self) {
+        let chart = self.take_inner().theme(ThemeConfig::cyberpunk());
+        self.put_inner(chart);
+    }
+
+    /// Apply a JsRuntimeTheme wholesale: background, grid, scale text,
+    /// candle bodies, wicks and borders, and glow
+    #[wasm_bindgen(js_name = applyTheme)]
+    pub fn apply_theme(&mut self, theme: &JsRuntimeTheme) {
+        let chart = self.take_inner().theme(&theme.inner);
+        self.put_inner(chart);
+    }
+
+    /// Glow blur radius around series and drawings (0 = off)
+    #[wasm_bindgen(js_name = setGlow)]
+    pub fn set_glow(&mut self, radius: f64) {
+        let chart = self.take_inner().glow(radius);
+        self.put_inner(chart);
+    }
+
     // =========================================================================
     // Moving Average Indicators (9 types)
     // =========================================================================
 
-    /// Simple Moving Average
+    /// Simple Moving Average of an optional price source: "close", "open", "high", "low", "hl2", "hlc3" or "ohlc4"
     #[wasm_bindgen]
-    pub fn sma(&mut self, period: usize, color: &str) {
-        let chart = self.take_inner().sma(period, color);
+    pub fn sma(
+        &mut self,
+        period: usize,
+        color: &str,
+        source: Option<String>,
+    ) -> Result<(), JsValue> {
+        let source = Self::parse_source(source)?;
+        let chart = self.take_inner().sma_on(period, color, source);
         self.put_inner(chart);
+        Ok(())
     }
 
-    /// Exponential Moving Average
+    /// Exponential Moving Average of an optional price source
     #[wasm_bindgen]
-    pub fn ema(&mut self, period: usize, color: &str) {
-        let chart = self.take_inner().ema(period, color);
+    pub fn ema(
+        &mut self,
+        period: usize,
+        color: &str,
+        source: Option<String>,
+    ) -> Result<(), JsValue> {
+        let source = Self::parse_source(source)?;
+        let chart = self.take_inner().ema_on(period, color, source);
         self.put_inner(chart);
+        Ok(())
     }
 
     /// Weighted Moving Average
@@ -301,10 +1056,38 @@ impl Chart {
     // Band Indicators (5 types)
     // =========================================================================
 
-    /// Bollinger Bands
-    #[wasm_bindgen]
-    pub fn bollinger(&mut self, period: usize, multiplier: f64) {
-        let chart = self.take_inner().bollinger(period, multiplier);
+    /// Bollinger Bands of an optional price source
+    #[wasm_bindgen]
+    pub fn bollinger(
+        &mut self,
+        period: usize,
+        multiplier: f64,
+        source: Option<String>,
+    ) -> Result<(), JsValue> {
+        let source = Self::parse_source(source)?;
+        let chart = self.take_inner().bollinger_on(period, multiplier, source);
+        self.put_inner(chart);
+        Ok(())
+    }
+
+    /// Bollinger Bands with the middle line and fill toggled, and dots
+    /// on bars closing outside the bands
+    #[wasm_bindgen(js_name = bollingerStyled)]
+    pub fn bollinger_styled(
+        &mut self,
+        period: usize,
+        multiplier: f64,
+        show_middle: bool,
+        fill: bool,
+        outside_markers: bool,
+    ) {
+        let mut style = BandStyle::bollinger()
+            .show_middle(show_middle)
+            .outside_markers(outside_markers);
+        style.fill = fill;
+        let chart = self
+            .take_inner()
+            .bollinger_styled(period, multiplier, &style);
         self.put_inner(chart);
     }
 
@@ -348,18 +1131,28 @@ impl Chart {
     // Oscillators / Momentum Indicators
     // =========================================================================
 
-    /// Relative Strength Index
+    /// Relative Strength Index of an optional price source
     #[wasm_bindgen]
-    pub fn rsi(&mut self, period: usize) {
-        let chart = self.take_inner().rsi(period);
+    pub fn rsi(&mut self, period: usize, source: Option<String>) -> Result<(), JsValue> {
+        let source = Self::parse_source(source)?;
+        let chart = self.take_inner().rsi_on(period, source);
         self.put_inner(chart);
+        Ok(())
     }
 
-    /// MACD
+    /// MACD of an optional price source
     #[wasm_bindgen]
-    pub fn macd(&mut self, fast: usize, slow: usize, signal: usize) {
-        let chart = self.take_inner().macd(fast, slow, signal);
+    pub fn macd(
+        &mut self,
+        fast: usize,
+        slow: usize,
+        signal: usize,
+        source: Option<String>,
+    ) -> Result<(), JsValue> {
+        let source = Self::parse_source(source)?;
+        let chart = self.take_inner().macd_on(fast, slow, signal, source);
         self.put_inner(chart);
+        Ok(())
     }
 
     /// MACD with default settings (12, 26, 9)
@@ -556,9 +1349,7 @@ impl Chart {
     /// Average Directional Index
     #[wasm_bindgen]
     pub fn adx(&mut self, period: usize) {
-        let id = format!("adx_{}", period);
-        let indicator = Indicator::adx(&id, period as u32);
-        let chart = self.take_inner().indicator(indicator);
+        let chart = self.take_inner().adx(period);
         self.put_inner(chart);
     }
 
@@ -607,12 +1398,16 @@ impl Chart {
         self.put_inner(chart);
     }
 
-    /// ZigZag
+    /// ZigZag between swing highs and lows reversing by `deviation` percent,
+    /// pivots labeled "none" (default), "price" or "change"
     #[wasm_bindgen]
-    pub fn zigzag(&mut self, deviation: f64) {
-        let indicator = Indicator::zigzag("zigzag", deviation);
-        let chart = self.take_inner().indicator(indicator);
+    pub fn zigzag(&mut self, deviation: f64, labels: Option<String>) -> Result<(), JsValue> {
+        let labels: PivotLabels = labels
+            .map_or(Ok(PivotLabels::None), |s| s.parse())
+            .map_err(|_| JsValue::from_str("unknown pivot labels"))?;
+        let chart = self.take_inner().zigzag_labeled(deviation, labels);
         self.put_inner(chart);
+        Ok(())
     }
 
     /// TRIX
@@ -632,20 +1427,21 @@ impl Chart {
         self.put_inner(chart);
     }
 
-    /// Parabolic SAR
+    /// Parabolic SAR dots colored by trend (step 0.02, max step 0.2 by default)
     #[wasm_bindgen]
-    pub fn psar(&mut self) {
-        let indicator = Indicator::psar("psar");
-        let chart = self.take_inner().indicator(indicator);
+    pub fn psar(&mut self, step: Option<f64>, max_step: Option<f64>) {
+        let chart = self
+            .take_inner()
+            .psar(step.unwrap_or(0.02), max_step.unwrap_or(0.2));
         self.put_inner(chart);
     }
 
-    /// Supertrend
+    /// Supertrend stepped line colored by trend, optionally filled to the close
     #[wasm_bindgen]
-    pub fn supertrend(&mut self, period: usize, multiplier: f64) {
-        let id = format!("supertrend_{}", period);
-        let indicator = Indicator::supertrend(&id, period as u32, multiplier);
-        let chart = self.take_inner().indicator(indicator);
+    pub fn supertrend(&mut self, period: usize, multiplier: f64, fill: Option<bool>) {
+        let chart =
+            self.take_inner()
+                .supertrend_with_fill(period, multiplier, fill.unwrap_or(false));
         self.put_inner(chart);
     }
 
@@ -668,28 +1464,34 @@ impl Chart {
         self.put_inner(chart);
     }
 
+    /// Volume with sell volume at the base and buy volume stacked on top,
+    /// per bar
+    ///
+    /// Bars missing either side fall back to the plain volume bar.
+    #[wasm_bindgen(js_name = setVolumeBreakdown)]
+    pub fn set_volume_breakdown(&mut self, buy: Vec<f64>, sell: Vec<f64>) {
+        let chart = self.take_inner().volume_breakdown(&buy, &sell);
+        self.put_inner(chart);
+    }
+
     /// On Balance Volume
     #[wasm_bindgen]
     pub fn obv(&mut self) {
-        let indicator = Indicator::obv("obv");
-        let chart = self.take_inner().indicator(indicator);
+        let chart = self.take_inner().obv();
         self.put_inner(chart);
     }
 
     /// Accumulation/Distribution Line
     #[wasm_bindgen(js_name = adLine)]
     pub fn ad_line(&mut self) {
-        let indicator = Indicator::ad_line("ad");
-        let chart = self.take_inner().indicator(indicator);
+        let chart = self.take_inner().ad_line();
         self.put_inner(chart);
     }
 
     /// Chaikin Money Flow
     #[wasm_bindgen]
     pub fn cmf(&mut self, period: usize) {
-        let id = format!("cmf_{}", period);
-        let indicator = Indicator::cmf(&id, period as u32);
-        let chart = self.take_inner().indicator(indicator);
+        let chart = self.take_inner().cmf(period);
         self.put_inner(chart);
     }
 
@@ -746,9 +1548,7 @@ impl Chart {
     /// Money Flow Index
     #[wasm_bindgen]
     pub fn mfi(&mut self, period: usize) {
-        let id = format!("mfi_{}", period);
-        let indicator = Indicator::mfi(&id, period as u32);
-        let chart = self.take_inner().indicator(indicator);
+        let chart = self.take_inner().mfi(period);
         self.put_inner(chart);
     }
 
@@ -921,24 +1721,42 @@ impl Chart {
         self.put_inner(chart);
     }
 
-    /// Take profit signal
+    /// Take profit signal, optionally with a short level line and price label
     #[wasm_bindgen(js_name = takeProfitSignal)]
-    pub fn take_profit_signal(&mut self, bar_index: usize, price: f64, label: Option<String>) {
+    pub fn take_profit_signal(
+        &mut self,
+        bar_index: usize,
+        price: f64,
+        label: Option<String>,
+        level_line: Option<bool>,
+    ) {
         let mut signal = SignalConfig::take_profit(bar_index, price);
         if let Some(l) = label {
             signal = signal.with_label(&l);
         }
+        if level_line.unwrap_or(false) {
+            signal = signal.with_level_line();
+        }
         let chart = self.take_inner().signal(signal);
         self.put_inner(chart);
     }
 
-    /// Stop loss signal
+    /// Stop loss signal, optionally with a short level line and price label
     #[wasm_bindgen(js_name = stopLossSignal)]
-    pub fn stop_loss_signal(&mut self, bar_index: usize, price: f64, label: Option<String>) {
+    pub fn stop_loss_signal(
+        &mut self,
+        bar_index: usize,
+        price: f64,
+        label: Option<String>,
+        level_line: Option<bool>,
+    ) {
         let mut signal = SignalConfig::stop_loss(bar_index, price);
         if let Some(l) = label {
             signal = signal.with_label(&l);
         }
+        if level_line.unwrap_or(false) {
+            signal = signal.with_level_line();
+        }
         let chart = self.take_inner().signal(signal);
         self.put_inner(chart);
     }
@@ -971,6 +1789,14 @@ impl Chart {
         self.put_inner(chart);
     }
 
+    /// Vertical line at a timestamp, which may lie after the last bar
+    #[wasm_bindgen(js_name = verticalLineAt)]
+    pub fn vertical_line_at(&mut self, timestamp: i64) {
+        let primitive = PrimitiveConfig::vertical_line_at(timestamp);
+        let chart = self.take_inner().primitive(primitive);
+        self.put_inner(chart);
+    }
+
     /// Trend line between two points
     #[wasm_bindgen(js_name = trendLine)]
     pub fn trend_line(&mut self, start_x: f64, start_y: f64, end_x: f64, end_y: f64) {
@@ -1039,12 +1865,29 @@ impl Chart {
         self.put_inner(chart);
     }
 
-    /// Regression trend channel
+    /// Regression trend channel fitted to the bars between the two points
+    ///
+    /// Bands sit `multiplier` (default 2) deviations from the regression
+    /// line; `deviation` is "std_dev" (default) or "std_error".
     #[wasm_bindgen(js_name = regressionTrend)]
-    pub fn regression_trend(&mut self, x1: f64, y1: f64, x2: f64, y2: f64) {
-        let primitive = PrimitiveConfig::regression_trend((x1, y1), (x2, y2));
-        let chart = self.take_inner().primitive(primitive);
-        self.put_inner(chart);
+    #[allow(clippy::too_many_arguments)]
+    pub fn regression_trend(
+        &mut self,
+        x1: f64,
+        y1: f64,
+        x2: f64,
+        y2: f64,
+        multiplier: Option<f64>,
+        deviation: Option<String>,
+    ) -> Result<(), JsValue> {
+        let deviation = deviation
+            .map_or(Ok(BandDeviation::StdDev), |d| d.parse())
+            .map_err(|_| JsValue::from_str("unknown band deviation"))?;
+        let primitive = PrimitiveConfig::regression_trend((x1, y1), (x2, y2))
+            .with_band(multiplier.unwrap_or(2.0), deviation);
+        let chart = self.take_inner().primitive(primitive);
+        self.put_inner(chart);
+        Ok(())
     }
 
     /// Flat top/bottom channel
@@ -1057,11 +1900,9 @@ impl Chart {
 
     /// Disjoint channel
     #[wasm_bindgen(js_name = disjointChannel)]
-    pub fn disjoint_channel(&mut self, points: Vec<f64>) {
+    pub fn disjoint_channel(&mut self, points: Vec<f64>) -> Result<(), JsValue> {
         let pts: Vec<(f64, f64)> = points.chunks(2).map(|c| (c[0], c[1])).collect();
-        let primitive = PrimitiveConfig::disjoint_channel(pts);
-        let chart = self.take_inner().primitive(primitive);
-        self.put_inner(chart);
+        self.add_checked(PrimitiveConfig::disjoint_channel(pts))
     }
 
     // =========================================================================
@@ -1110,20 +1951,16 @@ impl Chart {
 
     /// Polyline
     #[wasm_bindgen]
-    pub fn polyline(&mut self, points: Vec<f64>) {
+    pub fn polyline(&mut self, points: Vec<f64>) -> Result<(), JsValue> {
         let pts: Vec<(f64, f64)> = points.chunks(2).map(|c| (c[0], c[1])).collect();
-        let primitive = PrimitiveConfig::polyline(pts);
-        let chart = self.take_inner().primitive(primitive);
-        self.put_inner(chart);
+        self.add_checked(PrimitiveConfig::polyline(pts))
     }
 
     /// Path (closed polygon)
     #[wasm_bindgen]
-    pub fn path(&mut self, points: Vec<f64>) {
+    pub fn path(&mut self, points: Vec<f64>) -> Result<(), JsValue> {
         let pts: Vec<(f64, f64)> = points.chunks(2).map(|c| (c[0], c[1])).collect();
-        let primitive = PrimitiveConfig::path(pts);
-        let chart = self.take_inner().primitive(primitive);
-        self.put_inner(chart);
+        self.add_checked(PrimitiveConfig::path(pts))
     }
 
     /// Rotated rectangle
@@ -1136,20 +1973,16 @@ impl Chart {
 
     /// Curve
     #[wasm_bindgen]
-    pub fn curve(&mut self, points: Vec<f64>) {
+    pub fn curve(&mut self, points: Vec<f64>) -> Result<(), JsValue> {
         let pts: Vec<(f64, f64)> = points.chunks(2).map(|c| (c[0], c[1])).collect();
-        let primitive = PrimitiveConfig::curve(pts);
-        let chart = self.take_inner().primitive(primitive);
-        self.put_inner(chart);
+        self.add_checked(PrimitiveConfig::curve(pts))
     }
 
     /// Double curve
     #[wasm_bindgen(js_name = doubleCurve)]
-    pub fn double_curve(&mut self, points: Vec<f64>) {
+    pub fn double_curve(&mut self, points: Vec<f64>) -> Result<(), JsValue> {
         let pts: Vec<(f64, f64)> = points.chunks(2).map(|c| (c[0], c[1])).collect();
-        let primitive = PrimitiveConfig::double_curve(pts);
-        let chart = self.take_inner().primitive(primitive);
-        self.put_inner(chart);
+        self.add_checked(PrimitiveConfig::double_curve(pts))
     }
 
     // =========================================================================
@@ -1157,11 +1990,40 @@ impl Chart {
     // =========================================================================
 
     /// Fibonacci retracement
+    ///
+    /// `labels` ("ratio", "ratio_price" or "ratio_percent") labels each
+    /// level, overriding the chart-wide `setFibLabels`.
     #[wasm_bindgen(js_name = fibRetracement)]
-    pub fn fib_retracement(&mut self, x1: f64, y1: f64, x2: f64, y2: f64) {
-        let primitive = PrimitiveConfig::fib_retracement((x1, y1), (x2, y2));
+    pub fn fib_retracement(
+        &mut self,
+        x1: f64,
+        y1: f64,
+        x2: f64,
+        y2: f64,
+        labels: Option<String>,
+    ) -> Result<(), JsValue> {
+        let mut primitive = PrimitiveConfig::fib_retracement((x1, y1), (x2, y2));
+        if let Some(labels) = labels {
+            let mode: FibLabelMode = labels
+                .parse()
+                .map_err(|_| JsValue::from_str("unknown fib label mode"))?;
+            primitive = primitive.with_fib_labels(mode);
+        }
         let chart = self.take_inner().primitive(primitive);
         self.put_inner(chart);
+        Ok(())
+    }
+
+    /// Label the levels of every Fibonacci drawing: "ratio", "ratio_price"
+    /// (`0.618  123.45`) or "ratio_percent" (`0.618  61.8%`)
+    #[wasm_bindgen(js_name = setFibLabels)]
+    pub fn set_fib_labels(&mut self, mode: &str) -> Result<(), JsValue> {
+        let mode: FibLabelMode = mode
+            .parse()
+            .map_err(|_| JsValue::from_str("unknown fib label mode"))?;
+        let chart = self.take_inner().fib_labels(mode);
+        self.put_inner(chart);
+        Ok(())
     }
 
     /// Fibonacci extension
@@ -1322,38 +2184,30 @@ impl Chart {
 
     /// XABCD Pattern
     #[wasm_bindgen(js_name = xabcdPattern)]
-    pub fn xabcd_pattern(&mut self, points: Vec<f64>) {
+    pub fn xabcd_pattern(&mut self, points: Vec<f64>) -> Result<(), JsValue> {
         let pts: Vec<(f64, f64)> = points.chunks(2).map(|c| (c[0], c[1])).collect();
-        let primitive = PrimitiveConfig::xabcd_pattern(pts);
-        let chart = self.take_inner().primitive(primitive);
-        self.put_inner(chart);
+        self.add_checked(PrimitiveConfig::xabcd_pattern(pts))
     }
 
     /// Cypher Pattern
     #[wasm_bindgen(js_name = cypherPattern)]
-    pub fn cypher_pattern(&mut self, points: Vec<f64>) {
+    pub fn cypher_pattern(&mut self, points: Vec<f64>) -> Result<(), JsValue> {
         let pts: Vec<(f64, f64)> = points.chunks(2).map(|c| (c[0], c[1])).collect();
-        let primitive = PrimitiveConfig::cypher_pattern(pts);
-        let chart = self.take_inner().primitive(primitive);
-        self.put_inner(chart);
+        self.add_checked(PrimitiveConfig::cypher_pattern(pts))
     }
 
     /// Head and Shoulders
     #[wasm_bindgen(js_name = headShoulders)]
-    pub fn head_shoulders(&mut self, points: Vec<f64>) {
+    pub fn head_shoulders(&mut self, points: Vec<f64>) -> Result<(), JsValue> {
         let pts: Vec<(f64, f64)> = points.chunks(2).map(|c| (c[0], c[1])).collect();
-        let primitive = PrimitiveConfig::head_shoulders(pts);
-        let chart = self.take_inner().primitive(primitive);
-        self.put_inner(chart);
+        self.add_checked(PrimitiveConfig::head_shoulders(pts))
     }
 
     /// ABCD Pattern
     #[wasm_bindgen(js_name = abcdPattern)]
-    pub fn abcd_pattern(&mut self, points: Vec<f64>) {
+    pub fn abcd_pattern(&mut self, points: Vec<f64>) -> Result<(), JsValue> {
         let pts: Vec<(f64, f64)> = points.chunks(2).map(|c| (c[0], c[1])).collect();
-        let primitive = PrimitiveConfig::abcd_pattern(pts);
-        let chart = self.take_inner().primitive(primitive);
-        self.put_inner(chart);
+        self.add_checked(PrimitiveConfig::abcd_pattern(pts))
     }
 
     /// Triangle Pattern
@@ -1366,11 +2220,9 @@ impl Chart {
 
     /// Three Drives
     #[wasm_bindgen(js_name = threeDrives)]
-    pub fn three_drives(&mut self, points: Vec<f64>) {
+    pub fn three_drives(&mut self, points: Vec<f64>) -> Result<(), JsValue> {
         let pts: Vec<(f64, f64)> = points.chunks(2).map(|c| (c[0], c[1])).collect();
-        let primitive = PrimitiveConfig::three_drives(pts);
-        let chart = self.take_inner().primitive(primitive);
-        self.put_inner(chart);
+        self.add_checked(PrimitiveConfig::three_drives(pts))
     }
 
     // =========================================================================
@@ -1379,47 +2231,37 @@ impl Chart {
 
     /// Elliott Impulse Wave
     #[wasm_bindgen(js_name = elliottImpulse)]
-    pub fn elliott_impulse(&mut self, points: Vec<f64>) {
+    pub fn elliott_impulse(&mut self, points: Vec<f64>) -> Result<(), JsValue> {
         let pts: Vec<(f64, f64)> = points.chunks(2).map(|c| (c[0], c[1])).collect();
-        let primitive = PrimitiveConfig::elliott_impulse(pts);
-        let chart = self.take_inner().primitive(primitive);
-        self.put_inner(chart);
+        self.add_checked(PrimitiveConfig::elliott_impulse(pts))
     }
 
     /// Elliott Correction Wave
     #[wasm_bindgen(js_name = elliottCorrection)]
-    pub fn elliott_correction(&mut self, points: Vec<f64>) {
+    pub fn elliott_correction(&mut self, points: Vec<f64>) -> Result<(), JsValue> {
         let pts: Vec<(f64, f64)> = points.chunks(2).map(|c| (c[0], c[1])).collect();
-        let primitive = PrimitiveConfig::elliott_correction(pts);
-        let chart = self.take_inner().primitive(primitive);
-        self.put_inner(chart);
+        self.add_checked(PrimitiveConfig::elliott_correction(pts))
     }
 
     /// Elliott Triangle
     #[wasm_bindgen(js_name = elliottTriangle)]
-    pub fn elliott_triangle(&mut self, points: Vec<f64>) {
+    pub fn elliott_triangle(&mut self, points: Vec<f64>) -> Result<(), JsValue> {
         let pts: Vec<(f64, f64)> = points.chunks(2).map(|c| (c[0], c[1])).collect();
-        let primitive = PrimitiveConfig::elliott_triangle(pts);
-        let chart = self.take_inner().primitive(primitive);
-        self.put_inner(chart);
+        self.add_checked(PrimitiveConfig::elliott_triangle(pts))
     }
 
     /// Elliott Double Combo
     #[wasm_bindgen(js_name = elliottDoubleCombo)]
-    pub fn elliott_double_combo(&mut self, points: Vec<f64>) {
+    pub fn elliott_double_combo(&mut self, points: Vec<f64>) -> Result<(), JsValue> {
         let pts: Vec<(f64, f64)> = points.chunks(2).map(|c| (c[0], c[1])).collect();
-        let primitive = PrimitiveConfig::elliott_double_combo(pts);
-        let chart = self.take_inner().primitive(primitive);
-        self.put_inner(chart);
+        self.add_checked(PrimitiveConfig::elliott_double_combo(pts))
     }
 
     /// Elliott Triple Combo
     #[wasm_bindgen(js_name = elliottTripleCombo)]
-    pub fn elliott_triple_combo(&mut self, points: Vec<f64>) {
+    pub fn elliott_triple_combo(&mut self, points: Vec<f64>) -> Result<(), JsValue> {
         let pts: Vec<(f64, f64)> = points.chunks(2).map(|c| (c[0], c[1])).collect();
-        let primitive = PrimitiveConfig::elliott_triple_combo(pts);
-        let chart = self.take_inner().primitive(primitive);
-        self.put_inner(chart);
+        self.add_checked(PrimitiveConfig::elliott_triple_combo(pts))
     }
 
     // =========================================================================
@@ -1606,6 +2448,36 @@ impl Chart {
         self.put_inner(chart);
     }
 
+    /// Forecast path: one median, upper and lower value per bar after
+    /// `start_bar`, drawn as a dashed median in a shaded cone
+    #[wasm_bindgen(js_name = forecastPath)]
+    #[allow(clippy::too_many_arguments)]
+    pub fn forecast_path(
+        &mut self,
+        start_bar: f64,
+        median: Vec<f64>,
+        upper: Vec<f64>,
+        lower: Vec<f64>,
+        color: Option<String>,
+        fill_color: Option<String>,
+        fill_opacity: Option<f64>,
+    ) -> Result<(), JsValue> {
+        if median.len() != upper.len() || median.len() != lower.len() {
+            return Err(JsValue::from_str(
+                "median, upper and lower must have the same length",
+            ));
+        }
+        let mut primitive = PrimitiveConfig::forecast_path(start_bar, &median, &upper, &lower);
+        if let Some(color) = color {
+            primitive = primitive.with_color(&color);
+        }
+        primitive.fill_color = fill_color;
+        primitive.fill_opacity = fill_opacity;
+        let chart = self.take_inner().primitive(primitive);
+        self.put_inner(chart);
+        Ok(())
+    }
+
     /// Bars pattern
     #[wasm_bindgen(js_name = barsPattern)]
     pub fn bars_pattern(&mut self, x1: f64, y1: f64, x2: f64, y2: f64) {
@@ -1692,26 +2564,146 @@ impl Chart {
 
     /// Brush
     #[wasm_bindgen]
-    pub fn brush(&mut self, points: Vec<f64>) {
+    pub fn brush(&mut self, points: Vec<f64>) -> Result<(), JsValue> {
         let pts: Vec<(f64, f64)> = points.chunks(2).map(|c| (c[0], c[1])).collect();
-        let primitive = PrimitiveConfig::brush(pts);
-        let chart = self.take_inner().primitive(primitive);
-        self.put_inner(chart);
+        self.add_checked(PrimitiveConfig::brush(pts))
     }
 
     /// Highlighter
     #[wasm_bindgen]
-    pub fn highlighter(&mut self, points: Vec<f64>) {
+    pub fn highlighter(&mut self, points: Vec<f64>) -> Result<(), JsValue> {
         let pts: Vec<(f64, f64)> = points.chunks(2).map(|c| (c[0], c[1])).collect();
-        let primitive = PrimitiveConfig::highlighter(pts);
-        let chart = self.take_inner().primitive(primitive);
-        self.put_inner(chart);
+        self.add_checked(PrimitiveConfig::highlighter(pts))
     }
 
     // =========================================================================
     // Rendering
     // =========================================================================
 
+    /// Computed indicator vectors as `{ name, values: Float64Array }[]`
+    ///
+    /// Returns `undefined` for an unknown id and an empty array before bars are set.
+    #[wasm_bindgen(js_name = indicatorValues)]
+    pub fn indicator_values(&self, id: &str) -> JsValue {
+        let Some(vectors) = self.inner.as_ref().and_then(|c| c.indicator_values(id)) else {
+            return JsValue::UNDEFINED;
+        };
+        let result = js_sys::Array::new();
+        for vector in vectors {
+            let entry = js_sys::Object::new();
+            let values = js_sys::Float64Array::from(vector.values.as_slice());
+            let _ = js_sys::Reflect::set(&entry, &"name".into(), &vector.name.as_str().into());
+            let _ = js_sys::Reflect::set(&entry, &"values".into(), &values);
+            result.push(&entry);
+        }
+        result.into()
+    }
+
+    /// Labeled indicator values at a bar as `[label, value][]`
+    ///
+    /// Returns `undefined` for an unknown id; missing values are `NaN`.
+    #[wasm_bindgen(js_name = indicatorValuesAt)]
+    pub fn indicator_values_at(&self, id: &str, bar_index: usize) -> JsValue {
+        self.inner
+            .as_ref()
+            .and_then(|c| c.indicator_values_at(id, bar_index))
+            .and_then(|values| serde_wasm_bindgen::to_value(&values).ok())
+            .unwrap_or(JsValue::UNDEFINED)
+    }
+
+    /// Settings of an indicator for a dialog:
+    /// `[{id, name, prop_type, value, category, order, readonly, tooltip}]`;
+    /// `undefined` for an unknown id
+    #[wasm_bindgen(js_name = indicatorProperties)]
+    pub fn indicator_properties(&self, id: &str) -> JsValue {
+        self.inner
+            .as_ref()
+            .and_then(|c| c.indicator_properties(id))
+            .and_then(|properties| serde_wasm_bindgen::to_value(&properties).ok())
+            .unwrap_or(JsValue::UNDEFINED)
+    }
+
+    /// Change one indicator setting by its property id, e.g.
+    /// `updateIndicator("sma_20", "period", 50)`; new inputs recompute the
+    /// indicator from the chart's bars
+    #[wasm_bindgen(js_name = updateIndicator)]
+    pub fn update_indicator(
+        &mut self,
+        id: &str,
+        name: &str,
+        value: JsValue,
+    ) -> Result<(), JsValue> {
+        let value = if let Some(b) = value.as_bool() {
+            PropertyValue::Boolean(b)
+        } else if let Some(n) = value.as_f64() {
+            PropertyValue::Number(n)
+        } else if let Some(s) = value.as_string() {
+            PropertyValue::String(s)
+        } else {
+            return Err(JsValue::from_str(
+                "Indicator property values are booleans, numbers or strings",
+            ));
+        };
+        self.inner
+            .as_mut()
+            .expect("Chart already consumed")
+            .update_indicator(id, name, value)
+            .map_err(|e| JsValue::from_str(&e.to_string()))
+    }
+
+    /// Drawing tools available on this chart, for building a tool palette:
+    /// `[{type_id, name, category, min_points, max_points, supports_text, has_levels}]`
+    #[wasm_bindgen(js_name = listPrimitives)]
+    pub fn list_primitives(&self) -> JsValue {
+        self.inner
+            .as_ref()
+            .and_then(|c| serde_wasm_bindgen::to_value(&c.list_primitives()).ok())
+            .unwrap_or(JsValue::UNDEFINED)
+    }
+
+    /// Tooltip payload for a bar (time, OHLCV strings, indicator values with
+    /// labels and colors, signals); `undefined` when out of range
+    #[wasm_bindgen(js_name = barInfo)]
+    pub fn bar_info(&self, index: usize) -> JsValue {
+        self.inner
+            .as_ref()
+            .and_then(|c| c.bar_info(index))
+            .and_then(|info| serde_wasm_bindgen::to_value(&info).ok())
+            .unwrap_or(JsValue::UNDEFINED)
+    }
+
+    /// Legend contents (bar OHLCV, series name and type, overlay indicators
+    /// in legend order with their values and colors) at the hovered bar or
+    /// the last one; `undefined` without bars
+    #[wasm_bindgen(js_name = legendData)]
+    pub fn legend_data(&self) -> JsValue {
+        self.inner
+            .as_ref()
+            .and_then(|c| c.legend_data())
+            .and_then(|legend| serde_wasm_bindgen::to_value(&legend).ok())
+            .unwrap_or(JsValue::UNDEFINED)
+    }
+
+    /// Configuration problems that render silently wrong (indicators
+    /// without values, duplicate ids, oversized subpanes, bad colors,
+    /// out-of-range bar indices) as messages
+    #[wasm_bindgen]
+    pub fn validate(&self) -> Vec<String> {
+        self.inner
+            .as_ref()
+            .map(|c| c.validate().iter().map(|w| w.to_string()).collect())
+            .unwrap_or_default()
+    }
+
+    /// Plain-text summary of the chart for screen readers and alt text
+    #[wasm_bindgen]
+    pub fn describe(&self) -> String {
+        self.inner
+            .as_ref()
+            .map(|c| c.describe())
+            .unwrap_or_default()
+    }
+
     /// Render chart to SVG string
     #[wasm_bindgen(js_name = renderSvg)]
     pub fn render_svg(&self) -> String {
@@ -1720,6 +2712,31 @@ impl Chart {
             .map(|c| c.render_svg())
             .unwrap_or_default()
     }
+
+    /// One SVG per replay step as `string[]`, drawing bars up to each index
+    /// in `start..end`
+    #[wasm_bindgen(js_name = renderFrames)]
+    pub fn render_frames(&mut self, start: usize, end: usize, lock_scale: bool) -> JsValue {
+        let chart = self.take_inner().replay_lock_scale(lock_scale);
+        let result = js_sys::Array::new();
+        for frame in chart.render_frames(start..end) {
+            result.push(&frame.into());
+        }
+        self.put_inner(chart);
+        result.into()
+    }
+
+    /// Parts of the SVG that changed since the last call, as
+    /// `{ id, svg }[]`: set the `innerHTML` of the `<g>` with that `id`,
+    /// or replace the whole document when `id` is unset (always the case on
+    /// the first call)
+    #[wasm_bindgen(js_name = renderDirty)]
+    pub fn render_dirty(&mut self) -> JsValue {
+        self.inner
+            .as_mut()
+            .and_then(|c| serde_wasm_bindgen::to_value(&c.render_dirty()).ok())
+            .unwrap_or(JsValue::UNDEFINED)
+    }
 }
 
 // =============================================================================
@@ -1775,6 +2792,26 @@ impl JsViewport {
     pub fn scroll_to_start(&mut self) {
         self.inner.scroll_to_start();
     }
+
+    #[wasm_bindgen(js_name = scrollBy)]
+    pub fn scroll_by(&mut self, bars: f64) {
+        self.inner.scroll_by(bars);
+    }
+
+    #[wasm_bindgen(js_name = scrollOffset)]
+    pub fn scroll_offset(&self) -> f64 {
+        self.inner.scroll_offset()
+    }
+
+    #[wasm_bindgen(js_name = setScrollOffset)]
+    pub fn set_scroll_offset(&mut self, offset: f64) {
+        self.inner.set_scroll_offset(offset);
+    }
+
+    #[wasm_bindgen(js_name = setRightOffset)]
+    pub fn set_right_offset(&mut self, bars: f64) {
+        self.inner.set_right_offset(bars);
+    }
 }
 
 // =============================================================================
@@ -2016,7 +3053,7 @@ impl JsRuntimeTheme {
         }
     }
 
-    /// Deserialize from JSON string
+    /// Deserialize from JSON string, migrating themes saved by older versions
     #[wasm_bindgen(js_name = fromJson)]
     pub fn from_json(json: &str) -> Option<JsRuntimeTheme> {
         RuntimeTheme::from_json(json).map(|inner| Self { inner })
@@ -2043,8 +3080,19 @@ impl JsRuntimeTheme {
             .collect()
     }
 
+    /// Schema version written by toJson
+    #[wasm_bindgen(js_name = currentSchemaVersion)]
+    pub fn current_schema_version() -> u32 {
+        RuntimeTheme::CURRENT_SCHEMA_VERSION
+    }
+
     // === Basic properties ===
 
+    #[wasm_bindgen(getter, js_name = schemaVersion)]
+    pub fn schema_version(&self) -> u32 {
+        self.inner.schema_version
+    }
+
     #[wasm_bindgen(getter)]
     pub fn name(&self) -> String {
         self.inner.name.clone()
@@ -2216,26 +3264,259 @@ impl JsRuntimeTheme {
 // JsChartConfig - Low-level configuration
 // =============================================================================
 
-/// Low-level chart configuration.
+/// Complete chart configuration, built field by field or read from a
+/// JSON object, and rendered directly with `render(bars)`.
 #[wasm_bindgen]
 pub struct JsChartConfig {
-    #[allow(dead_code)]
     inner: RustChartConfig,
 }
 
+/// Read a config from a JSON string or a plain object; errors name the
+/// offending field, e.g. "indicators[1].placement: unknown variant ..."
+fn config_from_js(value: &JsValue) -> Result<RustChartConfig, JsValue> {
+    let json = match value.as_string() {
+        Some(json) => json,
+        None => js_sys::JSON::stringify(value)
+            .map(String::from)
+            .map_err(|_| JsValue::from_str("config is not JSON-serializable"))?,
+    };
+    RustChartConfig::from_json(&json).map_err(|e| JsValue::from_str(&e.to_string()))
+}
+
 #[wasm_bindgen]
 impl JsChartConfig {
     #[wasm_bindgen(constructor)]
-    pub fn new() -> Self {
+    pub fn new(width: Option<u32>, height: Option<u32>) -> Self {
         Self {
-            inner: RustChartConfig::default(),
+            inner: RustChart::new(width.unwrap_or(800), height.unwrap_or(600))
+                .build()
+                .0,
         }
     }
+
+    /// Read a config from a plain object or JSON string, with defaults for
+    /// the fields it leaves out
+    #[wasm_bindgen(js_name = fromObject)]
+    pub fn from_object(config: JsValue) -> Result<JsChartConfig, JsValue> {
+        Ok(Self {
+            inner: config_from_js(&config)?,
+        })
+    }
+
+    /// Serialize to a JSON string, readable back with fromObject
+    #[wasm_bindgen(js_name = toJson)]
+    pub fn to_json(&self) -> String {
+        self.inner.to_json()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn width(&self) -> u32 {
+        self.inner.width
+    }
+
+    #[wasm_bindgen(setter)]
+    pub fn set_width(&mut self, width: u32) {
+        self.inner.width = width;
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn height(&self) -> u32 {
+        self.inner.height
+    }
+
+    #[wasm_bindgen(setter)]
+    pub fn set_height(&mut self, height: u32) {
+        self.inner.height = height;
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn dpr(&self) -> f64 {
+        self.inner.dpr
+    }
+
+    #[wasm_bindgen(setter)]
+    pub fn set_dpr(&mut self, dpr: f64) {
+        self.inner.dpr = dpr;
+    }
+
+    /// Main series type, e.g. "candlestick", "line", "heikin_ashi"
+    #[wasm_bindgen(getter, js_name = seriesType)]
+    pub fn series_type(&self) -> String {
+        self.inner.series.series_type.as_str().to_string()
+    }
+
+    #[wasm_bindgen(setter, js_name = seriesType)]
+    pub fn set_series_type(&mut self, series_type: String) -> Result<(), JsValue> {
+        self.inner.series.series_type = series_type
+            .parse()
+            .map_err(|_| JsValue::from_str("unknown series type"))?;
+        Ok(())
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn background(&self) -> String {
+        self.inner.theme.background.clone()
+    }
+
+    #[wasm_bindgen(setter)]
+    pub fn set_background(&mut self, color: String) {
+        self.inner.theme.background = color;
+    }
+
+    #[wasm_bindgen(getter, js_name = textColor)]
+    pub fn text_color(&self) -> String {
+        self.inner.theme.text_color.clone()
+    }
+
+    #[wasm_bindgen(setter, js_name = textColor)]
+    pub fn set_text_color(&mut self, color: String) {
+        self.inner.theme.text_color = color;
+    }
+
+    #[wasm_bindgen(getter, js_name = gridColor)]
+    pub fn grid_color(&self) -> String {
+        self.inner.theme.grid_color.clone()
+    }
+
+    #[wasm_bindgen(setter, js_name = gridColor)]
+    pub fn set_grid_color(&mut self, color: String) {
+        self.inner.theme.grid_color = color;
+    }
+
+    #[wasm_bindgen(getter, js_name = showGrid)]
+    pub fn show_grid(&self) -> bool {
+        self.inner.theme.show_grid
+    }
+
+    #[wasm_bindgen(setter, js_name = showGrid)]
+    pub fn set_show_grid(&mut self, show: bool) {
+        self.inner.theme.show_grid = show;
+    }
+
+    #[wasm_bindgen(getter, js_name = upColor)]
+    pub fn up_color(&self) -> String {
+        self.inner.theme.up_color.clone()
+    }
+
+    #[wasm_bindgen(setter, js_name = upColor)]
+    pub fn set_up_color(&mut self, color: String) {
+        self.inner.theme.up_color = color;
+    }
+
+    #[wasm_bindgen(getter, js_name = downColor)]
+    pub fn down_color(&self) -> String {
+        self.inner.theme.down_color.clone()
+    }
+
+    #[wasm_bindgen(setter, js_name = downColor)]
+    pub fn set_down_color(&mut self, color: String) {
+        self.inner.theme.down_color = color;
+    }
+
+    #[wasm_bindgen(getter, js_name = borderColor)]
+    pub fn border_color(&self) -> String {
+        self.inner.theme.border_color.clone()
+    }
+
+    #[wasm_bindgen(setter, js_name = borderColor)]
+    pub fn set_border_color(&mut self, color: String) {
+        self.inner.theme.border_color = color;
+    }
+
+    /// Add a built-in indicator by kind ("sma", "ema", "wma", "hma",
+    /// "bollinger", "keltner", "donchian", "zigzag", "psar", "supertrend",
+    /// "rsi", "stochastic", "cci", "williams_r", "atr", "adx", "macd", "obv",
+    /// "ad", "cmf", "mfi") with its standard inputs, overridden by `inputs`,
+    /// e.g. `{fast: 8, source: "hl2"}`; values are computed from the bars
+    /// when rendered
+    #[wasm_bindgen(js_name = addIndicator)]
+    pub fn add_indicator(
+        &mut self,
+        kind: &str,
+        inputs: JsValue,
+        id: Option<String>,
+    ) -> Result<(), JsValue> {
+        let mut params: IndicatorParams = kind
+            .parse()
+            .map_err(|_| JsValue::from_str(&format!("unknown indicator kind '{}'", kind)))?;
+        if inputs.is_object() {
+            for entry in js_sys::Object::entries(&js_sys::Object::from(inputs)).iter() {
+                let entry = js_sys::Array::from(&entry);
+                let name = entry.get(0).as_string().unwrap_or_default();
+                let value = entry.get(1);
+                let known = if name == "source" {
+                    let source: Source = value
+                        .as_string()
+                        .and_then(|s| s.parse().ok())
+                        .ok_or_else(|| JsValue::from_str("unknown source"))?;
+                    params.set_source(source)
+                } else {
+                    let value = value.as_f64().ok_or_else(|| {
+                        JsValue::from_str(&format!("input '{}' must be a number", name))
+                    })?;
+                    params.set_input(&name, value)
+                };
+                if !known {
+                    return Err(JsValue::from_str(&format!(
+                        "{} has no input '{}'",
+                        kind, name
+                    )));
+                }
+            }
+        }
+        let id = id.unwrap_or_else(|| kind.to_string());
+        self.inner
+            .indicators
+            .push(Indicator::from_params(&id, params));
+        Ok(())
+    }
+
+    /// Add a drawing primitive by type id with flat [bar, price, ...] points
+    #[wasm_bindgen(js_name = addPrimitive)]
+    pub fn add_primitive(
+        &mut self,
+        type_id: &str,
+        points: Vec<f64>,
+        color: Option<String>,
+        id: Option<String>,
+    ) -> Result<(), JsValue> {
+        let pts: Vec<(f64, f64)> = points.chunks(2).map(|c| (c[0], c[1])).collect();
+        self.inner
+            .primitive_registry()
+            .validate(type_id, pts.len())
+            .map_err(|e| JsValue::from_str(&e.to_string()))?;
+        let mut primitive = PrimitiveConfig::new(type_id, pts);
+        if let Some(color) = color {
+            primitive = primitive.with_color(&color);
+        }
+        if let Some(id) = id {
+            primitive = primitive.with_id(&id);
+        }
+        self.inner.primitives.push(primitive);
+        Ok(())
+    }
+
+    /// Add a signal marker: kind is "buy", "sell", "entry", "exit", ...
+    #[wasm_bindgen(js_name = addSignal)]
+    pub fn add_signal(&mut self, kind: &str, bar_index: usize, price: f64, label: Option<String>) {
+        let signal_type = kind.parse().unwrap_or_default();
+        let mut signal = SignalConfig::new(signal_type, bar_index, price);
+        if let Some(label) = label {
+            signal = signal.with_label(&label);
+        }
+        self.inner.signals.push(signal);
+    }
+
+    /// Render this config over `bars` to an SVG string
+    pub fn render(&self, bars: Vec<JsBar>) -> String {
+        let bars: Vec<Bar> = bars.iter().map(|b| b.inner).collect();
+        ChartRenderer::new(&self.inner, &bars).render_svg()
+    }
 }
 
 impl Default for JsChartConfig {
     fn default() -> Self {
-        Self::new()
+        Self::new(None, None)
     }
 }
 
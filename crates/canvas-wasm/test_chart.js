@@ -172,10 +172,12 @@ async function main() {
     chart = new wasm.Chart(800, 400);
     chart.setBars(toBars(barData));
     chart.candlesticks();
-    chart.setBackground(runtime.background);
-    chart.setColors(runtime.candleUpBody, runtime.candleDownBody);
+    chart.applyTheme(runtime);
     chart.sma(20, "#ffff00");
     svg = chart.renderSvg();
+    if (!["#1a0a2e", "#00ffff", "#ff00ff"].every((c) => svg.includes(c))) {
+        throw new Error("runtime theme colors missing");
+    }
     saveSvg(svg, path.join(outputDir, '05_runtime_custom.svg'));
 
     // =========================================================================
@@ -255,6 +257,50 @@ async function main() {
     svg = chart.renderSvg();
     saveSvg(svg, path.join(outputDir, '11_signals.svg'));
 
+    // 12. Chart from Config (two subpanes, no builder calls)
+    console.log("12. Chart from Config");
+    chart = wasm.Chart.fromConfig({
+        width: 1000,
+        height: 600,
+        theme: { background: "#0b0e14" },
+        indicators: [
+            { params: { kind: "sma", period: 20 } },
+            { params: { kind: "rsi", period: 14 } },
+            { params: { kind: "macd", fast: 12, slow: 26, signal: 9 } },
+        ],
+        signals: [{ signal_type: "Buy", bar_index: 40, price: barData[40].low - 2.0 }],
+    });
+    chart.setBars(toBars(barData));
+    svg = chart.renderSvg();
+    if (!svg.includes("RSI 14") || !svg.includes("MACD (12,26,9)")) {
+        throw new Error("config subpanes missing");
+    }
+    saveSvg(svg, path.join(outputDir, '12_from_config.svg'));
+
+    const config = new wasm.JsChartConfig(1000, 600);
+    config.background = "#0b0e14";
+    config.seriesType = "heikin_ashi";
+    config.addIndicator("rsi", { period: 7 });
+    config.addIndicator("macd", { fast: 8, source: "hl2" });
+    config.addPrimitive("horizontal_line", [0, barData[0].close]);
+    config.addSignal("sell", 60, barData[60].high + 2.0, "Short");
+    svg = config.render(toBars(barData));
+    if (!svg.includes("RSI 7") || !svg.includes("MACD (8,26,9) hl2")) {
+        throw new Error("config indicators missing");
+    }
+    if (wasm.JsChartConfig.fromObject(config.toJson()).seriesType !== "heikin_ashi") {
+        throw new Error("config JSON roundtrip failed");
+    }
+
+    try {
+        wasm.Chart.fromConfig({ indicators: [{ params: { kind: "rsi", period: 14 }, placement: "nowhere" }] });
+        throw new Error("invalid config accepted");
+    } catch (e) {
+        if (!String(e).startsWith("indicators[0].placement:")) {
+            throw e;
+        }
+    }
+
     // =========================================================================
     // API Verification
     // =========================================================================
@@ -301,7 +347,7 @@ async function main() {
 
     console.log(`\n[OK] All charts generated in '${outputDir}/'\n`);
     console.log("Generated files:");
-    for (let i = 1; i <= 11; i++) {
+    for (let i = 1; i <= 12; i++) {
         const prefix = i < 10 ? "0" : "";
         console.log(`  - ${prefix}${i}_*.svg`);
     }
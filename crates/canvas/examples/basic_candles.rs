@@ -0,0 +1,38 @@
+//! Basic Candles Example
+//!
+//! The smallest useful chart: candlesticks with a title and legend.
+//!
+//! Run with `cargo run --example basic_candles`.
+
+mod common;
+
+use zengeld_canvas::api::Chart;
+use zengeld_canvas::core::testing::sample_bars;
+
+fn render() -> String {
+    let bars = sample_bars(120);
+    Chart::new(800, 450)
+        .bars(&bars)
+        .candlesticks()
+        .title("SAMPLE · 1D")
+        .legend(true)
+        .render_svg()
+}
+
+fn main() {
+    println!("Basic candlestick chart");
+    common::save_svg("basic_candles", &render());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_renders_candles() {
+        let svg = render();
+        common::assert_svg(&svg);
+        assert!(svg.contains("#26a69a"));
+        assert!(svg.contains("#ef5350"));
+    }
+}
@@ -7,7 +7,7 @@ use std::fs;
 use zengeld_canvas::api::{
     Chart, ChartConfig, Indicator, MultichartRenderer, PrimitiveConfig, SeriesConfig, SignalConfig,
 };
-use zengeld_canvas::core::Bar;
+use zengeld_canvas::core::testing::sample_bars;
 use zengeld_canvas::layout::MultichartLayout;
 use zengeld_canvas::{RuntimeTheme, UITheme};
 
@@ -17,7 +17,7 @@ fn main() {
     fs::create_dir_all(output_dir).expect("Failed to create output directory");
 
     // Generate sample data
-    let bars = generate_sample_bars(200);
+    let bars = sample_bars(200);
 
     println!("Generating chart gallery...\n");
 
@@ -424,59 +424,6 @@ fn main() {
     println!("{}", theme.to_json());
 }
 
-/// Generate sample OHLCV bars with realistic price movement
-fn generate_sample_bars(count: usize) -> Vec<Bar> {
-    let mut bars = Vec::with_capacity(count);
-    let mut price = 100.0;
-    let base_volume = 1_000_000.0;
-    let start_time = 1700000000i64;
-
-    for i in 0..count {
-        let trend = ((i as f64 / count as f64) * std::f64::consts::PI * 2.0).sin() * 10.0;
-        let noise = pseudo_random(i as u64) * 4.0 - 2.0;
-        let change = trend * 0.1 + noise;
-
-        price += change;
-        price = price.max(50.0);
-
-        let volatility = 1.0 + pseudo_random(i as u64 + 1000) * 2.0;
-        let high = price + volatility;
-        let low = price - volatility;
-
-        let open = if pseudo_random(i as u64 + 2000) > 0.5 {
-            low + pseudo_random(i as u64 + 3000) * (high - low)
-        } else {
-            high - pseudo_random(i as u64 + 4000) * (high - low)
-        };
-
-        let close = if pseudo_random(i as u64 + 5000) > 0.5 {
-            low + pseudo_random(i as u64 + 6000) * (high - low)
-        } else {
-            high - pseudo_random(i as u64 + 7000) * (high - low)
-        };
-
-        let volume = base_volume * (0.5 + pseudo_random(i as u64 + 8000) * 1.5);
-
-        bars.push(Bar {
-            timestamp: start_time + (i as i64 * 86400),
-            open,
-            high,
-            low,
-            close,
-            volume,
-        });
-    }
-
-    bars
-}
-
-/// Simple pseudo-random number generator (deterministic for reproducibility)
-fn pseudo_random(seed: u64) -> f64 {
-    let x = seed.wrapping_mul(1103515245).wrapping_add(12345);
-    let x = x.wrapping_mul(1103515245).wrapping_add(12345);
-    ((x >> 16) & 0x7fff) as f64 / 32767.0
-}
-
 /// Save SVG to file
 fn save_svg(svg: &str, path: &str) {
     fs::write(path, svg).unwrap_or_else(|_| panic!("Failed to write {}", path));
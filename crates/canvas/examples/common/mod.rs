@@ -0,0 +1,42 @@
+//! Shared helpers for the chart recipe examples
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Directory the examples write their SVGs to: `<target>/chart_examples`
+///
+/// Honours `CARGO_TARGET_DIR`, falling back to the workspace `target/`.
+pub fn output_dir() -> PathBuf {
+    let target = std::env::var_os("CARGO_TARGET_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| {
+            // crates/canvas -> workspace root
+            let manifest = Path::new(env!("CARGO_MANIFEST_DIR"));
+            manifest
+                .ancestors()
+                .nth(2)
+                .unwrap_or(manifest)
+                .join("target")
+        });
+    target.join("chart_examples")
+}
+
+/// Write an SVG into [`output_dir`] and return its path
+pub fn save_svg(name: &str, svg: &str) -> PathBuf {
+    let dir = output_dir();
+    fs::create_dir_all(&dir).expect("Failed to create output directory");
+    let path = dir.join(format!("{}.svg", name));
+    fs::write(&path, svg).unwrap_or_else(|_| panic!("Failed to write {}", path.display()));
+    println!("  -> Saved: {}", path.display());
+    path
+}
+
+/// Check that a rendered chart is a complete SVG document
+#[cfg(test)]
+pub fn assert_svg(svg: &str) {
+    assert!(svg.contains("<svg "), "not an SVG document");
+    assert!(
+        svg.trim_end().ends_with("</svg>"),
+        "SVG document is truncated"
+    );
+}
@@ -0,0 +1,85 @@
+//! Config from JSON Example
+//!
+//! `ChartConfig` is serde-serializable, so a chart can be described in
+//! JSON: here a short hand-written document is layered over the defaults,
+//! rendered, and the full config is written back out for reuse.
+//!
+//! Run with `cargo run --example config_from_json`.
+
+mod common;
+
+use serde_json::Value;
+use zengeld_canvas::api::{ChartConfig, ChartRenderer};
+use zengeld_canvas::core::testing::sample_bars;
+
+/// Only the settings that differ from `ChartConfig::default()`
+const CHART_JSON: &str = r##"{
+    "width": 960,
+    "height": 540,
+    "theme": {
+        "background": "#0b0e14",
+        "show_grid": true
+    },
+    "series": {
+        "series_type": "heikin_ashi"
+    }
+}"##;
+
+/// Recursively overlay `patch` onto `base`, object keys by key
+fn merge(base: &mut Value, patch: Value) {
+    match (base, patch) {
+        (Value::Object(base), Value::Object(patch)) => {
+            for (key, value) in patch {
+                merge(base.entry(key).or_insert(Value::Null), value);
+            }
+        }
+        (base, patch) => *base = patch,
+    }
+}
+
+/// Parse a partial chart document on top of the default config
+fn config_from_json(json: &str) -> Result<ChartConfig, serde_json::Error> {
+    let mut config = serde_json::to_value(ChartConfig::default())?;
+    merge(&mut config, serde_json::from_str(json)?);
+    serde_json::from_value(config)
+}
+
+fn render() -> (ChartConfig, String) {
+    let config = config_from_json(CHART_JSON).expect("valid chart JSON");
+    let bars = sample_bars(150);
+    let svg = ChartRenderer::new(&config, &bars).render_svg();
+    (config, svg)
+}
+
+fn main() {
+    println!("Chart described in JSON");
+    let (config, svg) = render();
+    common::save_svg("config_from_json", &svg);
+
+    // The complete config round-trips, so it can be stored and reloaded as-is
+    let full = serde_json::to_string_pretty(&config).expect("serializable config");
+    let path = common::output_dir().join("config_from_json.json");
+    std::fs::write(&path, full).expect("Failed to write config");
+    println!("  -> Saved: {}", path.display());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_partial_json_overrides_defaults() {
+        let (config, svg) = render();
+        assert_eq!((config.width, config.height), (960, 540));
+        assert!(config.theme.show_grid);
+        assert_eq!(config.theme.up_color, ChartConfig::default().theme.up_color);
+        common::assert_svg(&svg);
+        assert!(svg.contains("#0b0e14"));
+    }
+
+    #[test]
+    fn test_rejects_malformed_json() {
+        assert!(config_from_json("{\"width\": \"wide\"}").is_err());
+        assert!(config_from_json("not json").is_err());
+    }
+}
@@ -0,0 +1,46 @@
+//! Indicators and Subpanes Example
+//!
+//! Overlays share the price pane, oscillators get their own subpanes
+//! stacked under it in the order they are added.
+//!
+//! Run with `cargo run --example indicators_subpanes`.
+
+mod common;
+
+use zengeld_canvas::api::Chart;
+use zengeld_canvas::core::testing::sample_bars;
+
+fn render() -> String {
+    let bars = sample_bars(200);
+    Chart::new(900, 700)
+        .bars(&bars)
+        .candlesticks()
+        // Overlays on the main pane
+        .sma(20, "#2196F3")
+        .ema(50, "#FF9800")
+        .bollinger(20, 2.0)
+        // Subpanes, top to bottom
+        .volume()
+        .rsi(14)
+        .macd(12, 26, 9)
+        .legend(true)
+        .render_svg()
+}
+
+fn main() {
+    println!("Overlays plus volume, RSI and MACD subpanes");
+    common::save_svg("indicators_subpanes", &render());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_renders_overlays_and_subpanes() {
+        let svg = render();
+        common::assert_svg(&svg);
+        assert!(svg.contains("#2196f3"));
+        assert!(svg.contains("#ff9800"));
+    }
+}
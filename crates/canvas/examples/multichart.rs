@@ -0,0 +1,61 @@
+//! Multichart Example
+//!
+//! Several charts in one SVG using a `MultichartLayout` grid, each cell
+//! with its own `ChartConfig` and bars.
+//!
+//! Run with `cargo run --example multichart`.
+
+mod common;
+
+use zengeld_canvas::api::{Chart, MultichartRenderer};
+use zengeld_canvas::core::testing::sample_bars;
+use zengeld_canvas::layout::MultichartLayout;
+
+fn render() -> String {
+    let bars = sample_bars(200);
+    let layout = MultichartLayout::quad();
+
+    // Each cell is an ordinary builder chart; `build()` hands back its
+    // config with the indicator values computed
+    let (candles, bars) = Chart::new(600, 400)
+        .bars(&bars)
+        .candlesticks()
+        .sma(20, "#2196F3")
+        .build();
+    let (line, _) = Chart::new(600, 400)
+        .bars(&bars)
+        .line()
+        .ema(12, "#FF9800")
+        .build();
+    let (area, _) = Chart::new(600, 400).bars(&bars).area().build();
+    let (ohlc, _) = Chart::new(600, 400)
+        .bars(&bars)
+        .ohlc_bars()
+        .bollinger(20, 2.0)
+        .build();
+
+    MultichartRenderer::new(&layout, 1200, 800)
+        .chart(&candles, &bars)
+        .chart(&line, &bars)
+        .chart(&area, &bars)
+        .chart(&ohlc, &bars)
+        .render_svg()
+}
+
+fn main() {
+    println!("2x2 multichart grid");
+    common::save_svg("multichart", &render());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_renders_grid() {
+        let svg = render();
+        common::assert_svg(&svg);
+        assert!(svg.contains("#2196f3"));
+        assert!(svg.contains("#ff9800"));
+    }
+}
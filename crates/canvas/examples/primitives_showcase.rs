@@ -0,0 +1,52 @@
+//! Primitives Showcase Example
+//!
+//! Drawing tools placed in (bar index, price) coordinates: lines,
+//! channels, Fibonacci levels, shapes and markers.
+//!
+//! Run with `cargo run --example primitives_showcase`.
+
+mod common;
+
+use zengeld_canvas::api::{Chart, PrimitiveConfig};
+use zengeld_canvas::core::testing::sample_bars;
+
+fn render() -> String {
+    let bars = sample_bars(150);
+    // Anchor the drawings to actual bars so they sit on the price action
+    let at = |i: usize| (i as f64, bars[i].close);
+    let low = |i: usize| (i as f64, bars[i].low);
+    let high = |i: usize| (i as f64, bars[i].high);
+
+    Chart::new(1000, 600)
+        .bars(&bars)
+        .candlesticks()
+        .primitive(PrimitiveConfig::trend_line(low(10), low(60)).with_color("#2196F3"))
+        .primitive(PrimitiveConfig::horizontal_line(bars[75].close).with_color("#9C27B0"))
+        .primitive(PrimitiveConfig::parallel_channel(at(80), at(110), high(95)))
+        .primitive(PrimitiveConfig::fib_retracement(low(20), high(45)))
+        .primitive(
+            PrimitiveConfig::rectangle(high(115), low(135))
+                .with_color("#FF9800")
+                .with_fill("#FF9800", 0.15),
+        )
+        .primitive(PrimitiveConfig::arrow_up(low(140)))
+        .render_svg()
+}
+
+fn main() {
+    println!("Drawing primitives on a candlestick chart");
+    common::save_svg("primitives_showcase", &render());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_renders_primitives() {
+        let svg = render();
+        common::assert_svg(&svg);
+        assert!(svg.contains("#2196f3"));
+        assert!(svg.contains("#9c27b0"));
+    }
+}
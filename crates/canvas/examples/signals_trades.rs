@@ -0,0 +1,62 @@
+//! Signals and Trades Example
+//!
+//! Strategy output on top of the bars: entry/exit markers with level
+//! lines, plus long/short position boxes showing each trade's target and
+//! stop.
+//!
+//! Run with `cargo run --example signals_trades`.
+
+mod common;
+
+use zengeld_canvas::api::{Chart, PrimitiveConfig, SignalConfig};
+use zengeld_canvas::core::testing::sample_bars;
+
+fn render() -> String {
+    let bars = sample_bars(160);
+    let close = |i: usize| bars[i].close;
+
+    // A long trade: entry at 30, exit at 55, 4% target and 2% stop
+    let (entry, exit) = (30, 55);
+    let long = PrimitiveConfig::long_position(
+        (entry as f64, close(entry)),
+        (exit as f64, close(entry) * 1.04),
+        (exit as f64, close(entry) * 0.98),
+    );
+
+    // A short trade: entry at 100, exit at 130
+    let (short_entry, short_exit) = (100, 130);
+    let short = PrimitiveConfig::short_position(
+        (short_entry as f64, close(short_entry)),
+        (short_exit as f64, close(short_entry) * 0.96),
+        (short_exit as f64, close(short_entry) * 1.02),
+    );
+
+    Chart::new(1000, 550)
+        .bars(&bars)
+        .candlesticks()
+        .sma(20, "#2196F3")
+        .signal(SignalConfig::buy(entry, bars[entry].low).with_level_line())
+        .signal(SignalConfig::exit(exit, bars[exit].high).with_label("TP"))
+        .signal(SignalConfig::sell(short_entry, bars[short_entry].high).with_level_line())
+        .signal(SignalConfig::stop_loss(short_exit, bars[short_exit].low))
+        .primitive(long)
+        .primitive(short)
+        .render_svg()
+}
+
+fn main() {
+    println!("Strategy signals and trade positions");
+    common::save_svg("signals_trades", &render());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_renders_signals_and_positions() {
+        let svg = render();
+        common::assert_svg(&svg);
+        assert!(svg.contains("TP"));
+    }
+}
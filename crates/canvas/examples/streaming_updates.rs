@@ -0,0 +1,93 @@
+//! Streaming Updates Example
+//!
+//! A live-feed loop: each tick either updates the forming bar or opens a
+//! new one, and the chart is re-rendered into a numbered SVG frame.
+//!
+//! Run with `cargo run --example streaming_updates`.
+
+mod common;
+
+use zengeld_canvas::api::Chart;
+use zengeld_canvas::core::Bar;
+use zengeld_canvas::core::testing::sample_bars;
+
+/// Bars already loaded before the feed starts
+const HISTORY: usize = 100;
+/// Bars that arrive through the feed
+const LIVE_BARS: usize = 6;
+/// Price updates per live bar before it closes
+const TICKS_PER_BAR: usize = 3;
+
+/// Render one chart frame for the bars received so far
+fn render_frame(bars: &[Bar]) -> String {
+    Chart::new(800, 450)
+        .bars(bars)
+        .candlesticks()
+        .ema(20, "#FF9800")
+        .volume()
+        .render_svg()
+}
+
+/// Run the feed, returning one SVG frame per tick
+fn stream() -> Vec<String> {
+    // The source the "exchange" streams from
+    let source = sample_bars(HISTORY + LIVE_BARS);
+    let mut bars: Vec<Bar> = source[..HISTORY].to_vec();
+    let mut frames = Vec::new();
+
+    for target in &source[HISTORY..] {
+        // The bar opens flat at its open price...
+        bars.push(Bar {
+            high: target.open,
+            low: target.open,
+            close: target.open,
+            volume: 0.0,
+            ..*target
+        });
+
+        // ...and each tick walks it towards the final close
+        for tick in 1..=TICKS_PER_BAR {
+            let progress = tick as f64 / TICKS_PER_BAR as f64;
+            let price = target.open + (target.close - target.open) * progress;
+            let forming = bars.last_mut().expect("forming bar");
+            forming.close = price;
+            forming.high = forming.high.max(price);
+            forming.low = forming.low.min(price);
+            forming.volume = target.volume * progress;
+            if tick == TICKS_PER_BAR {
+                *forming = *target;
+            }
+
+            frames.push(render_frame(&bars));
+        }
+    }
+
+    frames
+}
+
+fn main() {
+    println!(
+        "Streaming {} bars into {} frames",
+        LIVE_BARS,
+        LIVE_BARS * TICKS_PER_BAR
+    );
+    for (i, svg) in stream().iter().enumerate() {
+        common::save_svg(&format!("streaming_{:03}", i), svg);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_renders_a_frame_per_tick() {
+        let frames = stream();
+        assert_eq!(frames.len(), LIVE_BARS * TICKS_PER_BAR);
+        for svg in &frames {
+            common::assert_svg(svg);
+        }
+        // The chart changes as ticks arrive
+        assert_ne!(frames[0], frames[1]);
+    }
+}
@@ -0,0 +1,85 @@
+//! Themes Example
+//!
+//! The same chart rendered with each built-in `UITheme` preset, plus a
+//! `RuntimeTheme` customised at runtime.
+//!
+//! Run with `cargo run --example themes`.
+
+mod common;
+
+use zengeld_canvas::api::Chart;
+use zengeld_canvas::core::Bar;
+use zengeld_canvas::core::testing::sample_bars;
+use zengeld_canvas::{RuntimeTheme, UITheme};
+
+fn themed(bars: &[Bar], background: &str, up: &str, down: &str, accent: &str) -> String {
+    Chart::new(800, 400)
+        .bars(bars)
+        .candlesticks()
+        .background(background)
+        .colors(up, down)
+        .sma(20, accent)
+        .render_svg()
+}
+
+fn render() -> Vec<(String, String)> {
+    let bars = sample_bars(150);
+
+    let presets = [
+        ("dark", UITheme::dark()),
+        ("light", UITheme::light()),
+        ("high_contrast", UITheme::high_contrast()),
+        ("cyberpunk", UITheme::cyberpunk()),
+    ];
+    let mut charts: Vec<(String, String)> = presets
+        .iter()
+        .map(|(name, theme)| {
+            let svg = themed(
+                &bars,
+                theme.chart.background,
+                theme.series.candle_up_body,
+                theme.series.candle_down_body,
+                theme.colors.accent,
+            );
+            (format!("themes_{}", name), svg)
+        })
+        .collect();
+
+    // Runtime themes start from a preset and can be edited (or loaded from JSON)
+    let mut custom = RuntimeTheme::from_preset("dark");
+    custom.chart.background = "#1a0a2e".to_string();
+    custom.series.candle_up_body = "#00ffff".to_string();
+    custom.series.candle_down_body = "#ff00ff".to_string();
+    let svg = themed(
+        &bars,
+        &custom.chart.background,
+        &custom.series.candle_up_body,
+        &custom.series.candle_down_body,
+        "#ffff00",
+    );
+    charts.push(("themes_custom".to_string(), svg));
+
+    charts
+}
+
+fn main() {
+    println!("Built-in and runtime themes");
+    for (name, svg) in render() {
+        common::save_svg(&name, &svg);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_renders_every_theme() {
+        let charts = render();
+        assert_eq!(charts.len(), 5);
+        for (_, svg) in &charts {
+            common::assert_svg(svg);
+        }
+        assert!(charts[4].1.contains("#1a0a2e"));
+    }
+}
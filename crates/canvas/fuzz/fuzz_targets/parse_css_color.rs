@@ -0,0 +1,18 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use zengeld_canvas::parse_css_color;
+use zengeld_canvas::render::Color;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(css) = std::str::from_utf8(data) {
+        let parsed = parse_css_color(css);
+        // Both entry points share one parser and must agree
+        let color = Color::from_css(css);
+        assert_eq!(parsed.ok(), color.map(|c| (c.r, c.g, c.b, c.a)));
+        // Anything that parses survives a trip through to_css
+        if let Some(color) = color {
+            assert_eq!(Color::from_css(&color.to_css()), Some(color));
+        }
+    }
+});
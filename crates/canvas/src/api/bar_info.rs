@@ -0,0 +1,61 @@
+//! Bar Info - structured hover payload for frontend tooltips
+//!
+//! Produced by `ChartRenderer::bar_info` / `Chart::bar_info`. Strings are
+//! formatted the same way the chart draws them, so hosts can show tooltips
+//! without duplicating the formatting rules.
+
+use serde::{Deserialize, Serialize};
+
+/// Everything a tooltip needs for one bar
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct BarInfo {
+    /// Bar index in the series
+    pub index: usize,
+    /// Raw unix timestamp (seconds)
+    pub timestamp: i64,
+    /// Timestamp formatted per the chart's time settings
+    pub time: String,
+    /// OHLC formatted with the main price scale's precision
+    pub open: String,
+    pub high: String,
+    pub low: String,
+    pub close: String,
+    /// Volume with K/M suffixes
+    pub volume: String,
+    /// Values of every indicator at this bar
+    pub indicators: Vec<IndicatorInfo>,
+    /// Signals placed on this bar
+    pub signals: Vec<SignalInfo>,
+}
+
+/// One indicator's values at a bar
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct IndicatorInfo {
+    pub id: String,
+    pub name: String,
+    pub values: Vec<IndicatorValueInfo>,
+}
+
+/// One vector's value at a bar
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct IndicatorValueInfo {
+    /// Vector display label
+    pub label: String,
+    /// Raw value; `None` during warm-up or gaps
+    pub value: Option<f64>,
+    /// Formatted value; `None` when there is no value
+    pub text: Option<String>,
+    /// CSS color the vector is drawn with at this bar
+    pub color: String,
+}
+
+/// A signal on a bar
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SignalInfo {
+    /// Signal type, e.g. "buy" or a strategy-defined tag
+    pub signal_type: String,
+    /// Price level formatted like the OHLC values
+    pub price: String,
+    /// Label or tooltip text
+    pub text: Option<String>,
+}
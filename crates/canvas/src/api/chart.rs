@@ -4,24 +4,62 @@
 //! 1. `ChartRenderer` - takes ChartConfig and renders to SVG
 //! 2. `Chart` - builder pattern that creates ChartConfig internally
 
-use super::config::{ChartConfig, PrimitiveConfig, SeriesConfig, SignalConfig, ThemeConfig};
-use crate::coords::{PriceScale, TickMarkWeight, format_time_by_weight};
-use crate::core::{Bar, PRICE_SCALE_WIDTH, TIME_SCALE_HEIGHT};
+use super::bar_info::{BarInfo, IndicatorInfo, IndicatorValueInfo, SignalInfo};
+use super::config::{
+    ChartConfig, ChartError, ConfigWarning, CountdownConfig, GapConfig, HtfOverlayConfig,
+    HtfOverlayStyle, InsetRect, InsetSeries, PointAnchor, PrimitiveConfig, SIGNAL_LEVEL_BARS,
+    SeasonalConfig, SeriesConfig, SignalConfig, TICK_PANE_RATIO, ThemeConfig, TickSeries,
+    TickStyle, VbpConfig, clamp_dpr,
+};
+use super::stats::{self, Phase, PhaseClock, RenderStats};
+use crate::coords::{
+    DAY, HOUR, MINUTE, PriceScale, PriceScaleMode, TickMarkWeight, TimeScale, format_countdown,
+    format_time_full, format_time_pattern,
+};
+use crate::core::{
+    Bar, ChartDensity, DuplicateBarMerge, PRICE_SCALE_MIN_WIDTH, PRICE_SCALE_PADDING_OUTER,
+    PRICE_SCALE_WIDTH, PRICE_SCALE_WIDTH_STEP, TimeSpacing, TimestampUnit, compute, downsample_m4,
+    format_indicator_value,
+};
 use crate::model::{
-    CandlestickData, CandlestickStyleOptions, Indicator, LineData, LineStyleOptions, SeriesType,
-    SingleValue, VectorStyle,
+    AreaData, AreaStyleOptions, BandStyle, BarData, BarStyleOptions, BaselineData,
+    BaselineStyleOptions, CandleWidthMode, CandlestickData, CandlestickStyleOptions, DenseCandles,
+    HaWickSource, Indicator, IndicatorFill, IndicatorParams, IndicatorPlacement,
+    IndicatorPropertyError, IndicatorRange, IndicatorVector, LegendData, LegendIndicator,
+    LegendPosition, LegendValue, LineData, LineStyleOptions, PivotLabels, PriceLineSource,
+    ScaleGroup, SeasonalPeriod, SeriesOptionsCommon, SeriesType, SignalVisual, SingleValue, Source,
+    Strategy, VectorStyle, VolumeSplitMode, Watermark, get_compare_color, point_of_control,
+    seasonal_lines, volume_by_price,
+};
+use crate::primitives::{
+    CompositeSpec, ConfigProperty, EllipseParams, FibLabelMode, PrimitiveError, PrimitiveInfo,
+    PrimitiveRegistry, PrimitiveTrait, PropertyValue, RenderContext,
+};
+use crate::render::chart::series::{BaselineParams, area_fill_zones};
+use crate::render::chart::{
+    StrategyParams, VectorContext, render_area, render_bars, render_baseline, render_candlesticks,
+    render_heikin_ashi, render_indicator_fills, render_line, render_strategy, render_vector,
+    render_watermark,
 };
-use crate::primitives::{EllipseParams, PrimitiveRegistry, RenderContext};
-use crate::render::chart::{render_candlesticks, render_line};
 use crate::render::engine::{
-    Color, FillStyle, FontWeight, LineStyle, Path, Point, Rect, RenderBackend, RenderBatch,
-    SvgBackend, TextAlign, TextBaseline, TextStyle,
+    Color, FillStyle, FontWeight, FrameElements, LineCap, LineJoin, LineStyle, Path, PathCommand,
+    Point, PrecisionCategory, Rect, RenderBackend, RenderBatch, RenderCommand, RenderDiff,
+    SvgBackend, SvgFragment, SvgPrecision, TextAlign, TextBaseline, TextStyle, estimate_text_width,
 };
+#[cfg(feature = "raster")]
+use crate::render::engine::{RasterBackend, RenderResult};
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+use std::ops::Range;
+use std::sync::Arc;
 
 /// Parameters for rendering a subpane indicator
 struct SubpaneRenderParams<'a> {
     /// The indicator to render
     indicator: &'a Indicator,
+    /// Its vectors to draw: all of them, or all but the last segment when
+    /// that goes in a group of its own
+    body: &'a Indicator,
     /// Y offset from the top of the chart
     y_offset: f64,
     /// Height of the subpane
@@ -30,205 +68,341 @@ struct SubpaneRenderParams<'a> {
     width: u32,
     /// Index of this pane (for primitive filtering)
     pane_idx: usize,
+    /// Distance between adjacent bar slots
+    bar_spacing: f64,
 }
 
-// =============================================================================
-// ChartRenderer - Renders ChartConfig to SVG
-// =============================================================================
-
-/// Renderer that takes a ChartConfig and produces SVG output
-pub struct ChartRenderer<'a> {
-    config: &'a ChartConfig,
-    bars: &'a [Bar],
+/// Value-to-y mapping of one pane, for primitives whose points span panes
+#[derive(Clone, Copy, Debug)]
+struct PaneTransform {
+    /// Top of the pane
+    top: f64,
+    /// Height of the pane
+    height: f64,
+    /// Value at the bottom edge
+    low: f64,
+    /// Value at the top edge
+    high: f64,
 }
 
-impl<'a> ChartRenderer<'a> {
-    /// Create a new renderer with config and bar data
-    pub fn new(config: &'a ChartConfig, bars: &'a [Bar]) -> Self {
-        Self { config, bars }
+impl PaneTransform {
+    fn value_to_y(&self, value: f64) -> f64 {
+        let ratio = (value - self.low) / (self.high - self.low);
+        self.top + self.height - ratio * self.height
     }
+}
 
-    /// Render the chart to SVG string
-    pub fn render_svg(&self) -> String {
-        if self.bars.is_empty() {
-            return self.empty_svg();
-        }
+/// Parameters for rendering a price scale column
+struct PriceScaleParams<'a> {
+    /// X where the scale starts (right edge of the plot)
+    chart_width: f64,
+    /// Top of the pane
+    y_offset: f64,
+    /// Height of the pane
+    pane_height: f64,
+    /// Bottom of the price range
+    price_min: f64,
+    /// Top of the price range
+    price_max: f64,
+    /// Y positions of axis tags; tick labels under them are dropped
+    tags: &'a [f64],
+    /// Instrument tick size (main pane only)
+    tick_size: Option<f64>,
+}
 
-        let width = self.config.width;
-        let height = self.config.height;
-        let dpr = self.config.dpr;
+/// Indicators grouped by where they are drawn
+struct IndicatorPanes<'a> {
+    /// Chart overlays on the main price scale (strategy overlays are drawn
+    /// by render_strategy)
+    overlays: Vec<&'a Indicator>,
+    overlay_bottoms: Vec<&'a Indicator>,
+    subpanes: Vec<&'a Indicator>,
+    /// Every indicator that shares the main price scale
+    price_overlays: Vec<&'a Indicator>,
+}
 
-        // Reserve space for scales
-        let price_scale_width = PRICE_SCALE_WIDTH;
-        let time_scale_height = TIME_SCALE_HEIGHT;
-        let chart_width = width as f64 - price_scale_width;
-        let chart_height = height as f64 - time_scale_height;
+impl<'a> IndicatorPanes<'a> {
+    fn split(indicators: &'a [Cow<'_, Indicator>], strategy: Option<&'a Strategy>) -> Self {
+        let strategy_indicators = strategy.iter().flat_map(|s| s.indicators.iter());
+        let all = || {
+            indicators
+                .iter()
+                .map(Cow::as_ref)
+                .chain(strategy_indicators.clone())
+        };
 
-        // Separate indicators into overlays, overlay_bottom, and subpanes
-        let overlays: Vec<&Indicator> = self
-            .config
-            .indicators
+        let overlays: Vec<&Indicator> = indicators
             .iter()
+            .map(Cow::as_ref)
             .filter(|ind| ind.placement.is_overlay())
             .collect();
-        let overlay_bottoms: Vec<&Indicator> = self
-            .config
-            .indicators
-            .iter()
-            .filter(|ind| ind.placement.is_overlay_bottom())
-            .collect();
-        let subpanes: Vec<&Indicator> = self
-            .config
-            .indicators
-            .iter()
-            .filter(|ind| ind.placement.is_subpane())
-            .collect();
-
-        // Calculate layout - subpanes share height with main chart
-        let total_subpane_ratio: f64 = subpanes.iter().map(|s| s.placement.height_ratio()).sum();
-        let main_ratio = 1.0 - total_subpane_ratio;
-        let main_height = chart_height * main_ratio;
-        let gap = 4.0;
-
-        // Create backend
-        let mut backend = SvgBackend::new(width, height, dpr);
-        backend.begin_frame(width as f64, height as f64, dpr);
-
-        // Background
-        let bg_color = &self.config.theme.background;
-        let bg = Color::from_css(bg_color).unwrap_or(Color::rgb(19, 23, 34));
-        backend.clear(bg);
-
-        // Calculate coordinate system for main chart
-        let (price_min, price_max) = self.price_range(&overlays);
-        let price_padding = (price_max - price_min) * 0.05;
-        let price_low = price_min - price_padding;
-        let price_high = price_max + price_padding;
-
-        let bar_count = self.bars.len();
-        let bar_spacing = chart_width / bar_count as f64;
-        let bar_width = (bar_spacing * 0.8).max(1.0);
-
-        let bar_to_x = |i: usize| -> f64 { bar_spacing * (i as f64 + 0.5) };
-
-        let price_to_y = |price: f64| -> f64 {
-            let ratio = (price - price_low) / (price_high - price_low);
-            main_height - ratio * main_height
-        };
-
-        // Grid (only on main chart, not on subpanes)
-        if self.config.theme.show_grid {
-            self.draw_grid(
-                &mut backend,
-                main_height,
-                bar_spacing,
-                chart_width as u32,
-                main_height as u32,
-            );
+        let price_overlays = all().filter(|ind| ind.placement.is_overlay()).collect();
+        Self {
+            overlays,
+            overlay_bottoms: all()
+                .filter(|ind| ind.placement.is_overlay_bottom())
+                .collect(),
+            subpanes: all().filter(|ind| ind.placement.is_subpane()).collect(),
+            price_overlays,
         }
+    }
+}
 
-        // Main series
-        let mut batch = RenderBatch::new();
-        self.render_main_series(&mut batch, &bar_to_x, &price_to_y, bar_width, dpr);
-        self.execute_batch(&mut backend, &batch);
+/// Heights of the main pane and of its price area, which excludes any
+/// reserved overlay_bottom strip
+fn main_pane_heights(
+    overlay_bottoms: &[&Indicator],
+    subpanes: &[&Indicator],
+    chart_height: f64,
+) -> (f64, f64) {
+    let total_subpane_ratio: f64 = subpanes.iter().map(|s| s.placement.height_ratio()).sum();
+    let main_height = chart_height * (1.0 - total_subpane_ratio);
+    let price_height = main_height - reserved_strip_height(overlay_bottoms, main_height);
+    (main_height, price_height)
+}
 
-        // Overlay indicators (share price scale with main chart)
-        self.render_overlay_indicators(&mut backend, &overlays, &bar_to_x, &price_to_y, dpr);
+/// Visible indicators as rendered, with smoothed vectors replaced by their
+/// SMA; hidden indicators and hidden groups are left out
+fn display_indicators(config: &ChartConfig) -> Vec<Cow<'_, Indicator>> {
+    config
+        .indicators
+        .iter()
+        .filter(|ind| config.is_indicator_visible(ind))
+        .map(|ind| ind.smoothed().map_or(Cow::Borrowed(ind), Cow::Owned))
+        .collect()
+}
 
-        // Overlay bottom indicators (own Y scale at bottom of main chart)
-        self.render_overlay_bottom_indicators(
-            &mut backend,
-            &overlay_bottoms,
-            &bar_to_x,
-            main_height,
-            chart_width,
-            dpr,
-        );
+/// Legend order of the overlays: ungrouped ones where they are, each
+/// group's members gathered at the group's first occurrence
+fn legend_entries<'a>(overlays: &[&'a Indicator]) -> Vec<&'a Indicator> {
+    let mut entries = Vec::with_capacity(overlays.len());
+    let mut seen_groups: Vec<&str> = Vec::new();
+    for indicator in overlays {
+        match indicator.group.as_deref() {
+            None => entries.push(*indicator),
+            Some(group) if !seen_groups.contains(&group) => {
+                seen_groups.push(group);
+                entries.extend(
+                    overlays
+                        .iter()
+                        .filter(|ind| ind.group.as_deref() == Some(group)),
+                );
+            }
+            Some(_) => {}
+        }
+    }
+    entries
+}
 
-        // Primitives on main pane
-        self.render_primitives(&mut backend, &bar_to_x, &price_to_y, dpr, None);
+/// Element id of a primitive in recorded frames: its id, else its index
+fn primitive_element(index: usize, primitive: &PrimitiveConfig) -> String {
+    match &primitive.id {
+        Some(id) => format!("primitive:{}", id),
+        None => format!("primitive[{}]", index),
+    }
+}
 
-        // Signals
-        self.render_signals(&mut backend, &bar_to_x, &price_to_y, dpr);
+/// Take the commands drawn within the slot of the bar at `x` out of the
+/// series batch, so that bar can be replaced on its own
+///
+/// Lines and areas spanning several bars stay behind, as does everything
+/// when the batch changes state (clips, layers), since its commands then
+/// can't be reordered.
+fn split_last_bar(series: &mut RenderBatch, x: f64, spacing: f64) -> RenderBatch {
+    let mut last = RenderBatch::new();
+    if series.iter().any(RenderCommand::is_state_command) {
+        return last;
+    }
+    let half = spacing / 2.0;
+    let in_slot = |cmd: &RenderCommand| {
+        cmd.bounds()
+            .is_some_and(|b| b.x >= x - half && b.right() <= x + half)
+    };
+    let mut body = RenderBatch::new();
+    for cmd in std::mem::take(series).into_commands() {
+        if in_slot(&cmd) {
+            last.push(cmd);
+        } else {
+            body.push(cmd);
+        }
+    }
+    *series = body;
+    last
+}
 
-        // Price scale for main chart
-        self.render_price_scale(
-            &mut backend,
-            chart_width,
-            0.0,
-            main_height,
-            price_low,
-            price_high,
+/// Split an indicator into what stays put and its last segment, so a live
+/// update only replaces the latter
+///
+/// The body keeps every value before the last bar of `len`. The last
+/// segment keeps the last bar's value and, for lines, areas and steps to
+/// join up, the finite value before it; everything else is NaN so bar
+/// indexes hold.
+fn split_last_segment(indicator: &Indicator, len: usize) -> (Indicator, Indicator) {
+    let last = len.saturating_sub(1);
+    let (mut body, mut tail) = (indicator.clone(), indicator.clone());
+    for vector in &mut body.vectors {
+        vector.values.truncate(last);
+    }
+    for vector in &mut tail.vectors {
+        let joined = !matches!(
+            vector.style,
+            VectorStyle::Histogram { .. } | VectorStyle::Dots { .. }
         );
-
-        // Subpane indicators with their own price scales
-        let mut y_offset = main_height + gap;
-        for (idx, indicator) in subpanes.iter().enumerate() {
-            let pane_height = chart_height * indicator.placement.height_ratio() - gap;
-            self.render_subpane_indicator(
-                &mut backend,
-                SubpaneRenderParams {
-                    indicator,
-                    y_offset,
-                    height: pane_height,
-                    width: chart_width as u32,
-                    pane_idx: idx,
-                },
-                &bar_to_x,
-            );
-
-            // Price scale for this subpane
-            let (sub_min, sub_max) = self.calculate_indicator_range(indicator);
-            self.render_price_scale(
-                &mut backend,
-                chart_width,
-                y_offset,
-                pane_height,
-                sub_min,
-                sub_max,
-            );
-
-            y_offset += pane_height + gap;
+        let before = vector.values[..last.min(vector.values.len())]
+            .iter()
+            .rposition(|v| v.is_finite())
+            .filter(|_| joined);
+        for (i, value) in vector.values.iter_mut().enumerate() {
+            if i != last && Some(i) != before {
+                *value = f64::NAN;
+            }
         }
+    }
+    (body, tail)
+}
 
-        // Time scale (at bottom, shared)
-        self.render_time_scale(&mut backend, chart_width, chart_height, bar_spacing);
-
-        backend.end_frame();
-        backend.to_svg()
+/// Last close of the main series and whether its bar is up, when the
+/// last-price line is enabled and the series shows its line or axis tag
+///
+/// [`PriceLineSource::LastBar`] takes the last bar and shows nothing when
+/// its close is missing; [`PriceLineSource::LastVisible`] takes the last
+/// bar that is drawn, skipping trailing bars without a close.
+fn series_last_price(config: &ChartConfig, bars: &[Bar]) -> Option<(f64, bool)> {
+    let common = &config.series.common;
+    if !config.show_last_price_line
+        || !config.series_visible()
+        || !(common.price_line_visible || common.last_value_visible)
+    {
+        return None;
     }
+    let bar = match common.price_line_source {
+        PriceLineSource::LastBar => bars.last(),
+        PriceLineSource::LastVisible => bars.iter().rfind(|bar| bar.close.is_finite()),
+    }?;
+    bar.close
+        .is_finite()
+        .then_some((bar.close, bar.close >= bar.open))
+}
 
-    // =========================================================================
-    // Private helpers
-    // =========================================================================
+/// Last-price line across `pane` at `y` and, with the price scale and last
+/// value shown, `label` on a tag in the scale column right of the pane,
+/// styled by the main series' common options
+///
+/// Dashes scale with twice the line width, so the default 1px dashed line
+/// keeps its 4px dashes.
+fn draw_last_price(
+    backend: &mut SvgBackend,
+    config: &ChartConfig,
+    is_up: bool,
+    pane: Rect,
+    y: f64,
+    scale_width: f64,
+    label: &str,
+) {
+    let (theme, common) = (&config.theme, &config.series.common);
+    let direction = if is_up {
+        Color::from_css(&theme.up_color).unwrap_or(Color::rgb(38, 166, 154))
+    } else {
+        Color::from_css(&theme.down_color).unwrap_or(Color::rgb(239, 83, 80))
+    };
+    let color = Color::from_css(&common.price_line_color).unwrap_or(direction);
 
-    fn empty_svg(&self) -> String {
-        format!(
-            r##"<?xml version="1.0" encoding="UTF-8"?>
-<svg xmlns="http://www.w3.org/2000/svg" width="{}" height="{}">
-<rect width="100%" height="100%" fill="{}"/>
-<text x="50%" y="50%" text-anchor="middle" fill="#787b86">No data</text>
-</svg>"##,
-            self.config.width, self.config.height, self.config.theme.background
-        )
+    if common.price_line_visible {
+        let width = common.price_line_width.max(1) as f64;
+        let dash = common.price_line_style.dash_pattern(2.0 * width);
+        let style = LineStyle {
+            dash: Some(dash).filter(|d| !d.is_empty()),
+            ..LineStyle::solid(color, width)
+        };
+        backend.crisp_line(Point::new(pane.x, y), Point::new(pane.right(), y), &style);
+    }
+    if !config.show_price_scale || !common.last_value_visible {
+        return;
     }
 
-    fn price_range(&self, overlays: &[&Indicator]) -> (f64, f64) {
-        let mut min = f64::INFINITY;
-        let mut max = f64::NEG_INFINITY;
+    let sizing = config.sizing();
+    let font_size = sizing.scale_font_size;
+    let tag_height = font_size + 6.0;
+    backend.crisp_fill_rect(
+        Rect::new(pane.right(), y - tag_height / 2.0, scale_width, tag_height),
+        color,
+    );
+    backend.text(
+        label,
+        Point::new(pane.right() + sizing.tick_length + 2.0, y),
+        &TextStyle {
+            color: Color::WHITE,
+            font_size,
+            font_weight: FontWeight::Normal,
+            align: TextAlign::Left,
+            baseline: TextBaseline::Middle,
+            ..Default::default()
+        },
+    );
+}
 
-        for bar in self.bars {
-            if !bar.low.is_nan() {
-                min = min.min(bar.low);
+/// Draw the vector-pair fills of an indicator; call before its lines
+fn draw_indicator_fills(
+    backend: &mut SvgBackend,
+    indicator: &Indicator,
+    bar_to_x: &impl Fn(usize) -> f64,
+    value_to_y: &impl Fn(f64) -> f64,
+) {
+    if indicator.fills.is_empty() {
+        return;
+    }
+    let mut batch = RenderBatch::new();
+    render_indicator_fills(&mut batch, indicator, bar_to_x, value_to_y, 1.0, None);
+    backend.execute_batch(&batch);
+}
+
+/// Value range of `vectors` under `range`; [`IndicatorRange::Price`] spans
+/// the `bars`
+fn value_range<'v>(
+    range: &IndicatorRange,
+    vectors: impl Iterator<Item = &'v IndicatorVector> + Clone,
+    bars: &[Bar],
+) -> (f64, f64) {
+    match range {
+        IndicatorRange::Fixed { min, max } => (*min, *max),
+        IndicatorRange::Symmetric => {
+            // Find max absolute value across all vectors
+            let mut max_abs = 0.0_f64;
+            for vector in vectors.clone() {
+                for &v in &vector.values {
+                    if !v.is_nan() {
+                        max_abs = max_abs.max(v.abs());
+                    }
+                }
             }
-            if !bar.high.is_nan() {
-                max = max.max(bar.high);
+            if max_abs == 0.0 {
+                // All zero or empty: keep the zero line centered
+                return (-1.0, 1.0);
+            }
+            let padding = max_abs * 0.1;
+            (-(max_abs + padding), max_abs + padding)
+        }
+        IndicatorRange::Price => {
+            // Use the same range as the main price chart (from bars)
+            let mut min = f64::INFINITY;
+            let mut max = f64::NEG_INFINITY;
+            for bar in bars {
+                if !bar.low.is_nan() {
+                    min = min.min(bar.low);
+                }
+                if !bar.high.is_nan() {
+                    max = max.max(bar.high);
+                }
             }
+            let padding = (max - min) * 0.05;
+            (min - padding, max + padding)
         }
+        IndicatorRange::Auto => {
+            // Auto-calculate from data
+            let mut min = f64::INFINITY;
+            let mut max = f64::NEG_INFINITY;
 
-        // Include overlay indicator values in range
-        for indicator in overlays {
-            for vector in &indicator.vectors {
+            for vector in vectors.clone() {
                 for &v in &vector.values {
                     if !v.is_nan() {
                         min = min.min(v);
@@ -236,1563 +410,2817 @@ impl<'a> ChartRenderer<'a> {
                     }
                 }
             }
-        }
 
-        (min, max)
+            // Add padding
+            let range = max - min;
+            if range > 0.0 {
+                let padding = range * 0.1;
+                (min - padding, max + padding)
+            } else if min.is_finite() {
+                // Flat series, possibly negative: center it
+                let half = if min == 0.0 { 1.0 } else { min.abs() * 0.1 };
+                (min - half, min + half)
+            } else {
+                (0.0, 100.0)
+            }
+        }
     }
+}
 
-    fn draw_grid(
-        &self,
-        backend: &mut SvgBackend,
-        height: f64,
-        bar_spacing: f64,
-        width: u32,
-        _chart_height: u32,
-    ) {
-        let grid_color =
-            Color::from_css(&self.config.theme.grid_color).unwrap_or(Color::rgb(30, 34, 45));
-        let style = LineStyle::solid(grid_color, 1.0);
+/// Y-axis range of an indicator from its IndicatorRange
+///
+/// Vectors in one of its scale groups are left out; see
+/// [`scale_group_range`].
+fn indicator_range(indicator: &Indicator, bars: &[Bar]) -> (f64, f64) {
+    let own = indicator
+        .vectors
+        .iter()
+        .filter(|v| indicator.scale_group_of(v).is_none());
+    value_range(&indicator.range, own, bars)
+}
 
-        // Horizontal lines
-        let h_count = 8;
-        for i in 1..h_count {
-            let y = height * i as f64 / h_count as f64;
-            backend.line(Point::new(0.0, y), Point::new(width as f64, y), &style);
+/// Range of one of an indicator's scale groups, fitted to its vectors
+fn scale_group_range(indicator: &Indicator, group: &ScaleGroup, bars: &[Bar]) -> (f64, f64) {
+    let members = indicator
+        .vectors
+        .iter()
+        .filter(|v| v.scale_group.as_deref() == Some(group.id.as_str()));
+    value_range(&group.range, members, bars)
+}
+
+/// Draw the fills, then the vectors of `drawn` in `pane` by their
+/// VectorStyle, each against its scale group's range in `indicator` when
+/// it has one, else the indicator's range
+///
+/// A fill between vectors on different ranges puts each edge where its
+/// own vector is drawn.
+fn draw_pane_vectors(
+    backend: &mut SvgBackend,
+    indicator: &Indicator,
+    drawn: &Indicator,
+    pane: Rect,
+    bar_spacing: f64,
+    bars: &[Bar],
+    bar_to_x: &impl Fn(usize) -> f64,
+) {
+    let own = indicator_range(indicator, bars);
+    let range_of = |vector: &IndicatorVector| match indicator.scale_group_of(vector) {
+        Some(group) => scale_group_range(indicator, group, bars),
+        None => own,
+    };
+    let to_y = |(min, max): (f64, f64)| {
+        move |v: f64| {
+            if max <= min {
+                return pane.bottom();
+            }
+            pane.bottom() - (v - min) / (max - min) * pane.height
         }
+    };
 
-        // Vertical lines
-        let v_step = (self.bars.len() / 10).max(1);
-        for i in (0..self.bars.len()).step_by(v_step) {
-            let x = bar_spacing * (i as f64 + 0.5);
-            backend.line(Point::new(x, 0.0), Point::new(x, height), &style);
+    if indicator.scale_groups.is_empty() {
+        draw_indicator_fills(backend, drawn, bar_to_x, &to_y(own));
+    } else if !drawn.fills.is_empty() {
+        // Fill edges as fractions of the pane height, each on its own range
+        let mut normalized = drawn.clone();
+        for vector in &mut normalized.vectors {
+            let (min, max) = range_of(vector);
+            for v in &mut vector.values {
+                *v = (*v - min) / (max - min);
+            }
         }
+        draw_indicator_fills(backend, &normalized, bar_to_x, &to_y((0.0, 1.0)));
     }
+    for vector in &drawn.vectors {
+        let range = range_of(vector);
+        let value_to_y = to_y(range);
+        let ctx = VectorContext::new(pane, bar_spacing, range, value_to_y);
+        render_vector(backend, &ctx, vector, bar_to_x, &value_to_y);
+    }
+}
 
-    fn render_main_series(
-        &self,
-        batch: &mut RenderBatch,
-        bar_to_x: &impl Fn(usize) -> f64,
-        price_to_y: &impl Fn(f64) -> f64,
-        bar_width: f64,
-        dpr: f64,
-    ) {
-        let series = &self.config.series;
-        let theme = &self.config.theme;
+/// Width of the column right of the price scale that holds the secondary
+/// axes of the scale groups in `subpanes`, zero when none shows one
+///
+/// `height` is the height the bar panes share.
+fn secondary_axis_width<'i>(
+    config: &ChartConfig,
+    subpanes: impl Iterator<Item = &'i Indicator>,
+    bars: &[Bar],
+    height: f64,
+) -> f64 {
+    if !config.show_price_scale || bars.is_empty() {
+        return 0.0;
+    }
+    let sizing = config.sizing();
+    let measure =
+        |text: &str| estimate_text_width(text, sizing.label_font_size(), FontWeight::Normal);
+    let label = subpanes
+        .flat_map(|indicator| {
+            let pane_height = height * indicator.placement.height_ratio();
+            indicator
+                .scale_groups
+                .iter()
+                .filter(|group| group.axis)
+                .map(move |group| {
+                    let (min, max) = scale_group_range(indicator, group, bars);
+                    PriceScale::new(min, max).label_width(pane_height, measure)
+                })
+        })
+        .fold(None, |widest: Option<f64>, w| {
+            Some(widest.map_or(w, |x| x.max(w)))
+        });
+    label.map_or(0.0, |label| {
+        let width = sizing.tick_length + 2.0 + label + PRICE_SCALE_PADDING_OUTER;
+        (width / PRICE_SCALE_WIDTH_STEP).ceil() * PRICE_SCALE_WIDTH_STEP
+    })
+}
 
-        match series.series_type {
-            SeriesType::Candlestick | SeriesType::HollowCandlestick => {
-                let data: Vec<CandlestickData> = self
-                    .bars
-                    .iter()
-                    .map(|b| CandlestickData {
-                        bar: *b,
-                        color: None,
-                        border_color: None,
-                        wick_color: None,
-                    })
-                    .collect();
+/// Labels of a scale group's range in the column starting at `x`, left of
+/// which the pane's own price scale sits, in the color of the group's
+/// first vector
+fn draw_secondary_axis(
+    backend: &mut SvgBackend,
+    config: &ChartConfig,
+    indicator: &Indicator,
+    group: &ScaleGroup,
+    (min, max): (f64, f64),
+    pane: Rect,
+    x: f64,
+) {
+    let color = indicator
+        .vectors
+        .iter()
+        .find(|v| v.scale_group.as_deref() == Some(group.id.as_str()))
+        .and_then(|v| Color::from_css(v.style.primary_color()))
+        .or_else(|| Color::from_css(&config.theme.text_color))
+        .unwrap_or(Color::rgb(180, 180, 180));
+    let sizing = config.sizing();
+    backend.crisp_line(
+        Point::new(x, pane.y),
+        Point::new(x, pane.bottom()),
+        &LineStyle::solid(color.with_alpha(0.4), 1.0),
+    );
+
+    let scale = PriceScale::new(min, max);
+    let font_size = scale
+        .calc_font_size(pane.height)
+        .min(sizing.label_font_size());
+    let style = TextStyle {
+        color,
+        font_size,
+        align: TextAlign::Left,
+        baseline: TextBaseline::Middle,
+        ..Default::default()
+    };
+    for (tick, y) in scale.label_ticks(pane.y, pane.height, font_size, &[]) {
+        backend.crisp_line(
+            Point::new(x, y),
+            Point::new(x + sizing.tick_length, y),
+            &LineStyle::solid(color, 1.0),
+        );
+        let label = scale.format_price(tick, pane.height);
+        backend.text(&label, Point::new(x + sizing.tick_length + 2.0, y), &style);
+    }
+}
 
-                let options = CandlestickStyleOptions {
-                    up_color: theme.up_color.clone(),
-                    down_color: theme.down_color.clone(),
-                    wick_visible: true,
-                    wick_color: String::new(),
-                    wick_up_color: theme.up_color.clone(),
-                    wick_down_color: theme.down_color.clone(),
-                    border_visible: series.series_type == SeriesType::HollowCandlestick,
-                    border_color: String::new(),
-                    border_up_color: theme.up_color.clone(),
-                    border_down_color: theme.down_color.clone(),
-                };
+/// A time-scale label: where its tick is and where its text is centered
+struct TimeLabel {
+    tick_x: f64,
+    center: f64,
+    width: f64,
+    weight: TickMarkWeight,
+    text: String,
+}
 
-                render_candlesticks(batch, &data, &options, bar_to_x, price_to_y, bar_width, dpr);
-            }
-            SeriesType::Line => {
-                let data: Vec<LineData> = self
-                    .bars
-                    .iter()
-                    .map(|b| LineData {
-                        point: SingleValue {
-                            timestamp: b.timestamp,
-                            value: b.close,
-                        },
-                        color: None,
-                    })
-                    .collect();
-
-                let options = LineStyleOptions {
-                    color: series
-                        .style
-                        .color
-                        .clone()
-                        .unwrap_or_else(|| theme.up_color.clone()),
-                    ..Default::default()
-                };
-                render_line(batch, &data, &options, bar_to_x, price_to_y, dpr);
-            }
-            SeriesType::Area => {
-                // Render as line with fill (simplified)
-                let data: Vec<LineData> = self
-                    .bars
-                    .iter()
-                    .map(|b| LineData {
-                        point: SingleValue {
-                            timestamp: b.timestamp,
-                            value: b.close,
-                        },
-                        color: None,
-                    })
-                    .collect();
-
-                let options = LineStyleOptions {
-                    color: series
-                        .style
-                        .color
-                        .clone()
-                        .unwrap_or_else(|| theme.up_color.clone()),
-                    ..Default::default()
-                };
-                render_line(batch, &data, &options, bar_to_x, price_to_y, dpr);
-            }
-            _ => {
-                // Default: candlesticks
-                let data: Vec<CandlestickData> = self
-                    .bars
-                    .iter()
-                    .map(|b| CandlestickData {
-                        bar: *b,
-                        color: None,
-                        border_color: None,
-                        wick_color: None,
-                    })
-                    .collect();
+impl TimeLabel {
+    fn new(tick_x: f64, text: String, weight: TickMarkWeight, font_size: f64) -> Self {
+        Self {
+            tick_x,
+            center: tick_x,
+            width: estimate_text_width(&text, font_size, FontWeight::Normal),
+            weight,
+            text,
+        }
+    }
 
-                let options = CandlestickStyleOptions {
-                    up_color: theme.up_color.clone(),
-                    down_color: theme.down_color.clone(),
-                    wick_visible: true,
-                    wick_color: String::new(),
-                    wick_up_color: theme.up_color.clone(),
-                    wick_down_color: theme.down_color.clone(),
-                    border_visible: false,
-                    border_color: String::new(),
-                    border_up_color: theme.up_color.clone(),
-                    border_down_color: theme.down_color.clone(),
-                };
+    fn overlaps(&self, other: &TimeLabel, gap: f64) -> bool {
+        (self.center - other.center).abs() < (self.width + other.width) / 2.0 + gap
+    }
+}
 
-                render_candlesticks(batch, &data, &options, bar_to_x, price_to_y, bar_width, dpr);
-            }
-        }
+/// Fit time labels between `left` and `right`
+///
+/// Labels are shifted to end flush with the edges rather than being cut
+/// off. The first and last always stay; interior labels follow, heaviest
+/// first, as long as they keep `gap` from the ones already placed.
+fn place_time_labels(labels: Vec<TimeLabel>, left: f64, right: f64, gap: f64) -> Vec<TimeLabel> {
+    let mut labels: Vec<TimeLabel> = labels
+        .into_iter()
+        .filter(|label| label.width <= right - left)
+        .map(|mut label| {
+            let half = label.width / 2.0;
+            label.center = label.tick_x.clamp(left + half, right - half);
+            label
+        })
+        .collect();
+    if labels.len() <= 1 {
+        return labels;
     }
 
-    /// Render overlay indicators (on main chart, share price Y scale)
-    fn render_overlay_indicators(
-        &self,
-        backend: &mut SvgBackend,
-        overlays: &[&Indicator],
-        bar_to_x: &impl Fn(usize) -> f64,
-        price_to_y: &impl Fn(f64) -> f64,
-        _dpr: f64,
-    ) {
-        for indicator in overlays {
-            for vector in &indicator.vectors {
-                self.render_vector(backend, vector, bar_to_x, price_to_y, 0.0);
-            }
+    let last = labels.pop().unwrap();
+    let mut rest = labels.split_off(1);
+    let mut placed = labels;
+    if !placed[0].overlaps(&last, gap) {
+        placed.push(last);
+    }
+    // Stable, so equal weights keep their left-to-right order
+    rest.sort_by_key(|label| std::cmp::Reverse(label.weight));
+    for label in rest {
+        if placed.iter().all(|other| !label.overlaps(other, gap)) {
+            placed.push(label);
         }
     }
+    placed.sort_by(|a, b| a.tick_x.total_cmp(&b.tick_x));
+    placed
+}
 
-    /// Render overlay_bottom indicators (at bottom of main chart with own Y scale)
-    fn render_overlay_bottom_indicators(
-        &self,
-        backend: &mut SvgBackend,
-        indicators: &[&Indicator],
-        bar_to_x: &impl Fn(usize) -> f64,
-        main_height: f64,
-        _chart_width: f64,
-        _dpr: f64,
-    ) {
-        for indicator in indicators {
-            let height_ratio = indicator.placement.height_ratio();
-            let indicator_height = main_height * height_ratio;
-            let y_bottom = main_height;
+/// Height taken from the bottom of the main pane by overlay_bottom indicators
+/// that reserve space. They all share one strip, so the tallest one wins.
+fn reserved_strip_height(overlay_bottoms: &[&Indicator], main_height: f64) -> f64 {
+    overlay_bottoms
+        .iter()
+        .filter(|ind| ind.placement.reserves_space())
+        .map(|ind| ind.placement.strip_height(main_height))
+        .fold(0.0, f64::max)
+}
 
-            // For Volume-like indicators: if vector.values is empty, use bars data
-            let has_data = indicator.vectors.iter().any(|v| !v.values.is_empty());
+/// Candlestick data with the config's per-bar color overrides applied
+///
+/// A custom color replaces the theme up/down color for the body, wick
+/// and border. Bars without an entry fall back to the theme.
+fn candlestick_data(config: &ChartConfig, bars: &[Bar]) -> Vec<CandlestickData> {
+    bars.iter()
+        .enumerate()
+        .map(|(i, b)| {
+            let color = config.bar_colors.get(i).cloned().flatten();
+            CandlestickData {
+                bar: *b,
+                border_color: color.clone(),
+                wick_color: color.clone(),
+                color,
+            }
+        })
+        .collect()
+}
 
-            if has_data {
-                // Use indicator's own values
-                let (range_min, range_max) = self.calculate_indicator_range(indicator);
-                let value_to_y = |v: f64| -> f64 {
-                    if range_max <= range_min {
-                        return y_bottom;
-                    }
-                    let ratio = (v - range_min) / (range_max - range_min);
-                    y_bottom - ratio * indicator_height
-                };
-                let zero_y = value_to_y(0.0);
+/// Candlestick options from the theme colors and the series style
+///
+/// Hollow candles keep their border; wick width and the dense-candle
+/// fallback come from the series style.
+fn candlestick_style_options(config: &ChartConfig) -> CandlestickStyleOptions {
+    let theme = &config.theme;
+    let style = &config.series.style;
+    let or_up = |color: &Option<String>| color.clone().unwrap_or_else(|| theme.up_color.clone());
+    let or_down =
+        |color: &Option<String>| color.clone().unwrap_or_else(|| theme.down_color.clone());
+    CandlestickStyleOptions {
+        up_color: theme.up_color.clone(),
+        down_color: theme.down_color.clone(),
+        wick_visible: true,
+        wick_color: String::new(),
+        wick_up_color: or_up(&theme.wick_up_color),
+        wick_down_color: or_down(&theme.wick_down_color),
+        border_visible: config.series.series_type == SeriesType::HollowCandlestick
+            || theme.candle_border_up_color.is_some()
+            || theme.candle_border_down_color.is_some(),
+        border_color: String::new(),
+        border_up_color: or_up(&theme.candle_border_up_color),
+        border_down_color: or_down(&theme.candle_border_down_color),
+        wick_width: style.wick_width,
+        wick_width_ratio: style.wick_width_ratio,
+        density_threshold: style.dense_threshold,
+        dense_mode: style.dense_candles.unwrap_or_default(),
+        ha_wick_source: HaWickSource::Smoothed,
+        candle_width: style.candle_width.unwrap_or_default(),
+    }
+}
 
-                for vector in &indicator.vectors {
-                    self.render_vector(backend, vector, bar_to_x, &value_to_y, zero_y);
-                }
-            } else {
-                // Auto-populate from bars (Volume indicator)
-                self.render_volume_from_bars(
-                    backend,
-                    indicator,
-                    bar_to_x,
-                    y_bottom,
-                    indicator_height,
-                );
-            }
-        }
+/// Heikin-Ashi options from the theme colors and the series style
+///
+/// Unlike plain candles there is no border or dense-candle fallback; wicks
+/// follow `ha_wick_source`.
+fn heikin_ashi_style_options(config: &ChartConfig) -> CandlestickStyleOptions {
+    let theme = &config.theme;
+    let style = &config.series.style;
+    CandlestickStyleOptions {
+        up_color: theme.up_color.clone(),
+        down_color: theme.down_color.clone(),
+        wick_color: String::new(),
+        wick_up_color: theme
+            .wick_up_color
+            .clone()
+            .unwrap_or(theme.up_color.clone()),
+        wick_down_color: theme
+            .wick_down_color
+            .clone()
+            .unwrap_or(theme.down_color.clone()),
+        wick_width: style.wick_width,
+        wick_width_ratio: style.wick_width_ratio,
+        ha_wick_source: style.ha_wick_source.unwrap_or_default(),
+        candle_width: style.candle_width.unwrap_or_default(),
+        ..Default::default()
     }
+}
 
-    /// Render Volume indicator using bar data directly
-    fn render_volume_from_bars(
-        &self,
-        backend: &mut SvgBackend,
-        indicator: &Indicator,
-        bar_to_x: &impl Fn(usize) -> f64,
-        y_bottom: f64,
-        indicator_height: f64,
-    ) {
-        if self.bars.is_empty() {
-            return;
+/// OHLC bar options from the theme colors and the series style
+fn bar_style_options(config: &ChartConfig) -> BarStyleOptions {
+    let style = &config.series.style;
+    BarStyleOptions {
+        up_color: config.theme.up_color.clone(),
+        down_color: config.theme.down_color.clone(),
+        show_open_tick: style.show_open_tick.unwrap_or(true),
+        show_close_tick: style.show_close_tick.unwrap_or(true),
+        tick_length: style.tick_length,
+        ..Default::default()
+    }
+}
+
+/// Dividing value of a baseline series: the configured one, which may be
+/// zero or negative, else the mean close
+fn baseline_value(series: &SeriesConfig, bars: &[Bar]) -> f64 {
+    series.style.baseline_value.unwrap_or_else(|| {
+        if bars.is_empty() {
+            0.0
+        } else {
+            bars.iter().map(|b| b.close).sum::<f64>() / bars.len() as f64
         }
+    })
+}
 
-        // Find max volume for scaling
-        let max_vol = self
-            .bars
-            .iter()
-            .map(|b| b.volume)
-            .filter(|v| !v.is_nan())
-            .fold(0.0_f64, f64::max);
+/// Fill base of an area series, which autoscale keeps in view
+fn area_base(series: &SeriesConfig) -> Option<f64> {
+    (series.series_type == SeriesType::Area)
+        .then_some(series.style.area_base)
+        .flatten()
+        .filter(|base| base.is_finite())
+}
 
-        if max_vol <= 0.0 {
-            return;
-        }
+/// Horizontal placement of bars across a pane
+///
+/// Slot `s` is centered at `x_offset + spacing * (s + 0.5)`. Indexed spacing
+/// puts bar `i` in slot `i`; proportional spacing advances by each bar's
+/// time step in median bar intervals. Past the last bar, slots continue one
+/// per bar.
+struct BarAxis {
+    x_offset: f64,
+    /// Width of one slot, which is also what candle widths derive from
+    spacing: f64,
+    /// Slot of each bar under proportional spacing
+    slots: Option<Vec<f64>>,
+}
 
-        let value_to_y = |v: f64| -> f64 {
-            let ratio = v / max_vol;
-            y_bottom - ratio * indicator_height
+impl BarAxis {
+    /// Fit `bars` plus the config's right offset into `width` pixels
+    fn new(bars: &[Bar], config: &ChartConfig, width: f64, x_offset: f64) -> Self {
+        let slots = match config.time_spacing {
+            TimeSpacing::Indexed => None,
+            TimeSpacing::Proportional => compute::median_bar_interval(bars).map(|interval| {
+                let steps = bars
+                    .windows(2)
+                    .map(|w| ((w[1].timestamp - w[0].timestamp) as f64 / interval as f64).max(1.0));
+                std::iter::once(0.0)
+                    .chain(steps.scan(0.0, |slot, step| {
+                        *slot += step;
+                        Some(*slot)
+                    }))
+                    .collect::<Vec<f64>>()
+            }),
         };
+        let slot_count = slots
+            .as_ref()
+            .and_then(|slots| slots.last())
+            .map_or(bars.len() as f64, |last| last + 1.0);
+        Self {
+            x_offset,
+            spacing: width / (slot_count + config.right_offset.max(0.0)),
+            slots,
+        }
+    }
 
-        // Get histogram style colors
-        let (up_color, down_color, bar_width_ratio) = indicator
-            .vectors
-            .first()
-            .map(|v| match &v.style {
-                VectorStyle::Histogram {
-                    up_color,
-                    down_color,
-                    bar_width_ratio,
-                } => (up_color.clone(), down_color.clone(), *bar_width_ratio),
-                _ => ("#26a69a".to_string(), "#ef5350".to_string(), 0.8),
-            })
-            .unwrap_or(("#26a69a".to_string(), "#ef5350".to_string(), 0.8));
-
-        let up = Color::from_css(&up_color).unwrap_or(Color::rgb(38, 166, 154));
-        let down = Color::from_css(&down_color).unwrap_or(Color::rgb(239, 83, 80));
+    fn slot(&self, i: usize) -> f64 {
+        match &self.slots {
+            Some(slots) => match slots.get(i) {
+                Some(&slot) => slot,
+                None => slots
+                    .last()
+                    .map_or(0.0, |last| last + (i + 1 - slots.len()) as f64),
+            },
+            None => i as f64,
+        }
+    }
 
-        let bar_spacing = self.config.width as f64 / self.bars.len() as f64;
-        let bar_width = bar_spacing * bar_width_ratio;
+    /// Center of bar `i`
+    fn x(&self, i: usize) -> f64 {
+        self.x_offset + self.spacing * (self.slot(i) + 0.5)
+    }
 
-        for (i, bar) in self.bars.iter().enumerate() {
-            let vol = bar.volume;
-            if vol.is_nan() || vol <= 0.0 {
-                continue;
+    /// Bar of `len` whose center is nearest to `x`
+    fn bar_at(&self, x: f64, len: usize) -> Option<usize> {
+        let last = len.checked_sub(1)?;
+        let slot = (x - self.x_offset) / self.spacing - 0.5;
+        let index = match &self.slots {
+            Some(slots) => {
+                let next = slots.partition_point(|&s| s < slot).min(last);
+                match next.checked_sub(1) {
+                    Some(prev) if slot - slots[prev] < slots[next] - slot => prev,
+                    _ => next,
+                }
             }
+            None => slot.round().max(0.0) as usize,
+        };
+        Some(index.min(last))
+    }
+}
 
-            let x = bar_to_x(i);
-            let y = value_to_y(vol);
-            let bar_h = (y_bottom - y).max(1.0);
+/// Round times for time-scale labels between `start` and `end` (unix
+/// seconds), spaced at least 90px apart across `width`, as `(timestamp, step)`
+fn tick_time_marks(start: f64, end: f64, width: f64) -> Vec<(i64, i64)> {
+    const STEPS: [i64; 17] = [
+        1,
+        2,
+        5,
+        10,
+        15,
+        30,
+        MINUTE,
+        2 * MINUTE,
+        5 * MINUTE,
+        15 * MINUTE,
+        30 * MINUTE,
+        HOUR,
+        2 * HOUR,
+        4 * HOUR,
+        12 * HOUR,
+        DAY,
+        7 * DAY,
+    ];
+    let max_marks = (width / 90.0).floor().max(1.0);
+    let step = STEPS
+        .into_iter()
+        .find(|&step| (end - start) / step as f64 <= max_marks)
+        .unwrap_or_else(|| ((end - start) / max_marks / DAY as f64).ceil() as i64 * DAY);
+    let first = (start / step as f64).ceil() as i64 * step;
+    (0..)
+        .map(|i| first + i * step)
+        .take_while(|&ts| ts as f64 <= end)
+        .map(|ts| (ts, step))
+        .collect()
+}
 
-            // Color based on bar direction
-            let color = if bar.close >= bar.open { up } else { down };
+/// Center of a fractional bar index: interpolated between the neighbouring
+/// bars, and extended through bars 0 and 1 before the first one
+fn fractional_bar_x(bar_to_x: &impl Fn(usize) -> f64, bar: f64) -> f64 {
+    let floor = bar.floor().max(0.0);
+    let i = floor as usize;
+    let (x0, x1) = (bar_to_x(i), bar_to_x(i + 1));
+    x0 + (x1 - x0) * (bar - floor)
+}
+
+/// Width of a histogram column: `ratio` of the pane's slot `spacing`, i.e.
+/// the candles' spacing, at least 1px
+///
+/// Taken from the slot rather than the distance between two bars, which
+/// proportional spacing widens across session gaps.
+fn histogram_bar_width(spacing: f64, ratio: f64) -> f64 {
+    (spacing * ratio).max(1.0)
+}
 
-            backend.fill_rect(Rect::new(x - bar_width / 2.0, y, bar_width, bar_h), color);
+/// Whether a drawn command passes within `tolerance` of `point`
+fn command_near(cmd: &RenderCommand, point: Point, tolerance: f64) -> bool {
+    let near_polyline = |points: &[Point], width: f64| {
+        points
+            .windows(2)
+            .any(|seg| point.distance_to_segment(seg[0], seg[1]) <= tolerance + width / 2.0)
+    };
+    match cmd {
+        RenderCommand::Line { from, to, style } => near_polyline(&[*from, *to], style.width),
+        RenderCommand::Polyline { points, style } => near_polyline(points, style.width),
+        RenderCommand::StrokePath { path, style } => {
+            // Straight paths by segment; curves fall back to their bounds
+            let mut segments = Vec::new();
+            let mut start = None;
+            for command in path.commands() {
+                match *command {
+                    PathCommand::MoveTo(p) => {
+                        if near_polyline(&segments, style.width) {
+                            return true;
+                        }
+                        segments = vec![p];
+                        start = Some(p);
+                    }
+                    PathCommand::LineTo(p) => segments.push(p),
+                    PathCommand::Close => segments.extend(start),
+                    _ => return path.bounds().expand(tolerance).contains(point),
+                }
+            }
+            near_polyline(&segments, style.width)
         }
+        cmd => cmd
+            .bounds()
+            .is_some_and(|bounds| bounds.expand(tolerance).contains(point)),
     }
+}
 
-    /// Render a single indicator vector based on its VectorStyle
-    fn render_vector(
-        &self,
-        backend: &mut SvgBackend,
-        vector: &crate::model::IndicatorVector,
-        bar_to_x: &impl Fn(usize) -> f64,
-        value_to_y: &impl Fn(f64) -> f64,
-        zero_y: f64, // For histogram bars
-    ) {
-        match &vector.style {
-            VectorStyle::Line {
-                color,
-                width,
-                dashed,
-            } => {
-                let points: Vec<Point> = vector
-                    .values
-                    .iter()
-                    .enumerate()
-                    .filter(|&(_, &v)| !v.is_nan())
-                    .map(|(i, &v)| Point::new(bar_to_x(i), value_to_y(v)))
-                    .collect();
+/// Suffix the id and name of an indicator computed on a non-close source,
+/// e.g. `sma_20_hl2` / "SMA 20 hl2"
+fn sourced(mut indicator: Indicator, source: Source) -> Indicator {
+    if source != Source::Close {
+        indicator.id = format!("{}_{}", indicator.id, source.as_str());
+        indicator.name = format!("{} {}", indicator.name, source.as_str());
+    }
+    if let Some(params) = &mut indicator.params {
+        params.set_source(source);
+    }
+    indicator
+}
 
-                if points.len() >= 2 {
-                    let c = Color::from_css(color).unwrap_or(Color::WHITE);
-                    let style = if *dashed {
-                        LineStyle::dashed(c, *width, 4.0, 4.0)
-                    } else {
-                        LineStyle::solid(c, *width)
-                    };
-                    backend.polyline(&points, &style);
-                }
-            }
-            VectorStyle::Histogram {
-                up_color,
-                down_color,
-                bar_width_ratio,
-            } => {
-                let bar_spacing = self.config.width as f64 / self.bars.len().max(1) as f64;
-                let bar_width = bar_spacing * bar_width_ratio;
-
-                for (i, &v) in vector.values.iter().enumerate() {
-                    if v.is_nan() {
-                        continue;
-                    }
+/// Bars as rendered: sorted when `config.sort_bars` is on, with timestamps
+/// converted from `unit` to seconds
+fn prepared_bars<'b>(config: &ChartConfig, bars: &'b [Bar], unit: TimestampUnit) -> Cow<'b, [Bar]> {
+    if config.sort_bars && !compute::bars_are_sorted(bars) {
+        let sorted = compute::sort_bars(bars, config.duplicate_merge);
+        return Cow::Owned(unit.normalize_bars(&sorted).into_owned());
+    }
+    unit.normalize_bars(bars)
+}
 
-                    let x = bar_to_x(i);
-                    let y = value_to_y(v);
+// =============================================================================
+// ChartRenderer - Renders ChartConfig to SVG
+// =============================================================================
 
-                    // Use directions vector if available, otherwise fallback to value sign
-                    let is_up = vector.direction_at(i).unwrap_or(v >= 0.0);
-                    let bar_color = if is_up {
-                        Color::from_css(up_color).unwrap_or(Color::rgb(38, 166, 154))
-                    } else {
-                        Color::from_css(down_color).unwrap_or(Color::rgb(239, 83, 80))
-                    };
+/// Renderer that takes a ChartConfig and produces SVG output
+pub struct ChartRenderer<'a> {
+    /// Config as given, or a copy cut down to the replayed bars
+    config: Cow<'a, ChartConfig>,
+    /// Config as given, validated against all bars for render stats
+    untrimmed: &'a ChartConfig,
+    /// Number of bars before any replay or scrolling
+    bar_count: usize,
+    /// Bars with timestamps in unix seconds
+    bars: Cow<'a, [Bar]>,
+    /// Unit of the input timestamps, with `Auto` settled on the bars
+    timestamp_unit: TimestampUnit,
+    registry: Option<&'a PrimitiveRegistry>,
+    /// Main price range of the whole data set, kept while replaying
+    locked_bounds: Option<(f64, f64)>,
+    /// Scrollbar thumb `(start, width)` over all bars, when
+    /// `min_bar_spacing` left the earliest ones out
+    scroll_thumb: Option<(f64, f64)>,
+    /// Wrap the parts that change on their own in replaceable groups
+    grouped: bool,
+    /// Width of the price scale column, settled on all bars before any
+    /// replay or scrolling so it holds still between frames
+    price_scale_width: f64,
+    /// Bars `min_bar_spacing` cut from the start, so indexes reported
+    /// back (e.g. by [`Self::legend_data`]) stay those of the full data
+    first_bar: usize,
+}
+
+impl<'a> ChartRenderer<'a> {
+    /// Create a new renderer with config and bar data
+    ///
+    /// Bar timestamps are read in `config.timestamp_unit`. With
+    /// `config.sort_bars` the bars are sorted here and indicators with
+    /// recorded parameters are recomputed on them. With `config.replay_to`
+    /// only bars up to that index are drawn.
+    pub fn new(config: &'a ChartConfig, bars: &'a [Bar]) -> Self {
+        let timestamp_unit = config
+            .timestamp_unit
+            .resolve(bars.iter().map(|b| b.timestamp));
+        let resorted = config.sort_bars && !compute::bars_are_sorted(bars);
+        let bars = prepared_bars(config, bars, timestamp_unit);
+        let renderer = Self {
+            config: Cow::Borrowed(config),
+            untrimmed: config,
+            bar_count: bars.len(),
+            bars,
+            timestamp_unit,
+            registry: None,
+            locked_bounds: None,
+            scroll_thumb: None,
+            grouped: false,
+            price_scale_width: 0.0,
+            first_bar: 0,
+        }
+        .recomputed(resorted)
+        .fitted_price_scale();
+        match config.replay_to {
+            Some(last) => renderer.replayed_to(last).scrolled_to_fit(),
+            None => renderer.scrolled_to_fit(),
+        }
+    }
 
-                    let bar_height = (zero_y - y).abs().max(1.0);
-                    let bar_y = if v >= 0.0 { y } else { zero_y };
+    /// Re-derive indicators whose parameters changed since their values
+    /// were computed, see [`Indicator::set_property`], or all of them when
+    /// the bars were `resorted`
+    fn recomputed(mut self, resorted: bool) -> Self {
+        let stale = |i: &Indicator| resorted || i.needs_recompute;
+        if !self.config.indicators.iter().any(stale) {
+            return self;
+        }
+        let config = self.config.to_mut();
+        for indicator in config.indicators.iter_mut().filter(|i| stale(i)) {
+            indicator.recompute(&self.bars);
+        }
+        self
+    }
 
-                    backend.fill_rect(
-                        Rect::new(x - bar_width / 2.0, bar_y, bar_width, bar_height),
-                        bar_color,
-                    );
+    /// Keep only the latest bars that fit at `config.min_bar_spacing`,
+    /// as if scrolled to the end
+    ///
+    /// Earlier bars, their indicator values and signals are dropped and
+    /// bar-anchored primitives shift along, so everything keeps its place
+    /// relative to the bars that remain. Only real bars are counted: the
+    /// right offset is room past them, not slots that push bars out.
+    fn scrolled_to_fit(mut self) -> Self {
+        let Some(min_spacing) = self
+            .config
+            .min_bar_spacing
+            .filter(|spacing| spacing.is_finite() && *spacing > 0.0)
+        else {
+            return self;
+        };
+        if self.bars.is_empty() {
+            return self;
+        }
+        let chart_width = self.config.width as f64 - self.price_scale_width();
+
+        let mut time_scale = TimeScale::new(chart_width);
+        time_scale.set_min_bar_spacing(min_spacing);
+        time_scale.set_bar_count(self.bars.len());
+        time_scale.fit_all(0.0, f64::INFINITY);
+        time_scale.scroll_to_end();
+        let Some(thumb) = time_scale.scroll_thumb() else {
+            return self;
+        };
+        let first = (time_scale.view_start.max(0.0) as usize).min(self.bars.len() - 1);
+        self.scroll_thumb = Some(thumb);
+        self.first_bar = first;
+
+        let config = self.config.to_mut();
+        config
+            .bar_colors
+            .drain(..first.min(config.bar_colors.len()));
+        config.hover_bar = config.hover_bar.and_then(|bar| bar.checked_sub(first));
+        for indicator in &mut config.indicators {
+            indicator.drop_first(first);
+        }
+        config.signals.retain(|signal| signal.bar_index >= first);
+        for signal in &mut config.signals {
+            signal.bar_index -= first;
+        }
+        for primitive in &mut config.primitives {
+            if primitive.anchor == PointAnchor::BarIndex {
+                for point in &mut primitive.points {
+                    point.0 -= first as f64;
                 }
             }
-            VectorStyle::Area {
-                color,
-                fill_alpha: _,
-                line_width,
-            } => {
-                // Draw filled area
-                let points: Vec<Point> = vector
-                    .values
-                    .iter()
-                    .enumerate()
-                    .filter(|&(_, &v)| !v.is_nan())
-                    .map(|(i, &v)| Point::new(bar_to_x(i), value_to_y(v)))
-                    .collect();
-
-                if points.len() >= 2 {
-                    let c = Color::from_css(color).unwrap_or(Color::WHITE);
-                    // Line on top
-                    backend.polyline(&points, &LineStyle::solid(c, *line_width));
-                    // TODO: fill area below line
-                }
+        }
+        if let Some(strategy) = &mut config.strategy {
+            for indicator in &mut strategy.indicators {
+                indicator.drop_first(first);
             }
-            VectorStyle::Dots {
-                color,
-                radius,
-                filled,
-            } => {
-                let c = Color::from_css(color).unwrap_or(Color::WHITE);
-                for (i, &v) in vector.values.iter().enumerate() {
-                    if v.is_nan() {
-                        continue;
-                    }
-                    let center = Point::new(bar_to_x(i), value_to_y(v));
-                    if *filled {
-                        backend.fill_circle(center, *radius, c);
-                    } else {
-                        backend.stroke_circle(center, *radius, &LineStyle::solid(c, 1.0));
-                    }
+            strategy.signals.retain(|signal| signal.bar >= first as f64);
+            for signal in &mut strategy.signals {
+                signal.bar -= first as f64;
+            }
+            for primitive in &mut strategy.primitives {
+                for point in &mut primitive.points {
+                    point.0 -= first as f64;
                 }
             }
-            VectorStyle::Step { color, width } => {
-                let c = Color::from_css(color).unwrap_or(Color::WHITE);
-                let style = LineStyle::solid(c, *width);
+        }
 
-                let mut prev: Option<(f64, f64)> = None;
-                for (i, &v) in vector.values.iter().enumerate() {
-                    if v.is_nan() {
-                        continue;
-                    }
-                    let x = bar_to_x(i);
-                    let y = value_to_y(v);
-
-                    if let Some((px, py)) = prev {
-                        // Horizontal then vertical (step)
-                        backend.line(Point::new(px, py), Point::new(x, py), &style);
-                        backend.line(Point::new(x, py), Point::new(x, y), &style);
-                    }
-                    prev = Some((x, y));
-                }
-            }
-            VectorStyle::Cloud { .. } => {
-                // Cloud requires two vectors - skip for now
+        self.bars = match self.bars {
+            Cow::Borrowed(bars) => Cow::Borrowed(&bars[first..]),
+            Cow::Owned(mut bars) => {
+                bars.drain(..first);
+                Cow::Owned(bars)
             }
-            VectorStyle::Hidden => {
-                // Don't render
-            }
-        }
+        };
+        self
     }
 
-    fn render_primitives(
-        &self,
-        backend: &mut SvgBackend,
-        bar_to_x: &impl Fn(usize) -> f64,
-        price_to_y: &impl Fn(f64) -> f64,
-        dpr: f64,
-        pane_id: Option<usize>,
-    ) {
-        let registry = PrimitiveRegistry::global().read().unwrap();
+    /// Cut the chart down to bars `0..=last`, as in a bar replay
+    ///
+    /// Later bars, indicator values and signals are dropped. Their slots
+    /// stay empty (added to the right offset), so bars keep their x position
+    /// from frame to frame, and with `config.replay_lock_scale` the price
+    /// scale keeps the range of the whole data set.
+    fn replayed_to(mut self, last: usize) -> Self {
+        let len = (last + 1).min(self.bars.len());
+        let hidden = self.bars.len() - len;
+        if hidden == 0 {
+            return self;
+        }
+        if self.config.replay_lock_scale && self.locked_bounds.is_none() {
+            self.locked_bounds = Some(self.main_price_bounds());
+        }
 
-        for prim_config in &self.config.primitives {
-            // Filter by pane
-            match (pane_id, &prim_config.pane_id) {
-                (None, None) => {}                        // Main pane, no pane_id specified
-                (Some(id), Some(pid)) if *pid == id => {} // Matching pane
-                _ => continue,                            // Skip non-matching
+        let config = self.config.to_mut();
+        config.right_offset += hidden as f64;
+        config.bar_colors.truncate(len);
+        config.hover_bar = config.hover_bar.filter(|&bar| bar < len);
+        for indicator in &mut config.indicators {
+            indicator.truncate(len);
+        }
+        config.signals.retain(|signal| signal.bar_index < len);
+        if let Some(strategy) = &mut config.strategy {
+            for indicator in &mut strategy.indicators {
+                indicator.truncate(len);
             }
+            strategy.signals.retain(|signal| signal.bar < len as f64);
+        }
 
-            // Create primitive from registry
-            if let Some(primitive) = registry.create(
-                &prim_config.type_id,
-                &prim_config.points,
-                Some(&prim_config.color),
-            ) {
-                // Create render context adapter
-                let mut ctx = SvgRenderContext::new(
-                    backend,
-                    bar_to_x,
-                    price_to_y,
-                    dpr,
-                    self.config.width as f64,
-                    self.config.height as f64,
-                );
-
-                // Render the primitive
-                primitive.render(&mut ctx, false);
+        self.bars = match self.bars {
+            Cow::Borrowed(bars) => Cow::Borrowed(&bars[..len]),
+            Cow::Owned(mut bars) => {
+                bars.truncate(len);
+                Cow::Owned(bars)
             }
-        }
+        };
+        self
     }
 
-    fn render_signals(
-        &self,
-        backend: &mut SvgBackend,
-        bar_to_x: &impl Fn(usize) -> f64,
-        price_to_y: &impl Fn(f64) -> f64,
-        _dpr: f64,
-    ) {
-        for signal in &self.config.signals {
-            let x = bar_to_x(signal.bar_index);
-            let y = price_to_y(signal.price);
-
-            let default_color = match signal.signal_type {
-                crate::primitives::SignalType::Buy | crate::primitives::SignalType::Entry => {
-                    "#26a69a"
-                }
-                crate::primitives::SignalType::Sell | crate::primitives::SignalType::Exit => {
-                    "#ef5350"
-                }
-                crate::primitives::SignalType::TakeProfit => "#26a69a",
-                crate::primitives::SignalType::StopLoss => "#ef5350",
-                crate::primitives::SignalType::Custom => "#9c27b0",
-            };
-            let color = signal
-                .color
-                .as_deref()
-                .and_then(Color::from_css)
-                .unwrap_or_else(|| Color::from_css(default_color).unwrap());
-            let size = signal.size * 12.0; // size is a multiplier
+    /// One SVG per replay step: each index in `frames` draws bars up to and
+    /// including it
+    ///
+    /// Bars are prepared once and, with `config.replay_lock_scale`, the
+    /// price range is computed once for all frames. Indices past the last
+    /// bar produce no frame.
+    pub fn render_frames(&self, frames: Range<usize>) -> impl Iterator<Item = String> + '_ {
+        let base = self.replay_base();
+        frames
+            .take_while(move |&last| last < self.bars.len())
+            .map(move |last| base.view().replayed_to(last).render_svg())
+    }
 
-            match signal.signal_type {
-                crate::primitives::SignalType::Buy | crate::primitives::SignalType::Entry => {
-                    // Up arrow
-                    self.draw_arrow_up(backend, x, y, size, color);
-                }
-                crate::primitives::SignalType::Sell | crate::primitives::SignalType::Exit => {
-                    // Down arrow
-                    self.draw_arrow_down(backend, x, y, size, color);
-                }
-                crate::primitives::SignalType::TakeProfit => {
-                    // Circle with checkmark feel
-                    backend.fill_circle(Point::new(x, y), size / 2.0, Color::rgb(38, 166, 154));
-                }
-                crate::primitives::SignalType::StopLoss => {
-                    // Circle with X feel
-                    backend.fill_circle(Point::new(x, y), size / 2.0, Color::rgb(239, 83, 80));
-                }
-                crate::primitives::SignalType::Custom => {
-                    // Diamond shape
-                    backend.fill_circle(Point::new(x, y), size / 2.0, color);
-                }
-            }
+    /// Rasterized replay frames, see [`Self::render_frames`] and
+    /// [`Self::render_rgba`]
+    #[cfg(feature = "raster")]
+    pub fn render_frames_rgba(
+        &self,
+        frames: Range<usize>,
+    ) -> impl Iterator<Item = (Vec<u8>, u32, u32)> + '_ {
+        let base = self.replay_base();
+        frames
+            .take_while(move |&last| last < self.bars.len())
+            .map(move |last| base.view().replayed_to(last).render_rgba())
+    }
 
-            // Label if present
-            if let Some(ref label) = signal.label {
-                use crate::render::engine::TextStyle;
-                backend.text(
-                    label,
-                    Point::new(x + size, y),
-                    &TextStyle {
-                        font_family: "sans-serif".into(),
-                        font_size: 10.0,
-                        font_weight: crate::render::engine::FontWeight::Normal,
-                        color,
-                        align: crate::render::engine::TextAlign::Left,
-                        baseline: crate::render::engine::TextBaseline::Middle,
-                    },
-                );
-            }
+    /// This renderer with the locked replay range computed up front
+    fn replay_base(&self) -> ChartRenderer<'_> {
+        let mut base = self.view();
+        if base.config.replay_lock_scale && base.locked_bounds.is_none() {
+            base.locked_bounds = Some(base.main_price_bounds());
         }
+        base
     }
 
-    fn draw_arrow_up(&self, backend: &mut SvgBackend, x: f64, y: f64, size: f64, color: Color) {
-        let half = size / 2.0;
-        let points = vec![
-            Point::new(x, y - half),        // top
-            Point::new(x - half, y + half), // bottom left
-            Point::new(x + half, y + half), // bottom right
-        ];
-        backend.fill_path(&Path::polygon(&points), &FillStyle::solid(color));
+    /// A renderer borrowing this one's config and bars
+    fn view(&self) -> ChartRenderer<'_> {
+        ChartRenderer {
+            config: Cow::Borrowed(&self.config),
+            untrimmed: self.untrimmed,
+            bar_count: self.bar_count,
+            bars: Cow::Borrowed(&self.bars),
+            timestamp_unit: self.timestamp_unit,
+            registry: self.registry,
+            locked_bounds: self.locked_bounds,
+            scroll_thumb: self.scroll_thumb,
+            grouped: self.grouped,
+            price_scale_width: self.price_scale_width,
+            first_bar: self.first_bar,
+        }
     }
 
-    fn draw_arrow_down(&self, backend: &mut SvgBackend, x: f64, y: f64, size: f64, color: Color) {
-        let half = size / 2.0;
-        let points = vec![
-            Point::new(x, y + half),        // bottom
-            Point::new(x - half, y - half), // top left
-            Point::new(x + half, y - half), // top right
-        ];
-        backend.fill_path(&Path::polygon(&points), &FillStyle::solid(color));
+    /// Create primitives from `registry` instead of the config's registry
+    pub fn with_registry(mut self, registry: &'a PrimitiveRegistry) -> Self {
+        self.registry = Some(registry);
+        self
     }
 
-    /// Render a subpane indicator (RSI, MACD, Volume, etc.)
-    fn render_subpane_indicator(
-        &self,
-        backend: &mut SvgBackend,
-        params: SubpaneRenderParams<'_>,
-        bar_to_x: &impl Fn(usize) -> f64,
-    ) {
-        let SubpaneRenderParams {
-            indicator,
-            y_offset,
-            height,
-            width,
-            pane_idx,
-        } = params;
+    /// Registry used for primitives: explicit, then config, then built-in
+    fn registry(&self) -> &PrimitiveRegistry {
+        self.registry
+            .unwrap_or_else(|| self.config.primitive_registry())
+    }
 
-        // Subpane background
-        let subpane_bg =
-            Color::from_css(&self.config.theme.background).unwrap_or(Color::rgb(19, 23, 34));
-        backend.fill_rect(Rect::new(0.0, y_offset, width as f64, height), subpane_bg);
+    /// Render the chart to SVG string
+    pub fn render_svg(&self) -> String {
+        self.draw(false, &mut PhaseClock::stopped()).to_svg()
+    }
 
-        // Separator line
-        let sep_color =
-            Color::from_css(&self.config.theme.grid_color).unwrap_or(Color::rgb(42, 46, 57));
-        backend.line(
-            Point::new(0.0, y_offset),
-            Point::new(width as f64, y_offset),
-            &LineStyle::solid(sep_color, 1.0),
-        );
+    /// Render the chart to SVG, or fail when its size is below
+    /// [`ChartConfig::min_size`]
+    ///
+    /// [`Self::render_svg`] draws only the background at such sizes.
+    pub fn try_render_svg(&self) -> Result<String, ChartError> {
+        self.config.check_dimensions()?;
+        Ok(self.render_svg())
+    }
 
-        // Calculate range based on indicator's IndicatorRange
-        let (range_min, range_max) = self.calculate_indicator_range(indicator);
+    /// Render to SVG with the parts that change on their own wrapped in
+    /// replaceable groups, see [`SvgFragment`]
+    ///
+    /// Groups are `zc-series` and `zc-last-bar` for the main series,
+    /// `zc-indicator-<id>` and `zc-indicator-<id>-last` for each overlay
+    /// and subpane indicator and its last segment (with a subpane's title),
+    /// `zc-price-scale`,
+    /// `zc-price-scale-<id>`,
+    /// `zc-last-price`, `zc-legend`, `zc-hover`, `zc-time-scale` and
+    /// `zc-countdown`.
+    pub fn render_svg_grouped(&self) -> String {
+        let mut view = self.view();
+        view.grouped = true;
+        view.draw(false, &mut PhaseClock::stopped()).to_svg()
+    }
 
-        let value_to_y = |v: f64| -> f64 {
-            let ratio = (v - range_min) / (range_max - range_min);
-            y_offset + height - ratio * height
+    /// Render to SVG and report bar and command counts, output size and
+    /// per-phase timings
+    ///
+    /// Commands are recorded while drawing, which adds a little to the
+    /// measured phases; [`Self::render_svg`] pays for neither.
+    pub fn render_svg_with_stats(&self) -> (String, RenderStats) {
+        let mut clock = PhaseClock::start();
+        let mut backend = self.draw(true, &mut clock);
+        let commands = backend.take_commands();
+        clock.lap(Phase::Layout);
+        let svg = backend.to_svg();
+        clock.lap(Phase::Serialization);
+
+        let mut stats = RenderStats {
+            bars: self.bars.len(),
+            commands: stats::command_counts(&commands),
+            svg_bytes: svg.len(),
+            timings: clock.timings(),
+            warnings: self.untrimmed.validate(self.bar_count),
         };
-
-        let zero_y = value_to_y(0.0);
-
-        // Draw indicator levels (reference lines like RSI 30/70, MACD zero line)
-        for level in &indicator.levels {
-            let y = value_to_y(level.value);
-            let color = Color::from_css(&level.color).unwrap_or(Color::rgb(120, 123, 134));
-            let style = match level.style.as_str() {
-                "dotted" => LineStyle::dashed(color, level.width, 2.0, 2.0),
-                "dashed" => LineStyle::dashed(color, level.width, 4.0, 4.0),
-                _ => LineStyle::solid(color, level.width),
-            };
-            backend.line(Point::new(0.0, y), Point::new(width as f64, y), &style);
+        if backend.non_finite_elements() > 0 {
+            stats.warnings.push(ConfigWarning::NonFiniteGeometry {
+                elements: backend.non_finite_elements(),
+            });
         }
+        (svg, stats)
+    }
 
-        // Draw indicator vectors using their VectorStyle
-        for vector in &indicator.vectors {
-            self.render_vector(backend, vector, bar_to_x, &value_to_y, zero_y);
-        }
+    /// Draw commands of the frame grouped by element, in draw order
+    ///
+    /// Elements are the background, grid, main series, each indicator
+    /// (`"indicator:<id>"`) and primitive (`"primitive:<id>"`, or
+    /// `"primitive[<index>]"` without an id), the scales and so on.
+    pub fn render_elements(&self) -> FrameElements {
+        self.draw(true, &mut PhaseClock::stopped()).take_elements()
+    }
 
-        // Render primitives for this pane
-        self.render_primitives(
-            backend,
-            bar_to_x,
-            &value_to_y,
-            self.config.dpr,
-            Some(pane_idx),
-        );
+    /// Commands that changed since `prev` was drawn, for sending only
+    /// what changed to a remote renderer
+    ///
+    /// Commands are keyed by element and position within it (see
+    /// [`RenderDiff`]), so appending a bar adds its candle and updates the
+    /// scales rather than resending the series. Bars only keep their x
+    /// between frames at a fixed spacing, as in a replay or with
+    /// `min_bar_spacing`; otherwise every bar moves and is updated.
+    pub fn diff_commands(&self, prev: &ChartRenderer<'_>) -> RenderDiff {
+        RenderDiff::between(&prev.render_elements(), &self.render_elements())
     }
 
-    /// Calculate the Y-axis range for an indicator based on its IndicatorRange
-    fn calculate_indicator_range(&self, indicator: &Indicator) -> (f64, f64) {
-        use crate::model::IndicatorRange;
-
-        match &indicator.range {
-            IndicatorRange::Fixed { min, max } => (*min, *max),
-            IndicatorRange::Symmetric => {
-                // Find max absolute value across all vectors
-                let mut max_abs = 0.0_f64;
-                for vector in &indicator.vectors {
-                    for &v in &vector.values {
-                        if !v.is_nan() {
-                            max_abs = max_abs.max(v.abs());
-                        }
-                    }
-                }
-                let padding = max_abs * 0.1;
-                (-(max_abs + padding), max_abs + padding)
-            }
-            IndicatorRange::Price => {
-                // Use the same range as the main price chart (from bars)
-                let mut min = f64::INFINITY;
-                let mut max = f64::NEG_INFINITY;
-                for bar in self.bars {
-                    if !bar.low.is_nan() {
-                        min = min.min(bar.low);
-                    }
-                    if !bar.high.is_nan() {
-                        max = max.max(bar.high);
-                    }
-                }
-                let padding = (max - min) * 0.05;
-                (min - padding, max + padding)
+    /// Rasterize the chart into an RGBA8 pixel buffer
+    ///
+    /// Returns `(pixels, width, height)` in device pixels (`config.width *
+    /// dpr_x` by `config.height * dpr_y`). Pixels are row-major from the
+    /// top-left corner with a stride of `width * 4` bytes, and color channels
+    /// are premultiplied by alpha, as GPU texture uploads expect. Text is not
+    /// rasterized. An empty buffer with zero size is returned when the
+    /// configured size cannot be allocated.
+    #[cfg(feature = "raster")]
+    pub fn render_rgba(&self) -> (Vec<u8>, u32, u32) {
+        match self.rasterize() {
+            Ok(raster) => {
+                let (width, height) = raster.pixel_size();
+                (raster.into_rgba(), width, height)
             }
-            IndicatorRange::Auto => {
-                // Auto-calculate from data
-                let mut min = f64::INFINITY;
-                let mut max = f64::NEG_INFINITY;
+            Err(_) => (Vec::new(), 0, 0),
+        }
+    }
 
-                for vector in &indicator.vectors {
-                    for &v in &vector.values {
-                        if !v.is_nan() {
-                            min = min.min(v);
-                            max = max.max(v);
-                        }
-                    }
-                }
+    /// Rasterize the chart and encode it as PNG
+    ///
+    /// Uses the same pipeline as [`Self::render_rgba`], so text is not drawn.
+    #[cfg(feature = "raster")]
+    pub fn render_png(&self) -> RenderResult<Vec<u8>> {
+        self.rasterize()?.encode_png()
+    }
 
-                // Add padding
-                let range = max - min;
-                if range > 0.0 {
-                    let padding = range * 0.1;
-                    (min - padding, max + padding)
-                } else {
-                    (0.0, 100.0)
-                }
-            }
+    /// Replay the frame's draw commands on a tiny-skia raster backend
+    #[cfg(feature = "raster")]
+    fn rasterize(&self) -> RenderResult<RasterBackend> {
+        let commands = self.draw(true, &mut PhaseClock::stopped()).take_commands();
+        let (dpr_x, dpr_y) = self.config.dpr_xy();
+        let (width, height) = (self.config.width, self.config.height);
+        let mut raster = RasterBackend::with_dpr_xy(width, height, dpr_x, dpr_y)?;
+        raster.execute_batch(&commands);
+        raster.end_frame();
+        Ok(raster)
+    }
+
+    /// Backend for a new frame, recording draw commands when `record` is set
+    fn frame_backend(&self, record: bool) -> SvgBackend {
+        let (width, height) = (self.config.width, self.config.height);
+        let (dpr_x, dpr_y) = self.config.dpr_xy();
+        let mut backend = SvgBackend::with_dpr_xy(width, height, dpr_x, dpr_y);
+        if record {
+            backend.record_commands();
         }
+        if self.grouped {
+            backend.mark_groups();
+        }
+        backend.begin_frame_xy(width as f64, height as f64, dpr_x, dpr_y);
+        backend.set_precision(self.config.svg_precision);
+        backend
     }
 
-    /// Render price scale (Y-axis) on the right side of the chart area
-    fn render_price_scale(
-        &self,
-        backend: &mut SvgBackend,
-        chart_width: f64,
-        y_offset: f64,
-        pane_height: f64,
-        price_min: f64,
-        price_max: f64,
-    ) {
-        let scale_x = chart_width;
-        let scale_width = PRICE_SCALE_WIDTH;
+    /// Draw the whole chart into a finished SVG backend
+    ///
+    /// Seasonal and empty charts are charged to the layout phase as a whole.
+    fn draw(&self, record: bool, clock: &mut PhaseClock) -> SvgBackend {
+        if self.config.check_dimensions().is_err() {
+            return self.draw_background(record);
+        }
+        if self.bars.is_empty() {
+            return match &self.config.ticks {
+                Some(ticks) if !ticks.ticks.is_empty() => self.draw_ticks(ticks, record),
+                _ => self.draw_empty(record),
+            };
+        }
+        if let Some(seasonal) = self.config.seasonal {
+            return self.draw_seasonal(seasonal, record);
+        }
 
-        // Background for price scale area
-        let bg_color =
-            Color::from_css(&self.config.theme.background).unwrap_or(Color::rgb(19, 23, 34));
-        backend.fill_rect(
-            Rect::new(scale_x, y_offset, scale_width, pane_height),
-            bg_color,
-        );
+        let width = self.config.width;
+        let height = self.config.height;
+        let dpr = self.config.pixel_ratio();
 
-        // Border line
-        let border_color =
-            Color::from_css(&self.config.theme.grid_color).unwrap_or(Color::rgb(42, 46, 57));
-        backend.line(
-            Point::new(scale_x, y_offset),
-            Point::new(scale_x, y_offset + pane_height),
-            &LineStyle::solid(border_color, 1.0),
-        );
+        // Reserve space for scales
+        let chart_width = width as f64 - self.price_scale_width();
+        let time_scale_y = height as f64 - self.time_scale_height();
+        let chart_height = self.bar_panes_height();
+
+        // Strategy with theme slot colors resolved; its overlays are drawn by
+        // render_strategy, while bottom/subpane indicators join the chart's own
+        let strategy = self.resolved_strategy();
+        let indicators = display_indicators(&self.config);
+        let IndicatorPanes {
+            overlays,
+            overlay_bottoms,
+            subpanes,
+            price_overlays,
+        } = IndicatorPanes::split(&indicators, strategy.as_ref());
 
-        // Generate price ticks using PriceScale
-        let price_scale = PriceScale::new(price_min, price_max);
-        let ticks = price_scale.generate_ticks(pane_height);
+        // Calculate layout - subpanes share height with main chart
+        let (main_height, price_height) =
+            main_pane_heights(&overlay_bottoms, &subpanes, chart_height);
+        let gap = 4.0;
 
-        let text_color =
-            Color::from_css(&self.config.theme.text_color).unwrap_or(Color::rgb(180, 180, 180));
-        let font_size = price_scale.calc_font_size(pane_height).min(11.0);
+        let mut backend = self.frame_backend(record);
 
-        let text_style = TextStyle {
-            color: text_color,
-            font_size,
-            font_weight: FontWeight::Normal,
-            align: TextAlign::Left,
-            baseline: TextBaseline::Middle,
-            ..Default::default()
-        };
+        // Background
+        backend.begin_element("background");
+        let bg_color = &self.config.theme.background;
+        let bg = Color::from_css(bg_color).unwrap_or(Color::rgb(19, 23, 34));
+        backend.clear(bg);
 
-        // Draw tick marks and labels
-        for tick in ticks {
-            let ratio = (tick - price_min) / (price_max - price_min);
-            let y = y_offset + pane_height - ratio * pane_height;
+        // Higher-timeframe candles, aggregated from the base bars
+        let htf_bars = self.htf_bars();
 
-            // Tick line
-            backend.line(
-                Point::new(scale_x, y),
-                Point::new(scale_x + 4.0, y),
-                &LineStyle::solid(border_color, 1.0),
+        // Calculate coordinate system for main chart
+        let (price_low, price_high) = self.price_bounds(&price_overlays, &htf_bars);
+
+        // Bars fill the pane less the right offset, left empty for
+        // primitives and signals anchored past the last bar
+        let axis = BarAxis::new(&self.bars, &self.config, chart_width, 0.0);
+        let bar_spacing = axis.spacing;
+        let bar_width = (bar_spacing * 0.8).max(1.0);
+
+        let bar_to_x = |i: usize| -> f64 { axis.x(i) };
+
+        let price_to_y = |price: f64| -> f64 {
+            let ratio = (price - price_low) / (price_high - price_low);
+            price_height - ratio * price_height
+        };
+
+        // Grid (only on main chart, not on subpanes)
+        if self.config.theme.show_grid {
+            backend.begin_element("grid");
+            self.set_category(&mut backend, PrecisionCategory::Axis);
+            self.draw_grid(
+                &mut backend,
+                main_height,
+                &bar_to_x,
+                chart_width as u32,
+                self.bars.len(),
             );
+            self.set_category(&mut backend, PrecisionCategory::Other);
+        }
 
-            // Label
-            let label = price_scale.format_price(tick, pane_height);
-            backend.text(&label, Point::new(scale_x + 6.0, y), &text_style);
+        // Main series and price overlays stay inside the price area
+        backend.begin_element("price_area");
+        backend.push_crisp_clip(Rect::new(0.0, 0.0, chart_width, price_height));
+
+        // Watermark is aligned in the price area, not the whole canvas
+        if let Some(watermark) = &self.config.watermark {
+            let mut batch = RenderBatch::new();
+            let price_area = Rect::new(0.0, 0.0, chart_width, price_height);
+            render_watermark(&mut batch, watermark, price_area, dpr);
+            self.execute_batch(&mut backend, &batch);
         }
-    }
 
-    /// Render time scale (X-axis) at the bottom of the chart
-    fn render_time_scale(
-        &self,
-        backend: &mut SvgBackend,
-        chart_width: f64,
-        chart_height: f64,
-        bar_spacing: f64,
-    ) {
-        let scale_y = chart_height;
-        let scale_height = TIME_SCALE_HEIGHT;
-        let total_width = chart_width + PRICE_SCALE_WIDTH;
+        // Volume by price and higher-timeframe candles sit behind the main series
+        self.render_volume_by_price(&mut backend, chart_width, &price_to_y);
+        self.render_htf_overlay(&mut backend, &htf_bars, &bar_to_x, &price_to_y, bar_spacing);
+        self.render_gaps(&mut backend, &bar_to_x, &price_to_y, bar_width);
 
-        // Background for time scale area
-        let bg_color =
-            Color::from_css(&self.config.theme.background).unwrap_or(Color::rgb(19, 23, 34));
-        backend.fill_rect(Rect::new(0.0, scale_y, total_width, scale_height), bg_color);
+        clock.lap(Phase::Layout);
 
-        // Border line at top of time scale
-        let border_color =
-            Color::from_css(&self.config.theme.grid_color).unwrap_or(Color::rgb(42, 46, 57));
-        backend.line(
-            Point::new(0.0, scale_y),
-            Point::new(chart_width, scale_y),
-            &LineStyle::solid(border_color, 1.0),
-        );
+        // Main series
+        backend.begin_element("series");
+        self.set_category(&mut backend, PrecisionCategory::Series);
+        let (mut series, mut last_bar) = (RenderBatch::new(), RenderBatch::new());
+        if self.config.series_visible() {
+            self.render_main_series(
+                &mut series,
+                &bar_to_x,
+                &price_to_y,
+                bar_width,
+                price_height,
+                dpr,
+            );
+            if backend.groups_marked() {
+                let x = bar_to_x(self.bars.len() - 1);
+                last_bar = split_last_bar(&mut series, x, bar_spacing);
+            }
+        }
+        backend.begin_group("series");
+        self.execute_batch(&mut backend, &series);
+        backend.end_group();
+        backend.begin_group("last-bar");
+        self.execute_batch(&mut backend, &last_bar);
+        backend.end_group();
+        clock.lap(Phase::Series);
 
-        let text_color =
-            Color::from_css(&self.config.theme.text_color).unwrap_or(Color::rgb(180, 180, 180));
-        let text_style = TextStyle {
-            color: text_color,
-            font_size: 10.0,
-            font_weight: FontWeight::Normal,
-            align: TextAlign::Center,
-            baseline: TextBaseline::Top,
-            ..Default::default()
-        };
+        // Overlay indicators (share price scale with main chart)
+        let price_pane = Rect::new(0.0, 0.0, chart_width, price_height);
+        let price_ctx =
+            VectorContext::new(price_pane, bar_spacing, (price_low, price_high), price_to_y);
+        self.render_overlay_indicators(&mut backend, &overlays, &bar_to_x, &price_to_y, &price_ctx);
 
-        // Calculate visible bar range and generate time ticks
-        let bar_count = self.bars.len();
-        if bar_count == 0 {
-            return;
-        }
+        backend.begin_element("price_area_end");
+        backend.pop_clip();
+        self.set_category(&mut backend, PrecisionCategory::Other);
 
-        // Determine appropriate tick spacing based on bar_spacing
-        let min_label_spacing = 60.0; // Minimum pixels between labels
-        let bars_per_tick = (min_label_spacing / bar_spacing).ceil() as usize;
-        let bars_per_tick = bars_per_tick.max(1);
+        // Overlay bottom indicators (own Y scale at bottom of main chart)
+        self.render_overlay_bottom_indicators(
+            &mut backend,
+            &overlay_bottoms,
+            &bar_to_x,
+            main_height,
+            chart_width,
+            bar_spacing,
+        );
 
-        // Find appropriate boundaries
-        let mut prev_ts: Option<i64> = None;
-        for i in (0..bar_count).step_by(bars_per_tick.max(1)) {
-            if i >= self.bars.len() {
-                break;
-            }
+        // Strategy (indicators, signals, primitives)
+        self.set_category(&mut backend, PrecisionCategory::Curve);
+        if let Some(strategy) = &strategy {
+            backend.begin_element("strategy");
+            self.render_strategy(
+                &mut backend,
+                strategy,
+                &bar_to_x,
+                bar_width,
+                &price_to_y,
+                dpr,
+            );
+        }
 
-            let ts = self.bars[i].timestamp;
-            let x = bar_spacing * (i as f64 + 0.5);
+        clock.lap(Phase::Indicators);
 
-            if x < 10.0 || x > chart_width - 30.0 {
-                prev_ts = Some(ts);
-                continue;
-            }
+        // Primitives and signals, clipped to the main pane so ones anchored
+        // past the right offset stop at the price scale
+        backend.begin_element("main_pane");
+        backend.push_crisp_clip(Rect::new(0.0, 0.0, chart_width, main_height));
+        self.render_primitives(&mut backend, &bar_to_x, &price_to_y, dpr, None);
+        let price_scale =
+            PriceScale::new(price_low, price_high).with_tick_size(self.config.tick_size);
+        let format_price = |price: f64| price_scale.format_price(price, price_height);
+        backend.begin_element("signals");
+        self.render_signals(&mut backend, &bar_to_x, &price_to_y, &format_price, dpr);
+        backend.pop_clip();
+        self.set_category(&mut backend, PrecisionCategory::Other);
+        clock.lap(Phase::Primitives);
+
+        // Legend of overlay indicators, above everything in the price area
+        if self.config.show_legend {
+            backend.begin_element("legend");
+            backend.begin_group("legend");
+            self.render_legend(&mut backend);
+            backend.end_group();
+        }
 
-            let weight = TickMarkWeight::from_timestamp(ts, prev_ts);
+        // Inset series in their own boxes, scaled apart from the main axis
+        backend.begin_element("insets");
+        self.render_insets(&mut backend, chart_width, price_height);
+
+        // Price scale for main chart; the last-price tag hides ticks under it
+        let last_price_y = self
+            .last_price()
+            .map(|(close, _)| price_to_y(close))
+            .filter(|y| (0.0..=price_height).contains(y));
+        backend.begin_element("price_scale");
+        backend.begin_group("price-scale");
+        self.set_category(&mut backend, PrecisionCategory::Axis);
+        self.render_price_scale(
+            &mut backend,
+            PriceScaleParams {
+                chart_width,
+                y_offset: 0.0,
+                pane_height: price_height,
+                price_min: price_low,
+                price_max: price_high,
+                tags: if self.config.series.common.last_value_visible {
+                    last_price_y.as_slice()
+                } else {
+                    &[]
+                },
+                tick_size: self.config.tick_size,
+            },
+        );
+        backend.end_group();
+        backend.begin_group("last-price");
+        if let Some(y) = last_price_y {
+            let price_scale =
+                PriceScale::new(price_low, price_high).with_tick_size(self.config.tick_size);
+            backend.begin_element("last_price");
+            self.render_last_price(&mut backend, chart_width, y, &price_scale, price_height);
+        }
+        backend.end_group();
 
-            // Only show significant ticks
-            if weight >= TickMarkWeight::Hour || i == 0 || (i % (bars_per_tick * 3)) == 0 {
-                // Tick mark
-                backend.line(
-                    Point::new(x, scale_y),
-                    Point::new(x, scale_y + 4.0),
-                    &LineStyle::solid(border_color, 1.0),
+        // Subpane indicators with their own price scales
+        clock.lap(Phase::Layout);
+        let mut y_offset = main_height + gap;
+        let mut subpane_transforms: Vec<(&str, PaneTransform)> = Vec::new();
+        let secondary_width = self.secondary_axis_width();
+        for (idx, indicator) in subpanes.iter().enumerate() {
+            let pane_height = chart_height * indicator.placement.height_ratio() - gap;
+            backend.begin_element(&format!("indicator:{}", indicator.id));
+            backend.begin_group(&format!("indicator-{}", indicator.id));
+            self.set_category(&mut backend, PrecisionCategory::Series);
+            let split = backend
+                .groups_marked()
+                .then(|| split_last_segment(indicator, self.bars.len()));
+            self.render_subpane_indicator(
+                &mut backend,
+                SubpaneRenderParams {
+                    indicator,
+                    body: split.as_ref().map_or(indicator, |(body, _)| body),
+                    y_offset,
+                    height: pane_height,
+                    width: chart_width as u32,
+                    pane_idx: idx,
+                    bar_spacing,
+                },
+                &bar_to_x,
+            );
+            if let Some((_, last)) = &split {
+                backend.end_group();
+                backend.begin_group(&format!("indicator-{}-last", indicator.id));
+                let pane = Rect::new(0.0, y_offset, chart_width, pane_height);
+                draw_pane_vectors(
+                    &mut backend,
+                    indicator,
+                    last,
+                    pane,
+                    bar_spacing,
+                    &self.bars,
+                    &bar_to_x,
                 );
+            }
+            // The title shows the latest values, so it goes with the last segment
+            if self.config.show_pane_titles {
+                self.render_pane_title(&mut backend, indicator, y_offset, pane_height, chart_width);
+            }
+            backend.end_group();
+            clock.lap(Phase::Indicators);
 
-                // Label
-                let label = format_time_by_weight(ts, weight);
-                backend.text(&label, Point::new(x, scale_y + 6.0), &text_style);
+            // Price scale for this subpane
+            let (sub_min, sub_max) = self.calculate_indicator_range(indicator);
+            backend.begin_element(&format!("price_scale:{}", indicator.id));
+            backend.begin_group(&format!("price-scale-{}", indicator.id));
+            self.set_category(&mut backend, PrecisionCategory::Axis);
+            self.render_price_scale(
+                &mut backend,
+                PriceScaleParams {
+                    chart_width,
+                    y_offset,
+                    pane_height,
+                    price_min: sub_min,
+                    price_max: sub_max,
+                    tags: &[],
+                    tick_size: None,
+                },
+            );
+            if secondary_width > 0.0 {
+                let pane = Rect::new(0.0, y_offset, chart_width, pane_height);
+                let axis_x = chart_width + self.price_scale_width() - secondary_width;
+                for group in indicator.scale_groups.iter().filter(|g| g.axis) {
+                    let range = scale_group_range(indicator, group, &self.bars);
+                    draw_secondary_axis(
+                        &mut backend,
+                        &self.config,
+                        indicator,
+                        group,
+                        range,
+                        pane,
+                        axis_x,
+                    );
+                }
             }
+            backend.end_group();
+            clock.lap(Phase::Layout);
 
-            prev_ts = Some(ts);
+            subpane_transforms.push((
+                indicator.id.as_str(),
+                PaneTransform {
+                    top: y_offset,
+                    height: pane_height,
+                    low: sub_min,
+                    high: sub_max,
+                },
+            ));
+            y_offset += pane_height + gap;
         }
-    }
+        self.set_category(&mut backend, PrecisionCategory::Other);
 
-    fn execute_batch(&self, backend: &mut SvgBackend, batch: &RenderBatch) {
-        use crate::render::engine::RenderCommand;
+        // Primitives linking points on different panes, clipped to the panes
+        // together so they can cross the boundaries between them
+        if self
+            .config
+            .primitives
+            .iter()
+            .any(|p| p.visible && p.spans_panes())
+        {
+            let main_transform = PaneTransform {
+                top: 0.0,
+                height: price_height,
+                low: price_low,
+                high: price_high,
+            };
+            backend.begin_element("cross_pane");
+            self.set_category(&mut backend, PrecisionCategory::Curve);
+            backend.push_crisp_clip(Rect::new(0.0, 0.0, chart_width, chart_height));
+            self.render_cross_pane_primitives(
+                &mut backend,
+                &bar_to_x,
+                main_transform,
+                &subpane_transforms,
+                dpr,
+            );
+            backend.begin_element("cross_pane_end");
+            backend.pop_clip();
+            self.set_category(&mut backend, PrecisionCategory::Other);
+            clock.lap(Phase::Primitives);
+        }
 
-        for cmd in batch.commands() {
-            match cmd {
-                RenderCommand::FillRect { rect, color } => {
-                    backend.fill_rect(*rect, *color);
-                }
-                RenderCommand::StrokeRect { rect, style } => {
-                    backend.stroke_rect(*rect, style);
-                }
-                RenderCommand::Line { from, to, style } => {
-                    backend.line(*from, *to, style);
-                }
-                RenderCommand::Polyline { points, style } => {
-                    backend.polyline(points, style);
-                }
-                RenderCommand::FillPath { path, style } => {
-                    backend.fill_path(path, style);
-                }
-                RenderCommand::StrokePath { path, style } => {
-                    backend.stroke_path(path, style);
-                }
-                RenderCommand::FillCircle {
-                    center,
-                    radius,
-                    color,
-                } => {
-                    backend.fill_circle(*center, *radius, *color);
-                }
-                RenderCommand::StrokeCircle {
-                    center,
-                    radius,
-                    style,
-                } => {
-                    backend.stroke_circle(*center, *radius, style);
-                }
-                RenderCommand::Text { text, pos, style } => {
-                    backend.text(text, *pos, style);
-                }
-                _ => {}
+        // Raw ticks in their own pane under the bars
+        if let Some(ticks) = &self.config.ticks {
+            let pane_top = chart_height + gap;
+            let pane = Rect::new(0.0, pane_top, chart_width, time_scale_y - pane_top);
+            if pane.height > 0.0 {
+                backend.begin_element("ticks");
+                self.render_tick_pane(&mut backend, ticks, pane, &bar_to_x);
             }
         }
-    }
-}
 
-// =============================================================================
-// MultichartRenderer - Renders multiple charts in a layout
-// =============================================================================
+        // Hovered bar column across all panes
+        backend.begin_element("hover");
+        backend.begin_group("hover");
+        self.render_hover_bar(
+            &mut backend,
+            time_scale_y,
+            &bar_to_x,
+            &price_to_y,
+            bar_spacing,
+            bar_width,
+        );
+        backend.end_group();
 
-use crate::layout::MultichartLayout;
+        // Scrollbar just above the time scale when bars were left out
+        backend.begin_element("scrollbar");
+        self.render_scroll_indicator(&mut backend, chart_width, time_scale_y);
 
-/// Renders multiple charts in a grid layout
-pub struct MultichartRenderer<'a> {
-    layout: &'a MultichartLayout,
-    charts: Vec<(&'a ChartConfig, &'a [Bar])>,
-    total_width: u32,
-    total_height: u32,
-    dpr: f64,
-}
+        // Time scale (at bottom, shared)
+        backend.begin_element("time_scale");
+        backend.begin_group("time-scale");
+        self.set_category(&mut backend, PrecisionCategory::Axis);
+        self.render_time_scale(
+            &mut backend,
+            chart_width,
+            time_scale_y,
+            &bar_to_x,
+            bar_spacing,
+        );
+        self.set_category(&mut backend, PrecisionCategory::Other);
+        backend.end_group();
 
-impl<'a> MultichartRenderer<'a> {
-    /// Create a new multichart renderer
-    pub fn new(layout: &'a MultichartLayout, total_width: u32, total_height: u32) -> Self {
-        Self {
-            layout,
-            charts: Vec::new(),
-            total_width,
-            total_height,
-            dpr: 1.0,
+        // Bar-close countdown on the time scale
+        backend.begin_element("countdown");
+        backend.begin_group("countdown");
+        self.render_countdown(&mut backend, time_scale_y, &bar_to_x, bar_spacing);
+        backend.end_group();
+
+        self.attach_description(&mut backend);
+        backend.end_frame();
+        backend
+    }
+
+    // =========================================================================
+    // Private helpers
+    // =========================================================================
+
+    /// Tooltip payload for the bar at `index`, or `None` when out of range
+    ///
+    /// Prices use the main price scale's precision, volume and indicator
+    /// values `format_indicator_value`, matching what the chart draws.
+    /// `index` counts bars of the full data, like [`LegendData::index`], so
+    /// bars `min_bar_spacing` cut from the start are out of range.
+    pub fn bar_info(&self, index: usize) -> Option<BarInfo> {
+        let index = index.checked_sub(self.first_bar)?;
+        let bar = self.bars.get(index)?;
+
+        let strategy = self.resolved_strategy();
+        let (price_scale, price_height) = self.main_price_scale();
+        let price = |v: f64| price_scale.format_price(v, price_height);
+        let time = self.format_bar_time(bar.timestamp);
+
+        // Raw values, as returned by indicator_values
+        let indicators = self
+            .config
+            .indicators
+            .iter()
+            .filter(|indicator| self.config.is_indicator_visible(indicator))
+            .chain(strategy.iter().flat_map(|s| s.indicators.iter()))
+            .map(|indicator| IndicatorInfo {
+                id: indicator.id.clone(),
+                name: indicator.name.clone(),
+                values: indicator
+                    .vectors
+                    .iter()
+                    .filter(|v| v.show_in_legend)
+                    .map(|vector| {
+                        let value = vector.value_at(index);
+                        IndicatorValueInfo {
+                            label: vector.display_label().to_string(),
+                            value,
+                            text: value.map(format_indicator_value),
+                            color: vector.color_at(index).to_string(),
+                        }
+                    })
+                    .collect(),
+            })
+            .collect();
+
+        let mut signals: Vec<SignalInfo> = self
+            .config
+            .signals
+            .iter()
+            .filter(|s| s.bar_index == index)
+            .map(|s| SignalInfo {
+                signal_type: s.signal_type.as_str().to_string(),
+                price: price(s.price),
+                text: s.label.clone(),
+            })
+            .collect();
+        if let Some(strategy) = &strategy {
+            signals.extend(
+                strategy
+                    .signals
+                    .iter()
+                    .filter(|s| s.visible && s.bar.round() == index as f64)
+                    .map(|s| SignalInfo {
+                        signal_type: s.signal_type.clone(),
+                        price: price(s.price),
+                        text: s.tooltip.clone(),
+                    }),
+            );
         }
+
+        Some(BarInfo {
+            index: self.first_bar + index,
+            timestamp: bar.timestamp,
+            time,
+            open: price(bar.open),
+            high: price(bar.high),
+            low: price(bar.low),
+            close: price(bar.close),
+            volume: format_indicator_value(bar.volume),
+            indicators,
+            signals,
+        })
     }
 
-    /// Set device pixel ratio
-    pub fn dpr(mut self, dpr: f64) -> Self {
-        self.dpr = dpr;
-        self
+    /// Structured contents of the legend at the hovered bar, or the last
+    /// bar when nothing is hovered
+    ///
+    /// Lists the same indicators, in the same order, as the drawn legend
+    /// (price overlays, group members gathered under their first
+    /// occurrence, toggled-off ones marked not visible), with the values
+    /// they are drawn with at that bar. Returns `None` without bars.
+    pub fn legend_data(&self) -> Option<LegendData> {
+        let last = self.bars.len().checked_sub(1)?;
+        let index = self.config.hover_bar.filter(|&i| i <= last).unwrap_or(last);
+        let bar = &self.bars[index];
+        let prev_close = index.checked_sub(1).map(|i| self.bars[i].close);
+
+        let entries = legend_entries(&self.legend_overlays())
+            .into_iter()
+            .map(|indicator| {
+                // Values as drawn, i.e. after smoothing
+                let smoothed = indicator.smoothed();
+                let shown = smoothed.as_ref().unwrap_or(indicator);
+                LegendIndicator {
+                    id: indicator.id.clone(),
+                    name: indicator.name.clone(),
+                    group: indicator.group.clone(),
+                    visible: self.config.is_indicator_visible(indicator),
+                    values: shown
+                        .vectors
+                        .iter()
+                        .filter(|v| v.show_in_legend)
+                        .map(|vector| LegendValue {
+                            label: vector.display_label().to_string(),
+                            value: vector.value_at(index),
+                            color: vector.color_at(index).to_string(),
+                        })
+                        .collect(),
+                }
+            })
+            .collect();
+
+        let title = &self.config.series.common.title;
+        Some(LegendData {
+            index: self.first_bar + index,
+            series_name: if title.is_empty() {
+                self.config.title.clone()
+            } else {
+                Some(title.clone())
+            },
+            series_type: self.config.series.series_type,
+            indicators: entries,
+            ..LegendData::from_bar(bar, prev_close)
+        })
     }
 
-    /// Add a chart to a cell
-    pub fn chart(mut self, config: &'a ChartConfig, bars: &'a [Bar]) -> Self {
-        self.charts.push((config, bars));
-        self
+    /// Overlay indicators with a legend row, toggled-off ones included
+    fn legend_overlays(&self) -> Vec<&Indicator> {
+        self.config
+            .indicators
+            .iter()
+            .filter(|ind| self.config.is_indicator_in_legend(ind) && ind.placement.is_overlay())
+            .collect()
     }
 
-    /// Render all charts to SVG
-    pub fn render_svg(&self) -> String {
-        let width = self.total_width;
-        let height = self.total_height;
-        let dpr = self.dpr;
+    /// Padded price range of the main pane: bars, price overlays and
+    /// higher-timeframe candles
+    fn main_price_bounds(&self) -> (f64, f64) {
+        let strategy = self.resolved_strategy();
+        let indicators = display_indicators(&self.config);
+        let panes = IndicatorPanes::split(&indicators, strategy.as_ref());
+        self.price_bounds(&panes.price_overlays, &self.htf_bars())
+    }
 
-        let mut backend = SvgBackend::new(width, height, dpr);
-        backend.begin_frame(width as f64, height as f64, dpr);
+    /// Width of the price scale column, zero when it is hidden
+    ///
+    /// The density's width, or with `price_scale_fit` the width of the
+    /// widest label on the main or a subpane scale, see
+    /// [`ChartConfig::price_scale_fit`].
+    pub fn price_scale_width(&self) -> f64 {
+        self.price_scale_width
+    }
 
-        // Background
-        let bg = Color::rgb(19, 23, 34);
-        backend.clear(bg);
+    /// Settle the price scale width on the bars as given, widened by the
+    /// column of any secondary axes
+    fn fitted_price_scale(mut self) -> Self {
+        let sizing = self.config.sizing();
+        let secondary = self.secondary_axis_width();
+        self.price_scale_width = secondary
+            + match (self.config.show_price_scale, self.config.price_scale_fit) {
+                (false, _) => 0.0,
+                (true, false) => sizing.price_scale_width,
+                (true, true) => {
+                    let label = self.widest_price_label(sizing.scale_font_size);
+                    let width = sizing.tick_length + 2.0 + label + PRICE_SCALE_PADDING_OUTER;
+                    let stepped = (width / PRICE_SCALE_WIDTH_STEP).ceil() * PRICE_SCALE_WIDTH_STEP;
+                    stepped
+                        .max(PRICE_SCALE_MIN_WIDTH)
+                        .max(self.config.price_scale_min_width.unwrap_or(0.0))
+                }
+            };
+        self
+    }
 
-        // Calculate cell bounds
-        let bounds = self.layout.calculate_bounds(width as f64, height as f64);
+    /// Width of the secondary axis column, see [`secondary_axis_width`]
+    fn secondary_axis_width(&self) -> f64 {
+        let strategy = self.resolved_strategy();
+        let indicators = display_indicators(&self.config);
+        let panes = IndicatorPanes::split(&indicators, strategy.as_ref());
+        secondary_axis_width(
+            &self.config,
+            panes.subpanes.iter().copied(),
+            &self.bars,
+            self.bar_panes_height(),
+        )
+    }
 
-        // Render each chart in its cell
-        for (idx, (_cell_id, cell_bounds)) in bounds.iter().enumerate() {
-            if let Some((config, bars)) = self.charts.get(idx) {
-                self.render_chart_in_cell(&mut backend, config, bars, cell_bounds, dpr);
-            }
+    /// Width of the widest label on the main price scale and the subpane
+    /// scales at `font_size`
+    fn widest_price_label(&self, font_size: f64) -> f64 {
+        if self.bars.is_empty() {
+            return 0.0;
         }
+        let measure = |text: &str| estimate_text_width(text, font_size, FontWeight::Normal);
+        let height = self.bar_panes_height();
+        let (low, high) = self.main_price_bounds();
+        let main = PriceScale::new(low, high)
+            .with_tick_size(self.config.tick_size)
+            .label_width(height, measure);
+
+        let strategy = self.resolved_strategy();
+        let indicators = display_indicators(&self.config);
+        let panes = IndicatorPanes::split(&indicators, strategy.as_ref());
+        panes
+            .subpanes
+            .iter()
+            .map(|indicator| {
+                let (low, high) = self.calculate_indicator_range(indicator);
+                let pane_height = height * indicator.placement.height_ratio();
+                PriceScale::new(low, high).label_width(pane_height, measure)
+            })
+            .fold(main, f64::max)
+    }
 
-        backend.end_frame();
-        backend.to_svg()
+    /// Height of the time scale strip, zero when it is hidden
+    fn time_scale_height(&self) -> f64 {
+        if self.config.show_time_scale {
+            self.config.sizing().time_scale_height
+        } else {
+            0.0
+        }
     }
 
-    fn render_chart_in_cell(
-        &self,
-        backend: &mut SvgBackend,
-        config: &ChartConfig,
-        bars: &[Bar],
-        bounds: &crate::layout::CellBounds,
-        _dpr: f64,
-    ) {
-        if bars.is_empty() {
-            return;
+    /// Height of the bar panes: the chart above the time scale, less the
+    /// tick pane when ticks are drawn under the bars
+    fn bar_panes_height(&self) -> f64 {
+        let height = self.config.height as f64 - self.time_scale_height();
+        match &self.config.ticks {
+            Some(ticks) if !ticks.ticks.is_empty() => height * (1.0 - TICK_PANE_RATIO),
+            _ => height,
         }
+    }
 
-        let x_offset = bounds.x;
-        let y_offset = bounds.y;
-        let cell_width = bounds.width;
-        let cell_height = bounds.height;
+    fn main_price_scale(&self) -> (PriceScale, f64) {
+        let strategy = self.resolved_strategy();
+        let indicators = display_indicators(&self.config);
+        let panes = IndicatorPanes::split(&indicators, strategy.as_ref());
+        let chart_height = self.bar_panes_height();
+        let (_, price_height) =
+            main_pane_heights(&panes.overlay_bottoms, &panes.subpanes, chart_height);
+        let (price_low, price_high) = self.main_price_bounds();
+        let price_scale =
+            PriceScale::new(price_low, price_high).with_tick_size(self.config.tick_size);
+        (price_scale, price_height)
+    }
 
-        // Reserve space for scales
-        let price_scale_width = PRICE_SCALE_WIDTH;
-        let time_scale_height = TIME_SCALE_HEIGHT;
-        let chart_width = cell_width - price_scale_width;
-        let chart_height = cell_height - time_scale_height;
+    /// Index in `config.primitives` of the topmost main-pane primitive drawn
+    /// within `tolerance` pixels of `(x, y)`
+    ///
+    /// Hidden and locked primitives are skipped. Strokes are tested against
+    /// their line segments, everything else against its bounding box.
+    pub fn hit_test(&self, x: f64, y: f64, tolerance: f64) -> Option<usize> {
+        if self.bars.is_empty() || self.config.seasonal.is_some() {
+            return None;
+        }
 
-        // Separate indicators
-        let overlays: Vec<&Indicator> = config
-            .indicators
-            .iter()
-            .filter(|ind| ind.placement.is_overlay())
-            .collect();
-        let overlay_bottoms: Vec<&Indicator> = config
-            .indicators
-            .iter()
-            .filter(|ind| ind.placement.is_overlay_bottom())
-            .collect();
-        let subpanes: Vec<&Indicator> = config
-            .indicators
+        // Main pane geometry, as in draw
+        let strategy = self.resolved_strategy();
+        let indicators = display_indicators(&self.config);
+        let panes = IndicatorPanes::split(&indicators, strategy.as_ref());
+        let chart_width = self.config.width as f64 - self.price_scale_width();
+        let chart_height = self.bar_panes_height();
+        let (main_height, price_height) =
+            main_pane_heights(&panes.overlay_bottoms, &panes.subpanes, chart_height);
+        if !(0.0..=chart_width).contains(&x) || !(0.0..=main_height).contains(&y) {
+            return None;
+        }
+        let (price_low, price_high) = self.price_bounds(&panes.price_overlays, &self.htf_bars());
+        let axis = BarAxis::new(&self.bars, &self.config, chart_width, 0.0);
+        let bar_to_x = |i: usize| -> f64 { axis.x(i) };
+        let price_to_y = |price: f64| -> f64 {
+            let ratio = (price - price_low) / (price_high - price_low);
+            price_height - ratio * price_height
+        };
+
+        let point = Point::new(x, y);
+        let registry = self.registry();
+        self.config
+            .primitives
             .iter()
-            .filter(|ind| ind.placement.is_subpane())
-            .collect();
+            .enumerate()
+            .rev()
+            .filter(|(_, p)| p.pane_id.is_none() && p.visible && !p.locked && !p.spans_panes())
+            .find(|(_, prim_config)| {
+                let points = self.resolve_points(prim_config);
+                let Ok(mut primitive) =
+                    registry.create(&prim_config.type_id, &points, Some(&prim_config.color))
+                else {
+                    return false;
+                };
+                self.apply_style(prim_config, primitive.as_mut());
 
-        // Calculate layout
-        let total_subpane_ratio: f64 = subpanes.iter().map(|s| s.placement.height_ratio()).sum();
-        let main_ratio = 1.0 - total_subpane_ratio;
-        let main_height = chart_height * main_ratio;
-        let gap = 2.0;
+                let mut backend = self.frame_backend(true);
+                let mut ctx = SvgRenderContext::new(
+                    &mut backend,
+                    &bar_to_x,
+                    &price_to_y,
+                    self.config.pixel_ratio(),
+                    self.config.width as f64,
+                    self.config.height as f64,
+                )
+                .with_bars(&self.bars);
+                primitive.render(&mut ctx, false);
+                backend
+                    .take_commands()
+                    .iter()
+                    .any(|cmd| command_near(cmd, point, tolerance))
+            })
+            .map(|(index, _)| index)
+    }
 
-        // Calculate price range
-        let (price_min, price_max) = Self::calc_price_range(bars, &overlays);
-        let price_padding = (price_max - price_min) * 0.05;
-        let price_low = price_min - price_padding;
-        let price_high = price_max + price_padding;
+    /// Full date and time of a bar, as in tooltips
+    fn format_bar_time(&self, ts: i64) -> String {
+        match self.config.time_format.as_deref() {
+            Some(pattern) if !pattern.trim().is_empty() => format_time_pattern(ts, pattern),
+            _ => format_time_full(ts),
+        }
+    }
 
-        let bar_count = bars.len();
-        let bar_spacing = chart_width / bar_count as f64;
-        let bar_width = (bar_spacing * 0.8).max(1.0);
+    /// Plain-text summary of the chart for screen readers and alt text
+    ///
+    /// Covers the title, date range, open and close with the change in
+    /// percent, the high and low with their dates, the latest value of each
+    /// indicator and the number of signals by type. Prices and dates use the
+    /// same formatters as the axis and tooltips.
+    pub fn describe(&self) -> String {
+        let mut sentences: Vec<String> = Vec::new();
+        if let Some(title) = self
+            .config
+            .title
+            .as_deref()
+            .filter(|t| !t.trim().is_empty())
+        {
+            sentences.push(format!("{}.", title.trim().trim_end_matches('.')));
+        }
+        let (Some(first), Some(last)) = (self.bars.first(), self.bars.last()) else {
+            sentences.push("No data.".to_string());
+            return sentences.join(" ");
+        };
 
-        // Coordinate transforms with offset
-        let bar_to_x = |i: usize| -> f64 { x_offset + bar_spacing * (i as f64 + 0.5) };
+        let (price_scale, price_height) = self.main_price_scale();
+        let price = |v: f64| price_scale.format_price(v, price_height);
+        let mut percent_scale = price_scale.clone();
+        percent_scale.set_mode(PriceScaleMode::Percent);
+        percent_scale.set_base_price(first.open);
+
+        sentences.push(format!(
+            "{} bars from {} to {}.",
+            self.bars.len(),
+            self.format_bar_time(first.timestamp),
+            self.format_bar_time(last.timestamp)
+        ));
+        sentences.push(format!(
+            "Opened at {} and closed at {} ({}).",
+            price(first.open),
+            price(last.close),
+            percent_scale.format_label(last.close, price_height)
+        ));
+
+        // First bar reaching each extreme
+        let extreme = |value: fn(&Bar) -> f64, better: fn(f64, f64) -> bool| {
+            self.bars
+                .iter()
+                .filter(|b| !value(b).is_nan())
+                .fold(None, |best: Option<&Bar>, b| match best {
+                    Some(best) if !better(value(b), value(best)) => Some(best),
+                    _ => Some(b),
+                })
+        };
+        if let (Some(high), Some(low)) = (
+            extreme(|b| b.high, |a, b| a > b),
+            extreme(|b| b.low, |a, b| a < b),
+        ) {
+            sentences.push(format!(
+                "High {} on {}, low {} on {}.",
+                price(high.high),
+                self.format_bar_time(high.timestamp),
+                price(low.low),
+                self.format_bar_time(low.timestamp)
+            ));
+        }
 
-        let price_to_y = |price: f64| -> f64 {
-            let ratio = (price - price_low) / (price_high - price_low);
-            y_offset + main_height - ratio * main_height
+        let strategy = self.resolved_strategy();
+        let last_index = self.bars.len() - 1;
+        let value_text = |v: Option<f64>| {
+            v.map(format_indicator_value)
+                .unwrap_or_else(|| "n/a".to_string())
         };
+        // Raw latest values, as in bar_info
+        let indicators: Vec<String> = self
+            .config
+            .indicators
+            .iter()
+            .filter(|indicator| self.config.is_indicator_visible(indicator))
+            .chain(strategy.iter().flat_map(|s| s.indicators.iter()))
+            .map(|indicator| {
+                let vectors: Vec<_> = indicator
+                    .vectors
+                    .iter()
+                    .filter(|v| v.show_in_legend)
+                    .collect();
+                match vectors.as_slice() {
+                    [] => indicator.name.clone(),
+                    [vector] => {
+                        format!(
+                            "{} {}",
+                            indicator.name,
+                            value_text(vector.value_at(last_index))
+                        )
+                    }
+                    vectors => {
+                        let values: Vec<String> = vectors
+                            .iter()
+                            .map(|v| {
+                                format!(
+                                    "{} {}",
+                                    v.display_label(),
+                                    value_text(v.value_at(last_index))
+                                )
+                            })
+                            .collect();
+                        format!("{} ({})", indicator.name, values.join(", "))
+                    }
+                }
+            })
+            .collect();
+        if !indicators.is_empty() {
+            sentences.push(format!("Indicators: {}.", indicators.join(", ")));
+        }
 
-        // Cell background
-        let bg_color = Color::from_css(&config.theme.background).unwrap_or(Color::rgb(19, 23, 34));
-        backend.fill_rect(
-            Rect::new(x_offset, y_offset, cell_width, cell_height),
-            bg_color,
-        );
+        let mut signals: BTreeMap<String, usize> = BTreeMap::new();
+        for signal in &self.config.signals {
+            *signals
+                .entry(signal.signal_type.as_str().to_string())
+                .or_default() += 1;
+        }
+        for signal in strategy
+            .iter()
+            .flat_map(|s| s.signals.iter())
+            .filter(|s| s.visible)
+        {
+            *signals.entry(signal.signal_type.clone()).or_default() += 1;
+        }
+        if !signals.is_empty() {
+            let counts: Vec<String> = signals
+                .iter()
+                .map(|(kind, count)| format!("{} {}", count, kind.replace('_', " ")))
+                .collect();
+            sentences.push(format!("Signals: {}.", counts.join(", ")));
+        }
 
-        // Border
-        let border_color =
-            Color::from_css(&config.theme.grid_color).unwrap_or(Color::rgb(42, 46, 57));
-        backend.stroke_rect(
-            Rect::new(x_offset, y_offset, cell_width, cell_height),
-            &LineStyle::solid(border_color, 1.0),
-        );
+        sentences.join(" ")
+    }
 
-        // Render main series
-        Self::render_series_simple(backend, bars, config, &bar_to_x, &price_to_y, bar_width);
+    /// Embed [`Self::describe`] as the SVG's `<desc>` when configured
+    fn attach_description(&self, backend: &mut SvgBackend) {
+        if self.config.embed_description {
+            backend.set_description(self.config.title.as_deref(), &self.describe());
+        }
+    }
 
-        // Render overlay indicators (share price Y scale)
-        for indicator in &overlays {
-            for vector in &indicator.vectors {
-                Self::render_vector_simple(
-                    backend,
-                    vector,
-                    &bar_to_x,
-                    &price_to_y,
-                    price_to_y(0.0),
-                );
+    /// Tick series as the main series: placed by time across the pane, with
+    /// the time scale labeled at round times between the first and last tick
+    /// (seconds, minutes or dates by their spacing, or `time_format`)
+    fn draw_ticks(&self, ticks: &TickSeries, record: bool) -> SvgBackend {
+        let width = self.config.width;
+        let height = self.config.height;
+        let chart_width = width as f64 - self.price_scale_width();
+        let chart_height = height as f64 - self.time_scale_height();
+        let theme = &self.config.theme;
+
+        let mut backend = self.frame_backend(record);
+        let bg = Color::from_css(&theme.background).unwrap_or(Color::rgb(19, 23, 34));
+        backend.clear(bg);
+
+        let seconds = ticks.seconds(self.config.timestamp_unit);
+        let start = seconds.first().map_or(0.0, |t| t.0);
+        let end = seconds.last().map_or(0.0, |t| t.0).max(start + 1.0);
+        let time_to_x = |t: f64| (t - start) / (end - start) * chart_width;
+
+        let pane = Rect::new(0.0, 0.0, chart_width, chart_height);
+        let points: Vec<(f64, f64)> = seconds.iter().map(|&(t, p)| (time_to_x(t), p)).collect();
+        let (low, high) = self.draw_tick_line(&mut backend, ticks, &points, pane);
+
+        let grid_color = Color::from_css(&theme.grid_color).unwrap_or(Color::rgb(42, 46, 57));
+        let marks = tick_time_marks(start, end, chart_width);
+        if theme.show_grid {
+            let style = LineStyle::solid(grid_color, 1.0);
+            for &(ts, _) in &marks {
+                let x = time_to_x(ts as f64);
+                backend.crisp_line(Point::new(x, 0.0), Point::new(x, chart_height), &style);
             }
         }
 
-        // Render overlay_bottom indicators (own Y scale at bottom of main chart)
-        Self::render_overlay_bottom_simple(
-            backend,
-            bars,
-            &overlay_bottoms,
-            &bar_to_x,
-            y_offset,
-            main_height,
-            config,
-        );
-
-        // Price scale
-        Self::render_price_scale_simple(
-            backend,
-            config,
-            x_offset + chart_width,
-            y_offset,
-            main_height,
-            price_low,
-            price_high,
+        self.render_price_scale(
+            &mut backend,
+            PriceScaleParams {
+                chart_width,
+                y_offset: 0.0,
+                pane_height: chart_height,
+                price_min: low,
+                price_max: high,
+                tags: &[],
+                tick_size: self.config.tick_size,
+            },
         );
 
-        // Subpanes
-        let mut sub_y_offset = y_offset + main_height + gap;
-        for indicator in &subpanes {
-            let pane_height = chart_height * indicator.placement.height_ratio() - gap;
-
-            // Subpane background
-            backend.fill_rect(
-                Rect::new(x_offset, sub_y_offset, chart_width, pane_height),
-                bg_color,
+        if self.config.show_time_scale {
+            // Time scale labeled by the tick times
+            backend.crisp_fill_rect(
+                Rect::new(0.0, chart_height, width as f64, self.time_scale_height()),
+                bg,
             );
-
-            // Separator
-            backend.line(
-                Point::new(x_offset, sub_y_offset),
-                Point::new(x_offset + chart_width, sub_y_offset),
-                &LineStyle::solid(border_color, 1.0),
+            backend.crisp_line(
+                Point::new(0.0, chart_height),
+                Point::new(chart_width, chart_height),
+                &LineStyle::solid(grid_color, 1.0),
             );
-
-            // Calculate subpane range
-            let (sub_min, sub_max) = Self::calc_indicator_range(indicator, bars);
-            let value_to_y = |v: f64| -> f64 {
-                let ratio = (v - sub_min) / (sub_max - sub_min);
-                sub_y_offset + pane_height - ratio * pane_height
+            let label_style = TextStyle {
+                color: Color::from_css(&theme.text_color).unwrap_or(Color::rgb(180, 180, 180)),
+                font_size: 10.0,
+                font_weight: FontWeight::Normal,
+                align: TextAlign::Center,
+                baseline: TextBaseline::Top,
+                ..Default::default()
             };
-            let zero_y = value_to_y(0.0);
-
-            // Render levels
-            for level in &indicator.levels {
-                let y = value_to_y(level.value);
-                let color = Color::from_css(&level.color).unwrap_or(Color::rgb(120, 123, 134));
-                let style = match level.style.as_str() {
-                    "dotted" => LineStyle::dashed(color, level.width, 2.0, 2.0),
-                    "dashed" => LineStyle::dashed(color, level.width, 4.0, 4.0),
-                    _ => LineStyle::solid(color, level.width),
+            for &(ts, step) in &marks {
+                let x = time_to_x(ts as f64);
+                let pattern = match self.config.time_format.as_deref() {
+                    Some(pattern) if !pattern.trim().is_empty() => pattern,
+                    _ if step < MINUTE => "%H:%M:%S",
+                    _ if step < DAY => "%H:%M",
+                    _ => "%d %b",
                 };
-                backend.line(
-                    Point::new(x_offset, y),
-                    Point::new(x_offset + chart_width, y),
-                    &style,
+                let label = format_time_pattern(ts, pattern);
+                backend.crisp_line(
+                    Point::new(x, chart_height),
+                    Point::new(x, chart_height + 4.0),
+                    &LineStyle::solid(grid_color, 1.0),
                 );
+                backend.text(&label, Point::new(x, chart_height + 6.0), &label_style);
             }
+        }
 
-            // Render vectors
-            for vector in &indicator.vectors {
-                Self::render_vector_simple(backend, vector, &bar_to_x, &value_to_y, zero_y);
-            }
+        self.attach_description(&mut backend);
+        backend.end_frame();
+        backend
+    }
 
-            // Price scale for subpane
-            Self::render_price_scale_simple(
-                backend,
-                config,
-                x_offset + chart_width,
-                sub_y_offset,
-                pane_height,
-                sub_min,
-                sub_max,
-            );
+    /// Tick series in its own pane under the bars, with its own price scale
+    ///
+    /// A tick between two bars' timestamps is placed that far between their
+    /// slots, shifted half a slot so a bar's open time lands on its left
+    /// edge; ticks past the last bar continue at one slot per timeframe.
+    fn render_tick_pane(
+        &self,
+        backend: &mut SvgBackend,
+        ticks: &TickSeries,
+        pane: Rect,
+        bar_to_x: &impl Fn(usize) -> f64,
+    ) {
+        let theme = &self.config.theme;
+        let bg = Color::from_css(&theme.background).unwrap_or(Color::rgb(19, 23, 34));
+        let sep_color = Color::from_css(&theme.grid_color).unwrap_or(Color::rgb(42, 46, 57));
+        backend.crisp_fill_rect(pane, bg);
+        backend.crisp_line(
+            Point::new(0.0, pane.y),
+            Point::new(pane.x + pane.width, pane.y),
+            &LineStyle::solid(sep_color, 1.0),
+        );
 
-            sub_y_offset += pane_height + gap;
-        }
+        let timeframe = self.timeframe_secs().unwrap_or(1) as f64;
+        let timestamps: Vec<f64> = self.bars.iter().map(|b| b.timestamp as f64).collect();
+        let time_to_bar = |t: f64| -> f64 {
+            let next = timestamps.partition_point(|&ts| ts <= t);
+            let bar = match next {
+                0 => (t - timestamps[0]) / timeframe,
+                n if n == timestamps.len() => (n - 1) as f64 + (t - timestamps[n - 1]) / timeframe,
+                n => {
+                    let (prev, next_ts) = (timestamps[n - 1], timestamps[n]);
+                    (n - 1) as f64 + (t - prev) / (next_ts - prev)
+                }
+            };
+            bar - 0.5
+        };
+        let points: Vec<(f64, f64)> = ticks
+            .seconds(self.config.timestamp_unit)
+            .into_iter()
+            .map(|(t, price)| (fractional_bar_x(bar_to_x, time_to_bar(t)), price))
+            .collect();
+        let (low, high) = self.draw_tick_line(backend, ticks, &points, pane);
 
-        // Time scale
-        Self::render_time_scale_simple(
+        self.render_price_scale(
             backend,
-            config,
-            bars,
-            x_offset,
-            y_offset + chart_height,
-            chart_width,
-            bar_spacing,
+            PriceScaleParams {
+                chart_width: pane.width,
+                y_offset: pane.y,
+                pane_height: pane.height,
+                price_min: low,
+                price_max: high,
+                tags: &[],
+                tick_size: self.config.tick_size,
+            },
         );
     }
 
-    fn calc_price_range(bars: &[Bar], overlays: &[&Indicator]) -> (f64, f64) {
-        let mut min = f64::INFINITY;
-        let mut max = f64::NEG_INFINITY;
+    /// Downsample `points` (pane x, price) to four per pixel column and draw
+    /// them scaled to their padded price range, which is returned
+    fn draw_tick_line(
+        &self,
+        backend: &mut SvgBackend,
+        ticks: &TickSeries,
+        points: &[(f64, f64)],
+        pane: Rect,
+    ) -> (f64, f64) {
+        let columns = pane.width.ceil().max(1.0) as usize;
+        let reduced = downsample_m4(points, pane.x, pane.x + pane.width, columns);
+
+        let (min, max) = reduced
+            .iter()
+            .fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), &(_, p)| {
+                (lo.min(p), hi.max(p))
+            });
+        if !min.is_finite() {
+            return (0.0, 1.0);
+        }
+        let padding = if max > min {
+            (max - min) * 0.05
+        } else {
+            (min.abs() * 0.01).max(1e-9)
+        };
+        let (low, high) = (min - padding, max + padding);
+        let price_to_y = |p: f64| pane.y + (high - p) / (high - low) * pane.height;
 
-        for bar in bars {
-            if !bar.low.is_nan() {
-                min = min.min(bar.low);
-            }
-            if !bar.high.is_nan() {
-                max = max.max(bar.high);
-            }
+        let line: Vec<Point> = reduced
+            .iter()
+            .map(|&(x, p)| Point::new(x, price_to_y(p)))
+            .collect();
+        let color = ticks
+            .color
+            .as_deref()
+            .and_then(Color::from_css)
+            .or_else(|| Color::from_css(&self.config.theme.up_color))
+            .unwrap_or(Color::rgb(38, 166, 154));
+
+        backend.push_crisp_clip(pane);
+        if ticks.style == TickStyle::Area && line.len() >= 2 {
+            let bottom = pane.y + pane.height;
+            let mut fill = line.clone();
+            fill.push(Point::new(line[line.len() - 1].x, bottom));
+            fill.push(Point::new(line[0].x, bottom));
+            backend.fill_path(
+                &Path::polygon(&fill),
+                &FillStyle::solid(color.with_alpha(0.2)),
+            );
         }
+        backend.polyline(
+            &line,
+            &LineStyle::solid(color, 1.0)
+                .with_join(LineJoin::Round)
+                .with_cap(LineCap::Round),
+        );
+        backend.pop_clip();
+        (low, high)
+    }
 
-        for indicator in overlays {
-            for vector in &indicator.vectors {
-                for &v in &vector.values {
-                    if !v.is_nan() {
-                        min = min.min(v);
-                        max = max.max(v);
-                    }
-                }
+    /// Seasonal mode: one line per period over an in-period x axis
+    ///
+    /// The latest period is drawn on top at full strength; older ones fade
+    /// with age. The time scale shows month names (or hours for days).
+    fn draw_seasonal(&self, seasonal: SeasonalConfig, record: bool) -> SvgBackend {
+        let width = self.config.width;
+        let height = self.config.height;
+        let chart_width = width as f64 - self.price_scale_width();
+        let chart_height = height as f64 - self.time_scale_height();
+        let theme = &self.config.theme;
+
+        let mut backend = self.frame_backend(record);
+        let bg = Color::from_css(&theme.background).unwrap_or(Color::rgb(19, 23, 34));
+        backend.clear(bg);
+
+        let lines = seasonal_lines(&self.bars, seasonal.period, seasonal.source);
+        let (mut min, mut max) = (0.0f64, 0.0f64);
+        for &(_, change) in lines.iter().flat_map(|l| &l.points) {
+            min = min.min(change);
+            max = max.max(change);
+        }
+        let padding = ((max - min) * 0.05).max(0.5);
+        let (low, high) = (min - padding, max + padding);
+        let value_to_y = |v: f64| chart_height - (v - low) / (high - low) * chart_height;
+
+        let grid_color = Color::from_css(&theme.grid_color).unwrap_or(Color::rgb(42, 46, 57));
+        let ticks = seasonal.period.ticks();
+        if theme.show_grid {
+            let style = LineStyle::solid(grid_color, 1.0);
+            for (position, _) in &ticks {
+                let x = position * chart_width;
+                backend.crisp_line(Point::new(x, 0.0), Point::new(x, chart_height), &style);
             }
         }
+        backend.crisp_line(
+            Point::new(0.0, value_to_y(0.0)),
+            Point::new(chart_width, value_to_y(0.0)),
+            &LineStyle::dashed(grid_color, 1.0, 4.0, 4.0),
+        );
 
-        if min.is_infinite() {
-            min = 0.0;
+        // Palette by age so the current period keeps the first color
+        let colors: Vec<Color> = (0..lines.len())
+            .map(|i| {
+                let age = lines.len() - 1 - i;
+                let color =
+                    Color::from_css(get_compare_color(age)).unwrap_or(Color::rgb(33, 150, 243));
+                let fade = 1.0 - age as f64 / lines.len() as f64;
+                color.with_alpha(if age == 0 { 1.0 } else { fade.max(0.2) })
+            })
+            .collect();
+
+        backend.push_crisp_clip(Rect::new(0.0, 0.0, chart_width, chart_height));
+        for (line, color) in lines.iter().zip(&colors) {
+            let points: Vec<Point> = line
+                .points
+                .iter()
+                .map(|&(position, change)| Point::new(position * chart_width, value_to_y(change)))
+                .collect();
+            let width = if line.current { 2.0 } else { 1.0 };
+            backend.polyline(&points, &LineStyle::solid(*color, width));
         }
-        if max.is_infinite() {
-            max = 100.0;
+        backend.pop_clip();
+
+        // Legend, newest first
+        let text_color = Color::from_css(&theme.text_color).unwrap_or(Color::rgb(180, 180, 180));
+        for (row, (line, color)) in lines.iter().zip(&colors).rev().enumerate() {
+            let y = 14.0 + row as f64 * 16.0;
+            backend.crisp_line(
+                Point::new(8.0, y),
+                Point::new(22.0, y),
+                &LineStyle::solid(*color, 2.0),
+            );
+            backend.text(
+                &line.label,
+                Point::new(28.0, y),
+                &TextStyle {
+                    color: text_color,
+                    font_size: 11.0,
+                    font_weight: if line.current {
+                        FontWeight::Bold
+                    } else {
+                        FontWeight::Normal
+                    },
+                    align: TextAlign::Left,
+                    baseline: TextBaseline::Middle,
+                    ..Default::default()
+                },
+            );
         }
 
-        (min, max)
-    }
-
-    fn calc_indicator_range(indicator: &Indicator, bars: &[Bar]) -> (f64, f64) {
-        use crate::model::IndicatorRange;
+        self.render_price_scale(
+            &mut backend,
+            PriceScaleParams {
+                chart_width,
+                y_offset: 0.0,
+                pane_height: chart_height,
+                price_min: low,
+                price_max: high,
+                tags: &[],
+                tick_size: None,
+            },
+        );
 
-        match &indicator.range {
-            IndicatorRange::Fixed { min, max } => (*min, *max),
-            IndicatorRange::Symmetric => {
-                let mut max_abs = 0.0_f64;
-                for vector in &indicator.vectors {
-                    for &v in &vector.values {
-                        if !v.is_nan() {
-                            max_abs = max_abs.max(v.abs());
-                        }
-                    }
-                }
-                let padding = max_abs * 0.1;
-                (-(max_abs + padding), max_abs + padding)
-            }
-            IndicatorRange::Price => {
-                let mut min = f64::INFINITY;
-                let mut max = f64::NEG_INFINITY;
-                for bar in bars {
-                    if !bar.low.is_nan() {
-                        min = min.min(bar.low);
-                    }
-                    if !bar.high.is_nan() {
-                        max = max.max(bar.high);
-                    }
-                }
-                let padding = (max - min) * 0.05;
-                (min - padding, max + padding)
-            }
-            IndicatorRange::Auto => {
-                let mut min = f64::INFINITY;
-                let mut max = f64::NEG_INFINITY;
-                for vector in &indicator.vectors {
-                    for &v in &vector.values {
-                        if !v.is_nan() {
-                            min = min.min(v);
-                            max = max.max(v);
-                        }
-                    }
-                }
-                let range = max - min;
-                if range > 0.0 {
-                    let padding = range * 0.1;
-                    (min - padding, max + padding)
-                } else {
-                    (0.0, 100.0)
-                }
+        if self.config.show_time_scale {
+            // Time scale with period labels instead of timestamps
+            backend.crisp_fill_rect(
+                Rect::new(0.0, chart_height, width as f64, self.time_scale_height()),
+                bg,
+            );
+            backend.crisp_line(
+                Point::new(0.0, chart_height),
+                Point::new(chart_width, chart_height),
+                &LineStyle::solid(grid_color, 1.0),
+            );
+            let label_style = TextStyle {
+                color: text_color,
+                font_size: 10.0,
+                font_weight: FontWeight::Normal,
+                align: TextAlign::Left,
+                baseline: TextBaseline::Top,
+                ..Default::default()
+            };
+            for (position, label) in &ticks {
+                let x = position * chart_width;
+                backend.crisp_line(
+                    Point::new(x, chart_height),
+                    Point::new(x, chart_height + 4.0),
+                    &LineStyle::solid(grid_color, 1.0),
+                );
+                backend.text(label, Point::new(x + 3.0, chart_height + 6.0), &label_style);
             }
         }
+
+        self.attach_description(&mut backend);
+        backend.end_frame();
+        backend
     }
 
-    fn render_series_simple(
-        backend: &mut SvgBackend,
-        bars: &[Bar],
-        config: &ChartConfig,
-        bar_to_x: &impl Fn(usize) -> f64,
-        price_to_y: &impl Fn(f64) -> f64,
-        bar_width: f64,
-    ) {
-        let up_color = Color::from_css(&config.theme.up_color).unwrap_or(Color::rgb(38, 166, 154));
-        let down_color =
-            Color::from_css(&config.theme.down_color).unwrap_or(Color::rgb(239, 83, 80));
+    /// Empty/loading state: theme chrome, optional skeleton candles and
+    /// the configured message
+    ///
+    /// The grid, price scale column and time scale strip (unless hidden) are
+    /// drawn at their usual size so the plot area does not jump when bars
+    /// arrive. There are
+    /// no prices or times yet, so the scales carry no labels.
+    /// Draw only the background, for a size the chart can't be laid out in
+    fn draw_background(&self, record: bool) -> SvgBackend {
+        let mut backend = self.frame_backend(record);
+        let theme = &self.config.theme;
+        backend.clear(Color::from_css(&theme.background).unwrap_or(Color::rgb(19, 23, 34)));
+        backend.end_frame();
+        backend
+    }
 
-        match &config.series.series_type {
-            SeriesType::Candlestick | SeriesType::HollowCandlestick | SeriesType::HeikinAshi => {
-                for (i, bar) in bars.iter().enumerate() {
-                    let x = bar_to_x(i);
-                    let is_up = bar.close >= bar.open;
-                    let color = if is_up { up_color } else { down_color };
+    fn draw_empty(&self, record: bool) -> SvgBackend {
+        let empty = &self.config.empty_state;
+        let (width, height) = (self.config.width, self.config.height);
+        let chart_width = width as f64 - self.price_scale_width();
+        let chart_height = height as f64 - self.time_scale_height();
 
-                    // Wick
-                    backend.line(
-                        Point::new(x, price_to_y(bar.high)),
-                        Point::new(x, price_to_y(bar.low)),
-                        &LineStyle::solid(color, 1.0),
-                    );
+        let mut backend = self.frame_backend(record);
 
-                    // Body
-                    let body_top = price_to_y(bar.open.max(bar.close));
-                    let body_bottom = price_to_y(bar.open.min(bar.close));
-                    let body_height = (body_bottom - body_top).max(1.0);
-                    backend.fill_rect(
-                        Rect::new(x - bar_width / 2.0, body_top, bar_width, body_height),
-                        color,
-                    );
-                }
+        let theme = &self.config.theme;
+        backend.clear(Color::from_css(&theme.background).unwrap_or(Color::rgb(19, 23, 34)));
+
+        let bar_count = if empty.show_skeleton {
+            empty.skeleton_bar_count
+        } else {
+            0
+        };
+        let bar_spacing = chart_width / bar_count.max(1) as f64;
+        if theme.show_grid {
+            self.draw_grid(
+                &mut backend,
+                chart_height,
+                &|i| bar_spacing * (i as f64 + 0.5),
+                chart_width as u32,
+                bar_count,
+            );
+        }
+
+        if bar_count > 0 {
+            let color = Color::from_css(&theme.text_color)
+                .unwrap_or(Color::rgb(180, 180, 180))
+                .with_alpha(0.15);
+            let wick = LineStyle::solid(color, 1.0);
+            let body_width = (bar_spacing * 0.6).max(1.0);
+            if empty.animate {
+                backend.push_pulse_layer(0.4, 1.6);
             }
-            SeriesType::Line => {
-                let points: Vec<Point> = bars
-                    .iter()
-                    .enumerate()
-                    .map(|(i, bar)| Point::new(bar_to_x(i), price_to_y(bar.close)))
-                    .collect();
-                if points.len() >= 2 {
-                    backend.polyline(&points, &LineStyle::solid(up_color, 1.5));
-                }
+            for (i, (top, bottom, high, low)) in empty.skeleton_candles().into_iter().enumerate() {
+                let x = bar_spacing * (i as f64 + 0.5);
+                backend.crisp_line(
+                    Point::new(x, high * chart_height),
+                    Point::new(x, low * chart_height),
+                    &wick,
+                );
+                backend.crisp_fill_rect(
+                    Rect::new(
+                        x - body_width / 2.0,
+                        top * chart_height,
+                        body_width,
+                        (bottom - top) * chart_height,
+                    ),
+                    color,
+                );
             }
-            SeriesType::Area => {
-                let line_color = config
-                    .series
-                    .style
-                    .color
-                    .as_ref()
-                    .and_then(|c| Color::from_css(c))
-                    .unwrap_or(up_color);
-                let fill_color = line_color.with_alpha(0.3);
+            if empty.animate {
+                backend.pop_layer();
+            }
+        }
 
-                let points: Vec<Point> = bars
-                    .iter()
-                    .enumerate()
-                    .map(|(i, bar)| Point::new(bar_to_x(i), price_to_y(bar.close)))
-                    .collect();
+        // Scale chrome without labels
+        let border_color = Color::from_css(&theme.grid_color).unwrap_or(Color::rgb(42, 46, 57));
+        let border = LineStyle::solid(border_color, 1.0);
+        if self.config.show_price_scale {
+            backend.crisp_line(
+                Point::new(chart_width, 0.0),
+                Point::new(chart_width, chart_height),
+                &border,
+            );
+        }
+        if self.config.show_time_scale {
+            backend.crisp_line(
+                Point::new(0.0, chart_height),
+                Point::new(chart_width, chart_height),
+                &border,
+            );
+        }
 
-                if points.len() >= 2 {
-                    // Line
-                    backend.polyline(&points, &LineStyle::solid(line_color, 1.5));
+        if !empty.message.is_empty() {
+            let style = TextStyle {
+                color: Color::from_css(&theme.text_color).unwrap_or(Color::rgb(120, 123, 134)),
+                font_size: 13.0,
+                font_weight: FontWeight::Normal,
+                align: TextAlign::Center,
+                baseline: TextBaseline::Middle,
+                ..Default::default()
+            };
+            backend.text(
+                &empty.message,
+                Point::new(chart_width / 2.0, chart_height / 2.0),
+                &style,
+            );
+        }
 
-                    // Fill
-                    let mut fill_points = points.clone();
-                    let base_y =
-                        price_to_y(bars.iter().map(|b| b.low).fold(f64::INFINITY, f64::min));
-                    fill_points.push(Point::new(points.last().unwrap().x, base_y));
-                    fill_points.push(Point::new(points.first().unwrap().x, base_y));
-                    backend.fill_path(&Path::polygon(&fill_points), &FillStyle::solid(fill_color));
-                }
-            }
-            SeriesType::Bar => {
-                for (i, bar) in bars.iter().enumerate() {
-                    let x = bar_to_x(i);
-                    let is_up = bar.close >= bar.open;
-                    let color = if is_up { up_color } else { down_color };
+        self.attach_description(&mut backend);
+        backend.end_frame();
+        backend
+    }
 
-                    // Vertical line (high to low)
-                    backend.line(
-                        Point::new(x, price_to_y(bar.high)),
-                        Point::new(x, price_to_y(bar.low)),
-                        &LineStyle::solid(color, 1.0),
-                    );
-                    // Open tick (left)
-                    backend.line(
-                        Point::new(x - bar_width / 2.0, price_to_y(bar.open)),
-                        Point::new(x, price_to_y(bar.open)),
-                        &LineStyle::solid(color, 1.0),
-                    );
-                    // Close tick (right)
-                    backend.line(
-                        Point::new(x, price_to_y(bar.close)),
-                        Point::new(x + bar_width / 2.0, price_to_y(bar.close)),
-                        &LineStyle::solid(color, 1.0),
-                    );
-                }
-            }
-            SeriesType::Baseline => {
-                let baseline = bars.iter().map(|b| b.close).sum::<f64>() / bars.len() as f64;
-                let baseline_y = price_to_y(baseline);
+    /// Higher-timeframe bars of the HTF overlay, if enabled
+    fn htf_bars(&self) -> Vec<(Bar, Range<usize>)> {
+        self.config
+            .htf_overlay
+            .as_ref()
+            .map(|htf| compute::resample(&self.bars, htf.interval_secs))
+            .unwrap_or_default()
+    }
 
-                // Baseline
-                let baseline_color = Color::rgb(120, 120, 120);
-                backend.line(
-                    Point::new(bar_to_x(0) - 10.0, baseline_y),
-                    Point::new(bar_to_x(bars.len() - 1) + 10.0, baseline_y),
-                    &LineStyle::dashed(baseline_color, 1.0, 4.0, 2.0),
-                );
+    /// Padded low/high of the main price scale
+    fn price_bounds(
+        &self,
+        price_overlays: &[&Indicator],
+        htf_bars: &[(Bar, Range<usize>)],
+    ) -> (f64, f64) {
+        if let Some(bounds) = self.locked_bounds {
+            return bounds;
+        }
+        let (mut price_min, mut price_max) = self.price_range(price_overlays);
+        for (bar, _) in htf_bars {
+            price_min = price_min.min(bar.low);
+            price_max = price_max.max(bar.high);
+        }
+        let price_padding = (price_max - price_min) * 0.05;
+        (price_min - price_padding, price_max + price_padding)
+    }
 
-                // Line with color based on above/below
-                for (i, bar) in bars.iter().enumerate().skip(1) {
-                    let prev = &bars[i - 1];
-                    let color = if bar.close >= baseline {
-                        up_color
-                    } else {
-                        down_color
-                    };
-                    backend.line(
-                        Point::new(bar_to_x(i - 1), price_to_y(prev.close)),
-                        Point::new(bar_to_x(i), price_to_y(bar.close)),
-                        &LineStyle::solid(color, 1.5),
-                    );
+    fn price_range(&self, overlays: &[&Indicator]) -> (f64, f64) {
+        let mut min = f64::INFINITY;
+        let mut max = f64::NEG_INFINITY;
+
+        if self.config.series_visible() {
+            (min, max) = self.series_range();
+        }
+
+        // Include overlay indicator values in range
+        for indicator in overlays.iter().filter(|ind| ind.autoscale) {
+            for vector in &indicator.vectors {
+                for &v in &vector.values {
+                    if !v.is_nan() {
+                        min = min.min(v);
+                        max = max.max(v);
+                    }
                 }
             }
-            _ => {
-                // Fallback to line
-                let points: Vec<Point> = bars
-                    .iter()
-                    .enumerate()
-                    .map(|(i, bar)| Point::new(bar_to_x(i), price_to_y(bar.close)))
-                    .collect();
-                if points.len() >= 2 {
-                    backend.polyline(&points, &LineStyle::solid(up_color, 1.5));
+        }
+
+        // And main-pane drawings that opted in
+        let drawings = self
+            .config
+            .primitives
+            .iter()
+            .filter(|p| p.autoscale && p.visible && p.pane_id.is_none());
+        for prim in drawings {
+            for (i, &(_, price)) in prim.points.iter().enumerate() {
+                let on_main = prim.point_panes.get(i).is_none_or(Option::is_none);
+                if on_main && price.is_finite() {
+                    min = min.min(price);
+                    max = max.max(price);
                 }
             }
         }
+
+        // A hidden series still scales an otherwise empty pane
+        if min > max {
+            return self.series_range();
+        }
+        (min, max)
     }
 
-    fn render_vector_simple(
-        backend: &mut SvgBackend,
-        vector: &crate::model::IndicatorVector,
-        bar_to_x: &impl Fn(usize) -> f64,
-        value_to_y: &impl Fn(f64) -> f64,
-        zero_y: f64,
-    ) {
-        match &vector.style {
-            VectorStyle::Line {
-                color,
-                width,
-                dashed,
-            } => {
-                let c = Color::from_css(color).unwrap_or(Color::WHITE);
-                let points: Vec<Point> = vector
-                    .values
-                    .iter()
-                    .enumerate()
-                    .filter(|&(_, &v)| !v.is_nan())
-                    .map(|(i, &v)| Point::new(bar_to_x(i), value_to_y(v)))
-                    .collect();
-                if points.len() >= 2 {
-                    let style = if *dashed {
-                        LineStyle::dashed(c, *width, 4.0, 2.0)
-                    } else {
-                        LineStyle::solid(c, *width)
-                    };
-                    backend.polyline(&points, &style);
-                }
-            }
-            VectorStyle::Histogram {
-                up_color,
-                down_color,
-                bar_width_ratio,
-            } => {
-                let up = Color::from_css(up_color).unwrap_or(Color::rgb(38, 166, 154));
-                let down = Color::from_css(down_color).unwrap_or(Color::rgb(239, 83, 80));
-                let bar_w = 3.0 * bar_width_ratio;
-
-                for (i, &v) in vector.values.iter().enumerate() {
-                    if v.is_nan() {
-                        continue;
-                    }
-                    let x = bar_to_x(i);
-                    let y = value_to_y(v);
-                    // Use directions vector if available, otherwise fallback to value sign
-                    let is_up = vector.direction_at(i).unwrap_or(v >= 0.0);
-                    let color = if is_up { up } else { down };
-                    let h = (zero_y - y).abs();
-                    let top_y = if v >= 0.0 { y } else { zero_y };
-                    backend.fill_rect(Rect::new(x - bar_w / 2.0, top_y, bar_w, h), color);
-                }
-            }
-            VectorStyle::Area {
-                color,
-                fill_alpha,
-                line_width,
-            } => {
-                let c = Color::from_css(color).unwrap_or(Color::WHITE);
-                let points: Vec<Point> = vector
-                    .values
-                    .iter()
-                    .enumerate()
-                    .filter(|&(_, &v)| !v.is_nan())
-                    .map(|(i, &v)| Point::new(bar_to_x(i), value_to_y(v)))
-                    .collect();
-                if points.len() >= 2 {
-                    backend.polyline(&points, &LineStyle::solid(c, *line_width));
-                    let fill = c.with_alpha(*fill_alpha);
-                    let mut fill_pts = points.clone();
-                    fill_pts.push(Point::new(points.last().unwrap().x, zero_y));
-                    fill_pts.push(Point::new(points.first().unwrap().x, zero_y));
-                    backend.fill_path(&Path::polygon(&fill_pts), &FillStyle::solid(fill));
-                }
+    /// Low/high of the main series, including an area baseline
+    fn series_range(&self) -> (f64, f64) {
+        let mut min = f64::INFINITY;
+        let mut max = f64::NEG_INFINITY;
+        for bar in self.bars.iter() {
+            if !bar.low.is_nan() {
+                min = min.min(bar.low);
             }
-            VectorStyle::Dots {
-                color,
-                radius,
-                filled,
-            } => {
-                let c = Color::from_css(color).unwrap_or(Color::WHITE);
-                for (i, &v) in vector.values.iter().enumerate() {
-                    if v.is_nan() {
-                        continue;
-                    }
-                    let center = Point::new(bar_to_x(i), value_to_y(v));
-                    if *filled {
-                        backend.fill_circle(center, *radius, c);
-                    } else {
-                        backend.stroke_circle(center, *radius, &LineStyle::solid(c, 1.0));
-                    }
-                }
+            if !bar.high.is_nan() {
+                max = max.max(bar.high);
             }
-            _ => {}
         }
+        if let Some(base) = area_base(&self.config.series) {
+            min = min.min(base);
+            max = max.max(base);
+        }
+        (min, max)
     }
 
-    fn render_price_scale_simple(
+    fn draw_grid(
+        &self,
         backend: &mut SvgBackend,
-        config: &ChartConfig,
-        x: f64,
-        y_offset: f64,
         height: f64,
-        price_min: f64,
-        price_max: f64,
+        bar_to_x: &impl Fn(usize) -> f64,
+        width: u32,
+        bar_count: usize,
     ) {
-        let bg_color = Color::from_css(&config.theme.background).unwrap_or(Color::rgb(19, 23, 34));
-        let border_color =
-            Color::from_css(&config.theme.grid_color).unwrap_or(Color::rgb(42, 46, 57));
-        let text_color =
-            Color::from_css(&config.theme.text_color).unwrap_or(Color::rgb(180, 180, 180));
-
-        backend.fill_rect(Rect::new(x, y_offset, PRICE_SCALE_WIDTH, height), bg_color);
-        backend.line(
-            Point::new(x, y_offset),
-            Point::new(x, y_offset + height),
-            &LineStyle::solid(border_color, 1.0),
-        );
+        let grid_color =
+            Color::from_css(&self.config.theme.grid_color).unwrap_or(Color::rgb(30, 34, 45));
+        let style = LineStyle::solid(grid_color, 1.0);
+        let sizing = self.config.sizing();
 
-        let price_scale = PriceScale::new(price_min, price_max);
-        let ticks = price_scale.generate_ticks(height);
-        let font_size = price_scale.calc_font_size(height).min(10.0);
-        let text_style = TextStyle {
-            color: text_color,
-            font_size,
-            font_weight: FontWeight::Normal,
-            align: TextAlign::Left,
-            baseline: TextBaseline::Middle,
-            ..Default::default()
-        };
+        // Horizontal lines, about one per grid spacing
+        let h_count = sizing.grid_count(height);
+        for i in 1..h_count {
+            let y = height * i as f64 / h_count as f64;
+            backend.crisp_line(Point::new(0.0, y), Point::new(width as f64, y), &style);
+        }
 
-        for tick in ticks {
-            let ratio = (tick - price_min) / (price_max - price_min);
-            let y = y_offset + height - ratio * height;
-            backend.line(
-                Point::new(x, y),
-                Point::new(x + 3.0, y),
-                &LineStyle::solid(border_color, 1.0),
-            );
-            let label = price_scale.format_price(tick, height);
-            backend.text(&label, Point::new(x + 4.0, y), &text_style);
+        // Vertical lines
+        let v_step = (bar_count / sizing.grid_count(width as f64)).max(1);
+        for i in (0..bar_count).step_by(v_step) {
+            let x = bar_to_x(i);
+            backend.crisp_line(Point::new(x, 0.0), Point::new(x, height), &style);
         }
     }
 
-    /// Render overlay_bottom indicators generically (own Y scale at bottom of main chart)
-    fn render_overlay_bottom_simple(
-        backend: &mut SvgBackend,
-        bars: &[Bar],
-        indicators: &[&Indicator],
+    fn render_main_series(
+        &self,
+        batch: &mut RenderBatch,
         bar_to_x: &impl Fn(usize) -> f64,
-        y_offset: f64,
-        main_height: f64,
-        config: &ChartConfig,
+        price_to_y: &impl Fn(f64) -> f64,
+        bar_width: f64,
+        price_height: f64,
+        dpr: f64,
     ) {
-        for indicator in indicators {
-            let height_ratio = indicator.placement.height_ratio();
-            let indicator_height = main_height * height_ratio;
-            let y_bottom = y_offset + main_height;
-
-            // For Volume-like indicators: if vector.values is empty, use bars data
-            let has_data = indicator.vectors.iter().any(|v| !v.values.is_empty());
+        let series = &self.config.series;
+        let theme = &self.config.theme;
 
-            if has_data {
-                // Calculate range for this indicator
-                let (range_min, range_max) = Self::calc_indicator_range(indicator, bars);
-                if range_max <= range_min {
-                    continue;
-                }
+        match series.series_type {
+            SeriesType::Candlestick | SeriesType::HollowCandlestick => {
+                let data = candlestick_data(&self.config, &self.bars);
 
-                let value_to_y = |v: f64| -> f64 {
-                    let ratio = (v - range_min) / (range_max - range_min);
-                    y_bottom - ratio * indicator_height
-                };
-                let zero_y = value_to_y(0.0);
+                let options = candlestick_style_options(&self.config);
 
-                for vector in &indicator.vectors {
-                    Self::render_vector_simple(backend, vector, bar_to_x, &value_to_y, zero_y);
-                }
-            } else {
-                // Auto-populate from bars (Volume indicator)
-                Self::render_volume_from_bars_simple(
-                    backend,
-                    bars,
-                    indicator,
+                render_candlesticks(batch, &data, &options, bar_to_x, price_to_y, bar_width, dpr);
+            }
+            SeriesType::Bar => {
+                let data: Vec<BarData> = self
+                    .bars
+                    .iter()
+                    .enumerate()
+                    .map(|(i, b)| BarData {
+                        bar: *b,
+                        color: self.config.bar_colors.get(i).cloned().flatten(),
+                    })
+                    .collect();
+                let options = bar_style_options(&self.config);
+                render_bars(batch, &data, &options, bar_to_x, price_to_y, bar_width, dpr);
+            }
+            SeriesType::HeikinAshi => {
+                let data = candlestick_data(&self.config, &self.bars);
+
+                let options = heikin_ashi_style_options(&self.config);
+
+                render_heikin_ashi(batch, &data, &options, bar_to_x, price_to_y, bar_width, dpr);
+            }
+            SeriesType::Line => {
+                let data: Vec<LineData> = self
+                    .bars
+                    .iter()
+                    .map(|b| LineData {
+                        point: SingleValue {
+                            timestamp: b.timestamp,
+                            value: b.close,
+                        },
+                        color: None,
+                    })
+                    .collect();
+
+                let options = LineStyleOptions {
+                    color: series
+                        .style
+                        .color
+                        .clone()
+                        .unwrap_or_else(|| theme.up_color.clone()),
+                    ..Default::default()
+                };
+                render_line(batch, &data, &options, bar_to_x, price_to_y, dpr);
+            }
+            SeriesType::Area => {
+                let data: Vec<AreaData> = self
+                    .bars
+                    .iter()
+                    .map(|b| AreaData {
+                        point: SingleValue {
+                            timestamp: b.timestamp,
+                            value: b.close,
+                        },
+                        color: None,
+                    })
+                    .collect();
+
+                let line_color = series.style.color.as_ref().unwrap_or(&theme.up_color);
+                let opacity = series.style.fill_opacity.unwrap_or(0.3);
+                let fill = |css: &str, alpha: f64| {
+                    Color::from_css(css)
+                        .map(|c| c.with_alpha(alpha).to_css())
+                        .unwrap_or_else(|| css.to_string())
+                };
+                let options = AreaStyleOptions {
+                    top_color: fill(line_color, opacity),
+                    bottom_color: fill(line_color, 0.0),
+                    base_value: area_base(series),
+                    below_color: series.style.down_color.as_deref().map(|c| fill(c, opacity)),
+                    line_color: line_color.clone(),
+                    ..Default::default()
+                };
+                render_area(
+                    batch,
+                    &data,
+                    &options,
+                    bar_to_x,
+                    price_to_y,
+                    price_height,
+                    dpr,
+                );
+            }
+            SeriesType::Baseline => {
+                let data: Vec<BaselineData> = self
+                    .bars
+                    .iter()
+                    .map(|b| BaselineData {
+                        point: SingleValue {
+                            timestamp: b.timestamp,
+                            value: b.close,
+                        },
+                        ..Default::default()
+                    })
+                    .collect();
+
+                let zone = |css: &str, alpha: f64| {
+                    Color::from_css(css)
+                        .map(|c| c.with_alpha(alpha).to_css())
+                        .unwrap_or_else(|| css.to_string())
+                };
+                let options = BaselineStyleOptions {
+                    top_fill_color1: zone(&theme.up_color, 0.28),
+                    top_fill_color2: zone(&theme.up_color, 0.05),
+                    top_line_color: theme.up_color.clone(),
+                    bottom_fill_color1: zone(&theme.down_color, 0.05),
+                    bottom_fill_color2: zone(&theme.down_color, 0.28),
+                    bottom_line_color: theme.down_color.clone(),
+                    line_width: series
+                        .style
+                        .line_width
+                        .map_or(2, |w| w.round().max(1.0) as u32),
+                    ..Default::default()
+                };
+                render_baseline(
+                    batch,
+                    BaselineParams {
+                        data: &data,
+                        options: &options,
+                        bar_to_x,
+                        price_to_y,
+                        baseline_value: baseline_value(series, &self.bars),
+                        chart_bottom: price_height,
+                        dpr,
+                    },
+                );
+            }
+            _ => {
+                // Default: candlesticks
+                let data = candlestick_data(&self.config, &self.bars);
+
+                let options = candlestick_style_options(&self.config);
+
+                render_candlesticks(batch, &data, &options, bar_to_x, price_to_y, bar_width, dpr);
+            }
+        }
+    }
+
+    /// Render overlay indicators (on main chart, share price Y scale)
+    fn render_overlay_indicators(
+        &self,
+        backend: &mut SvgBackend,
+        overlays: &[&Indicator],
+        bar_to_x: &impl Fn(usize) -> f64,
+        price_to_y: &impl Fn(f64) -> f64,
+        ctx: &VectorContext,
+    ) {
+        let draw = |backend: &mut SvgBackend, indicator: &Indicator| {
+            draw_indicator_fills(backend, indicator, bar_to_x, price_to_y);
+            for vector in &indicator.vectors {
+                render_vector(backend, ctx, vector, bar_to_x, price_to_y);
+            }
+        };
+        for indicator in overlays {
+            backend.begin_element(&format!("indicator:{}", indicator.id));
+            backend.begin_group(&format!("indicator-{}", indicator.id));
+            if !backend.groups_marked() {
+                draw(backend, indicator);
+                backend.end_group();
+                continue;
+            }
+            let (body, last) = split_last_segment(indicator, self.bars.len());
+            draw(backend, &body);
+            backend.end_group();
+            backend.begin_group(&format!("indicator-{}-last", indicator.id));
+            draw(backend, &last);
+            backend.end_group();
+        }
+    }
+
+    /// Legend of overlay indicators, top-left of the main pane
+    ///
+    /// Grouped indicators are listed, indented, under one header per group,
+    /// placed where the group's first member would be.
+    fn render_legend(&self, backend: &mut SvgBackend) {
+        let theme = &self.config.theme;
+        let text_color = Color::from_css(&theme.text_color).unwrap_or(Color::rgb(180, 180, 180));
+        // Toggled-off indicators keep their row in a faded text color
+        let muted = text_color.with_alpha(0.4);
+        let text_style = |weight, color| TextStyle {
+            color,
+            font_size: 11.0,
+            font_weight: weight,
+            align: TextAlign::Left,
+            baseline: TextBaseline::Middle,
+            ..Default::default()
+        };
+
+        let mut row = 0;
+        let mut next_y = || {
+            row += 1;
+            row as f64 * 16.0 - 2.0
+        };
+        let entry = |backend: &mut SvgBackend, indicator: &Indicator, x: f64, y: f64| {
+            let hidden = !self.config.is_indicator_visible(indicator);
+            if let Some(vector) = indicator.vectors.iter().find(|v| v.show_in_legend) {
+                let color = if hidden {
+                    muted
+                } else {
+                    Color::from_css(vector.style.primary_color())
+                        .unwrap_or(Color::rgb(33, 150, 243))
+                };
+                backend.crisp_line(
+                    Point::new(x, y),
+                    Point::new(x + 14.0, y),
+                    &LineStyle::solid(color, 2.0),
+                );
+            }
+            let color = if hidden { muted } else { text_color };
+            backend.text(
+                &indicator.name,
+                Point::new(x + 20.0, y),
+                &text_style(FontWeight::Normal, color),
+            );
+        };
+
+        // The titled main series heads the list, faded while hidden
+        let title = &self.config.series.common.title;
+        if !title.is_empty() {
+            let y = next_y();
+            let (swatch, color) = if self.config.series_visible() {
+                let style = &self.config.series.style;
+                let series_color = style.color.as_deref().unwrap_or(&theme.up_color);
+                let swatch = Color::from_css(series_color).unwrap_or(text_color);
+                (swatch, text_color)
+            } else {
+                (muted, muted)
+            };
+            backend.crisp_line(
+                Point::new(8.0, y),
+                Point::new(22.0, y),
+                &LineStyle::solid(swatch, 2.0),
+            );
+            backend.text(
+                title,
+                Point::new(28.0, y),
+                &text_style(FontWeight::Bold, color),
+            );
+        }
+
+        let mut header: Option<&str> = None;
+        for indicator in legend_entries(&self.legend_overlays()) {
+            match indicator.group.as_deref() {
+                None => entry(backend, indicator, 8.0, next_y()),
+                Some(group) => {
+                    if header != Some(group) {
+                        header = Some(group);
+                        backend.text(
+                            group,
+                            Point::new(8.0, next_y()),
+                            &text_style(FontWeight::Bold, text_color),
+                        );
+                    }
+                    entry(backend, indicator, 20.0, next_y());
+                }
+            }
+        }
+    }
+
+    /// Mini line charts of the inset series, each in a bordered box over
+    /// the price area and scaled to its own range
+    fn render_insets(&self, backend: &mut SvgBackend, chart_width: f64, price_height: f64) {
+        let theme = &self.config.theme;
+        let mut bg = Color::from_css(&theme.background).unwrap_or(Color::rgb(19, 23, 34));
+        bg.a = 217;
+        let mut border = Color::from_css(&theme.border_color).unwrap_or(Color::rgb(42, 46, 57));
+        border.a = 160;
+        let text_color = Color::from_css(&theme.text_color).unwrap_or(Color::rgb(180, 180, 180));
+        let pad = 4.0;
+
+        for (idx, inset) in self.config.inset_series.iter().enumerate() {
+            let (x, y, w, h) = inset.rect.to_pixels(chart_width, price_height);
+            if w <= 2.0 * pad || h <= 2.0 * pad {
+                continue;
+            }
+            let rect = Rect::new(x, y, w, h);
+            backend.crisp_fill_rect(rect, bg);
+            backend.crisp_stroke_rect(rect, &LineStyle::solid(border, 1.0));
+
+            if let Some(label) = &inset.label {
+                backend.text(
+                    label,
+                    Point::new(x + pad, y + pad + 5.0),
+                    &TextStyle {
+                        color: text_color,
+                        font_size: 10.0,
+                        align: TextAlign::Left,
+                        baseline: TextBaseline::Middle,
+                        ..Default::default()
+                    },
+                );
+            }
+
+            let (lo, hi) = inset
+                .values
+                .iter()
+                .filter(|v| v.is_finite())
+                .fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), &v| {
+                    (lo.min(v), hi.max(v))
+                });
+            if lo > hi {
+                continue;
+            }
+            let (inner_top, inner_h) = (y + pad, h - 2.0 * pad);
+            let value_to_y = |v: f64| {
+                if hi > lo {
+                    inner_top + (hi - v) / (hi - lo) * inner_h
+                } else {
+                    inner_top + inner_h / 2.0
+                }
+            };
+            let step = (w - 2.0 * pad) / (inset.values.len().max(2) - 1) as f64;
+            let points: Vec<Point> = inset
+                .values
+                .iter()
+                .enumerate()
+                .filter(|(_, v)| v.is_finite())
+                .map(|(i, &v)| Point::new(x + pad + i as f64 * step, value_to_y(v)))
+                .collect();
+            if points.len() >= 2 {
+                let color = inset
+                    .color
+                    .as_deref()
+                    .and_then(Color::from_css)
+                    .unwrap_or_else(|| {
+                        Color::from_css(get_compare_color(idx)).unwrap_or(Color::WHITE)
+                    });
+                backend.polyline(&points, &LineStyle::solid(color, 1.5));
+            }
+        }
+    }
+
+    /// Render overlay_bottom indicators (at bottom of main chart with own Y scale)
+    fn render_overlay_bottom_indicators(
+        &self,
+        backend: &mut SvgBackend,
+        indicators: &[&Indicator],
+        bar_to_x: &impl Fn(usize) -> f64,
+        main_height: f64,
+        chart_width: f64,
+        bar_spacing: f64,
+    ) {
+        for indicator in indicators {
+            backend.begin_element(&format!("indicator:{}", indicator.id));
+            let indicator_height = indicator.placement.strip_height(main_height);
+            let y_bottom = main_height;
+            let opacity = indicator.placement.opacity();
+            if opacity < 1.0 {
+                backend.push_layer(opacity);
+            }
+
+            // For Volume-like indicators: if vector.values is empty, use bars data
+            let has_data = indicator.vectors.iter().any(|v| !v.values.is_empty());
+
+            if has_data {
+                // Use indicator's own values
+                let pane = Rect::new(
+                    0.0,
+                    y_bottom - indicator_height,
+                    chart_width,
+                    indicator_height,
+                );
+                draw_pane_vectors(
+                    backend,
+                    indicator,
+                    indicator,
+                    pane,
+                    bar_spacing,
+                    &self.bars,
+                    bar_to_x,
+                );
+            } else {
+                // Auto-populate from bars (Volume indicator)
+                self.render_volume_from_bars(
+                    backend,
+                    indicator,
                     bar_to_x,
+                    bar_spacing,
                     y_bottom,
                     indicator_height,
-                    config,
                 );
             }
+
+            if opacity < 1.0 {
+                backend.pop_layer();
+            }
         }
     }
 
-    /// Render Volume indicator using bar data directly (for MultichartRenderer)
-    fn render_volume_from_bars_simple(
+    /// Render Volume indicator using bar data directly
+    fn render_volume_from_bars(
+        &self,
         backend: &mut SvgBackend,
-        bars: &[Bar],
         indicator: &Indicator,
         bar_to_x: &impl Fn(usize) -> f64,
+        bar_spacing: f64,
         y_bottom: f64,
         indicator_height: f64,
-        config: &ChartConfig,
     ) {
-        if bars.is_empty() {
+        if self.bars.is_empty() {
             return;
         }
 
         // Find max volume for scaling
-        let max_vol = bars
+        let max_vol = self
+            .bars
             .iter()
             .map(|b| b.volume)
             .filter(|v| !v.is_nan())
@@ -1807,7 +3235,7 @@ impl<'a> MultichartRenderer<'a> {
             y_bottom - ratio * indicator_height
         };
 
-        // Get histogram style colors from indicator, fallback to theme colors
+        // Get histogram style colors
         let (up_color, down_color, bar_width_ratio) = indicator
             .vectors
             .first()
@@ -1817,946 +3245,7947 @@ impl<'a> MultichartRenderer<'a> {
                     down_color,
                     bar_width_ratio,
                 } => (up_color.clone(), down_color.clone(), *bar_width_ratio),
-                _ => (
-                    config.theme.up_color.clone(),
-                    config.theme.down_color.clone(),
-                    0.8,
-                ),
+                _ => ("#26a69a".to_string(), "#ef5350".to_string(), 0.8),
+            })
+            .unwrap_or(("#26a69a".to_string(), "#ef5350".to_string(), 0.8));
+
+        let up = Color::from_css(&up_color).unwrap_or(Color::rgb(38, 166, 154));
+        let down = Color::from_css(&down_color).unwrap_or(Color::rgb(239, 83, 80));
+
+        let bar_width = histogram_bar_width(bar_spacing, bar_width_ratio);
+
+        for (i, bar) in self.bars.iter().enumerate() {
+            let vol = bar.volume;
+            if vol.is_nan() || vol <= 0.0 {
+                continue;
+            }
+
+            let x = bar_to_x(i);
+            let y = value_to_y(vol);
+            let bar_h = (y_bottom - y).max(1.0);
+
+            // Color based on bar direction
+            let color = if bar.close >= bar.open { up } else { down };
+
+            backend.crisp_fill_rect(Rect::new(x - bar_width / 2.0, y, bar_width, bar_h), color);
+        }
+    }
+
+    fn render_primitives(
+        &self,
+        backend: &mut SvgBackend,
+        bar_to_x: &impl Fn(usize) -> f64,
+        price_to_y: &impl Fn(f64) -> f64,
+        dpr: f64,
+        pane_id: Option<usize>,
+    ) {
+        let primitives = self.config.primitives.iter().enumerate();
+        for (index, prim_config) in primitives.filter(|(_, p)| p.visible && !p.spans_panes()) {
+            // Filter by pane
+            match (pane_id, &prim_config.pane_id) {
+                (None, None) => {}                        // Main pane, no pane_id specified
+                (Some(id), Some(pid)) if *pid == id => {} // Matching pane
+                _ => continue,                            // Skip non-matching
+            }
+            backend.begin_element(&primitive_element(index, prim_config));
+
+            let points = self.resolve_points(prim_config);
+            self.render_primitive(backend, prim_config, &points, bar_to_x, price_to_y, dpr);
+        }
+    }
+
+    /// Primitives with points on more than one pane
+    ///
+    /// Each point's value is mapped to y on its own pane: the subpane of its
+    /// indicator, else the primitive's pane. The primitive is then drawn in
+    /// pixels, so one line can join a price swing to an oscillator swing.
+    /// Primitives naming an indicator without a subpane are skipped.
+    fn render_cross_pane_primitives(
+        &self,
+        backend: &mut SvgBackend,
+        bar_to_x: &impl Fn(usize) -> f64,
+        main: PaneTransform,
+        subpanes: &[(&str, PaneTransform)],
+        dpr: f64,
+    ) {
+        let primitives = self.config.primitives.iter().enumerate();
+        for (index, prim_config) in primitives.filter(|(_, p)| p.visible && p.spans_panes()) {
+            backend.begin_element(&primitive_element(index, prim_config));
+            let own = match prim_config.pane_id {
+                None => Some(main),
+                Some(idx) => subpanes.get(idx).map(|&(_, pane)| pane),
+            };
+            let pixels = self
+                .resolve_points(prim_config)
+                .iter()
+                .enumerate()
+                .map(|(i, &(bar, value))| {
+                    let pane = match prim_config.point_panes.get(i).and_then(Option::as_deref) {
+                        Some(id) => subpanes
+                            .iter()
+                            .find(|(indicator_id, _)| *indicator_id == id)
+                            .map(|&(_, pane)| pane),
+                        None => own,
+                    }?;
+                    Some((bar, pane.value_to_y(value)))
+                })
+                .collect::<Option<Vec<_>>>();
+            if let Some(pixels) = pixels {
+                self.render_primitive(backend, prim_config, &pixels, bar_to_x, &|y| y, dpr);
+            }
+        }
+    }
+
+    /// Apply a primitive's style overrides, then chart-wide defaults it
+    /// leaves unset
+    fn apply_style(&self, prim_config: &PrimitiveConfig, primitive: &mut dyn PrimitiveTrait) {
+        prim_config.apply_style(primitive);
+        if prim_config.fib_labels.is_none()
+            && let Some(mode) = self.config.fib_labels
+        {
+            primitive.set_fib_labels(mode);
+        }
+    }
+
+    /// Create one primitive from the registry and draw it, then its
+    /// selection on top
+    fn render_primitive(
+        &self,
+        backend: &mut SvgBackend,
+        prim_config: &PrimitiveConfig,
+        points: &[(f64, f64)],
+        bar_to_x: &impl Fn(usize) -> f64,
+        price_to_y: &impl Fn(f64) -> f64,
+        dpr: f64,
+    ) {
+        let Ok(mut primitive) =
+            self.registry()
+                .create(&prim_config.type_id, points, Some(&prim_config.color))
+        else {
+            return;
+        };
+        self.apply_style(prim_config, primitive.as_mut());
+
+        let mut ctx = SvgRenderContext::new(
+            backend,
+            bar_to_x,
+            price_to_y,
+            dpr,
+            self.config.width as f64,
+            self.config.height as f64,
+        )
+        .with_bars(&self.bars);
+        let selected = self.config.is_primitive_selected(prim_config);
+        primitive.render(&mut ctx, selected);
+        if selected {
+            primitive.render_selection(&mut ctx);
+        }
+    }
+
+    /// Primitive points on the bar axis
+    ///
+    /// Timestamp anchors are mapped onto the bars, extrapolating by the
+    /// median bar interval past either end. Without bars to place them on,
+    /// they are left as given.
+    fn resolve_points<'p>(&self, prim_config: &'p PrimitiveConfig) -> Cow<'p, [(f64, f64)]> {
+        if prim_config.anchor != PointAnchor::Timestamp {
+            return Cow::Borrowed(&prim_config.points);
+        }
+        prim_config
+            .points
+            .iter()
+            .map(|&(time, price)| {
+                let time = self.timestamp_unit.to_seconds(time as i64);
+                compute::timestamp_to_bar(&self.bars, time).map(|bar| (bar, price))
+            })
+            .collect::<Option<Vec<_>>>()
+            .map_or(Cow::Borrowed(&prim_config.points), Cow::Owned)
+    }
+
+    /// Visible strategy with theme slot colors resolved against the chart theme
+    fn resolved_strategy(&self) -> Option<Strategy> {
+        let mut strategy = self.config.strategy.clone().filter(|s| s.visible)?;
+        strategy.resolve_colors(&self.config.theme.strategy_theme());
+        for indicator in &mut strategy.indicators {
+            if let Some(smoothed) = indicator.smoothed() {
+                *indicator = smoothed;
+            }
+        }
+        Some(strategy)
+    }
+
+    fn render_strategy(
+        &self,
+        backend: &mut SvgBackend,
+        strategy: &Strategy,
+        bar_to_x: &impl Fn(usize) -> f64,
+        bar_width: f64,
+        price_to_y: &impl Fn(f64) -> f64,
+        dpr: f64,
+    ) {
+        let bar_to_x_float = |b: f64| -> f64 { fractional_bar_x(bar_to_x, b) };
+
+        // Only overlays share the main price scale
+        let overlay_only = Strategy {
+            indicators: strategy
+                .indicators
+                .iter()
+                .filter(|ind| ind.placement.is_overlay())
+                .cloned()
+                .collect(),
+            ..strategy.clone()
+        };
+
+        let mut batch = RenderBatch::new();
+        render_strategy(
+            &mut batch,
+            StrategyParams {
+                strategy: &overlay_only,
+                bar_to_x,
+                bar_to_x_float,
+                price_to_y,
+                bar_width,
+                dpr,
+                visible_range: None,
+            },
+        );
+        self.execute_batch(backend, &batch);
+
+        // Drawings go through the primitive registry: both strategy primitives
+        // and signals whose visual is a primitive reference
+        let drawings =
+            strategy
+                .primitives
+                .iter()
+                .filter(|p| p.visible)
+                .map(|p| (&p.primitive_type, &p.points, p.color.as_deref()))
+                .chain(strategy.signals.iter().filter(|s| s.visible).filter_map(
+                    |s| match &s.visual {
+                        SignalVisual::Primitive {
+                            primitive_type,
+                            points,
+                            color,
+                        } => Some((primitive_type, points, color.as_deref())),
+                        _ => None,
+                    },
+                ));
+
+        let registry = self.registry();
+        for (type_id, points, color) in drawings {
+            if let Ok(primitive) = registry.create(type_id, points, color) {
+                let mut ctx = SvgRenderContext::new(
+                    backend,
+                    bar_to_x,
+                    price_to_y,
+                    dpr,
+                    self.config.width as f64,
+                    self.config.height as f64,
+                )
+                .with_bars(&self.bars);
+                primitive.render(&mut ctx, false);
+            }
+        }
+    }
+
+    fn render_signals(
+        &self,
+        backend: &mut SvgBackend,
+        bar_to_x: &impl Fn(usize) -> f64,
+        price_to_y: &impl Fn(f64) -> f64,
+        format_price: &impl Fn(f64) -> String,
+        dpr: f64,
+    ) {
+        use crate::primitives::SignalType;
+
+        // Glyph anchors and sizes sit on whole device pixels so edges stay sharp
+        let snap = |v: f64| (v * dpr).round() / dpr;
+        let sizing = self.config.sizing();
+
+        for signal in &self.config.signals {
+            let x = snap(bar_to_x(signal.bar_index));
+            let y = snap(price_to_y(signal.price));
+
+            let default_color = match signal.signal_type {
+                SignalType::Buy | SignalType::Entry | SignalType::TakeProfit => "#26a69a",
+                SignalType::Sell | SignalType::Exit | SignalType::StopLoss => "#ef5350",
+                SignalType::Custom => "#9c27b0",
+            };
+            let color = signal
+                .color
+                .as_deref()
+                .and_then(Color::from_css)
+                .unwrap_or_else(|| Color::from_css(default_color).unwrap());
+            let size = snap(signal.size * sizing.signal_size); // size is a multiplier
+            let half = size / 2.0;
+
+            match signal.signal_type {
+                SignalType::Buy | SignalType::Entry => {
+                    self.draw_arrow_up(backend, x, y, size, color);
+                }
+                SignalType::Sell | SignalType::Exit => {
+                    self.draw_arrow_down(backend, x, y, size, color);
+                }
+                SignalType::TakeProfit => {
+                    // Target: ring around a dot
+                    let ring = LineStyle::solid(color, (size / 6.0).max(1.0));
+                    backend.stroke_circle(Point::new(x, y), half * 0.85, &ring);
+                    backend.fill_circle(Point::new(x, y), half * 0.35, color);
+                }
+                SignalType::StopLoss => {
+                    // Stop sign
+                    let octagon: Vec<Point> = (0..8)
+                        .map(|i| {
+                            let angle = std::f64::consts::PI * (i as f64 + 0.5) / 4.0;
+                            Point::new(x + half * angle.cos(), y + half * angle.sin())
+                        })
+                        .collect();
+                    backend.fill_path(&Path::polygon(&octagon), &FillStyle::solid(color));
+                }
+                SignalType::Custom => {
+                    let diamond = [
+                        Point::new(x, y - half),
+                        Point::new(x + half, y),
+                        Point::new(x, y + half),
+                        Point::new(x - half, y),
+                    ];
+                    backend.fill_path(&Path::polygon(&diamond), &FillStyle::solid(color));
+                }
+            }
+
+            let text_style = |baseline| TextStyle {
+                font_family: "sans-serif".into(),
+                font_size: sizing.label_font_size(),
+                font_weight: FontWeight::Normal,
+                color,
+                align: TextAlign::Left,
+                baseline,
+            };
+
+            // Level line from the glyph's right edge, priced just above it,
+            // with the signal's own label moved below so the two don't collide
+            let level = signal.level_line
+                && matches!(
+                    signal.signal_type,
+                    SignalType::TakeProfit | SignalType::StopLoss
+                );
+            if level {
+                let start = x + half;
+                let end = bar_to_x(signal.bar_index + SIGNAL_LEVEL_BARS).max(start + 40.0);
+                backend.crisp_line(
+                    Point::new(start, y),
+                    Point::new(end, y),
+                    &LineStyle::solid(color, 1.0),
+                );
+                backend.text(
+                    &format_price(signal.price),
+                    Point::new(start + 3.0, y - 2.0),
+                    &text_style(TextBaseline::Bottom),
+                );
+            }
+
+            if let Some(ref label) = signal.label {
+                let (pos, baseline) = if level {
+                    (Point::new(x + half + 3.0, y + 2.0), TextBaseline::Top)
+                } else {
+                    (Point::new(x + size, y), TextBaseline::Middle)
+                };
+                backend.text(label, pos, &text_style(baseline));
+            }
+        }
+    }
+
+    fn draw_arrow_up(&self, backend: &mut SvgBackend, x: f64, y: f64, size: f64, color: Color) {
+        let half = size / 2.0;
+        let points = vec![
+            Point::new(x, y - half),        // top
+            Point::new(x - half, y + half), // bottom left
+            Point::new(x + half, y + half), // bottom right
+        ];
+        backend.fill_path(&Path::polygon(&points), &FillStyle::solid(color));
+    }
+
+    fn draw_arrow_down(&self, backend: &mut SvgBackend, x: f64, y: f64, size: f64, color: Color) {
+        let half = size / 2.0;
+        let points = vec![
+            Point::new(x, y + half),        // bottom
+            Point::new(x - half, y - half), // top left
+            Point::new(x + half, y - half), // top right
+        ];
+        backend.fill_path(&Path::polygon(&points), &FillStyle::solid(color));
+    }
+
+    /// Render a subpane indicator (RSI, MACD, Volume, etc.)
+    fn render_subpane_indicator(
+        &self,
+        backend: &mut SvgBackend,
+        params: SubpaneRenderParams<'_>,
+        bar_to_x: &impl Fn(usize) -> f64,
+    ) {
+        let SubpaneRenderParams {
+            indicator,
+            body,
+            y_offset,
+            height,
+            width,
+            pane_idx,
+            bar_spacing,
+        } = params;
+
+        // Subpane background
+        let subpane_bg =
+            Color::from_css(&self.config.theme.background).unwrap_or(Color::rgb(19, 23, 34));
+        backend.crisp_fill_rect(Rect::new(0.0, y_offset, width as f64, height), subpane_bg);
+
+        // Separator line
+        let sep_color =
+            Color::from_css(&self.config.theme.grid_color).unwrap_or(Color::rgb(42, 46, 57));
+        backend.crisp_line(
+            Point::new(0.0, y_offset),
+            Point::new(width as f64, y_offset),
+            &LineStyle::solid(sep_color, 1.0),
+        );
+
+        // Calculate range based on indicator's IndicatorRange
+        let (range_min, range_max) = self.calculate_indicator_range(indicator);
+
+        let value_to_y = |v: f64| -> f64 {
+            let ratio = (v - range_min) / (range_max - range_min);
+            y_offset + height - ratio * height
+        };
+
+        // Draw indicator levels (reference lines like RSI 30/70, MACD zero line)
+        for level in &indicator.levels {
+            let y = value_to_y(level.value);
+            let color = Color::from_css(&level.color).unwrap_or(Color::rgb(120, 123, 134));
+            let style = match level.style.as_str() {
+                "dotted" => LineStyle::dashed(color, level.width, 2.0, 2.0),
+                "dashed" => LineStyle::dashed(color, level.width, 4.0, 4.0),
+                _ => LineStyle::solid(color, level.width),
+            };
+            backend.crisp_line(Point::new(0.0, y), Point::new(width as f64, y), &style);
+        }
+
+        let pane = Rect::new(0.0, y_offset, width as f64, height);
+        draw_pane_vectors(
+            backend,
+            indicator,
+            body,
+            pane,
+            bar_spacing,
+            &self.bars,
+            bar_to_x,
+        );
+
+        // Render primitives for this pane
+        self.render_primitives(
+            backend,
+            bar_to_x,
+            &value_to_y,
+            self.config.pixel_ratio(),
+            Some(pane_idx),
+        );
+    }
+
+    /// Indicator name and latest values in a corner of its subpane
+    ///
+    /// Values take their vector colors and sit on a translucent box so the
+    /// lines underneath don't run through the text.
+    fn render_pane_title(
+        &self,
+        backend: &mut SvgBackend,
+        indicator: &Indicator,
+        y_offset: f64,
+        height: f64,
+        width: f64,
+    ) {
+        const FONT_SIZE: f64 = 11.0;
+        const PAD: f64 = 4.0;
+        const MARGIN: f64 = 4.0;
+        const GAP: f64 = 6.0;
+
+        let theme = &self.config.theme;
+        let text_color = Color::from_css(&theme.text_color).unwrap_or(Color::rgb(180, 180, 180));
+        let mut segments: Vec<(String, Color)> = vec![(indicator.name.clone(), text_color)];
+        if let Some(last) = self.bars.len().checked_sub(1) {
+            for vector in indicator.vectors.iter().filter(|v| v.show_in_legend) {
+                let Some(value) = vector.value_at(last) else {
+                    continue;
+                };
+                // Histogram bars are colored like the last bar drawn
+                let color = match &vector.style {
+                    VectorStyle::Histogram { down_color, .. }
+                        if !vector.direction_at(last).unwrap_or(value >= 0.0) =>
+                    {
+                        down_color.clone()
+                    }
+                    style => style.primary_color().to_string(),
+                };
+                let color = Color::from_css(&color).unwrap_or(text_color);
+                segments.push((format_indicator_value(value), color));
+            }
+        }
+
+        let widths: Vec<f64> = segments
+            .iter()
+            .map(|(text, _)| estimate_text_width(text, FONT_SIZE, FontWeight::Normal))
+            .collect();
+        let box_w = widths.iter().sum::<f64>() + GAP * (widths.len() - 1) as f64 + 2.0 * PAD;
+        let box_h = FONT_SIZE + 2.0 * PAD;
+        if box_h + 2.0 * MARGIN > height {
+            return;
+        }
+        let x = match self.config.legend_position {
+            LegendPosition::TopLeft | LegendPosition::BottomLeft => MARGIN,
+            LegendPosition::TopRight | LegendPosition::BottomRight => width - box_w - MARGIN,
+        };
+        let y = match self.config.legend_position {
+            LegendPosition::TopLeft | LegendPosition::TopRight => y_offset + MARGIN,
+            LegendPosition::BottomLeft | LegendPosition::BottomRight => {
+                y_offset + height - box_h - MARGIN
+            }
+        };
+
+        let mut bg = Color::from_css(&theme.background).unwrap_or(Color::rgb(19, 23, 34));
+        bg.a = 191;
+        backend.fill_rect(Rect::new(x, y, box_w, box_h), bg);
+
+        let mut text_x = x + PAD;
+        for ((text, color), text_w) in segments.iter().zip(&widths) {
+            let style = TextStyle {
+                color: *color,
+                font_size: FONT_SIZE,
+                align: TextAlign::Left,
+                baseline: TextBaseline::Middle,
+                ..Default::default()
+            };
+            backend.text(text, Point::new(text_x, y + box_h / 2.0), &style);
+            text_x += text_w + GAP;
+        }
+    }
+
+    /// Y-axis range of an indicator over the drawn bars, see
+    /// [`indicator_range`]
+    fn calculate_indicator_range(&self, indicator: &Indicator) -> (f64, f64) {
+        indicator_range(indicator, &self.bars)
+    }
+
+    /// Render price scale (Y-axis) on the right side of the chart area
+    fn render_price_scale(&self, backend: &mut SvgBackend, params: PriceScaleParams<'_>) {
+        if !self.config.show_price_scale {
+            return;
+        }
+        let PriceScaleParams {
+            chart_width,
+            y_offset,
+            pane_height,
+            price_min,
+            price_max,
+            tags,
+            tick_size,
+        } = params;
+        let scale_x = chart_width;
+        let sizing = self.config.sizing();
+        let scale_width = self.price_scale_width();
+
+        // Background for price scale area
+        let bg_color =
+            Color::from_css(&self.config.theme.background).unwrap_or(Color::rgb(19, 23, 34));
+        backend.crisp_fill_rect(
+            Rect::new(scale_x, y_offset, scale_width, pane_height),
+            bg_color,
+        );
+
+        // Border line
+        let border_color =
+            Color::from_css(&self.config.theme.grid_color).unwrap_or(Color::rgb(42, 46, 57));
+        backend.crisp_line(
+            Point::new(scale_x, y_offset),
+            Point::new(scale_x, y_offset + pane_height),
+            &LineStyle::solid(border_color, 1.0),
+        );
+
+        let price_scale = PriceScale::new(price_min, price_max).with_tick_size(tick_size);
+
+        let text_color =
+            Color::from_css(&self.config.theme.text_color).unwrap_or(Color::rgb(180, 180, 180));
+        // Labels fitted to the default column, scaled with the column width;
+        // a fitted column was measured at full size instead
+        let font_size = if self.config.price_scale_fit {
+            sizing.scale_font_size
+        } else {
+            (price_scale.calc_font_size(pane_height) * scale_width / PRICE_SCALE_WIDTH)
+                .min(sizing.scale_font_size)
+        };
+
+        let text_style = TextStyle {
+            color: text_color,
+            font_size,
+            font_weight: FontWeight::Normal,
+            align: TextAlign::Left,
+            baseline: TextBaseline::Middle,
+            ..Default::default()
+        };
+
+        // Draw tick marks and labels, thinned so labels never overlap
+        for (tick, y) in price_scale.label_ticks(y_offset, pane_height, font_size, tags) {
+            // Tick line
+            backend.crisp_line(
+                Point::new(scale_x, y),
+                Point::new(scale_x + sizing.tick_length, y),
+                &LineStyle::solid(border_color, 1.0),
+            );
+
+            // Label
+            let label = price_scale.format_price(tick, pane_height);
+            let label_x = scale_x + sizing.tick_length + 2.0;
+            backend.text(&label, Point::new(label_x, y), &text_style);
+        }
+    }
+
+    /// Render time scale (X-axis) at the bottom of the chart
+    fn render_time_scale(
+        &self,
+        backend: &mut SvgBackend,
+        chart_width: f64,
+        chart_height: f64,
+        bar_to_x: &impl Fn(usize) -> f64,
+        bar_spacing: f64,
+    ) {
+        if !self.config.show_time_scale {
+            return;
+        }
+        let scale_y = chart_height;
+        let sizing = self.config.sizing();
+        let scale_height = sizing.time_scale_height;
+
+        // Background for time scale area
+        let bg_color =
+            Color::from_css(&self.config.theme.background).unwrap_or(Color::rgb(19, 23, 34));
+        backend.crisp_fill_rect(Rect::new(0.0, scale_y, chart_width, scale_height), bg_color);
+
+        // Border line at top of time scale
+        let border_color =
+            Color::from_css(&self.config.theme.grid_color).unwrap_or(Color::rgb(42, 46, 57));
+        backend.crisp_line(
+            Point::new(0.0, scale_y),
+            Point::new(chart_width, scale_y),
+            &LineStyle::solid(border_color, 1.0),
+        );
+
+        let text_color =
+            Color::from_css(&self.config.theme.text_color).unwrap_or(Color::rgb(180, 180, 180));
+        let text_style = TextStyle {
+            color: text_color,
+            font_size: sizing.label_font_size(),
+            font_weight: FontWeight::Normal,
+            align: TextAlign::Center,
+            baseline: TextBaseline::Top,
+            ..Default::default()
+        };
+
+        // Determine appropriate tick spacing based on bar_spacing
+        let bars_per_tick = (sizing.min_time_label_spacing / bar_spacing).ceil() as usize;
+        let bars_per_tick = bars_per_tick.max(1);
+
+        // Significant ticks whose bar is on screen; what counts as
+        // significant follows the bar interval
+        let interval = self
+            .config
+            .timeframe_secs
+            .filter(|&tf| tf > 0)
+            .or_else(|| compute::median_bar_interval(&self.bars))
+            .unwrap_or(0);
+        let threshold = TickMarkWeight::label_threshold(interval);
+        let mut candidates = Vec::new();
+        let mut prev_ts: Option<i64> = None;
+        for i in (0..self.bars.len()).step_by(bars_per_tick) {
+            let ts = self.bars[i].timestamp;
+            let x = bar_to_x(i);
+            let weight = TickMarkWeight::from_timestamp(ts, prev_ts);
+            prev_ts = Some(ts);
+
+            let significant = weight >= threshold || i == 0 || (i % (bars_per_tick * 3)) == 0;
+            if significant && (0.0..=chart_width).contains(&x) {
+                let label = self.config.format_time(ts, weight);
+                candidates.push(TimeLabel::new(x, label, weight, text_style.font_size));
+            }
+        }
+
+        for label in place_time_labels(candidates, 0.0, chart_width, 8.0) {
+            backend.crisp_line(
+                Point::new(label.tick_x, scale_y),
+                Point::new(label.tick_x, scale_y + sizing.tick_length),
+                &LineStyle::solid(border_color, 1.0),
+            );
+            backend.text(
+                &label.text,
+                Point::new(label.center, scale_y + sizing.tick_length + 2.0),
+                &text_style,
+            );
+        }
+
+        // Corner cell under the price scale, drawn last so nothing bleeds in
+        backend.crisp_fill_rect(
+            Rect::new(chart_width, scale_y, self.price_scale_width(), scale_height),
+            bg_color,
+        );
+    }
+
+    /// Bar timeframe: configured value, or the spacing of the last two bars
+    fn timeframe_secs(&self) -> Option<i64> {
+        self.config.timeframe_secs.filter(|&tf| tf > 0).or_else(|| {
+            let n = self.bars.len();
+            if n < 2 {
+                return None;
+            }
+            let delta = self.bars[n - 1].timestamp - self.bars[n - 2].timestamp;
+            (delta > 0).then_some(delta)
+        })
+    }
+
+    /// Render the time-to-close tag under the last bar
+    fn render_countdown(
+        &self,
+        backend: &mut SvgBackend,
+        chart_height: f64,
+        bar_to_x: &impl Fn(usize) -> f64,
+        bar_spacing: f64,
+    ) {
+        let Some(countdown) = self
+            .config
+            .countdown
+            .as_ref()
+            .filter(|_| self.config.show_time_scale)
+        else {
+            return;
+        };
+        let (Some(last), Some(timeframe)) = (self.bars.last(), self.timeframe_secs()) else {
+            return;
+        };
+
+        let close_ts = last.timestamp + timeframe;
+        let now = self.timestamp_unit.to_seconds(countdown.now);
+        let label = format_countdown(close_ts - now, timeframe);
+        let x = bar_to_x(self.bars.len() - 1);
+
+        let theme = &self.config.theme;
+        if countdown.show_close_line {
+            let close_x = x + bar_spacing / 2.0;
+            let mut line_color =
+                Color::from_css(&theme.crosshair_color).unwrap_or(Color::rgb(117, 134, 150));
+            line_color.a = 96;
+            backend.crisp_line(
+                Point::new(close_x, 0.0),
+                Point::new(close_x, chart_height),
+                &LineStyle::dashed(line_color, 1.0, 2.0, 2.0),
+            );
+        }
+
+        // Styled like the crosshair time label
+        let sizing = self.config.sizing();
+        let style = TextStyle {
+            color: Color::from_css(&theme.crosshair_label_text).unwrap_or(Color::WHITE),
+            font_size: sizing.label_font_size(),
+            font_weight: FontWeight::Normal,
+            align: TextAlign::Center,
+            baseline: TextBaseline::Middle,
+            ..Default::default()
+        };
+        let label_width = backend.measure_text(&label, &style).width + 10.0;
+        let label_height = sizing.time_scale_height - 8.0;
+        let bg = Color::from_css(&theme.crosshair_label_bg).unwrap_or(Color::rgb(54, 58, 69));
+        backend.crisp_fill_rect(
+            Rect::new(
+                x - label_width / 2.0,
+                chart_height + 4.0,
+                label_width,
+                label_height,
+            ),
+            bg,
+        );
+        backend.text(
+            &label,
+            Point::new(x, chart_height + 4.0 + label_height / 2.0),
+            &style,
+        );
+    }
+
+    /// Faint volume-by-price bars growing leftwards from the right edge of
+    /// the main pane, plus a dashed line at the point of control
+    fn render_volume_by_price(
+        &self,
+        backend: &mut SvgBackend,
+        chart_width: f64,
+        price_to_y: &impl Fn(f64) -> f64,
+    ) {
+        let Some(vbp) = &self.config.volume_by_price else {
+            return;
+        };
+        let buckets = volume_by_price(&self.bars, vbp.source, vbp.buckets);
+        let max_volume = buckets.iter().map(|b| b.volume()).fold(0.0, f64::max);
+        if max_volume <= 0.0 {
+            return;
+        }
+
+        let theme = &self.config.theme;
+        let neutral = vbp
+            .color
+            .as_deref()
+            .and_then(Color::from_css)
+            .unwrap_or(Color::rgb(120, 123, 134));
+        let up = Color::from_css(&theme.up_color).unwrap_or(Color::rgb(38, 166, 154));
+        let down = Color::from_css(&theme.down_color).unwrap_or(Color::rgb(239, 83, 80));
+        let max_width = chart_width * vbp.width_ratio.clamp(0.0, 1.0);
+
+        for bucket in &buckets {
+            let width = max_width * bucket.volume() / max_volume;
+            if width <= 0.0 {
+                continue;
+            }
+            let top = price_to_y(bucket.high);
+            let height = (price_to_y(bucket.low) - top - 1.0).max(1.0);
+            let left = chart_width - width;
+            let mut fill = |x: f64, w: f64, color: Color| {
+                if w > 0.0 {
+                    backend.crisp_fill_rect(Rect::new(x, top, w, height), color.with_alpha(0.25));
+                }
+            };
+            if vbp.split_up_down {
+                let up_width = width * bucket.up_volume / bucket.volume();
+                fill(left, up_width, up);
+                fill(left + up_width, width - up_width, down);
+            } else {
+                fill(left, width, neutral);
+            }
+        }
+
+        if vbp.show_poc
+            && let Some(poc) = point_of_control(&buckets)
+        {
+            let y = price_to_y(buckets[poc].mid());
+            backend.crisp_line(
+                Point::new(0.0, y),
+                Point::new(chart_width, y),
+                &LineStyle::dashed(neutral.with_alpha(0.8), 1.0, 4.0, 4.0),
+            );
+        }
+    }
+
+    /// Translucent higher-timeframe candles, each spanning exactly its
+    /// child bars
+    fn render_htf_overlay(
+        &self,
+        backend: &mut SvgBackend,
+        htf_bars: &[(Bar, Range<usize>)],
+        bar_to_x: &impl Fn(usize) -> f64,
+        price_to_y: &impl Fn(f64) -> f64,
+        bar_spacing: f64,
+    ) {
+        let Some(htf) = &self.config.htf_overlay else {
+            return;
+        };
+        let style = htf.style;
+        let theme = &self.config.theme;
+        let up = Color::from_css(&theme.up_color).unwrap_or(Color::rgb(38, 166, 154));
+        let down = Color::from_css(&theme.down_color).unwrap_or(Color::rgb(239, 83, 80));
+
+        for (bar, children) in htf_bars {
+            if children.is_empty() || !bar.open.is_finite() || !bar.close.is_finite() {
+                continue;
+            }
+            let color = if bar.close >= bar.open { up } else { down };
+            let color = color.with_alpha(style.opacity);
+
+            let left = bar_to_x(children.start) - bar_spacing / 2.0;
+            let right = bar_to_x(children.end - 1) + bar_spacing / 2.0;
+            let body_top = price_to_y(bar.open.max(bar.close));
+            let body_bottom = price_to_y(bar.open.min(bar.close));
+            let body = Rect::new(left, body_top, right - left, body_bottom - body_top);
+
+            if style.filled {
+                backend.crisp_fill_rect(body, color);
+            } else {
+                backend.crisp_stroke_rect(body, &LineStyle::solid(color, 1.0));
+            }
+
+            if style.show_wicks && bar.high.is_finite() && bar.low.is_finite() {
+                let x = (left + right) / 2.0;
+                let wick = LineStyle::solid(color, 1.0);
+                backend.crisp_line(
+                    Point::new(x, price_to_y(bar.high)),
+                    Point::new(x, body_top),
+                    &wick,
+                );
+                backend.crisp_line(
+                    Point::new(x, body_bottom),
+                    Point::new(x, price_to_y(bar.low)),
+                    &wick,
+                );
+            }
+        }
+    }
+
+    /// Shade each gap between the previous close and a bar's open, from
+    /// the previous bar to this one, and optionally mark it with a triangle
+    fn render_gaps(
+        &self,
+        backend: &mut SvgBackend,
+        bar_to_x: &impl Fn(usize) -> f64,
+        price_to_y: &impl Fn(f64) -> f64,
+        bar_width: f64,
+    ) {
+        let Some(gaps) = &self.config.show_gaps else {
+            return;
+        };
+        let theme = &self.config.theme;
+        let up = Color::from_css(&theme.up_color).unwrap_or(Color::rgb(38, 166, 154));
+        let down = Color::from_css(&theme.down_color).unwrap_or(Color::rgb(239, 83, 80));
+
+        for (i, pair) in self.bars.windows(2).enumerate() {
+            let (prev_close, open) = (pair[0].close, pair[1].open);
+            if !prev_close.is_finite()
+                || !open.is_finite()
+                || !gaps.threshold.is_gap(prev_close, open)
+            {
+                continue;
+            }
+            let color = if open > prev_close { up } else { down };
+            let (y_close, y_open) = (price_to_y(prev_close), price_to_y(open));
+            let (left, right) = (bar_to_x(i), bar_to_x(i + 1));
+
+            if gaps.shade {
+                let top = y_close.min(y_open);
+                backend.crisp_fill_rect(
+                    Rect::new(left, top, right - left, (y_close - y_open).abs()),
+                    color.with_alpha(gaps.opacity),
+                );
+            }
+            if gaps.marker {
+                // Just left of the bar body, at the middle of the gap
+                let size = (bar_width / 2.0).clamp(3.0, 6.0);
+                let x = right - bar_width / 2.0 - size;
+                let y = (y_close + y_open) / 2.0;
+                let dir = if open > prev_close { -1.0 } else { 1.0 };
+                let points = [
+                    Point::new(x - size / 2.0, y - dir * size / 2.0),
+                    Point::new(x + size / 2.0, y - dir * size / 2.0),
+                    Point::new(x, y + dir * size / 2.0),
+                ];
+                backend.fill_path(&Path::polygon(&points), &FillStyle::solid(color));
+            }
+        }
+    }
+
+    /// Thin track along the bottom of the bar panes with a thumb over the
+    /// drawn share of all bars
+    fn render_scroll_indicator(&self, backend: &mut SvgBackend, chart_width: f64, bottom: f64) {
+        let Some((start, width)) = self
+            .scroll_thumb
+            .filter(|_| self.config.show_scroll_indicator)
+        else {
+            return;
+        };
+        let theme = &self.config.theme;
+        let color = Color::from_css(&theme.text_color).unwrap_or(Color::rgb(180, 180, 180));
+        let height = 3.0;
+        let y = bottom - height - 1.0;
+
+        backend.crisp_fill_rect(
+            Rect::new(0.0, y, chart_width, height),
+            color.with_alpha(0.12),
+        );
+        backend.crisp_fill_rect(
+            Rect::new(start * chart_width, y, width * chart_width, height),
+            color.with_alpha(0.5),
+        );
+    }
+
+    /// Faint band over the hovered bar's column in every pane, plus an
+    /// accent outline around its candle body
+    fn render_hover_bar(
+        &self,
+        backend: &mut SvgBackend,
+        chart_height: f64,
+        bar_to_x: &impl Fn(usize) -> f64,
+        price_to_y: &impl Fn(f64) -> f64,
+        bar_spacing: f64,
+        bar_width: f64,
+    ) {
+        let Some(index) = self.config.hover_bar.filter(|&i| i < self.bars.len()) else {
+            return;
+        };
+        let x = bar_to_x(index);
+        let accent = Color::from_css(&self.config.theme.crosshair_color)
+            .unwrap_or(Color::rgb(117, 134, 150));
+
+        backend.crisp_fill_rect(
+            Rect::new(x - bar_spacing / 2.0, 0.0, bar_spacing, chart_height),
+            accent.with_alpha(0.12),
+        );
+
+        let bar = &self.bars[index];
+        let has_body = matches!(
+            self.config.series.series_type,
+            SeriesType::Candlestick | SeriesType::HollowCandlestick
+        );
+        if has_body && bar.open.is_finite() && bar.close.is_finite() {
+            let top = price_to_y(bar.open.max(bar.close));
+            let bottom = price_to_y(bar.open.min(bar.close));
+            backend.crisp_stroke_rect(
+                Rect::new(x - bar_width / 2.0, top, bar_width, (bottom - top).max(1.0)),
+                &LineStyle::solid(accent, 1.0),
+            );
+        }
+    }
+
+    /// Last close and whether its bar is up, see [`series_last_price`]
+    fn last_price(&self) -> Option<(f64, bool)> {
+        series_last_price(&self.config, &self.bars)
+    }
+
+    /// Line across the main pane at the last close, with its price tagged
+    /// on the axis in the last bar's color
+    fn render_last_price(
+        &self,
+        backend: &mut SvgBackend,
+        chart_width: f64,
+        y: f64,
+        price_scale: &PriceScale,
+        pane_height: f64,
+    ) {
+        let Some((close, is_up)) = self.last_price() else {
+            return;
+        };
+        let label = price_scale.format_price(close, pane_height);
+        let pane = Rect::new(0.0, 0.0, chart_width, pane_height);
+        draw_last_price(
+            backend,
+            &self.config,
+            is_up,
+            pane,
+            y,
+            self.price_scale_width(),
+            &label,
+        );
+    }
+
+    /// Mark what is drawn next; series and curves glow with the theme
+    fn set_category(&self, backend: &mut SvgBackend, category: PrecisionCategory) {
+        backend.set_category(category);
+        let glow = match category {
+            PrecisionCategory::Series | PrecisionCategory::Curve => self.config.theme.glow,
+            _ => 0.0,
+        };
+        backend.set_glow(glow);
+    }
+
+    fn execute_batch(&self, backend: &mut SvgBackend, batch: &RenderBatch) {
+        use crate::render::engine::RenderCommand;
+
+        for cmd in batch.commands() {
+            match cmd {
+                RenderCommand::FillRect { rect, color } => {
+                    backend.crisp_fill_rect(*rect, *color);
+                }
+                RenderCommand::StrokeRect { rect, style } => {
+                    backend.crisp_stroke_rect(*rect, style);
+                }
+                RenderCommand::Line { from, to, style } if from.x == to.x || from.y == to.y => {
+                    backend.crisp_line(*from, *to, style);
+                }
+                RenderCommand::Line { from, to, style } => {
+                    backend.line(*from, *to, style);
+                }
+                RenderCommand::Polyline { points, style } => {
+                    backend.polyline(points, style);
+                }
+                RenderCommand::FillPath { path, style } => {
+                    backend.fill_path(path, style);
+                }
+                RenderCommand::StrokePath { path, style } => {
+                    backend.stroke_path(path, style);
+                }
+                RenderCommand::FillCircle {
+                    center,
+                    radius,
+                    color,
+                } => {
+                    backend.fill_circle(*center, *radius, *color);
+                }
+                RenderCommand::StrokeCircle {
+                    center,
+                    radius,
+                    style,
+                } => {
+                    backend.stroke_circle(*center, *radius, style);
+                }
+                RenderCommand::Text { text, pos, style } => {
+                    backend.text(text, *pos, style);
+                }
+                RenderCommand::Image { id, src, dst } => {
+                    backend.image(id, *src, *dst);
+                }
+                RenderCommand::SetAlpha { alpha } => {
+                    backend.set_alpha(*alpha);
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+// =============================================================================
+// MultichartRenderer - Renders multiple charts in a layout
+// =============================================================================
+
+use crate::layout::{CellBounds, CellId, MultichartLayout};
+
+/// Price area of a multichart cell: where its bars sit and how prices map
+/// to y, shared by rendering and [`MultichartRenderer::locate`]
+struct CellPriceArea {
+    axis: BarAxis,
+    /// Top of the cell
+    top: f64,
+    /// Cell width less the price scale and any secondary axes
+    chart_width: f64,
+    /// Width of the secondary axis column right of the price scale
+    secondary_width: f64,
+    /// Main pane height less any bottom strips
+    price_height: f64,
+    price_low: f64,
+    price_high: f64,
+}
+
+impl CellPriceArea {
+    fn new(config: &ChartConfig, bars: &[Bar], bounds: &CellBounds) -> Self {
+        let sizing = config.sizing();
+        let chart_height = bounds.height - sizing.time_scale_height;
+
+        let indicators = display_indicators(config);
+        let subpanes = indicators
+            .iter()
+            .map(Cow::as_ref)
+            .filter(|ind| ind.placement.is_subpane());
+        let secondary_width = secondary_axis_width(config, subpanes, bars, chart_height);
+        let chart_width = bounds.width - sizing.price_scale_width - secondary_width;
+        let placements = indicators.iter().map(|ind| (ind.as_ref(), &ind.placement));
+        let overlays: Vec<&Indicator> = placements
+            .clone()
+            .filter_map(|(ind, p)| p.is_overlay().then_some(ind))
+            .collect();
+        let overlay_bottoms: Vec<&Indicator> = placements
+            .clone()
+            .filter_map(|(ind, p)| p.is_overlay_bottom().then_some(ind))
+            .collect();
+        let subpane_ratio: f64 = placements
+            .filter(|(_, p)| p.is_subpane())
+            .map(|(_, p)| p.height_ratio())
+            .sum();
+        let main_height = chart_height * (1.0 - subpane_ratio);
+
+        let (price_min, price_max) =
+            MultichartRenderer::calc_price_range(bars, &overlays, area_base(&config.series));
+        let price_padding = (price_max - price_min) * 0.05;
+        Self {
+            axis: BarAxis::new(bars, config, chart_width, bounds.x),
+            top: bounds.y,
+            chart_width,
+            secondary_width,
+            price_height: main_height - reserved_strip_height(&overlay_bottoms, main_height),
+            price_low: price_min - price_padding,
+            price_high: price_max + price_padding,
+        }
+    }
+
+    fn price_to_y(&self, price: f64) -> f64 {
+        let ratio = (price - self.price_low) / (self.price_high - self.price_low);
+        self.top + self.price_height - ratio * self.price_height
+    }
+
+    fn y_to_price(&self, y: f64) -> f64 {
+        let ratio = (self.top + self.price_height - y) / self.price_height;
+        self.price_low + ratio * (self.price_high - self.price_low)
+    }
+}
+
+/// Renders multiple charts in a grid layout
+pub struct MultichartRenderer<'a> {
+    layout: &'a MultichartLayout,
+    charts: Vec<(&'a ChartConfig, &'a [Bar])>,
+    total_width: u32,
+    total_height: u32,
+    dpr: f64,
+}
+
+impl<'a> MultichartRenderer<'a> {
+    /// Create a new multichart renderer
+    pub fn new(layout: &'a MultichartLayout, total_width: u32, total_height: u32) -> Self {
+        Self {
+            layout,
+            charts: Vec::new(),
+            total_width,
+            total_height,
+            dpr: 1.0,
+        }
+    }
+
+    /// Set device pixel ratio
+    pub fn dpr(mut self, dpr: f64) -> Self {
+        self.dpr = dpr;
+        self
+    }
+
+    /// Add a chart to a cell
+    pub fn chart(mut self, config: &'a ChartConfig, bars: &'a [Bar]) -> Self {
+        self.charts.push((config, bars));
+        self
+    }
+
+    /// Render all charts to SVG
+    pub fn render_svg(&self) -> String {
+        let width = self.total_width;
+        let height = self.total_height;
+        let dpr = self.dpr;
+
+        let mut backend = SvgBackend::new(width, height, dpr);
+        backend.begin_frame(width as f64, height as f64, dpr);
+
+        // Background
+        let bg = Color::rgb(19, 23, 34);
+        backend.clear(bg);
+
+        // Calculate cell bounds
+        let bounds = self.layout.calculate_bounds(width as f64, height as f64);
+
+        // Render each chart in its cell
+        for (idx, (_cell_id, cell_bounds)) in bounds.iter().enumerate() {
+            if let Some((config, bars)) = self.charts.get(idx) {
+                let bars = prepared_bars(config, bars, config.timestamp_unit);
+                self.render_chart_in_cell(&mut backend, config, &bars, cell_bounds, dpr);
+            }
+        }
+
+        backend.end_frame();
+        backend.to_svg()
+    }
+
+    /// Cell, bar index and price under a pixel of the rendered grid
+    ///
+    /// Finds the cell containing `(px, py)` and inverts its price-area
+    /// transform; the bar is the one whose center is nearest. Points in a
+    /// gap, over a cell's scales or subpanes, or in a cell without a chart
+    /// give `None`.
+    pub fn locate(&self, px: f64, py: f64) -> Option<(CellId, usize, f64)> {
+        let bounds = self
+            .layout
+            .calculate_bounds(self.total_width as f64, self.total_height as f64);
+        let (idx, (cell_id, cell)) = bounds
+            .iter()
+            .enumerate()
+            .find(|(_, (_, cell))| cell.contains(px, py))?;
+        let (config, bars) = self.charts.get(idx)?;
+        let bars = prepared_bars(config, bars, config.timestamp_unit);
+
+        let area = CellPriceArea::new(config, &bars, cell);
+        if px >= cell.x + area.chart_width || py >= area.top + area.price_height {
+            return None;
+        }
+        let bar = area.axis.bar_at(px, bars.len())?;
+        Some((*cell_id, bar, area.y_to_price(py)))
+    }
+
+    fn render_chart_in_cell(
+        &self,
+        backend: &mut SvgBackend,
+        config: &ChartConfig,
+        bars: &[Bar],
+        bounds: &CellBounds,
+        dpr: f64,
+    ) {
+        if bars.is_empty() {
+            return;
+        }
+
+        let x_offset = bounds.x;
+        let y_offset = bounds.y;
+        let cell_width = bounds.width;
+        let cell_height = bounds.height;
+
+        // Price range, bar placement and the space left by the scales
+        let area = CellPriceArea::new(config, bars, bounds);
+        let (price_height, price_low, price_high) =
+            (area.price_height, area.price_low, area.price_high);
+        let sizing = config.sizing();
+        let price_scale_width = sizing.price_scale_width;
+        let chart_width = area.chart_width;
+        let chart_height = cell_height - sizing.time_scale_height;
+
+        // Separate indicators
+        let indicators = display_indicators(config);
+        let overlays: Vec<&Indicator> = indicators
+            .iter()
+            .map(Cow::as_ref)
+            .filter(|ind| ind.placement.is_overlay())
+            .collect();
+        let overlay_bottoms: Vec<&Indicator> = indicators
+            .iter()
+            .map(Cow::as_ref)
+            .filter(|ind| ind.placement.is_overlay_bottom())
+            .collect();
+        let subpanes: Vec<&Indicator> = indicators
+            .iter()
+            .map(Cow::as_ref)
+            .filter(|ind| ind.placement.is_subpane())
+            .collect();
+
+        // Calculate layout
+        let total_subpane_ratio: f64 = subpanes.iter().map(|s| s.placement.height_ratio()).sum();
+        let main_ratio = 1.0 - total_subpane_ratio;
+        let main_height = chart_height * main_ratio;
+        let gap = 2.0;
+
+        let axis = &area.axis;
+        let bar_spacing = axis.spacing;
+        let bar_width = (bar_spacing * 0.8).max(1.0);
+
+        // Coordinate transforms with offset
+        let bar_to_x = |i: usize| -> f64 { axis.x(i) };
+        let price_to_y = |price: f64| -> f64 { area.price_to_y(price) };
+
+        // Cell background
+        let bg_color = Color::from_css(&config.theme.background).unwrap_or(Color::rgb(19, 23, 34));
+        backend.crisp_fill_rect(
+            Rect::new(x_offset, y_offset, cell_width, cell_height),
+            bg_color,
+        );
+
+        // Border
+        let border_color =
+            Color::from_css(&config.theme.grid_color).unwrap_or(Color::rgb(42, 46, 57));
+        backend.crisp_stroke_rect(
+            Rect::new(x_offset, y_offset, cell_width, cell_height),
+            &LineStyle::solid(border_color, 1.0),
+        );
+
+        // Main series and price overlays stay inside the price area
+        backend.push_crisp_clip(Rect::new(x_offset, y_offset, chart_width, price_height));
+
+        // Render main series
+        if config.series_visible() {
+            backend.set_glow(config.theme.glow);
+            Self::render_series_simple(
+                backend,
+                bars,
+                config,
+                &bar_to_x,
+                &price_to_y,
+                bar_width,
+                dpr,
+            );
+            backend.set_glow(0.0);
+        }
+
+        // Render overlay indicators (share price Y scale)
+        let price_pane = Rect::new(x_offset, y_offset, chart_width, price_height);
+        let price_ctx =
+            VectorContext::new(price_pane, bar_spacing, (price_low, price_high), price_to_y);
+        for indicator in &overlays {
+            draw_indicator_fills(backend, indicator, &bar_to_x, &price_to_y);
+            for vector in &indicator.vectors {
+                render_vector(backend, &price_ctx, vector, &bar_to_x, &price_to_y);
+            }
+        }
+
+        backend.pop_clip();
+
+        // Render overlay_bottom indicators (own Y scale at bottom of main chart)
+        Self::render_overlay_bottom_simple(
+            backend,
+            bars,
+            &overlay_bottoms,
+            axis,
+            Rect::new(x_offset, y_offset, chart_width, main_height),
+            config,
+        );
+
+        // Price scale; the last-price tag hides ticks under it
+        let last_price = series_last_price(config, bars)
+            .map(|(close, is_up)| (close, is_up, price_to_y(close)))
+            .filter(|&(_, _, y)| (y_offset..=y_offset + price_height).contains(&y));
+        let tag = last_price
+            .filter(|_| config.series.common.last_value_visible)
+            .map(|(_, _, y)| y);
+        Self::render_price_scale_simple(
+            backend,
+            config,
+            x_offset + chart_width,
+            y_offset,
+            price_height,
+            (price_low, price_high),
+            tag.as_slice(),
+        );
+        if let Some((close, is_up, y)) = last_price {
+            let label = PriceScale::new(price_low, price_high).format_price(close, price_height);
+            let pane = Rect::new(x_offset, y_offset, chart_width, price_height);
+            draw_last_price(backend, config, is_up, pane, y, price_scale_width, &label);
+        }
+
+        // Subpanes
+        let mut sub_y_offset = y_offset + main_height + gap;
+        for indicator in &subpanes {
+            let pane_height = chart_height * indicator.placement.height_ratio() - gap;
+
+            // Subpane background
+            backend.crisp_fill_rect(
+                Rect::new(x_offset, sub_y_offset, chart_width, pane_height),
+                bg_color,
+            );
+
+            // Separator
+            backend.crisp_line(
+                Point::new(x_offset, sub_y_offset),
+                Point::new(x_offset + chart_width, sub_y_offset),
+                &LineStyle::solid(border_color, 1.0),
+            );
+
+            // Calculate subpane range
+            let (sub_min, sub_max) = indicator_range(indicator, bars);
+            let value_to_y = |v: f64| -> f64 {
+                let ratio = (v - sub_min) / (sub_max - sub_min);
+                sub_y_offset + pane_height - ratio * pane_height
+            };
+
+            // Render levels
+            for level in &indicator.levels {
+                let y = value_to_y(level.value);
+                let color = Color::from_css(&level.color).unwrap_or(Color::rgb(120, 123, 134));
+                let style = match level.style.as_str() {
+                    "dotted" => LineStyle::dashed(color, level.width, 2.0, 2.0),
+                    "dashed" => LineStyle::dashed(color, level.width, 4.0, 4.0),
+                    _ => LineStyle::solid(color, level.width),
+                };
+                backend.crisp_line(
+                    Point::new(x_offset, y),
+                    Point::new(x_offset + chart_width, y),
+                    &style,
+                );
+            }
+
+            // Render fills, then vectors
+            let pane = Rect::new(x_offset, sub_y_offset, chart_width, pane_height);
+            draw_pane_vectors(
+                backend,
+                indicator,
+                indicator,
+                pane,
+                bar_spacing,
+                bars,
+                &bar_to_x,
+            );
+
+            // Price scale for subpane, then any secondary axes beside it
+            Self::render_price_scale_simple(
+                backend,
+                config,
+                x_offset + chart_width,
+                sub_y_offset,
+                pane_height,
+                (sub_min, sub_max),
+                &[],
+            );
+            for group in indicator.scale_groups.iter().filter(|g| g.axis) {
+                let range = scale_group_range(indicator, group, bars);
+                let axis_x = x_offset + cell_width - area.secondary_width;
+                draw_secondary_axis(backend, config, indicator, group, range, pane, axis_x);
+            }
+
+            sub_y_offset += pane_height + gap;
+        }
+
+        // Time scale
+        Self::render_time_scale_simple(
+            backend,
+            config,
+            bars,
+            x_offset,
+            y_offset + chart_height,
+            chart_width,
+            axis,
+        );
+    }
+
+    fn calc_price_range(
+        bars: &[Bar],
+        overlays: &[&Indicator],
+        area_base: Option<f64>,
+    ) -> (f64, f64) {
+        let mut min = f64::INFINITY;
+        let mut max = f64::NEG_INFINITY;
+
+        for bar in bars {
+            if !bar.low.is_nan() {
+                min = min.min(bar.low);
+            }
+            if !bar.high.is_nan() {
+                max = max.max(bar.high);
+            }
+        }
+        if let Some(base) = area_base {
+            min = min.min(base);
+            max = max.max(base);
+        }
+
+        for indicator in overlays.iter().filter(|ind| ind.autoscale) {
+            for vector in &indicator.vectors {
+                for &v in &vector.values {
+                    if !v.is_nan() {
+                        min = min.min(v);
+                        max = max.max(v);
+                    }
+                }
+            }
+        }
+
+        if min.is_infinite() {
+            min = 0.0;
+        }
+        if max.is_infinite() {
+            max = 100.0;
+        }
+
+        (min, max)
+    }
+
+    fn render_series_simple(
+        backend: &mut SvgBackend,
+        bars: &[Bar],
+        config: &ChartConfig,
+        bar_to_x: &impl Fn(usize) -> f64,
+        price_to_y: &impl Fn(f64) -> f64,
+        bar_width: f64,
+        dpr: f64,
+    ) {
+        let up_color = Color::from_css(&config.theme.up_color).unwrap_or(Color::rgb(38, 166, 154));
+        let down_color =
+            Color::from_css(&config.theme.down_color).unwrap_or(Color::rgb(239, 83, 80));
+
+        match &config.series.series_type {
+            SeriesType::Candlestick | SeriesType::HollowCandlestick => {
+                let data = candlestick_data(config, bars);
+                let options = CandlestickStyleOptions {
+                    border_visible: false,
+                    ..candlestick_style_options(config)
+                };
+                let mut batch = RenderBatch::new();
+                render_candlesticks(
+                    &mut batch, &data, &options, bar_to_x, price_to_y, bar_width, dpr,
+                );
+                backend.execute_batch(&batch);
+            }
+            SeriesType::HeikinAshi => {
+                let data = candlestick_data(config, bars);
+                let options = heikin_ashi_style_options(config);
+                let mut batch = RenderBatch::new();
+                render_heikin_ashi(
+                    &mut batch, &data, &options, bar_to_x, price_to_y, bar_width, dpr,
+                );
+                backend.execute_batch(&batch);
+            }
+            SeriesType::Line => {
+                let points: Vec<Point> = bars
+                    .iter()
+                    .enumerate()
+                    .map(|(i, bar)| Point::new(bar_to_x(i), price_to_y(bar.close)))
+                    .collect();
+                if points.len() >= 2 {
+                    backend.polyline(&points, &LineStyle::solid(up_color, 1.5));
+                }
+            }
+            SeriesType::Area => {
+                let line_color = config
+                    .series
+                    .style
+                    .color
+                    .as_ref()
+                    .and_then(|c| Color::from_css(c))
+                    .unwrap_or(up_color);
+                let fill_color = line_color.with_alpha(0.3);
+
+                let points: Vec<Point> = bars
+                    .iter()
+                    .enumerate()
+                    .map(|(i, bar)| Point::new(bar_to_x(i), price_to_y(bar.close)))
+                    .collect();
+
+                if points.len() >= 2 {
+                    // Line
+                    backend.polyline(&points, &LineStyle::solid(line_color, 1.5));
+
+                    // Fill down to the base, else the lowest low
+                    let base = area_base(&config.series).unwrap_or_else(|| {
+                        bars.iter().map(|b| b.low).fold(f64::INFINITY, f64::min)
+                    });
+                    let below_color = config
+                        .series
+                        .style
+                        .down_color
+                        .as_ref()
+                        .and_then(|c| Color::from_css(c))
+                        .filter(|_| area_base(&config.series).is_some());
+                    let (above, below) = area_fill_zones(&points, price_to_y(base));
+                    let below_fill = below_color.map_or(fill_color, |c| c.with_alpha(0.3));
+                    backend.fill_path(&Path::polygon(&above), &FillStyle::solid(fill_color));
+                    backend.fill_path(&Path::polygon(&below), &FillStyle::solid(below_fill));
+                }
+            }
+            SeriesType::Bar => {
+                let options = bar_style_options(config);
+                let tick = options.tick_length_for(bar_width);
+                for (i, bar) in bars.iter().enumerate() {
+                    let x = bar_to_x(i);
+                    let is_up = bar.close >= bar.open;
+                    let color = config
+                        .bar_colors
+                        .get(i)
+                        .and_then(|c| c.as_deref())
+                        .and_then(Color::from_css)
+                        .unwrap_or(if is_up { up_color } else { down_color });
+
+                    // Vertical line (high to low)
+                    backend.crisp_line(
+                        Point::new(x, price_to_y(bar.high)),
+                        Point::new(x, price_to_y(bar.low)),
+                        &LineStyle::solid(color, 1.0),
+                    );
+                    // Open tick (left)
+                    if options.show_open_tick {
+                        backend.crisp_line(
+                            Point::new(x - tick, price_to_y(bar.open)),
+                            Point::new(x, price_to_y(bar.open)),
+                            &LineStyle::solid(color, 1.0),
+                        );
+                    }
+                    // Close tick (right)
+                    if options.show_close_tick {
+                        backend.crisp_line(
+                            Point::new(x, price_to_y(bar.close)),
+                            Point::new(x + tick, price_to_y(bar.close)),
+                            &LineStyle::solid(color, 1.0),
+                        );
+                    }
+                }
+            }
+            SeriesType::Baseline => {
+                let baseline = baseline_value(&config.series, bars);
+                let baseline_y = price_to_y(baseline);
+
+                // Baseline
+                let baseline_color = Color::rgb(120, 120, 120);
+                backend.crisp_line(
+                    Point::new(bar_to_x(0) - 10.0, baseline_y),
+                    Point::new(bar_to_x(bars.len() - 1) + 10.0, baseline_y),
+                    &LineStyle::dashed(baseline_color, 1.0, 4.0, 2.0),
+                );
+
+                // Line with color based on above/below
+                for (i, bar) in bars.iter().enumerate().skip(1) {
+                    let prev = &bars[i - 1];
+                    let color = if bar.close >= baseline {
+                        up_color
+                    } else {
+                        down_color
+                    };
+                    backend.line(
+                        Point::new(bar_to_x(i - 1), price_to_y(prev.close)),
+                        Point::new(bar_to_x(i), price_to_y(bar.close)),
+                        &LineStyle::solid(color, 1.5),
+                    );
+                }
+            }
+            _ => {
+                // Fallback to line
+                let points: Vec<Point> = bars
+                    .iter()
+                    .enumerate()
+                    .map(|(i, bar)| Point::new(bar_to_x(i), price_to_y(bar.close)))
+                    .collect();
+                if points.len() >= 2 {
+                    backend.polyline(&points, &LineStyle::solid(up_color, 1.5));
+                }
+            }
+        }
+    }
+
+    fn render_price_scale_simple(
+        backend: &mut SvgBackend,
+        config: &ChartConfig,
+        x: f64,
+        y_offset: f64,
+        height: f64,
+        (price_min, price_max): (f64, f64),
+        tags: &[f64],
+    ) {
+        let bg_color = Color::from_css(&config.theme.background).unwrap_or(Color::rgb(19, 23, 34));
+        let border_color =
+            Color::from_css(&config.theme.grid_color).unwrap_or(Color::rgb(42, 46, 57));
+        let text_color =
+            Color::from_css(&config.theme.text_color).unwrap_or(Color::rgb(180, 180, 180));
+        let sizing = config.sizing();
+        let scale_width = sizing.price_scale_width;
+
+        backend.crisp_fill_rect(Rect::new(x, y_offset, scale_width, height), bg_color);
+        backend.crisp_line(
+            Point::new(x, y_offset),
+            Point::new(x, y_offset + height),
+            &LineStyle::solid(border_color, 1.0),
+        );
+
+        let price_scale = PriceScale::new(price_min, price_max);
+        // Scaled with the column width, as in the single-chart price scale
+        let font_size = (price_scale.calc_font_size(height) * scale_width / PRICE_SCALE_WIDTH)
+            .min(sizing.scale_font_size);
+        let text_style = TextStyle {
+            color: text_color,
+            font_size,
+            font_weight: FontWeight::Normal,
+            align: TextAlign::Left,
+            baseline: TextBaseline::Middle,
+            ..Default::default()
+        };
+
+        for (tick, y) in price_scale.label_ticks(y_offset, height, font_size, tags) {
+            backend.crisp_line(
+                Point::new(x, y),
+                Point::new(x + sizing.tick_length, y),
+                &LineStyle::solid(border_color, 1.0),
+            );
+            let label = price_scale.format_price(tick, height);
+            let label_x = x + sizing.tick_length + 2.0;
+            backend.text(&label, Point::new(label_x, y), &text_style);
+        }
+    }
+
+    /// Render overlay_bottom indicators generically (own Y scale at bottom of main chart)
+    fn render_overlay_bottom_simple(
+        backend: &mut SvgBackend,
+        bars: &[Bar],
+        indicators: &[&Indicator],
+        axis: &BarAxis,
+        main_pane: Rect,
+        config: &ChartConfig,
+    ) {
+        let bar_to_x = &|i: usize| -> f64 { axis.x(i) };
+        for indicator in indicators {
+            let indicator_height = indicator.placement.strip_height(main_pane.height);
+            let y_bottom = main_pane.y + main_pane.height;
+            let opacity = indicator.placement.opacity();
+            if opacity < 1.0 {
+                backend.push_layer(opacity);
+            }
+
+            // For Volume-like indicators: if vector.values is empty, use bars data
+            let has_data = indicator.vectors.iter().any(|v| !v.values.is_empty());
+
+            if has_data {
+                let pane = Rect::new(
+                    main_pane.x,
+                    y_bottom - indicator_height,
+                    main_pane.width,
+                    indicator_height,
+                );
+                draw_pane_vectors(
+                    backend,
+                    indicator,
+                    indicator,
+                    pane,
+                    axis.spacing,
+                    bars,
+                    bar_to_x,
+                );
+            } else {
+                // Auto-populate from bars (Volume indicator)
+                Self::render_volume_from_bars_simple(
+                    backend,
+                    bars,
+                    indicator,
+                    axis,
+                    y_bottom,
+                    indicator_height,
+                    config,
+                );
+            }
+
+            if opacity < 1.0 {
+                backend.pop_layer();
+            }
+        }
+    }
+
+    /// Render Volume indicator using bar data directly (for MultichartRenderer)
+    fn render_volume_from_bars_simple(
+        backend: &mut SvgBackend,
+        bars: &[Bar],
+        indicator: &Indicator,
+        axis: &BarAxis,
+        y_bottom: f64,
+        indicator_height: f64,
+        config: &ChartConfig,
+    ) {
+        if bars.is_empty() {
+            return;
+        }
+
+        // Find max volume for scaling
+        let max_vol = bars
+            .iter()
+            .map(|b| b.volume)
+            .filter(|v| !v.is_nan())
+            .fold(0.0_f64, f64::max);
+
+        if max_vol <= 0.0 {
+            return;
+        }
+
+        let value_to_y = |v: f64| -> f64 {
+            let ratio = v / max_vol;
+            y_bottom - ratio * indicator_height
+        };
+
+        // Get histogram style colors from indicator, fallback to theme colors
+        let (up_color, down_color, bar_width_ratio) = indicator
+            .vectors
+            .first()
+            .map(|v| match &v.style {
+                VectorStyle::Histogram {
+                    up_color,
+                    down_color,
+                    bar_width_ratio,
+                } => (up_color.clone(), down_color.clone(), *bar_width_ratio),
+                _ => (
+                    config.theme.up_color.clone(),
+                    config.theme.down_color.clone(),
+                    0.8,
+                ),
+            })
+            .unwrap_or((
+                config.theme.up_color.clone(),
+                config.theme.down_color.clone(),
+                0.8,
+            ));
+
+        let up = Color::from_css(&up_color).unwrap_or(Color::rgb(38, 166, 154));
+        let down = Color::from_css(&down_color).unwrap_or(Color::rgb(239, 83, 80));
+
+        let bar_w = histogram_bar_width(axis.spacing, bar_width_ratio);
+
+        for (i, bar) in bars.iter().enumerate() {
+            let vol = bar.volume;
+            if vol.is_nan() || vol <= 0.0 {
+                continue;
+            }
+
+            let x = axis.x(i);
+            let y = value_to_y(vol);
+            let bar_h = (y_bottom - y).max(1.0);
+
+            // Color based on bar direction
+            let color = if bar.close >= bar.open { up } else { down };
+
+            backend.crisp_fill_rect(Rect::new(x - bar_w / 2.0, y, bar_w, bar_h), color);
+        }
+    }
+
+    fn render_time_scale_simple(
+        backend: &mut SvgBackend,
+        config: &ChartConfig,
+        bars: &[Bar],
+        x_offset: f64,
+        y: f64,
+        width: f64,
+        axis: &BarAxis,
+    ) {
+        let bg_color = Color::from_css(&config.theme.background).unwrap_or(Color::rgb(19, 23, 34));
+        let border_color =
+            Color::from_css(&config.theme.grid_color).unwrap_or(Color::rgb(42, 46, 57));
+        let text_color =
+            Color::from_css(&config.theme.text_color).unwrap_or(Color::rgb(180, 180, 180));
+        let sizing = config.sizing();
+
+        backend.crisp_fill_rect(
+            Rect::new(
+                x_offset,
+                y,
+                width + sizing.price_scale_width,
+                sizing.time_scale_height,
+            ),
+            bg_color,
+        );
+        backend.crisp_line(
+            Point::new(x_offset, y),
+            Point::new(x_offset + width, y),
+            &LineStyle::solid(border_color, 1.0),
+        );
+
+        let text_style = TextStyle {
+            color: text_color,
+            font_size: sizing.label_font_size(),
+            font_weight: FontWeight::Normal,
+            align: TextAlign::Center,
+            baseline: TextBaseline::Top,
+            ..Default::default()
+        };
+
+        let step = (sizing.min_time_label_spacing / axis.spacing).ceil() as usize;
+        let step = step.max(1);
+
+        let (left, right) = (x_offset, x_offset + width);
+        let threshold =
+            TickMarkWeight::label_threshold(compute::median_bar_interval(bars).unwrap_or(0));
+        let mut candidates = Vec::new();
+        let mut prev_ts: Option<i64> = None;
+        for i in (0..bars.len()).step_by(step) {
+            let ts = bars[i].timestamp;
+            let x = axis.x(i);
+            let weight = TickMarkWeight::from_timestamp(ts, prev_ts);
+            prev_ts = Some(ts);
+
+            let significant = weight >= threshold || (i % (step * 2)) == 0;
+            if significant && (left..=right).contains(&x) {
+                let label = config.format_time(ts, weight);
+                candidates.push(TimeLabel::new(x, label, weight, text_style.font_size));
+            }
+        }
+
+        for label in place_time_labels(candidates, left, right, 6.0) {
+            backend.crisp_line(
+                Point::new(label.tick_x, y),
+                Point::new(label.tick_x, y + sizing.tick_length),
+                &LineStyle::solid(border_color, 1.0),
+            );
+            let label_y = y + sizing.tick_length + 2.0;
+            backend.text(&label.text, Point::new(label.center, label_y), &text_style);
+        }
+    }
+}
+
+// =============================================================================
+// SvgRenderContext - Adapter for primitive rendering
+// =============================================================================
+
+use crate::primitives::FillRule as PrimitiveFillRule;
+use crate::render::engine::{FillRule, PathBuilder};
+
+/// Adapter to use SvgBackend with primitive RenderContext trait
+struct SvgRenderContext<'a, F1, F2>
+where
+    F1: Fn(usize) -> f64,
+    F2: Fn(f64) -> f64,
+{
+    backend: &'a mut SvgBackend,
+    bar_to_x: &'a F1,
+    price_to_y: &'a F2,
+    bars: &'a [Bar],
+    dpr: f64,
+    viewport_width: f64,
+    viewport_height: f64,
+    // Drawing state
+    path_builder: PathBuilder,
+    stroke_color: Color,
+    stroke_width: f64,
+    fill_color: Color,
+    fill_rule: FillRule,
+    dash_pattern: Vec<f64>,
+    line_cap: LineCap,
+    line_join: LineJoin,
+    global_alpha: f64,
+    fill_opacity: f64,
+    stroke_opacity: f64,
+    font_size: f64,
+    font_weight: FontWeight,
+    text_color: Color,
+}
+
+impl<'a, F1, F2> SvgRenderContext<'a, F1, F2>
+where
+    F1: Fn(usize) -> f64,
+    F2: Fn(f64) -> f64,
+{
+    fn new(
+        backend: &'a mut SvgBackend,
+        bar_to_x: &'a F1,
+        price_to_y: &'a F2,
+        dpr: f64,
+        viewport_width: f64,
+        viewport_height: f64,
+    ) -> Self {
+        Self {
+            backend,
+            bar_to_x,
+            price_to_y,
+            bars: &[],
+            dpr,
+            viewport_width,
+            viewport_height,
+            path_builder: PathBuilder::new(),
+            stroke_color: Color::from_css("#2196F3").unwrap_or(Color::WHITE),
+            stroke_width: 2.0,
+            fill_color: Color::TRANSPARENT,
+            fill_rule: FillRule::NonZero,
+            dash_pattern: Vec::new(),
+            line_cap: LineCap::default(),
+            line_join: LineJoin::default(),
+            global_alpha: 1.0,
+            fill_opacity: 1.0,
+            stroke_opacity: 1.0,
+            font_size: 12.0,
+            font_weight: FontWeight::Normal,
+            text_color: Color::WHITE,
+        }
+    }
+
+    /// Bars primitives fitted to price data are drawn from
+    fn with_bars(mut self, bars: &'a [Bar]) -> Self {
+        self.bars = bars;
+        self
+    }
+
+    /// Text style for the current font and fill state
+    fn text_style(&self) -> TextStyle {
+        TextStyle {
+            font_family: "sans-serif".into(),
+            font_size: self.font_size,
+            font_weight: self.font_weight,
+            color: self.text_color.with_alpha(self.global_alpha),
+            align: TextAlign::Left,
+            baseline: TextBaseline::Top,
+        }
+    }
+}
+
+impl<'a, F1, F2> RenderContext for SvgRenderContext<'a, F1, F2>
+where
+    F1: Fn(usize) -> f64,
+    F2: Fn(f64) -> f64,
+{
+    fn chart_width(&self) -> f64 {
+        self.viewport_width
+    }
+
+    fn chart_height(&self) -> f64 {
+        self.viewport_height
+    }
+
+    fn bar_to_x(&self, bar: f64) -> f64 {
+        fractional_bar_x(self.bar_to_x, bar)
+    }
+
+    fn price_to_y(&self, price: f64) -> f64 {
+        (self.price_to_y)(price)
+    }
+
+    fn dpr(&self) -> f64 {
+        self.dpr
+    }
+
+    fn dpr_xy(&self) -> (f64, f64) {
+        self.backend.dpr_xy()
+    }
+
+    fn bars(&self) -> &[Bar] {
+        self.bars
+    }
+
+    fn set_stroke_color(&mut self, color: &str) {
+        self.stroke_color = Color::from_css(color).unwrap_or(Color::WHITE);
+    }
+
+    fn set_stroke_width(&mut self, width: f64) {
+        self.stroke_width = width;
+    }
+
+    fn set_fill_color(&mut self, color: &str) {
+        self.fill_color = Color::from_css(color).unwrap_or(Color::TRANSPARENT);
+    }
+
+    fn set_fill_rule(&mut self, rule: PrimitiveFillRule) {
+        self.fill_rule = match rule {
+            PrimitiveFillRule::NonZero => FillRule::NonZero,
+            PrimitiveFillRule::EvenOdd => FillRule::EvenOdd,
+        };
+    }
+
+    fn set_line_dash(&mut self, pattern: &[f64]) {
+        self.dash_pattern = pattern.to_vec();
+    }
+
+    fn begin_path(&mut self) {
+        self.path_builder.clear();
+    }
+
+    fn move_to(&mut self, x: f64, y: f64) {
+        self.path_builder.move_to(Point::new(x, y));
+    }
+
+    fn line_to(&mut self, x: f64, y: f64) {
+        self.path_builder.line_to(Point::new(x, y));
+    }
+
+    fn close_path(&mut self) {
+        self.path_builder.close();
+    }
+
+    fn stroke(&mut self) {
+        let path = std::mem::take(&mut self.path_builder).build();
+        let dash = if self.dash_pattern.is_empty() {
+            None
+        } else {
+            Some(self.dash_pattern.clone())
+        };
+        let style = LineStyle {
+            color: self
+                .stroke_color
+                .with_alpha(self.global_alpha * self.stroke_opacity),
+            width: self.stroke_width,
+            dash,
+            cap: self.line_cap,
+            join: self.line_join,
+        };
+        self.backend.stroke_path(&path, &style);
+    }
+
+    fn fill(&mut self) {
+        let path = std::mem::take(&mut self.path_builder)
+            .build()
+            .with_fill_rule(self.fill_rule);
+        let style = FillStyle::Solid(
+            self.fill_color
+                .with_alpha(self.global_alpha * self.fill_opacity),
+        );
+        self.backend.fill_path(&path, &style);
+    }
+
+    fn stroke_rect(&mut self, x: f64, y: f64, w: f64, h: f64) {
+        let dash = if self.dash_pattern.is_empty() {
+            None
+        } else {
+            Some(self.dash_pattern.clone())
+        };
+        let style = LineStyle {
+            color: self
+                .stroke_color
+                .with_alpha(self.global_alpha * self.stroke_opacity),
+            width: self.stroke_width,
+            dash,
+            cap: self.line_cap,
+            join: self.line_join,
+        };
+        self.backend.stroke_rect(Rect::new(x, y, w, h), &style);
+    }
+
+    fn fill_rect(&mut self, x: f64, y: f64, w: f64, h: f64) {
+        self.backend.fill_rect(
+            Rect::new(x, y, w, h),
+            self.fill_color
+                .with_alpha(self.global_alpha * self.fill_opacity),
+        );
+    }
+
+    fn ellipse(&mut self, params: EllipseParams) {
+        let EllipseParams {
+            cx,
+            cy,
+            rx,
+            ry,
+            rotation,
+            start,
+            end,
+        } = params;
+        self.path_builder
+            .ellipse(Point::new(cx, cy), rx, ry, rotation, start, end);
+    }
+
+    fn arc(&mut self, cx: f64, cy: f64, radius: f64, start: f64, end: f64) {
+        self.path_builder
+            .arc(Point::new(cx, cy), radius, start, end);
+    }
+
+    fn quadratic_curve_to(&mut self, cpx: f64, cpy: f64, x: f64, y: f64) {
+        self.path_builder
+            .quad_to(Point::new(cpx, cpy), Point::new(x, y));
+    }
+
+    fn bezier_curve_to(&mut self, cp1x: f64, cp1y: f64, cp2x: f64, cp2y: f64, x: f64, y: f64) {
+        self.path_builder.cubic_to(
+            Point::new(cp1x, cp1y),
+            Point::new(cp2x, cp2y),
+            Point::new(x, y),
+        );
+    }
+
+    fn set_font(&mut self, font: &str) {
+        // Parse CSS font shorthand like "italic bold 12px sans-serif"
+        self.font_weight = FontWeight::Normal;
+        for part in font.split_whitespace() {
+            match part {
+                "bold" | "bolder" | "600" | "700" | "800" | "900" => {
+                    self.font_weight = FontWeight::Bold
+                }
+                "lighter" | "100" | "200" | "300" => self.font_weight = FontWeight::Light,
+                _ => {
+                    if let Some(Ok(size)) = part.strip_suffix("px").map(str::parse::<f64>) {
+                        self.font_size = size;
+                    }
+                }
+            }
+        }
+    }
+
+    fn set_text_align(&mut self, _align: crate::primitives::core::render::TextAlign) {
+        // Store for text rendering
+    }
+
+    fn set_text_baseline(&mut self, _baseline: crate::primitives::core::render::TextBaseline) {
+        // Store for text rendering
+    }
+
+    fn set_global_alpha(&mut self, alpha: f64) {
+        self.global_alpha = alpha.clamp(0.0, 1.0);
+    }
+
+    fn set_fill_opacity(&mut self, opacity: f64) {
+        self.fill_opacity = opacity.clamp(0.0, 1.0);
+    }
+
+    fn set_stroke_opacity(&mut self, opacity: f64) {
+        self.stroke_opacity = opacity.clamp(0.0, 1.0);
+    }
+
+    fn set_line_cap(&mut self, cap: &str) {
+        if let Ok(cap) = cap.parse() {
+            self.line_cap = cap;
+        }
+    }
+
+    fn set_line_join(&mut self, join: &str) {
+        if let Ok(join) = join.parse() {
+            self.line_join = join;
+        }
+    }
+
+    fn fill_text(&mut self, text: &str, x: f64, y: f64) {
+        let style = self.text_style();
+        self.backend.text(text, Point::new(x, y), &style);
+    }
+
+    fn stroke_text(&mut self, _text: &str, _x: f64, _y: f64) {
+        // Text stroking not commonly needed
+    }
+
+    fn measure_text(&self, text: &str) -> f64 {
+        self.backend.measure_text(text, &self.text_style()).width
+    }
+
+    fn save(&mut self) {
+        // Would need state stack for proper save/restore
+    }
+
+    fn restore(&mut self) {
+        // Would need state stack for proper save/restore
+    }
+
+    fn clip(&mut self) {
+        // SVG clipping requires different approach
+    }
+
+    fn translate(&mut self, _x: f64, _y: f64) {
+        // Would need transform matrix
+    }
+
+    fn rotate(&mut self, _angle: f64) {
+        // Would need transform matrix
+    }
+
+    fn scale(&mut self, _x: f64, _y: f64) {
+        // Would need transform matrix
+    }
+
+    fn rect(&mut self, x: f64, y: f64, w: f64, h: f64) {
+        self.path_builder.move_to(Point::new(x, y));
+        self.path_builder.line_to(Point::new(x + w, y));
+        self.path_builder.line_to(Point::new(x + w, y + h));
+        self.path_builder.line_to(Point::new(x, y + h));
+        self.path_builder.close();
+    }
+}
+
+// =============================================================================
+// Chart Builder - Creates ChartConfig with fluent API
+// =============================================================================
+
+/// High-level chart builder that creates ChartConfig
+pub struct Chart {
+    config: ChartConfig,
+    bars: Vec<Bar>,
+    /// Document sent last by [`Self::render_dirty`]
+    last_svg: Option<String>,
+    /// Widest fitted price scale [`Self::render_dirty`] has drawn, which
+    /// later calls don't go below
+    price_scale_floor: Option<f64>,
+}
+
+impl Chart {
+    /// Create a new chart builder with given dimensions
+    pub fn new(width: u32, height: u32) -> Self {
+        Self {
+            config: ChartConfig {
+                width,
+                height,
+                dpr: 1.0,
+                theme: ThemeConfig::default(),
+                series: SeriesConfig::candlestick(),
+                layout: super::config::LayoutConfig::single(),
+                ..Default::default()
+            },
+            bars: Vec::new(),
+            last_svg: None,
+            price_scale_floor: None,
+        }
+    }
+
+    /// Create a chart builder, failing when the size is below
+    /// [`ChartConfig::min_size`] at the default density
+    pub fn try_new(width: u32, height: u32) -> Result<Self, ChartError> {
+        let chart = Self::new(width, height);
+        chart.config.check_dimensions()?;
+        Ok(chart)
+    }
+
+    /// Start from a complete config, e.g. one read with
+    /// [`ChartConfig::from_json`]; bars are set with [`Self::bars`]
+    pub fn from_config(config: ChartConfig) -> Self {
+        Self {
+            config,
+            bars: Vec::new(),
+            last_svg: None,
+            price_scale_floor: None,
+        }
+    }
+
+    /// Set device pixel ratio, clamped to
+    /// [`MIN_DPR`](crate::api::MIN_DPR)..=[`MAX_DPR`](crate::api::MAX_DPR)
+    pub fn dpr(mut self, dpr: f64) -> Self {
+        self.config.dpr = clamp_dpr(dpr);
+        self
+    }
+
+    /// Set independent horizontal and vertical pixel ratios, e.g. for a
+    /// printer with non-square dots; the SVG keeps its CSS size over a
+    /// viewBox of `width * dpr_x` by `height * dpr_y`
+    pub fn dpr_xy(mut self, dpr_x: f64, dpr_y: f64) -> Self {
+        self.config.dpr_x = Some(clamp_dpr(dpr_x));
+        self.config.dpr_y = Some(clamp_dpr(dpr_y));
+        self
+    }
+
+    /// Set OHLCV bar data
+    ///
+    /// With [`Self::sort_bars`] on, the bars are sorted and deduplicated here
+    /// and indicators already added are recomputed on the clean series.
+    pub fn bars(mut self, bars: &[Bar]) -> Self {
+        self.bars = bars.to_vec();
+        self.clean_bars();
+        self
+    }
+
+    /// Sort bars by timestamp and merge duplicate timestamps (off by default)
+    ///
+    /// Applies to bars already set and to later [`Self::bars`] calls.
+    pub fn sort_bars(mut self, sort: bool) -> Self {
+        self.config.sort_bars = sort;
+        self.clean_bars();
+        self
+    }
+
+    /// How [`Self::sort_bars`] merges bars sharing a timestamp
+    ///
+    /// Set it before the bars: duplicates already merged stay merged.
+    pub fn duplicate_merge(mut self, merge: DuplicateBarMerge) -> Self {
+        self.config.duplicate_merge = merge;
+        self.clean_bars();
+        self
+    }
+
+    /// Leave `bars` bar widths empty after the last bar, for drawings and
+    /// signals anchored in the future
+    pub fn right_offset(mut self, bars: f64) -> Self {
+        self.config.right_offset = bars.max(0.0);
+        self
+    }
+
+    /// Place bars by index, or by timestamp so that gaps such as weekends
+    /// show as empty space
+    pub fn time_spacing(mut self, spacing: TimeSpacing) -> Self {
+        self.config.time_spacing = spacing;
+        self
+    }
+
+    /// Draw bars only up to index `last`, as in a bar replay
+    pub fn replay_to(mut self, last: usize) -> Self {
+        self.config.replay_to = Some(last);
+        self
+    }
+
+    /// Keep the full data set's price scale while replaying
+    pub fn replay_lock_scale(mut self, lock: bool) -> Self {
+        self.config.replay_lock_scale = lock;
+        self
+    }
+
+    fn clean_bars(&mut self) {
+        if self.config.sort_bars && !compute::bars_are_sorted(&self.bars) {
+            self.bars = compute::sort_bars(&self.bars, self.config.duplicate_merge);
+            for indicator in &mut self.config.indicators {
+                indicator.recompute(&self.bars);
+            }
+        }
+    }
+
+    /// Switch the series type and style, keeping its common options
+    /// (title, visibility, last-value settings)
+    fn set_series(&mut self, series: SeriesConfig) {
+        let common = std::mem::take(&mut self.config.series.common);
+        self.config.series = SeriesConfig { common, ..series };
+    }
+
+    /// Name the main series in the legend and `legend_data`
+    pub fn series_title(mut self, title: &str) -> Self {
+        self.config.series.common.title = title.to_string();
+        self
+    }
+
+    /// Main series visibility, legend title and last-value options; the
+    /// last-price line itself is switched on with [`Self::last_price_line`]
+    pub fn series_options(mut self, options: SeriesOptionsCommon) -> Self {
+        self.config.series.common = options;
+        self
+    }
+
+    /// Use candlestick series
+    pub fn candlesticks(mut self) -> Self {
+        self.set_series(SeriesConfig::candlestick());
+        self
+    }
+
+    /// Use Heikin Ashi series
+    pub fn heikin_ashi(mut self) -> Self {
+        self.set_series(SeriesConfig::heikin_ashi());
+        self
+    }
+
+    /// Replace the bars with Renko bricks of `box_size`, drawn as candles
+    ///
+    /// Bricks are evenly spaced; each is stamped with the time of the bar
+    /// that completed it, so the time axis shows real times at the brick
+    /// boundaries. Add indicators after this to compute them on the bricks.
+    pub fn renko(self, box_size: f64) -> Self {
+        let bricks = compute::renko(&self.bars, box_size);
+        self.bricks(&bricks)
+    }
+
+    /// Replace the bars with line-break bricks turning after `lines` lines
+    /// (3 for the classic three-line break), drawn as candles; see
+    /// [`Self::renko`]
+    pub fn line_break(self, lines: usize) -> Self {
+        let bricks = compute::line_break(&self.bars, lines);
+        self.bricks(&bricks)
+    }
+
+    fn bricks(mut self, bricks: &[compute::Brick]) -> Self {
+        self.bars = bricks.iter().map(compute::Brick::to_bar).collect();
+        // Several bricks can share a source bar's timestamp
+        self.config.sort_bars = false;
+        self.config.time_spacing = TimeSpacing::Indexed;
+        self.set_series(SeriesConfig::candlestick());
+        self
+    }
+
+    /// Candle wicks as a fraction of the body width, never under 1px
+    pub fn wick_width_ratio(mut self, ratio: f64) -> Self {
+        self.config.series.style.wick_width_ratio = Some(ratio);
+        self
+    }
+
+    /// Draw candle bodies `pixels` wide at any bar spacing, narrowed only
+    /// where neighbours would overlap
+    pub fn candle_width_pixels(mut self, pixels: f64) -> Self {
+        self.config.series.style.candle_width = Some(CandleWidthMode::FixedPixels(pixels));
+        self
+    }
+
+    /// Past `bars_per_pixel`, drop candle wicks or collapse candles to
+    /// high-low lines
+    pub fn dense_candles(mut self, bars_per_pixel: f64, mode: DenseCandles) -> Self {
+        self.config.series.style.dense_threshold = Some(bars_per_pixel);
+        self.config.series.style.dense_candles = Some(mode);
+        self
+    }
+
+    /// Draw Heikin Ashi wicks from the smoothed or the real high/low
+    pub fn ha_wick_source(mut self, source: HaWickSource) -> Self {
+        self.config.series.style.ha_wick_source = Some(source);
+        self
+    }
+
+    /// Use OHLC bar series (high-low line with open and close ticks)
+    pub fn ohlc_bars(mut self) -> Self {
+        self.set_series(SeriesConfig::bar());
+        self
+    }
+
+    /// Length of OHLC bar open/close ticks in pixels, regardless of spacing
+    pub fn bar_tick_length(mut self, length: f64) -> Self {
+        self.config.series.style.tick_length = Some(length);
+        self
+    }
+
+    /// Show or hide the open and close ticks of OHLC bars, e.g. close-only
+    /// bars with `bar_ticks(false, true)`
+    pub fn bar_ticks(mut self, show_open: bool, show_close: bool) -> Self {
+        self.config.series.style.show_open_tick = Some(show_open);
+        self.config.series.style.show_close_tick = Some(show_close);
+        self
+    }
+
+    /// Use line series
+    pub fn line(mut self) -> Self {
+        self.set_series(SeriesConfig::line());
+        self
+    }
+
+    /// Use area series
+    pub fn area(mut self) -> Self {
+        self.set_series(SeriesConfig::area());
+        self
+    }
+
+    /// Fill the area series down to `base` rather than the pane bottom,
+    /// keeping `base` in the price range (call after [`Self::area`])
+    pub fn area_base(mut self, base: f64) -> Self {
+        self.config.series.style.area_base = Some(base);
+        self
+    }
+
+    /// Fill the part of the area series under its base with `color`
+    pub fn area_below_color(mut self, color: &str) -> Self {
+        self.config.series.style.down_color = Some(color.into());
+        self
+    }
+
+    /// Use baseline series, split into up/down zones at `value`
+    /// (any price, including zero or negative)
+    pub fn baseline(mut self, value: f64) -> Self {
+        self.set_series(SeriesConfig::baseline(value));
+        self
+    }
+
+    /// Set up/down colors
+    pub fn colors(mut self, up: &str, down: &str) -> Self {
+        self.config.theme.up_color = up.into();
+        self.config.theme.down_color = down.into();
+        self
+    }
+
+    /// Set background color
+    pub fn background(mut self, color: &str) -> Self {
+        self.config.theme.background = color.into();
+        self
+    }
+
+    /// Use a theme's colors and effects: a [`ThemeConfig`] or a whole
+    /// [`RuntimeTheme`](crate::core::RuntimeTheme), e.g. one edited and
+    /// loaded from JSON
+    pub fn theme(mut self, theme: impl Into<ThemeConfig>) -> Self {
+        self.config.theme = theme.into();
+        self
+    }
+
+    /// Glow blur radius around series and drawings; 0 turns the theme's
+    /// glow off for faster rendering and smaller output
+    pub fn glow(mut self, radius: f64) -> Self {
+        self.config.theme.glow = radius;
+        self
+    }
+
+    /// Enable/disable grid
+    pub fn grid(mut self, show: bool) -> Self {
+        self.config.theme.show_grid = show;
+        self
+    }
+
+    /// Show the price scale (on by default); hidden, the plot takes its width
+    pub fn price_scale(mut self, show: bool) -> Self {
+        self.config.show_price_scale = show;
+        self
+    }
+
+    /// Size the price scale to its widest label rather than the density's
+    /// fixed width (off by default), see [`ChartConfig::price_scale_fit`]
+    pub fn price_scale_fit(mut self, fit: bool) -> Self {
+        self.config.price_scale_fit = fit;
+        self
+    }
+
+    /// Keep a fitted price scale at least `width` wide
+    pub fn price_scale_min_width(mut self, width: f64) -> Self {
+        self.config.price_scale_min_width = Some(width);
+        self
+    }
+
+    /// Width reserved for the price scale column, zero when it is hidden
+    pub fn price_scale_width(&self) -> f64 {
+        ChartRenderer::new(&self.config, &self.bars).price_scale_width()
+    }
+
+    /// Show the time scale (on by default); hidden, the plot takes its height
+    pub fn time_scale(mut self, show: bool) -> Self {
+        self.config.show_time_scale = show;
+        self
+    }
+
+    /// Bare line of closes over the whole canvas, for tiny embedded charts:
+    /// no scales, grid, legend, pane titles or last-price line
+    pub fn sparkline(self) -> Self {
+        self.line()
+            .price_scale(false)
+            .time_scale(false)
+            .grid(false)
+            .legend(false)
+            .pane_titles(false)
+            .last_price_line(false)
+    }
+
+    /// Set per-bar colors (`None` keeps the theme up/down color)
+    ///
+    /// Entries beyond the bar count are ignored; bars beyond the list use the theme.
+    pub fn bar_colors(mut self, colors: Vec<Option<String>>) -> Self {
+        self.config.bar_colors = colors;
+        self
+    }
+
+    /// Force a time label pattern such as `"%H:%M"` (see `format_time_pattern`)
+    pub fn time_format(mut self, pattern: &str) -> Self {
+        self.config.time_format = Some(pattern.to_string());
+        self
+    }
+
+    /// Set the bar timeframe in seconds (inferred from bar spacing if unset)
+    pub fn timeframe(mut self, secs: i64) -> Self {
+        self.config.timeframe_secs = Some(secs);
+        self
+    }
+
+    /// Show each subpane's indicator name and latest values (on by default)
+    pub fn pane_titles(mut self, show: bool) -> Self {
+        self.config.show_pane_titles = show;
+        self
+    }
+
+    /// Size of the scales, grid and signal glyphs: compact for thumbnails,
+    /// comfortable for large displays
+    pub fn density(mut self, density: ChartDensity) -> Self {
+        self.config.density = density;
+        self
+    }
+
+    /// Corner of the subpane titles
+    pub fn legend_position(mut self, position: LegendPosition) -> Self {
+        self.config.legend_position = position;
+        self
+    }
+
+    /// Set the symbol or title used in the text description
+    pub fn title(mut self, title: &str) -> Self {
+        self.config.title = Some(title.to_string());
+        self
+    }
+
+    /// Embed the text description in the SVG for screen readers
+    pub fn embed_description(mut self, embed: bool) -> Self {
+        self.config.embed_description = embed;
+        self
+    }
+
+    /// Read bar timestamps (and the countdown's `now`) in `unit`
+    ///
+    /// [`TimestampUnit::Auto`] takes a series as milliseconds when its
+    /// timestamps exceed [`crate::core::AUTO_MILLIS_THRESHOLD`].
+    pub fn timestamp_unit(mut self, unit: TimestampUnit) -> Self {
+        self.config.timestamp_unit = unit;
+        self
+    }
+
+    /// Show time remaining until the last bar closes, relative to `now`
+    pub fn countdown(mut self, now: i64) -> Self {
+        let show_close_line = self
+            .config
+            .countdown
+            .as_ref()
+            .is_some_and(|c| c.show_close_line);
+        self.config.countdown = Some(CountdownConfig::new(now).with_close_line(show_close_line));
+        self
+    }
+
+    /// Create primitives from `registry`, e.g. one with custom primitives
+    pub fn registry(mut self, registry: impl Into<Arc<PrimitiveRegistry>>) -> Self {
+        self.config = self.config.registry(registry);
+        self
+    }
+
+    /// Register a composite primitive so `PrimitiveConfig`s can use its type ID
+    pub fn register_composite(&mut self, spec: CompositeSpec) -> Result<(), PrimitiveError> {
+        self.config.register_composite(spec)
+    }
+
+    /// Registry this chart creates primitives from
+    pub fn primitive_registry(&self) -> &PrimitiveRegistry {
+        self.config.primitive_registry()
+    }
+
+    /// Drawing tools available on this chart, for building a tool palette
+    pub fn list_primitives(&self) -> Vec<PrimitiveInfo> {
+        self.primitive_registry().list_info()
+    }
+
+    /// Highlight the hovered bar, or clear the highlight with `None`
+    pub fn hover_bar(mut self, index: Option<usize>) -> Self {
+        self.config.hover_bar = index;
+        self
+    }
+
+    /// Show a dashed line and axis tag at the last close
+    pub fn last_price_line(mut self, show: bool) -> Self {
+        self.config.show_last_price_line = show;
+        self
+    }
+
+    /// Draw a faint line at the projected bar close (requires `countdown`)
+    pub fn countdown_line(mut self, show: bool) -> Self {
+        if let Some(countdown) = self.config.countdown.as_mut() {
+            countdown.show_close_line = show;
+        }
+        self
+    }
+
+    /// Switch to seasonal mode: overlay each year (or day) of `source` as
+    /// a separate line on a shared Jan-Dec (or 00:00-24:00) axis
+    pub fn seasonal(mut self, period: SeasonalPeriod, source: Source) -> Self {
+        self.config.seasonal = Some(SeasonalConfig::new(period, source));
+        self
+    }
+
+    /// Pin a mini line chart of `bars` closes inside `rect` of the main pane
+    ///
+    /// It is scaled to its own range and does not affect the price scale.
+    pub fn add_inset(mut self, bars: &[Bar], rect: InsetRect) -> Self {
+        self.config.inset_series.push(InsetSeries::new(bars, rect));
+        self
+    }
+
+    /// Pin a prepared inset series, e.g. with a color or caption
+    pub fn inset(mut self, inset: InsetSeries) -> Self {
+        self.config.inset_series.push(inset);
+        self
+    }
+
+    /// Plot raw `(timestamp, price)` trade ticks as a line: in a pane under
+    /// the bars, or as the main series when no bars are set
+    ///
+    /// Ticks are downsampled to four points per pixel column when drawn, so
+    /// a million of them renders quickly.
+    pub fn ticks(mut self, ticks: &[(i64, f64)]) -> Self {
+        let series = self.config.ticks.take().unwrap_or_default();
+        self.config.ticks = Some(TickSeries {
+            ticks: ticks.to_vec(),
+            ..series
+        });
+        self
+    }
+
+    /// Set a prepared tick series, e.g. with an area style or color
+    pub fn tick_series(mut self, ticks: TickSeries) -> Self {
+        self.config.ticks = Some(ticks);
+        self
+    }
+
+    /// Draw higher-timeframe candles (e.g. 14400 for 4h) behind the main series
+    pub fn htf_overlay(mut self, interval_secs: i64, style: HtfOverlayStyle) -> Self {
+        self.config.htf_overlay = Some(HtfOverlayConfig::new(interval_secs, style));
+        self
+    }
+
+    /// Draw a watermark behind the series, aligned in the main pane
+    pub fn watermark(mut self, watermark: Watermark) -> Self {
+        self.config.watermark = Some(watermark);
+        self
+    }
+
+    /// Never squeeze bars narrower than `spacing` pixels; bars that do not
+    /// fit are left out from the start, as if scrolled to the end
+    pub fn min_bar_spacing(mut self, spacing: f64) -> Self {
+        self.config.min_bar_spacing = Some(spacing);
+        self
+    }
+
+    /// Show a scrollbar when `min_bar_spacing` leaves bars out
+    pub fn scroll_indicator(mut self, show: bool) -> Self {
+        self.config.show_scroll_indicator = show;
+        self
+    }
+
+    /// Mark bars that open away from the previous close, e.g.
+    /// `GapConfig::new(GapThreshold::Percent(1.0))`
+    pub fn show_gaps(mut self, gaps: GapConfig) -> Self {
+        self.config.show_gaps = Some(gaps);
+        self
+    }
+
+    /// Draw a volume-by-price histogram along the right edge of the main pane
+    pub fn volume_by_price(mut self, config: VbpConfig) -> Self {
+        self.config.volume_by_price = Some(config);
+        self
+    }
+
+    // =========================================================================
+    // Overlay Indicators
+    // =========================================================================
+
+    /// Add SMA overlay
+    pub fn sma(self, period: usize, color: &str) -> Self {
+        self.sma_on(period, color, Source::Close)
+    }
+
+    /// Add SMA overlay computed on `source` (e.g. hl2)
+    pub fn sma_on(mut self, period: usize, color: &str, source: Source) -> Self {
+        if self.bars.is_empty() || period == 0 {
+            return self;
+        }
+        let id = format!("sma_{}", period);
+        let mut indicator = sourced(Indicator::sma(&id, period as u32, color), source);
+        indicator.recompute(&self.bars);
+        self.config.indicators.push(indicator);
+        self
+    }
+
+    /// Add EMA overlay
+    pub fn ema(self, period: usize, color: &str) -> Self {
+        self.ema_on(period, color, Source::Close)
+    }
+
+    /// Add EMA overlay computed on `source`
+    pub fn ema_on(mut self, period: usize, color: &str, source: Source) -> Self {
+        if self.bars.is_empty() || period == 0 {
+            return self;
+        }
+        let id = format!("ema_{}", period);
+        let mut indicator = sourced(Indicator::ema(&id, period as u32, color), source);
+        indicator.recompute(&self.bars);
+        self.config.indicators.push(indicator);
+        self
+    }
+
+    /// Add Bollinger Bands overlay
+    pub fn bollinger(self, period: usize, multiplier: f64) -> Self {
+        self.bollinger_on(period, multiplier, Source::Close)
+    }
+
+    /// Add Bollinger Bands overlay computed on `source`
+    pub fn bollinger_on(self, period: usize, multiplier: f64, source: Source) -> Self {
+        self.bollinger_band(period, multiplier, source, &BandStyle::bollinger())
+    }
+
+    /// Add Bollinger Bands overlay drawn with `style`, e.g. without the
+    /// middle line or with dots on bars closing outside the bands
+    pub fn bollinger_styled(self, period: usize, multiplier: f64, style: &BandStyle) -> Self {
+        self.bollinger_band(period, multiplier, Source::Close, style)
+    }
+
+    fn bollinger_band(
+        mut self,
+        period: usize,
+        multiplier: f64,
+        source: Source,
+        style: &BandStyle,
+    ) -> Self {
+        if self.bars.is_empty() || period == 0 {
+            return self;
+        }
+        let id = format!("bb_{}", period);
+        let mut indicator = Indicator::bollinger_styled(&id, period as u32, style).with_params(
+            IndicatorParams::Bollinger {
+                period,
+                multiplier,
+                source,
+            },
+        );
+        indicator = sourced(indicator, source);
+        indicator.recompute(&self.bars);
+        self.config.indicators.push(indicator);
+        self
+    }
+
+    /// Add ZigZag overlay: straight lines between swing highs and lows that
+    /// reverse by at least `deviation` percent
+    pub fn zigzag(self, deviation: f64) -> Self {
+        self.zigzag_labeled(deviation, PivotLabels::None)
+    }
+
+    /// Add ZigZag overlay with each pivot labeled by its price or its change
+    /// from the previous pivot
+    pub fn zigzag_labeled(mut self, deviation: f64, labels: PivotLabels) -> Self {
+        if self.bars.is_empty() {
+            return self;
+        }
+        let mut indicator = Indicator::zigzag("zigzag", deviation);
+        indicator.recompute(&self.bars);
+        if let VectorStyle::Pivots { labels: style, .. } = &mut indicator.vectors[0].style {
+            *style = labels;
+        }
+        self.config.indicators.push(indicator);
+        self
+    }
+
+    /// Add Parabolic SAR dots, colored by trend, with acceleration `step`
+    /// up to `max_step` (Wilder used 0.02 and 0.2)
+    pub fn psar(mut self, step: f64, max_step: f64) -> Self {
+        if self.bars.is_empty() {
+            return self;
+        }
+        let mut indicator =
+            Indicator::psar("psar").with_params(IndicatorParams::Psar { step, max_step });
+        indicator.recompute(&self.bars);
+        self.config.indicators.push(indicator);
+        self
+    }
+
+    /// Add Supertrend stepped line, colored by trend
+    pub fn supertrend(self, period: usize, multiplier: f64) -> Self {
+        self.supertrend_with_fill(period, multiplier, false)
+    }
+
+    /// Add Supertrend, optionally shaded between the line and the close
+    pub fn supertrend_with_fill(mut self, period: usize, multiplier: f64, fill: bool) -> Self {
+        if self.bars.is_empty() || period == 0 {
+            return self;
+        }
+        let id = format!("supertrend_{}", period);
+        let mut indicator = Indicator::supertrend(&id, period as u32, multiplier);
+        if fill {
+            indicator = indicator.with_supertrend_fill();
+        }
+        indicator.recompute(&self.bars);
+        self.config.indicators.push(indicator);
+        self
+    }
+
+    /// Add custom overlay with values
+    pub fn overlay(mut self, name: &str, values: Vec<f64>, color: &str) -> Self {
+        use crate::model::{IndicatorRange, IndicatorVector, VectorStyle};
+        let id = format!("custom_{}", name.to_lowercase().replace(' ', "_"));
+        let indicator = Indicator::new(&id, name)
+            .overlay()
+            .range(IndicatorRange::Auto)
+            .add_vector(
+                IndicatorVector::new(name, VectorStyle::line(color, 1.5)).with_values(values),
+            );
+        self.config.indicators.push(indicator);
+        self
+    }
+
+    // =========================================================================
+    // Subpane Indicators
+    // =========================================================================
+
+    /// Add RSI indicator
+    pub fn rsi(self, period: usize) -> Self {
+        self.rsi_on(period, Source::Close)
+    }
+
+    /// Add RSI indicator computed on `source`
+    pub fn rsi_on(mut self, period: usize, source: Source) -> Self {
+        if self.bars.is_empty() || period == 0 {
+            return self;
+        }
+        let id = format!("rsi_{}", period);
+        let mut indicator = sourced(Indicator::rsi(&id, period as u32), source);
+        indicator.recompute(&self.bars);
+        self.config.indicators.push(indicator);
+        self
+    }
+
+    /// Add ADX indicator with +DI/-DI lines
+    pub fn adx(mut self, period: usize) -> Self {
+        if self.bars.is_empty() || period == 0 {
+            return self;
+        }
+        let id = format!("adx_{}", period);
+        let mut indicator = Indicator::adx(&id, period as u32);
+        indicator.recompute(&self.bars);
+        self.config.indicators.push(indicator);
+        self
+    }
+
+    /// Add MACD indicator
+    pub fn macd(self, fast: usize, slow: usize, signal: usize) -> Self {
+        self.macd_on(fast, slow, signal, Source::Close)
+    }
+
+    /// Add MACD indicator computed on `source`
+    pub fn macd_on(mut self, fast: usize, slow: usize, signal: usize, source: Source) -> Self {
+        if self.bars.is_empty() {
+            return self;
+        }
+        let id = format!("macd_{}_{}", fast, slow);
+        let mut indicator = sourced(
+            Indicator::macd(&id, fast as u32, slow as u32, signal as u32),
+            source,
+        );
+        indicator.recompute(&self.bars);
+        self.config.indicators.push(indicator);
+        self
+    }
+
+    /// Add On-Balance Volume in a subpane
+    pub fn obv(self) -> Self {
+        self.volume_indicator(Indicator::obv("obv"))
+    }
+
+    /// Add the Accumulation/Distribution line in a subpane
+    pub fn ad_line(self) -> Self {
+        self.volume_indicator(Indicator::ad_line("ad"))
+    }
+
+    /// Add Chaikin Money Flow over `period` bars in a subpane
+    pub fn cmf(self, period: usize) -> Self {
+        if period == 0 {
+            return self;
+        }
+        let id = format!("cmf_{}", period);
+        self.volume_indicator(Indicator::cmf(&id, period as u32))
+    }
+
+    /// Add Money Flow Index over `period` bars in a subpane
+    pub fn mfi(self, period: usize) -> Self {
+        if period == 0 {
+            return self;
+        }
+        let id = format!("mfi_{}", period);
+        self.volume_indicator(Indicator::mfi(&id, period as u32))
+    }
+
+    fn volume_indicator(mut self, mut indicator: Indicator) -> Self {
+        if self.bars.is_empty() {
+            return self;
+        }
+        indicator.recompute(&self.bars);
+        self.config.indicators.push(indicator);
+        self
+    }
+
+    /// Add Volume indicator
+    pub fn volume(mut self) -> Self {
+        if self.bars.is_empty() {
+            return self;
+        }
+        let values: Vec<f64> = self.bars.iter().map(|b| b.volume).collect();
+        let directions: Vec<bool> = self.bars.iter().map(|b| b.close >= b.open).collect();
+        let mut indicator = Indicator::volume("volume");
+        indicator.vectors[0].values = values;
+        indicator.vectors[0].directions = directions;
+        self.config.indicators.push(indicator);
+        self
+    }
+
+    /// Add Volume with a buy/sell breakdown per bar, stacked like
+    /// [`Self::volume_split`]
+    ///
+    /// Bars missing a `buy` or `sell` entry fall back to the plain volume
+    /// bar. Replaces a Volume indicator added earlier.
+    pub fn volume_breakdown(self, buy: &[f64], sell: &[f64]) -> Self {
+        if self.bars.is_empty() {
+            return self;
+        }
+        let volumes: Vec<f64> = self.bars.iter().map(|b| b.volume).collect();
+        let directions: Vec<bool> = self.bars.iter().map(|b| b.close >= b.open).collect();
+        let splits: Vec<(f64, f64)> = (0..self.bars.len())
+            .map(|i| {
+                let side = |values: &[f64]| values.get(i).copied().unwrap_or(f64::NAN);
+                (side(buy), side(sell))
+            })
+            .collect();
+        let indicator = Indicator::volume_breakdown("volume", &volumes, &directions, &splits);
+        self.set_volume_indicator(indicator)
+    }
+
+    /// Add Volume split into aggressive buy/sell volume (stacked)
+    ///
+    /// `splits` holds `(buy, sell)` per bar. Missing trailing entries are
+    /// left empty. Replaces a Volume indicator added earlier.
+    pub fn volume_split(self, splits: &[(f64, f64)]) -> Self {
+        self.volume_split_with_mode(splits, VolumeSplitMode::Stacked)
+    }
+
+    /// Add Volume split into aggressive buy/sell volume with a draw mode
+    pub fn volume_split_with_mode(self, splits: &[(f64, f64)], mode: VolumeSplitMode) -> Self {
+        if self.bars.is_empty() {
+            return self;
+        }
+        let mut padded: Vec<(f64, f64)> = splits.iter().take(self.bars.len()).copied().collect();
+        padded.resize(self.bars.len(), (f64::NAN, f64::NAN));
+        let indicator = Indicator::volume_split("volume", &padded, mode);
+        self.set_volume_indicator(indicator)
+    }
+
+    /// Put `indicator` in place of the Volume indicator, or add it
+    fn set_volume_indicator(mut self, indicator: Indicator) -> Self {
+        match self.config.indicators.iter_mut().find(|i| i.id == "volume") {
+            Some(existing) => *existing = indicator,
+            None => self.config.indicators.push(indicator),
+        }
+        self
+    }
+
+    /// Add a custom indicator from precomputed vectors, shading between
+    /// any vector pairs listed in `fills`
+    pub fn custom_indicator(
+        mut self,
+        name: &str,
+        placement: IndicatorPlacement,
+        vectors: Vec<IndicatorVector>,
+        fills: Vec<IndicatorFill>,
+    ) -> Self {
+        let id = format!("custom_{}", name.to_lowercase().replace(' ', "_"));
+        let mut indicator = Indicator::new(&id, name).placement(placement);
+        indicator.vectors = vectors;
+        indicator.fills = fills;
+        self.config.indicators.push(indicator);
+        self
+    }
+
+    /// Add a pre-configured indicator; band indicators get their
+    /// outside-band markers from the chart's bars
+    pub fn indicator(mut self, mut indicator: Indicator) -> Self {
+        indicator.mark_outside_band(&self.bars);
+        self.config.indicators.push(indicator);
+        self
+    }
+
+    /// Put indicator `id` in a legend group
+    pub fn indicator_group(mut self, id: &str, group: &str) -> Self {
+        if let Some(indicator) = self.config.indicators.iter_mut().find(|ind| ind.id == id) {
+            indicator.group = Some(group.to_string());
+        }
+        self
+    }
+
+    /// Scale the named vectors of subpane indicator `id` by a scale group
+    /// of their own, see [`ScaleGroup`]
+    pub fn scale_group(mut self, id: &str, group: ScaleGroup, vectors: &[&str]) -> Self {
+        if let Some(indicator) = self.config.indicators.iter_mut().find(|ind| ind.id == id) {
+            for vector in &mut indicator.vectors {
+                if vectors.contains(&vector.name.as_str()) {
+                    vector.scale_group = Some(group.id.clone());
+                }
+            }
+            indicator.scale_groups.retain(|g| g.id != group.id);
+            indicator.scale_groups.push(group);
+        }
+        self
+    }
+
+    /// Decimals written for SVG coordinates per element category, e.g.
+    /// [`SvgPrecision::compact`] for smaller files
+    pub fn svg_precision(mut self, precision: SvgPrecision) -> Self {
+        self.config.svg_precision = precision;
+        self
+    }
+
+    /// Show or hide every indicator in `group`
+    pub fn set_group_visible(&mut self, group: &str, visible: bool) {
+        self.config.set_group_visible(group, visible);
+    }
+
+    /// Toggle one indicator, as a click on its legend entry would: a
+    /// hidden one is not drawn or autoscaled but keeps a greyed legend row
+    pub fn set_indicator_visible(&mut self, id: &str, visible: bool) {
+        self.config.set_indicator_visible(id, visible);
+    }
+
+    /// Settings of an indicator by id, see [`Indicator::properties`]
+    pub fn indicator_properties(&self, id: &str) -> Option<Vec<ConfigProperty>> {
+        self.config
+            .indicators
+            .iter()
+            .find(|ind| ind.id == id)
+            .map(Indicator::properties)
+    }
+
+    /// Change one setting of an indicator, e.g. `("period", Integer(50))`
+    ///
+    /// New inputs re-derive the indicator's values from the chart's bars;
+    /// other indicators are left as they are.
+    pub fn update_indicator(
+        &mut self,
+        id: &str,
+        name: &str,
+        value: PropertyValue,
+    ) -> Result<(), IndicatorPropertyError> {
+        let indicator = self
+            .config
+            .indicators
+            .iter_mut()
+            .find(|ind| ind.id == id)
+            .ok_or_else(|| IndicatorPropertyError::UnknownIndicator(id.to_string()))?;
+        indicator.set_property(name, value)?;
+        if indicator.needs_recompute {
+            indicator.recompute(&self.bars);
+        }
+        Ok(())
+    }
+
+    /// Toggle the main series; a hidden one is not drawn or autoscaled
+    pub fn set_series_visible(&mut self, visible: bool) {
+        self.config.hidden_series = !visible;
+    }
+
+    /// Show a legend of the overlay indicators, grouped by indicator group
+    pub fn legend(mut self, show: bool) -> Self {
+        self.config.show_legend = show;
+        self
+    }
+
+    /// Set the instrument tick size (e.g. 0.25), which snaps price ticks to
+    /// its multiples and formats prices with its decimal precision
+    pub fn tick_size(mut self, tick_size: f64) -> Self {
+        self.config.tick_size = Some(tick_size);
+        self
+    }
+
+    /// Message shown while there are no bars (empty for none)
+    pub fn empty_message(mut self, message: &str) -> Self {
+        self.config.empty_state.message = message.to_string();
+        self
+    }
+
+    /// Draw `bar_count` placeholder candles while there are no bars,
+    /// optionally pulsing with SVG animation
+    pub fn skeleton(mut self, bar_count: usize, animate: bool) -> Self {
+        let empty = &mut self.config.empty_state;
+        empty.show_skeleton = bar_count > 0;
+        empty.skeleton_bar_count = bar_count;
+        empty.animate = animate;
+        self
+    }
+
+    /// Smooth the lines of indicator `id` with an SMA of `period` bars
+    ///
+    /// Only the rendered lines change; `indicator_values` keeps returning
+    /// the raw values. A period of 0 or 1 turns smoothing off.
+    pub fn smoothing(mut self, id: &str, period: usize) -> Self {
+        if let Some(indicator) = self.config.indicators.iter_mut().find(|ind| ind.id == id) {
+            for vector in &mut indicator.vectors {
+                vector.smoothing = (period > 1).then_some(period);
+            }
+        }
+        self
+    }
+
+    // =========================================================================
+    // Primitives
+    // =========================================================================
+
+    /// Add a primitive drawing
+    pub fn primitive(mut self, primitive: PrimitiveConfig) -> Self {
+        self.config.primitives.push(primitive);
+        self
+    }
+
+    /// Add multiple primitives
+    pub fn primitives(mut self, primitives: Vec<PrimitiveConfig>) -> Self {
+        self.config.primitives.extend(primitives);
+        self
+    }
+
+    /// Label the levels of Fibonacci drawings, unless a drawing sets its
+    /// own [`PrimitiveConfig::with_fib_labels`]
+    pub fn fib_labels(mut self, mode: FibLabelMode) -> Self {
+        self.config.fib_labels = Some(mode);
+        self
+    }
+
+    /// Draw the primitive with `id` selected
+    pub fn select_primitive(mut self, id: &str) -> Self {
+        self.config.set_primitive_selected(id, true);
+        self
+    }
+
+    /// Select or deselect the primitive with `id`
+    pub fn set_primitive_selected(&mut self, id: &str, selected: bool) {
+        self.config.set_primitive_selected(id, selected);
+    }
+
+    /// Show or hide the primitive with `id`, keeping it in the config
+    pub fn set_primitive_visibility(&mut self, id: &str, visible: bool) {
+        self.config.set_primitive_visibility(id, visible);
+    }
+
+    /// Lock the primitive with `id` against hit-testing and selection
+    pub fn set_primitive_locked(&mut self, id: &str, locked: bool) {
+        self.config.set_primitive_locked(id, locked);
+    }
+
+    /// Let the primitive with `id` stretch the automatic price range
+    pub fn set_primitive_autoscale(&mut self, id: &str, autoscale: bool) {
+        self.config.set_primitive_autoscale(id, autoscale);
+    }
+
+    /// Shift primitive points by bars and price, see
+    /// [`ChartConfig::translate_primitives`]
+    pub fn translate_primitives(
+        &mut self,
+        bar_delta: f64,
+        price_delta: f64,
+        ids: Option<&[String]>,
+    ) -> usize {
+        self.config
+            .translate_primitives(bar_delta, price_delta, ids)
+    }
+
+    /// Scale primitive points about an origin, see
+    /// [`ChartConfig::scale_primitives`]
+    pub fn scale_primitives(
+        &mut self,
+        origin: (f64, f64),
+        factor: (f64, f64),
+        ids: Option<&[String]>,
+    ) -> usize {
+        self.config.scale_primitives(origin, factor, ids)
+    }
+
+    /// Topmost hit-testable primitive at `(x, y)`, see
+    /// [`ChartRenderer::hit_test`]
+    pub fn hit_test(&self, x: f64, y: f64, tolerance: f64) -> Option<usize> {
+        ChartRenderer::new(&self.config, &self.bars).hit_test(x, y, tolerance)
+    }
+
+    // =========================================================================
+    // Signals
+    // =========================================================================
+
+    /// Add a signal marker
+    pub fn signal(mut self, signal: SignalConfig) -> Self {
+        self.config.signals.push(signal);
+        self
+    }
+
+    /// Add multiple signals
+    pub fn signals(mut self, signals: Vec<SignalConfig>) -> Self {
+        self.config.signals.extend(signals);
+        self
+    }
+
+    // =========================================================================
+    // Strategy
+    // =========================================================================
+
+    /// Attach a strategy (replaces any previous one)
+    ///
+    /// Colors may name theme slots (`"buy"`, `"sell"`, `"primary"`, ...);
+    /// without a strategy theme they resolve against the chart theme.
+    pub fn strategy(mut self, strategy: Strategy) -> Self {
+        self.config.strategy = Some(strategy);
+        self
+    }
+
+    // =========================================================================
+    // Queries
+    // =========================================================================
+
+    /// Computed vectors of an indicator, by id
+    ///
+    /// Indicator values are computed once when the indicator is added, so
+    /// reading them here costs nothing extra at render time. Values are raw,
+    /// before any `smoothing`. Returns `None` for an unknown id, bars or
+    /// not, and an empty slice for a known one while no bars are set.
+    pub fn indicator_values(&self, id: &str) -> Option<&[IndicatorVector]> {
+        let indicator = self.config.indicators.iter().find(|ind| ind.id == id)?;
+        if self.bars.is_empty() {
+            return Some(&[]);
+        }
+        Some(indicator.vectors.as_slice())
+    }
+
+    /// Tooltip payload for a bar (OHLCV, indicator values, signals, time),
+    /// formatted like the chart; `None` when out of range
+    pub fn bar_info(&self, index: usize) -> Option<BarInfo> {
+        ChartRenderer::new(&self.config, &self.bars).bar_info(index)
+    }
+
+    /// Configuration problems that render silently wrong, see
+    /// [`ChartConfig::validate`]
+    pub fn validate(&self) -> Vec<ConfigWarning> {
+        self.config.validate(self.bars.len())
+    }
+
+    /// Structured legend contents, see [`ChartRenderer::legend_data`]
+    pub fn legend_data(&self) -> Option<LegendData> {
+        ChartRenderer::new(&self.config, &self.bars).legend_data()
+    }
+
+    /// Plain-text summary of the chart (see [`ChartRenderer::describe`])
+    pub fn describe(&self) -> String {
+        ChartRenderer::new(&self.config, &self.bars).describe()
+    }
+
+    /// Labeled values of an indicator's vectors at a bar (for tooltips)
+    ///
+    /// Returns `None` for an unknown id. Missing values come back as `NaN`.
+    pub fn indicator_values_at(&self, id: &str, bar_index: usize) -> Option<Vec<(String, f64)>> {
+        self.config
+            .indicators
+            .iter()
+            .find(|ind| ind.id == id)
+            .map(|ind| ind.values_at(bar_index))
+    }
+
+    // =========================================================================
+    // Build & Render
+    // =========================================================================
+
+    /// Get the built ChartConfig
+    pub fn build(self) -> (ChartConfig, Vec<Bar>) {
+        (self.config, self.bars)
+    }
+
+    /// Render directly to SVG string
+    pub fn render_svg(&self) -> String {
+        ChartRenderer::new(&self.config, &self.bars).render_svg()
+    }
+
+    /// Render to SVG, failing on a size too small to lay out, see
+    /// [`ChartRenderer::try_render_svg`]
+    pub fn try_render_svg(&self) -> Result<String, ChartError> {
+        ChartRenderer::new(&self.config, &self.bars).try_render_svg()
+    }
+
+    /// Render to SVG with metrics, see [`ChartRenderer::render_svg_with_stats`]
+    pub fn render_svg_with_stats(&self) -> (String, RenderStats) {
+        ChartRenderer::new(&self.config, &self.bars).render_svg_with_stats()
+    }
+
+    /// Commands that changed since `prev`, see [`ChartRenderer::diff_commands`]
+    pub fn diff_commands(&self, prev: &Chart) -> RenderDiff {
+        ChartRenderer::new(&self.config, &self.bars)
+            .diff_commands(&ChartRenderer::new(&prev.config, &prev.bars))
+    }
+
+    /// Render with replaceable groups, see
+    /// [`ChartRenderer::render_svg_grouped`]
+    pub fn render_svg_grouped(&self) -> String {
+        ChartRenderer::new(&self.config, &self.bars).render_svg_grouped()
+    }
+
+    /// Parts of the grouped SVG that changed since the last call, for live
+    /// updates that swap only those groups
+    ///
+    /// The first call returns the whole document; after that only the
+    /// changed groups are returned, or the whole document again when
+    /// something outside them changed (see [`SvgFragment::between`]).
+    /// A fitted price scale only grows between calls, so the plot doesn't
+    /// jump back and forth as labels gain and lose digits.
+    pub fn render_dirty(&mut self) -> Vec<SvgFragment> {
+        let (svg, width) = {
+            let config = self.live_config();
+            let renderer = ChartRenderer::new(&config, &self.bars);
+            let width = renderer.price_scale_width() - renderer.secondary_axis_width();
+            (renderer.render_svg_grouped(), width)
+        };
+        if self.config.price_scale_fit {
+            self.price_scale_floor = Some(width);
+        }
+        let fragments = match &self.last_svg {
+            Some(prev) => SvgFragment::between(prev, &svg),
+            None => vec![SvgFragment {
+                id: None,
+                svg: svg.clone(),
+            }],
+        };
+        self.last_svg = Some(svg);
+        fragments
+    }
+
+    /// Config drawn by [`Self::render_dirty`]: the chart's own, with a
+    /// fitted price scale kept as wide as the widest one drawn so far
+    fn live_config(&self) -> Cow<'_, ChartConfig> {
+        match self.price_scale_floor {
+            Some(floor)
+                if self.config.price_scale_fit
+                    && self
+                        .config
+                        .price_scale_min_width
+                        .is_none_or(|min| min < floor) =>
+            {
+                Cow::Owned(ChartConfig {
+                    price_scale_min_width: Some(floor),
+                    ..self.config.clone()
+                })
+            }
+            _ => Cow::Borrowed(&self.config),
+        }
+    }
+
+    /// Render one SVG per replay step, see [`ChartRenderer::render_frames`]
+    pub fn render_frames(&self, frames: Range<usize>) -> Vec<String> {
+        ChartRenderer::new(&self.config, &self.bars)
+            .render_frames(frames)
+            .collect()
+    }
+
+    /// Rasterize to a premultiplied RGBA8 buffer, see
+    /// [`ChartRenderer::render_rgba`]
+    #[cfg(feature = "raster")]
+    pub fn render_rgba(&self) -> (Vec<u8>, u32, u32) {
+        ChartRenderer::new(&self.config, &self.bars).render_rgba()
+    }
+
+    /// Rasterize and encode as PNG
+    #[cfg(feature = "raster")]
+    pub fn render_png(&self) -> RenderResult<Vec<u8>> {
+        ChartRenderer::new(&self.config, &self.bars).render_png()
+    }
+}
+
+// =============================================================================
+// Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::GapThreshold;
+    use crate::coords::format_time_by_weight;
+    use crate::core::TIME_SCALE_HEIGHT;
+    use crate::model::{IndicatorPlacement, Signal};
+    use crate::primitives::{BandDeviation, DivergenceType};
+
+    fn sample_bars(n: usize) -> Vec<Bar> {
+        let mut bars = Vec::with_capacity(n);
+        let mut price = 100.0;
+
+        for i in 0..n {
+            let change = (i as f64 * 0.5).sin() * 2.0;
+            let vol = 1.0 + (i as f64 * 0.3).sin().abs();
+
+            let open = price;
+            let close = price + change;
+            let high = open.max(close) + vol;
+            let low = open.min(close) - vol;
+
+            bars.push(Bar {
+                timestamp: 1700000000 + (i as i64) * 3600,
+                open,
+                high,
+                low,
+                close,
+                volume: 1000.0 + (i as f64 * 100.0),
+            });
+
+            price = close;
+        }
+
+        bars
+    }
+
+    #[test]
+    fn test_empty_chart() {
+        let svg = Chart::new(800, 600).render_svg();
+        assert!(svg.contains("<svg"));
+        assert!(svg.contains("No data"));
+    }
+
+    #[test]
+    fn test_empty_state_skeleton() {
+        let chart = Chart::new(800, 600)
+            .empty_message("Waiting for data…")
+            .skeleton(12, false);
+        let svg = chart.render_svg();
+        assert!(svg.contains(">Waiting for data…</text>"));
+        assert!(!svg.contains("<animate"));
+        // Background plus one body per skeleton candle
+        assert_eq!(svg.matches("<rect").count(), 1 + 12);
+        // Price scale border stays where it is with data
+        assert!(svg.contains(r#"x1="730.50" y1="0.00" x2="730.50""#));
+        // Deterministic for a seed, different across seeds
+        assert_eq!(svg, chart.render_svg());
+        let mut config = chart.config.clone();
+        config.empty_state.seed = 7;
+        assert_ne!(svg, ChartRenderer::new(&config, &[]).render_svg());
+
+        let animated = Chart::new(800, 600).skeleton(12, true).render_svg();
+        assert_eq!(animated.matches("<animate").count(), 1);
+    }
+
+    #[test]
+    fn test_candlestick_chart() {
+        let bars = sample_bars(50);
+        let svg = Chart::new(800, 600).bars(&bars).candlesticks().render_svg();
+        assert!(svg.contains("<svg"));
+        assert!(svg.contains("<rect")); // candle bodies
+    }
+
+    #[test]
+    fn test_chart_with_sma() {
+        let bars = sample_bars(100);
+        let svg = Chart::new(800, 600)
+            .bars(&bars)
+            .sma(20, "#2196F3")
+            .render_svg();
+        assert!(svg.contains("<svg"));
+    }
+
+    #[test]
+    fn test_sma_on_hl2_source() {
+        let bars = sample_bars(60);
+        let chart =
+            Chart::new(800, 600)
+                .bars(&bars)
+                .sma(5, "#2196F3")
+                .sma_on(5, "#ff9800", Source::Hl2);
+        let close = &chart.config.indicators[0];
+        let hl2 = &chart.config.indicators[1];
+        assert_eq!(
+            (hl2.id.as_str(), hl2.name.as_str()),
+            ("sma_5_hl2", "SMA 5 hl2")
+        );
+
+        // Each value is the mean of the last 5 (high + low) / 2
+        let manual = |i: usize| -> f64 {
+            bars[i - 4..=i]
+                .iter()
+                .map(|b| (b.high + b.low) / 2.0)
+                .sum::<f64>()
+                / 5.0
+        };
+        let values = &hl2.vectors[0].values;
+        assert!(values[..4].iter().all(|v| v.is_nan()));
+        for (i, value) in values.iter().enumerate().skip(4) {
+            assert!((value - manual(i)).abs() < 1e-9, "bar {i}");
+        }
+        assert!((4..bars.len()).any(|i| (values[i] - close.vectors[0].values[i]).abs() > 1e-6));
+    }
+
+    #[test]
+    fn test_chart_with_rsi() {
+        let bars = sample_bars(100);
+        let svg = Chart::new(800, 600).bars(&bars).rsi(14).render_svg();
+        assert!(svg.contains("<svg"));
+    }
+
+    #[test]
+    fn test_full_chart() {
+        let bars = sample_bars(200);
+        let svg = Chart::new(1200, 800)
+            .bars(&bars)
+            .candlesticks()
+            .sma(20, "#2196F3")
+            .sma(50, "#FF9800")
+            .bollinger(20, 2.0)
+            .rsi(14)
+            .macd(12, 26, 9)
+            .render_svg();
+
+        assert!(svg.contains("<svg"));
+        assert!(svg.len() > 1000);
+    }
+
+    #[test]
+    fn test_chart_renderer_from_config() {
+        let bars = sample_bars(100);
+        let config = ChartConfig {
+            width: 800,
+            height: 600,
+            dpr: 1.0,
+            theme: ThemeConfig::default(),
+            series: SeriesConfig::candlestick(),
+            indicators: vec![],
+            primitives: vec![],
+            signals: vec![],
+            layout: super::super::config::LayoutConfig::single(),
+            ..Default::default()
+        };
+
+        let svg = ChartRenderer::new(&config, &bars).render_svg();
+        assert!(svg.contains("<svg"));
+    }
+
+    #[test]
+    fn test_chart_with_primitives() {
+        let bars = sample_bars(100);
+        let svg = Chart::new(800, 600)
+            .bars(&bars)
+            .candlesticks()
+            .primitive(PrimitiveConfig::trend_line((10.0, 100.0), (50.0, 110.0)))
+            .primitive(PrimitiveConfig::horizontal_line(105.0))
+            .render_svg();
+
+        assert!(svg.contains("<svg"));
+    }
+
+    #[test]
+    fn test_primitive_label_boxes_fit_measured_text() {
+        let bars = sample_bars(100);
+        let price = bars[40].close;
+        let svg = Chart::new(800, 600)
+            .bars(&bars)
+            .primitive(PrimitiveConfig::new("price_label", vec![(40.0, price)]))
+            .render_svg();
+        // Padded 4px either side of the 14px label
+        let label = format!("{:.2}", price);
+        let width = estimate_text_width(&label, 14.0, FontWeight::Normal) + 8.0;
+        assert!(svg.contains(&format!(r#"width="{:.2}""#, width)));
+    }
+
+    #[test]
+    fn test_chart_with_signals() {
+        let bars = sample_bars(100);
+        let svg = Chart::new(800, 600)
+            .bars(&bars)
+            .candlesticks()
+            .signal(SignalConfig::buy(25, 100.0))
+            .signal(SignalConfig::sell(75, 105.0))
+            .render_svg();
+
+        assert!(svg.contains("<svg"));
+    }
+
+    #[test]
+    fn test_tp_sl_custom_signal_glyphs_and_levels() {
+        let bars = sample_bars(100);
+        let svg = Chart::new(800, 600)
+            .bars(&bars)
+            .signal(
+                SignalConfig::take_profit(40, 104.0)
+                    .with_color("#ac0001")
+                    .with_label("TP")
+                    .with_level_line(),
+            )
+            .signal(SignalConfig::stop_loss(50, 98.0).with_color("#ac0002"))
+            .signal(SignalConfig::custom(60, 101.0, "X").with_color("#ac0003"))
+            .render_svg();
+        let drawn = |color: &str| -> Vec<&str> {
+            svg.lines()
+                .filter(|l| l.contains(&format!("\"{color}\"")))
+                .collect()
+        };
+        let corners = |path: &str| path.matches(" L").count() + 1;
+
+        // Target ring and dot, stop-sign octagon, custom diamond, each in its color
+        let tp = drawn("#ac0001");
+        assert!(
+            tp.iter()
+                .any(|l| l.starts_with("<circle") && l.contains("fill=\"none\""))
+        );
+        assert!(
+            tp.iter()
+                .any(|l| l.starts_with("<circle") && l.contains("stroke=\"none\""))
+        );
+        let sl = drawn("#ac0002");
+        assert_eq!(sl.len(), 1);
+        assert_eq!(corners(sl[0]), 8);
+        let custom = drawn("#ac0003");
+        assert!(
+            custom
+                .iter()
+                .any(|l| l.starts_with("<path") && corners(l) == 4)
+        );
+
+        // TP level: a line right of the glyph, its price above and label below
+        let line = tp.iter().find(|l| l.starts_with("<line")).unwrap();
+        let attr = |l: &str, name: &str| -> f64 {
+            l.split(&format!(" {name}=\""))
+                .nth(1)
+                .unwrap()
+                .split('"')
+                .next()
+                .unwrap()
+                .parse()
+                .unwrap()
+        };
+        let (level_y, glyph_x) = (attr(line, "y1"), attr(tp[0], "cx"));
+        assert!(attr(line, "x1") > glyph_x);
+        assert!(attr(line, "x2") - attr(line, "x1") >= 40.0);
+        let text = |content: &str| {
+            *tp.iter()
+                .find(|l| l.starts_with("<text") && l.contains(&format!(">{content}<")))
+                .unwrap()
+        };
+        assert!(attr(text("104"), "y") < level_y);
+        assert!(attr(text("TP"), "y") > level_y);
+        assert!(attr(text("TP"), "x") > glyph_x);
+        // Stop loss without the option draws no level
+        assert!(!sl.iter().any(|l| l.starts_with("<line")));
+    }
+
+    #[test]
+    fn test_builder_indicators_match_compute() {
+        let bars = sample_bars(100);
+        let closes = compute::closes(&bars);
+        let chart = Chart::new(800, 600)
+            .bars(&bars)
+            .sma(20, "#2196F3")
+            .macd(12, 26, 9);
+
+        let same = |a: &[f64], b: &[f64]| {
+            a.len() == b.len()
+                && a.iter()
+                    .zip(b)
+                    .all(|(x, y)| x.to_bits() == y.to_bits() || (x.is_nan() && y.is_nan()))
+        };
+
+        let sma = &chart.config.indicators[0].vectors[0].values;
+        assert!(same(sma, &compute::sma(&closes, 20)));
+
+        let (line, signal, hist) = compute::macd(&closes, 12, 26, 9);
+        let macd = &chart.config.indicators[1];
+        assert!(same(&macd.vectors[0].values, &line));
+        assert!(same(&macd.vectors[1].values, &signal));
+        assert!(same(&macd.vectors[2].values, &hist));
+    }
+
+    #[test]
+    fn test_volume_split_shorter_than_bars() {
+        let bars = sample_bars(50);
+        let splits: Vec<(f64, f64)> = (0..30).map(|i| (i as f64 * 10.0, 5.0)).collect();
+        let chart = Chart::new(800, 600)
+            .bars(&bars)
+            .volume_split_with_mode(&splits, VolumeSplitMode::Mirrored);
+
+        let volume = &chart.config.indicators[0];
+        assert_eq!(volume.vectors[0].values.len(), bars.len());
+        assert!(volume.vectors[0].values[40].is_nan());
+        // Last bar has no split data, so no totals in the title
+        assert_eq!(volume.name, "Volume");
+        assert!(chart.render_svg().contains("<svg"));
+    }
+
+    #[test]
+    fn test_volume_breakdown_stacks_buy_and_sell() {
+        let closes: Vec<f64> = (0..20).map(|i| 100.0 + i as f64).collect();
+        let bars: Vec<Bar> = bars_from_closes(&closes)
+            .into_iter()
+            .map(|b| Bar { volume: 100.0, ..b })
+            .collect();
+        // Only the last bar has a breakdown
+        let mut buy = vec![f64::NAN; 20];
+        let mut sell = vec![f64::NAN; 20];
+        buy[19] = 60.0;
+        sell[19] = 40.0;
+        let chart = Chart::new(800, 600)
+            .bars(&bars)
+            .volume()
+            .volume_breakdown(&buy, &sell);
+        // Replaces the plain volume, in the strip volume_split draws in
+        assert_eq!(chart.config.indicators.len(), 1);
+        assert!(chart.config.indicators[0].placement.is_overlay_bottom());
+        let svg = chart.render_svg();
+
+        // Sell at the base of its bar, 40% of the total
+        let sells = rects_of(&svg, "#ef5350");
+        assert_eq!(sells.len(), 1);
+        let (sell_y, sell_h) = sells[0];
+        let base = sell_y + sell_h;
+        let columns: Vec<(f64, f64)> = rects_of(&svg, "#26a69a")
+            .into_iter()
+            .filter(|&(y, h)| (y + h - base).abs() <= 0.5)
+            .collect();
+        assert_eq!(columns.len(), 20);
+        let total_h = columns[19].1;
+        assert!(
+            (sell_h / total_h - 0.4).abs() < 0.05,
+            "{sell_h} / {total_h}"
+        );
+
+        // The other bars fall back to a plain bar as tall as the split one
+        assert!(columns.iter().all(|&(_, h)| (h - total_h).abs() <= 1.0));
+    }
+
+    #[test]
+    fn test_countdown_label() {
+        let bars = sample_bars(50);
+        let last_ts = bars.last().unwrap().timestamp;
+        // sample bars are one hour apart; 2m31s before close
+        let svg = Chart::new(800, 600)
+            .bars(&bars)
+            .countdown(last_ts + 3600 - 151)
+            .countdown_line(true)
+            .render_svg();
+        assert!(svg.contains("02:31"));
+
+        // Line and label take the chart theme's crosshair colors
+        let theme = ThemeConfig {
+            crosshair_color: "#ff00aa".into(),
+            crosshair_label_bg: "#00aaff".into(),
+            crosshair_label_text: "#112233".into(),
+            ..ThemeConfig::light()
+        };
+        let svg = Chart::new(800, 600)
+            .bars(&bars)
+            .theme(theme)
+            .countdown(last_ts + 3600 - 151)
+            .countdown_line(true)
+            .render_svg();
+        assert!(
+            svg.lines()
+                .any(|l| l.starts_with("<line") && l.contains("#ff00aa"))
+        );
+        assert!(
+            svg.lines()
+                .any(|l| l.starts_with("<rect") && l.contains("#00aaff"))
+        );
+        assert!(
+            svg.lines()
+                .any(|l| l.contains("#112233") && l.contains(">02:31<"))
+        );
+
+        let svg = Chart::new(800, 600)
+            .bars(&bars)
+            .timeframe(86400 * 7)
+            .countdown(last_ts)
+            .render_svg();
+        assert!(svg.contains("7d 00h"));
+    }
+
+    #[test]
+    fn test_millisecond_timestamps_render_like_seconds() {
+        let bars = sample_bars(50);
+        let millis: Vec<Bar> = bars
+            .iter()
+            .map(|b| Bar {
+                timestamp: b.timestamp * 1000,
+                ..*b
+            })
+            .collect();
+        let last_ts = bars.last().unwrap().timestamp;
+        let render = |bars: &[Bar], now: i64| {
+            Chart::new(800, 600)
+                .bars(bars)
+                .timestamp_unit(TimestampUnit::Auto)
+                .htf_overlay(4 * 3600, HtfOverlayStyle::default())
+                .countdown(now)
+                .render_svg()
+        };
+
+        let seconds = render(&bars, last_ts + 3600 - 151);
+        assert!(seconds.contains("02:31"));
+        assert_eq!(seconds, render(&millis, (last_ts + 3600 - 151) * 1000));
+
+        // Explicit milliseconds agree with Auto, and seconds stay untouched
+        let explicit = Chart::new(800, 600)
+            .bars(&millis)
+            .timestamp_unit(TimestampUnit::Milliseconds)
+            .render_svg();
+        let plain = Chart::new(800, 600).bars(&bars).render_svg();
+        assert_eq!(explicit, plain);
+        assert_eq!(TimestampUnit::Auto.to_seconds(1_700_000_000), 1_700_000_000);
+        assert_eq!(
+            TimestampUnit::Auto.to_seconds(1_700_000_000_999),
+            1_700_000_000
+        );
+        assert_eq!("ms".parse(), Ok(TimestampUnit::Milliseconds));
+    }
+
+    #[test]
+    fn test_sort_bars_cleans_feed_before_indicators() {
+        let clean = sample_bars(40);
+        let mut feed = clean.clone();
+        feed.swap(10, 11);
+        // A replay of bar 20 with a different close, delivered late
+        feed.push(Bar {
+            close: clean[20].close + 1.0,
+            ..clean[20]
+        });
+
+        let sorted = Chart::new(800, 600)
+            .sort_bars(true)
+            .bars(&feed)
+            .sma(5, "#2962ff");
+        let mut expected = clean.clone();
+        expected[20].close += 1.0;
+        assert_eq!(sorted.bars.len(), 40);
+        assert!(
+            sorted
+                .bars
+                .iter()
+                .zip(&expected)
+                .all(|(a, b)| { a.timestamp == b.timestamp && a.close == b.close })
+        );
+        let sma = &sorted.config.indicators[0].vectors[0].values;
+        let want = compute::sma(&compute::closes(&expected), 5);
+        assert!(sma[4..].iter().zip(&want[4..]).all(|(a, b)| a == b));
+
+        // Same picture as the clean series; off by default
+        let reference = Chart::new(800, 600)
+            .bars(&expected)
+            .sma(5, "#2962ff")
+            .render_svg();
+        assert_eq!(sorted.render_svg(), reference);
+        assert_eq!(Chart::new(800, 600).bars(&feed).bars.len(), 41);
+
+        // The renderer sorts a raw config too, recomputing its indicators
+        let mut raw = Chart::new(800, 600).bars(&feed).sma(5, "#2962ff");
+        raw.config.sort_bars = true;
+        assert_eq!(
+            ChartRenderer::new(&raw.config, &raw.bars).render_svg(),
+            reference
+        );
+
+        // Turning it on later cleans bars already set and recomputes the
+        // indicators computed on the raw feed
+        let late = Chart::new(800, 600)
+            .bars(&feed)
+            .sma(5, "#2962ff")
+            .duplicate_merge(DuplicateBarMerge::Combine)
+            .sort_bars(true);
+        assert_eq!(late.bars.len(), 40);
+        assert_eq!(late.bars[20].close, expected[20].close);
+        assert_eq!(late.bars[20].volume, clean[20].volume * 2.0);
+        let sma = &late.config.indicators[0].vectors[0].values;
+        assert_eq!(sma.len(), 40);
+        assert!(sma[4..].iter().zip(&want[4..]).all(|(a, b)| a == b));
+    }
+
+    #[test]
+    fn test_inset_series_in_corner() {
+        let closes: Vec<f64> = (0..20)
+            .map(|i| 5000.0 + (i as f64 * 0.7).sin() * 40.0)
+            .collect();
+        let bars = sample_bars(50);
+        let svg = Chart::new(800, 600)
+            .bars(&bars)
+            .inset(
+                InsetSeries::new(&bars_from_closes(&closes), InsetRect::top_right(0.25, 0.2))
+                    .with_color("#ff00aa"),
+            )
+            .render_svg();
+        let attr = |line: &str, name: &str| -> f64 {
+            line.split(&format!(" {}=\"", name))
+                .nth(1)
+                .unwrap()
+                .split('"')
+                .next()
+                .unwrap()
+                .parse()
+                .unwrap()
+        };
+
+        // Bordered box in the top-right corner of the 730x570 price area
+        let border = svg
+            .lines()
+            .find(|l| l.starts_with("<rect") && l.contains("fill=\"none\""))
+            .unwrap();
+        let (x, y) = (attr(border, "x"), attr(border, "y"));
+        let (w, h) = (attr(border, "width"), attr(border, "height"));
+        assert!((x + w - 730.0 * 0.98).abs() <= 1.0);
+        assert!((y - 570.0 * 0.02).abs() <= 1.0);
+        assert!((w - 730.0 * 0.25).abs() <= 1.0 && (h - 570.0 * 0.2).abs() <= 1.0);
+
+        // Polyline spans the box, scaled to the inset's own range
+        let line = svg
+            .lines()
+            .find(|l| l.contains("stroke=\"#ff00aa\""))
+            .unwrap();
+        let points: Vec<(f64, f64)> = line
+            .split("points=\"")
+            .nth(1)
+            .unwrap()
+            .split('"')
+            .next()
+            .unwrap()
+            .split(' ')
+            .map(|p| {
+                let (px, py) = p.split_once(',').unwrap();
+                (px.parse().unwrap(), py.parse().unwrap())
+            })
+            .collect();
+        assert_eq!(points.len(), 20);
+        assert!((points[0].0 - (x + 4.0)).abs() <= 1.0);
+        assert!((points[19].0 - (x + w - 4.0)).abs() <= 1.0);
+        let top = points.iter().map(|p| p.1).fold(f64::INFINITY, f64::min);
+        let bottom = points.iter().map(|p| p.1).fold(f64::NEG_INFINITY, f64::max);
+        assert!((top - (y + 4.0)).abs() <= 1.0 && (bottom - (y + h - 4.0)).abs() <= 1.0);
+
+        // The main chart is drawn exactly as without the inset
+        let plain = Chart::new(800, 600).bars(&bars).render_svg();
+        assert!(plain.lines().all(|l| svg.contains(l)));
+    }
+
+    #[test]
+    fn test_bar_colors_override_theme() {
+        let bars = sample_bars(5);
+        let chart = Chart::new(800, 600).bars(&bars).bar_colors(vec![
+            None,
+            None,
+            Some("#123456".to_string()),
+        ]);
+
+        let data = candlestick_data(&chart.config, &chart.bars);
+        assert_eq!(data[2].color.as_deref(), Some("#123456"));
+        assert!(data[0].color.is_none());
+        // Shorter color list falls back to the theme for the rest
+        assert!(data[4].color.is_none());
+
+        let svg = chart.render_svg();
+        assert!(svg.contains("#123456"));
+        assert!(svg.contains("#26a69a") || svg.contains("#ef5350"));
+
+        // Multichart cells honor them too, for candles and OHLC bars
+        for series_type in [SeriesType::Candlestick, SeriesType::Bar] {
+            let mut config = chart.config.clone();
+            config.series.series_type = series_type;
+            let svg = MultichartRenderer::new(&MultichartLayout::single(), 800, 600)
+                .chart(&config, &chart.bars)
+                .render_svg();
+            assert!(svg.contains("#123456"), "{series_type:?}");
+        }
+    }
+
+    #[test]
+    fn test_multichart_heikin_ashi_follows_wick_source() {
+        let bars = sample_bars(60);
+        let cell = |chart: Chart| {
+            let (config, bars) = chart.build();
+            MultichartRenderer::new(&MultichartLayout::single(), 800, 600)
+                .chart(&config, &bars)
+                .render_svg()
+        };
+        let candles = cell(Chart::new(800, 600).bars(&bars));
+        let smoothed = cell(Chart::new(800, 600).bars(&bars).heikin_ashi());
+        let original = cell(
+            Chart::new(800, 600)
+                .bars(&bars)
+                .heikin_ashi()
+                .ha_wick_source(HaWickSource::Original),
+        );
+        // Smoothed bodies rather than raw OHLC, with wicks from the option
+        assert_ne!(smoothed, candles);
+        assert_ne!(original, smoothed);
+    }
+
+    #[test]
+    fn test_indicator_values_at() {
+        let bars = sample_bars(100);
+        let chart = Chart::new(800, 600).bars(&bars).macd(12, 26, 9);
+
+        let values = chart.indicator_values_at("macd_12_26", 50).unwrap();
+        assert_eq!(values.len(), 3);
+        assert_eq!(values[0].0, "MACD");
+        assert_eq!(values[1].0, "Signal");
+        assert_eq!(values[2].0, "Hist");
+        assert!(values.iter().all(|(_, v)| !v.is_nan()));
+
+        assert!(chart.indicator_values_at("missing", 50).is_none());
+    }
+
+    #[test]
+    fn test_indicator_values() {
+        // Without bars an unknown id is still unknown; a known one is empty
+        let chart = Chart::new(800, 600).indicator(Indicator::sma("sma_20", 20, "#2196F3"));
+        assert!(chart.indicator_values("unknown").is_none());
+        assert!(chart.indicator_values("sma_20").unwrap().is_empty());
+
+        let bars = sample_bars(60);
+        let chart = Chart::new(800, 600).bars(&bars).sma(20, "#2196F3");
+        let vectors = chart.indicator_values("sma_20").unwrap();
+        assert_eq!(vectors.len(), 1);
+        assert_eq!(vectors[0].values.len(), bars.len());
+        assert!(!vectors[0].values[19].is_nan());
+
+        assert!(chart.indicator_values("unknown").is_none());
+    }
+
+    #[test]
+    fn test_update_indicator_recomputes_only_that_indicator() {
+        let bars = sample_bars(120);
+        let mut chart = Chart::new(800, 600)
+            .bars(&bars)
+            .sma(20, "#2196F3")
+            .ema(10, "#FF9800");
+        let ema_before = chart.indicator_values("ema_10").unwrap()[0].values.clone();
+
+        chart
+            .update_indicator("sma_20", "period", PropertyValue::Integer(50))
+            .unwrap();
+
+        let sma = &chart.indicator_values("sma_20").unwrap()[0].values;
+        let expected = compute::sma(&compute::closes(&bars), 50);
+        assert!(sma[48].is_nan());
+        assert_eq!(sma[49..], expected[49..]);
+        assert_eq!(chart.config.indicators[0].name, "SMA 50");
+        let ema_after = &chart.indicator_values("ema_10").unwrap()[0].values;
+        assert_eq!(ema_after[9..], ema_before[9..]);
+
+        assert_eq!(
+            chart.update_indicator("missing", "period", PropertyValue::Integer(5)),
+            Err(IndicatorPropertyError::UnknownIndicator("missing".into()))
+        );
+        let period = chart.indicator_properties("sma_20").unwrap();
+        assert_eq!(period[0].value, PropertyValue::Integer(50));
+    }
+
+    #[test]
+    fn test_renderer_recomputes_changed_indicators() {
+        let bars = sample_bars(80);
+        let mut config = Chart::new(800, 600).bars(&bars).rsi(14).config;
+        config.indicators[0]
+            .set_property("period", PropertyValue::Integer(7))
+            .unwrap();
+
+        let renderer = ChartRenderer::new(&config, &bars);
+        let expected = compute::rsi(&compute::closes(&bars), 7);
+        assert_eq!(
+            renderer.config.indicators[0].vectors[0].values[7..],
+            expected[7..]
+        );
+        assert!(!renderer.config.indicators[0].needs_recompute);
+    }
+
+    #[test]
+    fn test_chart_from_json_config_computes_subpanes() {
+        let bars = sample_bars(80);
+        let config = ChartConfig::from_json(
+            r#"{"indicators": [
+                {"params": {"kind": "rsi", "period": 14}},
+                {"params": {"kind": "macd", "fast": 12, "slow": 26, "signal": 9}}
+            ]}"#,
+        )
+        .unwrap();
+        let renderer = ChartRenderer::new(&config, &bars);
+        let expected = compute::rsi(&compute::closes(&bars), 14);
+        assert_eq!(
+            renderer.config.indicators[0].vectors[0].values[14..],
+            expected[14..]
+        );
+        assert_eq!(renderer.config.indicators[1].vectors[2].values.len(), 80);
+
+        let svg = Chart::from_config(config).bars(&bars).render_svg();
+        assert!(svg.contains("RSI 14"));
+        assert!(svg.contains("MACD (12,26,9)"));
+    }
+
+    #[test]
+    fn test_time_format_override() {
+        // Daily bars would normally get day/month labels
+        let bars: Vec<Bar> = (0..40)
+            .map(|i| {
+                Bar::new(
+                    1_704_067_200 + i * 86_400 + 9 * 3600,
+                    100.0,
+                    101.0,
+                    99.0,
+                    100.5,
+                )
+            })
+            .collect();
+        let svg = Chart::new(800, 400)
+            .bars(&bars)
+            .time_format("%H:%M")
+            .render_svg();
+
+        let labels: Vec<&str> = svg
+            .split("text-anchor=\"middle\"")
+            .skip(1)
+            .filter_map(|chunk| chunk.split('>').nth(1))
+            .filter_map(|rest| rest.split('<').next())
+            .collect();
+        assert!(!labels.is_empty());
+        assert!(labels.iter().all(|l| *l == "09:00"), "{:?}", labels);
+    }
+
+    #[test]
+    fn test_strategy_rendered_before_primitives() {
+        let bars = sample_bars(50);
+        let strategy = Strategy::new("bt", "Backtest")
+            .add_signal(Signal::new(
+                10.0,
+                100.0,
+                SignalVisual::dot("secondary", 4.0),
+            ))
+            .add_indicator(Indicator::rsi("bt_rsi", 14));
+        let mut chart = Chart::new(800, 600)
+            .bars(&bars)
+            .candlesticks()
+            .strategy(strategy)
+            .primitive(PrimitiveConfig::horizontal_line(100.0).with_color("#abcdef"));
+        let svg = chart.render_svg();
+
+        // Theme slot names resolve to concrete colors
+        let dot = svg.find("#ff9800").expect("strategy signal color");
+        let primitive = svg.find("#abcdef").expect("user primitive color");
+        assert!(dot < primitive);
+
+        // The strategy's RSI gets its own subpane with a price scale
+        chart.config.strategy.as_mut().unwrap().visible = false;
+        let hidden = chart.render_svg();
+        assert!(!hidden.contains("#ff9800"));
+        assert!(svg.matches("<text").count() > hidden.matches("<text").count());
+    }
+
+    #[test]
+    fn test_density_sizes_scales_and_grid() {
+        let bars = sample_bars(60);
+        // (width, height, density, scale width, strip height, grid lines,
+        //  price label font, time label font, tick length)
+        let cases = [
+            (
+                300,
+                200,
+                ChartDensity::Compact,
+                50.0,
+                20.0,
+                3,
+                "9.0",
+                "8.0",
+                3.0,
+            ),
+            (
+                3840,
+                2160,
+                ChartDensity::Comfortable,
+                100.0,
+                42.0,
+                18,
+                "16.0",
+                "15.0",
+                6.0,
+            ),
+        ];
+        for (width, height, density, scale_w, strip_h, grid, price_font, time_font, tick) in cases {
+            let svg = Chart::new(width, height)
+                .bars(&bars)
+                .candlesticks()
+                .grid(true)
+                .density(density)
+                .render_svg();
+            let chart_width = width as f64 - scale_w;
+            let chart_height = height as f64 - strip_h;
+
+            let grid_lines = svg
+                .lines()
+                .filter(|l| {
+                    l.starts_with(r#"<line x1="0.00""#)
+                        && l.contains(&format!(r#"x2="{:.2}""#, chart_width))
+                        && !l.contains(&format!(r#"y1="{:.2}""#, chart_height + 0.5))
+                })
+                .count();
+            assert_eq!(grid_lines, grid, "{:?} grid", density);
+
+            // Price ticks span the tick length, labels sit two pixels past them
+            let price_labels: Vec<&str> = svg
+                .lines()
+                .filter(|l| l.starts_with(&format!(r#"<text x="{:.2}""#, chart_width + tick + 2.0)))
+                .collect();
+            assert!(price_labels.len() >= 3, "{:?} price labels", density);
+            assert!(
+                price_labels
+                    .iter()
+                    .all(|l| l.contains(&format!(r#"font-size="{}""#, price_font)))
+            );
+            let price_ticks = svg
+                .lines()
+                .filter(|l| {
+                    l.starts_with(&format!(r#"<line x1="{:.2}""#, chart_width))
+                        && l.contains(&format!(r#"x2="{:.2}""#, chart_width + tick))
+                })
+                .count();
+            assert_eq!(price_ticks, price_labels.len());
+
+            // Time labels below their ticks, a pixel smaller than price labels
+            let time_labels: Vec<&str> = svg
+                .lines()
+                .filter(|l| {
+                    l.starts_with("<text")
+                        && l.contains(&format!(r#"y="{:.2}""#, chart_height + tick + 2.0))
+                })
+                .collect();
+            assert!(time_labels.len() >= 3, "{:?} time labels", density);
+            assert!(
+                time_labels
+                    .iter()
+                    .all(|l| l.contains(&format!(r#"font-size="{}""#, time_font)))
+            );
+
+            // Multichart cells size their scale strips and labels the same way
+            let (config, cell_bars) = Chart::new(width, height)
+                .bars(&bars)
+                .candlesticks()
+                .density(density)
+                .build();
+            let svg = MultichartRenderer::new(&MultichartLayout::single(), width, height)
+                .chart(&config, &cell_bars)
+                .render_svg();
+            let price_labels = svg
+                .lines()
+                .filter(|l| l.starts_with(&format!(r#"<text x="{:.2}""#, chart_width + tick + 2.0)))
+                .count();
+            assert!(price_labels >= 3, "{:?} cell price labels", density);
+            let time_labels: Vec<&str> = svg
+                .lines()
+                .filter(|l| {
+                    l.starts_with("<text")
+                        && l.contains(&format!(r#"y="{:.2}""#, chart_height + tick + 2.0))
+                })
+                .collect();
+            assert!(time_labels.len() >= 2, "{:?} cell time labels", density);
+            assert!(
+                time_labels
+                    .iter()
+                    .all(|l| l.contains(&format!(r#"font-size="{}""#, time_font)))
+            );
+        }
+
+        // Normal keeps the fixed scale sizes
+        let sizing = ChartConfig::default().sizing();
+        assert_eq!(sizing.price_scale_width, PRICE_SCALE_WIDTH);
+        assert_eq!(sizing.time_scale_height, TIME_SCALE_HEIGHT);
+        assert_eq!(sizing.grid_count(570.0), 8);
+    }
+
+    #[test]
+    fn test_price_labels_do_not_overlap_in_short_subpane() {
+        let bars = sample_bars(120);
+        // 570px plot height: 54/570 of it minus the 4px gap leaves a 50px pane
+        let mut chart = Chart::new(800, 600)
+            .bars(&bars)
+            .candlesticks()
+            .macd(12, 26, 9);
+        chart.config.indicators[0].placement = IndicatorPlacement::subpane(54.0 / 570.0);
+        let svg = chart.render_svg();
+
+        let label_x = format!("<text x=\"{:.2}\"", 800.0 - PRICE_SCALE_WIDTH + 6.0);
+        let mut labels: Vec<(f64, f64)> = svg
+            .split(&label_x)
+            .skip(1)
+            .map(|rest| {
+                let attr = |name: &str| -> f64 {
+                    let start = rest.find(&format!("{}=\"", name)).unwrap() + name.len() + 2;
+                    let end = start + rest[start..].find('"').unwrap();
+                    rest[start..end].parse().unwrap()
+                };
+                (attr("y"), attr("font-size"))
             })
-            .unwrap_or((
-                config.theme.up_color.clone(),
-                config.theme.down_color.clone(),
-                0.8,
-            ));
+            .collect();
+        labels.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+        assert!(labels.iter().any(|&(y, _)| y > 520.0), "subpane has labels");
+        for w in labels.windows(2) {
+            let font_size = w[0].1.max(w[1].1);
+            assert!(
+                w[1].0 - w[0].0 >= font_size,
+                "labels at {} and {} overlap",
+                w[0].0,
+                w[1].0
+            );
+        }
+    }
 
-        let up = Color::from_css(&up_color).unwrap_or(Color::rgb(38, 166, 154));
-        let down = Color::from_css(&down_color).unwrap_or(Color::rgb(239, 83, 80));
+    /// Height of the first clip rect, i.e. the main price area
+    fn price_clip_height(svg: &str) -> f64 {
+        let clip = &svg[svg.find("<clipPath").unwrap()..];
+        let start = clip.find("height=\"").unwrap() + 8;
+        let end = start + clip[start..].find('"').unwrap();
+        clip[start..end].parse().unwrap()
+    }
 
-        let bar_w = 3.0 * bar_width_ratio;
+    /// Lowest end of any vertical line inside the plot area (candle wicks)
+    fn lowest_wick(svg: &str) -> f64 {
+        let attr = |line: &str, name: &str| -> f64 {
+            let start = line.find(&format!(" {}=\"", name)).unwrap() + name.len() + 3;
+            let end = start + line[start..].find('"').unwrap();
+            line[start..end].parse().unwrap()
+        };
+        svg.split("<line")
+            .skip(1)
+            .filter(|l| {
+                let x = attr(l, "x1");
+                x == attr(l, "x2") && x < 730.0 && attr(l, "y1") < 570.0
+            })
+            .map(|l| attr(l, "y1").max(attr(l, "y2")))
+            .fold(0.0, f64::max)
+    }
 
-        for (i, bar) in bars.iter().enumerate() {
-            let vol = bar.volume;
-            if vol.is_nan() || vol <= 0.0 {
-                continue;
-            }
+    fn volume_chart(placement: IndicatorPlacement) -> Chart {
+        let bars = sample_bars(60);
+        let mut chart = Chart::new(800, 600).bars(&bars).candlesticks().volume();
+        chart.config.indicators[0].placement = placement;
+        chart
+    }
 
-            let x = bar_to_x(i);
-            let y = value_to_y(vol);
-            let bar_h = (y_bottom - y).max(1.0);
+    #[test]
+    fn test_overlay_bottom_reserved_space() {
+        // 570px main pane, 20% volume strip
+        let shared = volume_chart(IndicatorPlacement::overlay_bottom(0.2)).render_svg();
+        assert_eq!(price_clip_height(&shared), 570.0);
+
+        let reserved =
+            volume_chart(IndicatorPlacement::overlay_bottom(0.2).with_reserved_space(true))
+                .render_svg();
+        assert_eq!(price_clip_height(&reserved), 456.0);
+
+        // Candle wicks are mapped into the reduced area
+        assert!(lowest_wick(&shared) > 456.0);
+        assert!(lowest_wick(&reserved) <= 456.0);
+
+        let capped = volume_chart(
+            IndicatorPlacement::overlay_bottom(0.2)
+                .with_reserved_space(true)
+                .with_max_height(50.0),
+        )
+        .render_svg();
+        assert_eq!(price_clip_height(&capped), 520.0);
+    }
 
-            // Color based on bar direction
-            let color = if bar.close >= bar.open { up } else { down };
+    #[test]
+    fn test_overlay_bottom_opacity() {
+        let svg =
+            volume_chart(IndicatorPlacement::overlay_bottom(0.2).with_opacity(0.5)).render_svg();
+        assert!(svg.contains("<g opacity=\"0.50\">"));
 
-            backend.fill_rect(Rect::new(x - bar_w / 2.0, y, bar_w, bar_h), color);
-        }
+        let opaque = volume_chart(IndicatorPlacement::overlay_bottom(0.2)).render_svg();
+        assert!(!opaque.contains("<g opacity="));
     }
 
-    fn render_time_scale_simple(
-        backend: &mut SvgBackend,
-        config: &ChartConfig,
-        bars: &[Bar],
-        x_offset: f64,
-        y: f64,
-        width: f64,
-        bar_spacing: f64,
-    ) {
-        let bg_color = Color::from_css(&config.theme.background).unwrap_or(Color::rgb(19, 23, 34));
-        let border_color =
-            Color::from_css(&config.theme.grid_color).unwrap_or(Color::rgb(42, 46, 57));
-        let text_color =
-            Color::from_css(&config.theme.text_color).unwrap_or(Color::rgb(180, 180, 180));
+    #[test]
+    fn test_multichart_overlay_bottom_reserved_space() {
+        let render = |reserve: bool| {
+            let (config, bars) =
+                volume_chart(IndicatorPlacement::overlay_bottom(0.25).with_reserved_space(reserve))
+                    .build();
+            let layout = MultichartLayout::single();
+            MultichartRenderer::new(&layout, 800, 600)
+                .chart(&config, &bars)
+                .render_svg()
+        };
+        let shared = price_clip_height(&render(false));
+        let reserved = price_clip_height(&render(true));
+        // Clip edges snap to whole pixels
+        assert!((reserved - shared * 0.75).abs() <= 1.0);
+    }
 
-        backend.fill_rect(
-            Rect::new(x_offset, y, width + PRICE_SCALE_WIDTH, TIME_SCALE_HEIGHT),
-            bg_color,
-        );
-        backend.line(
-            Point::new(x_offset, y),
-            Point::new(x_offset + width, y),
-            &LineStyle::solid(border_color, 1.0),
+    #[test]
+    fn test_multichart_locate_inverts_cell_transform() {
+        let layout = MultichartLayout::quad();
+        let (first, first_bars) = Chart::new(400, 300).bars(&sample_bars(40)).build();
+        // A different price level and bar count, so the cell's own scale counts
+        let mut raised = sample_bars(80);
+        for bar in &mut raised {
+            bar.open += 500.0;
+            bar.high += 500.0;
+            bar.low += 500.0;
+            bar.close += 500.0;
+        }
+        let (second, second_bars) = Chart::new(400, 300).bars(&raised).rsi(14).build();
+        let multichart = MultichartRenderer::new(&layout, 800, 600)
+            .chart(&first, &first_bars)
+            .chart(&second, &second_bars);
+
+        let bounds = layout.calculate_bounds(800.0, 600.0);
+        let (second_id, cell) = bounds[1];
+        let area = CellPriceArea::new(&second, &second_bars, &cell);
+        let (x, y) = (area.axis.x(30), area.price_to_y(raised[30].close));
+
+        let (id, bar, price) = multichart.locate(x, y).expect("inside the second cell");
+        assert_eq!(id, second_id);
+        assert_eq!(bar, 30);
+        assert!((price - raised[30].close).abs() < 1e-6);
+        // Nearest bar from a little off-center
+        assert_eq!(
+            multichart.locate(x + area.axis.spacing * 0.3, y).unwrap().1,
+            30
         );
 
-        let text_style = TextStyle {
-            color: text_color,
-            font_size: 9.0,
-            font_weight: FontWeight::Normal,
-            align: TextAlign::Center,
-            baseline: TextBaseline::Top,
-            ..Default::default()
-        };
+        // Gap between cells, the price scale, the RSI subpane, an empty cell
+        let (_, left) = bounds[0];
+        assert_eq!(multichart.locate(left.x + left.width + 0.5, y), None);
+        assert_eq!(multichart.locate(cell.x + cell.width - 5.0, y), None);
+        assert_eq!(multichart.locate(x, cell.y + cell.height - 40.0), None);
+        let (_, empty) = bounds[2];
+        assert_eq!(multichart.locate(empty.x + 50.0, empty.y + 50.0), None);
+    }
 
-        let min_spacing = 50.0;
-        let step = (min_spacing / bar_spacing).ceil() as usize;
-        let step = step.max(1);
+    #[test]
+    fn test_histogram_width_follows_bar_spacing() {
+        let bars = sample_bars(60);
+        let widths = |svg: &str, color: &str| -> Vec<f64> {
+            svg.lines()
+                .filter(|l| l.starts_with("<rect") && l.contains(&format!("fill=\"{}\"", color)))
+                .filter_map(|l| l.split(" width=\"").nth(1)?.split('"').next()?.parse().ok())
+                .collect()
+        };
+        let chart = |width: u32| {
+            let mut chart = Chart::new(width, 500).bars(&bars).macd(12, 26, 9);
+            chart.config.indicators[0].vectors[2].style =
+                VectorStyle::histogram_colored("#ff00aa", "#00aaff");
+            chart
+        };
 
-        let mut prev_ts: Option<i64> = None;
-        for i in (0..bars.len()).step_by(step) {
-            let ts = bars[i].timestamp;
-            let x = x_offset + bar_spacing * (i as f64 + 0.5);
-            if x < x_offset + 5.0 || x > x_offset + width - 20.0 {
-                prev_ts = Some(ts);
-                continue;
+        for width in [400, 800, 1600] {
+            let spacing = (width as f64 - PRICE_SCALE_WIDTH) / bars.len() as f64;
+            let expected = (spacing * 0.8).max(1.0);
+            let single = chart(width).render_svg();
+            let (config, bars) = chart(width).build();
+            let layout = MultichartLayout::single();
+            let cell = MultichartRenderer::new(&layout, width, 500)
+                .chart(&config, &bars)
+                .render_svg();
+
+            // Columns match the candles' width, give or take pixel snapping
+            for svg in [&single, &cell] {
+                let columns: Vec<f64> = ["#ff00aa", "#00aaff"]
+                    .iter()
+                    .flat_map(|color| widths(svg, color))
+                    .collect();
+                assert!(!columns.is_empty());
+                for w in columns {
+                    assert!((w - expected).abs() <= 1.0, "{width}px: {w} vs {expected}");
+                }
             }
+        }
 
-            let weight = TickMarkWeight::from_timestamp(ts, prev_ts);
-            if weight >= TickMarkWeight::Hour || (i % (step * 2)) == 0 {
-                backend.line(
-                    Point::new(x, y),
-                    Point::new(x, y + 3.0),
-                    &LineStyle::solid(border_color, 1.0),
-                );
-                let label = format_time_by_weight(ts, weight);
-                backend.text(&label, Point::new(x, y + 4.0), &text_style);
+        // Proportional spacing with a ten-day gap after the first bar:
+        // volume columns keep the slot width rather than spanning the gap
+        let mut daily = sample_bars(20);
+        for (i, bar) in daily.iter_mut().enumerate() {
+            let day = if i == 0 { 0 } else { i as i64 + 10 };
+            bar.timestamp = 1_700_000_000 + day * DAY;
+        }
+        let mut volume = Indicator::volume("v");
+        volume.vectors[0].style = VectorStyle::histogram_colored("#ff00aa", "#00aaff");
+        let (config, daily) = Chart::new(800, 500)
+            .bars(&daily)
+            .time_spacing(TimeSpacing::Proportional)
+            .indicator(volume)
+            .build();
+        let expected = (800.0 - PRICE_SCALE_WIDTH) / 30.0 * 0.8;
+        let single = ChartRenderer::new(&config, &daily).render_svg();
+        let cell = MultichartRenderer::new(&MultichartLayout::single(), 800, 500)
+            .chart(&config, &daily)
+            .render_svg();
+        for svg in [&single, &cell] {
+            let columns: Vec<f64> = ["#ff00aa", "#00aaff"]
+                .iter()
+                .flat_map(|color| widths(svg, color))
+                .collect();
+            assert_eq!(columns.len(), 20);
+            for w in columns {
+                assert!((w - expected).abs() <= 1.0, "{w} vs {expected}");
             }
-            prev_ts = Some(ts);
         }
     }
-}
 
-// =============================================================================
-// SvgRenderContext - Adapter for primitive rendering
-// =============================================================================
+    #[test]
+    fn test_vectors_render_alike_in_chart_and_multichart_cell() {
+        let bars = sample_bars(40);
+        // Around the closes, so the overlay shares the main price scale
+        let wave: Vec<f64> = bars
+            .iter()
+            .enumerate()
+            .map(|(i, bar)| bar.close + (i as f64 / 4.0).sin())
+            .collect();
+        let mut gapped = wave.clone();
+        gapped[15..20].fill(f64::NAN);
+        let deltas: Vec<f64> = (0..40).map(|i| (i as f64 / 4.0).sin()).collect();
+        let vectors = vec![
+            IndicatorVector::new("Line", VectorStyle::dashed("#a00001", 1.0))
+                .with_values(gapped.clone()),
+            IndicatorVector::new("Area", VectorStyle::area("#a00002", 0.3)).with_values(gapped),
+            IndicatorVector::new("Step", VectorStyle::step("#a00003", 1.0))
+                .with_values(wave.iter().map(|v| v.round()).collect()),
+            IndicatorVector::new(
+                "Dots",
+                VectorStyle::Dots {
+                    color: "#a00004".to_string(),
+                    radius: 2.0,
+                    filled: false,
+                    down_color: None,
+                },
+            )
+            .with_values(wave),
+            IndicatorVector::new("Hist", VectorStyle::histogram_colored("#a00005", "#a00006"))
+                .with_values(deltas),
+        ];
+        let chart = || {
+            Chart::new(800, 600)
+                .bars(&bars)
+                .custom_indicator(
+                    "Shapes",
+                    IndicatorPlacement::overlay(),
+                    vectors.clone(),
+                    vec![],
+                )
+                .legend(false)
+        };
+        let drawn = |svg: &str| -> Vec<String> {
+            svg.lines()
+                .filter(|l| (1..=6).any(|i| l.contains(&format!("#a0000{i}"))))
+                .map(str::to_string)
+                .collect()
+        };
 
-use crate::render::engine::PathBuilder;
+        let single = drawn(&chart().render_svg());
+        let (config, cell_bars) = chart().build();
+        let layout = MultichartLayout::horizontal_split().with_gap(0.0);
+        let cells = drawn(
+            &MultichartRenderer::new(&layout, 1600, 600)
+                .chart(&config, &cell_bars)
+                .chart(&config, &cell_bars)
+                .render_svg(),
+        );
 
-/// Adapter to use SvgBackend with primitive RenderContext trait
-struct SvgRenderContext<'a, F1, F2>
-where
-    F1: Fn(usize) -> f64,
-    F2: Fn(f64) -> f64,
-{
-    backend: &'a mut SvgBackend,
-    bar_to_x: &'a F1,
-    price_to_y: &'a F2,
-    dpr: f64,
-    viewport_width: f64,
-    viewport_height: f64,
-    // Drawing state
-    path_builder: PathBuilder,
-    stroke_color: Color,
-    stroke_width: f64,
-    fill_color: Color,
-    dash_pattern: Vec<f64>,
-    global_alpha: f64,
-    font_size: f64,
-    text_color: Color,
-}
+        // Every style draws, step and unfilled dots included
+        for i in 1..=6 {
+            assert!(single.iter().any(|l| l.contains(&format!("#a0000{i}"))));
+        }
+        assert!(single.iter().any(|l| l.contains("stroke-dasharray=\"4")));
+        assert!(
+            single
+                .iter()
+                .any(|l| l.starts_with("<circle") && l.contains("fill=\"none\""))
+        );
 
-impl<'a, F1, F2> SvgRenderContext<'a, F1, F2>
-where
-    F1: Fn(usize) -> f64,
-    F2: Fn(f64) -> f64,
-{
-    fn new(
-        backend: &'a mut SvgBackend,
-        bar_to_x: &'a F1,
-        price_to_y: &'a F2,
-        dpr: f64,
-        viewport_width: f64,
-        viewport_height: f64,
-    ) -> Self {
-        Self {
-            backend,
-            bar_to_x,
-            price_to_y,
-            dpr,
-            viewport_width,
-            viewport_height,
-            path_builder: PathBuilder::new(),
-            stroke_color: Color::from_css("#2196F3").unwrap_or(Color::WHITE),
-            stroke_width: 2.0,
-            fill_color: Color::TRANSPARENT,
-            dash_pattern: Vec::new(),
-            global_alpha: 1.0,
-            font_size: 12.0,
-            text_color: Color::WHITE,
+        // The left cell matches the chart exactly, the right one shifted
+        assert_eq!(cells.len(), 2 * single.len());
+        let (left, right) = cells.split_at(single.len());
+        assert_eq!(left, &single[..]);
+        let rect_xs = |lines: &[String]| -> Vec<f64> {
+            lines
+                .iter()
+                .filter(|l| l.starts_with("<rect"))
+                .filter_map(|l| l.split(" x=\"").nth(1)?.split('"').next()?.parse().ok())
+                .collect()
+        };
+        let (xs, shifted) = (rect_xs(&single), rect_xs(right));
+        assert_eq!(xs.len(), shifted.len());
+        for (x, shifted) in xs.iter().zip(&shifted) {
+            assert!((shifted - x - 800.0).abs() < 0.01, "{x} vs {shifted}");
         }
     }
-}
 
-impl<'a, F1, F2> RenderContext for SvgRenderContext<'a, F1, F2>
-where
-    F1: Fn(usize) -> f64,
-    F2: Fn(f64) -> f64,
-{
-    fn chart_width(&self) -> f64 {
-        self.viewport_width
+    #[test]
+    fn test_self_intersecting_path_fill_rule() {
+        let bars = sample_bars(50);
+        // Pentagram: each vertex connects to the one two steps ahead
+        let star: Vec<(f64, f64)> = [0, 2, 4, 1, 3, 0]
+            .iter()
+            .map(|&i| {
+                let angle = std::f64::consts::TAU * i as f64 / 5.0;
+                (25.0 + 10.0 * angle.sin(), 100.0 + 5.0 * angle.cos())
+            })
+            .collect();
+        let render = |rule: Option<PrimitiveFillRule>| {
+            let mut config = PrimitiveConfig::new("path", star.clone()).with_fill_color("#ff9800");
+            config.fill_rule = rule;
+            Chart::new(800, 600)
+                .bars(&bars)
+                .primitive(config)
+                .render_svg()
+        };
+
+        let nonzero = render(None);
+        let evenodd = render(Some(PrimitiveFillRule::EvenOdd));
+        let filled = |svg: &str| {
+            svg.lines()
+                .find(|l| l.contains(r##"fill="#ff9800""##))
+                .map(str::to_string)
+        };
+        let (nonzero_fill, evenodd_fill) = (filled(&nonzero).unwrap(), filled(&evenodd).unwrap());
+        assert!(!nonzero_fill.contains("fill-rule"));
+        assert!(evenodd_fill.contains(r#"fill-rule="evenodd""#));
+        assert_ne!(nonzero, evenodd);
+        // Same outline, only the rule differs
+        assert_eq!(
+            nonzero_fill,
+            evenodd_fill.replace(r#" fill-rule="evenodd""#, "")
+        );
+        assert_eq!(
+            render(Some(PrimitiveFillRule::NonZero)),
+            nonzero,
+            "nonzero is the default"
+        );
     }
 
-    fn chart_height(&self) -> f64 {
-        self.viewport_height
+    #[test]
+    fn test_fib_level_labels() {
+        let bars = sample_bars(60);
+        // The 0.618 level of a 61.65 -> 161.65 move sits at 123.45
+        let fib = || PrimitiveConfig::fib_retracement((10.0, 61.65), (40.0, 161.65));
+        let render = |chart: Chart| chart.bars(&bars).render_svg();
+
+        let plain = render(Chart::new(800, 600).primitive(fib()));
+        assert!(!plain.contains(">0.618"));
+
+        let prices =
+            render(Chart::new(800, 600).primitive(fib().with_fib_labels(FibLabelMode::RatioPrice)));
+        assert!(prices.contains(">0.618  123.45<"));
+        assert!(prices.contains(">0  61.65<"));
+
+        // The chart-wide mode applies unless the drawing sets its own
+        let percents = render(
+            Chart::new(800, 600)
+                .fib_labels(FibLabelMode::RatioPercent)
+                .primitive(fib()),
+        );
+        assert!(percents.contains(">0.618  61.8%<"));
+        assert!(percents.contains(">1  100%<"));
+        let ratio_only = render(
+            Chart::new(800, 600)
+                .fib_labels(FibLabelMode::RatioPercent)
+                .primitive(fib().with_fib_labels(FibLabelMode::RatioOnly)),
+        );
+        assert!(ratio_only.contains(">0.618<"));
+        assert!(!ratio_only.contains("61.8%"));
     }
 
-    fn bar_to_x(&self, bar: f64) -> f64 {
-        // Interpolate between bar indices for sub-bar precision
-        let bar_floor = bar.floor() as usize;
-        let bar_ceil = bar.ceil() as usize;
-        let frac = bar - bar.floor();
-
-        let x_floor = (self.bar_to_x)(bar_floor);
-        if bar_floor == bar_ceil || frac < 0.001 {
-            x_floor
-        } else {
-            let x_ceil = (self.bar_to_x)(bar_ceil);
-            x_floor + (x_ceil - x_floor) * frac
-        }
+    #[test]
+    fn test_regression_trend_fits_enclosed_bars() {
+        let bars_with = |noise: f64| -> Vec<Bar> {
+            (0..40)
+                .map(|i| {
+                    let close = 100.0 + i as f64 + if i % 2 == 0 { noise } else { -noise };
+                    Bar {
+                        timestamp: 1_700_000_000 + i * 3600,
+                        open: close,
+                        high: close + 1.0,
+                        low: close - 1.0,
+                        close,
+                        volume: 1.0,
+                    }
+                })
+                .collect()
+        };
+        // Start y of the upper band, lower band and center line; the anchor
+        // prices are ignored once there are bars to fit
+        let channel = |bars: &[Bar], multiplier: f64, deviation: BandDeviation| -> Vec<f64> {
+            let config = PrimitiveConfig::regression_trend((10.0, 0.0), (30.0, 0.0))
+                .with_color("#9c27b0")
+                .with_band(multiplier, deviation);
+            Chart::new(800, 600)
+                .bars(bars)
+                .primitive(config)
+                .render_svg()
+                .lines()
+                .filter(|l| l.starts_with("<path") && l.contains(r##"stroke="#9c27b0""##))
+                .map(|l| l.split(' ').nth(2).unwrap().parse::<f64>().unwrap())
+                .collect()
+        };
+
+        let linear = channel(&bars_with(0.0), 2.0, BandDeviation::StdDev);
+        assert_eq!(linear.len(), 3);
+        assert!(linear.iter().all(|&y| y == linear[2]));
+
+        let noisy = bars_with(2.0);
+        let two = channel(&noisy, 2.0, BandDeviation::StdDev);
+        let (upper, lower, center) = (two[0], two[1], two[2]);
+        assert!(upper < center && center < lower);
+        assert!(((upper + lower) / 2.0 - center).abs() <= 1.0);
+        // Bands scale with the multiplier, and the standard error is wider
+        let one = channel(&noisy, 1.0, BandDeviation::StdDev);
+        assert!(((lower - upper) - 2.0 * (one[1] - one[0])).abs() <= 2.0);
+        let error = channel(&noisy, 2.0, BandDeviation::StdError);
+        assert!(error[1] - error[0] > lower - upper);
     }
 
-    fn price_to_y(&self, price: f64) -> f64 {
-        (self.price_to_y)(price)
+    #[test]
+    fn test_divergence_links_price_and_indicator_panes() {
+        let bars = sample_bars(60);
+        let low = bars[30].low;
+        let render = |rsi_value: f64, indicator_id: &str| -> String {
+            Chart::new(800, 600)
+                .bars(&bars)
+                .rsi(14)
+                .primitive(PrimitiveConfig::indicator_divergence(
+                    (30.0, low),
+                    (45.0, rsi_value),
+                    indicator_id,
+                    DivergenceType::RegularBearish,
+                ))
+                .render_svg()
+        };
+        // Connecting line as ((x1, y1), (x2, y2))
+        let link = |svg: &str| -> Option<((f64, f64), (f64, f64))> {
+            let line = svg.lines().find(|l| {
+                l.starts_with("<path") && l.contains(r##"stroke="#ef5350" stroke-width="2.00""##)
+            })?;
+            let d: Vec<f64> = line.split('"').nth(1)?[1..]
+                .split([' ', 'L'])
+                .filter_map(|v| v.parse().ok())
+                .collect();
+            Some(((d[0], d[1]), (d[2], d[3])))
+        };
+
+        let svg = render(30.0, "rsi_14");
+        let ((x1, y1), (x2, y2)) = link(&svg).unwrap();
+        let (chart, _) = Chart::new(800, 600).bars(&bars).build();
+        let axis = BarAxis::new(&bars, &chart, 730.0, 0.0);
+        assert!((x1 - axis.x(30)).abs() <= 1.0 && (x2 - axis.x(45)).abs() <= 1.0);
+
+        // The price pivot sits in the price pane, the RSI pivot in the RSI
+        // subpane under it, and the line is clipped to both panes together
+        let (main_height, _) = main_pane_heights(
+            &[],
+            &[&Indicator::rsi("rsi_14", 14)],
+            600.0 - TIME_SCALE_HEIGHT,
+        );
+        assert!(y1 < main_height && y2 > main_height);
+        assert!(svg.contains(&format!(
+            r#"width="730.00" height="{:.2}""#,
+            600.0 - TIME_SCALE_HEIGHT
+        )));
+        let ((_, _), (_, y2_high)) = link(&render(70.0, "rsi_14")).unwrap();
+        assert!(y2_high < y2 && y2_high > main_height);
+
+        // Pivot markers and the downward arrow of a bearish divergence
+        let fills = svg
+            .lines()
+            .filter(|l| l.starts_with("<path") && l.contains(r##"fill="#ef5350""##))
+            .count();
+        assert_eq!(fills, 3);
+
+        // No subpane to resolve the indicator pivot on: nothing is drawn
+        assert!(link(&render(30.0, "macd_12_26_9")).is_none());
     }
 
-    fn dpr(&self) -> f64 {
-        self.dpr
+    #[test]
+    fn test_selected_primitive_draws_halo_and_handles() {
+        let bars = sample_bars(50);
+        let line = |id: &str, color: &str| {
+            PrimitiveConfig::trend_line((10.0, 100.0), (40.0, 110.0))
+                .with_id(id)
+                .with_color(color)
+                .with_line_width(2.0)
+        };
+        let svg = Chart::new(800, 600)
+            .bars(&bars)
+            .primitive(line("picked", "#e91e63"))
+            .primitive(line("other", "#00bcd4"))
+            .select_primitive("picked")
+            .render_svg();
+        let lines_of =
+            |color: &str| -> Vec<&str> { svg.lines().filter(|l| l.contains(color)).collect() };
+        let (selected, unselected) = (lines_of("#e91e63"), lines_of("#00bcd4"));
+        let widest = |lines: &[&str]| {
+            lines
+                .iter()
+                .filter_map(|l| l.split("stroke-width=\"").nth(1))
+                .filter_map(|w| w.split('"').next()?.parse::<f64>().ok())
+                .fold(0.0, f64::max)
+        };
+
+        // Halo: the line color, translucent and 3px wider on each side
+        assert_eq!(widest(&unselected), 2.0);
+        assert_eq!(widest(&selected), 8.0);
+        assert!(
+            selected
+                .iter()
+                .any(|l| l.contains(r#"stroke-opacity="0.25""#))
+        );
+
+        // One white-filled, line-outlined handle per control point
+        let handles = svg.lines().filter(|l| l.contains(r##"fill="#ffffff""##));
+        assert_eq!(handles.count(), 2);
+        let outlines = selected.iter().filter(|l| l.contains(" A")).count();
+        assert_eq!(outlines, 2);
+        assert!(!unselected.iter().any(|l| l.contains(" A")));
+
+        // Selection is by id and can be cleared
+        let mut chart = Chart::new(800, 600)
+            .bars(&bars)
+            .primitive(line("picked", "#e91e63"))
+            .select_primitive("picked");
+        chart.set_primitive_selected("picked", false);
+        assert!(!chart.render_svg().contains(r##"fill="#ffffff""##));
+
+        // A shape's corners don't outline it: the halo boxes them instead
+        let svg = Chart::new(800, 600)
+            .bars(&bars)
+            .primitive(
+                PrimitiveConfig::rectangle((10.0, 100.0), (40.0, 110.0))
+                    .with_id("box")
+                    .with_color("#e91e63")
+                    .with_line_width(2.0),
+            )
+            .select_primitive("box")
+            .render_svg();
+        let halos: Vec<&str> = svg
+            .lines()
+            .filter(|l| l.contains(r#"stroke-opacity="0.25""#))
+            .collect();
+        assert_eq!(halos.len(), 1);
+        assert!(halos[0].starts_with("<rect"));
+        assert_eq!(widest(&halos), 8.0);
     }
 
-    fn set_stroke_color(&mut self, color: &str) {
-        self.stroke_color = Color::from_css(color).unwrap_or(Color::WHITE);
+    #[test]
+    fn test_psar_and_supertrend_color_by_trend() {
+        // Up, down, then up again: both indicators flip twice
+        let bars: Vec<Bar> = (0..60)
+            .map(|i| {
+                let close = match i {
+                    0..20 => 100.0 + i as f64,
+                    20..40 => 120.0 - (i - 20) as f64 * 1.5,
+                    _ => 90.0 + (i - 40) as f64 * 1.5,
+                };
+                Bar {
+                    timestamp: 1_700_000_000 + i * 3600,
+                    open: close,
+                    high: close + 1.0,
+                    low: close - 1.0,
+                    close,
+                    volume: 1.0,
+                }
+            })
+            .collect();
+        let (highs, lows, closes) = (
+            compute::highs(&bars),
+            compute::lows(&bars),
+            compute::closes(&bars),
+        );
+        let svg = Chart::new(800, 600)
+            .bars(&bars)
+            .psar(0.02, 0.2)
+            .supertrend_with_fill(5, 2.0, true)
+            .render_svg();
+        let (up, down) = ("#26a69a", "#ef5350");
+
+        let (_, long) = compute::psar(&highs, &lows, 0.02, 0.2);
+        assert_eq!(long[1..].windows(2).filter(|w| w[0] != w[1]).count(), 2);
+        let dots = |color: &str| {
+            svg.lines()
+                .filter(|l| l.starts_with("<circle") && l.contains(&format!("fill=\"{}\"", color)))
+                .count()
+        };
+        assert_eq!(dots(up), long[1..].iter().filter(|&&l| l).count());
+        assert_eq!(dots(down), long[1..].iter().filter(|&&l| !l).count());
+
+        // One horizontal step per pair of bars in the same trend, none
+        // across the two flips
+        let (line, rising) = compute::supertrend(&highs, &lows, &closes, 5, 2.0);
+        let first = line.iter().position(|v| v.is_finite()).unwrap();
+        let pairs = |trend: bool| {
+            rising[first..]
+                .windows(2)
+                .filter(|w| w[0] == trend && w[1] == trend)
+                .count()
+        };
+        assert_eq!(pairs(true) + pairs(false), 60 - first - 1 - 2);
+        let steps = |color: &str| {
+            svg.lines()
+                .filter(|l| l.starts_with("<line") && l.contains(&format!("stroke=\"{}\"", color)))
+                .filter(|l| {
+                    let attr = |name: &str| {
+                        let rest = l.split(&format!(" {}=\"", name)).nth(1).unwrap();
+                        rest.split('"').next().unwrap()
+                    };
+                    attr("y1") == attr("y2") && attr("x1") != attr("x2")
+                })
+                .count()
+        };
+        assert_eq!(steps(up), pairs(true));
+        assert_eq!(steps(down), pairs(false));
+
+        // The fill to the close changes color with each flip
+        let fills: Vec<&str> = svg
+            .lines()
+            .filter(|l| l.starts_with("<path") && l.contains("fill-opacity"))
+            .collect();
+        assert_eq!(fills.len(), 3);
+        assert!(fills[0].contains(up) && fills[1].contains(down) && fills[2].contains(up));
     }
 
-    fn set_stroke_width(&mut self, width: f64) {
-        self.stroke_width = width;
+    #[test]
+    fn test_zigzag_connects_pivots_with_labels() {
+        let bars = sample_bars(80);
+        let pivots = compute::zigzag_pivots(&compute::highs(&bars), &compute::lows(&bars), 2.0);
+        assert!(pivots.len() >= 3);
+
+        let svg = Chart::new(800, 600)
+            .bars(&bars)
+            .zigzag_labeled(2.0, PivotLabels::Change)
+            .render_svg();
+        let line = svg
+            .lines()
+            .find(|l| l.starts_with("<polyline") && l.contains("stroke=\"#ff9800\""))
+            .expect("zigzag line");
+        let points = line.split('"').nth(1).unwrap();
+        // One vertex per pivot: the NaN runs between pivots do not break it
+        assert_eq!(points.split(' ').count(), pivots.len());
+
+        let change = (pivots[1].value / pivots[0].value - 1.0) * 100.0;
+        assert!(svg.contains(&format!(">{:+.2}%<", change)));
     }
 
-    fn set_fill_color(&mut self, color: &str) {
-        self.fill_color = Color::from_css(color).unwrap_or(Color::TRANSPARENT);
+    #[test]
+    fn test_area_fills_down_to_its_base() {
+        // An equity curve that dips below zero
+        let closes = [20.0, 35.0, 10.0, -15.0, -30.0, -5.0, 25.0, 40.0];
+        let bars: Vec<Bar> = closes
+            .iter()
+            .enumerate()
+            .map(|(i, &c)| Bar::new(1_700_000_000 + i as i64 * 3600, c, c + 2.0, c - 2.0, c))
+            .collect();
+        let chart = || Chart::new(400, 300).bars(&bars).area();
+        let fills = |chart: Chart| -> Vec<(String, Vec<(f64, f64)>)> {
+            chart
+                .render_svg()
+                .lines()
+                .filter(|l| l.starts_with("<path d=") && !l.contains("fill=\"none\""))
+                .map(|l| {
+                    let coords: Vec<f64> = l
+                        .split('"')
+                        .nth(1)
+                        .unwrap()
+                        .split_whitespace()
+                        .filter(|t| *t != "Z")
+                        .map(|t| t.trim_start_matches(['M', 'L']).parse().unwrap())
+                        .collect();
+                    let points = coords.chunks(2).map(|c| (c[0], c[1])).collect();
+                    (l.to_string(), points)
+                })
+                .collect()
+        };
+
+        let (config, bars) = chart().area_base(0.0).build();
+        let renderer = ChartRenderer::new(&config, &bars);
+        let (low, high) = renderer.main_price_bounds();
+        let (_, height) = renderer.main_price_scale();
+        let zero_y = height - (0.0 - low) / (high - low) * height;
+        let near = |y: f64, target: f64| (y - target).abs() < 0.01;
+
+        // Without a base the fill drops to the pane bottom
+        let default = fills(chart());
+        assert_eq!(default.len(), 1);
+        assert!(near(default[0].1.last().unwrap().1, height));
+
+        // With one it closes along price 0, around the dip below it
+        let based = fills(chart().area_base(0.0));
+        assert_eq!(based.len(), 1);
+        let points = &based[0].1;
+        assert!(zero_y < height - 1.0);
+        assert!(near(points[0].1, zero_y) && near(points.last().unwrap().1, zero_y));
+        assert!(points.iter().any(|&(_, y)| y > zero_y + 10.0));
+
+        // A below color splits the fill at the base
+        let split = fills(chart().area_base(0.0).area_below_color("#ff0000"));
+        assert_eq!(split.len(), 2);
+        let (above, below) = (&split[0], &split[1]);
+        assert!(above.1.iter().all(|&(_, y)| y <= zero_y + 0.01));
+        assert!(below.0.contains("#ff0000"));
+        assert!(below.1.iter().all(|&(_, y)| y >= zero_y - 0.01));
+        assert!(below.1.iter().any(|&(_, y)| y > zero_y + 10.0));
+    }
+
+    #[test]
+    fn test_autoscale_opt_in_for_drawings_opt_out_for_overlays() {
+        let bars = sample_bars(50);
+        let bounds = |chart: Chart| {
+            let (config, bars) = chart.build();
+            ChartRenderer::new(&config, &bars).main_price_bounds()
+        };
+        let chart = || Chart::new(800, 600).bars(&bars);
+        let base = bounds(chart());
+        let far = 10_000.0;
+
+        let line = PrimitiveConfig::horizontal_line(far);
+        assert_eq!(bounds(chart().primitive(line.clone())), base);
+        assert!(bounds(chart().primitive(line.with_autoscale(true))).1 > far);
+
+        // Indicators are in by default
+        let projection = Indicator::new("projection", "Projection")
+            .overlay()
+            .add_vector(
+                IndicatorVector::new("Target", VectorStyle::line("#ff0000", 1.0))
+                    .with_values(vec![far; bars.len()]),
+            );
+        assert!(bounds(chart().indicator(projection.clone())).1 > far);
+        assert_eq!(
+            bounds(chart().indicator(projection.with_autoscale(false))),
+            base
+        );
     }
 
-    fn set_line_dash(&mut self, pattern: &[f64]) {
-        self.dash_pattern = pattern.to_vec();
-    }
+    #[test]
+    fn test_sparkline_fills_canvas_without_axes() {
+        let bars = sample_bars(30);
+        let series = |svg: &str| -> Vec<(f64, f64)> {
+            let path = svg.lines().find(|l| l.starts_with("<path d=\"M")).unwrap();
+            let d = path.split('"').nth(1).unwrap();
+            d.split(['M', 'L'])
+                .filter_map(|p| {
+                    let (x, y) = p.trim().split_once(' ')?;
+                    Some((x.parse().ok()?, y.parse().ok()?))
+                })
+                .collect()
+        };
 
-    fn begin_path(&mut self) {
-        self.path_builder.clear();
-    }
+        let svg = Chart::new(200, 50).bars(&bars).sparkline().render_svg();
+        let points = series(&svg);
+        assert_eq!(points.len(), 30);
+        // Half a bar slot in from either edge, top to bottom within the padding
+        let spacing = 200.0 / 30.0;
+        assert!((points[0].0 - spacing / 2.0).abs() < 0.01);
+        assert!((points[29].0 - (200.0 - spacing / 2.0)).abs() < 0.01);
+        let (top, bottom) = points
+            .iter()
+            .fold((f64::MAX, f64::MIN), |(t, b), p| (t.min(p.1), b.max(p.1)));
+        assert!((0.0..10.0).contains(&top) && bottom <= 50.0 && bottom > 40.0);
+        assert!(!svg.contains("<text") && !svg.contains("<line"));
 
-    fn move_to(&mut self, x: f64, y: f64) {
-        self.path_builder.move_to(Point::new(x, y));
+        // Scales take their room back when shown
+        let scaled = Chart::new(200, 50)
+            .bars(&bars)
+            .sparkline()
+            .price_scale(true)
+            .render_svg();
+        assert!(
+            series(&scaled)
+                .iter()
+                .all(|p| p.0 < 200.0 - PRICE_SCALE_WIDTH)
+        );
+        assert!(scaled.contains("<text"));
     }
 
-    fn line_to(&mut self, x: f64, y: f64) {
-        self.path_builder.line_to(Point::new(x, y));
+    #[test]
+    fn test_render_stats_counts_commands() {
+        let bars = bars_from_closes(&[100.0, 101.0, 100.5]);
+        let chart = Chart::new(400, 300)
+            .bars(&bars)
+            .grid(false)
+            .primitive(PrimitiveConfig::horizontal_line(100.5))
+            .signal(SignalConfig::buy(1, 100.0));
+        let (svg, stats) = chart.render_svg_with_stats();
+        assert_eq!(svg, chart.render_svg());
+        assert_eq!(stats.bars, 3);
+        assert_eq!(stats.svg_bytes, svg.len());
+
+        // Background, 3 candle bodies, last-price line and tag, the scales'
+        // ticks and labels, the scale corner cell, the primitive stroke and
+        // the signal arrow
+        let expected = [
+            ("FillPath", 1),
+            ("FillRect", 7),
+            ("Line", 14),
+            ("PopClip", 2),
+            ("PushClip", 2),
+            ("StrokePath", 1),
+            ("Text", 9),
+        ];
+        assert_eq!(stats.commands, expected.into_iter().collect());
+        assert_eq!(stats.command_count(), 36);
+        assert!(stats.timings.total() > std::time::Duration::ZERO);
     }
 
-    fn close_path(&mut self) {
-        self.path_builder.close();
+    #[test]
+    fn test_indicator_lines_and_brushes_draw_round_joins() {
+        let bars = sample_bars(50);
+        let stroke = |svg: &str, color: &str| -> String {
+            svg.lines()
+                .find(|l| {
+                    (l.starts_with("<polyline") || l.starts_with("<path")) && l.contains(color)
+                })
+                .unwrap()
+                .to_string()
+        };
+        let chart = Chart::new(800, 600)
+            .bars(&bars)
+            .sma(10, "#ab0003")
+            .primitive(
+                PrimitiveConfig::new("brush", vec![(5.0, 100.0), (10.0, 104.0), (15.0, 99.0)])
+                    .with_color("#ab0001"),
+            )
+            .primitive(
+                PrimitiveConfig::new("polyline", vec![(5.0, 100.0), (10.0, 104.0), (15.0, 99.0)])
+                    .with_color("#ab0002"),
+            );
+        let svg = chart.render_svg();
+
+        let round = r#"stroke-linecap="round" stroke-linejoin="round""#;
+        assert!(stroke(&svg, "#ab0001").contains(round));
+        assert!(stroke(&svg, "#ab0003").contains(round));
+        // Drawings that don't ask for round joins keep miters
+        assert!(stroke(&svg, "#ab0002").contains(r#"stroke-linejoin="miter""#));
     }
 
-    fn stroke(&mut self) {
-        let path = std::mem::take(&mut self.path_builder).build();
-        let dash = if self.dash_pattern.is_empty() {
-            None
-        } else {
-            Some(self.dash_pattern.clone())
+    #[test]
+    fn test_hidden_and_locked_primitives() {
+        let bars = sample_bars(50);
+        let line = |id: &str, color: &str| {
+            PrimitiveConfig::horizontal_line(bars[25].close)
+                .with_id(id)
+                .with_color(color)
         };
-        let style = LineStyle {
-            color: self.stroke_color.with_alpha(self.global_alpha),
-            width: self.stroke_width,
-            dash,
-            ..Default::default()
+        let mut chart = Chart::new(800, 600)
+            .bars(&bars)
+            .primitive(line("below", "#e91e63"))
+            .primitive(line("above", "#00bcd4"));
+        let hits = |chart: &Chart| -> Vec<usize> {
+            let mut hits: Vec<usize> = (0..600)
+                .filter_map(|y| chart.hit_test(400.0, y as f64, 3.0))
+                .collect();
+            hits.dedup();
+            hits
         };
-        self.backend.stroke_path(&path, &style);
+
+        // The topmost primitive wins, locked ones fall through, hidden ones
+        // are neither drawn nor hit
+        assert_eq!(hits(&chart), vec![1]);
+        assert_eq!(chart.hit_test(400.0, 5.0, 3.0), None);
+        chart.set_primitive_locked("above", true);
+        assert_eq!(hits(&chart), vec![0]);
+        assert!(chart.render_svg().contains("#00bcd4"));
+        chart.set_primitive_visibility("below", false);
+        assert!(hits(&chart).is_empty());
+        assert!(!chart.render_svg().contains("#e91e63"));
+
+        // Locked primitives are never drawn selected
+        chart.set_primitive_selected("above", true);
+        assert!(!chart.render_svg().contains(r##"fill="#ffffff""##));
+
+        // Both flags survive JSON, and default when absent
+        let json = serde_json::to_string(&chart.config.primitives).unwrap();
+        let restored: Vec<PrimitiveConfig> = serde_json::from_str(&json).unwrap();
+        assert!(!restored[0].visible && !restored[0].locked);
+        assert!(restored[1].visible && restored[1].locked);
+        let bare: PrimitiveConfig =
+            serde_json::from_str(r#"{"type_id": "horizontal_line", "points": [[0, 1]]}"#).unwrap();
+        assert!(bare.visible && !bare.locked);
     }
 
-    fn fill(&mut self) {
-        let path = std::mem::take(&mut self.path_builder).build();
-        let style = FillStyle::Solid(self.fill_color.with_alpha(self.global_alpha));
-        self.backend.fill_path(&path, &style);
+    fn bars_from_closes(closes: &[f64]) -> Vec<Bar> {
+        closes
+            .iter()
+            .enumerate()
+            .map(|(i, &close)| Bar {
+                timestamp: 1700000000 + i as i64 * 3600,
+                open: close - 0.05,
+                high: close + 0.1,
+                low: close - 0.1,
+                close,
+                volume: 1000.0,
+            })
+            .collect()
     }
 
-    fn stroke_rect(&mut self, x: f64, y: f64, w: f64, h: f64) {
-        let dash = if self.dash_pattern.is_empty() {
-            None
-        } else {
-            Some(self.dash_pattern.clone())
-        };
-        let style = LineStyle {
-            color: self.stroke_color.with_alpha(self.global_alpha),
-            width: self.stroke_width,
-            dash,
-            ..Default::default()
+    /// `(y, height)` of every rect filled with `color`
+    fn rects_of(svg: &str, color: &str) -> Vec<(f64, f64)> {
+        let attr = |line: &str, name: &str| -> f64 {
+            line.split(&format!(" {}=\"", name))
+                .nth(1)
+                .unwrap()
+                .split('"')
+                .next()
+                .unwrap()
+                .parse()
+                .unwrap()
         };
-        self.backend.stroke_rect(Rect::new(x, y, w, h), &style);
+        svg.lines()
+            .filter(|l| l.starts_with("<rect") && l.contains(&format!("fill=\"{}\"", color)))
+            .map(|l| (attr(l, "y"), attr(l, "height")))
+            .collect()
     }
 
-    fn fill_rect(&mut self, x: f64, y: f64, w: f64, h: f64) {
-        self.backend.fill_rect(
-            Rect::new(x, y, w, h),
-            self.fill_color.with_alpha(self.global_alpha),
-        );
-    }
+    #[test]
+    fn test_all_negative_series() {
+        let closes: Vec<f64> = (0..40).map(|i| -5.0 + 4.0 * i as f64 / 39.0).collect();
+        let histogram = IndicatorVector::new(
+            "Spread",
+            VectorStyle::histogram_colored("#00ff01", "#ff0001"),
+        )
+        .with_values(closes.clone());
+        let svg = Chart::new(800, 600)
+            .bars(&bars_from_closes(&closes))
+            .baseline(-3.0)
+            .custom_indicator(
+                "Spread",
+                IndicatorPlacement::subpane(0.25),
+                vec![histogram],
+                vec![],
+            )
+            .render_svg();
 
-    fn ellipse(&mut self, params: EllipseParams) {
-        let EllipseParams { cx, cy, rx, ry, .. } = params;
-        // Approximate ellipse with bezier curves
-        let kappa = 0.5522847498;
-        let ox = rx * kappa;
-        let oy = ry * kappa;
+        // Bars hang from the top of the subpane, where zero is clamped
+        let bars = rects_of(&svg, "#ff0001");
+        assert_eq!(bars.len(), 40);
+        let top = bars[0].0;
+        assert!(top > 427.0, "{}", top);
+        for &(y, height) in &bars {
+            assert_eq!(y, top);
+            assert!(y + height <= 570.0);
+        }
+        assert!(rects_of(&svg, "#00ff01").is_empty());
 
-        self.path_builder.move_to(Point::new(cx - rx, cy));
-        self.path_builder.cubic_to(
-            Point::new(cx - rx, cy - oy),
-            Point::new(cx - ox, cy - ry),
-            Point::new(cx, cy - ry),
-        );
-        self.path_builder.cubic_to(
-            Point::new(cx + ox, cy - ry),
-            Point::new(cx + rx, cy - oy),
-            Point::new(cx + rx, cy),
-        );
-        self.path_builder.cubic_to(
-            Point::new(cx + rx, cy + oy),
-            Point::new(cx + ox, cy + ry),
-            Point::new(cx, cy + ry),
-        );
-        self.path_builder.cubic_to(
-            Point::new(cx - ox, cy + ry),
-            Point::new(cx - rx, cy + oy),
-            Point::new(cx - rx, cy),
+        // Negative ticks keep their sign and precision on both scales
+        let labels: Vec<&str> = svg
+            .lines()
+            .filter(|l| l.contains(r#"x="736.00""#))
+            .filter_map(|l| l.split('>').nth(1)?.split('<').next())
+            .collect();
+        assert!(labels.contains(&"-4.5"), "{:?}", labels);
+        assert!(labels.contains(&"-3"), "{:?}", labels);
+        assert!(
+            !labels
+                .iter()
+                .any(|l| l.trim_start_matches('-').is_empty() || *l == "-0")
         );
-        self.path_builder.close();
+
+        // The baseline at -3 splits the line into both zones
+        let theme = ThemeConfig::default();
+        assert!(svg.contains(&format!("stroke=\"{}\"", theme.up_color.to_lowercase())));
+        assert!(svg.contains(&format!("stroke=\"{}\"", theme.down_color.to_lowercase())));
     }
 
-    fn arc(&mut self, cx: f64, cy: f64, radius: f64, start: f64, end: f64) {
-        // Simple arc approximation - just add the arc endpoints
-        let start_x = cx + radius * start.cos();
-        let start_y = cy + radius * start.sin();
-        let end_x = cx + radius * end.cos();
-        let end_y = cy + radius * end.sin();
+    #[test]
+    fn test_zero_crossing_series() {
+        let closes: Vec<f64> = (0..40).map(|i| -2.0 + 5.0 * i as f64 / 39.0).collect();
+        let histogram = IndicatorVector::new(
+            "Funding",
+            VectorStyle::histogram_colored("#00ff01", "#ff0001"),
+        )
+        .with_values(closes.clone());
+        let area = IndicatorVector::new("Area", VectorStyle::area("#0000fe", 0.5))
+            .with_values(closes.clone());
+        let svg = Chart::new(800, 600)
+            .bars(&bars_from_closes(&closes))
+            .custom_indicator("Area", IndicatorPlacement::overlay(), vec![area], vec![])
+            .custom_indicator(
+                "Funding",
+                IndicatorPlacement::subpane(0.25),
+                vec![histogram],
+                vec![],
+            )
+            .render_svg();
 
-        self.path_builder.move_to(Point::new(start_x, start_y));
-        // For now just line to - proper arc would need SVG arc command
-        self.path_builder.line_to(Point::new(end_x, end_y));
+        // Up bars end and down bars start on the same zero line
+        let (up, down) = (rects_of(&svg, "#00ff01"), rects_of(&svg, "#ff0001"));
+        assert_eq!(up.len() + down.len(), 40);
+        let zero_y = down[0].0;
+        assert!(down.iter().all(|&(y, _)| y == zero_y));
+        assert!(up.iter().all(|&(y, h)| (y + h - zero_y).abs() <= 1.0));
+
+        // The overlay area closes on the zero price, not at y = 0
+        let fill = svg
+            .lines()
+            .find(|l| l.starts_with("<path") && l.contains("0000fe"))
+            .unwrap();
+        let ys: Vec<f64> = fill
+            .split("d=\"")
+            .nth(1)
+            .unwrap()
+            .split('"')
+            .next()
+            .unwrap()
+            .split(|c: char| c.is_ascii_alphabetic() || c == ' ')
+            .filter_map(|n| n.parse().ok())
+            .skip(1)
+            .step_by(2)
+            .collect();
+        // Closes at the last two points; the line runs from -2 to 3 over the first 40
+        let (first, last) = (ys[0], ys[39]);
+        let zero_y = first + (last - first) * 2.0 / 5.0;
+        for &y in &ys[ys.len() - 2..] {
+            assert!((y - zero_y).abs() < 0.01, "{} vs {}", y, zero_y);
+        }
     }
 
-    fn quadratic_curve_to(&mut self, cpx: f64, cpy: f64, x: f64, y: f64) {
-        self.path_builder
-            .quad_to(Point::new(cpx, cpy), Point::new(x, y));
-    }
+    #[test]
+    fn test_arrow_line_head_points_along_line() {
+        let bars = sample_bars(50);
+        let svg = Chart::new(800, 600)
+            .bars(&bars)
+            .primitive(
+                PrimitiveConfig::new("arrow_line", vec![(10.0, 100.0), (40.0, 110.0)])
+                    .with_color("#e91e63"),
+            )
+            .render_svg();
+        let path_points = |marker: &str| -> Vec<(f64, f64)> {
+            let line = svg.lines().find(|l| l.contains(marker)).unwrap();
+            let d = line
+                .split("d=\"")
+                .nth(1)
+                .unwrap()
+                .split('"')
+                .next()
+                .unwrap();
+            let nums: Vec<f64> = d
+                .split(|c: char| c.is_ascii_alphabetic() || c == ' ')
+                .filter_map(|s| s.parse().ok())
+                .collect();
+            nums.chunks(2).map(|p| (p[0], p[1])).collect()
+        };
 
-    fn bezier_curve_to(&mut self, cp1x: f64, cp1y: f64, cp2x: f64, cp2y: f64, x: f64, y: f64) {
-        self.path_builder.cubic_to(
-            Point::new(cp1x, cp1y),
-            Point::new(cp2x, cp2y),
-            Point::new(x, y),
+        let shaft = path_points(r##"stroke="#e91e63""##);
+        let head = path_points(r##"fill="#e91e63""##);
+        assert_eq!(head.len(), 3, "filled triangle");
+        let (a, end) = (shaft[0], shaft[1]);
+        let tip = head[0];
+        let base = ((head[1].0 + head[2].0) / 2.0, (head[1].1 + head[2].1) / 2.0);
+
+        // Base-to-tip runs along A->B, and the tip lies beyond the shaft end
+        let line = (end.0 - a.0, end.1 - a.1);
+        let arrow = (tip.0 - base.0, tip.1 - base.1);
+        let len = |v: (f64, f64)| v.0.hypot(v.1);
+        let cross = (line.0 * arrow.1 - line.1 * arrow.0) / (len(line) * len(arrow));
+        assert!(cross.abs() < 0.01, "head rotated off the line: {}", cross);
+        assert!(line.0 * arrow.0 + line.1 * arrow.1 > 0.0);
+        assert!(
+            (len(arrow) - 12.0).abs() < 0.05,
+            "head sized for a 2px line"
         );
+        assert!(len((tip.0 - end.0, tip.1 - end.1)) > 5.0);
+        // Base corners straddle the line symmetrically
+        assert!((len((head[1].0 - base.0, head[1].1 - base.1)) - 4.8).abs() < 0.05);
     }
 
-    fn set_font(&mut self, font: &str) {
-        // Parse font string like "12px sans-serif"
-        if let Some(size_str) = font.split("px").next() {
-            if let Ok(size) = size_str.trim().parse::<f64>() {
-                self.font_size = size;
-            }
-        }
+    #[test]
+    fn test_primitive_fill_and_stroke_opacity() {
+        use crate::primitives::{Configurable, PrimitiveTrait, PropertyValue, Rectangle};
+
+        let bars = sample_bars(50);
+        let rect = |config: PrimitiveConfig| {
+            Chart::new(800, 600)
+                .bars(&bars)
+                .primitive(config)
+                .render_svg()
+        };
+
+        // Explicit fill color without opacity falls back to 0.15
+        let svg = rect(
+            PrimitiveConfig::rectangle((10.0, 100.0), (20.0, 104.0)).with_fill_color("#FF9800"),
+        );
+        assert!(svg.contains(r##"fill="#ff9800" fill-opacity="0.15""##));
+
+        let svg = rect(
+            PrimitiveConfig::rectangle((10.0, 100.0), (20.0, 104.0))
+                .with_fill_opacity(0.4)
+                .with_stroke_opacity(0.5),
+        );
+        assert!(svg.contains(r##"fill="#2196f3" fill-opacity="0.40""##));
+        assert!(svg.contains(r##"stroke="#2196f3" stroke-opacity="0.50""##));
+
+        let svg = rect(PrimitiveConfig::highlighter(vec![
+            (10.0, 100.0),
+            (20.0, 102.0),
+            (30.0, 101.0),
+        ]));
+        assert!(svg.contains(r##"stroke-opacity="0.25""##));
+
+        // Exposed through the configurable property set
+        let mut shape = Rectangle::new(0.0, 100.0, 10.0, 110.0, "#2196F3");
+        let ids: Vec<String> = shape.get_properties().into_iter().map(|p| p.id).collect();
+        assert!(ids.contains(&"fill_opacity".to_string()));
+        assert!(shape.set_property("stroke_opacity", PropertyValue::Number(0.3)));
+        assert_eq!(shape.opacity(), Some((0.2, 0.3)));
     }
 
-    fn set_text_align(&mut self, _align: crate::primitives::core::render::TextAlign) {
-        // Store for text rendering
+    #[test]
+    fn test_independent_axis_dpr() {
+        let bars = sample_bars(50);
+        let svg = Chart::new(800, 600)
+            .bars(&bars)
+            .candlesticks()
+            .grid(true)
+            .dpr_xy(2.0, 3.0)
+            .render_svg();
+        assert!(svg.contains(
+            r#"width="800" height="600" viewBox="0 0 1600 1800" preserveAspectRatio="none""#
+        ));
+        assert!(svg.contains(r#"<g transform="scale(2,3)">"#));
+
+        // Grid rows (570 / 8 = 71.25) snap to the middle of a device row
+        // (1px is 3 device pixels); grid columns (14.6 * 5.5 = 80.3) to a
+        // whole device column (1px is 2 device pixels)
+        let grid = "#1e222d";
+        let row = format!("{:.2}", (71.25f64 * 3.0).floor() / 3.0 + 0.5 / 3.0);
+        assert!(svg.lines().any(|l| l.starts_with(&format!(
+            r#"<line x1="0.00" y1="{row}" x2="730.00" y2="{row}""#
+        )) && l.contains(grid)));
+        assert!(svg.lines().any(
+            |l| l.starts_with(r#"<line x1="80.50" y1="0.00" x2="80.50""#) && l.contains(grid)
+        ));
+
+        // A uniform dpr keeps the logical viewBox and no scaling group
+        let uniform = Chart::new(800, 600).bars(&bars).dpr(2.0).render_svg();
+        assert!(uniform.contains(r#"width="800" height="600" viewBox="0 0 800 600">"#));
+        assert!(!uniform.contains("<g transform=\"scale("));
     }
 
-    fn set_text_baseline(&mut self, _baseline: crate::primitives::core::render::TextBaseline) {
-        // Store for text rendering
+    #[test]
+    fn test_watermark_centers_in_main_pane() {
+        let watermark = Watermark::simple("BTCUSD");
+        let svg = Chart::new(800, 600)
+            .bars(&sample_bars(50))
+            .candlesticks()
+            .watermark(watermark)
+            .render_svg();
+        let line = svg
+            .lines()
+            .find(|l| l.starts_with("<text") && l.contains(">BTCUSD<"))
+            .unwrap();
+        let x: f64 = line.split('"').nth(1).unwrap().parse().unwrap();
+        let width = estimate_text_width("BTCUSD", 48.0, FontWeight::Normal);
+
+        // Centered between the left edge and the price scale, not the canvas
+        let chart_width = 800.0 - PRICE_SCALE_WIDTH;
+        assert!(
+            (x + width / 2.0 - chart_width / 2.0).abs() < 0.5,
+            "{}",
+            line
+        );
     }
 
-    fn set_global_alpha(&mut self, alpha: f64) {
-        self.global_alpha = alpha.clamp(0.0, 1.0);
+    #[test]
+    fn test_min_bar_spacing_limits_bars_and_shows_scrollbar() {
+        let bars = sample_bars(1000);
+        let (config, bars) = Chart::new(800, 600)
+            .bars(&bars)
+            .candlesticks()
+            .sma(20, "#2196F3")
+            .signal(SignalConfig::buy(10, 100.0))
+            .signal(SignalConfig::buy(990, 100.0))
+            .min_bar_spacing(4.0)
+            .scroll_indicator(true)
+            .build();
+
+        // 730px of chart at 4px per bar fits the last 182 of 1000 bars
+        let renderer = ChartRenderer::new(&config, &bars);
+        let first = 1000 - 182;
+        assert_eq!(renderer.bars.len(), 182);
+        assert_eq!(renderer.bars[0].timestamp, bars[first].timestamp);
+        let sma = |config: &ChartConfig| config.indicators[0].vectors[0].values.clone();
+        assert_eq!(sma(&renderer.config)[0], sma(&config)[first]);
+        let signals: Vec<usize> = renderer
+            .config
+            .signals
+            .iter()
+            .map(|s| s.bar_index)
+            .collect();
+        assert_eq!(signals, [990 - first]);
+
+        // The thumb covers the visible 18.2% at the right end of the track
+        let chart_width = 800.0 - PRICE_SCALE_WIDTH;
+        let svg = renderer.render_svg();
+        let thumb = svg
+            .lines()
+            .find(|l| l.starts_with("<rect") && l.contains(r#"fill-opacity="0.50""#))
+            .unwrap();
+        let attr = |i: usize| thumb.split('"').nth(i).unwrap().parse::<f64>().unwrap();
+        assert!((attr(5) - 0.182 * chart_width).abs() <= 1.0, "{}", thumb);
+        assert!((attr(1) + attr(5) - chart_width).abs() <= 1.0, "{}", thumb);
+
+        // Without the floor every bar is squeezed in and there is no scrollbar
+        let mut config = config;
+        config.min_bar_spacing = None;
+        let renderer = ChartRenderer::new(&config, &bars);
+        assert_eq!(renderer.bars.len(), 1000);
+        assert!(!renderer.render_svg().contains(r#"fill-opacity="0.50""#));
     }
 
-    fn set_line_cap(&mut self, _cap: &str) {
-        // SVG supports this but we ignore for now
+    #[test]
+    fn test_min_bar_spacing_counts_real_bars_only() {
+        // Nothing to fit: no panic, nothing drawn
+        let chart = Chart::new(800, 600)
+            .bars(&[])
+            .min_bar_spacing(100.0)
+            .right_offset(10.0);
+        assert!(chart.render_svg().contains("<svg"));
+        assert!(chart.legend_data().is_none());
+
+        let bars = sample_bars(1000);
+        let kept = |offset: f64| {
+            let (config, bars) = Chart::new(800, 600)
+                .bars(&bars)
+                .min_bar_spacing(4.0)
+                .right_offset(offset)
+                .hover_bar(Some(990))
+                .build();
+            let renderer = ChartRenderer::new(&config, &bars);
+            (renderer.bars.len(), renderer.legend_data().unwrap().index)
+        };
+        // The right offset does not push real bars out, and the legend
+        // reports the bar's index in the full data
+        assert_eq!(kept(0.0), (182, 990));
+        assert_eq!(kept(10.0), (182, 990));
+
+        // The tooltip takes and reports the same index as the legend, and
+        // describes the same bar
+        let chart = Chart::new(800, 600)
+            .bars(&bars)
+            .min_bar_spacing(4.0)
+            .hover_bar(Some(990));
+        let legend = chart.legend_data().unwrap();
+        let info = chart.bar_info(legend.index).unwrap();
+        assert_eq!(info.index, legend.index);
+        assert_eq!(info.timestamp, bars[990].timestamp);
+        assert!(chart.bar_info(0).is_none());
     }
 
-    fn set_line_join(&mut self, _join: &str) {
-        // SVG supports this but we ignore for now
+    #[test]
+    fn test_gap_shading_follows_threshold() {
+        // Bars all closing at 100, bar 10 opening 2% higher
+        let mut bars = sample_bars(20);
+        for bar in bars.iter_mut() {
+            (bar.open, bar.high, bar.low, bar.close) = (100.0, 101.0, 99.0, 100.0);
+        }
+        (bars[10].open, bars[10].high) = (102.0, 103.0);
+
+        let gap_rects = |threshold: f64| {
+            let (config, bars) = Chart::new(800, 600)
+                .bars(&bars)
+                .candlesticks()
+                .show_gaps(GapConfig::new(GapThreshold::Percent(threshold)))
+                .build();
+            let svg = ChartRenderer::new(&config, &bars).render_svg();
+            let rects: Vec<String> = svg
+                .lines()
+                .filter(|l| l.starts_with("<rect") && l.contains(r#"fill-opacity="0.15""#))
+                .map(str::to_string)
+                .collect();
+            (config, bars, rects)
+        };
+
+        let (config, bars, rects) = gap_rects(1.0);
+        assert_eq!(rects.len(), 1, "{:?}", rects);
+        let attr = |name: &str| -> f64 {
+            let start = rects[0].find(&format!(" {}=\"", name)).unwrap() + name.len() + 3;
+            let rest = &rects[0][start..];
+            rest[..rest.find('"').unwrap()].parse().unwrap()
+        };
+        let renderer = ChartRenderer::new(&config, &bars);
+        let (low, high) = renderer.main_price_bounds();
+        let (_, price_height) = renderer.main_price_scale();
+        let price_to_y = |p: f64| price_height - (p - low) / (high - low) * price_height;
+        assert!((attr("y") - price_to_y(102.0)).abs() <= 1.0);
+        assert!((attr("y") + attr("height") - price_to_y(100.0)).abs() <= 1.0);
+        assert!(rects[0].contains("#26a69a"));
+
+        assert!(gap_rects(3.0).2.is_empty());
+        assert!(GapThreshold::Absolute(1.5).is_gap(100.0, 102.0));
+        assert!(!GapThreshold::Absolute(2.5).is_gap(100.0, 97.5));
     }
 
-    fn fill_text(&mut self, text: &str, x: f64, y: f64) {
-        use crate::render::engine::TextStyle;
-        self.backend.text(
-            text,
-            Point::new(x, y),
-            &TextStyle {
-                font_family: "sans-serif".into(),
-                font_size: self.font_size,
-                font_weight: crate::render::engine::FontWeight::Normal,
-                color: self.text_color.with_alpha(self.global_alpha),
-                align: crate::render::engine::TextAlign::Left,
-                baseline: crate::render::engine::TextBaseline::Top,
-            },
+    #[test]
+    fn test_htf_overlay_spans_child_bars_behind_series() {
+        // A day of 15m bars starting at 01:30 UTC: the first 4h candle only
+        // covers 01:30-04:00 (10 bars), the last one 00:00-01:30 (6 bars)
+        let day = 1_700_006_400;
+        let mut bars = sample_bars(96);
+        for (i, bar) in bars.iter_mut().enumerate() {
+            bar.timestamp = day + 5400 + i as i64 * 900;
+        }
+        let spacing = 730.0 / 96.0;
+
+        let svg = Chart::new(800, 600)
+            .bars(&bars)
+            .candlesticks()
+            .htf_overlay(14400, HtfOverlayStyle::outline())
+            .render_svg();
+        let lines: Vec<&str> = svg.lines().collect();
+        let htf: Vec<usize> = (0..lines.len())
+            .filter(|&i| {
+                lines[i].starts_with("<rect") && lines[i].contains(r#"stroke-opacity="0.35""#)
+            })
+            .collect();
+        assert_eq!(htf.len(), 7);
+
+        // Outline edges snap to half pixels, so widths are whole pixels
+        let widths = htf
+            .iter()
+            .map(|&i| lines[i].split('"').nth(5).unwrap().parse::<f64>().unwrap());
+        for (width, n) in widths.zip([10, 16, 16, 16, 16, 16, 6]) {
+            assert_eq!(width.fract(), 0.0);
+            assert!((width - n as f64 * spacing).abs() <= 1.0);
+        }
+        assert!(lines[htf[0]].starts_with(r#"<rect x="0.50""#));
+
+        // Wicks follow the bodies, and everything precedes the base candles
+        assert!(
+            lines
+                .iter()
+                .any(|l| l.starts_with("<line") && l.contains(r#"stroke-opacity="0.35""#))
         );
-    }
+        let first_candle = lines
+            .iter()
+            .position(|l| {
+                (l.contains("#26a69a") || l.contains("#ef5350")) && !l.contains("opacity")
+            })
+            .unwrap();
+        assert!(htf.iter().all(|&i| i < first_candle));
 
-    fn stroke_text(&mut self, _text: &str, _x: f64, _y: f64) {
-        // Text stroking not commonly needed
+        let filled = Chart::new(800, 600)
+            .bars(&bars)
+            .candlesticks()
+            .htf_overlay(14400, HtfOverlayStyle::filled().with_wicks(false))
+            .render_svg();
+        assert_eq!(filled.matches(r#"fill-opacity="0.12""#).count(), 7);
+        assert!(!filled.contains(r#"stroke-opacity="0.12""#));
     }
 
-    fn measure_text(&self, text: &str) -> f64 {
-        // Approximate: average char width is ~0.6 * font_size
-        text.len() as f64 * self.font_size * 0.6
-    }
+    #[test]
+    fn test_volume_by_price_hugs_right_edge_and_follows_replay() {
+        let bars = sample_bars(120);
+        let histogram = |chart: Chart| -> Vec<(f64, f64, f64)> {
+            chart
+                .render_svg()
+                .lines()
+                .filter(|l| l.starts_with("<rect") && l.contains(r#"fill-opacity="0.25""#))
+                .map(|l| {
+                    let attr = |n: usize| l.split('"').nth(n).unwrap().parse::<f64>().unwrap();
+                    (attr(1), attr(3), attr(5))
+                })
+                .collect()
+        };
 
-    fn save(&mut self) {
-        // Would need state stack for proper save/restore
+        let full = histogram(
+            Chart::new(800, 600)
+                .bars(&bars)
+                .candlesticks()
+                .volume_by_price(VbpConfig::new(12, 0.3).with_poc()),
+        );
+        assert!(!full.is_empty() && full.len() <= 12);
+        // Right ends sit on the pane edge, the fullest bar spans 30% of it
+        for &(x, _, width) in &full {
+            assert!((x + width - 730.0).abs() <= 1.0);
+        }
+        let widest = full.iter().map(|r| r.2).fold(0.0, f64::max);
+        assert!((widest - 219.0).abs() <= 1.0);
+
+        // Only replayed bars are counted
+        let replayed = histogram(
+            Chart::new(800, 600)
+                .bars(&bars)
+                .candlesticks()
+                .volume_by_price(VbpConfig::new(12, 0.3))
+                .replay_to(30),
+        );
+        assert!(!replayed.is_empty());
+        assert_ne!(full, replayed);
+
+        let split = histogram(
+            Chart::new(800, 600)
+                .bars(&bars)
+                .candlesticks()
+                .volume_by_price(VbpConfig::new(12, 0.3).with_up_down()),
+        );
+        assert!(split.len() > full.len());
     }
 
-    fn restore(&mut self) {
-        // Would need state stack for proper save/restore
-    }
+    #[test]
+    fn test_last_price_line_and_tag() {
+        // 7 bars end on an up bar, 50 on a down bar
+        for n in [7, 50] {
+            let bars = sample_bars(n);
+            let last = bars[n - 1];
+            let plain = Chart::new(800, 600).bars(&bars).candlesticks();
+            assert!(!plain.render_svg().contains("stroke-dasharray"));
+            let svg = plain.last_price_line(true).render_svg();
+
+            let low = bars.iter().map(|b| b.low).fold(f64::INFINITY, f64::min);
+            let high = bars
+                .iter()
+                .map(|b| b.high)
+                .fold(f64::NEG_INFINITY, f64::max);
+            let pad = (high - low) * 0.05;
+            let y = 570.0 - (last.close - low + pad) / (high - low + 2.0 * pad) * 570.0;
+            let color = if last.close >= last.open {
+                "#26a69a"
+            } else {
+                "#ef5350"
+            };
 
-    fn clip(&mut self) {
-        // SVG clipping requires different approach
+            // Line and tag snap to pixels; the label stays on the exact price
+            let line_y = crate::render::engine::crisp_coord(y, 1.0);
+            let line = format!(r#"<line x1="0.00" y1="{line_y:.2}" x2="730.00" y2="{line_y:.2}""#);
+            let line = svg.lines().find(|l| l.starts_with(&line)).unwrap();
+            assert!(line.contains(color) && line.contains("stroke-dasharray"));
+
+            let tag = format!(r#"<rect x="730.00" y="{:.2}""#, (y - 8.5).round());
+            let tag = svg.lines().find(|l| l.starts_with(&tag)).unwrap();
+            assert!(tag.contains(&format!(r#"fill="{color}""#)));
+            let text = format!(r#"<text x="736.00" y="{y:.2}""#);
+            assert!(
+                svg.lines()
+                    .any(|l| l.starts_with(&text) && l.contains("#ffffff"))
+            );
+
+            // Tick labels never sit under the tag
+            let under_tag = svg
+                .lines()
+                .filter(|l| l.starts_with(r#"<text x="736.00""#))
+                .filter_map(|l| l.split('"').nth(3)?.parse::<f64>().ok())
+                .filter(|&ty| (ty - y).abs() < 11.0 && (ty - y).abs() > 0.01)
+                .count();
+            assert_eq!(under_tag, 0);
+        }
     }
 
-    fn translate(&mut self, _x: f64, _y: f64) {
-        // Would need transform matrix
+    #[test]
+    fn test_custom_indicator_fill_behind_lines_and_split_on_gaps() {
+        let bars = sample_bars(60);
+        let closes = compute::closes(&bars);
+        let mut upper: Vec<f64> = closes.iter().map(|c| c + 5.0).collect();
+        let lower: Vec<f64> = closes.iter().map(|c| c - 5.0).collect();
+        upper[30] = f64::NAN;
+
+        for placement in [
+            IndicatorPlacement::Overlay,
+            IndicatorPlacement::subpane(0.2),
+        ] {
+            let chart = Chart::new(800, 600)
+                .bars(&bars)
+                .candlesticks()
+                .custom_indicator(
+                    "Band",
+                    placement,
+                    vec![
+                        IndicatorVector::new("Upper", VectorStyle::line("#FF9800", 1.0))
+                            .with_values(upper.clone()),
+                        IndicatorVector::new("Lower", VectorStyle::line("#FF9800", 1.0))
+                            .with_values(lower.clone()),
+                    ],
+                    vec![IndicatorFill::new(0, 1, "#00FF00").with_alpha(0.25)],
+                );
+            let svg = chart.render_svg();
+            let fills: Vec<usize> = svg
+                .match_indices("<path d=")
+                .map(|(pos, _)| pos)
+                .filter(|&pos| {
+                    svg[pos..]
+                        .lines()
+                        .next()
+                        .unwrap()
+                        .contains(r##"fill="#00ff00" fill-opacity="0.25""##)
+                })
+                .collect();
+            assert_eq!(fills.len(), 2, "NaN at bar 30 splits the band");
+
+            let first_line = svg.find("<polyline").unwrap();
+            assert!(fills.iter().all(|&pos| pos < first_line));
+        }
     }
 
-    fn rotate(&mut self, _angle: f64) {
-        // Would need transform matrix
+    #[test]
+    fn test_band_factories_fill_outer_vectors() {
+        let bars = sample_bars(40);
+        let chart = Chart::new(800, 600).bars(&bars).bollinger(20, 2.0);
+        let bb = &chart.config.indicators[0];
+        assert_eq!(bb.vectors[bb.fills[0].upper_vector].name, "Upper");
+        assert_eq!(bb.vectors[bb.fills[0].lower_vector].name, "Lower");
+
+        let (upper, _, lower) = compute::bollinger(&compute::closes(&bars), 20, 2.0);
+        assert_eq!(bb.vectors[1].values[30].to_bits(), upper[30].to_bits());
+        assert_eq!(bb.vectors[2].values[30].to_bits(), lower[30].to_bits());
+
+        for indicator in [
+            Indicator::keltner("kc", 20),
+            Indicator::donchian("dc", 20),
+            Indicator::price_channel("pc", 20),
+        ] {
+            let fill = &indicator.fills[0];
+            assert_eq!(indicator.vectors[fill.upper_vector].name, "Upper");
+            assert_eq!(indicator.vectors[fill.lower_vector].name, "Lower");
+        }
     }
 
-    fn scale(&mut self, _x: f64, _y: f64) {
-        // Would need transform matrix
+    #[test]
+    fn test_adx_renders_three_lines() {
+        let bars = sample_bars(80);
+        let chart = Chart::new(800, 600).bars(&bars).candlesticks().adx(14);
+        let adx = chart.indicator_values("adx_14").unwrap();
+        let labels: Vec<&str> = adx.iter().map(|v| v.name.as_str()).collect();
+        assert_eq!(labels, ["ADX", "+DI", "-DI"]);
+        assert!(adx[0].values[26].is_nan() && !adx[0].values[27].is_nan());
+
+        // Everything below the 570 * 0.85 main pane belongs to the subpane
+        let svg = chart.render_svg();
+        let subpane_lines = svg
+            .split("<polyline points=\"")
+            .skip(1)
+            .filter(|p| {
+                let first = p.split(' ').next().unwrap();
+                let y: f64 = first.split(',').nth(1).unwrap().parse().unwrap();
+                y > 570.0 * 0.85
+            })
+            .count();
+        assert_eq!(subpane_lines, 3);
     }
 
-    fn rect(&mut self, x: f64, y: f64, w: f64, h: f64) {
-        self.path_builder.move_to(Point::new(x, y));
-        self.path_builder.line_to(Point::new(x + w, y));
-        self.path_builder.line_to(Point::new(x + w, y + h));
-        self.path_builder.line_to(Point::new(x, y + h));
-        self.path_builder.close();
+    #[test]
+    fn test_rsi_smoothing_renders_sma_of_raw_values() {
+        let bars = sample_bars(120);
+        let raw_chart = Chart::new(800, 600).bars(&bars).candlesticks().rsi(14);
+        let chart = Chart::new(800, 600)
+            .bars(&bars)
+            .candlesticks()
+            .rsi(14)
+            .smoothing("rsi_14", 3);
+        let same = |a: &[f64], b: &[f64]| {
+            a.len() == b.len()
+                && a.iter()
+                    .zip(b)
+                    .all(|(x, y)| x.to_bits() == y.to_bits() || (x.is_nan() && y.is_nan()))
+        };
+
+        // Queried values stay raw; the rendered ones are their 3-bar SMA
+        let raw = &chart.indicator_values("rsi_14").unwrap()[0];
+        let expected_raw = compute::rsi(&compute::closes(&bars), 14);
+        assert!(same(&raw.values, &expected_raw));
+        let smoothed = raw.display_values();
+        assert!(same(&smoothed, &compute::sma(&raw.values, 3)));
+        // Warm-up extends by period - 1 bars
+        assert!(smoothed[15].is_nan() && !smoothed[16].is_nan());
+
+        // Sum of vertical moves along the subpane polyline
+        let subpane_line = |svg: &str| -> (usize, f64) {
+            let points = svg
+                .split("<polyline points=\"")
+                .skip(1)
+                .map(|p| p.split('"').next().unwrap())
+                .map(|p| {
+                    p.split(' ')
+                        .map(|xy| xy.split(',').nth(1).unwrap().parse::<f64>().unwrap())
+                        .collect::<Vec<_>>()
+                })
+                .find(|ys| ys[0] > 570.0 * 0.85)
+                .unwrap();
+            let travel = points.windows(2).map(|w| (w[1] - w[0]).abs()).sum();
+            (points.len(), travel)
+        };
+        let (raw_points, raw_travel) = subpane_line(&raw_chart.render_svg());
+        let (points, travel) = subpane_line(&chart.render_svg());
+        assert_eq!(points, raw_points - 2);
+        assert!(travel < raw_travel);
     }
-}
 
-// =============================================================================
-// Chart Builder - Creates ChartConfig with fluent API
-// =============================================================================
+    #[test]
+    fn test_seasonal_overlays_years_on_month_axis() {
+        // Daily bars from 2022-01-01 through mid 2024
+        let jan_2022 = 1_640_995_200;
+        let bars: Vec<Bar> = sample_bars(912)
+            .into_iter()
+            .enumerate()
+            .map(|(i, bar)| Bar {
+                timestamp: jan_2022 + i as i64 * 86_400,
+                ..bar
+            })
+            .collect();
+        let svg = Chart::new(800, 600)
+            .bars(&bars)
+            .seasonal(SeasonalPeriod::Year, Source::Close)
+            .render_svg();
 
-/// High-level chart builder that creates ChartConfig
-pub struct Chart {
-    config: ChartConfig,
-    bars: Vec<Bar>,
-}
+        // One line per year, each starting at the left edge; the current
+        // year is wider and opaque, older years fade with age
+        let lines: Vec<&str> = svg.lines().filter(|l| l.starts_with("<polyline")).collect();
+        assert_eq!(lines.len(), 3);
+        assert!(
+            lines
+                .iter()
+                .all(|l| l.starts_with(r#"<polyline points="0.00,"#))
+        );
+        assert!(lines[0].contains(r#"stroke-opacity="0.33""#));
+        assert!(lines[1].contains(r#"stroke-opacity="0.67""#));
+        assert!(lines[2].contains(r#"stroke-width="2.00""#) && !lines[2].contains("opacity"));
 
-impl Chart {
-    /// Create a new chart builder with given dimensions
-    pub fn new(width: u32, height: u32) -> Self {
-        Self {
-            config: ChartConfig {
-                width,
-                height,
-                dpr: 1.0,
-                theme: ThemeConfig::default(),
-                series: SeriesConfig::candlestick(),
-                indicators: Vec::new(),
-                primitives: Vec::new(),
-                signals: Vec::new(),
-                layout: super::config::LayoutConfig::single(),
-            },
-            bars: Vec::new(),
+        // Legend per period, month names on the time scale
+        for label in ["2022", "2023", "2024", "Jan", "Jul", "Dec"] {
+            assert!(svg.contains(&format!(">{label}</text>")), "{label}");
         }
+        // The candlestick series is replaced, not drawn underneath
+        assert!(!svg.contains("#26a69a") && !svg.contains("#ef5350"));
     }
 
-    /// Set device pixel ratio
-    pub fn dpr(mut self, dpr: f64) -> Self {
-        self.config.dpr = dpr;
-        self
+    #[test]
+    fn test_million_ticks_as_main_series_downsample_per_column() {
+        // One tick every 10ms for about 2.8 hours, in milliseconds
+        let start_ms = 1_700_000_000_000;
+        let ticks: Vec<(i64, f64)> = (0..1_000_000)
+            .map(|i| {
+                let spike = if i == 500_000 { 50.0 } else { 0.0 };
+                (start_ms + i * 10, 100.0 + (i as f64 * 0.001).sin() + spike)
+            })
+            .collect();
+        let svg = Chart::new(800, 400)
+            .timestamp_unit(TimestampUnit::Milliseconds)
+            .ticks(&ticks)
+            .render_svg();
+
+        let line = svg
+            .split("<polyline points=\"")
+            .nth(1)
+            .and_then(|rest| rest.split('"').next())
+            .unwrap();
+        let points: Vec<(f64, f64)> = line
+            .split(' ')
+            .map(|p| {
+                let (x, y) = p.split_once(',').unwrap();
+                (x.parse().unwrap(), y.parse().unwrap())
+            })
+            .collect();
+        let chart_width = 800.0 - PRICE_SCALE_WIDTH;
+        assert!(points.len() <= 4 * chart_width as usize);
+        assert_eq!(points[0].0, 0.0);
+        assert!((points[points.len() - 1].0 - chart_width).abs() < 0.01);
+        // The spike survives downsampling as the topmost point mid-pane
+        let top = points
+            .iter()
+            .fold(points[0], |a, &b| if b.1 < a.1 { b } else { a });
+        assert!((top.0 - chart_width / 2.0).abs() < 1.0, "{top:?}");
+
+        // Time scale from the tick timestamps, not an empty-state chart
+        assert!(svg.contains(">22:30</text>") && svg.contains(">00:00</text>"));
     }
 
-    /// Set OHLCV bar data
-    pub fn bars(mut self, bars: &[Bar]) -> Self {
-        self.bars = bars.to_vec();
-        self
+    #[test]
+    fn test_ticks_get_their_own_pane_under_bars() {
+        let bars = sample_bars(50);
+        let last = bars[49].timestamp;
+        // Ticks across the last ten hourly bars
+        let ticks: Vec<(i64, f64)> = (0..36_000).map(|i| (last - 9 * 3600 + i, 5.0)).collect();
+        let (config, _) = Chart::new(800, 600).bars(&bars).build();
+        let plain = ChartRenderer::new(&config, &bars);
+        let (config, _) = Chart::new(800, 600).bars(&bars).ticks(&ticks).build();
+        let renderer = ChartRenderer::new(&config, &bars);
+
+        // The bar panes give up the tick pane's share of the height
+        let (_, plain_height) = plain.main_price_scale();
+        let (_, height) = renderer.main_price_scale();
+        assert!((height - plain_height * (1.0 - TICK_PANE_RATIO)).abs() < 1e-9);
+
+        let svg = renderer.render_svg();
+        let line = svg
+            .lines()
+            .find(|l| l.starts_with("<polyline") && l.contains("stroke-linejoin=\"round\""))
+            .unwrap();
+        let xs: Vec<f64> = line
+            .split('"')
+            .nth(1)
+            .unwrap()
+            .split(' ')
+            .map(|p| p.split_once(',').unwrap().0.parse().unwrap())
+            .collect();
+        // From bar 40's left edge to bar 49's right edge, give or take the
+        // pixel column a flat line keeps only its first point of
+        let axis = BarAxis::new(&bars, &config, 800.0 - PRICE_SCALE_WIDTH, 0.0);
+        assert!((xs[0] - (axis.x(40) - axis.spacing / 2.0)).abs() < 0.01);
+        assert!((xs[xs.len() - 1] - (axis.x(49) + axis.spacing / 2.0)).abs() <= 1.0);
+        assert!(xs.len() <= 2 * (axis.spacing * 10.0).ceil() as usize);
     }
 
-    /// Use candlestick series
-    pub fn candlesticks(mut self) -> Self {
-        self.config.series = SeriesConfig::candlestick();
-        self
-    }
+    #[test]
+    fn test_ohlc_bar_ticks_length_and_visibility() {
+        let bars = sample_bars(10);
+        // (x1, y1, x2, y2) of the bar strokes, drawn in the up/down colors
+        let strokes = |chart: Chart| -> Vec<[f64; 4]> {
+            chart
+                .render_svg()
+                .lines()
+                .filter(|l| {
+                    l.starts_with("<line") && (l.contains("#26a69a") || l.contains("#ef5350"))
+                })
+                .map(|l| {
+                    let mut coords = [0.0; 4];
+                    for (slot, attr) in coords.iter_mut().zip(["x1", "y1", "x2", "y2"]) {
+                        let value = l.split(&format!(r#" {attr}=""#)).nth(1).unwrap();
+                        *slot = value.split('"').next().unwrap().parse().unwrap();
+                    }
+                    coords
+                })
+                .collect()
+        };
+        // (bars, open ticks, close ticks); horizontal ends snap to whole
+        // pixels, so a 3px tick spans 3-4px from the half-pixel center line
+        let ticks = |lines: &[[f64; 4]]| -> (usize, usize, usize) {
+            let centers: Vec<f64> = lines
+                .iter()
+                .filter(|l| l[0] == l[2])
+                .map(|l| l[0])
+                .collect();
+            let tick = |l: &&[f64; 4], from: f64, to: f64| {
+                l[1] == l[3] && (l[2] - l[0] - 3.5).abs() <= 0.5 && (from..=to).contains(&l[0])
+            };
+            let open = lines
+                .iter()
+                .filter(|l| centers.iter().any(|&c| tick(l, c - 4.0, c - 3.0)))
+                .count();
+            let close = lines
+                .iter()
+                .filter(|l| centers.iter().any(|&c| tick(l, c - 0.5, c)))
+                .count();
+            (centers.len(), open, close)
+        };
 
-    /// Use line series
-    pub fn line(mut self) -> Self {
-        self.config.series = SeriesConfig::line();
-        self
-    }
+        let chart = || {
+            Chart::new(800, 400)
+                .bars(&bars)
+                .ohlc_bars()
+                .bar_tick_length(3.0)
+        };
+        assert_eq!(ticks(&strokes(chart())), (10, 10, 10));
 
-    /// Use area series
-    pub fn area(mut self) -> Self {
-        self.config.series = SeriesConfig::area();
-        self
+        // Close-only bars keep the high-low line and the close tick
+        assert_eq!(ticks(&strokes(chart().bar_ticks(false, true))), (10, 0, 10));
+        assert_eq!(ticks(&strokes(chart().bar_ticks(true, false))), (10, 10, 0));
     }
 
-    /// Set up/down colors
-    pub fn colors(mut self, up: &str, down: &str) -> Self {
-        self.config.theme.up_color = up.into();
-        self.config.theme.down_color = down.into();
-        self
+    #[test]
+    fn test_bar_info_matches_indicator_vectors() {
+        let bars = sample_bars(80);
+        let chart = Chart::new(800, 600)
+            .bars(&bars)
+            .candlesticks()
+            .sma(20, "#2196F3")
+            .macd(12, 26, 9)
+            .signal(SignalConfig::buy(40, 99.0).with_label("entry"));
+        assert!(chart.bar_info(80).is_none());
+
+        let info = chart.bar_info(40).unwrap();
+        assert_eq!(info.timestamp, bars[40].timestamp);
+        // 1700000000 + 40h = 2023-11-16 14:13 UTC
+        assert_eq!(info.time, "16.11 14:13");
+        assert_eq!(info.signals.len(), 1);
+        assert_eq!(info.signals[0].text.as_deref(), Some("entry"));
+        assert!(chart.bar_info(41).unwrap().signals.is_empty());
+
+        for indicator in &info.indicators {
+            let vectors = chart.indicator_values(&indicator.id).unwrap();
+            for (value, vector) in indicator.values.iter().zip(vectors) {
+                assert_eq!(value.label, vector.display_label());
+                assert_eq!(value.value, Some(vector.values[40]));
+                assert_eq!(
+                    value.text.as_deref(),
+                    Some(format_indicator_value(vector.values[40]).as_str())
+                );
+            }
+        }
+        assert_eq!(info.indicators.len(), 2);
+        assert_eq!(info.indicators[0].values[0].color, "#2196F3");
+
+        // Warm-up values have no text
+        let early = chart.bar_info(5).unwrap();
+        assert_eq!(early.indicators[0].values[0].value, None);
+        assert_eq!(early.indicators[0].values[0].text, None);
+
+        // Prices use the precision of the drawn price scale
+        let decimals = info.close.split('.').nth(1).map_or(0, str::len);
+        let labels = svg_price_labels(&chart.render_svg());
+        assert!(!labels.is_empty());
+        assert!(
+            labels
+                .iter()
+                .all(|label| label.split('.').nth(1).map_or(0, str::len) == decimals)
+        );
     }
 
-    /// Set background color
-    pub fn background(mut self, color: &str) -> Self {
-        self.config.theme.background = color.into();
-        self
+    #[test]
+    fn test_describe_summarizes_range_and_extremes() {
+        let bars = sample_bars(80);
+        let chart = Chart::new(800, 600)
+            .bars(&bars)
+            .title("BTC/USD <spot>")
+            .sma(20, "#2196F3")
+            .macd(12, 26, 9)
+            .signal(SignalConfig::buy(10, 99.0))
+            .signal(SignalConfig::buy(40, 99.0))
+            .signal(SignalConfig::sell(60, 101.0));
+        let text = chart.describe();
+        assert_eq!(text, chart.describe());
+
+        // High and low are the data extremes, formatted like the tooltip
+        let (hi_idx, hi) =
+            bars.iter().enumerate().fold(
+                (0, f64::MIN),
+                |m, (i, b)| if b.high > m.1 { (i, b.high) } else { m },
+            );
+        let (lo_idx, lo) =
+            bars.iter().enumerate().fold(
+                (0, f64::MAX),
+                |m, (i, b)| if b.low < m.1 { (i, b.low) } else { m },
+            );
+        let (hi_info, lo_info) = (
+            chart.bar_info(hi_idx).unwrap(),
+            chart.bar_info(lo_idx).unwrap(),
+        );
+        let expected = format!(
+            "High {} on {}, low {} on {}.",
+            hi_info.high, hi_info.time, lo_info.low, lo_info.time
+        );
+        assert!(text.contains(&expected), "{text}");
+        // Same values up to the scale's rounding
+        let decimals = hi_info.high.split('.').nth(1).map_or(0, str::len) as i32;
+        let tolerance = 0.5 * 10f64.powi(-decimals) + 1e-9;
+        assert!((hi_info.high.parse::<f64>().unwrap() - hi).abs() <= tolerance);
+        assert!((lo_info.low.parse::<f64>().unwrap() - lo).abs() <= tolerance);
+
+        let first = chart.bar_info(0).unwrap();
+        let last = chart.bar_info(79).unwrap();
+        assert!(text.starts_with("BTC/USD <spot>. 80 bars from "));
+        assert!(text.contains(&format!("from {} to {}.", first.time, last.time)));
+        assert!(text.contains(&format!(
+            "Opened at {} and closed at {} (",
+            first.open, last.close
+        )));
+        let sma = chart.indicator_values("sma_20").unwrap()[0].values[79];
+        assert!(text.contains(&format!("SMA 20 {}", format_indicator_value(sma))));
+        assert!(text.ends_with("Signals: 2 buy, 1 sell."));
+
+        // Embedded only on request, escaped and linked from the root element
+        assert!(!chart.render_svg().contains("<desc"));
+        let svg = chart.embed_description(true).render_svg();
+        assert!(
+            svg.contains(
+                r#"role="img" aria-labelledby="chart-title" aria-describedby="chart-desc""#
+            )
+        );
+        assert!(svg.contains(r#"<title id="chart-title">BTC/USD &lt;spot&gt;</title>"#));
+        assert!(svg.contains(r#"<desc id="chart-desc">BTC/USD &lt;spot&gt;. 80 bars"#));
+        assert_eq!(Chart::new(800, 600).describe(), "No data.");
     }
 
-    /// Enable/disable grid
-    pub fn grid(mut self, show: bool) -> Self {
-        self.config.theme.show_grid = show;
-        self
-    }
+    #[test]
+    fn test_subpane_scale_groups_keep_each_vector_on_its_own_range() {
+        let bars = sample_bars(60);
+        let line: Vec<f64> = (0..60)
+            .map(|i| 50.0 + 45.0 * (i as f64 * 0.3).sin())
+            .collect();
+        let hist: Vec<f64> = (0..60).map(|i| 5.0 * (i as f64 * 0.2).cos()).collect();
+        let oscillator = |grouped: bool| {
+            let histogram =
+                IndicatorVector::new("Hist", VectorStyle::histogram()).with_values(hist.clone());
+            let indicator = Indicator::new("osc", "Osc")
+                .subpane(0.3)
+                .fixed_range(0.0, 100.0)
+                .add_vector(
+                    IndicatorVector::new("Line", VectorStyle::line("#ff9800", 1.0))
+                        .with_values(line.clone()),
+                );
+            if grouped {
+                indicator
+                    .add_vector(histogram.with_scale_group("hist"))
+                    .add_scale_group(
+                        ScaleGroup::new("hist", IndicatorRange::symmetric()).with_axis(),
+                    )
+            } else {
+                indicator.add_vector(histogram)
+            }
+        };
+        // Vertical extent of the line and tallest histogram column
+        let extents = |grouped: bool| -> (f64, f64) {
+            let chart = Chart::new(800, 600)
+                .bars(&bars)
+                .indicator(oscillator(grouped));
+            let elements = ChartRenderer::new(&chart.config, &chart.bars).render_elements();
+            let commands = &elements
+                .iter()
+                .find(|(id, _)| id == "indicator:osc")
+                .unwrap()
+                .1;
+            let mut line_span = (f64::INFINITY, f64::NEG_INFINITY);
+            let mut column = 0.0_f64;
+            for cmd in commands {
+                match cmd {
+                    RenderCommand::Polyline { points, .. } => {
+                        for p in points {
+                            line_span = (line_span.0.min(p.y), line_span.1.max(p.y));
+                        }
+                    }
+                    // Columns, not the pane background
+                    RenderCommand::FillRect { rect, .. } if rect.width < 20.0 => {
+                        column = column.max(rect.height)
+                    }
+                    _ => {}
+                }
+            }
+            (line_span.1 - line_span.0, column)
+        };
 
-    // =========================================================================
-    // Overlay Indicators
-    // =========================================================================
+        // A 0.3 subpane of the 570px above the time scale, less the gap
+        let pane = 570.0 * 0.3 - 4.0;
+        let (line_flat, column_flat) = extents(false);
+        let (line_grouped, column_grouped) = extents(true);
+        // On the 0-100 scale the +-5 histogram is a sliver
+        assert!(column_flat < 0.1 * pane);
+        // Each group fills the pane: the line 90% of it (5..95), the
+        // histogram half of it less the symmetric padding
+        assert!(line_grouped > 0.85 * pane);
+        assert_eq!(line_grouped, line_flat);
+        assert!(column_grouped > 0.4 * pane);
+
+        // The group's axis gets a column of its own right of the price
+        // scale's labels, outside the plot
+        let chart = |grouped| {
+            Chart::new(800, 600)
+                .bars(&bars)
+                .indicator(oscillator(grouped))
+        };
+        let (flat, grouped) = (chart(false), chart(true));
+        let column = grouped.price_scale_width() - flat.price_scale_width();
+        assert!(column > 0.0);
+        let labels_from = |svg: String, x: f64| {
+            svg.lines()
+                .filter_map(|l| {
+                    l.strip_prefix(r#"<text x=""#)?
+                        .split('"')
+                        .next()?
+                        .parse()
+                        .ok()
+                })
+                .filter(|&text_x: &f64| text_x > x)
+                .count()
+        };
+        assert_eq!(labels_from(flat.render_svg(), 800.0 - column), 0);
+        assert!(labels_from(grouped.render_svg(), 800.0 - column) >= 3);
+
+        // Multichart cells scale the group and reserve the column too
+        let cell = |grouped| {
+            let (config, bars) = chart(grouped).build();
+            MultichartRenderer::new(&MultichartLayout::single(), 800, 600)
+                .chart(&config, &bars)
+                .render_svg()
+        };
+        assert_eq!(labels_from(cell(false), 800.0 - column), 0);
+        assert!(labels_from(cell(true), 800.0 - column) >= 3);
 
-    /// Add SMA overlay
-    pub fn sma(mut self, period: usize, color: &str) -> Self {
-        if self.bars.is_empty() || period == 0 {
-            return self;
+        // A fill joins each vector where it is drawn on its own range
+        let banded = Indicator::new("band", "Band")
+            .subpane(0.3)
+            .fixed_range(0.0, 1.0)
+            .add_vector(
+                IndicatorVector::new("Upper", VectorStyle::line("#ff9800", 1.0))
+                    .with_values(vec![175.0; 60])
+                    .with_scale_group("wide"),
+            )
+            .add_vector(
+                IndicatorVector::new("Lower", VectorStyle::line("#2962ff", 1.0))
+                    .with_values(vec![0.25; 60]),
+            )
+            .add_fill(IndicatorFill::new(0, 1, "#2962ff"))
+            .add_scale_group(ScaleGroup::new("wide", IndicatorRange::fixed(100.0, 200.0)));
+        let chart = Chart::new(800, 600).bars(&bars).indicator(banded);
+        let elements = ChartRenderer::new(&chart.config, &chart.bars).render_elements();
+        let commands = &elements
+            .iter()
+            .find(|(id, _)| id == "indicator:band")
+            .unwrap()
+            .1;
+        let (mut lines, mut fill) = (Vec::new(), (f64::INFINITY, f64::NEG_INFINITY));
+        for cmd in commands {
+            match cmd {
+                RenderCommand::Polyline { points, .. } => lines.push(points[0].y),
+                RenderCommand::FillPath { path, .. } => {
+                    let bounds = path.bounds();
+                    fill = (fill.0.min(bounds.y), fill.1.max(bounds.bottom()));
+                }
+                _ => {}
+            }
         }
-        let values = calculate_sma(&self.bars, period);
-        let id = format!("sma_{}", period);
-        let mut indicator = Indicator::sma(&id, period as u32, color);
-        indicator.vectors[0].values = values;
-        self.config.indicators.push(indicator);
-        self
+        // Within the half pixel the two snap to differently
+        assert!((fill.0 - lines[0]).abs() <= 0.5 && (fill.1 - lines[1]).abs() <= 0.5);
+
+        // A symmetric group of zeros keeps a usable range around zero
+        let zeros =
+            IndicatorVector::new("Hist", VectorStyle::histogram()).with_values(vec![0.0; 60]);
+        let range = value_range(&IndicatorRange::symmetric(), [&zeros].into_iter(), &bars);
+        assert_eq!(range, (-1.0, 1.0));
     }
 
-    /// Add EMA overlay
-    pub fn ema(mut self, period: usize, color: &str) -> Self {
-        if self.bars.is_empty() || period == 0 {
-            return self;
+    #[test]
+    fn test_volume_indicators_render_in_subpanes() {
+        let bars = sample_bars(60);
+        let chart = Chart::new(800, 600)
+            .bars(&bars)
+            .obv()
+            .ad_line()
+            .cmf(20)
+            .mfi(14);
+        let obv = &chart.config.indicators[0];
+        assert!(obv.placement.is_subpane());
+        assert_eq!(obv.vectors[0].values, compute::obv(&bars));
+        assert!(chart.config.indicators[3].vectors[0].values[14].is_finite());
+
+        let elements = ChartRenderer::new(&chart.config, &chart.bars).render_elements();
+        for id in ["obv", "ad", "cmf_20", "mfi_14"] {
+            let commands = &elements
+                .iter()
+                .find(|(element, _)| *element == format!("indicator:{}", id))
+                .unwrap()
+                .1;
+            let line = commands.iter().find_map(|cmd| match cmd {
+                RenderCommand::Polyline { points, .. } => Some(points.len()),
+                _ => None,
+            });
+            assert!(line.unwrap_or(0) > 10, "{} draws no line", id);
         }
-        let values = calculate_ema(&self.bars, period);
-        let id = format!("ema_{}", period);
-        let mut indicator = Indicator::ema(&id, period as u32, color);
-        indicator.vectors[0].values = values;
-        self.config.indicators.push(indicator);
-        self
     }
 
-    /// Add Bollinger Bands overlay
-    pub fn bollinger(mut self, period: usize, multiplier: f64) -> Self {
-        if self.bars.is_empty() || period == 0 {
-            return self;
-        }
-        let (upper, middle, lower) = calculate_bollinger(&self.bars, period, multiplier);
-        let id = format!("bb_{}", period);
-        let mut indicator = Indicator::bollinger(&id, period as u32);
-        // Bollinger has 3 vectors: upper, middle, lower
-        if indicator.vectors.len() >= 3 {
-            indicator.vectors[0].values = upper;
-            indicator.vectors[1].values = middle;
-            indicator.vectors[2].values = lower;
-        }
-        self.config.indicators.push(indicator);
-        self
+    #[test]
+    fn test_subpane_title_shows_name_and_latest_value() {
+        let bars = sample_bars(60);
+        let chart = Chart::new(800, 600).bars(&bars).rsi(14);
+        let rsi = &chart.config.indicators[0];
+        let latest = format_indicator_value(rsi.vectors[0].values[59]);
+        let color = rsi.vectors[0].style.primary_color().to_lowercase();
+        let svg = chart.render_svg();
+
+        // 0.15 subpane below a 484.5px main pane, plus the 4px gap
+        let pane_top = 570.0 * 0.85 + 4.0;
+        let text_at = |text: &str| -> (f64, f64, String) {
+            let line = svg
+                .lines()
+                .find(|l| l.starts_with("<text") && l.ends_with(&format!(">{text}</text>")))
+                .unwrap_or_else(|| panic!("no {text}"));
+            let attr = |name: &str| line.split(&format!(" {name}=\"")).nth(1).unwrap();
+            let num = |name: &str| attr(name).split('"').next().unwrap().parse().unwrap();
+            (
+                num("x"),
+                num("y"),
+                attr("fill").split('"').next().unwrap().to_string(),
+            )
+        };
+        let (name_x, name_y, _) = text_at("RSI 14");
+        assert!(name_x < 20.0);
+        assert!(name_y > pane_top && name_y < pane_top + 20.0);
+
+        let (value_x, value_y, value_fill) = text_at(&latest);
+        assert!(value_x > name_x && value_x < 100.0);
+        assert_eq!(value_y, name_y);
+        assert_eq!(value_fill, color);
+
+        // Other corners follow the legend position; titles can be turned off
+        let mut config = chart.config.clone();
+        config.legend_position = LegendPosition::BottomRight;
+        let svg = ChartRenderer::new(&config, &chart.bars).render_svg();
+        let line = svg.lines().find(|l| l.ends_with(">RSI 14</text>")).unwrap();
+        let x: f64 = line
+            .split(" x=\"")
+            .nth(1)
+            .unwrap()
+            .split('"')
+            .next()
+            .unwrap()
+            .parse()
+            .unwrap();
+        assert!(x > 600.0);
+        config.show_pane_titles = false;
+        let svg = ChartRenderer::new(&config, &chart.bars).render_svg();
+        assert!(!svg.contains(">RSI 14</text>"));
     }
 
-    /// Add custom overlay with values
-    pub fn overlay(mut self, name: &str, values: Vec<f64>, color: &str) -> Self {
-        use crate::model::{IndicatorRange, IndicatorVector, VectorStyle};
-        let id = format!("custom_{}", name.to_lowercase().replace(' ', "_"));
-        let indicator = Indicator::new(&id, name)
-            .overlay()
-            .range(IndicatorRange::Auto)
-            .add_vector(
-                IndicatorVector::new(name, VectorStyle::line(color, 1.5)).with_values(values),
-            );
-        self.config.indicators.push(indicator);
-        self
-    }
+    /// Main-pane price scale labels (above the MACD subpane)
+    #[cfg(feature = "raster")]
+    #[test]
+    fn test_render_rgba_premultiplied_buffer() {
+        let bars = sample_bars(30);
+        let chart = Chart::new(200, 120)
+            .bars(&bars)
+            .candlesticks()
+            .background("#204060")
+            .dpr(2.0);
 
-    // =========================================================================
-    // Subpane Indicators
-    // =========================================================================
+        let (pixels, width, height) = chart.render_rgba();
+        assert_eq!((width, height), (400, 240));
+        assert_eq!(pixels.len(), (width * height * 4) as usize);
+        // Opaque background: premultiplied and straight alpha agree
+        assert_eq!(pixels[..4], [0x20, 0x40, 0x60, 0xff]);
 
-    /// Add RSI indicator
-    pub fn rsi(mut self, period: usize) -> Self {
-        if self.bars.is_empty() || period == 0 {
-            return self;
-        }
-        let values = calculate_rsi(&self.bars, period);
-        let id = format!("rsi_{}", period);
-        let mut indicator = Indicator::rsi(&id, period as u32);
-        indicator.vectors[0].values = values;
-        self.config.indicators.push(indicator);
-        self
+        // Something other than the background is drawn (candles)
+        assert!(pixels.chunks(4).any(|p| p != [0x20, 0x40, 0x60, 0xff]));
+
+        let png = chart.render_png().unwrap();
+        assert_eq!(&png[..8], b"\x89PNG\r\n\x1a\n");
     }
 
-    /// Add MACD indicator
-    pub fn macd(mut self, fast: usize, slow: usize, signal: usize) -> Self {
-        if self.bars.is_empty() {
-            return self;
-        }
-        let (macd_line, signal_line, histogram) = calculate_macd(&self.bars, fast, slow, signal);
-        let id = format!("macd_{}_{}", fast, slow);
-        let mut indicator = Indicator::macd(&id, fast as u32, slow as u32, signal as u32);
-        // MACD has 3 vectors: MACD line, Signal line, Histogram
-        if indicator.vectors.len() >= 3 {
-            indicator.vectors[0].values = macd_line;
-            indicator.vectors[1].values = signal_line;
-            indicator.vectors[2].values = histogram;
-        }
-        self.config.indicators.push(indicator);
-        self
+    #[test]
+    fn test_future_anchored_primitives_render_in_right_offset() {
+        let bars = sample_bars(40);
+        let last = bars.last().unwrap().timestamp;
+        let interval = bars[1].timestamp - bars[0].timestamp;
+        let line_x = |svg: &str, color: &str| -> f64 {
+            let line = svg
+                .lines()
+                .find(|l| l.starts_with("<path") && l.contains(color))
+                .unwrap();
+            let d = line.split("d=\"M").nth(1).unwrap();
+            d.split(' ').next().unwrap().parse().unwrap()
+        };
+
+        // 10 bars past the end, by index and by time, in a 12-bar gutter
+        let svg = Chart::new(800, 600)
+            .bars(&bars)
+            .candlesticks()
+            .right_offset(12.0)
+            .primitive(PrimitiveConfig::vertical_line(49.0).with_color("#aa0001"))
+            .primitive(
+                PrimitiveConfig::vertical_line_at(last + 10 * interval).with_color("#aa0002"),
+            )
+            .render_svg();
+
+        let chart_width = 800.0 - PRICE_SCALE_WIDTH;
+        let spacing = chart_width / 52.0;
+        let last_bar_x = spacing * 39.5;
+        let by_index = line_x(&svg, "#aa0001");
+        let by_time = line_x(&svg, "#aa0002");
+        assert!(by_index > last_bar_x && by_index < chart_width);
+        assert!((by_index - spacing * 49.5).abs() < 1.0);
+        assert!((by_time - by_index).abs() < 0.01);
+
+        // Drawn inside the main-pane clip so overflow stops at the scale
+        let clip = svg.find("<clipPath").unwrap();
+        assert!(svg[clip..].contains(&format!(r#"width="{:.2}""#, chart_width)));
     }
 
-    /// Add Volume indicator
-    pub fn volume(mut self) -> Self {
-        if self.bars.is_empty() {
-            return self;
+    #[test]
+    fn test_arcs_render_as_svg_arc_segments() {
+        let bars = sample_bars(40);
+        let svg = Chart::new(800, 600)
+            .bars(&bars)
+            .primitive(
+                PrimitiveConfig::arc((10.0, 100.0), (20.0, 100.0), (10.0, 105.0))
+                    .with_color("#aa0001"),
+            )
+            .primitive(PrimitiveConfig::fib_arcs((10.0, 95.0), (25.0, 100.0)).with_color("#aa0002"))
+            .primitive(
+                PrimitiveConfig::fib_circles((30.0, 100.0), (33.0, 101.0)).with_color("#aa0003"),
+            )
+            .render_svg();
+        let paths = |color: &str| -> Vec<String> {
+            svg.lines()
+                .filter(|l| l.starts_with("<path") && l.contains(color))
+                .map(|l| {
+                    l.split(r#"d=""#)
+                        .nth(1)
+                        .unwrap()
+                        .split('"')
+                        .next()
+                        .unwrap()
+                        .into()
+                })
+                .collect()
+        };
+        let radius = |d: &str| -> f64 {
+            let arc = d.split('A').nth(1).unwrap();
+            arc.split(' ').next().unwrap().parse().unwrap()
+        };
+
+        // One arc segment rather than a polyline
+        let arc = paths("#aa0001");
+        assert_eq!(arc.len(), 1);
+        assert_eq!(arc[0].matches('A').count(), 1, "{}", arc[0]);
+        assert!(!arc[0].contains('L'));
+
+        // Concentric semicircles at the fib ratios of the baseline length
+        let levels = [0.236, 0.382, 0.5, 0.618, 0.786, 1.0];
+        let fib: Vec<String> = paths("#aa0002")
+            .into_iter()
+            .filter(|d| d.contains('A'))
+            .collect();
+        assert_eq!(fib.len(), levels.len());
+        let outer = radius(fib.last().unwrap());
+        for (d, level) in fib.iter().zip(levels) {
+            assert_eq!(d.matches('A').count(), 1);
+            assert!((radius(d) / outer - level).abs() < 0.01, "{}", d);
         }
-        let values: Vec<f64> = self.bars.iter().map(|b| b.volume).collect();
-        let directions: Vec<bool> = self.bars.iter().map(|b| b.close >= b.open).collect();
-        let mut indicator = Indicator::volume("volume");
-        indicator.vectors[0].values = values;
-        indicator.vectors[0].directions = directions;
-        self.config.indicators.push(indicator);
-        self
-    }
 
-    /// Add a pre-configured indicator
-    pub fn indicator(mut self, indicator: Indicator) -> Self {
-        self.config.indicators.push(indicator);
-        self
+        // Full ellipses close in two half turns, since SVG can't draw an
+        // arc whose ends meet
+        let circles = paths("#aa0003");
+        assert!(!circles.is_empty());
+        assert!(circles.iter().all(|d| d.matches('A').count() == 2));
     }
 
-    // =========================================================================
-    // Primitives
-    // =========================================================================
+    #[test]
+    fn test_forecast_path_cone_fills_future_slots() {
+        let bars = sample_bars(40);
+        let last = bars[39].close;
+        let n = 10;
+        let median: Vec<f64> = (1..=n).map(|k| last + k as f64 * 0.2).collect();
+        let upper: Vec<f64> = median
+            .iter()
+            .enumerate()
+            .map(|(k, m)| m + k as f64 * 0.3)
+            .collect();
+        let lower: Vec<f64> = median
+            .iter()
+            .enumerate()
+            .map(|(k, m)| m - k as f64 * 0.3)
+            .collect();
+        let mut forecast = PrimitiveConfig::forecast_path(39.0, &median, &upper, &lower);
+        forecast.color = "#aa0001".into();
+        forecast.fill_color = Some("#aa0002".into());
+        forecast.fill_opacity = Some(0.3);
+        let svg = Chart::new(800, 600)
+            .bars(&bars)
+            .candlesticks()
+            .right_offset(12.0)
+            .primitive(forecast)
+            .render_svg();
 
-    /// Add a primitive drawing
-    pub fn primitive(mut self, primitive: PrimitiveConfig) -> Self {
-        self.config.primitives.push(primitive);
-        self
+        let spacing = (800.0 - PRICE_SCALE_WIDTH) / 52.0;
+        let cone = svg
+            .lines()
+            .find(|l| l.starts_with("<path") && l.contains(r##"fill="#aa0002""##))
+            .unwrap();
+        assert!(cone.contains(r#"fill-opacity="0.30""#), "{cone}");
+        let d = cone
+            .split("d=\"")
+            .nth(1)
+            .unwrap()
+            .split('"')
+            .next()
+            .unwrap();
+        let numbers: Vec<f64> = d
+            .split(|c: char| c.is_ascii_alphabetic() || c.is_whitespace() || c == ',')
+            .filter_map(|t| t.parse().ok())
+            .collect();
+        let xs: Vec<f64> = numbers.chunks(2).map(|p| p[0]).collect();
+        assert_eq!(xs.len(), 2 * n);
+        // One vertex per future slot along each edge, bars 40 to 49
+        for (k, x) in xs[..n].iter().enumerate() {
+            assert!((x - spacing * (40.5 + k as f64)).abs() < 0.01, "{x}");
+        }
+        assert_eq!(xs[n], xs[n - 1]);
+        assert_eq!(xs[2 * n - 1], xs[0]);
+
+        // The median is dashed on top of the cone
+        let line = svg
+            .lines()
+            .find(|l| l.starts_with("<path") && l.contains(r##"stroke="#aa0001""##))
+            .unwrap();
+        assert!(line.contains("stroke-dasharray"));
+        assert!(svg.find(cone).unwrap() < svg.find(line).unwrap());
     }
 
-    /// Add multiple primitives
-    pub fn primitives(mut self, primitives: Vec<PrimitiveConfig>) -> Self {
-        self.config.primitives.extend(primitives);
-        self
-    }
+    #[test]
+    fn test_proportional_time_spacing_opens_weekend_gaps() {
+        // Two trading weeks of daily bars from Monday 2024-01-01
+        let day = 86_400;
+        let bars: Vec<Bar> = (0..14)
+            .filter(|d| d % 7 < 5)
+            .map(|d| Bar::new(1_704_067_200 + d * day, 100.0, 101.0, 99.0, 100.5))
+            .collect();
+        let line_x = |svg: &str, color: &str| -> f64 {
+            let line = svg
+                .lines()
+                .find(|l| l.starts_with("<path") && l.contains(color))
+                .unwrap();
+            let d = line.split("d=\"M").nth(1).unwrap();
+            d.split(' ').next().unwrap().parse().unwrap()
+        };
+        // Bar-index anchored lines on Tue, Wed, Fri, Mon and between Fri and Mon
+        let marks = [(1.0, "#aa0001"), (2.0, "#aa0002"), (4.0, "#aa0004")];
+        let marks = marks
+            .into_iter()
+            .chain([(5.0, "#aa0005"), (4.5, "#aa0045")]);
+        let xs = |spacing: TimeSpacing| {
+            let mut chart = Chart::new(800, 600).bars(&bars).time_spacing(spacing);
+            for (bar, color) in marks.clone() {
+                chart = chart.primitive(PrimitiveConfig::vertical_line(bar).with_color(color));
+            }
+            let svg = chart.render_svg();
+            marks
+                .clone()
+                .map(|(_, color)| line_x(&svg, color))
+                .collect::<Vec<_>>()
+        };
 
-    // =========================================================================
-    // Signals
-    // =========================================================================
+        let [tue, wed, fri, mon, between] = xs(TimeSpacing::Proportional)[..] else {
+            unreachable!()
+        };
+        assert!(mon - fri > 2.5 * (wed - tue));
+        assert!((between - (fri + mon) / 2.0).abs() < 0.01);
 
-    /// Add a signal marker
-    pub fn signal(mut self, signal: SignalConfig) -> Self {
-        self.config.signals.push(signal);
-        self
-    }
+        let [tue, wed, fri, mon, _] = xs(TimeSpacing::Indexed)[..] else {
+            unreachable!()
+        };
+        assert!(((mon - fri) - (wed - tue)).abs() < 0.01);
 
-    /// Add multiple signals
-    pub fn signals(mut self, signals: Vec<SignalConfig>) -> Self {
-        self.config.signals.extend(signals);
-        self
+        // Ten bars over 12 slots: one per day, weekends collapsed to one step
+        let config = ChartConfig {
+            time_spacing: TimeSpacing::Proportional,
+            ..Default::default()
+        };
+        let axis = BarAxis::new(&bars, &config, 600.0, 0.0);
+        assert_eq!(axis.spacing, 50.0);
+        assert_eq!(axis.slot(5), 7.0);
+        assert_eq!(axis.slot(11), 13.0);
     }
 
-    // =========================================================================
-    // Build & Render
-    // =========================================================================
+    #[test]
+    fn test_render_frames_replays_one_bar_per_frame() {
+        let bars = sample_bars(30);
+        let chart = Chart::new(800, 600)
+            .bars(&bars)
+            .candlesticks()
+            .sma(5, "#2962ff")
+            .replay_lock_scale(true);
+        let bodies = |svg: &str| {
+            let start = svg.find(r#"<g clip-path="url(#clip0)">"#).unwrap();
+            let end = start + svg[start..].find("</g>").unwrap();
+            svg[start..end].matches("<rect").count()
+        };
 
-    /// Get the built ChartConfig
-    pub fn build(self) -> (ChartConfig, Vec<Bar>) {
-        (self.config, self.bars)
+        let frames = chart.render_frames(0..5);
+        assert_eq!(frames.len(), 5);
+        let full = chart.render_svg();
+        for (k, frame) in frames.iter().enumerate() {
+            assert_eq!(bodies(frame), k + 1);
+            // Locked scale: same price labels as the full chart
+            assert_eq!(svg_price_labels(frame), svg_price_labels(&full));
+        }
+
+        // Bars keep their slots: the first body sits where it does in the
+        // full chart
+        let first_body = |svg: &str| {
+            let start = svg.find(r#"<g clip-path="url(#clip0)">"#).unwrap();
+            let rect = start + svg[start..].find("<rect").unwrap();
+            svg[rect..].lines().next().unwrap().to_string()
+        };
+        assert_eq!(first_body(&frames[0]), first_body(&full));
+
+        // Without the lock the scale follows the visible bars
+        let unlocked = chart.replay_lock_scale(false).render_frames(0..1);
+        assert_ne!(svg_price_labels(&unlocked[0]), svg_price_labels(&full));
+        assert!(
+            Chart::new(800, 600)
+                .bars(&bars)
+                .render_frames(40..45)
+                .is_empty()
+        );
     }
 
-    /// Render directly to SVG string
-    pub fn render_svg(&self) -> String {
-        ChartRenderer::new(&self.config, &self.bars).render_svg()
+    fn svg_price_labels(svg: &str) -> Vec<String> {
+        svg.lines()
+            .filter(|l| l.starts_with(r#"<text x="736.00""#))
+            .filter(|l| l.split('"').nth(3).and_then(|y| y.parse::<f64>().ok()) < Some(400.0))
+            .filter_map(|l| l.split('>').nth(1)?.split('<').next().map(String::from))
+            .collect()
     }
-}
 
-// =============================================================================
-// Indicator Calculations (same as before)
-// =============================================================================
+    #[test]
+    fn test_hover_bar_band_and_outline() {
+        let bars = sample_bars(50);
+        let chart = Chart::new(800, 600)
+            .bars(&bars)
+            .candlesticks()
+            .rsi(14)
+            .hover_bar(Some(10));
+        let svg = chart.render_svg();
+
+        // Column of bar 10 across the main pane and the RSI subpane
+        // Band edges snap to whole pixels, the 1px outline to half pixels
+        let spacing: f64 = 730.0 / 50.0;
+        let band = format!(
+            r##"<rect x="{:.2}" y="0.00" width="{:.2}" height="570.00" fill="#758696" fill-opacity="0.12""##,
+            (10.0 * spacing).round(),
+            (11.0 * spacing).round() - (10.0 * spacing).round()
+        );
+        assert!(svg.contains(&band));
+        let outline = format!(
+            r#"<rect x="{:.2}""#,
+            crate::render::engine::crisp_coord(10.5 * spacing - spacing * 0.4, 1.0)
+        );
+        assert!(svg.lines().any(|l| l.starts_with(&outline)
+            && l.contains(r#"fill="none""#)
+            && l.contains("#758696")));
 
-fn calculate_sma(bars: &[Bar], period: usize) -> Vec<f64> {
-    let mut result = vec![f64::NAN; bars.len()];
+        let cleared = chart.hover_bar(None).render_svg();
+        assert!(!cleared.contains("#758696"));
 
-    for i in (period - 1)..bars.len() {
-        let sum: f64 = bars[i + 1 - period..=i].iter().map(|b| b.close).sum();
-        result[i] = sum / period as f64;
+        // The accent follows the chart theme's crosshair color
+        let light = Chart::new(800, 600)
+            .bars(&bars)
+            .candlesticks()
+            .theme(ThemeConfig::light())
+            .hover_bar(Some(10))
+            .render_svg();
+        assert!(!light.contains("#758696"));
+        assert!(light.contains(r##"fill="#9598a1" fill-opacity="0.12""##));
     }
 
-    result
-}
+    #[test]
+    fn test_custom_registry_is_per_chart() {
+        use crate::primitives::PrimitiveTrait;
+        use crate::primitives::catalog::lines::horizontal_line::{self, HorizontalLine};
+
+        fn support(points: &[(f64, f64)], _color: &str) -> Box<dyn PrimitiveTrait> {
+            Box::new(HorizontalLine::new(points[0].1, "#123456"))
+        }
+
+        let mut registry = PrimitiveRegistry::with_defaults();
+        registry.register("support", support, horizontal_line::metadata());
+        assert_eq!(registry.all().last().map(|m| m.type_id), Some("support"));
+        assert!(PrimitiveRegistry::global().get("support").is_none());
 
-fn calculate_ema(bars: &[Bar], period: usize) -> Vec<f64> {
-    let mut result = vec![f64::NAN; bars.len()];
-    let multiplier = 2.0 / (period as f64 + 1.0);
+        let bars = sample_bars(50);
+        let level = bars[25].close;
+        let plain = Chart::new(800, 600)
+            .bars(&bars)
+            .primitive(PrimitiveConfig::new("support", vec![(0.0, level)]));
+        assert!(!plain.render_svg().contains("#123456"));
 
-    if bars.len() >= period {
-        let sum: f64 = bars[0..period].iter().map(|b| b.close).sum();
-        result[period - 1] = sum / period as f64;
+        let custom = Chart::new(800, 600)
+            .bars(&bars)
+            .primitive(PrimitiveConfig::new("support", vec![(0.0, level)]))
+            .registry(registry.clone());
+        assert!(custom.render_svg().contains("#123456"));
 
-        for i in period..bars.len() {
-            result[i] = (bars[i].close - result[i - 1]) * multiplier + result[i - 1];
-        }
+        // An explicit renderer registry wins over the config's
+        let svg = ChartRenderer::new(&plain.config, &plain.bars)
+            .with_registry(&registry)
+            .render_svg();
+        assert!(svg.contains("#123456"));
     }
 
-    result
-}
+    #[test]
+    fn test_composite_liquidity_sweep() {
+        // Swept level from p0 to p1, zone box, midline and a label
+        let spec = CompositeSpec::from_json(
+            r##"{
+                "type_id": "liquidity_sweep",
+                "display_name": "Liquidity Sweep",
+                "points": 2,
+                "ops": [
+                    {"op": "rect", "from": {"x": "p0.x", "y": "p0.y"},
+                     "to": {"x": "p1.x", "y": "p1.y"}, "fill_opacity": 0.1},
+                    {"op": "line", "from": {"x": "p0.x", "y": "p0.y"},
+                     "to": {"x": "p1.x", "y": "p0.y"}, "color": "#ff9800"},
+                    {"op": "line", "from": {"x": "p0.x", "y": "lerp(p0.y, p1.y, 0.5)"},
+                     "to": {"x": "p1.x", "y": "lerp(p0.y, p1.y, 0.5)"}, "dashed": true},
+                    {"op": "text", "at": {"x": "p1.x + 1", "y": "p0.y"}, "text": "Sweep"}
+                ]
+            }"##,
+        )
+        .unwrap();
 
-fn calculate_bollinger(
-    bars: &[Bar],
-    period: usize,
-    multiplier: f64,
-) -> (Vec<f64>, Vec<f64>, Vec<f64>) {
-    let mut upper = vec![f64::NAN; bars.len()];
-    let mut middle = vec![f64::NAN; bars.len()];
-    let mut lower = vec![f64::NAN; bars.len()];
+        let bars = sample_bars(50);
+        let (low, high) = (bars[20].low, bars[30].high);
+        let mut chart = Chart::new(800, 600).bars(&bars);
+        chart.register_composite(spec).unwrap();
+        assert_eq!(
+            chart
+                .primitive_registry()
+                .get("liquidity_sweep")
+                .map(|m| m.display_name),
+            Some("Liquidity Sweep")
+        );
+        assert!(PrimitiveRegistry::global().get("liquidity_sweep").is_none());
 
-    for i in (period - 1)..bars.len() {
-        let slice: Vec<f64> = bars[i + 1 - period..=i].iter().map(|b| b.close).collect();
-        let mean = slice.iter().sum::<f64>() / period as f64;
-        let variance = slice.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / period as f64;
-        let stddev = variance.sqrt();
+        let svg = chart
+            .primitive(
+                PrimitiveConfig::new("liquidity_sweep", vec![(20.0, low), (30.0, high)])
+                    .with_color("#2962ff"),
+            )
+            .render_svg();
+        assert!(svg.contains(r##"fill="#2962ff" fill-opacity="0.10""##));
+        assert!(svg.contains(r##"stroke="#ff9800""##));
+        assert!(svg.contains("stroke-dasharray"));
+        assert!(svg.contains(">Sweep</text>"));
+    }
 
-        middle[i] = mean;
-        upper[i] = mean + multiplier * stddev;
-        lower[i] = mean - multiplier * stddev;
+    #[test]
+    fn test_primitive_text_measured_with_font_table() {
+        let mut backend = SvgBackend::new(800, 600, 1.0);
+        let bar_to_x = |i: usize| i as f64;
+        let price_to_y = |p: f64| p;
+        let mut ctx =
+            SvgRenderContext::new(&mut backend, &bar_to_x, &price_to_y, 1.0, 800.0, 600.0);
+
+        let label = "Liquidity sweep above 1.0845 (Asia high)";
+        ctx.set_font("italic bold 12px sans-serif");
+        let expected = crate::render::engine::estimate_text_width(label, 12.0, FontWeight::Bold);
+        assert_eq!(ctx.measure_text(label), expected);
+
+        ctx.set_font("14px sans-serif");
+        assert_eq!(
+            ctx.measure_text("Hi"),
+            crate::render::engine::estimate_text_width("Hi", 14.0, FontWeight::Normal)
+        );
     }
 
-    (upper, middle, lower)
-}
+    #[test]
+    fn test_dpr2_lines_land_on_device_pixels() {
+        let bars = sample_bars(60);
+        let svg = Chart::new(800, 600)
+            .dpr(2.0)
+            .bars(&bars)
+            .candlesticks()
+            .rsi(14)
+            .macd(12, 26, 9)
+            .volume()
+            .last_price_line(true)
+            .hover_bar(Some(20))
+            .render_svg();
 
-fn calculate_rsi(bars: &[Bar], period: usize) -> Vec<f64> {
-    let mut result = vec![f64::NAN; bars.len()];
+        let attr = |line: &str, name: &str| -> f64 {
+            let start = line.find(&format!(" {name}=\"")).unwrap() + name.len() + 3;
+            let end = start + line[start..].find('"').unwrap();
+            line[start..end].parse().unwrap()
+        };
 
-    if bars.len() < period + 1 {
-        return result;
-    }
+        // A 1px line at dpr 2 covers two device pixels, so it is centered
+        // on a device pixel boundary: a multiple of half a logical pixel
+        let mut checked = 0;
+        for line in svg.lines().filter(|l| l.starts_with("<line")) {
+            if !line.contains(r#"stroke-width="1.00""#) {
+                continue;
+            }
+            let (x1, y1, x2, y2) = (
+                attr(line, "x1"),
+                attr(line, "y1"),
+                attr(line, "x2"),
+                attr(line, "y2"),
+            );
+            if x1 == x2 {
+                assert_eq!((x1 * 2.0).fract(), 0.0, "{line}");
+                checked += 1;
+            } else if y1 == y2 {
+                assert_eq!((y1 * 2.0).fract(), 0.0, "{line}");
+                checked += 1;
+            }
+        }
+        assert!(checked > 20);
 
-    let mut gains = Vec::new();
-    let mut losses = Vec::new();
+        // The price-area clip ends on a device pixel too
+        let clip = &svg[svg.find("<clipPath").unwrap()..];
+        let clip = &clip[..clip.find("/>").unwrap()];
+        for name in ["x", "y", "width", "height"] {
+            assert_eq!((attr(clip, name) * 2.0).fract(), 0.0);
+        }
 
-    for i in 1..bars.len() {
-        let change = bars[i].close - bars[i - 1].close;
-        if change > 0.0 {
-            gains.push(change);
-            losses.push(0.0);
-        } else {
-            gains.push(0.0);
-            losses.push(-change);
+        // Multichart cells snap candle bodies at the same dpr, so their
+        // edges land on device pixels and widths may be half a pixel
+        let (config, bars) = Chart::new(800, 600).bars(&bars).candlesticks().build();
+        let svg = MultichartRenderer::new(&MultichartLayout::grid(2, 2), 801, 603)
+            .dpr(2.0)
+            .chart(&config, &bars)
+            .render_svg();
+        let bodies: Vec<(f64, f64)> = svg
+            .lines()
+            .filter(|l| l.starts_with("<rect") && l.contains(r##"fill="#26a69a""##))
+            .map(|l| (attr(l, "x"), attr(l, "width")))
+            .collect();
+        assert!(bodies.len() > 20);
+        for &(x, width) in &bodies {
+            assert_eq!((x * 2.0).fract(), 0.0);
+            assert_eq!((width * 2.0).fract(), 0.0);
         }
+        assert!(bodies.iter().any(|&(_, width)| width.fract() == 0.5));
     }
 
-    let first_avg_gain: f64 = gains[0..period].iter().sum::<f64>() / period as f64;
-    let first_avg_loss: f64 = losses[0..period].iter().sum::<f64>() / period as f64;
-
-    let mut avg_gain = first_avg_gain;
-    let mut avg_loss = first_avg_loss;
+    #[test]
+    fn test_grouped_legend_and_hidden_group() {
+        let bars = sample_bars(60);
+        let mut chart = Chart::new(800, 600)
+            .bars(&bars)
+            .candlesticks()
+            .sma(50, "#9c27b0")
+            .ema(9, "#f44336")
+            .ema(21, "#4caf50")
+            .ema(34, "#ffeb3b")
+            .indicator_group("ema_9", "EMAs")
+            .indicator_group("ema_21", "EMAs")
+            .indicator_group("ema_34", "EMAs")
+            .legend(true);
+
+        let svg = chart.render_svg();
+        let text_y = |label: &str| -> f64 {
+            let line = svg
+                .lines()
+                .find(|l| l.contains(&format!(">{label}</text>")))
+                .unwrap();
+            let start = line.find(" y=\"").unwrap() + 4;
+            line[start..start + line[start..].find('"').unwrap()]
+                .parse()
+                .unwrap()
+        };
+        assert_eq!(svg.matches(">EMAs</text>").count(), 1);
+        // Members follow their header, indented, one row each
+        let header = text_y("EMAs");
+        for (row, label) in ["EMA 9", "EMA 21", "EMA 34"].iter().enumerate() {
+            assert_eq!(text_y(label), header + 16.0 * (row + 1) as f64);
+            let entry = svg
+                .lines()
+                .find(|l| l.contains(&format!(">{label}</text>")))
+                .unwrap();
+            assert!(entry.starts_with(r#"<text x="40.00""#));
+        }
+        assert!(text_y("SMA 50") < header);
+
+        chart.set_group_visible("EMAs", false);
+        let hidden = chart.render_svg();
+        assert!(!hidden.contains(">EMAs</text>"));
+        for (label, color) in [
+            ("EMA 9", "#f44336"),
+            ("EMA 21", "#4caf50"),
+            ("EMA 34", "#ffeb3b"),
+        ] {
+            assert!(!hidden.contains(&format!(">{label}</text>")));
+            assert!(!hidden.contains(color));
+        }
+        assert!(hidden.contains(">SMA 50</text>"));
+        assert_eq!(chart.bar_info(40).unwrap().indicators.len(), 1);
 
-    result[period] = if avg_loss == 0.0 {
-        100.0
-    } else {
-        100.0 - 100.0 / (1.0 + avg_gain / avg_loss)
-    };
+        chart.set_group_visible("EMAs", true);
+        assert_eq!(chart.render_svg(), svg);
+    }
 
-    for i in (period + 1)..bars.len() {
-        avg_gain = (avg_gain * (period as f64 - 1.0) + gains[i - 1]) / period as f64;
-        avg_loss = (avg_loss * (period as f64 - 1.0) + losses[i - 1]) / period as f64;
-        result[i] = if avg_loss == 0.0 {
-            100.0
-        } else {
-            100.0 - 100.0 / (1.0 + avg_gain / avg_loss)
+    #[test]
+    fn test_degenerate_sizes_never_panic_or_emit_nan() {
+        let bars = sample_bars(30);
+        let chart = |width, height, dpr| {
+            Chart::new(width, height)
+                .dpr(dpr)
+                .bars(&bars)
+                .candlesticks()
+                .sma(5, "#2196f3")
+                .rsi(14)
+                .macd(12, 26, 9)
         };
+        let clean = |svg: &str| !svg.contains("NaN") && !svg.contains("inf");
+        for width in 0..50 {
+            for height in 0..50 {
+                let chart = chart(width, height, 1.0);
+                assert!(clean(&chart.render_svg()), "{}x{}", width, height);
+                assert!(matches!(
+                    chart.try_render_svg(),
+                    Err(ChartError::InvalidDimensions { .. })
+                ));
+            }
+        }
+
+        // Around the minimum, where the plot is a few pixels across
+        let (min_width, min_height) = ChartConfig::default().min_size();
+        assert_eq!((min_width, min_height), (156, 76));
+        for width in min_width - 2..min_width + 40 {
+            for height in min_height - 2..min_height + 40 {
+                let svg = chart(width, height, 1.0).render_svg();
+                assert!(clean(&svg), "{}x{}", width, height);
+            }
+        }
+        for dpr in [0.0, -1.0, f64::NAN, f64::INFINITY, 100.0] {
+            let chart = chart(800, 600, dpr);
+            assert!((0.1..=8.0).contains(&chart.config.pixel_ratio()));
+            let svg = chart.try_render_svg().unwrap();
+            assert!(clean(&svg), "dpr {}", dpr);
+        }
+        assert!(Chart::try_new(0, 600).is_err());
+        assert!(Chart::try_new(800, 600).is_ok());
+
+        let (_, stats) = chart(10, 10, 1.0).render_svg_with_stats();
+        assert!(stats.warnings.contains(&ConfigWarning::InvalidDimensions {
+            width: 10,
+            height: 10
+        }));
     }
 
-    result
-}
+    #[test]
+    fn test_render_stats_collect_config_warnings() {
+        let bars = sample_bars(50);
+        let chart = Chart::new(800, 600)
+            .bars(&bars)
+            .candlesticks()
+            .sma(200, "#2196F3")
+            .sma(20, "#ff9800");
+
+        let warnings = chart.validate();
+        assert_eq!(
+            warnings,
+            vec![ConfigWarning::IndicatorWithoutValues {
+                id: "sma_200".into(),
+                bars: 50
+            }]
+        );
+        let (_, stats) = chart.render_svg_with_stats();
+        assert_eq!(stats.warnings, warnings);
+
+        // Replay and scrolling trim what is drawn, not what is checked: a
+        // signal past the replayed bars is still on the data
+        let replayed = chart
+            .signal(SignalConfig::buy(45, 100.0))
+            .min_bar_spacing(30.0)
+            .replay_to(30);
+        let (_, stats) = replayed.render_svg_with_stats();
+        assert_eq!(stats.warnings, warnings);
+    }
 
-fn calculate_macd(
-    bars: &[Bar],
-    fast: usize,
-    slow: usize,
-    signal: usize,
-) -> (Vec<f64>, Vec<f64>, Vec<f64>) {
-    let fast_ema = calculate_ema(bars, fast);
-    let slow_ema = calculate_ema(bars, slow);
-
-    let macd_line: Vec<f64> = fast_ema
-        .iter()
-        .zip(slow_ema.iter())
-        .map(|(&f, &s)| {
-            if f.is_nan() || s.is_nan() {
-                f64::NAN
-            } else {
-                f - s
-            }
-        })
-        .collect();
+    #[test]
+    fn test_toggled_off_indicator_keeps_greyed_legend_row() {
+        let bars = sample_bars(60);
+        let high = Indicator::line("high", "High", "#ff9800").values(vec![500.0; 60]);
+        let mut chart = Chart::new(800, 600)
+            .bars(&bars)
+            .candlesticks()
+            .ema(9, "#f44336")
+            .indicator(high)
+            .legend(true);
+        let shown = chart.render_svg();
+        let bounds =
+            |chart: &Chart| ChartRenderer::new(&chart.config, &chart.bars).main_price_bounds();
+        assert!(bounds(&chart).1 > 500.0);
+
+        chart.set_indicator_visible("ema_9", false);
+        chart.set_indicator_visible("high", false);
+        let hidden = chart.render_svg();
+        let legend_row = |svg: &str, name: &str| {
+            svg.lines()
+                .find(|l| l.contains(&format!(">{name}</text>")))
+                .map(str::to_string)
+        };
+        assert!(shown.contains("<polyline") && shown.contains("#f44336"));
+        assert!(!hidden.contains("#f44336"));
+        let row = legend_row(&hidden, "EMA 9").expect("legend row kept");
+        assert_ne!(Some(row.clone()), legend_row(&shown, "EMA 9"), "{row}");
+        assert!(row.contains("0.40"), "{row}");
+
+        // The 500 line no longer stretches the scale
+        let (low, top) = bounds(&chart);
+        let bar_high = bars.iter().map(|b| b.high).fold(f64::MIN, f64::max);
+        assert!(top < 500.0 && top > bar_high, "{low} {top}");
+
+        let legend = chart.legend_data().unwrap();
+        assert!(legend.indicators.iter().all(|entry| !entry.visible));
+        assert!(chart.bar_info(30).unwrap().indicators.is_empty());
+
+        chart.set_indicator_visible("ema_9", true);
+        chart.set_indicator_visible("high", true);
+        assert_eq!(chart.render_svg(), shown);
+    }
 
-    let mut signal_line = vec![f64::NAN; bars.len()];
-    let multiplier = 2.0 / (signal as f64 + 1.0);
+    #[test]
+    fn test_hidden_series_leaves_scale_to_overlays() {
+        let bars = sample_bars(60);
+        let mut chart = Chart::new(800, 600)
+            .bars(&bars)
+            .candlesticks()
+            .ema(9, "#f44336");
+        let candles = chart.render_svg();
+
+        chart.set_series_visible(false);
+        let svg = chart.render_svg();
+        assert!(svg.matches("<rect").count() < candles.matches("<rect").count());
+        let renderer = ChartRenderer::new(&chart.config, &chart.bars);
+        let ema = &chart.config.indicators[0].vectors[0].values;
+        let (low, high) = (
+            ema.iter()
+                .copied()
+                .filter(|v| v.is_finite())
+                .fold(f64::MAX, f64::min),
+            ema.iter()
+                .copied()
+                .filter(|v| v.is_finite())
+                .fold(f64::MIN, f64::max),
+        );
+        let pad = (high - low) * 0.05;
+        assert_eq!(renderer.main_price_bounds(), (low - pad, high + pad));
+
+        // With nothing else on the scale the bars still set it
+        chart.set_indicator_visible("ema_9", false);
+        let (config, data) = chart.build();
+        let (low, high) = ChartRenderer::new(&config, &data).main_price_bounds();
+        assert!(low < bars[0].low && high > bars[0].high);
+    }
 
-    let first_valid = macd_line
-        .iter()
-        .position(|&v| !v.is_nan())
-        .unwrap_or(bars.len());
+    #[test]
+    fn test_series_options_hide_series_and_drive_last_value() {
+        let bars = sample_bars(60);
+        // RSI bands reuse the up/down colors as lines; bodies are rects
+        let candle =
+            |l: &str| l.starts_with("<rect") && (l.contains("#26a69a") || l.contains("#ef5350"));
+        let hidden = Chart::new(800, 600)
+            .bars(&bars)
+            .candlesticks()
+            .ema(9, "#f44336")
+            .rsi(14)
+            .series_options(SeriesOptionsCommon {
+                visible: false,
+                ..Default::default()
+            })
+            .last_price_line(true);
+        let svg = hidden.render_svg();
+        assert!(!svg.lines().any(candle));
+        assert!(svg.contains("#f44336") && svg.contains("RSI"));
+        assert!(svg.lines().any(|l| l.starts_with(r#"<text x="736.00""#)));
+
+        // The title survives a later change of series type
+        let chart = Chart::new(800, 600)
+            .bars(&bars)
+            .series_title("BTCUSD")
+            .line()
+            .legend(true);
+        assert_eq!(
+            chart.legend_data().unwrap().series_name.as_deref(),
+            Some("BTCUSD")
+        );
+        assert!(chart.render_svg().contains(">BTCUSD<"));
+
+        // The scale background shares the tag's x; the tag is the other fill
+        let tag = |l: &str| l.starts_with(r#"<rect x="730.00""#) && !l.contains("#131722");
+        let last_price = |options: SeriesOptionsCommon| {
+            Chart::new(800, 600)
+                .bars(&bars)
+                .candlesticks()
+                .series_options(options)
+                .last_price_line(true)
+                .render_svg()
+        };
+        let no_label = last_price(SeriesOptionsCommon {
+            last_value_visible: false,
+            ..Default::default()
+        });
+        assert!(no_label.contains("stroke-dasharray"));
+        assert!(!no_label.lines().any(tag));
 
-    if first_valid + signal <= bars.len() {
-        let sum: f64 = macd_line[first_valid..(first_valid + signal)]
-            .iter()
-            .filter(|v| !v.is_nan())
-            .sum();
-        signal_line[first_valid + signal - 1] = sum / signal as f64;
+        let no_line = last_price(SeriesOptionsCommon {
+            price_line_visible: false,
+            ..Default::default()
+        });
+        assert!(!no_line.contains("stroke-dasharray"));
+        assert!(no_line.lines().any(tag));
+
+        let styled = last_price(SeriesOptionsCommon {
+            price_line_color: "#2962ff".to_string(),
+            price_line_style: crate::model::LineStyle::Solid,
+            price_line_width: 2,
+            ..Default::default()
+        });
+        let line = styled
+            .lines()
+            .find(|l| l.starts_with("<line") && l.contains("#2962ff"))
+            .unwrap();
+        assert!(line.contains(r#"stroke-width="2.00""#) && !line.contains("stroke-dasharray"));
+
+        // The default 1px dashed line keeps its 4px dashes
+        assert!(no_label.contains(r#"stroke-dasharray="4.00,4.00""#));
+
+        // A trailing bar without a close hides the line unless the source
+        // falls back to the last bar that has one
+        let mut open_bar = bars.clone();
+        let last = *open_bar.last().unwrap();
+        open_bar.push(Bar {
+            timestamp: last.timestamp + 60,
+            close: f64::NAN,
+            ..last
+        });
+        let source = |price_line_source: PriceLineSource| {
+            let chart = Chart::new(800, 600)
+                .bars(&open_bar)
+                .candlesticks()
+                .series_options(SeriesOptionsCommon {
+                    price_line_source,
+                    ..Default::default()
+                })
+                .last_price_line(true);
+            let (config, bars) = chart.build();
+            series_last_price(&config, &bars)
+        };
+        assert_eq!(source(PriceLineSource::LastBar), None);
+        assert_eq!(
+            source(PriceLineSource::LastVisible),
+            Some((last.close, last.close >= last.open))
+        );
 
-        for i in (first_valid + signal)..bars.len() {
-            if !macd_line[i].is_nan() && !signal_line[i - 1].is_nan() {
-                signal_line[i] =
-                    (macd_line[i] - signal_line[i - 1]) * multiplier + signal_line[i - 1];
-            }
-        }
+        // Cells of a multichart follow the same options
+        let cell = |options: SeriesOptionsCommon| {
+            let (config, bars) = Chart::new(800, 600)
+                .bars(&bars)
+                .candlesticks()
+                .series_options(options)
+                .last_price_line(true)
+                .build();
+            MultichartRenderer::new(&MultichartLayout::single(), 800, 600)
+                .chart(&config, &bars)
+                .render_svg()
+        };
+        let shown = cell(SeriesOptionsCommon::default());
+        assert!(shown.lines().any(candle));
+        assert!(shown.contains(r#"stroke-dasharray="4.00,4.00""#));
+
+        // Tick labels never sit under the cell's last-price tag, even when
+        // the last close lands on a round tick
+        let closes: Vec<f64> = (0..40)
+            .map(|i| 90.0 + i as f64 * 0.5)
+            .chain([100.0])
+            .collect();
+        let (config, round) = Chart::new(800, 600)
+            .bars(&bars_from_closes(&closes))
+            .candlesticks()
+            .last_price_line(true)
+            .build();
+        let tagged = MultichartRenderer::new(&MultichartLayout::single(), 800, 600)
+            .chart(&config, &round)
+            .render_svg();
+        let text_at = |l: &str| -> Option<(f64, f64)> {
+            let mut attrs = l.strip_prefix("<text ")?.split('"');
+            Some((attrs.nth(1)?.parse().ok()?, attrs.nth(1)?.parse().ok()?))
+        };
+        let (tag_x, tag_y) = tagged
+            .lines()
+            .filter(|l| l.contains("#ffffff"))
+            .find_map(text_at)
+            .unwrap();
+        let under_tag = tagged
+            .lines()
+            .filter(|l| !l.contains("#ffffff"))
+            .filter_map(text_at)
+            .filter(|&(x, y)| x == tag_x && (y - tag_y).abs() < 11.0)
+            .count();
+        assert_eq!(under_tag, 0);
+        let hidden = cell(SeriesOptionsCommon {
+            visible: false,
+            ..Default::default()
+        });
+        assert!(!hidden.lines().any(candle));
+        assert!(!hidden.contains("stroke-dasharray"));
     }
 
-    let histogram: Vec<f64> = macd_line
-        .iter()
-        .zip(signal_line.iter())
-        .map(|(&m, &s)| {
-            if m.is_nan() || s.is_nan() {
-                f64::NAN
-            } else {
-                m - s
+    #[test]
+    fn test_fitted_price_scale_follows_label_width() {
+        // Cent prices around 20 and around 123,400
+        let shifted = |factor: f64, offset: f64| -> Vec<Bar> {
+            sample_bars(60)
+                .into_iter()
+                .map(|b| Bar {
+                    open: b.open * factor + offset,
+                    high: b.high * factor + offset,
+                    low: b.low * factor + offset,
+                    close: b.close * factor + offset,
+                    ..b
+                })
+                .collect()
+        };
+        let small = shifted(0.2, 0.0);
+        let large = shifted(1.0, 123_300.0);
+        let chart = |bars: &[Bar]| {
+            Chart::new(800, 600)
+                .bars(bars)
+                .tick_size(0.01)
+                .price_scale_fit(true)
+        };
+        let narrow = chart(&small).price_scale_width();
+        let wide = chart(&large).price_scale_width();
+        assert!(narrow >= PRICE_SCALE_MIN_WIDTH);
+        assert!(wide > narrow, "{} vs {}", wide, narrow);
+        assert_eq!(wide % PRICE_SCALE_WIDTH_STEP, 0.0);
+        // Fixed by default
+        let fixed = Chart::new(800, 600).bars(&large);
+        assert_eq!(fixed.price_scale_width(), PRICE_SCALE_WIDTH);
+
+        // Every price label ends inside the canvas
+        for bars in [&small, &large] {
+            let chart = chart(bars);
+            let left = 800.0 - chart.price_scale_width();
+            let font = chart.config.sizing().scale_font_size;
+            let svg = chart.render_svg();
+            let labels: Vec<(f64, &str)> = svg
+                .lines()
+                .filter(|l| l.starts_with("<text x=\""))
+                .filter_map(|l| {
+                    let x: f64 = l.split('"').nth(1)?.parse().ok()?;
+                    let text = l.rsplit_once("</text>")?.0.rsplit_once('>')?.1;
+                    (x > left).then_some((x, text))
+                })
+                .collect();
+            assert!(labels.len() >= 3);
+            for (x, text) in labels {
+                let end = x + estimate_text_width(text, font, FontWeight::Normal);
+                assert!(end <= 800.0, "{} ends at {}", text, end);
             }
-        })
-        .collect();
+        }
 
-    (macd_line, signal_line, histogram)
-}
+        // Across live updates the scale grows but never shrinks back
+        let mut live = chart(&large);
+        live.render_dirty();
+        live = live.bars(&small);
+        live.render_dirty();
+        let config = live.live_config();
+        assert_eq!(
+            ChartRenderer::new(&config, &live.bars).price_scale_width(),
+            wide
+        );
+        // The high-water width stays with the session, not the config
+        assert_eq!(live.config.price_scale_min_width, None);
+        assert_eq!(live.price_scale_width(), narrow);
+    }
 
-// =============================================================================
-// Tests
-// =============================================================================
+    #[test]
+    fn test_render_dirty_swaps_changed_groups() {
+        let mut bars = sample_bars(60);
+        let build = |bars: &[Bar]| {
+            Chart::new(800, 600)
+                .bars(bars)
+                .candlesticks()
+                .sma(5, "#2196f3")
+                .rsi(14)
+                .countdown(bars[bars.len() - 1].timestamp + 1800)
+        };
+        let mut chart = build(&bars);
+        let first = chart.render_dirty();
+        assert_eq!(first.len(), 1);
+        assert_eq!(first[0].id, None);
+        let mut svg = first[0].svg.clone();
+        assert!(svg.contains(r#"<g id="zc-last-bar">"#));
+        assert!(svg.contains(r#"<g id="zc-countdown">"#));
+        assert_eq!(svg, chart.render_svg_grouped());
+        // The SMA's last segment joins its last two values on its own
+        let last_sma = svg
+            .split(r#"<g id="zc-indicator-sma_5-last">"#)
+            .nth(1)
+            .and_then(|rest| rest.split("</g>").next())
+            .unwrap();
+        let points = last_sma.split(r#"points=""#).nth(1).unwrap();
+        assert_eq!(
+            points.split('"').next().unwrap().split_whitespace().count(),
+            2
+        );
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+        // Ticks inside the last bar's range, then a new bar
+        let last = bars.len() - 1;
+        let (low, high) = (bars[last].low, bars[last].high);
+        let step = |bars: &[Bar], svg: &mut String, chart: &mut Chart| {
+            let mut next = build(bars);
+            next.last_svg = chart.last_svg.take();
+            *chart = next;
+            let fragments = chart.render_dirty();
+            SvgFragment::apply(svg, &fragments);
+            assert_eq!(*svg, build(bars).render_svg_grouped());
+            fragments
+        };
+        let ids = |fragments: &[SvgFragment]| -> Vec<Option<String>> {
+            fragments.iter().map(|f| f.id.clone()).collect()
+        };
+        for close in [low + 0.1, high - 0.1] {
+            bars[last].close = close;
+            let fragments = step(&bars, &mut svg, &mut chart);
+            assert_eq!(
+                ids(&fragments),
+                [
+                    Some("zc-last-bar".into()),
+                    Some("zc-indicator-sma_5-last".into()),
+                    Some("zc-indicator-rsi_14-last".into()),
+                ]
+            );
+        }
+        let mut bar = bars[last];
+        bar.timestamp += 3600;
+        bars.push(bar);
+        let fragments = step(&bars, &mut svg, &mut chart);
+        assert!(ids(&fragments).contains(&Some("zc-time-scale".into())));
+        assert!(!ids(&fragments).contains(&None));
+        assert!(step(&bars, &mut svg, &mut chart).is_empty());
+    }
 
-    fn sample_bars(n: usize) -> Vec<Bar> {
-        let mut bars = Vec::with_capacity(n);
-        let mut price = 100.0;
+    #[test]
+    fn test_diff_commands_after_appending_a_bar() {
+        use crate::render::engine::CommandDelta;
+
+        let bars = sample_bars(60);
+        let frame = |last| {
+            Chart::new(800, 600)
+                .bars(&bars)
+                .candlesticks()
+                .ema(9, "#f44336")
+                .replay_to(last)
+                .replay_lock_scale(true)
+        };
+        let (prev, next) = (frame(40), frame(41));
+        let elements =
+            |chart: &Chart| ChartRenderer::new(&chart.config, &chart.bars).render_elements();
+        let full = elements(&next);
+        let diff = next.diff_commands(&prev);
+
+        // The new candle is added; the other 41 are not resent
+        let series = &full.iter().find(|(id, _)| id == "series").unwrap().1;
+        let added: Vec<_> = diff
+            .deltas
+            .iter()
+            .filter(|d| d.element() == "series")
+            .collect();
+        assert!(added.iter().all(|d| matches!(d, CommandDelta::Add { .. })));
+        assert_eq!(added.len() * 42, series.len());
+        for element in diff.elements() {
+            assert!(
+                [
+                    "series",
+                    "indicator:ema_9",
+                    "last_price",
+                    "price_scale",
+                    "time_scale"
+                ]
+                .contains(&element),
+                "{element}"
+            );
+        }
 
-        for i in 0..n {
-            let change = (i as f64 * 0.5).sin() * 2.0;
-            let vol = 1.0 + (i as f64 * 0.3).sin().abs();
+        let mut received = elements(&prev);
+        diff.apply(&mut received);
+        assert_eq!(received, full);
+        assert!(next.diff_commands(&next).is_empty());
 
-            let open = price;
-            let close = price + change;
-            let high = open.max(close) + vol;
-            let low = open.min(close) - vol;
+        // Moving the hovered bar only touches the hover column
+        let hovered = frame(41).hover_bar(Some(10));
+        let moved = frame(41).hover_bar(Some(12)).diff_commands(&hovered);
+        assert_eq!(moved.elements(), ["hover"]);
+    }
 
-            bars.push(Bar {
-                timestamp: 1700000000 + (i as i64) * 3600,
-                open,
-                high,
-                low,
-                close,
-                volume: 1000.0 + (i as f64 * 100.0),
-            });
+    #[test]
+    fn test_compact_precision_keeps_axes_integer() {
+        let bars = sample_bars(40);
+        let chart = Chart::new(800, 600).bars(&bars).candlesticks();
+        let full = chart.render_svg();
+        let compact = chart.svg_precision(SvgPrecision::compact()).render_svg();
+        assert!(compact.len() < full.len());
+
+        let grid = compact
+            .lines()
+            .find(|l| l.starts_with("<line") && l.contains(r##"stroke="#1e222d""##))
+            .unwrap();
+        let x1 = grid.split('"').nth(1).unwrap();
+        assert!(!x1.contains('.'), "{grid}");
+        // Candle bodies are series: one decimal
+        let body = compact
+            .lines()
+            .find(|l| l.starts_with("<rect") && l.contains(r##"fill="#26a69a""##))
+            .unwrap();
+        let width = body.split('"').nth(5).unwrap();
+        assert_eq!(width.split('.').nth(1).map(str::len), Some(1), "{body}");
+    }
 
-            price = close;
+    #[test]
+    fn test_fixed_pixel_candles_keep_width_and_centered_wicks() {
+        let attr = |l: &str, name: &str| -> f64 {
+            let start = l.find(&format!(" {name}=\"")).unwrap() + name.len() + 3;
+            l[start..].split('"').next().unwrap().parse().unwrap()
+        };
+        let colored = |l: &str| l.contains("#26a69a") || l.contains("#ef5350");
+        // Bar spacing from 12px to 36px
+        for (width, n) in [(800, 20), (800, 60), (1600, 45)] {
+            let bars = sample_bars(n);
+            let svg = Chart::new(width, 600)
+                .bars(&bars)
+                .candlesticks()
+                .candle_width_pixels(6.0)
+                .render_svg();
+            let bodies: Vec<&str> = svg
+                .lines()
+                .filter(|l| l.starts_with("<rect") && colored(l))
+                .collect();
+            let wicks: Vec<f64> = svg
+                .lines()
+                .filter(|l| l.starts_with("<line") && colored(l))
+                .filter(|l| attr(l, "x1") == attr(l, "x2"))
+                .map(|l| attr(l, "x1"))
+                .collect();
+            assert_eq!(bodies.len(), n);
+            assert_eq!(wicks.len(), n);
+            for (body, wick) in bodies.iter().zip(&wicks) {
+                assert_eq!(attr(body, "width"), 6.0, "{body}");
+                assert!((attr(body, "x") + 3.0 - wick).abs() <= 0.5, "{body} {wick}");
+            }
         }
 
-        bars
+        // Packed tighter than 6px, bodies shrink to the spacing
+        let bars = sample_bars(200);
+        let spacing = (800.0 - PRICE_SCALE_WIDTH) / 200.0;
+        let svg = Chart::new(800, 600)
+            .bars(&bars)
+            .candlesticks()
+            .candle_width_pixels(6.0)
+            .render_svg();
+        assert!(
+            svg.lines()
+                .filter(|l| l.starts_with("<rect") && colored(l))
+                .all(|l| attr(l, "width") <= spacing.round())
+        );
     }
 
     #[test]
-    fn test_empty_chart() {
-        let svg = Chart::new(800, 600).render_svg();
-        assert!(svg.contains("<svg"));
-        assert!(svg.contains("No data"));
+    fn test_dense_candles_cut_commands_at_20k_bars() {
+        let bars = sample_bars(20_000);
+        let chart = Chart::new(800, 400).bars(&bars).candlesticks().grid(false);
+        let (_, full) = chart.render_svg_with_stats();
+        assert!(full.commands["Line"] > 20_000);
+        assert!(full.commands["FillRect"] > 20_000);
+
+        // Culling drops one wick per bar and keeps the bodies
+        let chart = chart.dense_candles(1.0, DenseCandles::CullWicks);
+        let (_, cull) = chart.render_svg_with_stats();
+        assert_eq!(cull.commands["Line"], full.commands["Line"] - 20_000);
+        assert_eq!(cull.commands["FillRect"], full.commands["FillRect"]);
+
+        // High-low lines merge bars into one line per pixel column
+        let chart = chart.dense_candles(1.0, DenseCandles::HighLow);
+        let (_, high_low) = chart.render_svg_with_stats();
+        assert!(
+            high_low.command_count() < 1_000,
+            "{}",
+            high_low.command_count()
+        );
+        assert_eq!(
+            high_low.commands["FillRect"],
+            full.commands["FillRect"] - 20_000
+        );
     }
 
     #[test]
-    fn test_candlestick_chart() {
-        let bars = sample_bars(50);
-        let svg = Chart::new(800, 600).bars(&bars).candlesticks().render_svg();
-        assert!(svg.contains("<svg"));
-        assert!(svg.contains("<rect")); // candle bodies
+    fn test_runtime_theme_colors_reach_svg() {
+        let mut theme = crate::core::RuntimeTheme::dark();
+        theme.chart.background = "#101820".into();
+        theme.series.candle_up_body = "#00c853".into();
+        theme.series.candle_down_body = "#d50000".into();
+        theme.series.candle_up_wick = "#b9f6ca".into();
+        theme.series.candle_down_wick = "#ff8a80".into();
+        let theme = crate::core::RuntimeTheme::from_json(&theme.to_json()).unwrap();
+
+        let svg = Chart::new(600, 300)
+            .bars(&sample_bars(40))
+            .theme(theme)
+            .render_svg();
+        for color in ["#101820", "#00c853", "#d50000", "#b9f6ca", "#ff8a80"] {
+            assert!(svg.contains(color), "{color} missing");
+        }
+        // The defaults are gone
+        assert!(!svg.contains("#26a69a") && !svg.contains("#131722"));
     }
 
     #[test]
-    fn test_chart_with_sma() {
-        let bars = sample_bars(100);
-        let svg = Chart::new(800, 600)
+    fn test_cyberpunk_theme_glows_series() {
+        let bars = sample_bars(30);
+        let chart = Chart::new(400, 300)
             .bars(&bars)
-            .sma(20, "#2196F3")
-            .render_svg();
-        assert!(svg.contains("<svg"));
+            .line()
+            .theme(ThemeConfig::cyberpunk());
+        let svg = chart.render_svg();
+        assert!(
+            svg.contains(r#"<filter id="glow300""#),
+            "blur filter in defs"
+        );
+        assert!(svg.contains("<feGaussianBlur"));
+        let series = svg
+            .lines()
+            .find(|l| l.contains(r#"filter="url(#glow300)""#))
+            .expect("an element references the glow");
+        assert!(series.contains("stroke=\"#"), "{series}");
+        // Scales and text stay sharp
+        assert!(
+            svg.lines()
+                .filter(|l| l.starts_with("<text"))
+                .all(|l| !l.contains("filter="))
+        );
+
+        // Skippable, and absent from themes without the effect
+        for svg in [
+            chart.glow(0.0).render_svg(),
+            Chart::new(400, 300).bars(&bars).line().render_svg(),
+        ] {
+            assert!(!svg.contains("<filter"));
+            assert!(!svg.contains("filter="));
+        }
     }
 
     #[test]
-    fn test_chart_with_rsi() {
-        let bars = sample_bars(100);
-        let svg = Chart::new(800, 600).bars(&bars).rsi(14).render_svg();
-        assert!(svg.contains("<svg"));
+    fn test_place_time_labels_keeps_first_and_last() {
+        let label = |x: f64, weight| TimeLabel::new(x, "12:00".into(), weight, 10.0);
+        let labels = vec![
+            label(5.0, TickMarkWeight::Hour),
+            label(40.0, TickMarkWeight::Hour),
+            label(60.0, TickMarkWeight::Day),
+            label(198.0, TickMarkWeight::Hour),
+        ];
+        let placed = place_time_labels(labels, 0.0, 200.0, 8.0);
+        let ticks: Vec<f64> = placed.iter().map(|l| l.tick_x).collect();
+        // 40 collides with its neighbours; the heavier day label at 60 wins
+        assert_eq!(ticks, [5.0, 60.0, 198.0]);
+        // Edge labels are shifted inside, not cut off
+        assert_eq!(placed[0].center, placed[0].width / 2.0);
+        assert_eq!(placed[2].center, 200.0 - placed[2].width / 2.0);
     }
 
     #[test]
-    fn test_full_chart() {
-        let bars = sample_bars(200);
-        let svg = Chart::new(1200, 800)
-            .bars(&bars)
-            .candlesticks()
-            .sma(20, "#2196F3")
-            .sma(50, "#FF9800")
-            .bollinger(20, 2.0)
-            .rsi(14)
-            .macd(12, 26, 9)
-            .render_svg();
+    fn test_time_labels_stay_inside_narrow_chart() {
+        let bars = sample_bars(120);
+        let svg = Chart::new(320, 240).bars(&bars).candlesticks().render_svg();
+        let chart_width = 320.0 - PRICE_SCALE_WIDTH;
+        let label_y = 240.0 - TIME_SCALE_HEIGHT + 6.0;
+
+        let labels: Vec<(f64, String)> = svg
+            .lines()
+            .filter(|l| l.starts_with("<text") && l.contains(&format!(r#"y="{label_y:.2}""#)))
+            .map(|l| {
+                let x: f64 = l.split('"').nth(1).unwrap().parse().unwrap();
+                let text = &l[l.find('>').unwrap() + 1..l.find("</text>").unwrap()];
+                (x, text.to_string())
+            })
+            .collect();
+        assert!(labels.len() >= 2, "{labels:?}");
+        let mut prev_right = f64::NEG_INFINITY;
+        for (center, text) in &labels {
+            let half = estimate_text_width(text, 10.0, FontWeight::Normal) / 2.0;
+            // Centers are written rounded to 2 decimals
+            assert!(
+                center - half >= -0.005 && center + half <= chart_width + 0.005,
+                "{text} at {center}"
+            );
+            assert!(center - half > prev_right, "{labels:?}");
+            prev_right = center + half;
+        }
+    }
 
-        assert!(svg.contains("<svg"));
-        assert!(svg.len() > 1000);
+    /// Text of the time-axis labels of a chart `height` pixels tall
+    fn time_axis_labels(svg: &str, height: u32) -> Vec<String> {
+        let label_y = height as f64 - TIME_SCALE_HEIGHT + 6.0;
+        svg.lines()
+            .filter(|l| l.starts_with("<text") && l.contains(&format!(r#"y="{label_y:.2}""#)))
+            .map(|l| l[l.find('>').unwrap() + 1..l.find("</text>").unwrap()].to_string())
+            .collect()
     }
 
     #[test]
-    fn test_chart_renderer_from_config() {
-        let bars = sample_bars(100);
-        let config = ChartConfig {
-            width: 800,
-            height: 600,
-            dpr: 1.0,
-            theme: ThemeConfig::default(),
-            series: SeriesConfig::candlestick(),
-            indicators: vec![],
-            primitives: vec![],
-            signals: vec![],
-            layout: super::super::config::LayoutConfig::single(),
+    fn test_calendar_time_labels_for_daily_weekly_monthly_bars() {
+        use crate::coords::time_scale::days_from_civil;
+        let render = |days: Vec<i64>| {
+            let bars: Vec<Bar> = days
+                .iter()
+                .map(|d| Bar::new(d * 86_400, 100.0, 101.0, 99.0, 100.5))
+                .collect();
+            time_axis_labels(&Chart::new(800, 400).bars(&bars).render_svg(), 400)
         };
 
-        let svg = ChartRenderer::new(&config, &bars).render_svg();
-        assert!(svg.contains("<svg"));
+        // Daily weekdays through the turn of 2024
+        let start = days_from_civil(2024, 10, 1);
+        let daily = (0..140)
+            .map(|d| start + d)
+            .filter(|d| (d + 3).rem_euclid(7) < 5);
+        assert_eq!(
+            render(daily.collect()),
+            ["2024", "Nov", "Dec", "16 Dec", "2025", "22 Jan", "Feb"]
+        );
+
+        // Weekly Mondays from 2024-01-01
+        let monday = days_from_civil(2024, 1, 1);
+        assert_eq!(
+            render((0..80).map(|w| monday + 7 * w).collect()),
+            [
+                "2024", "Feb", "Q2 '24", "May", "Q3 '24", "Sep", "Q4 '24", "Dec", "2025", "Mar",
+                "Q2 '25", "Jun"
+            ]
+        );
+
+        // Monthly first-of-month bars from 2022
+        let monthly = (0..48).map(|m| days_from_civil(2022 + m / 12, (m % 12) as u32 + 1, 1));
+        assert_eq!(
+            render(monthly.collect()),
+            [
+                "2022", "Q2 '22", "Q3 '22", "2023", "Q2 '23", "Q3 '23", "2024", "Q2 '24", "Q3 '24",
+                "2025", "Q2 '25", "Q3 '25"
+            ]
+        );
     }
 
     #[test]
-    fn test_chart_with_primitives() {
-        let bars = sample_bars(100);
-        let svg = Chart::new(800, 600)
-            .bars(&bars)
-            .candlesticks()
-            .primitive(PrimitiveConfig::trend_line((10.0, 100.0), (50.0, 110.0)))
-            .primitive(PrimitiveConfig::horizontal_line(105.0))
-            .render_svg();
-
-        assert!(svg.contains("<svg"));
+    fn test_renko_time_labels_show_source_bar_timestamps() {
+        // Daily bars; bricks form only on some days, several on others
+        let bars = sample_bars(300);
+        let bricks = compute::renko(&bars, 2.0);
+        let chart = Chart::new(800, 400).bars(&bars).renko(2.0);
+        assert_eq!(chart.bars.len(), bricks.len());
+        assert!(bricks.len() < bars.len());
+        let svg = chart.render_svg();
+
+        let label_y = 400.0 - TIME_SCALE_HEIGHT + 6.0;
+        let labels: Vec<String> = svg
+            .lines()
+            .filter(|l| l.starts_with("<text") && l.contains(&format!(r#"y="{label_y:.2}""#)))
+            .map(|l| l[l.find('>').unwrap() + 1..l.find("</text>").unwrap()].to_string())
+            .collect();
+        assert!(labels.len() >= 3, "{labels:?}");
+
+        // Each default-formatted label names the bar that completed a
+        // brick, in brick order
+        let weights = [
+            TickMarkWeight::Hour,
+            TickMarkWeight::Day,
+            TickMarkWeight::Month,
+            TickMarkWeight::Quarter,
+            TickMarkWeight::Year,
+        ];
+        let mut last = 0;
+        for label in &labels {
+            let i = (last..bricks.len())
+                .find(|&i| {
+                    weights
+                        .iter()
+                        .any(|&w| format_time_by_weight(bricks[i].timestamp, w) == *label)
+                })
+                .unwrap_or_else(|| panic!("{label} in {labels:?}"));
+            last = i;
+        }
     }
 
     #[test]
-    fn test_chart_with_signals() {
-        let bars = sample_bars(100);
-        let svg = Chart::new(800, 600)
+    fn test_legend_data_follows_hover_bar() {
+        let bars = sample_bars(60);
+        let mut chart = Chart::new(800, 600)
             .bars(&bars)
             .candlesticks()
-            .signal(SignalConfig::buy(25, 100.0))
-            .signal(SignalConfig::sell(75, 105.0))
-            .render_svg();
+            .title("BTCUSD")
+            .ema(9, "#f44336")
+            .sma(20, "#2196F3")
+            .ema(21, "#4caf50")
+            .macd(12, 26, 9)
+            .indicator_group("ema_9", "EMAs")
+            .indicator_group("ema_21", "EMAs")
+            .legend(true);
+        assert!(Chart::new(800, 600).legend_data().is_none());
+
+        let assert_values_at = |chart: &Chart, index: usize| {
+            let legend = chart.legend_data().unwrap();
+            assert_eq!(legend.index, index);
+            assert_eq!(legend.close, bars[index].close);
+            assert_eq!(legend.prev_close, Some(bars[index - 1].close));
+            // Overlays only, grouped EMAs gathered ahead of the SMA
+            let ids: Vec<&str> = legend.indicators.iter().map(|i| i.id.as_str()).collect();
+            assert_eq!(ids, ["ema_9", "ema_21", "sma_20"]);
+            for entry in &legend.indicators {
+                let vectors = chart.indicator_values(&entry.id).unwrap();
+                for (value, vector) in entry.values.iter().zip(vectors) {
+                    assert_eq!(value.label, vector.display_label());
+                    assert_eq!(value.value, Some(vector.values[index]));
+                }
+            }
+            legend
+        };
 
-        assert!(svg.contains("<svg"));
+        let last = assert_values_at(&chart, 59);
+        assert_eq!(last.series_name.as_deref(), Some("BTCUSD"));
+        assert_eq!(last.series_type, SeriesType::Candlestick);
+        assert_eq!(last.indicators[0].group.as_deref(), Some("EMAs"));
+        assert_eq!(last.indicators[2].values[0].color, "#2196F3");
+
+        chart = chart.hover_bar(Some(30));
+        assert_values_at(&chart, 30);
+        // Out of range hover falls back to the last bar
+        chart = chart.hover_bar(Some(500));
+        assert_values_at(&chart, 59);
+
+        chart.set_group_visible("EMAs", false);
+        let legend = chart.legend_data().unwrap();
+        assert_eq!(legend.indicators.len(), 1);
+        assert_eq!(legend.indicators[0].id, "sma_20");
     }
 }
@@ -14,13 +14,31 @@
 //! ├── subpanes (SubpaneConfig - RSI, MACD, etc.)
 //! ├── primitives (PrimitiveConfig - 96 drawing tools)
 //! ├── signals (SignalConfig - buy/sell markers)
+//! ├── strategy (Strategy - indicators + signals + primitives as one unit)
 //! └── layout (LayoutConfig - multichart, sync)
 //! ```
 
+use super::diff::ConfigDiff;
+use crate::coords::{TickMarkWeight, format_time_by_weight, format_time_pattern};
+use crate::core::{
+    Bar, ChartDensity, ChartSizing, CssColorError, DuplicateBarMerge, RuntimeTheme, TimeSpacing,
+    TimestampUnit, parse_css_color,
+};
 use crate::layout::PaneId;
-use crate::model::{Indicator, SeriesType};
-use crate::primitives::{PrimitiveKind, PrimitiveMetadata, PrimitiveRegistry, SignalType};
+use crate::model::{
+    CandleWidthMode, DenseCandles, HaWickSource, Indicator, IndicatorParams, IndicatorPlacement,
+    LegendPosition, SeasonalPeriod, SeriesOptionsCommon, SeriesType, Source, Strategy,
+    StrategyTheme, Watermark,
+};
+use crate::primitives::{
+    BandDeviation, CompositeSpec, DivergenceType, FibLabelMode, FillRule, PrimitiveError,
+    PrimitiveKind, PrimitiveMetadata, PrimitiveRegistry, PrimitiveTrait, SignalType,
+};
+use crate::render::SvgPrecision;
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashSet;
+use std::sync::Arc;
 
 // =============================================================================
 // Main Chart Configuration
@@ -33,55 +51,1507 @@ pub struct ChartConfig {
     pub width: u32,
     pub height: u32,
     pub dpr: f64,
+    /// Per-axis overrides of `dpr` for non-square device pixels
+    #[serde(default)]
+    pub dpr_x: Option<f64>,
+    #[serde(default)]
+    pub dpr_y: Option<f64>,
+
+    /// Theme configuration
+    pub theme: ThemeConfig,
+
+    /// Main series configuration
+    pub series: SeriesConfig,
+
+    /// Indicators (overlays + subpanes unified)
+    /// Each Indicator has placement (Overlay or SubPane) and vectors with styles
+    #[serde(default)]
+    pub indicators: Vec<Indicator>,
+
+    /// Drawing primitives (trend lines, fibonacci, patterns, etc.)
+    #[serde(default)]
+    pub primitives: Vec<PrimitiveConfig>,
+
+    /// Trading signals (buy/sell markers)
+    #[serde(default)]
+    pub signals: Vec<SignalConfig>,
+
+    /// Layout configuration (multichart, sync)
+    #[serde(default)]
+    pub layout: LayoutConfig,
+
+    /// Per-bar color overrides for the main series (`None` = theme color)
+    #[serde(default)]
+    pub bar_colors: Vec<Option<String>>,
+
+    /// Time label pattern (e.g. `"%H:%M"`), overriding weight-based formatting
+    /// of both the time axis and the crosshair time label. This is the one
+    /// time format override; see [`format_time_pattern`] for supported
+    /// specifiers.
+    #[serde(default)]
+    pub time_format: Option<String>,
+
+    /// Bar timeframe in seconds (60 = 1m, 86400 = 1D).
+    /// When unset, it is inferred from the spacing of the last two bars.
+    #[serde(default)]
+    pub timeframe_secs: Option<i64>,
+
+    /// Unit of bar timestamps and the countdown's `now`; converted to
+    /// seconds before formatting, tick weighting and resampling
+    #[serde(default)]
+    pub timestamp_unit: TimestampUnit,
+
+    /// Sort bars by timestamp and merge duplicates before anything uses them.
+    /// Off by default, so bars are drawn in the order given.
+    #[serde(default)]
+    pub sort_bars: bool,
+
+    /// How duplicate timestamps are merged when `sort_bars` is on
+    #[serde(default)]
+    pub duplicate_merge: DuplicateBarMerge,
+
+    /// Empty space after the last bar, in bars. Primitives and signals
+    /// anchored past the data are drawn into it.
+    #[serde(default)]
+    pub right_offset: f64,
+
+    /// Bar placement: by index, or by timestamp with gaps for missing
+    /// sessions
+    #[serde(default)]
+    pub time_spacing: TimeSpacing,
+
+    /// Bar replay: draw bars, indicator values and signals only up to this
+    /// index, keeping later bars' slots empty
+    #[serde(default)]
+    pub replay_to: Option<usize>,
+
+    /// Keep the whole data set's price range while replaying, so the scale
+    /// does not move from frame to frame
+    #[serde(default)]
+    pub replay_lock_scale: bool,
+
+    /// Bar-close countdown on the time scale
+    #[serde(default)]
+    pub countdown: Option<CountdownConfig>,
+
+    /// Strategy overlay (drawn after the series, before user primitives)
+    #[serde(default)]
+    pub strategy: Option<Strategy>,
+
+    /// Dashed line at the last close with a price tag on the axis,
+    /// colored by the last bar's direction
+    #[serde(default)]
+    pub show_last_price_line: bool,
+
+    /// Higher-timeframe candles drawn behind the main series
+    #[serde(default)]
+    pub htf_overlay: Option<HtfOverlayConfig>,
+
+    /// Narrowest slot a bar may get, in pixels. When all bars do not fit,
+    /// only the latest ones that do are drawn (replay frames then index
+    /// those bars). Single charts only: `MultichartRenderer` draws every
+    /// bar of each cell.
+    #[serde(default)]
+    pub min_bar_spacing: Option<f64>,
+
+    /// Thin scrollbar under the bars showing the drawn range among all
+    /// bars, when `min_bar_spacing` leaves some out
+    #[serde(default)]
+    pub show_scroll_indicator: bool,
+
+    /// Mark bars that open away from the previous close
+    #[serde(default)]
+    pub show_gaps: Option<GapConfig>,
+
+    /// Text (and logo) block drawn behind the series, aligned in the main pane
+    #[serde(default)]
+    pub watermark: Option<Watermark>,
+
+    /// Seasonal mode: one line per period instead of the main series
+    #[serde(default)]
+    pub seasonal: Option<SeasonalConfig>,
+
+    /// Secondary series drawn in their own boxes on the main pane
+    #[serde(default)]
+    pub inset_series: Vec<InsetSeries>,
+
+    /// Volume-by-price histogram along the right edge of the main pane
+    #[serde(default)]
+    pub volume_by_price: Option<VbpConfig>,
+
+    /// Raw trade ticks, in a pane under the bars or as the main series
+    /// when there are no bars
+    #[serde(default)]
+    pub ticks: Option<TickSeries>,
+
+    /// Hovered bar, highlighted with a full-height band and body outline
+    #[serde(default)]
+    pub hover_bar: Option<usize>,
+
+    /// Legend of the main pane's overlay indicators, top-left
+    #[serde(default)]
+    pub show_legend: bool,
+
+    /// Name and latest values of each subpane's indicator in a pane corner
+    #[serde(default = "default_true")]
+    pub show_pane_titles: bool,
+
+    /// Price scale column on the right; when hidden the plot takes its width
+    #[serde(default = "default_true")]
+    pub show_price_scale: bool,
+
+    /// Size the price scale column to its widest label instead of the
+    /// density's fixed width, rounded up to
+    /// [`PRICE_SCALE_WIDTH_STEP`](crate::core::PRICE_SCALE_WIDTH_STEP) and at
+    /// least [`PRICE_SCALE_MIN_WIDTH`](crate::core::PRICE_SCALE_MIN_WIDTH)
+    #[serde(default)]
+    pub price_scale_fit: bool,
+
+    /// Narrowest a fitted price scale gets, e.g. the width reserved for an
+    /// earlier frame, so the plot never jumps back when labels shrink
+    #[serde(default)]
+    pub price_scale_min_width: Option<f64>,
+
+    /// Time scale strip at the bottom; when hidden the plot takes its height
+    #[serde(default = "default_true")]
+    pub show_time_scale: bool,
+
+    /// Corner of the subpane titles
+    #[serde(default)]
+    pub legend_position: LegendPosition,
+
+    /// Size class of the scales, grid and signal glyphs
+    #[serde(default)]
+    pub density: ChartDensity,
+
+    /// Symbol or title of the chart, used in its text description
+    #[serde(default)]
+    pub title: Option<String>,
+
+    /// Embed the text description in the SVG as `<title>`/`<desc>`
+    /// referenced by `aria-labelledby`/`aria-describedby`
+    #[serde(default)]
+    pub embed_description: bool,
+
+    /// Indicator groups hidden from rendering and the legend
+    #[serde(default)]
+    pub hidden_groups: HashSet<String>,
+
+    /// Indicators toggled off: not drawn and left out of autoscale, but
+    /// still listed in the legend, greyed out
+    #[serde(default)]
+    pub hidden_indicator_ids: HashSet<String>,
+
+    /// Main series toggled off: not drawn, no last-price tag, and left out
+    /// of autoscale unless nothing else is on the main scale
+    #[serde(default)]
+    pub hidden_series: bool,
+
+    /// Ids of primitives drawn selected: halo and control-point handles
+    #[serde(default)]
+    pub selected_primitive_ids: HashSet<String>,
+
+    /// Label format for the levels of every Fibonacci drawing without its
+    /// own [`PrimitiveConfig::fib_labels`] (unset = no labels)
+    #[serde(default)]
+    pub fib_labels: Option<FibLabelMode>,
+
+    /// Coordinate decimals in the SVG output, per element category
+    #[serde(default)]
+    pub svg_precision: SvgPrecision,
+
+    /// Instrument tick size for the main price scale (e.g. 0.25 for ES).
+    /// Ticks land on its multiples and prices use its decimal precision.
+    #[serde(default)]
+    pub tick_size: Option<f64>,
+
+    /// What to draw when there are no bars
+    #[serde(default)]
+    pub empty_state: EmptyStateConfig,
+
+    /// Primitive registry for this chart (`None` = built-in registry).
+    /// Attach one with custom primitives via [`ChartConfig::registry`].
+    #[serde(skip)]
+    pub registry: Option<Arc<PrimitiveRegistry>>,
+}
+
+impl Default for ChartConfig {
+    fn default() -> Self {
+        Self {
+            width: 800,
+            height: 600,
+            dpr: 1.0,
+            dpr_x: None,
+            dpr_y: None,
+            theme: ThemeConfig::default(),
+            series: SeriesConfig::default(),
+            indicators: Vec::new(),
+            primitives: Vec::new(),
+            signals: Vec::new(),
+            layout: LayoutConfig::default(),
+            bar_colors: Vec::new(),
+            time_format: None,
+            timeframe_secs: None,
+            timestamp_unit: TimestampUnit::default(),
+            sort_bars: false,
+            duplicate_merge: DuplicateBarMerge::default(),
+            right_offset: 0.0,
+            time_spacing: TimeSpacing::default(),
+            replay_to: None,
+            replay_lock_scale: false,
+            countdown: None,
+            strategy: None,
+            show_last_price_line: false,
+            htf_overlay: None,
+            min_bar_spacing: None,
+            show_scroll_indicator: false,
+            show_gaps: None,
+            watermark: None,
+            seasonal: None,
+            inset_series: Vec::new(),
+            volume_by_price: None,
+            ticks: None,
+            hover_bar: None,
+            show_legend: false,
+            show_pane_titles: true,
+            show_price_scale: true,
+            price_scale_fit: false,
+            price_scale_min_width: None,
+            show_time_scale: true,
+            legend_position: LegendPosition::default(),
+            density: ChartDensity::default(),
+            title: None,
+            embed_description: false,
+            hidden_groups: HashSet::new(),
+            hidden_indicator_ids: HashSet::new(),
+            hidden_series: false,
+            selected_primitive_ids: HashSet::new(),
+            fib_labels: None,
+            svg_precision: SvgPrecision::default(),
+            tick_size: None,
+            empty_state: EmptyStateConfig::default(),
+            registry: None,
+        }
+    }
+}
+
+impl ChartConfig {
+    pub fn new(width: u32, height: u32) -> Self {
+        Self {
+            width,
+            height,
+            ..Default::default()
+        }
+    }
+
+    /// Whether the main series is drawn: not toggled off with
+    /// `hidden_series` and not hidden by its own options
+    pub fn series_visible(&self) -> bool {
+        !self.hidden_series && self.series.common.visible
+    }
+
+    /// Scale, grid and glyph sizes of the configured density
+    pub fn sizing(&self) -> ChartSizing {
+        self.density.sizing()
+    }
+
+    /// Horizontal and vertical device pixel ratios, falling back to `dpr`,
+    /// each clamped like [`Self::pixel_ratio`]
+    pub fn dpr_xy(&self) -> (f64, f64) {
+        (
+            clamp_dpr(self.dpr_x.unwrap_or(self.dpr)),
+            clamp_dpr(self.dpr_y.unwrap_or(self.dpr)),
+        )
+    }
+
+    /// `dpr` clamped to [`MIN_DPR`]..=[`MAX_DPR`], or 1 when it is not a
+    /// number
+    pub fn pixel_ratio(&self) -> f64 {
+        clamp_dpr(self.dpr)
+    }
+
+    /// Smallest size the chart lays out in: room for the price scale twice
+    /// over horizontally and the time scale vertically, plus a margin;
+    /// hidden scales take no room
+    pub fn min_size(&self) -> (u32, u32) {
+        let sizing = self.sizing();
+        let scale = |shown: bool, size: f64| if shown { size } else { 0.0 };
+        let price_scale = scale(self.show_price_scale, sizing.price_scale_width);
+        let time_scale = scale(self.show_time_scale, sizing.time_scale_height);
+        (
+            (2.0 * price_scale + MIN_PLOT_MARGIN) as u32,
+            (2.0 * time_scale + MIN_PLOT_MARGIN) as u32,
+        )
+    }
+
+    /// Check the size against [`Self::min_size`]
+    pub fn check_dimensions(&self) -> Result<(), ChartError> {
+        let (min_width, min_height) = self.min_size();
+        if self.width < min_width || self.height < min_height {
+            return Err(ChartError::InvalidDimensions {
+                width: self.width,
+                height: self.height,
+                min_width,
+                min_height,
+            });
+        }
+        Ok(())
+    }
+
+    /// Attach a primitive registry, e.g. one with custom primitives
+    pub fn registry(mut self, registry: impl Into<Arc<PrimitiveRegistry>>) -> Self {
+        self.registry = Some(registry.into());
+        self
+    }
+
+    /// Register a composite primitive on this chart's registry,
+    /// starting from the built-in registry if none is attached
+    pub fn register_composite(&mut self, spec: CompositeSpec) -> Result<(), PrimitiveError> {
+        let registry = self
+            .registry
+            .get_or_insert_with(|| Arc::new(PrimitiveRegistry::global().clone()));
+        Arc::make_mut(registry).register_composite(spec)?;
+        Ok(())
+    }
+
+    /// Registry used to create this chart's primitives
+    pub fn primitive_registry(&self) -> &PrimitiveRegistry {
+        self.registry
+            .as_deref()
+            .unwrap_or_else(|| PrimitiveRegistry::global())
+    }
+
+    /// Colors that will not parse, as `"field: reason"` messages
+    ///
+    /// Rendering never fails on a bad color - each one falls back to a
+    /// default - so this is the place to surface typos to the user.
+    pub fn color_warnings(&self) -> Vec<String> {
+        self.invalid_colors()
+            .into_iter()
+            .map(|(field, err)| format!("{}: {}", field, err))
+            .collect()
+    }
+
+    /// Checks for settings that render as nothing or as nonsense without
+    /// failing: a size too small to draw in, indicators without values or
+    /// vectors, duplicate indicator ids, subpanes taking most of the
+    /// height, bad colors, and signals or primitives past the last of
+    /// `bars_len` bars and the `right_offset` after them
+    pub fn validate(&self, bars_len: usize) -> Vec<ConfigWarning> {
+        let mut warnings = Vec::new();
+        if self.check_dimensions().is_err() {
+            warnings.push(ConfigWarning::InvalidDimensions {
+                width: self.width,
+                height: self.height,
+            });
+        }
+
+        let mut seen_ids: HashSet<&str> = HashSet::new();
+        let mut duplicates: Vec<&str> = Vec::new();
+        for indicator in &self.indicators {
+            if !seen_ids.insert(&indicator.id) && !duplicates.contains(&indicator.id.as_str()) {
+                duplicates.push(&indicator.id);
+            }
+            if indicator.vectors.is_empty() {
+                warnings.push(ConfigWarning::EmptyIndicator {
+                    id: indicator.id.clone(),
+                });
+            } else if bars_len > 0
+                && indicator
+                    .vectors
+                    .iter()
+                    .all(|v| v.values.iter().all(|value| !value.is_finite()))
+            {
+                warnings.push(ConfigWarning::IndicatorWithoutValues {
+                    id: indicator.id.clone(),
+                    bars: bars_len,
+                });
+            }
+        }
+        warnings.extend(
+            duplicates
+                .into_iter()
+                .map(|id| ConfigWarning::DuplicateIndicatorId { id: id.to_string() }),
+        );
+
+        let subpane_ratio: f64 = self
+            .indicators
+            .iter()
+            .filter(|ind| self.is_indicator_visible(ind))
+            .filter_map(|ind| match ind.placement {
+                IndicatorPlacement::SubPane { height_ratio } => Some(height_ratio),
+                _ => None,
+            })
+            .sum();
+        if subpane_ratio > MAX_SUBPANE_RATIO {
+            warnings.push(ConfigWarning::SubpaneRatios {
+                total: subpane_ratio,
+            });
+        }
+
+        warnings.extend(
+            self.invalid_colors()
+                .into_iter()
+                .map(|(field, error)| ConfigWarning::InvalidColor { field, error }),
+        );
+
+        let end = bars_len as f64 + self.right_offset;
+        for (i, signal) in self.signals.iter().enumerate() {
+            if signal.bar_index as f64 >= end {
+                warnings.push(ConfigWarning::BarIndexOutOfRange {
+                    field: format!("signals[{}]", i),
+                    index: signal.bar_index as f64,
+                    bars: bars_len,
+                });
+            }
+        }
+        for (i, primitive) in self.primitives.iter().enumerate() {
+            if primitive.anchor != PointAnchor::BarIndex {
+                continue;
+            }
+            let outside = primitive
+                .points
+                .iter()
+                .map(|&(bar, _)| bar)
+                .find(|&bar| bar < 0.0 || bar >= end);
+            if let Some(index) = outside {
+                warnings.push(ConfigWarning::BarIndexOutOfRange {
+                    field: format!("primitives[{}]", i),
+                    index,
+                    bars: bars_len,
+                });
+            }
+        }
+
+        warnings
+    }
+
+    fn invalid_colors(&self) -> Vec<(String, CssColorError)> {
+        let mut colors: Vec<(String, &str)> = Vec::new();
+
+        let theme = &self.theme;
+        for (field, color) in [
+            ("background", &theme.background),
+            ("grid_color", &theme.grid_color),
+            ("up_color", &theme.up_color),
+            ("down_color", &theme.down_color),
+            ("text_color", &theme.text_color),
+            ("border_color", &theme.border_color),
+        ] {
+            colors.push((format!("theme.{}", field), color));
+        }
+
+        let style = &self.series.style;
+        for (field, color) in [
+            ("color", &style.color),
+            ("up_color", &style.up_color),
+            ("down_color", &style.down_color),
+        ] {
+            if let Some(color) = color {
+                colors.push((format!("series.style.{}", field), color));
+            }
+        }
+
+        for (i, color) in self.bar_colors.iter().enumerate() {
+            if let Some(color) = color {
+                colors.push((format!("bar_colors[{}]", i), color));
+            }
+        }
+
+        for indicator in &self.indicators {
+            for vector in &indicator.vectors {
+                for (field, color) in vector.style.colors() {
+                    let path = format!(
+                        "indicator '{}' vector '{}' {}",
+                        indicator.id, vector.name, field
+                    );
+                    colors.push((path, color));
+                }
+            }
+            for (i, fill) in indicator.fills.iter().enumerate() {
+                colors.push((
+                    format!("indicator '{}' fills[{}].color", indicator.id, i),
+                    &fill.color,
+                ));
+                if let Some(crossed) = &fill.crossed_color {
+                    let path = format!("indicator '{}' fills[{}].crossed_color", indicator.id, i);
+                    colors.push((path, crossed));
+                }
+            }
+        }
+
+        for (i, primitive) in self.primitives.iter().enumerate() {
+            colors.push((format!("primitives[{}].color", i), &primitive.color));
+            if let Some(fill) = &primitive.fill_color {
+                colors.push((format!("primitives[{}].fill_color", i), fill));
+            }
+        }
+
+        if let Some(watermark) = &self.watermark {
+            for (i, line) in watermark.lines.iter().enumerate() {
+                colors.push((format!("watermark.lines[{}].color", i), &line.color));
+            }
+        }
+
+        for (i, signal) in self.signals.iter().enumerate() {
+            if let Some(color) = &signal.color {
+                colors.push((format!("signals[{}].color", i), color));
+            }
+        }
+
+        colors
+            .into_iter()
+            .filter_map(|(field, color)| parse_css_color(color).err().map(|err| (field, err)))
+            .collect()
+    }
+
+    /// Show or hide every indicator in `group`
+    pub fn set_group_visible(&mut self, group: &str, visible: bool) {
+        if visible {
+            self.hidden_groups.remove(group);
+        } else {
+            self.hidden_groups.insert(group.to_string());
+        }
+    }
+
+    /// Toggle the indicator with `id`; a hidden one keeps a greyed legend row
+    pub fn set_indicator_visible(&mut self, id: &str, visible: bool) {
+        if visible {
+            self.hidden_indicator_ids.remove(id);
+        } else {
+            self.hidden_indicator_ids.insert(id.to_string());
+        }
+    }
+
+    /// Select or deselect the primitive with `id`
+    pub fn set_primitive_selected(&mut self, id: &str, selected: bool) {
+        if selected {
+            self.selected_primitive_ids.insert(id.to_string());
+        } else {
+            self.selected_primitive_ids.remove(id);
+        }
+    }
+
+    /// Whether a primitive is drawn selected (it needs an id to be
+    /// selectable, and locked primitives never are)
+    pub fn is_primitive_selected(&self, primitive: &PrimitiveConfig) -> bool {
+        !primitive.locked
+            && primitive
+                .id
+                .as_ref()
+                .is_some_and(|id| self.selected_primitive_ids.contains(id))
+    }
+
+    /// Show or hide the primitives with `id`
+    pub fn set_primitive_visibility(&mut self, id: &str, visible: bool) {
+        for primitive in self.primitives_with_id(id) {
+            primitive.visible = visible;
+        }
+    }
+
+    /// Lock or unlock the primitives with `id`
+    pub fn set_primitive_locked(&mut self, id: &str, locked: bool) {
+        for primitive in self.primitives_with_id(id) {
+            primitive.locked = locked;
+        }
+    }
+
+    /// Include or exclude the primitives with `id` from the automatic price range
+    pub fn set_primitive_autoscale(&mut self, id: &str, autoscale: bool) {
+        for primitive in self.primitives_with_id(id) {
+            primitive.autoscale = autoscale;
+        }
+    }
+
+    /// Shift primitive points by `bar_delta` bars and `price_delta`, e.g.
+    /// by the number of bars prepended on a history load; all primitives,
+    /// or only those whose id is in `ids`
+    ///
+    /// Timestamp-anchored points keep their time and points read on a
+    /// subpane keep their value, as neither is in bars or price. Returns
+    /// the number of primitives moved.
+    pub fn translate_primitives(
+        &mut self,
+        bar_delta: f64,
+        price_delta: f64,
+        ids: Option<&[String]>,
+    ) -> usize {
+        self.transform_primitives(ids, |bar, price| (bar + bar_delta, price + price_delta))
+    }
+
+    /// Scale primitive points about `origin` by `factor`, both as
+    /// `(bar, price)`, e.g. prices by a quarter about zero after a 4:1
+    /// split: `scale_primitives((0.0, 0.0), (1.0, 0.25), None)`
+    ///
+    /// Covers the same points as [`translate_primitives`](Self::translate_primitives).
+    pub fn scale_primitives(
+        &mut self,
+        origin: (f64, f64),
+        factor: (f64, f64),
+        ids: Option<&[String]>,
+    ) -> usize {
+        self.transform_primitives(ids, |bar, price| {
+            (
+                origin.0 + (bar - origin.0) * factor.0,
+                origin.1 + (price - origin.1) * factor.1,
+            )
+        })
+    }
+
+    fn transform_primitives(
+        &mut self,
+        ids: Option<&[String]>,
+        transform: impl Fn(f64, f64) -> (f64, f64),
+    ) -> usize {
+        let selected = |p: &&mut PrimitiveConfig| {
+            ids.is_none_or(|ids| p.id.as_ref().is_some_and(|id| ids.contains(id)))
+        };
+        let mut count = 0;
+        for primitive in self.primitives.iter_mut().filter(selected) {
+            let by_bar = primitive.anchor == PointAnchor::BarIndex;
+            let on_main = primitive.pane_id.is_none();
+            for (i, point) in primitive.points.iter_mut().enumerate() {
+                let (bar, price) = transform(point.0, point.1);
+                if by_bar {
+                    point.0 = bar;
+                }
+                if on_main && primitive.point_panes.get(i).is_none_or(Option::is_none) {
+                    point.1 = price;
+                }
+            }
+            count += 1;
+        }
+        count
+    }
+
+    fn primitives_with_id<'s>(
+        &'s mut self,
+        id: &'s str,
+    ) -> impl Iterator<Item = &'s mut PrimitiveConfig> {
+        self.primitives
+            .iter_mut()
+            .filter(move |p| p.id.as_deref() == Some(id))
+    }
+
+    /// Whether an indicator is drawn: listed in the legend and not toggled
+    /// off with [`set_indicator_visible`](Self::set_indicator_visible)
+    pub fn is_indicator_visible(&self, indicator: &Indicator) -> bool {
+        self.is_indicator_in_legend(indicator) && !self.hidden_indicator_ids.contains(&indicator.id)
+    }
+
+    /// Whether an indicator has a legend row: visible itself and not in a
+    /// hidden group
+    pub fn is_indicator_in_legend(&self, indicator: &Indicator) -> bool {
+        indicator.visible
+            && indicator
+                .group
+                .as_ref()
+                .is_none_or(|group| !self.hidden_groups.contains(group))
+    }
+
+    /// Visual-affecting changes from `self` to `other`, grouped by area
+    ///
+    /// `Display` on the result gives a review-friendly summary.
+    pub fn diff(&self, other: &ChartConfig) -> ConfigDiff {
+        ConfigDiff::between(self, other)
+    }
+
+    /// Format a time-axis or crosshair label for `ts` in unix seconds
+    ///
+    /// Uses `time_format` when set and non-empty, otherwise picks a format
+    /// from the tick weight.
+    pub fn format_time(&self, ts: i64, weight: TickMarkWeight) -> String {
+        match self.time_format.as_deref() {
+            Some(pattern) if !pattern.trim().is_empty() => {
+                let label = format_time_pattern(ts, pattern);
+                if label.trim().is_empty() {
+                    format_time_by_weight(ts, weight)
+                } else {
+                    label
+                }
+            }
+            _ => format_time_by_weight(ts, weight),
+        }
+    }
+}
+
+// =============================================================================
+// Empty State Configuration
+// =============================================================================
+
+/// Empty and loading state, drawn when the chart has no bars
+///
+/// The background, grid and scale columns are kept so the plot area does
+/// not shift when data arrives.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct EmptyStateConfig {
+    /// Centered message; empty for none
+    #[serde(default = "default_empty_message")]
+    pub message: String,
+    /// Grey placeholder candles behind the message
+    #[serde(default)]
+    pub show_skeleton: bool,
+    /// Number of placeholder candles
+    #[serde(default = "default_skeleton_bar_count")]
+    pub skeleton_bar_count: usize,
+    /// Pulse the placeholders with an SVG `<animate>`. Off by default since
+    /// some SVG consumers strip or reject animation.
+    #[serde(default)]
+    pub animate: bool,
+    /// Seed for the placeholder candle shapes
+    #[serde(default)]
+    pub seed: u64,
+}
+
+fn default_empty_message() -> String {
+    "No data".into()
+}
+
+fn default_skeleton_bar_count() -> usize {
+    40
+}
+
+impl Default for EmptyStateConfig {
+    fn default() -> Self {
+        Self {
+            message: default_empty_message(),
+            show_skeleton: false,
+            skeleton_bar_count: default_skeleton_bar_count(),
+            animate: false,
+            seed: 0,
+        }
+    }
+}
+
+impl EmptyStateConfig {
+    /// Loading state: skeleton candles under `message`
+    pub fn loading(message: &str) -> Self {
+        Self {
+            message: message.to_string(),
+            show_skeleton: true,
+            ..Default::default()
+        }
+    }
+
+    /// Placeholder candles as (body top, body bottom, wick top, wick bottom),
+    /// each a fraction of the price area height from the top
+    ///
+    /// Shapes follow a seeded random walk, so the same seed always gives the
+    /// same skeleton.
+    pub fn skeleton_candles(&self) -> Vec<(f64, f64, f64, f64)> {
+        let mut state = self.seed ^ 0x9E37_79B9_7F4A_7C15;
+        let mut next = || {
+            // xorshift64*
+            state ^= state >> 12;
+            state ^= state << 25;
+            state ^= state >> 27;
+            (state.wrapping_mul(0x2545_F491_4F6C_DD1D) >> 11) as f64 / (1u64 << 53) as f64
+        };
+
+        let mut level = 0.5;
+        (0..self.skeleton_bar_count)
+            .map(|_| {
+                let open = level;
+                level = (level + (next() - 0.5) * 0.12).clamp(0.3, 0.7);
+                let (top, bottom) = (open.min(level), open.max(level) + 0.01);
+                (top, bottom, top - next() * 0.05, bottom + next() * 0.05)
+            })
+            .collect()
+    }
+}
+
+// =============================================================================
+// Countdown Configuration
+// =============================================================================
+
+/// Time remaining until the last bar closes, shown on the time scale
+///
+/// `now` is supplied by the host (in the chart's `timestamp_unit`) so
+/// rendering stays deterministic; re-render with a new `now` to tick the
+/// countdown.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CountdownConfig {
+    /// Current time, in the same unit as the bar timestamps
+    pub now: i64,
+    /// Draw a faint vertical line at the projected close time
+    #[serde(default)]
+    pub show_close_line: bool,
+}
+
+impl CountdownConfig {
+    pub fn new(now: i64) -> Self {
+        Self {
+            now,
+            show_close_line: false,
+        }
+    }
+
+    pub fn with_close_line(mut self, show: bool) -> Self {
+        self.show_close_line = show;
+        self
+    }
+}
+
+/// Higher-timeframe candles behind the main series
+///
+/// Base bars are aggregated into `interval_secs` buckets and each bucket is
+/// drawn spanning exactly its child bars, so a partial first or last bucket
+/// is only as wide as the bars it covers.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct HtfOverlayConfig {
+    /// Higher timeframe in seconds (14400 = 4h)
+    pub interval_secs: i64,
+    #[serde(default)]
+    pub style: HtfOverlayStyle,
+}
+
+impl HtfOverlayConfig {
+    pub fn new(interval_secs: i64, style: HtfOverlayStyle) -> Self {
+        Self {
+            interval_secs,
+            style,
+        }
+    }
+}
+
+/// Look of higher-timeframe candles
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq)]
+pub struct HtfOverlayStyle {
+    /// Fill the body instead of drawing its outline
+    #[serde(default)]
+    pub filled: bool,
+    /// Draw high/low wicks
+    #[serde(default = "default_true")]
+    pub show_wicks: bool,
+    /// Opacity of the up/down theme colors
+    #[serde(default = "default_htf_opacity")]
+    pub opacity: f64,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_htf_opacity() -> f64 {
+    0.35
+}
+
+impl Default for HtfOverlayStyle {
+    fn default() -> Self {
+        Self::outline()
+    }
+}
+
+impl HtfOverlayStyle {
+    /// Hollow body outlines
+    pub fn outline() -> Self {
+        Self {
+            filled: false,
+            show_wicks: true,
+            opacity: default_htf_opacity(),
+        }
+    }
+
+    /// Bodies filled with a low alpha
+    pub fn filled() -> Self {
+        Self {
+            filled: true,
+            show_wicks: true,
+            opacity: 0.12,
+        }
+    }
+
+    pub fn with_wicks(mut self, show: bool) -> Self {
+        self.show_wicks = show;
+        self
+    }
+
+    pub fn with_opacity(mut self, opacity: f64) -> Self {
+        self.opacity = opacity.clamp(0.0, 1.0);
+        self
+    }
+}
+
+/// How far a bar must open from the previous close to count as a gap
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq)]
+pub enum GapThreshold {
+    /// Price distance
+    Absolute(f64),
+    /// Percent of the previous close (`1.0` = 1%)
+    Percent(f64),
+}
+
+impl GapThreshold {
+    /// Whether opening at `open` after closing at `prev_close` gaps by
+    /// more than the threshold
+    pub fn is_gap(&self, prev_close: f64, open: f64) -> bool {
+        let distance = (open - prev_close).abs();
+        if distance.is_nan() || distance == 0.0 {
+            return false;
+        }
+        match *self {
+            Self::Absolute(min) => distance > min,
+            Self::Percent(pct) => prev_close != 0.0 && distance / prev_close.abs() * 100.0 > pct,
+        }
+    }
+}
+
+/// Gap markers between the previous close and a bar's open
+///
+/// Up gaps use the theme's up color, down gaps its down color.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq)]
+pub struct GapConfig {
+    pub threshold: GapThreshold,
+    /// Shade the gap from the previous bar to this one
+    #[serde(default = "default_true")]
+    pub shade: bool,
+    /// Small triangle beside the bar, pointing in the gap's direction
+    #[serde(default)]
+    pub marker: bool,
+    /// Opacity of the shading
+    #[serde(default = "default_gap_opacity")]
+    pub opacity: f64,
+}
+
+fn default_gap_opacity() -> f64 {
+    0.15
+}
+
+impl GapConfig {
+    /// Shaded gaps of more than `threshold`
+    pub fn new(threshold: GapThreshold) -> Self {
+        Self {
+            threshold,
+            shade: true,
+            marker: false,
+            opacity: default_gap_opacity(),
+        }
+    }
+
+    pub fn with_shade(mut self, shade: bool) -> Self {
+        self.shade = shade;
+        self
+    }
+
+    pub fn with_marker(mut self, marker: bool) -> Self {
+        self.marker = marker;
+        self
+    }
+
+    pub fn with_opacity(mut self, opacity: f64) -> Self {
+        self.opacity = opacity.clamp(0.0, 1.0);
+        self
+    }
+}
+
+/// Seasonal overlay mode
+///
+/// Replaces the time-based chart with one line per period on a shared
+/// in-period x axis, each normalized to % change from its period start.
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct SeasonalConfig {
+    #[serde(default)]
+    pub period: SeasonalPeriod,
+    #[serde(default)]
+    pub source: Source,
+}
+
+impl SeasonalConfig {
+    pub fn new(period: SeasonalPeriod, source: Source) -> Self {
+        Self { period, source }
+    }
+}
+
+/// Volume-by-price histogram of the drawn bars
+///
+/// Faint horizontal bars hug the right edge of the main pane, behind the
+/// series and above the grid. It covers exactly the bars drawn, so a replay
+/// only counts the bars replayed so far.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct VbpConfig {
+    /// Number of price buckets
+    #[serde(default = "default_vbp_buckets")]
+    pub buckets: usize,
+    /// Length of the fullest bucket's bar as a fraction of the pane width
+    #[serde(default = "default_vbp_width")]
+    pub width_ratio: f64,
+    /// Price each bar's volume is counted at
+    #[serde(default = "default_vbp_source")]
+    pub source: Source,
+    /// Line across the pane at the bucket with the most volume
+    #[serde(default)]
+    pub show_poc: bool,
+    /// Split each bar into up and down volume in the theme's colors
+    #[serde(default)]
+    pub split_up_down: bool,
+    /// Bar color when not split (a neutral gray when unset)
+    #[serde(default)]
+    pub color: Option<String>,
+}
+
+fn default_vbp_buckets() -> usize {
+    24
+}
+
+fn default_vbp_width() -> f64 {
+    0.3
+}
+
+fn default_vbp_source() -> Source {
+    Source::Hlc3
+}
+
+impl Default for VbpConfig {
+    fn default() -> Self {
+        Self {
+            buckets: default_vbp_buckets(),
+            width_ratio: default_vbp_width(),
+            source: default_vbp_source(),
+            show_poc: false,
+            split_up_down: false,
+            color: None,
+        }
+    }
+}
+
+impl VbpConfig {
+    pub fn new(buckets: usize, width_ratio: f64) -> Self {
+        Self {
+            buckets,
+            width_ratio,
+            ..Default::default()
+        }
+    }
+
+    pub fn with_source(mut self, source: Source) -> Self {
+        self.source = source;
+        self
+    }
+
+    pub fn with_poc(mut self) -> Self {
+        self.show_poc = true;
+        self
+    }
+
+    pub fn with_up_down(mut self) -> Self {
+        self.split_up_down = true;
+        self
+    }
+
+    pub fn with_color(mut self, color: &str) -> Self {
+        self.color = Some(color.to_string());
+        self
+    }
+}
+
+/// Gap between a corner inset and the pane edges, as a fraction of the pane
+pub const INSET_MARGIN: f64 = 0.02;
+
+/// Box of an inset as fractions of the main price pane, from its top-left
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq)]
+pub struct InsetRect {
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+}
+
+impl InsetRect {
+    pub fn new(x: f64, y: f64, width: f64, height: f64) -> Self {
+        Self {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    pub fn top_left(width: f64, height: f64) -> Self {
+        Self::new(INSET_MARGIN, INSET_MARGIN, width, height)
+    }
+
+    pub fn top_right(width: f64, height: f64) -> Self {
+        Self::new(1.0 - width - INSET_MARGIN, INSET_MARGIN, width, height)
+    }
+
+    pub fn bottom_left(width: f64, height: f64) -> Self {
+        Self::new(INSET_MARGIN, 1.0 - height - INSET_MARGIN, width, height)
+    }
+
+    pub fn bottom_right(width: f64, height: f64) -> Self {
+        Self::new(
+            1.0 - width - INSET_MARGIN,
+            1.0 - height - INSET_MARGIN,
+            width,
+            height,
+        )
+    }
+
+    /// Pixel box `(x, y, width, height)` in a pane of the given size,
+    /// kept inside the pane
+    pub fn to_pixels(&self, pane_width: f64, pane_height: f64) -> (f64, f64, f64, f64) {
+        let fraction = |v: f64| {
+            if v.is_finite() {
+                v.clamp(0.0, 1.0)
+            } else {
+                0.0
+            }
+        };
+        let (x, y) = (fraction(self.x), fraction(self.y));
+        let width = fraction(self.width).min(1.0 - x);
+        let height = fraction(self.height).min(1.0 - y);
+        (
+            x * pane_width,
+            y * pane_height,
+            width * pane_width,
+            height * pane_height,
+        )
+    }
+}
+
+/// Secondary series drawn as a mini line chart in a box on the main pane
+///
+/// The series is scaled to its own range inside the box and leaves the main
+/// price scale alone, e.g. a correlated index pinned to a corner.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct InsetSeries {
+    /// One close per bar
+    pub values: Vec<f64>,
+    pub rect: InsetRect,
+    /// Line color (a compare color by inset position when unset)
+    #[serde(default)]
+    pub color: Option<String>,
+    /// Caption in the top-left corner of the box
+    #[serde(default)]
+    pub label: Option<String>,
+}
+
+impl InsetSeries {
+    /// Inset of the closes of `bars`
+    pub fn new(bars: &[Bar], rect: InsetRect) -> Self {
+        Self {
+            values: bars.iter().map(|b| b.close).collect(),
+            rect,
+            color: None,
+            label: None,
+        }
+    }
+
+    pub fn with_color(mut self, color: &str) -> Self {
+        self.color = Some(color.to_string());
+        self
+    }
+
+    pub fn with_label(mut self, label: &str) -> Self {
+        self.label = Some(label.to_string());
+        self
+    }
+}
 
-    /// Theme configuration
-    pub theme: ThemeConfig,
+/// Share of the chart height taken by the tick pane under the bars
+pub const TICK_PANE_RATIO: f64 = 0.2;
 
-    /// Main series configuration
-    pub series: SeriesConfig,
+/// How a tick series is drawn
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TickStyle {
+    #[default]
+    Line,
+    /// Line with a translucent fill down to the pane bottom
+    Area,
+}
 
-    /// Indicators (overlays + subpanes unified)
-    /// Each Indicator has placement (Overlay or SubPane) and vectors with styles
-    #[serde(default)]
-    pub indicators: Vec<Indicator>,
+impl std::str::FromStr for TickStyle {
+    type Err = ();
 
-    /// Drawing primitives (trend lines, fibonacci, patterns, etc.)
-    #[serde(default)]
-    pub primitives: Vec<PrimitiveConfig>,
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "line" => Ok(Self::Line),
+            "area" => Ok(Self::Area),
+            _ => Err(()),
+        }
+    }
+}
 
-    /// Trading signals (buy/sell markers)
+/// Raw trade ticks: a price per timestamp, with no OHLC
+///
+/// Ticks are placed by time, so the series is drawn with at most four points
+/// per pixel column (see [`crate::downsample_m4`]) however many there
+/// are. Under bars they get their own pane and price scale, with each tick
+/// between the bars its time falls between; without bars they are the main
+/// series and drive the time scale.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct TickSeries {
+    /// `(timestamp, price)` sorted by time, timestamps in the chart's
+    /// `timestamp_unit` (`Auto` detects milliseconds from the ticks alone)
+    pub ticks: Vec<(i64, f64)>,
     #[serde(default)]
-    pub signals: Vec<SignalConfig>,
-
-    /// Layout configuration (multichart, sync)
+    pub style: TickStyle,
+    /// Line color (the theme's up color when unset)
     #[serde(default)]
-    pub layout: LayoutConfig,
+    pub color: Option<String>,
 }
 
-impl Default for ChartConfig {
-    fn default() -> Self {
+impl TickSeries {
+    pub fn new(ticks: &[(i64, f64)]) -> Self {
         Self {
-            width: 800,
-            height: 600,
-            dpr: 1.0,
-            theme: ThemeConfig::default(),
-            series: SeriesConfig::default(),
-            indicators: Vec::new(),
-            primitives: Vec::new(),
-            signals: Vec::new(),
-            layout: LayoutConfig::default(),
+            ticks: ticks.to_vec(),
+            ..Default::default()
+        }
+    }
+
+    pub fn with_style(mut self, style: TickStyle) -> Self {
+        self.style = style;
+        self
+    }
+
+    pub fn with_color(mut self, color: &str) -> Self {
+        self.color = Some(color.to_string());
+        self
+    }
+
+    /// Ticks with timestamps as fractional unix seconds, so sub-second
+    /// ticks in milliseconds keep their spacing
+    pub fn seconds(&self, unit: TimestampUnit) -> Vec<(f64, f64)> {
+        let scale = match unit.resolve(self.ticks.iter().map(|t| t.0)) {
+            TimestampUnit::Milliseconds => 1000.0,
+            _ => 1.0,
+        };
+        self.ticks
+            .iter()
+            .map(|&(ts, price)| (ts as f64 / scale, price))
+            .collect()
+    }
+}
+
+// =============================================================================
+// JSON Configuration
+// =============================================================================
+
+/// A JSON chart config that could not be read, with the path of the
+/// offending field, e.g. `indicators[1].placement`
+#[derive(Clone, Debug, PartialEq)]
+pub struct ConfigParseError {
+    /// Field path from the config root (empty for the root itself)
+    pub path: String,
+    pub message: String,
+}
+
+impl std::fmt::Display for ConfigParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.path.is_empty() {
+            write!(f, "{}", self.message)
+        } else {
+            write!(f, "{}: {}", self.path, self.message)
         }
     }
 }
 
+impl std::error::Error for ConfigParseError {}
+
 impl ChartConfig {
-    pub fn new(width: u32, height: u32) -> Self {
+    /// Read a config from JSON, filling fields it leaves out from
+    /// [`ChartConfig::default`]
+    ///
+    /// Nested objects are merged with the defaults, so `{"theme":
+    /// {"background": "#fff"}}` keeps the other theme colors. An indicator
+    /// given only as `{"params": {"kind": "rsi", "period": 14}}` (plus an
+    /// optional `id`, `name`, ...) becomes the built-in one of
+    /// [`Indicator::from_params`]; indicators with params and no values are
+    /// computed from the bars when rendered.
+    pub fn from_json(json: &str) -> Result<Self, ConfigParseError> {
+        let mut patch: Value =
+            serde_json::from_str(json).map_err(|e| ConfigParseError::at(json, &e))?;
+        if let Some(indicators) = patch.get_mut("indicators").and_then(Value::as_array_mut) {
+            expand_indicator_params(indicators)?;
+        }
+        let mut merged = serde_json::to_value(Self::default()).unwrap_or(Value::Null);
+        merge_json(&mut merged, patch);
+
+        // Parse from text, not the value, so errors carry a position to
+        // resolve into a field path
+        let text = serde_json::to_string_pretty(&merged).unwrap_or_default();
+        let mut config: Self =
+            serde_json::from_str(&text).map_err(|e| ConfigParseError::at(&text, &e))?;
+        for indicator in &mut config.indicators {
+            if indicator.params.is_some() && indicator.vectors.iter().all(|v| v.values.is_empty()) {
+                indicator.needs_recompute = true;
+            }
+        }
+        Ok(config)
+    }
+
+    /// Serialize to JSON, readable back with [`ChartConfig::from_json`]
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).unwrap_or_default()
+    }
+}
+
+impl ConfigParseError {
+    /// Error for `error`, with the path of the field at its position in `text`
+    fn at(text: &str, error: &serde_json::Error) -> Self {
+        let message = error.to_string();
+        let message = match message.rsplit_once(" at line ") {
+            Some((message, _)) => message.to_string(),
+            None => message,
+        };
         Self {
-            width,
-            height,
-            ..Default::default()
+            path: json_path_at(text, error.line(), error.column()),
+            message,
+        }
+    }
+}
+
+/// Replace each `{"params": ...}` indicator without vectors by the built-in
+/// indicator for those params, keeping the fields it does set
+fn expand_indicator_params(indicators: &mut [Value]) -> Result<(), ConfigParseError> {
+    let mut ids: Vec<String> = Vec::new();
+    for (i, entry) in indicators.iter_mut().enumerate() {
+        let Some(object) = entry.as_object() else {
+            continue;
+        };
+        let Some(params) = object.get("params") else {
+            continue;
+        };
+        if object.contains_key("vectors") {
+            continue;
+        }
+        let params: IndicatorParams =
+            serde_json::from_value(params.clone()).map_err(|e| ConfigParseError {
+                path: format!("indicators[{}].params", i),
+                message: e.to_string(),
+            })?;
+        let id = match object.get("id").and_then(Value::as_str) {
+            Some(id) => id.to_string(),
+            None => {
+                let kind = serde_json::to_value(params)
+                    .ok()
+                    .and_then(|v| v.get("kind").and_then(Value::as_str).map(str::to_string))
+                    .unwrap_or_default();
+                if ids.contains(&kind) {
+                    format!("{}_{}", kind, i)
+                } else {
+                    kind
+                }
+            }
+        };
+        let mut expanded =
+            serde_json::to_value(Indicator::from_params(&id, params)).unwrap_or(Value::Null);
+        merge_json(&mut expanded, std::mem::take(entry));
+        expanded["id"] = Value::String(id.clone());
+        *entry = expanded;
+        ids.push(id);
+    }
+    Ok(())
+}
+
+/// Merge `patch` into `base`: objects field by field, anything else replaced
+fn merge_json(base: &mut Value, patch: Value) {
+    match (base, patch) {
+        (Value::Object(base), Value::Object(patch)) => {
+            for (key, value) in patch {
+                match base.get_mut(&key) {
+                    Some(field) => merge_json(field, value),
+                    None => {
+                        base.insert(key, value);
+                    }
+                }
+            }
+        }
+        (base, patch) => *base = patch,
+    }
+}
+
+/// Path of the field at 1-based `line` and `column` of the JSON `text`,
+/// e.g. `indicators[1].placement`
+///
+/// At a closing brace or bracket (missing fields, wrong lengths) the path
+/// is that of the object or array itself.
+fn json_path_at(text: &str, line: usize, column: usize) -> String {
+    enum Frame {
+        Object(Option<String>),
+        Array(usize),
+    }
+
+    let line_start: usize = text
+        .split_inclusive('\n')
+        .take(line.saturating_sub(1))
+        .map(str::len)
+        .sum();
+    let offset = (line_start + column.saturating_sub(1)).min(text.len());
+
+    let mut frames: Vec<Frame> = Vec::new();
+    let mut in_string = false;
+    let mut escaped = false;
+    let mut expect_key = false;
+    let mut reading_key = false;
+    let mut string = String::new();
+    for (i, c) in text.char_indices() {
+        if i >= offset {
+            break;
+        }
+        if in_string {
+            match c {
+                _ if escaped => {
+                    escaped = false;
+                    string.push(c);
+                }
+                '\\' => escaped = true,
+                '"' => {
+                    in_string = false;
+                    if reading_key && let Some(Frame::Object(key)) = frames.last_mut() {
+                        *key = Some(std::mem::take(&mut string));
+                    }
+                }
+                _ => string.push(c),
+            }
+            continue;
+        }
+        match c {
+            '"' => {
+                in_string = true;
+                reading_key = expect_key && matches!(frames.last(), Some(Frame::Object(_)));
+                string.clear();
+            }
+            '{' => {
+                frames.push(Frame::Object(None));
+                expect_key = true;
+            }
+            '[' => {
+                frames.push(Frame::Array(0));
+                expect_key = false;
+            }
+            '}' | ']' => {
+                frames.pop();
+                expect_key = false;
+            }
+            ':' => expect_key = false,
+            ',' => match frames.last_mut() {
+                Some(Frame::Array(index)) => *index += 1,
+                Some(Frame::Object(_)) => expect_key = true,
+                None => {}
+            },
+            _ => {}
+        }
+    }
+
+    if matches!(text[offset..].chars().next(), Some('}' | ']')) {
+        frames.pop();
+    }
+    let mut path = String::new();
+    for frame in frames {
+        match frame {
+            Frame::Object(Some(key)) => {
+                if !path.is_empty() {
+                    path.push('.');
+                }
+                path.push_str(&key);
+            }
+            Frame::Array(index) => path.push_str(&format!("[{}]", index)),
+            Frame::Object(None) => {}
         }
     }
+    path
 }
 
 // =============================================================================
@@ -105,6 +1575,40 @@ pub struct ThemeConfig {
     pub text_color: String,
     /// Border color
     pub border_color: String,
+    /// Candle wick colors (unset = `up_color` / `down_color`)
+    #[serde(default)]
+    pub wick_up_color: Option<String>,
+    #[serde(default)]
+    pub wick_down_color: Option<String>,
+    /// Candle border colors (unset = `up_color` / `down_color`)
+    #[serde(default)]
+    pub candle_border_up_color: Option<String>,
+    #[serde(default)]
+    pub candle_border_down_color: Option<String>,
+    /// Glow blur radius in pixels around series and drawings (0 = none)
+    #[serde(default)]
+    pub glow: f64,
+    /// Crosshair line color, also used by the countdown close line and the
+    /// hover highlight
+    #[serde(default = "default_crosshair_color")]
+    pub crosshair_color: String,
+    /// Crosshair label background and text, also used by the countdown label
+    #[serde(default = "default_crosshair_label_bg")]
+    pub crosshair_label_bg: String,
+    #[serde(default = "default_crosshair_label_text")]
+    pub crosshair_label_text: String,
+}
+
+fn default_crosshair_color() -> String {
+    "#758696".into()
+}
+
+fn default_crosshair_label_bg() -> String {
+    "#363a45".into()
+}
+
+fn default_crosshair_label_text() -> String {
+    "#ffffff".into()
 }
 
 impl Default for ThemeConfig {
@@ -117,10 +1621,50 @@ impl Default for ThemeConfig {
             down_color: "#ef5350".into(),
             text_color: "#b2b5be".into(),
             border_color: "#2a2e39".into(),
+            wick_up_color: None,
+            wick_down_color: None,
+            candle_border_up_color: None,
+            candle_border_down_color: None,
+            glow: 0.0,
+            crosshair_color: default_crosshair_color(),
+            crosshair_label_bg: default_crosshair_label_bg(),
+            crosshair_label_text: default_crosshair_label_text(),
+        }
+    }
+}
+
+/// The colors of a runtime theme that a rendered chart uses: background,
+/// grid, scale text and border, candle bodies, wicks and borders, crosshair
+/// line and label, and glow.
+/// Toolbar, button and other UI colors have no counterpart in the SVG.
+impl From<&RuntimeTheme> for ThemeConfig {
+    fn from(theme: &RuntimeTheme) -> Self {
+        Self {
+            background: theme.chart.background.clone(),
+            grid_color: theme.chart.grid_line.clone(),
+            show_grid: false,
+            up_color: theme.series.candle_up_body.clone(),
+            down_color: theme.series.candle_down_body.clone(),
+            text_color: theme.chart.scale_text.clone(),
+            border_color: theme.chart.scale_border.clone(),
+            wick_up_color: Some(theme.series.candle_up_wick.clone()),
+            wick_down_color: Some(theme.series.candle_down_wick.clone()),
+            candle_border_up_color: theme.series.candle_up_border.clone(),
+            candle_border_down_color: theme.series.candle_down_border.clone(),
+            glow: theme.effects.glow,
+            crosshair_color: theme.chart.crosshair_line.clone(),
+            crosshair_label_bg: theme.chart.crosshair_label_bg.clone(),
+            crosshair_label_text: theme.chart.crosshair_label_text.clone(),
         }
     }
 }
 
+impl From<RuntimeTheme> for ThemeConfig {
+    fn from(theme: RuntimeTheme) -> Self {
+        Self::from(&theme)
+    }
+}
+
 impl ThemeConfig {
     /// Dark theme (default)
     pub fn dark() -> Self {
@@ -137,6 +1681,150 @@ impl ThemeConfig {
             down_color: "#ef5350".into(),
             text_color: "#434651".into(),
             border_color: "#dee2e6".into(),
+            wick_up_color: None,
+            wick_down_color: None,
+            candle_border_up_color: None,
+            candle_border_down_color: None,
+            glow: 0.0,
+            crosshair_color: "#9598a1".into(),
+            crosshair_label_bg: "#131722".into(),
+            crosshair_label_text: "#ffffff".into(),
+        }
+    }
+
+    /// Cyberpunk theme: neon candles and lines that glow
+    pub fn cyberpunk() -> Self {
+        Self::from(&RuntimeTheme::cyberpunk())
+    }
+    /// Strategy colors derived from this theme
+    ///
+    /// Buy/profit follow `up_color` and sell/loss follow `down_color`.
+    pub fn strategy_theme(&self) -> StrategyTheme {
+        StrategyTheme {
+            buy: self.up_color.clone(),
+            sell: self.down_color.clone(),
+            profit: self.up_color.clone(),
+            loss: self.down_color.clone(),
+            ..StrategyTheme::default()
+        }
+    }
+}
+
+// =============================================================================
+// Config Validation
+// =============================================================================
+
+/// Subpane height ratios past this leave the main chart too little room
+pub const MAX_SUBPANE_RATIO: f64 = 0.9;
+
+/// Lowest device pixel ratio drawn with; smaller ones are raised to it
+pub const MIN_DPR: f64 = 0.1;
+
+/// Highest device pixel ratio drawn with; larger ones are lowered to it
+pub const MAX_DPR: f64 = 8.0;
+
+/// Plot room kept on top of the scales by [`ChartConfig::min_size`]
+const MIN_PLOT_MARGIN: f64 = 16.0;
+
+/// Clamp a device pixel ratio to [`MIN_DPR`]..=[`MAX_DPR`]; NaN becomes 1
+pub fn clamp_dpr(dpr: f64) -> f64 {
+    if dpr.is_nan() {
+        1.0
+    } else {
+        dpr.clamp(MIN_DPR, MAX_DPR)
+    }
+}
+
+/// A chart that cannot be rendered as configured
+#[derive(Clone, Debug, PartialEq)]
+pub enum ChartError {
+    /// Width or height below [`ChartConfig::min_size`], leaving no room to
+    /// plot next to the scales
+    InvalidDimensions {
+        width: u32,
+        height: u32,
+        min_width: u32,
+        min_height: u32,
+    },
+}
+
+impl std::fmt::Display for ChartError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidDimensions {
+                width,
+                height,
+                min_width,
+                min_height,
+            } => write!(
+                f,
+                "chart size {}x{} is below the minimum {}x{}",
+                width, height, min_width, min_height
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ChartError {}
+
+/// A setting [`ChartConfig::validate`] expects to render wrong or not at all
+#[derive(Clone, Debug, PartialEq)]
+pub enum ConfigWarning {
+    /// No vector of the indicator has a value, usually because its period
+    /// is longer than the data
+    IndicatorWithoutValues { id: String, bars: usize },
+    /// Indicator without any vectors to draw
+    EmptyIndicator { id: String },
+    /// Several indicators share an id, so lookups by id only find the first
+    DuplicateIndicatorId { id: String },
+    /// Visible subpanes ask for more than [`MAX_SUBPANE_RATIO`] of the height
+    SubpaneRatios { total: f64 },
+    /// A color that does not parse and falls back to a default
+    InvalidColor { field: String, error: CssColorError },
+    /// A signal or primitive point outside the bars and the right offset
+    BarIndexOutOfRange {
+        field: String,
+        index: f64,
+        bars: usize,
+    },
+    /// Size below [`ChartConfig::min_size`]; only the background is drawn
+    InvalidDimensions { width: u32, height: u32 },
+    /// Elements left out of the SVG for NaN or infinite coordinates,
+    /// reported by [`crate::api::ChartRenderer::render_svg_with_stats`]
+    NonFiniteGeometry { elements: usize },
+}
+
+impl std::fmt::Display for ConfigWarning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::IndicatorWithoutValues { id, bars } => write!(
+                f,
+                "indicator '{}' has no values over {} bars (period longer than the data?)",
+                id, bars
+            ),
+            Self::EmptyIndicator { id } => write!(f, "indicator '{}' has no vectors", id),
+            Self::DuplicateIndicatorId { id } => {
+                write!(f, "indicator id '{}' is used more than once", id)
+            }
+            Self::SubpaneRatios { total } => write!(
+                f,
+                "subpane height ratios add up to {:.2}, above {}",
+                total, MAX_SUBPANE_RATIO
+            ),
+            Self::InvalidColor { field, error } => write!(f, "{}: {}", field, error),
+            Self::BarIndexOutOfRange { field, index, bars } => write!(
+                f,
+                "{}: bar index {} is outside the {} bars",
+                field, index, bars
+            ),
+            Self::InvalidDimensions { width, height } => {
+                write!(f, "chart size {}x{} is too small to draw", width, height)
+            }
+            Self::NonFiniteGeometry { elements } => write!(
+                f,
+                "{} elements with NaN or infinite coordinates were left out",
+                elements
+            ),
         }
     }
 }
@@ -153,6 +1841,9 @@ pub struct SeriesConfig {
     /// Style options (type-specific)
     #[serde(default)]
     pub style: SeriesStyleConfig,
+    /// Visibility, legend title and last-value options
+    #[serde(default)]
+    pub common: SeriesOptionsCommon,
 }
 
 impl Default for SeriesConfig {
@@ -160,6 +1851,7 @@ impl Default for SeriesConfig {
         Self {
             series_type: SeriesType::Candlestick,
             style: SeriesStyleConfig::default(),
+            common: SeriesOptionsCommon::default(),
         }
     }
 }
@@ -172,6 +1864,7 @@ impl SeriesConfig {
         Self {
             series_type: SeriesType::Candlestick,
             style: SeriesStyleConfig::default(),
+            common: SeriesOptionsCommon::default(),
         }
     }
 
@@ -180,6 +1873,7 @@ impl SeriesConfig {
         Self {
             series_type: SeriesType::HollowCandlestick,
             style: SeriesStyleConfig::default(),
+            common: SeriesOptionsCommon::default(),
         }
     }
 
@@ -188,6 +1882,7 @@ impl SeriesConfig {
         Self {
             series_type: SeriesType::HeikinAshi,
             style: SeriesStyleConfig::default(),
+            common: SeriesOptionsCommon::default(),
         }
     }
 
@@ -196,6 +1891,7 @@ impl SeriesConfig {
         Self {
             series_type: SeriesType::Bar,
             style: SeriesStyleConfig::default(),
+            common: SeriesOptionsCommon::default(),
         }
     }
 
@@ -204,6 +1900,7 @@ impl SeriesConfig {
         Self {
             series_type: SeriesType::HlcArea,
             style: SeriesStyleConfig::default(),
+            common: SeriesOptionsCommon::default(),
         }
     }
 
@@ -214,6 +1911,7 @@ impl SeriesConfig {
         Self {
             series_type: SeriesType::Line,
             style: SeriesStyleConfig::default(),
+            common: SeriesOptionsCommon::default(),
         }
     }
 
@@ -222,6 +1920,7 @@ impl SeriesConfig {
         Self {
             series_type: SeriesType::StepLine,
             style: SeriesStyleConfig::default(),
+            common: SeriesOptionsCommon::default(),
         }
     }
 
@@ -230,6 +1929,7 @@ impl SeriesConfig {
         Self {
             series_type: SeriesType::LineWithMarkers,
             style: SeriesStyleConfig::default(),
+            common: SeriesOptionsCommon::default(),
         }
     }
 
@@ -238,6 +1938,7 @@ impl SeriesConfig {
         Self {
             series_type: SeriesType::Area,
             style: SeriesStyleConfig::default(),
+            common: SeriesOptionsCommon::default(),
         }
     }
 
@@ -249,6 +1950,7 @@ impl SeriesConfig {
                 baseline_value: Some(baseline_value),
                 ..Default::default()
             },
+            common: SeriesOptionsCommon::default(),
         }
     }
 
@@ -257,6 +1959,7 @@ impl SeriesConfig {
         Self {
             series_type: SeriesType::Histogram,
             style: SeriesStyleConfig::default(),
+            common: SeriesOptionsCommon::default(),
         }
     }
 
@@ -265,6 +1968,7 @@ impl SeriesConfig {
         Self {
             series_type: SeriesType::Columns,
             style: SeriesStyleConfig::default(),
+            common: SeriesOptionsCommon::default(),
         }
     }
 
@@ -286,6 +1990,43 @@ impl SeriesConfig {
         self.style.line_width = Some(width);
         self
     }
+
+    /// Set a fixed candle wick width
+    pub fn with_wick_width(mut self, width: f64) -> Self {
+        self.style.wick_width = Some(width);
+        self
+    }
+
+    /// Scale candle wicks with the body width
+    pub fn with_wick_width_ratio(mut self, ratio: f64) -> Self {
+        self.style.wick_width_ratio = Some(ratio);
+        self
+    }
+
+    /// Drop wicks or collapse candles to high-low lines past `bars_per_pixel`
+    pub fn with_dense_candles(mut self, bars_per_pixel: f64, mode: DenseCandles) -> Self {
+        self.style.dense_threshold = Some(bars_per_pixel);
+        self.style.dense_candles = Some(mode);
+        self
+    }
+
+    /// Draw candle bodies at a share of the bar spacing or fixed pixels
+    pub fn with_candle_width(mut self, mode: CandleWidthMode) -> Self {
+        self.style.candle_width = Some(mode);
+        self
+    }
+
+    /// Choose whether Heikin Ashi wicks span the smoothed or the real range
+    pub fn with_ha_wick_source(mut self, source: HaWickSource) -> Self {
+        self.style.ha_wick_source = Some(source);
+        self
+    }
+
+    /// Name the series in the legend
+    pub fn with_title(mut self, title: &str) -> Self {
+        self.common.title = title.to_string();
+        self
+    }
 }
 
 /// Series style options
@@ -295,18 +2036,40 @@ pub struct SeriesStyleConfig {
     pub color: Option<String>,
     /// Up/bullish color
     pub up_color: Option<String>,
-    /// Down/bearish color
+    /// Down/bearish color (Area series: fill under `area_base`)
     pub down_color: Option<String>,
     /// Line width
     pub line_width: Option<f64>,
     /// Baseline value (for Baseline series)
     pub baseline_value: Option<f64>,
+    /// Value the fill drops to (Area series; unset = pane bottom)
+    pub area_base: Option<f64>,
     /// Show wicks (candlestick)
     pub show_wicks: Option<bool>,
     /// Show borders (candlestick)
     pub show_borders: Option<bool>,
     /// Fill opacity (area charts)
     pub fill_opacity: Option<f64>,
+    /// Wick width in pixels (candlestick; unset = scale with bar width)
+    pub wick_width: Option<f64>,
+    /// Wick width as a fraction of the body width, at least 1px
+    /// (candlestick; unset = 15% clamped to 1..=4px, `wick_width` wins)
+    pub wick_width_ratio: Option<f64>,
+    /// Bars per pixel past which candles fall back to `dense_candles`
+    /// (candlestick; unset = always full candles)
+    pub dense_threshold: Option<f64>,
+    /// What dense candles collapse to (unset = high-low lines)
+    pub dense_candles: Option<DenseCandles>,
+    /// Heikin Ashi wick range (unset = smoothed)
+    pub ha_wick_source: Option<HaWickSource>,
+    /// Candle body width (candlestick; unset = a share of the bar spacing)
+    pub candle_width: Option<CandleWidthMode>,
+    /// Open/close tick length in pixels (OHLC bars; unset = half the bar width)
+    pub tick_length: Option<f64>,
+    /// Draw the open tick (OHLC bars; unset = shown)
+    pub show_open_tick: Option<bool>,
+    /// Draw the close tick (OHLC bars; unset = shown)
+    pub show_close_tick: Option<bool>,
 }
 
 /// Line style type
@@ -330,6 +2093,10 @@ fn default_line_width() -> f64 {
 /// Drawing primitive configuration
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct PrimitiveConfig {
+    /// Identifier for selecting this primitive (see
+    /// [`ChartConfig::selected_primitive_ids`])
+    #[serde(default)]
+    pub id: Option<String>,
     /// Primitive type ID (matches PrimitiveRegistry)
     pub type_id: String,
     /// Control points [(bar_index, price), ...]
@@ -345,8 +2112,13 @@ pub struct PrimitiveConfig {
     pub line_style: LineStyleType,
     /// Fill color (for shapes)
     pub fill_color: Option<String>,
-    /// Fill opacity
+    /// Fill opacity for shapes and brushes (unset = primitive default)
     pub fill_opacity: Option<f64>,
+    /// Line opacity for shapes and brushes (unset = opaque).
+    /// Both opacities apply on top of the color's alpha; primitives are still
+    /// stacked in list order, so a translucent shape never changes what is on top.
+    #[serde(default)]
+    pub stroke_opacity: Option<f64>,
     /// Text label
     pub text: Option<String>,
     /// Extend mode (for lines)
@@ -357,6 +2129,40 @@ pub struct PrimitiveConfig {
     /// Target pane (main or subpane id)
     #[serde(default)]
     pub pane_id: Option<PaneId>,
+    /// Fill rule for freeform shapes (path, polyline, brush); unset = nonzero
+    #[serde(default)]
+    pub fill_rule: Option<FillRule>,
+    /// Band multiplier for regression channels (unset = primitive default)
+    #[serde(default)]
+    pub band_multiplier: Option<f64>,
+    /// Residual measure regression channel bands use (unset = standard deviation)
+    #[serde(default)]
+    pub band_deviation: Option<BandDeviation>,
+    /// Divergence kind for divergence events (unset = regular bullish)
+    #[serde(default)]
+    pub divergence: Option<DivergenceType>,
+    /// Label format for Fibonacci levels (unset = the chart's
+    /// [`ChartConfig::fib_labels`])
+    #[serde(default)]
+    pub fib_labels: Option<FibLabelMode>,
+    /// Indicator ID whose subpane each point's value is read on, by point
+    /// index; missing or unset entries stay on the primitive's own pane.
+    /// Primitives spanning panes are drawn over all panes at once.
+    #[serde(default)]
+    pub point_panes: Vec<Option<String>>,
+    /// How the first coordinate of each point is read
+    #[serde(default)]
+    pub anchor: PointAnchor,
+    /// Drawn at all; hidden primitives stay in the config
+    #[serde(default = "default_true")]
+    pub visible: bool,
+    /// Drawn but not hit-testable or selectable
+    #[serde(default)]
+    pub locked: bool,
+    /// Point prices stretch the main pane's automatic price range; off by
+    /// default, so a far-away drawing does not squash the bars
+    #[serde(default)]
+    pub autoscale: bool,
 }
 
 fn default_primitive_color() -> String {
@@ -374,6 +2180,18 @@ pub enum ExtendMode {
     Both,
 }
 
+/// Horizontal coordinate of primitive points
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum PointAnchor {
+    /// Bar index, fractional and past the last bar allowed
+    #[default]
+    BarIndex,
+    /// Timestamp in the chart's `timestamp_unit`; times past the data are
+    /// placed by the median bar interval
+    Timestamp,
+}
+
 /// Level configuration (for Fibonacci, Gann, etc.)
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct LevelConfig {
@@ -387,6 +2205,7 @@ impl PrimitiveConfig {
     /// Create a primitive config
     pub fn new(type_id: &str, points: Vec<(f64, f64)>) -> Self {
         Self {
+            id: None,
             type_id: type_id.into(),
             points,
             color: default_primitive_color(),
@@ -394,45 +2213,79 @@ impl PrimitiveConfig {
             line_style: LineStyleType::Solid,
             fill_color: None,
             fill_opacity: None,
+            stroke_opacity: None,
             text: None,
             extend: None,
             levels: Vec::new(),
             pane_id: None,
+            fill_rule: None,
+            band_multiplier: None,
+            band_deviation: None,
+            divergence: None,
+            fib_labels: None,
+            point_panes: Vec::new(),
+            anchor: PointAnchor::BarIndex,
+            visible: true,
+            locked: false,
+            autoscale: false,
         }
     }
 
+    /// Read point x coordinates as timestamps instead of bar indices
+    pub fn anchored_to_time(mut self) -> Self {
+        self.anchor = PointAnchor::Timestamp;
+        self
+    }
+
+    /// Keep the primitive in the config without drawing it
+    pub fn hidden(mut self) -> Self {
+        self.visible = false;
+        self
+    }
+
+    /// Draw the primitive but leave it out of hit-testing and selection
+    pub fn locked(mut self) -> Self {
+        self.locked = true;
+        self
+    }
+
     // =================================================================
     // Registry Integration
     // =================================================================
 
     /// Get all available primitive type IDs from the registry
     pub fn available_types() -> Vec<&'static str> {
-        let registry = PrimitiveRegistry::global().read().unwrap();
-        registry.all().map(|m| m.type_id).collect()
+        PrimitiveRegistry::global()
+            .all()
+            .map(|m| m.type_id)
+            .collect()
     }
 
     /// Get available primitives by kind
     pub fn types_by_kind(kind: PrimitiveKind) -> Vec<&'static str> {
-        let registry = PrimitiveRegistry::global().read().unwrap();
-        registry.by_kind(kind).to_vec()
+        PrimitiveRegistry::global().by_kind(kind).to_vec()
     }
 
     /// Get metadata for a primitive type
     pub fn metadata(type_id: &str) -> Option<PrimitiveMetadata> {
-        let registry = PrimitiveRegistry::global().read().unwrap();
-        registry.get(type_id).cloned()
+        PrimitiveRegistry::global().get(type_id).cloned()
     }
 
     /// Check if a type_id is valid (exists in registry)
     pub fn is_valid_type(type_id: &str) -> bool {
-        let registry = PrimitiveRegistry::global().read().unwrap();
-        registry.get(type_id).is_some()
+        PrimitiveRegistry::global().get(type_id).is_some()
+    }
+
+    /// Check the type ID and point count against the built-in registry
+    pub fn validate(&self) -> Result<(), PrimitiveError> {
+        PrimitiveRegistry::global().validate(&self.type_id, self.points.len())
     }
 
     /// Create a primitive instance from this config
-    pub fn create_primitive(&self) -> Option<Box<dyn crate::primitives::PrimitiveTrait>> {
-        let registry = PrimitiveRegistry::global().read().unwrap();
-        registry.create(&self.type_id, &self.points, Some(&self.color))
+    pub fn create_primitive(
+        &self,
+    ) -> Result<Box<dyn crate::primitives::PrimitiveTrait>, PrimitiveError> {
+        PrimitiveRegistry::global().create(&self.type_id, &self.points, Some(&self.color))
     }
 
     /// Create config from registry type_id with validation
@@ -460,6 +2313,11 @@ impl PrimitiveConfig {
         Self::new("vertical_line", vec![(bar_index, 0.0)])
     }
 
+    /// Vertical line at a time, e.g. a scheduled event after the last bar
+    pub fn vertical_line_at(timestamp: i64) -> Self {
+        Self::new("vertical_line", vec![(timestamp as f64, 0.0)]).anchored_to_time()
+    }
+
     pub fn ray(p1: (f64, f64), p2: (f64, f64)) -> Self {
         Self::new("ray", vec![p1, p2])
     }
@@ -787,7 +2645,7 @@ impl PrimitiveConfig {
     }
 
     // =================================================================
-    // Projection (6 types)
+    // Projection (7 types)
     // =================================================================
 
     pub fn long_position(entry: (f64, f64), tp: (f64, f64), sl: (f64, f64)) -> Self {
@@ -802,6 +2660,26 @@ impl PrimitiveConfig {
         Self::new("forecast", vec![p1, p2])
     }
 
+    /// Projected path from a model: one median, upper and lower value per
+    /// bar after `start_bar`, drawn as a dashed median in a shaded cone
+    ///
+    /// The series are cut to the shortest. Points hold the medians, then
+    /// the upper and the lower bounds, so the path moves and autoscales
+    /// like any other primitive.
+    pub fn forecast_path(start_bar: f64, median: &[f64], upper: &[f64], lower: &[f64]) -> Self {
+        let len = median.len().min(upper.len()).min(lower.len());
+        let points = [median, upper, lower]
+            .iter()
+            .flat_map(|values| {
+                values[..len]
+                    .iter()
+                    .enumerate()
+                    .map(move |(k, &price)| (start_bar + 1.0 + k as f64, price))
+            })
+            .collect();
+        Self::new("forecast_path", points)
+    }
+
     pub fn bars_pattern(p1: (f64, f64), p2: (f64, f64)) -> Self {
         Self::new("bars_pattern", vec![p1, p2])
     }
@@ -889,6 +2767,21 @@ impl PrimitiveConfig {
         Self::new("divergence", vec![p1, p2])
     }
 
+    /// Divergence linking a `(bar, price)` pivot on the price pane to a
+    /// `(bar, value)` pivot on the subpane of `indicator_id`, colored by kind
+    pub fn indicator_divergence(
+        price_pivot: (f64, f64),
+        indicator_pivot: (f64, f64),
+        indicator_id: &str,
+        kind: DivergenceType,
+    ) -> Self {
+        let mut config = Self::new("divergence", vec![price_pivot, indicator_pivot])
+            .with_color(kind.default_color())
+            .with_point_pane(1, indicator_id);
+        config.divergence = Some(kind);
+        config
+    }
+
     /// Pattern match event (detected chart pattern)
     pub fn pattern_match(points: Vec<(f64, f64)>) -> Self {
         Self::new("pattern_match", points)
@@ -925,6 +2818,12 @@ impl PrimitiveConfig {
     // Builder Methods
     // =================================================================
 
+    /// Identifier used to select the primitive
+    pub fn with_id(mut self, id: &str) -> Self {
+        self.id = Some(id.into());
+        self
+    }
+
     pub fn with_color(mut self, color: &str) -> Self {
         self.color = color.into();
         self
@@ -941,6 +2840,85 @@ impl PrimitiveConfig {
         self
     }
 
+    /// Fill color without an explicit opacity (the primitive picks its default)
+    pub fn with_fill_color(mut self, color: &str) -> Self {
+        self.fill_color = Some(color.into());
+        self
+    }
+
+    /// Fill rule for self-intersecting freeform shapes
+    pub fn with_fill_rule(mut self, rule: FillRule) -> Self {
+        self.fill_rule = Some(rule);
+        self
+    }
+
+    /// Read point `index` on the subpane of indicator `indicator_id`
+    pub fn with_point_pane(mut self, index: usize, indicator_id: &str) -> Self {
+        if self.point_panes.len() <= index {
+            self.point_panes.resize(index + 1, None);
+        }
+        self.point_panes[index] = Some(indicator_id.into());
+        self
+    }
+
+    /// Whether any point sits on another pane than the primitive's own
+    pub fn spans_panes(&self) -> bool {
+        self.point_panes.iter().any(Option::is_some)
+    }
+
+    /// Include the point prices in the main pane's automatic price range
+    pub fn with_autoscale(mut self, autoscale: bool) -> Self {
+        self.autoscale = autoscale;
+        self
+    }
+
+    /// Regression channel bands `multiplier` deviations from the center line
+    pub fn with_band(mut self, multiplier: f64, deviation: BandDeviation) -> Self {
+        self.band_multiplier = Some(multiplier);
+        self.band_deviation = Some(deviation);
+        self
+    }
+
+    pub fn with_fill_opacity(mut self, opacity: f64) -> Self {
+        self.fill_opacity = Some(opacity);
+        self
+    }
+
+    pub fn with_stroke_opacity(mut self, opacity: f64) -> Self {
+        self.stroke_opacity = Some(opacity);
+        self
+    }
+
+    /// Label each Fibonacci level with the ratio and, per `mode`, its
+    /// price or percent of the move
+    pub fn with_fib_labels(mut self, mode: FibLabelMode) -> Self {
+        self.fib_labels = Some(mode);
+        self
+    }
+
+    /// Apply the fill color, opacity, fill rule, band, divergence and
+    /// Fibonacci label overrides to a created primitive
+    pub fn apply_style(&self, primitive: &mut dyn PrimitiveTrait) {
+        if let Some(fill) = &self.fill_color {
+            primitive.data_mut().color.fill = Some(fill.clone());
+        }
+        if self.fill_opacity.is_some() || self.stroke_opacity.is_some() {
+            primitive.set_opacity(self.fill_opacity, self.stroke_opacity);
+        }
+        if let Some(rule) = self.fill_rule {
+            primitive.set_fill_rule(rule);
+        }
+        if self.band_multiplier.is_some() || self.band_deviation.is_some() {
+            primitive.set_band(self.band_multiplier, self.band_deviation);
+        }
+        if let Some(kind) = self.divergence {
+            primitive.set_divergence_type(kind);
+        }
+        if let Some(mode) = self.fib_labels {
+            primitive.set_fib_labels(mode);
+        }
+    }
+
     pub fn with_text(mut self, text: &str) -> Self {
         self.text = Some(text.into());
         self
@@ -984,12 +2962,18 @@ pub struct SignalConfig {
     pub label: Option<String>,
     /// Target pane
     pub pane_id: Option<PaneId>,
+    /// Draw a short level line with a price label (take profit and stop loss only)
+    #[serde(default)]
+    pub level_line: bool,
 }
 
 fn default_signal_size() -> f64 {
     1.0
 }
 
+/// Bars a take profit or stop loss level line extends right of its signal
+pub const SIGNAL_LEVEL_BARS: usize = 5;
+
 impl SignalConfig {
     pub fn new(signal_type: SignalType, bar_index: usize, price: f64) -> Self {
         Self {
@@ -1000,6 +2984,7 @@ impl SignalConfig {
             size: 1.0,
             label: None,
             pane_id: None,
+            level_line: false,
         }
     }
 
@@ -1052,6 +3037,13 @@ impl SignalConfig {
         self.pane_id = Some(pane_id);
         self
     }
+
+    /// Also draw the level as a short line with its price; only take profit
+    /// and stop loss signals have one
+    pub fn with_level_line(mut self) -> Self {
+        self.level_line = true;
+        self
+    }
 }
 
 // =============================================================================
@@ -1199,6 +3191,64 @@ impl LayoutConfig {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_from_json_merges_defaults_and_expands_params() {
+        let config = ChartConfig::from_json(
+            r##"{
+                "width": 640,
+                "theme": {"background": "#ffffff"},
+                "indicators": [
+                    {"params": {"kind": "rsi", "period": 7}},
+                    {"params": {"kind": "macd", "fast": 12, "slow": 26, "signal": 9}, "id": "m"}
+                ]
+            }"##,
+        )
+        .unwrap();
+        assert_eq!((config.width, config.height), (640, 600));
+        assert_eq!(config.theme.background, "#ffffff");
+        assert_eq!(config.theme.up_color, ThemeConfig::default().up_color);
+        let ids: Vec<&str> = config.indicators.iter().map(|i| i.id.as_str()).collect();
+        assert_eq!(ids, ["rsi", "m"]);
+        assert_eq!(config.indicators[0].name, "RSI 7");
+        assert!(config.indicators.iter().all(|i| i.needs_recompute));
+        assert!(config.indicators[1].placement.is_subpane());
+
+        let restored = ChartConfig::from_json(&config.to_json()).unwrap();
+        assert_eq!(restored.indicators.len(), 2);
+    }
+
+    #[test]
+    fn test_from_json_error_paths() {
+        let path = |json: &str| ChartConfig::from_json(json).unwrap_err().path;
+        assert_eq!(path(r#"{"width": "wide"}"#), "width");
+        assert_eq!(path(r#"{"theme": {"show_grid": 3}}"#), "theme.show_grid");
+        assert_eq!(
+            path(
+                r#"{"indicators": [{"params": {"kind": "rsi", "period": 14}}, {"params": {"kind": "sma", "period": 5}, "placement": "nowhere"}]}"#
+            ),
+            "indicators[1].placement"
+        );
+        assert_eq!(
+            path(r#"{"primitives": [{"points": [[0, 1]]}]}"#),
+            "primitives[0]"
+        );
+        assert_eq!(
+            path(r#"{"indicators": [{"params": {"kind": "bogus"}}]}"#),
+            "indicators[0].params"
+        );
+        let error = ChartConfig::from_json(
+            r#"{"signals": [{"signal_type": "buy", "bar_index": -1, "price": 1}]}"#,
+        )
+        .unwrap_err();
+        assert_eq!(error.path, "signals[0].bar_index");
+        assert!(
+            error
+                .to_string()
+                .starts_with("signals[0].bar_index: invalid value")
+        );
+        assert!(!error.message.contains("line"));
+    }
+
     #[test]
     fn test_default_config() {
         let config = ChartConfig::default();
@@ -1245,6 +3295,49 @@ mod tests {
         assert_eq!(fib.color, "#FFD700");
     }
 
+    #[test]
+    fn test_translate_and_scale_primitives_round_trip() {
+        let mut config = ChartConfig::new(800, 600);
+        config.primitives = vec![
+            PrimitiveConfig::trend_line((10.5, 101.37), (50.0, 120.25)).with_id("line"),
+            PrimitiveConfig::long_position((60.0, 110.0), (60.0, 118.5), (60.0, 104.75))
+                .with_id("long"),
+            PrimitiveConfig::text((30.0, 99.9), "note"),
+            PrimitiveConfig::horizontal_line(105.0).anchored_to_time(),
+        ];
+        let mut on_subpane = PrimitiveConfig::trend_line((5.0, 30.0), (25.0, 70.0));
+        on_subpane.point_panes = vec![Some("rsi".into()), None];
+        config.primitives.push(on_subpane);
+        let points = |config: &ChartConfig| -> Vec<Vec<(f64, f64)>> {
+            config.primitives.iter().map(|p| p.points.clone()).collect()
+        };
+        let before = points(&config);
+
+        // History prepended: every drawing moves 250 bars right
+        assert_eq!(config.translate_primitives(250.0, 0.5, None), 5);
+        let moved = points(&config);
+        assert_eq!(moved[0], [(260.5, 101.87), (300.0, 120.75)]);
+        assert_eq!(moved[1][2], (310.0, 105.25));
+        assert_eq!(moved[2], [(280.0, 100.4)]);
+        // Times stay put; the RSI value too
+        assert_eq!(moved[3][0].0, before[3][0].0);
+        assert_eq!(moved[4], [(255.0, 30.0), (275.0, 70.5)]);
+        config.translate_primitives(-250.0, -0.5, None);
+        assert_eq!(points(&config), before);
+
+        // 4:1 split on the selected drawings only
+        let ids = ["long".to_string()];
+        assert_eq!(
+            config.scale_primitives((0.0, 0.0), (1.0, 0.25), Some(&ids)),
+            1
+        );
+        let split = points(&config);
+        assert_eq!(split[1], [(60.0, 27.5), (60.0, 29.625), (60.0, 26.1875)]);
+        assert_eq!(split[0], before[0]);
+        config.scale_primitives((0.0, 0.0), (1.0, 4.0), Some(&ids));
+        assert_eq!(points(&config), before);
+    }
+
     #[test]
     fn test_signal_config() {
         let buy = SignalConfig::buy(25, 105.0);
@@ -1291,10 +3384,130 @@ mod tests {
                 SignalConfig::take_profit(60, 135.0),
             ],
             layout: LayoutConfig::single(),
+            ..Default::default()
         };
 
         assert_eq!(config.indicators.len(), 6);
         assert_eq!(config.primitives.len(), 3);
         assert_eq!(config.signals.len(), 3);
+        assert_eq!(config.color_warnings(), Vec::<String>::new());
+    }
+
+    #[test]
+    fn test_color_warnings_name_the_field() {
+        let mut config = ChartConfig {
+            indicators: vec![Indicator::sma("sma_20", 20, "#2196F")],
+            primitives: vec![PrimitiveConfig::horizontal_line(110.0).with_color("tomato")],
+            signals: vec![SignalConfig::buy(25, 105.0).with_color("hsl(120, 50%)")],
+            bar_colors: vec![None, Some("reddish".into())],
+            ..Default::default()
+        };
+        config.theme.grid_color = "rgb(1, 2, 3".into();
+
+        let warnings = config.color_warnings();
+        assert_eq!(warnings.len(), 4, "{:?}", warnings);
+        assert_eq!(
+            warnings[0],
+            "theme.grid_color: Invalid color 'rgb(1, 2, 3': missing closing parenthesis"
+        );
+        assert_eq!(warnings[1], "bar_colors[1]: Unknown color name: reddish");
+        assert!(warnings[2].starts_with("indicator 'sma_20' vector '"));
+        assert!(
+            warnings[2]
+                .ends_with(" color: Invalid hex color '#2196F': expected 3, 4, 6 or 8 hex digits")
+        );
+        assert!(warnings[3].starts_with("signals[0].color: "));
+    }
+    #[test]
+    fn test_validate_reports_each_warning_class() {
+        let line = |id: &str| Indicator::line(id, id, "#2196f3").values(vec![1.0; 50]);
+        let clean = ChartConfig {
+            indicators: vec![line("a"), line("b").subpane(0.3)],
+            signals: vec![SignalConfig::buy(49, 100.0)],
+            primitives: vec![PrimitiveConfig::trend_line((0.0, 90.0), (49.0, 110.0))],
+            ..Default::default()
+        };
+        assert_eq!(clean.validate(50), Vec::new());
+
+        let only = |config: ChartConfig| {
+            let warnings = config.validate(50);
+            assert_eq!(warnings.len(), 1, "{:?}", warnings);
+            warnings.into_iter().next().unwrap()
+        };
+
+        let mut config = clean.clone();
+        config.indicators[0] = Indicator::sma("sma_200", 200, "#2196f3").values(vec![f64::NAN; 50]);
+        let warning = only(config);
+        assert_eq!(
+            warning,
+            ConfigWarning::IndicatorWithoutValues {
+                id: "sma_200".into(),
+                bars: 50
+            }
+        );
+        assert_eq!(
+            warning.to_string(),
+            "indicator 'sma_200' has no values over 50 bars (period longer than the data?)"
+        );
+
+        let mut config = clean.clone();
+        config.indicators.push(Indicator::new("empty", "Empty"));
+        assert_eq!(
+            only(config),
+            ConfigWarning::EmptyIndicator { id: "empty".into() }
+        );
+
+        let mut config = clean.clone();
+        config.indicators.push(line("a"));
+        config.indicators.push(line("a"));
+        assert_eq!(
+            only(config),
+            ConfigWarning::DuplicateIndicatorId { id: "a".into() }
+        );
+
+        let mut config = clean.clone();
+        config.indicators.push(line("c").subpane(0.4));
+        config.indicators.push(line("d").subpane(0.5));
+        assert!(matches!(
+            only(config.clone()),
+            ConfigWarning::SubpaneRatios { total } if (total - 1.2).abs() < 1e-9
+        ));
+        // Hidden subpanes take no room
+        config.indicators[3].visible = false;
+        assert_eq!(config.validate(50), Vec::new());
+
+        let mut config = clean.clone();
+        config.theme.up_color = "greeen".into();
+        assert_eq!(
+            only(config).to_string(),
+            "theme.up_color: Unknown color name: greeen"
+        );
+
+        let mut config = clean.clone();
+        config.signals.push(SignalConfig::sell(50, 100.0));
+        assert_eq!(
+            only(config).to_string(),
+            "signals[1]: bar index 50 is outside the 50 bars"
+        );
+
+        let mut config = clean.clone();
+        config
+            .primitives
+            .push(PrimitiveConfig::trend_line((10.0, 90.0), (60.5, 110.0)));
+        assert_eq!(
+            only(config.clone()),
+            ConfigWarning::BarIndexOutOfRange {
+                field: "primitives[1]".into(),
+                index: 60.5,
+                bars: 50
+            }
+        );
+        // Points in the right offset are drawn into it
+        config.right_offset = 11.0;
+        assert_eq!(config.validate(50), Vec::new());
+        config.right_offset = 0.0;
+        // Time-anchored points are not bar indices
+        config.primitives[1] = config.primitives[1].clone().anchored_to_time();
+        assert_eq!(config.validate(50), Vec::new());
     }
 }
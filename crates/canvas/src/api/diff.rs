@@ -0,0 +1,501 @@
+//! Config Diff - what visually changes between two chart configs
+//!
+//! Produced by [`ChartConfig::diff`]. Fields are compared through their serde
+//! representation, so new config fields are covered without touching this
+//! module. Collections are matched by identity instead of position where one
+//! exists: indicators by id, primitives and signals by index.
+
+use super::config::ChartConfig;
+use serde_json::Value;
+use std::collections::HashSet;
+use std::fmt;
+
+/// Part of the config a change belongs to
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum DiffArea {
+    /// Dimensions, formatting and other top-level settings
+    Chart,
+    Series,
+    Theme,
+    Indicators,
+    Primitives,
+    Signals,
+    Layout,
+}
+
+impl DiffArea {
+    fn of_field(field: &str) -> Self {
+        match field {
+            "series" => Self::Series,
+            "theme" => Self::Theme,
+            "indicators" => Self::Indicators,
+            "primitives" | "fib_labels" => Self::Primitives,
+            "signals" => Self::Signals,
+            "layout" => Self::Layout,
+            _ => Self::Chart,
+        }
+    }
+}
+
+impl fmt::Display for DiffArea {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Chart => "chart",
+            Self::Series => "series",
+            Self::Theme => "theme",
+            Self::Indicators => "indicators",
+            Self::Primitives => "primitives",
+            Self::Signals => "signals",
+            Self::Layout => "layout",
+        })
+    }
+}
+
+/// One visual-affecting change
+///
+/// Values are rendered as compact JSON (`"#26a69a"`, `1.5`, `null`).
+#[derive(Clone, Debug, PartialEq)]
+pub enum ConfigChange {
+    /// A field changed; `path` starts at the config root
+    /// (`"theme.up_color"`, `"primitives[2].color"`)
+    Field {
+        area: DiffArea,
+        path: String,
+        old: String,
+        new: String,
+    },
+    IndicatorAdded {
+        id: String,
+    },
+    IndicatorRemoved {
+        id: String,
+    },
+    /// A parameter of an indicator present in both configs changed;
+    /// `param` is relative to the indicator (`"vectors[0].style.color"`)
+    IndicatorParam {
+        id: String,
+        param: String,
+        old: String,
+        new: String,
+    },
+    PrimitiveAdded {
+        index: usize,
+        type_id: String,
+    },
+    PrimitiveRemoved {
+        index: usize,
+        type_id: String,
+    },
+    /// Control points of a primitive changed
+    PrimitiveMoved {
+        index: usize,
+        type_id: String,
+        old: Vec<(f64, f64)>,
+        new: Vec<(f64, f64)>,
+    },
+    SignalAdded {
+        index: usize,
+        bar_index: usize,
+        price: f64,
+    },
+    SignalRemoved {
+        index: usize,
+        bar_index: usize,
+        price: f64,
+    },
+}
+
+impl ConfigChange {
+    /// Area the change is grouped under
+    pub fn area(&self) -> DiffArea {
+        match self {
+            Self::Field { area, .. } => *area,
+            Self::IndicatorAdded { .. }
+            | Self::IndicatorRemoved { .. }
+            | Self::IndicatorParam { .. } => DiffArea::Indicators,
+            Self::PrimitiveAdded { .. }
+            | Self::PrimitiveRemoved { .. }
+            | Self::PrimitiveMoved { .. } => DiffArea::Primitives,
+            Self::SignalAdded { .. } | Self::SignalRemoved { .. } => DiffArea::Signals,
+        }
+    }
+}
+
+fn format_points(points: &[(f64, f64)]) -> String {
+    points
+        .iter()
+        .map(|(x, y)| format!("({}, {})", x, y))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+impl fmt::Display for ConfigChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Field { path, old, new, .. } => write!(f, "{}: {} -> {}", path, old, new),
+            Self::IndicatorAdded { id } => write!(f, "+ indicator \"{}\"", id),
+            Self::IndicatorRemoved { id } => write!(f, "- indicator \"{}\"", id),
+            Self::IndicatorParam {
+                id,
+                param,
+                old,
+                new,
+            } => {
+                write!(f, "indicator \"{}\" {}: {} -> {}", id, param, old, new)
+            }
+            Self::PrimitiveAdded { index, type_id } => {
+                write!(f, "+ primitive #{} {}", index, type_id)
+            }
+            Self::PrimitiveRemoved { index, type_id } => {
+                write!(f, "- primitive #{} {}", index, type_id)
+            }
+            Self::PrimitiveMoved {
+                index,
+                type_id,
+                old,
+                new,
+            } => write!(
+                f,
+                "primitive #{} {} moved: {} -> {}",
+                index,
+                type_id,
+                format_points(old),
+                format_points(new)
+            ),
+            Self::SignalAdded {
+                index,
+                bar_index,
+                price,
+            } => write!(f, "+ signal #{} at bar {} @ {}", index, bar_index, price),
+            Self::SignalRemoved {
+                index,
+                bar_index,
+                price,
+            } => write!(f, "- signal #{} at bar {} @ {}", index, bar_index, price),
+        }
+    }
+}
+
+/// Visual-affecting changes from one config to another, see [`ChartConfig::diff`]
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ConfigDiff {
+    /// Changes in area order
+    pub changes: Vec<ConfigChange>,
+}
+
+impl ConfigDiff {
+    pub(crate) fn between(old: &ChartConfig, new: &ChartConfig) -> Self {
+        let (Ok(Value::Object(old_fields)), Ok(Value::Object(new_fields))) =
+            (serde_json::to_value(old), serde_json::to_value(new))
+        else {
+            return Self::default();
+        };
+
+        let mut changes = Vec::new();
+        for (field, old_value) in &old_fields {
+            let new_value = new_fields.get(field).unwrap_or(&Value::Null);
+            match field.as_str() {
+                "indicators" => diff_indicators(old, new, &mut changes),
+                "primitives" => diff_primitives(old, new, &mut changes),
+                "signals" => diff_signals(old, new, &mut changes),
+                // Serialized set order is arbitrary; compare as sets
+                "hidden_groups" => diff_set(
+                    field,
+                    DiffArea::Chart,
+                    (&old.hidden_groups, &new.hidden_groups),
+                    &mut changes,
+                ),
+                "hidden_indicator_ids" => diff_set(
+                    field,
+                    DiffArea::Indicators,
+                    (&old.hidden_indicator_ids, &new.hidden_indicator_ids),
+                    &mut changes,
+                ),
+                "selected_primitive_ids" => diff_set(
+                    field,
+                    DiffArea::Primitives,
+                    (&old.selected_primitive_ids, &new.selected_primitive_ids),
+                    &mut changes,
+                ),
+                _ => {
+                    let area = DiffArea::of_field(field);
+                    for (path, old, new) in diff_values(field, old_value, new_value) {
+                        changes.push(ConfigChange::Field {
+                            area,
+                            path,
+                            old,
+                            new,
+                        });
+                    }
+                }
+            }
+        }
+        // Stable sort keeps field order within an area
+        changes.sort_by_key(ConfigChange::area);
+        Self { changes }
+    }
+
+    /// Whether the two configs render the same
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// Number of changes
+    pub fn len(&self) -> usize {
+        self.changes.len()
+    }
+
+    /// Changes in one area
+    pub fn in_area(&self, area: DiffArea) -> impl Iterator<Item = &ConfigChange> {
+        self.changes.iter().filter(move |c| c.area() == area)
+    }
+}
+
+impl fmt::Display for ConfigDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_empty() {
+            return f.write_str("no visual changes\n");
+        }
+        let mut area = None;
+        for change in &self.changes {
+            if area != Some(change.area()) {
+                area = Some(change.area());
+                writeln!(f, "{}:", change.area())?;
+            }
+            writeln!(f, "  {}", change)?;
+        }
+        Ok(())
+    }
+}
+
+fn diff_indicators(old: &ChartConfig, new: &ChartConfig, changes: &mut Vec<ConfigChange>) {
+    for indicator in &old.indicators {
+        match new.indicators.iter().find(|i| i.id == indicator.id) {
+            None => changes.push(ConfigChange::IndicatorRemoved {
+                id: indicator.id.clone(),
+            }),
+            Some(other) => {
+                let (Ok(a), Ok(b)) = (serde_json::to_value(indicator), serde_json::to_value(other))
+                else {
+                    continue;
+                };
+                for (param, old, new) in diff_values("", &a, &b) {
+                    changes.push(ConfigChange::IndicatorParam {
+                        id: indicator.id.clone(),
+                        param,
+                        old,
+                        new,
+                    });
+                }
+            }
+        }
+    }
+    for indicator in &new.indicators {
+        if !old.indicators.iter().any(|i| i.id == indicator.id) {
+            changes.push(ConfigChange::IndicatorAdded {
+                id: indicator.id.clone(),
+            });
+        }
+    }
+}
+
+fn diff_set(
+    field: &str,
+    area: DiffArea,
+    (old, new): (&HashSet<String>, &HashSet<String>),
+    changes: &mut Vec<ConfigChange>,
+) {
+    if old == new {
+        return;
+    }
+    let sorted = |set: &HashSet<String>| {
+        let mut items: Vec<_> = set.iter().cloned().collect();
+        items.sort();
+        Value::from(items).to_string()
+    };
+    changes.push(ConfigChange::Field {
+        area,
+        path: field.to_string(),
+        old: sorted(old),
+        new: sorted(new),
+    });
+}
+
+fn diff_primitives(old: &ChartConfig, new: &ChartConfig, changes: &mut Vec<ConfigChange>) {
+    let count = old.primitives.len().max(new.primitives.len());
+    for index in 0..count {
+        match (old.primitives.get(index), new.primitives.get(index)) {
+            (Some(a), Some(b)) if a.type_id == b.type_id => {
+                if a.points != b.points {
+                    changes.push(ConfigChange::PrimitiveMoved {
+                        index,
+                        type_id: a.type_id.clone(),
+                        old: a.points.clone(),
+                        new: b.points.clone(),
+                    });
+                }
+                let (Ok(Value::Object(mut a)), Ok(Value::Object(mut b))) =
+                    (serde_json::to_value(a), serde_json::to_value(b))
+                else {
+                    continue;
+                };
+                a.remove("points");
+                b.remove("points");
+                let path = format!("primitives[{}]", index);
+                for (path, old, new) in diff_values(&path, &Value::Object(a), &Value::Object(b)) {
+                    changes.push(ConfigChange::Field {
+                        area: DiffArea::Primitives,
+                        path,
+                        old,
+                        new,
+                    });
+                }
+            }
+            (a, b) => {
+                if let Some(a) = a {
+                    changes.push(ConfigChange::PrimitiveRemoved {
+                        index,
+                        type_id: a.type_id.clone(),
+                    });
+                }
+                if let Some(b) = b {
+                    changes.push(ConfigChange::PrimitiveAdded {
+                        index,
+                        type_id: b.type_id.clone(),
+                    });
+                }
+            }
+        }
+    }
+}
+
+fn diff_signals(old: &ChartConfig, new: &ChartConfig, changes: &mut Vec<ConfigChange>) {
+    let count = old.signals.len().max(new.signals.len());
+    for index in 0..count {
+        match (old.signals.get(index), new.signals.get(index)) {
+            (Some(a), Some(b)) => {
+                let (Ok(a), Ok(b)) = (serde_json::to_value(a), serde_json::to_value(b)) else {
+                    continue;
+                };
+                let path = format!("signals[{}]", index);
+                for (path, old, new) in diff_values(&path, &a, &b) {
+                    changes.push(ConfigChange::Field {
+                        area: DiffArea::Signals,
+                        path,
+                        old,
+                        new,
+                    });
+                }
+            }
+            (Some(a), None) => changes.push(ConfigChange::SignalRemoved {
+                index,
+                bar_index: a.bar_index,
+                price: a.price,
+            }),
+            (None, Some(b)) => changes.push(ConfigChange::SignalAdded {
+                index,
+                bar_index: b.bar_index,
+                price: b.price,
+            }),
+            (None, None) => {}
+        }
+    }
+}
+
+/// Leaf differences between two JSON values as (path, old, new)
+///
+/// Objects recurse per key and equal-length arrays per element; anything
+/// else that differs is reported whole.
+fn diff_values(path: &str, old: &Value, new: &Value) -> Vec<(String, String, String)> {
+    let mut out = Vec::new();
+    collect_diffs(path, old, new, &mut out);
+    out
+}
+
+fn collect_diffs(path: &str, old: &Value, new: &Value, out: &mut Vec<(String, String, String)>) {
+    match (old, new) {
+        (Value::Object(a), Value::Object(b)) => {
+            let keys = a.keys().chain(b.keys().filter(|k| !a.contains_key(*k)));
+            for key in keys {
+                let child = if path.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{}.{}", path, key)
+                };
+                collect_diffs(
+                    &child,
+                    a.get(key).unwrap_or(&Value::Null),
+                    b.get(key).unwrap_or(&Value::Null),
+                    out,
+                );
+            }
+        }
+        (Value::Array(a), Value::Array(b)) if a.len() == b.len() => {
+            for (i, (x, y)) in a.iter().zip(b).enumerate() {
+                collect_diffs(&format!("{}[{}]", path, i), x, y, out);
+            }
+        }
+        _ if old != new => out.push((path.to_string(), compact(old), compact(new))),
+        _ => {}
+    }
+}
+
+/// Short JSON text for a value; long arrays collapse to their length
+fn compact(value: &Value) -> String {
+    match value {
+        Value::Array(items) if items.len() > 8 => format!("[{} items]", items.len()),
+        _ => value.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::PrimitiveConfig;
+    use crate::model::Indicator;
+
+    #[test]
+    fn test_diff_reports_exactly_the_changes() {
+        let old = ChartConfig {
+            indicators: vec![Indicator::sma("sma_20", 20, "#2196F3")],
+            primitives: vec![PrimitiveConfig::trend_line((10.0, 100.0), (50.0, 120.0))],
+            ..Default::default()
+        };
+        let mut new = old.clone();
+        new.theme.up_color = "#00ff00".into();
+        new.indicators[0] = Indicator::sma("sma_20", 20, "#FF9800");
+        new.primitives[0].points[1] = (55.0, 125.0);
+
+        assert!(old.diff(&old.clone()).is_empty());
+
+        let diff = old.diff(&new);
+        assert_eq!(diff.len(), 3, "{}", diff);
+        assert_eq!(
+            diff.changes[0],
+            ConfigChange::Field {
+                area: DiffArea::Theme,
+                path: "theme.up_color".into(),
+                old: "\"#26a69a\"".into(),
+                new: "\"#00ff00\"".into(),
+            }
+        );
+        let ConfigChange::IndicatorParam { id, param, .. } = &diff.changes[1] else {
+            panic!("expected indicator param change: {}", diff);
+        };
+        assert_eq!(id, "sma_20");
+        assert_eq!(param, "vectors[0].style.color");
+        assert_eq!(
+            diff.changes[2],
+            ConfigChange::PrimitiveMoved {
+                index: 0,
+                type_id: "trend_line".into(),
+                old: vec![(10.0, 100.0), (50.0, 120.0)],
+                new: vec![(10.0, 100.0), (55.0, 125.0)],
+            }
+        );
+        assert_eq!(
+            diff.to_string().lines().next_back(),
+            Some("  primitive #0 trend_line moved: (10, 100) (50, 120) -> (10, 100) (55, 125)")
+        );
+    }
+}
@@ -6,8 +6,9 @@
 //!
 //! For quick, simple charts with method chaining:
 //!
-//! ```rust,ignore
+//! ```
 //! use zengeld_canvas::api::Chart;
+//! # let bars = zengeld_canvas::core::testing::sample_bars(100);
 //!
 //! let svg = Chart::new(800, 600)
 //!     .bars(&bars)
@@ -15,15 +16,17 @@
 //!     .sma(20, "#2196F3")
 //!     .rsi(14)
 //!     .render_svg();
+//! assert!(svg.contains("<svg"));
 //! ```
 //!
 //! ## 2. Configuration Pattern (Full Control)
 //!
 //! For complex charts with declarative configuration:
 //!
-//! ```rust,ignore
-//! use zengeld_canvas::api::{ChartConfig, SeriesConfig};
+//! ```
+//! use zengeld_canvas::api::{ChartConfig, ChartRenderer, PrimitiveConfig, SeriesConfig, SignalConfig};
 //! use zengeld_canvas::model::Indicator;
+//! # let bars = zengeld_canvas::core::testing::sample_bars(100);
 //!
 //! let config = ChartConfig {
 //!     width: 1200,
@@ -49,6 +52,7 @@
 //! };
 //!
 //! let svg = ChartRenderer::new(&config, &bars).render_svg();
+//! assert!(svg.contains("<svg"));
 //! ```
 //!
 //! ## Coverage
@@ -62,19 +66,43 @@
 //! - **7 Signal Types**: Buy, Sell, Entry, Exit, TakeProfit, StopLoss, Custom
 //! - **Multichart Layouts**: Grid 2x2, 3x3, 1+3, vertical/horizontal stacks
 
+mod bar_info;
 mod chart;
 mod config;
+mod diff;
+mod stats;
 
 // Simple builder API
 pub use chart::{Chart, ChartRenderer, MultichartRenderer};
 
+// Hover payload
+pub use bar_info::{BarInfo, IndicatorInfo, IndicatorValueInfo, SignalInfo};
+
+// Render metrics
+pub use stats::{PhaseTimings, RenderStats};
+
+// Config comparison
+pub use diff::{ConfigChange, ConfigDiff, DiffArea};
+
 // Full configuration API
 pub use config::{
-    ChartConfig, ExtendMode, LayoutConfig, LayoutType, LevelConfig, LineStyleType, PrimitiveConfig,
-    SeriesConfig, SeriesStyleConfig, SignalConfig, ThemeConfig,
+    ChartConfig, ChartError, ConfigParseError, ConfigWarning, CountdownConfig, EmptyStateConfig,
+    ExtendMode, GapConfig, GapThreshold, HtfOverlayConfig, HtfOverlayStyle, INSET_MARGIN,
+    InsetRect, InsetSeries, LayoutConfig, LayoutType, LevelConfig, LineStyleType, MAX_DPR,
+    MAX_SUBPANE_RATIO, MIN_DPR, PointAnchor, PrimitiveConfig, SIGNAL_LEVEL_BARS, SeasonalConfig,
+    SeriesConfig, SeriesStyleConfig, SignalConfig, TICK_PANE_RATIO, ThemeConfig, TickSeries,
+    TickStyle, VbpConfig, clamp_dpr,
 };
 
 // Re-export Indicator types from model
 pub use crate::model::{
-    Indicator, IndicatorLevel, IndicatorPlacement, IndicatorRange, IndicatorVector, VectorStyle,
+    BandLine, BandStyle, Indicator, IndicatorFill, IndicatorLevel, IndicatorPlacement,
+    IndicatorRange, IndicatorVector, ScaleGroup, Signal, SignalVisual, Strategy, StrategyPrimitive,
+    StrategyTheme, VectorStyle, VolumeSplitMode,
 };
+
+// Seasonal mode types
+pub use crate::model::{SeasonalPeriod, Source};
+
+// Primitive validation errors and palette entries
+pub use crate::primitives::{PrimitiveError, PrimitiveInfo};
@@ -0,0 +1,125 @@
+//! Render Stats - per-render metrics for observability
+//!
+//! Produced by `ChartRenderer::render_svg_with_stats` /
+//! `Chart::render_svg_with_stats`. Plain `render_svg` never starts the
+//! clock or records commands.
+
+use super::config::ConfigWarning;
+use crate::render::engine::RenderBatch;
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+/// Metrics of one SVG render
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct RenderStats {
+    /// Bars drawn
+    pub bars: usize,
+    /// Render commands issued, keyed by variant name (e.g. `"FillRect"`)
+    pub commands: BTreeMap<&'static str, usize>,
+    /// Length of the SVG output in bytes
+    pub svg_bytes: usize,
+    /// Time spent in each render phase
+    pub timings: PhaseTimings,
+    /// What [`ChartConfig::validate`](super::ChartConfig::validate) found
+    /// in the rendered config
+    pub warnings: Vec<ConfigWarning>,
+}
+
+impl RenderStats {
+    /// Total render commands across all variants
+    pub fn command_count(&self) -> usize {
+        self.commands.values().sum()
+    }
+}
+
+/// Wall time per render phase
+///
+/// Always zero on wasm32, which has no monotonic clock in std.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct PhaseTimings {
+    /// Geometry, grid, scales, legend and everything not listed below
+    pub layout: Duration,
+    /// Main series and higher-timeframe candles
+    pub series: Duration,
+    /// Overlay, bottom and subpane indicators, and the strategy
+    pub indicators: Duration,
+    /// Primitives and signals on the main pane
+    pub primitives: Duration,
+    /// Building the SVG string from the drawn frame
+    pub serialization: Duration,
+}
+
+impl PhaseTimings {
+    /// Sum of all phases
+    pub fn total(&self) -> Duration {
+        self.layout + self.series + self.indicators + self.primitives + self.serialization
+    }
+}
+
+/// Render phase a [`PhaseClock`] lap is charged to
+#[derive(Clone, Copy, Debug)]
+pub(crate) enum Phase {
+    Layout,
+    Series,
+    Indicators,
+    Primitives,
+    Serialization,
+}
+
+/// Lap timer threaded through a render; a stopped clock does nothing
+#[derive(Debug, Default)]
+pub(crate) struct PhaseClock {
+    #[cfg(not(target_arch = "wasm32"))]
+    last: Option<std::time::Instant>,
+    timings: PhaseTimings,
+}
+
+impl PhaseClock {
+    /// Clock that never reads the time
+    pub(crate) fn stopped() -> Self {
+        Self::default()
+    }
+
+    /// Clock whose first lap starts now
+    pub(crate) fn start() -> Self {
+        Self {
+            #[cfg(not(target_arch = "wasm32"))]
+            last: Some(std::time::Instant::now()),
+            timings: PhaseTimings::default(),
+        }
+    }
+
+    /// Charge the time since the previous lap to `phase`
+    #[inline]
+    pub(crate) fn lap(&mut self, phase: Phase) {
+        #[cfg(not(target_arch = "wasm32"))]
+        if let Some(last) = &mut self.last {
+            let now = std::time::Instant::now();
+            let elapsed = now - *last;
+            *last = now;
+            let slot = match phase {
+                Phase::Layout => &mut self.timings.layout,
+                Phase::Series => &mut self.timings.series,
+                Phase::Indicators => &mut self.timings.indicators,
+                Phase::Primitives => &mut self.timings.primitives,
+                Phase::Serialization => &mut self.timings.serialization,
+            };
+            *slot += elapsed;
+        }
+        #[cfg(target_arch = "wasm32")]
+        let _ = phase;
+    }
+
+    pub(crate) fn timings(&self) -> PhaseTimings {
+        self.timings
+    }
+}
+
+/// Commands in `batch` counted by variant name
+pub(crate) fn command_counts(batch: &RenderBatch) -> BTreeMap<&'static str, usize> {
+    let mut counts = BTreeMap::new();
+    for cmd in batch.iter() {
+        *counts.entry(cmd.name()).or_insert(0) += 1;
+    }
+    counts
+}
@@ -30,13 +30,14 @@ pub use viewport::Viewport;
 
 // X-axis (TimeScale)
 pub use time_scale::{
-    DAY, HOUR, MINUTE, TickMarkWeight, TimeScale, TimeTick, format_time_by_weight, format_time_full,
+    DAY, HOUR, MINUTE, TickMarkWeight, TimeScale, TimeTick, format_countdown,
+    format_time_by_weight, format_time_full, format_time_pattern,
 };
 
 // Y-axis (PriceScale)
 pub use price_scale::{
-    NICE_MULTIPLIERS, PriceScale, PriceScaleMode, format_price, lwc_nice_number, nice_number,
-    nice_price_step, price_precision,
+    MAX_PRICE_TICKS, NICE_MULTIPLIERS, PriceScale, PriceScaleMode, format_price, lwc_nice_number,
+    nice_number, nice_price_step, price_precision, resolve_label_collisions,
 };
 
 // Legacy alias for ChartCoords users
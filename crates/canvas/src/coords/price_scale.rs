@@ -117,36 +117,83 @@ pub fn nice_price_step(range: f64, target_ticks: f64) -> f64 {
     nice_number(rough_step)
 }
 
+/// Most price ticks a scale generates, whatever the pane height
+pub const MAX_PRICE_TICKS: usize = 12;
+
 /// Determine decimal precision based on step size
+///
+/// Enough decimals that prices one step apart never format the same
+/// (0.5 -> 1, 0.25 -> 2, 0.0003 -> 4), up to 12.
 pub fn price_precision(step: f64) -> usize {
-    if step >= 1.0 {
-        0
-    } else if step >= 0.1 {
-        1
-    } else if step >= 0.01 {
-        2
-    } else if step >= 0.001 {
-        3
-    } else if step >= 0.0001 {
-        4
+    if !step.is_finite() || step <= 0.0 {
+        return 0;
+    }
+    let decimals = (-step.log10() - 1e-9).ceil().clamp(0.0, 12.0) as usize;
+    // A step like 0.25 needs one more digit than its magnitude
+    let scaled = step * 10f64.powi(decimals as i32);
+    if decimals < 12 && (scaled - scaled.round()).abs() > 1e-6 * scaled {
+        decimals + 1
     } else {
-        5
+        decimals
     }
 }
 
+/// Decimal places implied by an instrument tick size
+/// (0.25 -> 2, 0.5 -> 1, 0.0001 -> 4, 5.0 -> 0)
+pub fn tick_precision(tick_size: f64) -> usize {
+    (0..8)
+        .find(|&decimals| {
+            let scaled = tick_size * 10f64.powi(decimals as i32);
+            (scaled - scaled.round()).abs() < 1e-9 * scaled.max(1.0)
+        })
+        .unwrap_or(8)
+}
+
 /// Format a price value with appropriate precision based on step
+///
+/// Negative prices keep their minus sign; values that round to zero
+/// never show as "-0.00".
 pub fn format_price(price: f64, step: f64) -> String {
-    let precision = price_precision(step);
-    match precision {
-        0 => format!("{:.0}", price),
-        1 => format!("{:.1}", price),
-        2 => format!("{:.2}", price),
-        3 => format!("{:.3}", price),
-        4 => format!("{:.4}", price),
-        _ => format!("{:.5}", price),
+    format_fixed(price, price_precision(step))
+}
+
+/// `price` with `decimals` digits, without a negative zero
+fn format_fixed(price: f64, decimals: usize) -> String {
+    let text = format!("{:.*}", decimals, price);
+    match text.strip_prefix('-') {
+        Some(unsigned) if unsigned.bytes().all(|b| b == b'0' || b == b'.') => unsigned.to_string(),
+        _ => text,
     }
 }
 
+/// Multiples of `step` within `lo..=hi`, at most [`MAX_PRICE_TICKS`]
+///
+/// Ticks are computed by index rather than by accumulating `step`, so
+/// float drift neither skips nor duplicates values. Returns nothing when
+/// `step` is zero, non-finite or too small to tell adjacent ticks apart.
+fn step_grid(lo: f64, hi: f64, step: f64) -> Vec<f64> {
+    let magnitude = lo.abs().max(hi.abs());
+    if !step.is_finite() || step <= magnitude * f64::EPSILON * 16.0 {
+        return Vec::new();
+    }
+    let first = (lo / step).ceil();
+    let mut ticks: Vec<f64> = (0..MAX_PRICE_TICKS)
+        // `+ 0.0` turns a -0.0 tick into 0.0
+        .map(|i| (first + i as f64) * step + 0.0)
+        .take_while(|&price| price <= hi)
+        .collect();
+    ticks.dedup();
+    ticks
+}
+
+/// `count` evenly spaced prices from `lo` to `hi` inclusive
+fn linspace(lo: f64, hi: f64, count: usize) -> Vec<f64> {
+    let last = (count.max(2) - 1) as f64;
+    (0..count.max(2))
+        .map(|i| lo + (hi - lo) * i as f64 / last)
+        .collect()
+}
+
 // =============================================================================
 // Price Scale
 // =============================================================================
@@ -166,6 +213,9 @@ pub struct PriceScale {
     pub mode: PriceScaleMode,
     /// Base price for percent mode (usually first visible bar's close)
     pub base_price: f64,
+    /// Instrument tick size; grid ticks land on its multiples and labels
+    /// use its precision
+    pub tick_size: Option<f64>,
 }
 
 impl Default for PriceScale {
@@ -177,6 +227,7 @@ impl Default for PriceScale {
             width: PRICE_SCALE_WIDTH, // Fixed constant width
             mode: PriceScaleMode::Normal,
             base_price: 100.0,
+            tick_size: None,
         }
     }
 }
@@ -191,19 +242,79 @@ impl PriceScale {
         }
     }
 
+    /// Snap ticks and label precision to an instrument tick size
+    ///
+    /// Non-positive or non-finite sizes are ignored.
+    pub fn with_tick_size(mut self, tick_size: Option<f64>) -> Self {
+        self.tick_size = tick_size.filter(|t| t.is_finite() && *t > 0.0);
+        self
+    }
+
     /// Get the current price range
     #[inline]
     pub fn range(&self) -> f64 {
         self.price_max - self.price_min
     }
 
+    /// The range ticks are generated over
+    ///
+    /// A range that is zero, or too small to resolve at the prices'
+    /// magnitude (a flat series), is widened by 1% either side of its
+    /// middle, or by 1 around zero. `None` when the bounds are not finite.
+    pub fn tick_bounds(&self) -> Option<(f64, f64)> {
+        if !self.price_min.is_finite() || !self.price_max.is_finite() {
+            return None;
+        }
+        let (lo, hi) = (
+            self.price_min.min(self.price_max),
+            self.price_min.max(self.price_max),
+        );
+        let mid = lo / 2.0 + hi / 2.0;
+        if hi - lo > mid.abs() * 1e-12 {
+            return Some((lo, hi));
+        }
+        let half = if mid == 0.0 { 1.0 } else { mid.abs() * 0.01 };
+        Some((mid - half, mid + half))
+    }
+
     /// Calculate nice price step using [2, 2.5, 2] pattern
     ///
-    /// Uses approximately 30px between price grid lines.
+    /// Uses approximately 30px between price grid lines, coarsened until
+    /// there are at most [`MAX_PRICE_TICKS`]. With a tick size the step is
+    /// rounded up to a whole number of ticks.
     pub fn calc_step(&self, chart_height: f64) -> f64 {
-        let range = self.range();
-        let target_ticks = (chart_height / 30.0).clamp(4.0, 20.0);
-        nice_price_step(range, target_ticks)
+        let Some((lo, hi)) = self.tick_bounds() else {
+            return 1.0;
+        };
+        let range = hi - lo;
+        let target_ticks = if chart_height.is_finite() {
+            (chart_height / 30.0).clamp(4.0, 20.0)
+        } else {
+            4.0
+        };
+        let mut step = self.snap_step(nice_price_step(range, target_ticks));
+        for _ in 0..64 {
+            if range / step < MAX_PRICE_TICKS as f64 {
+                break;
+            }
+            step = self.snap_step(nice_number(step * 1.5));
+        }
+        step
+    }
+
+    /// Round a step up to a whole number of ticks when a tick size is set
+    fn snap_step(&self, step: f64) -> f64 {
+        match self.tick_size {
+            Some(tick) => {
+                let ticks = step / tick;
+                if (ticks - ticks.round()).abs() < 1e-9 {
+                    ticks.round().max(1.0) * tick
+                } else {
+                    ticks.ceil() * tick
+                }
+            }
+            None => step,
+        }
     }
 
     /// Get the fixed width constant
@@ -213,10 +324,17 @@ impl PriceScale {
         PRICE_SCALE_WIDTH
     }
 
-    /// Format a price using the current step
+    /// Format a price using the current step, or the tick size's
+    /// precision when one is set (also for prices between ticks)
     pub fn format_price(&self, price: f64, chart_height: f64) -> String {
-        let step = self.calc_step(chart_height);
-        format_price(price, step)
+        self.format_with_step(price, self.calc_step(chart_height))
+    }
+
+    fn format_with_step(&self, price: f64, step: f64) -> String {
+        match self.tick_size {
+            Some(tick) => format_fixed(price, tick_precision(tick)),
+            None => format_price(price, step),
+        }
     }
 
     /// Calculate dynamic font size based on label length
@@ -227,17 +345,14 @@ impl PriceScale {
         let step = self.calc_step(chart_height);
 
         // Find the longest label
-        let first = (self.price_min / step).ceil() * step;
         let mut max_len = 0;
-        let mut price = first;
-        while price < self.price_max {
-            let label = format_price(price, step);
+        for price in self.generate_ticks(chart_height) {
+            let label = self.format_with_step(price, step);
             max_len = max_len.max(label.len());
-            price += step;
         }
 
         // Also check the max price label
-        let max_label = format_price(self.price_max, step);
+        let max_label = self.format_with_step(self.price_max, step);
         max_len = max_len.max(max_label.len());
 
         // Dynamic font size: fewer chars = bigger font, more chars = smaller font
@@ -254,6 +369,22 @@ impl PriceScale {
         }
     }
 
+    /// Width of the widest price label, as measured by `measure_text`
+    ///
+    /// Covers every tick label and, like [`Self::calc_font_size`], the
+    /// labels of the range ends.
+    pub fn label_width<F>(&self, chart_height: f64, measure_text: F) -> f64
+    where
+        F: Fn(&str) -> f64,
+    {
+        let step = self.calc_step(chart_height);
+        self.generate_ticks(chart_height)
+            .into_iter()
+            .chain([self.price_min, self.price_max])
+            .map(|price| measure_text(&self.format_with_step(price, step)))
+            .fold(0.0, f64::max)
+    }
+
     /// Calculate auto-scale based on visible bars and optional MA values
     ///
     /// Updates price_min and price_max to fit the visible data with padding.
@@ -299,16 +430,50 @@ impl PriceScale {
         }
     }
 
+    /// Price labels for a pane as `(price, y)`, with overlapping ones removed
+    ///
+    /// The pane spans `y_offset..y_offset + height`. See
+    /// [`resolve_label_collisions`] for how labels are chosen.
+    pub fn label_ticks(
+        &self,
+        y_offset: f64,
+        height: f64,
+        font_size: f64,
+        tags: &[f64],
+    ) -> Vec<(f64, f64)> {
+        let Some((lo, hi)) = self.tick_bounds() else {
+            return Vec::new();
+        };
+        let ticks: Vec<(f64, f64)> = self
+            .generate_ticks(height)
+            .into_iter()
+            .map(|price| {
+                let ratio = (price - lo) / (hi - lo);
+                (price, y_offset + height - ratio * height)
+            })
+            .collect();
+        resolve_label_collisions(&ticks, font_size, (y_offset, y_offset + height), tags)
+    }
+
     /// Generate price tick values for the grid
+    ///
+    /// Any finite range yields between 2 and [`MAX_PRICE_TICKS`] strictly
+    /// increasing ticks within [`tick_bounds`](Self::tick_bounds). When the
+    /// step leaves fewer than two, the range is split evenly instead.
     pub fn generate_ticks(&self, chart_height: f64) -> Vec<f64> {
-        let step = self.calc_step(chart_height);
-        let first = (self.price_min / step).ceil() * step;
-
-        let mut ticks = Vec::new();
-        let mut price = first;
-        while price < self.price_max {
-            ticks.push(price);
-            price += step;
+        let Some((lo, hi)) = self.tick_bounds() else {
+            return Vec::new();
+        };
+        let mut ticks = step_grid(lo, hi, self.calc_step(chart_height));
+        if let Some(tick) = self.tick_size {
+            // Snap to the tick grid so float drift never shows in labels
+            for price in &mut ticks {
+                *price = (*price / tick).round() * tick;
+            }
+            ticks.dedup();
+        }
+        if ticks.len() < 2 {
+            ticks = linspace(lo, hi, 2);
         }
         ticks
     }
@@ -322,6 +487,15 @@ impl PriceScale {
         self.mode = mode;
     }
 
+    /// The mode actually used for mapping and ticks: a logarithmic scale
+    /// over a range reaching zero or below (spreads, rates) is drawn linear
+    pub fn effective_mode(&self) -> PriceScaleMode {
+        match self.mode {
+            PriceScaleMode::Logarithmic if self.price_min <= 0.0 => PriceScaleMode::Normal,
+            mode => mode,
+        }
+    }
+
     /// Toggle to next scale mode
     pub fn toggle_mode(&mut self) {
         self.mode = self.mode.next();
@@ -355,7 +529,7 @@ impl PriceScale {
     /// Uses inverted Y axis (price increases upward, Y increases downward).
     #[inline]
     pub fn price_to_y(&self, price: f64, chart_height: f64) -> f64 {
-        match self.mode {
+        match self.effective_mode() {
             PriceScaleMode::Normal => {
                 // Linear: Y = height * (1 - (price - min) / range)
                 let range = self.price_max - self.price_min;
@@ -398,7 +572,7 @@ impl PriceScale {
     /// Convert Y coordinate to price using current scale mode
     #[inline]
     pub fn y_to_price(&self, y: f64, chart_height: f64) -> f64 {
-        match self.mode {
+        match self.effective_mode() {
             PriceScaleMode::Normal => {
                 // Linear: price = max - (y / height) * range
                 let range = self.price_max - self.price_min;
@@ -427,9 +601,64 @@ impl PriceScale {
         }
     }
 
+    /// Price range after dragging the scale by `delta_y` pixels
+    ///
+    /// Dragging down (positive delta) expands the range and dragging up
+    /// compresses it, keeping the price under `anchor_y` fixed. Each pane
+    /// height of drag scales the range by a factor of e. Log scales stretch
+    /// in log space. The factor is clamped, so min stays below max.
+    pub fn drag_scale(&self, anchor_y: f64, delta_y: f64, pane_height: f64) -> (f64, f64) {
+        if pane_height <= 0.0 || !delta_y.is_finite() || !anchor_y.is_finite() {
+            return (self.price_min, self.price_max);
+        }
+        let factor = (delta_y / pane_height).exp().clamp(1e-6, 1e6);
+        let anchor = self.y_to_price(anchor_y, pane_height);
+
+        if self.effective_mode() == PriceScaleMode::Logarithmic {
+            let (log_min, log_max) = self.log_bounds();
+            let log_anchor = anchor.max(0.0001).ln();
+            (
+                (log_anchor - (log_anchor - log_min) * factor).exp(),
+                (log_anchor + (log_max - log_anchor) * factor).exp(),
+            )
+        } else {
+            (
+                anchor - (anchor - self.price_min) * factor,
+                anchor + (self.price_max - anchor) * factor,
+            )
+        }
+    }
+
+    /// Price range after panning the scale by `delta_y` pixels
+    ///
+    /// Dragging down (positive delta) moves the content down, so both bounds
+    /// rise by the price the drag covers. Log scales shift in log space.
+    pub fn pan(&self, delta_y: f64, pane_height: f64) -> (f64, f64) {
+        if pane_height <= 0.0 || !delta_y.is_finite() {
+            return (self.price_min, self.price_max);
+        }
+        let ratio = delta_y / pane_height;
+
+        if self.effective_mode() == PriceScaleMode::Logarithmic {
+            let (log_min, log_max) = self.log_bounds();
+            let shift = ratio * (log_max - log_min);
+            ((log_min + shift).exp(), (log_max + shift).exp())
+        } else {
+            let shift = ratio * self.range();
+            (self.price_min + shift, self.price_max + shift)
+        }
+    }
+
+    /// Natural-log bounds, guarded the same way as `price_to_y`
+    fn log_bounds(&self) -> (f64, f64) {
+        let safe_min = self.price_min.max(0.0001);
+        let safe_max = self.price_max.max(safe_min + 0.0001);
+        (safe_min.ln(), safe_max.ln())
+    }
+
     /// Format label for price scale based on current mode
     pub fn format_label(&self, price: f64, chart_height: f64) -> String {
-        match self.mode {
+        match self.effective_mode() {
             PriceScaleMode::Normal => self.format_price(price, chart_height),
             PriceScaleMode::Percent => {
                 let pct = self.price_to_percent(price);
@@ -448,7 +677,7 @@ impl PriceScale {
 
     /// Generate tick values appropriate for current scale mode
     pub fn generate_ticks_for_mode(&self, chart_height: f64) -> Vec<f64> {
-        match self.mode {
+        match self.effective_mode() {
             PriceScaleMode::Normal => self.generate_ticks(chart_height),
             PriceScaleMode::Percent => {
                 // Generate percent-based ticks, convert back to prices
@@ -458,14 +687,10 @@ impl PriceScale {
                 let target_ticks = (chart_height / 30.0).clamp(4.0, 20.0);
                 let step = nice_price_step(pct_range, target_ticks);
 
-                let first = (pct_min / step).ceil() * step;
-                let mut ticks = Vec::new();
-                let mut pct = first;
-                while pct < pct_max {
-                    ticks.push(self.percent_to_price(pct));
-                    pct += step;
-                }
-                ticks
+                step_grid(pct_min, pct_max, step)
+                    .into_iter()
+                    .map(|pct| self.percent_to_price(pct))
+                    .collect()
             }
             PriceScaleMode::Logarithmic => {
                 // Generate log-spaced ticks
@@ -479,19 +704,98 @@ impl PriceScale {
                 let target_ticks = (chart_height / 30.0).clamp(4.0, 20.0);
                 let log_step = nice_price_step(log_range, target_ticks);
 
-                let first = (log_min / log_step).ceil() * log_step;
-                let mut ticks = Vec::new();
-                let mut log_val = first;
-                while log_val < log_max {
-                    ticks.push(10.0_f64.powf(log_val));
-                    log_val += log_step;
-                }
-                ticks
+                step_grid(log_min, log_max, log_step)
+                    .into_iter()
+                    .map(|log_val| 10.0_f64.powf(log_val))
+                    .collect()
             }
         }
     }
 }
 
+// =============================================================================
+// Label Collision Resolution
+// =============================================================================
+
+/// Pick which price labels to draw so that none overlap
+///
+/// `ticks` are `(price, y)` pairs in tick order, `pane` is the `(top, bottom)`
+/// pixel extent of the pane and `tags` are the Y positions of value tags
+/// (last price, indicator values, price lines) drawn on the same scale.
+/// A label is treated as `font_size` pixels tall.
+///
+/// Labels whose text would cross the pane's top or bottom edge are dropped
+/// first, so adjacent panes never collide at their shared border. While any
+/// two labels are closer than `font_size`, every other label is dropped,
+/// keeping whichever half contains the roundest price. Finally, labels
+/// covered by a value tag are suppressed.
+pub fn resolve_label_collisions(
+    ticks: &[(f64, f64)],
+    font_size: f64,
+    pane: (f64, f64),
+    tags: &[f64],
+) -> Vec<(f64, f64)> {
+    let (top, bottom) = pane;
+    let half = font_size / 2.0;
+
+    let mut labels: Vec<(f64, f64)> = ticks
+        .iter()
+        .copied()
+        .filter(|&(_, y)| y - half >= top && y + half <= bottom)
+        .collect();
+
+    let collides = |labels: &[(f64, f64)]| {
+        labels
+            .windows(2)
+            .any(|w| (w[1].1 - w[0].1).abs() < font_size)
+    };
+
+    while labels.len() > 1 && collides(&labels) {
+        let (even, odd): (Vec<_>, Vec<_>) =
+            labels.iter().enumerate().partition(|(i, _)| i % 2 == 0);
+        let even: Vec<(f64, f64)> = even.into_iter().map(|(_, &l)| l).collect();
+        let odd: Vec<(f64, f64)> = odd.into_iter().map(|(_, &l)| l).collect();
+
+        let rank = |half: &[(f64, f64)]| {
+            let best = half
+                .iter()
+                .map(|&(price, _)| price_roundness(price))
+                .max()
+                .unwrap_or(i32::MIN);
+            (best, half.len())
+        };
+        labels = if rank(&odd) > rank(&even) { odd } else { even };
+    }
+
+    labels.retain(|&(_, y)| tags.iter().all(|tag| (y - tag).abs() >= font_size));
+    labels
+}
+
+/// How "round" a price is: higher for multiples of larger powers of ten,
+/// with a half step for multiples of five (100 > 50 > 10 > 5 > 1 > 0.5)
+fn price_roundness(price: f64) -> i32 {
+    if price == 0.0 {
+        return i32::MAX;
+    }
+    let is_multiple = |unit: f64| {
+        let q = price / unit;
+        q.round() != 0.0 && (q - q.round()).abs() < 1e-6
+    };
+    (-8..=12)
+        .rev()
+        .find_map(|exp| {
+            let unit = 10f64.powi(exp);
+            if is_multiple(unit) {
+                Some(exp * 2 + 1)
+            } else if is_multiple(unit * 0.5) {
+                Some(exp * 2)
+            } else {
+                None
+            }
+        })
+        .unwrap_or(i32::MIN)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -513,6 +817,116 @@ mod tests {
         assert_eq!(price_precision(0.5), 1);
         assert_eq!(price_precision(0.05), 2);
         assert_eq!(price_precision(0.005), 3);
+        assert_eq!(price_precision(0.25), 2);
+        assert_eq!(price_precision(0.0003), 4);
+        assert_eq!(price_precision(1e-9), 9);
+        assert_eq!(price_precision(1e9), 0);
+    }
+
+    #[test]
+    fn test_tick_size_snaps_ticks_and_precision() {
+        assert_eq!(tick_precision(0.25), 2);
+        assert_eq!(tick_precision(0.5), 1);
+        assert_eq!(tick_precision(0.0001), 4);
+        assert_eq!(tick_precision(5.0), 0);
+
+        let scale = PriceScale::new(4500.1, 4503.9).with_tick_size(Some(0.25));
+        let ticks = scale.generate_ticks(570.0);
+        assert!(ticks.len() > 3);
+        for tick in &ticks {
+            assert_eq!((tick / 0.25).fract(), 0.0);
+            assert_eq!(
+                scale
+                    .format_price(*tick, 570.0)
+                    .split('.')
+                    .nth(1)
+                    .unwrap()
+                    .len(),
+                2
+            );
+        }
+        // Prices between ticks (e.g. under the crosshair) keep tick precision
+        assert_eq!(scale.format_price(4501.1, 570.0), "4501.10");
+        assert_eq!(
+            PriceScale::new(4500.1, 4503.9).format_price(4501.1, 570.0),
+            "4501.1"
+        );
+    }
+
+    #[test]
+    fn test_ticks_hold_invariants_over_random_ranges() {
+        // xorshift64* so failures reproduce
+        let mut state = 0x9E37_79B9_7F4A_7C15_u64;
+        let mut unit = move || {
+            state ^= state >> 12;
+            state ^= state << 25;
+            state ^= state >> 27;
+            (state.wrapping_mul(0x2545_F491_4F6C_DD1D) >> 11) as f64 / (1u64 << 53) as f64
+        };
+
+        for _ in 0..5000 {
+            let magnitude = 10f64.powf(unit() * 18.0 - 9.0);
+            let sign = if unit() < 0.2 { -1.0 } else { 1.0 };
+            let range = 10f64.powf(unit() * 18.0 - 9.0);
+            let height = 40.0 + unit() * 800.0;
+            let low = sign * magnitude;
+            let scale = PriceScale::new(low, low + range);
+            let (lo, hi) = scale.tick_bounds().unwrap();
+
+            let ticks = scale.generate_ticks(height);
+            let case = format!("{low:e}..+{range:e} at {height:.0}px: {ticks:?}");
+            assert!((2..=MAX_PRICE_TICKS).contains(&ticks.len()), "{case}");
+            assert!(ticks.windows(2).all(|w| w[0] < w[1]), "{case}");
+            assert!(ticks.iter().all(|t| (lo..=hi).contains(t)), "{case}");
+            let labels: Vec<String> = ticks
+                .iter()
+                .map(|&t| scale.format_price(t, height))
+                .collect();
+            assert!(labels.windows(2).all(|w| w[0] != w[1]), "{case} {labels:?}");
+        }
+    }
+
+    #[test]
+    fn test_degenerate_ranges_expand_around_value() {
+        // Flat series
+        let scale = PriceScale::new(1.0, 1.0);
+        assert_eq!(scale.tick_bounds(), Some((0.99, 1.01)));
+        let ticks = scale.generate_ticks(300.0);
+        assert!(ticks.len() >= 2 && ticks.contains(&1.0));
+        assert_eq!(PriceScale::new(0.0, 0.0).tick_bounds(), Some((-1.0, 1.0)));
+
+        // Stablecoin: 1.0000 ± 0.0001
+        let scale = PriceScale::new(0.9999, 1.0001);
+        let labels: Vec<String> = scale
+            .generate_ticks(300.0)
+            .iter()
+            .map(|&t| scale.format_price(t, 300.0))
+            .collect();
+        assert!(labels.len() >= 2);
+        assert!(
+            labels.iter().all(|l| l.len() == "1.00000".len()),
+            "{labels:?}"
+        );
+        assert!(labels.contains(&"1.00000".to_string()));
+
+        // Unresolvable at this magnitude
+        let scale = PriceScale::new(1e9, 1e9 + 1e-7);
+        assert!(scale.generate_ticks(300.0).len() >= 2);
+
+        // Tick size coarser than the range falls back to the range ends
+        let scale = PriceScale::new(100.1, 100.2).with_tick_size(Some(1.0));
+        assert_eq!(scale.generate_ticks(300.0), vec![100.1, 100.2]);
+
+        assert!(
+            PriceScale::new(f64::NAN, 1.0)
+                .generate_ticks(300.0)
+                .is_empty()
+        );
+        assert!(
+            !PriceScale::new(0.0, 100.0)
+                .generate_ticks(f64::NAN)
+                .is_empty()
+        );
     }
 
     #[test]
@@ -520,6 +934,34 @@ mod tests {
         assert_eq!(format_price(123.456, 1.0), "123");
         assert_eq!(format_price(123.456, 0.1), "123.5");
         assert_eq!(format_price(123.456, 0.01), "123.46");
+        assert_eq!(format_price(-1.25, 0.01), "-1.25");
+        assert_eq!(format_price(-0.004, 0.01), "0.00");
+        assert_eq!(format_price(-0.0, 1.0), "0");
+    }
+
+    #[test]
+    fn test_negative_ranges() {
+        let below = PriceScale::new(-5.0, -1.0);
+        let ticks = below.generate_ticks(300.0);
+        assert!(ticks.iter().all(|t| (-5.0..=-1.0).contains(t)));
+        assert_eq!(below.format_price(-4.5, 300.0), "-4.5");
+
+        let across = PriceScale::new(-2.0, 3.0);
+        let ticks = across.generate_ticks(300.0);
+        assert!(ticks.contains(&0.0));
+        let labels: Vec<String> = ticks
+            .iter()
+            .map(|&t| across.format_price(t, 300.0))
+            .collect();
+        assert!(labels.contains(&"0.0".to_string()) && labels.contains(&"-1.5".to_string()));
+
+        // A log scale cannot show zero or below, so it falls back to linear
+        let mut log = across.clone();
+        log.set_mode(PriceScaleMode::Logarithmic);
+        assert_eq!(log.effective_mode(), PriceScaleMode::Normal);
+        assert_eq!(log.price_to_y(0.5, 100.0), 50.0);
+        assert_eq!(log.y_to_price(50.0, 100.0), 0.5);
+        assert_eq!(log.generate_ticks_for_mode(300.0), ticks);
     }
 
     #[test]
@@ -530,6 +972,27 @@ mod tests {
         assert!(step > 5.0 && step < 20.0);
     }
 
+    #[test]
+    fn test_drag_scale_and_pan() {
+        let scale = PriceScale::new(100.0, 200.0);
+
+        // Dragging down from the middle expands evenly around 150
+        let (min, max) = scale.drag_scale(150.0, 60.0, 300.0);
+        assert!(min < 100.0 && max > 200.0);
+        assert!(((150.0 - min) - (max - 150.0)).abs() < 1e-9);
+        assert!(((max - min) - 100.0 * 0.2_f64.exp()).abs() < 1e-9);
+
+        // Dragging up compresses, however far
+        let (min, max) = scale.drag_scale(150.0, -1e9, 300.0);
+        assert!(min < max && min > 100.0 && max < 200.0);
+
+        // 30px of a 300px pane is a tenth of the 100 range
+        let (min, max) = scale.pan(30.0, 300.0);
+        assert!((min - 110.0).abs() < 1e-9);
+        assert!((max - 210.0).abs() < 1e-9);
+        assert_eq!(scale.pan(5.0, 0.0), (100.0, 200.0));
+    }
+
     #[test]
     fn test_generate_ticks() {
         let scale = PriceScale::new(0.0, 100.0);
@@ -541,4 +1004,38 @@ mod tests {
             assert!(*tick <= scale.price_max);
         }
     }
+
+    #[test]
+    fn test_resolve_label_collisions_thins_dense_ticks() {
+        // 1.0 apart in price, 4px apart on screen
+        let ticks: Vec<(f64, f64)> = (1..=20)
+            .map(|i| (i as f64, 100.0 - i as f64 * 4.0))
+            .collect();
+        let labels = resolve_label_collisions(&ticks, 10.0, (-10.0, 110.0), &[]);
+
+        assert!(labels.len() > 1);
+        for w in labels.windows(2) {
+            assert!((w[1].1 - w[0].1).abs() >= 10.0);
+        }
+        // The roundest price survives thinning
+        assert!(labels.iter().any(|&(p, _)| p == 10.0));
+    }
+
+    #[test]
+    fn test_resolve_label_collisions_edges_and_tags() {
+        let ticks = [(1.0, 2.0), (2.0, 30.0), (3.0, 60.0), (4.0, 98.0)];
+        let labels = resolve_label_collisions(&ticks, 10.0, (0.0, 100.0), &[]);
+        assert_eq!(labels, vec![(2.0, 30.0), (3.0, 60.0)]);
+
+        let labels = resolve_label_collisions(&ticks, 10.0, (0.0, 100.0), &[55.0]);
+        assert_eq!(labels, vec![(2.0, 30.0)]);
+    }
+
+    #[test]
+    fn test_price_roundness_order() {
+        let order = [100.0, 50.0, 10.0, 5.0, 1.0, 0.5];
+        for w in order.windows(2) {
+            assert!(price_roundness(w[0]) > price_roundness(w[1]));
+        }
+    }
 }
@@ -17,6 +17,7 @@
 //! ```
 
 use crate::Bar;
+use crate::core::compute::median_bar_interval;
 
 // =============================================================================
 // Time Constants
@@ -29,6 +30,11 @@ pub const HOUR: i64 = 3600;
 /// Seconds in a day
 pub const DAY: i64 = 86400;
 
+/// Short month names for time labels
+const MONTH_NAMES: [&str; 12] = [
+    "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+];
+
 // =============================================================================
 // Tick Mark Weight
 // =============================================================================
@@ -36,7 +42,7 @@ pub const DAY: i64 = 86400;
 /// Hierarchical weights for time tick marks
 ///
 /// Higher weight = more important = larger font/brighter color.
-/// Year=70, Month=60, Day=50, Week=40, Hour=30, etc.
+/// Year=70, Quarter=65, Month=60, Day=50, Week=40, Hour=30, etc.
 #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug, Default)]
 #[repr(u8)]
 pub enum TickMarkWeight {
@@ -59,33 +65,36 @@ pub enum TickMarkWeight {
     Day = 50,
     /// Month boundaries
     Month = 60,
+    /// Quarter boundaries (January, April, July, October)
+    Quarter = 65,
     /// Year boundaries
     Year = 70,
 }
 
 impl TickMarkWeight {
     /// Calculate weight based on timestamp boundary
+    ///
+    /// Calendar boundaries are UTC: a bar starts a month when its month
+    /// differs from the previous bar's, a week when it falls in a later
+    /// Monday-to-Sunday week.
     pub fn from_timestamp(ts: i64, prev_ts: Option<i64>) -> Self {
         let prev = prev_ts.unwrap_or(0);
+        let (days, prev_days) = (ts.div_euclid(DAY), prev.div_euclid(DAY));
+        let (year, month, _) = civil_from_days(days);
+        let (prev_year, prev_month, _) = civil_from_days(prev_days);
 
-        // Year boundary
-        let year = ts / (365 * DAY);
-        let prev_year = prev / (365 * DAY);
         if year != prev_year {
             return TickMarkWeight::Year;
         }
-
-        // Month boundary
-        let month = ts / (30 * DAY);
-        let prev_month = prev / (30 * DAY);
+        if (month - 1) / 3 != (prev_month - 1) / 3 {
+            return TickMarkWeight::Quarter;
+        }
         if month != prev_month {
             return TickMarkWeight::Month;
         }
 
-        // Week boundary
-        let week = ts / (7 * DAY);
-        let prev_week = prev / (7 * DAY);
-        if week != prev_week {
+        // 1970-01-01 was a Thursday; shift so weeks start on Monday
+        if (days + 3).div_euclid(7) != (prev_days + 3).div_euclid(7) {
             return TickMarkWeight::Week;
         }
 
@@ -134,9 +143,27 @@ impl TickMarkWeight {
         TickMarkWeight::Second
     }
 
-    /// Check if major weight (Year/Month)
+    /// Lowest weight that earns a time-axis label for bars `interval`
+    /// seconds apart
+    ///
+    /// Intraday bars label hours, daily and weekly bars months, and monthly
+    /// bars quarters. An unknown interval (0) counts as intraday.
+    pub fn label_threshold(interval: i64) -> Self {
+        if interval < DAY {
+            TickMarkWeight::Hour
+        } else if interval < 28 * DAY {
+            TickMarkWeight::Month
+        } else {
+            TickMarkWeight::Quarter
+        }
+    }
+
+    /// Check if major weight (Year/Quarter/Month)
     pub fn is_major(&self) -> bool {
-        matches!(self, TickMarkWeight::Year | TickMarkWeight::Month)
+        matches!(
+            self,
+            TickMarkWeight::Year | TickMarkWeight::Quarter | TickMarkWeight::Month
+        )
     }
 
     /// Check if medium weight (Day/Week)
@@ -185,8 +212,18 @@ pub struct TimeScale {
 
     /// Total number of bars in data
     pub bar_count: usize,
+
+    /// Empty space allowed past the last bar when scrolling, in bars
+    pub right_offset: f64,
+
+    /// Narrowest bar spacing zoom and fit may go to; bars that do not fit
+    /// at this spacing have to be scrolled to
+    pub min_bar_spacing: f64,
 }
 
+/// Widest bar spacing zoom may go to (unless the minimum is higher)
+const MAX_BAR_SPACING: f64 = 100.0;
+
 impl Default for TimeScale {
     fn default() -> Self {
         Self {
@@ -195,6 +232,8 @@ impl Default for TimeScale {
             bar_width_ratio: 0.8,
             chart_width: 800.0,
             bar_count: 0,
+            right_offset: 0.0,
+            min_bar_spacing: 2.0,
         }
     }
 }
@@ -224,7 +263,22 @@ impl TimeScale {
 
     /// Set bar spacing (pixels per bar)
     pub fn set_bar_spacing(&mut self, spacing: f64) {
-        self.bar_spacing = spacing.clamp(2.0, 100.0);
+        self.bar_spacing = self.clamp_spacing(spacing);
+    }
+
+    /// Set the narrowest bar spacing, widening the current one if needed
+    pub fn set_min_bar_spacing(&mut self, spacing: f64) {
+        if spacing.is_finite() && spacing > 0.0 {
+            self.min_bar_spacing = spacing;
+            self.bar_spacing = self.clamp_spacing(self.bar_spacing);
+        }
+    }
+
+    fn clamp_spacing(&self, spacing: f64) -> f64 {
+        spacing.clamp(
+            self.min_bar_spacing,
+            MAX_BAR_SPACING.max(self.min_bar_spacing),
+        )
     }
 
     /// Set bar width ratio
@@ -232,6 +286,11 @@ impl TimeScale {
         self.bar_width_ratio = ratio.clamp(0.1, 1.0);
     }
 
+    /// Set how many bars of empty space may follow the last bar
+    pub fn set_right_offset(&mut self, bars: f64) {
+        self.right_offset = bars.max(0.0);
+    }
+
     // =========================================================================
     // Visible Range
     // =========================================================================
@@ -269,6 +328,19 @@ impl TimeScale {
         )
     }
 
+    /// Scrollbar thumb as `(start, width)` fractions of all bars, or `None`
+    /// when every bar is in view
+    pub fn scroll_thumb(&self) -> Option<(f64, f64)> {
+        let total = self.bar_count as f64;
+        let visible = self.visible_bars() as f64;
+        if total == 0.0 || visible >= total {
+            return None;
+        }
+        let width = visible / total;
+        let start = (self.view_start / total).clamp(0.0, 1.0 - width);
+        Some((start, width))
+    }
+
     // =========================================================================
     // Coordinate Conversion
     // =========================================================================
@@ -332,21 +404,48 @@ impl TimeScale {
         self.view_start = 0.0;
     }
 
-    /// Fit all bars in view
+    /// Distance of the right edge from the last bar, in bars
+    ///
+    /// `0.0` means the latest bar sits at the right edge (as after
+    /// [`scroll_to_end`](Self::scroll_to_end)); positive values look back into
+    /// history and negative values show empty space past the last bar.
+    pub fn scroll_offset(&self) -> f64 {
+        self.bar_count as f64 - self.visible_bars() as f64 - self.view_start
+    }
+
+    /// Set the scroll offset, clamped between `-right_offset` and the first bar
+    ///
+    /// Fractional offsets are kept as-is for smooth animation.
+    pub fn set_scroll_offset(&mut self, offset: f64) {
+        let end_start = self.bar_count as f64 - self.visible_bars() as f64;
+        let max_offset = end_start.max(0.0);
+        let offset = offset.clamp(-self.right_offset, max_offset);
+        self.view_start = end_start - offset;
+    }
+
+    /// Scroll by a number of bars (positive = back into history)
+    pub fn scroll_by(&mut self, bars: f64) {
+        self.set_scroll_offset(self.scroll_offset() + bars);
+    }
+
+    /// Fit all bars in view, or as many as fit at the minimum spacing
     pub fn fit_all(&mut self, min_spacing: f64, max_spacing: f64) {
         if self.bar_count > 0 {
-            self.bar_spacing =
-                (self.chart_width / self.bar_count as f64).clamp(min_spacing, max_spacing);
+            let min_spacing = min_spacing.max(self.min_bar_spacing);
+            self.bar_spacing = (self.chart_width / self.bar_count as f64)
+                .clamp(min_spacing, max_spacing.max(min_spacing));
             self.view_start = 0.0;
         }
     }
 
     /// Set visible range by bar indices
+    ///
+    /// A range too wide for the minimum spacing is cut short at its end.
     pub fn set_visible_range(&mut self, start: f64, end: f64) {
         let count = end - start;
         if count > 0.0 {
             self.view_start = start;
-            self.bar_spacing = self.chart_width / count;
+            self.bar_spacing = (self.chart_width / count).max(self.min_bar_spacing);
         }
     }
 
@@ -357,7 +456,7 @@ impl TimeScale {
         }
 
         let anchor_bar = self.x_to_bar_f64(anchor_x);
-        self.bar_spacing = (self.bar_spacing * factor).clamp(2.0, 100.0);
+        self.bar_spacing = self.clamp_spacing(self.bar_spacing * factor);
 
         let anchor_ratio = anchor_x / self.chart_width;
         let visible = self.visible_bars() as f64;
@@ -384,6 +483,7 @@ impl TimeScale {
         let typical_label_width = 50.0;
         let min_spacing_bars = (typical_label_width / self.bar_spacing).ceil().max(1.0) as usize;
 
+        let threshold = TickMarkWeight::label_threshold(median_bar_interval(bars).unwrap_or(0));
         let mut prev_ts: Option<i64> = None;
         let mut candidates: Vec<(usize, f64, TickMarkWeight, i64)> = Vec::new();
 
@@ -401,7 +501,7 @@ impl TimeScale {
             }
 
             let weight = TickMarkWeight::from_timestamp(ts, prev_ts);
-            if weight >= TickMarkWeight::Hour {
+            if weight >= threshold {
                 candidates.push((i, x, weight, ts));
             }
             prev_ts = Some(ts);
@@ -465,45 +565,129 @@ impl TimeScale {
 // =============================================================================
 
 /// Format time label based on weight
+///
+/// Years as `2025`, quarters as `Q3 '24`, months as `Sep`, weeks and days
+/// as `5 Sep` and anything finer as `HH:MM` (UTC).
 pub fn format_time_by_weight(ts: i64, weight: TickMarkWeight) -> String {
-    let total_days = ts / DAY;
-    let year = 1970 + (total_days / 365) as i32;
-    let day_of_year = total_days % 365;
-    let month = (day_of_year / 30) as i32 + 1;
-    let day = (day_of_year % 30) as i32 + 1;
-    let hour = ((ts % DAY) / HOUR) as i32;
-    let minute = ((ts % HOUR) / MINUTE) as i32;
+    let (year, month, day) = civil_from_days(ts.div_euclid(DAY));
+    let secs = ts.rem_euclid(DAY);
+    let month_name = MONTH_NAMES[(month - 1) as usize];
 
     match weight {
         TickMarkWeight::Year => format!("{}", year),
-        TickMarkWeight::Month => {
-            let names = [
-                "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
-            ];
-            names[((month - 1) as usize).min(11)].to_string()
-        }
-        TickMarkWeight::Week | TickMarkWeight::Day => {
-            let names = [
-                "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
-            ];
-            format!("{} {}", day, names[((month - 1) as usize).min(11)])
+        TickMarkWeight::Quarter => {
+            format!("Q{} '{:02}", (month - 1) / 3 + 1, year.rem_euclid(100))
         }
-        _ => format!("{:02}:{:02}", hour, minute),
+        TickMarkWeight::Month => month_name.to_string(),
+        TickMarkWeight::Week | TickMarkWeight::Day => format!("{} {}", day, month_name),
+        _ => format!("{:02}:{:02}", secs / HOUR, (secs % HOUR) / MINUTE),
     }
 }
 
-/// Format full timestamp for display
+/// Format full timestamp for display (`dd.mm HH:MM`, UTC)
 pub fn format_time_full(ts: i64) -> String {
-    let total_days = ts / DAY;
-    let day_of_year = total_days % 365;
-    let month = (day_of_year / 30) as i32 + 1;
-    let day = (day_of_year % 30) as i32 + 1;
-    let hour = ((ts % DAY) / HOUR) as i32;
-    let minute = ((ts % HOUR) / MINUTE) as i32;
+    let (_, month, day) = civil_from_days(ts.div_euclid(DAY));
+    let secs = ts.rem_euclid(DAY);
+    let hour = secs / HOUR;
+    let minute = (secs % HOUR) / MINUTE;
 
     format!("{:02}.{:02} {:02}:{:02}", day, month, hour, minute)
 }
 
+/// Format a timestamp with a strftime-like pattern
+///
+/// Supported specifiers: `%Y` (2024), `%y` (24), `%m` (01-12), `%d` (01-31),
+/// `%b` (Jan), `%H` (00-23), `%M` (00-59), `%S` (00-59) and `%%`.
+/// Unknown specifiers are dropped so a bad pattern degrades gracefully
+/// instead of leaking raw `%` codes into labels.
+///
+/// # Examples
+///
+/// ```
+/// use zengeld_canvas::coords::format_time_pattern;
+///
+/// // 2024-01-01 14:05:09 UTC
+/// assert_eq!(format_time_pattern(1_704_117_909, "%H:%M"), "14:05");
+/// assert_eq!(format_time_pattern(1_704_117_909, "%d %b %Y"), "01 Jan 2024");
+/// ```
+pub fn format_time_pattern(ts: i64, pattern: &str) -> String {
+    let (year, month, day) = civil_from_days(ts.div_euclid(DAY));
+    let secs = ts.rem_euclid(DAY);
+    let hour = secs / HOUR;
+    let minute = (secs % HOUR) / MINUTE;
+    let second = secs % MINUTE;
+
+    let mut out = String::with_capacity(pattern.len() + 8);
+    let mut chars = pattern.chars();
+    while let Some(c) = chars.next() {
+        if c != '%' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('Y') => out.push_str(&year.to_string()),
+            Some('y') => out.push_str(&format!("{:02}", year.rem_euclid(100))),
+            Some('m') => out.push_str(&format!("{:02}", month)),
+            Some('d') => out.push_str(&format!("{:02}", day)),
+            Some('b') => out.push_str(MONTH_NAMES[(month - 1) as usize]),
+            Some('H') => out.push_str(&format!("{:02}", hour)),
+            Some('M') => out.push_str(&format!("{:02}", minute)),
+            Some('S') => out.push_str(&format!("{:02}", second)),
+            Some('%') => out.push('%'),
+            _ => {}
+        }
+    }
+    out
+}
+
+/// Convert days since the unix epoch to a (year, month, day) civil date
+pub(crate) fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    // Howard Hinnant's days-to-civil algorithm (proleptic Gregorian)
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+/// Convert a (year, month, day) civil date to days since the unix epoch
+pub(crate) fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    // Inverse of civil_from_days
+    let year = year - i64::from(month <= 2);
+    let era = year.div_euclid(400);
+    let yoe = year.rem_euclid(400);
+    let mp = i64::from((month + 9) % 12);
+    let doy = (153 * mp + 2) / 5 + i64::from(day) - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+/// Format time remaining until a bar closes
+///
+/// Intraday timeframes show `mm:ss`, or `h:mm:ss` once an hour or more is
+/// left. Daily and longer timeframes switch to `Nd HHh` while at least a
+/// day remains. Negative remaining time is clamped to zero.
+pub fn format_countdown(remaining_secs: i64, timeframe_secs: i64) -> String {
+    let remaining = remaining_secs.max(0);
+    let days = remaining / DAY;
+    let hours = (remaining % DAY) / HOUR;
+    let minutes = (remaining % HOUR) / MINUTE;
+    let seconds = remaining % MINUTE;
+
+    if timeframe_secs >= DAY && days > 0 {
+        format!("{}d {:02}h", days, hours)
+    } else if remaining >= HOUR {
+        format!("{}:{:02}:{:02}", remaining / HOUR, minutes, seconds)
+    } else {
+        format!("{:02}:{:02}", minutes, seconds)
+    }
+}
+
 // =============================================================================
 // Tests
 // =============================================================================
@@ -563,11 +747,82 @@ mod tests {
 
     #[test]
     fn test_tick_weight_ordering() {
-        assert!(TickMarkWeight::Year > TickMarkWeight::Month);
+        assert!(TickMarkWeight::Year > TickMarkWeight::Quarter);
+        assert!(TickMarkWeight::Quarter > TickMarkWeight::Month);
         assert!(TickMarkWeight::Month > TickMarkWeight::Day);
         assert!(TickMarkWeight::Day > TickMarkWeight::Hour);
     }
 
+    #[test]
+    fn test_calendar_tick_weights_and_labels() {
+        let day = |y, m, d| days_from_civil(y, m, d) * DAY;
+        let labeled = |stamps: &[i64]| -> Vec<(TickMarkWeight, String)> {
+            stamps
+                .windows(2)
+                .map(|w| {
+                    let weight = TickMarkWeight::from_timestamp(w[1], Some(w[0]));
+                    (weight, format_time_by_weight(w[1], weight))
+                })
+                .collect()
+        };
+        let label = |weight, text: &str| (weight, text.to_string());
+
+        // Sunday, Monday, Tuesday, New Year
+        let daily = [
+            day(2024, 12, 28),
+            day(2024, 12, 29),
+            day(2024, 12, 30),
+            day(2024, 12, 31),
+            day(2025, 1, 1),
+        ];
+        assert_eq!(
+            labeled(&daily),
+            [
+                label(TickMarkWeight::Day, "29 Dec"),
+                label(TickMarkWeight::Week, "30 Dec"),
+                label(TickMarkWeight::Day, "31 Dec"),
+                label(TickMarkWeight::Year, "2025"),
+            ]
+        );
+
+        let monthly = [
+            day(2024, 8, 1),
+            day(2024, 9, 1),
+            day(2024, 10, 1),
+            day(2024, 11, 1),
+        ];
+        assert_eq!(
+            labeled(&monthly),
+            [
+                label(TickMarkWeight::Month, "Sep"),
+                label(TickMarkWeight::Quarter, "Q4 '24"),
+                label(TickMarkWeight::Month, "Nov"),
+            ]
+        );
+
+        // Intraday bars keep hour labels
+        let hourly = [day(2024, 3, 5) + 9 * HOUR, day(2024, 3, 5) + 10 * HOUR];
+        assert_eq!(labeled(&hourly), [label(TickMarkWeight::Hour, "10:00")]);
+    }
+
+    #[test]
+    fn test_label_threshold_follows_bar_interval() {
+        assert_eq!(TickMarkWeight::label_threshold(0), TickMarkWeight::Hour);
+        assert_eq!(
+            TickMarkWeight::label_threshold(5 * MINUTE),
+            TickMarkWeight::Hour
+        );
+        assert_eq!(TickMarkWeight::label_threshold(DAY), TickMarkWeight::Month);
+        assert_eq!(
+            TickMarkWeight::label_threshold(7 * DAY),
+            TickMarkWeight::Month
+        );
+        assert_eq!(
+            TickMarkWeight::label_threshold(31 * DAY),
+            TickMarkWeight::Quarter
+        );
+    }
+
     #[test]
     fn test_zoom() {
         let mut ts = TimeScale::new(800.0);
@@ -588,4 +843,81 @@ mod tests {
         assert_eq!(ts.view_start, 0.0);
         assert_eq!(ts.bar_spacing, 8.0);
     }
+
+    #[test]
+    fn test_format_countdown() {
+        assert_eq!(format_countdown(151, MINUTE * 5), "02:31");
+        assert_eq!(format_countdown(3 * HOUR + 61, 4 * HOUR), "3:01:01");
+        assert_eq!(format_countdown(2 * DAY + 4 * HOUR, 7 * DAY), "2d 04h");
+        assert_eq!(format_countdown(5 * HOUR, DAY), "5:00:00");
+        assert_eq!(format_countdown(-10, MINUTE), "00:00");
+    }
+
+    #[test]
+    fn test_format_time_pattern() {
+        // 2024-02-29 23:59:30 UTC
+        let ts = 1_709_251_170;
+        assert_eq!(
+            format_time_pattern(ts, "%Y-%m-%d %H:%M:%S"),
+            "2024-02-29 23:59:30"
+        );
+        assert_eq!(format_time_pattern(ts, "%y %b"), "24 Feb");
+        assert_eq!(format_time_pattern(0, "%Y-%m-%d"), "1970-01-01");
+        // Unknown specifiers are dropped, literal percent survives
+        assert_eq!(format_time_pattern(ts, "%H%Q:%M 100%%"), "23:59 100%");
+    }
+
+    #[test]
+    fn test_scroll_offset_round_trip_and_clamp() {
+        let mut ts = TimeScale {
+            bar_spacing: 10.0,
+            chart_width: 100.0,
+            bar_count: 50,
+            ..Default::default()
+        };
+        ts.scroll_to_end();
+        assert_eq!(ts.scroll_offset(), 0.0);
+
+        ts.set_scroll_offset(12.5);
+        ts.scroll_by(5.0);
+        ts.scroll_by(-5.0);
+        assert_eq!(ts.scroll_offset(), 12.5);
+
+        // Can't scroll past the first bar...
+        ts.scroll_by(1000.0);
+        assert_eq!(ts.scroll_offset(), 40.0);
+        assert_eq!(ts.view_start, 0.0);
+
+        // ...or further right than the right offset allows
+        ts.scroll_by(-1000.0);
+        assert_eq!(ts.scroll_offset(), 0.0);
+        ts.set_right_offset(3.0);
+        ts.scroll_by(-1000.0);
+        assert_eq!(ts.scroll_offset(), -3.0);
+    }
+
+    #[test]
+    fn test_min_bar_spacing_requires_scroll() {
+        let mut ts = TimeScale::new(800.0);
+        ts.set_bar_count(1000);
+        ts.set_min_bar_spacing(4.0);
+
+        // 1000 bars would need 0.8px each; only 200 fit at the floor
+        ts.fit_all(0.5, 50.0);
+        assert_eq!(ts.bar_spacing, 4.0);
+        assert_eq!(ts.visible_bars(), 200);
+        ts.set_visible_range(0.0, 1000.0);
+        assert_eq!(ts.visible_bars(), 200);
+        ts.zoom(0.1, 400.0);
+        assert_eq!(ts.bar_spacing, 4.0);
+
+        ts.scroll_to_end();
+        assert_eq!(ts.scroll_thumb(), Some((0.8, 0.2)));
+        ts.scroll_to_start();
+        assert_eq!(ts.scroll_thumb(), Some((0.0, 0.2)));
+
+        // Everything fits again at a wider chart
+        ts.set_chart_width(4000.0);
+        assert_eq!(ts.scroll_thumb(), None);
+    }
 }
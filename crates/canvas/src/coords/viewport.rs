@@ -195,6 +195,11 @@ impl Viewport {
         self.time_scale.set_bar_spacing(spacing);
     }
 
+    /// Set the narrowest bar spacing; bars that do not fit at it need scrolling
+    pub fn set_min_bar_spacing(&mut self, spacing: f64) {
+        self.time_scale.set_min_bar_spacing(spacing);
+    }
+
     // =========================================================================
     // Y-axis: Price ↔ Pixel (delegated to PriceScale)
     // =========================================================================
@@ -242,6 +247,12 @@ impl Viewport {
         self.time_scale.set_visible_range(start, end);
     }
 
+    /// Scrollbar thumb as `(start, width)` fractions of all bars, `None`
+    /// when every bar is in view
+    pub fn scroll_thumb(&self) -> Option<(f64, f64)> {
+        self.time_scale.scroll_thumb()
+    }
+
     // =========================================================================
     // Navigation
     // =========================================================================
@@ -261,6 +272,26 @@ impl Viewport {
         self.time_scale.scroll_to_start();
     }
 
+    /// Scroll by bars (positive = back into history), clamped to the data
+    pub fn scroll_by(&mut self, bars: f64) {
+        self.time_scale.scroll_by(bars);
+    }
+
+    /// Bars between the right edge and the latest bar (0 = at the end)
+    pub fn scroll_offset(&self) -> f64 {
+        self.time_scale.scroll_offset()
+    }
+
+    /// Set the scroll offset in bars; fractional values are allowed
+    pub fn set_scroll_offset(&mut self, offset: f64) {
+        self.time_scale.set_scroll_offset(offset);
+    }
+
+    /// Set how many bars of empty space may follow the last bar
+    pub fn set_right_offset(&mut self, bars: f64) {
+        self.time_scale.set_right_offset(bars);
+    }
+
     /// Fit all bars
     pub fn fit_all(&mut self, min_spacing: f64, max_spacing: f64) {
         self.time_scale.fit_all(min_spacing, max_spacing);
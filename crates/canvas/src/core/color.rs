@@ -2,148 +2,538 @@
 //!
 //! Provides CSS color parsing that returns raw RGBA tuples,
 //! allowing platform-specific code to convert to their native color types.
+//! The render engine's `Color::from_css` is built on the same parser.
+
+use std::fmt;
+
+/// Why a CSS color string failed to parse
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CssColorError {
+    /// Empty or whitespace-only input
+    Empty,
+
+    /// `#` not followed by 3, 4, 6 or 8 hex digits
+    InvalidHex(String),
+
+    /// `rgb()`, `rgba()`, `hsl()` or `hsla()` with bad arguments
+    InvalidFunction { input: String, reason: String },
+
+    /// A color function other than `rgb`/`rgba`/`hsl`/`hsla`
+    UnsupportedFunction(String),
+
+    /// Not a CSS named color
+    UnknownName(String),
+}
+
+impl fmt::Display for CssColorError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CssColorError::Empty => write!(f, "Empty color"),
+            CssColorError::InvalidHex(input) => write!(
+                f,
+                "Invalid hex color '{}': expected 3, 4, 6 or 8 hex digits",
+                input
+            ),
+            CssColorError::InvalidFunction { input, reason } => {
+                write!(f, "Invalid color '{}': {}", input, reason)
+            }
+            CssColorError::UnsupportedFunction(name) => {
+                write!(f, "Unsupported color function: {}()", name)
+            }
+            CssColorError::UnknownName(name) => write!(f, "Unknown color name: {}", name),
+        }
+    }
+}
+
+impl std::error::Error for CssColorError {}
 
 /// Parse CSS color string to RGBA tuple
 ///
 /// Supports:
-/// - Hex formats: `#RGB`, `#RRGGBB`, `#RRGGBBAA`
-/// - RGB function: `rgb(r, g, b)`
-/// - RGBA function: `rgba(r, g, b, a)`
-/// - Keyword: `transparent`
+/// - Hex formats: `#RGB`, `#RGBA`, `#RRGGBB`, `#RRGGBBAA`
+/// - `rgb()`/`rgba()` with comma or space separated channels, as numbers
+///   (0-255) or percentages, and an optional alpha (`rgb(38 166 154 / 50%)`)
+/// - `hsl()`/`hsla()` with the hue in `deg` (default), `rad`, `grad` or
+///   `turn`
+/// - The standard named colors and `transparent`
+///
+/// Function and color names are case-insensitive, out-of-range channels
+/// are clamped and fractional results are rounded.
 ///
-/// Returns (r, g, b, a) where each component is 0-255.
-/// Returns (255, 255, 255, 255) (white) for unrecognized formats.
+/// Returns (r, g, b, a) where each component is 0-255, or why the string
+/// is not a color; callers choose their own fallback.
 ///
 /// # Examples
 ///
 /// ```
 /// use zengeld_canvas::parse_css_color;
 ///
-/// assert_eq!(parse_css_color("#FF0000"), (255, 0, 0, 255));
-/// assert_eq!(parse_css_color("#00FF0080"), (0, 255, 0, 128));
-/// assert_eq!(parse_css_color("rgb(100, 150, 200)"), (100, 150, 200, 255));
-/// assert_eq!(parse_css_color("rgba(100, 150, 200, 0.5)"), (100, 150, 200, 127));
-/// assert_eq!(parse_css_color("transparent"), (0, 0, 0, 0));
+/// assert_eq!(parse_css_color("#FF0000"), Ok((255, 0, 0, 255)));
+/// assert_eq!(parse_css_color("#00FF0080"), Ok((0, 255, 0, 128)));
+/// assert_eq!(parse_css_color("rgb(100, 150, 200)"), Ok((100, 150, 200, 255)));
+/// assert_eq!(parse_css_color("rgba(38,166,154,0.5)"), Ok((38, 166, 154, 128)));
+/// assert_eq!(parse_css_color("hsl(120deg 100% 25%)"), Ok((0, 128, 0, 255)));
+/// assert_eq!(parse_css_color("RebeccaPurple"), Ok((102, 51, 153, 255)));
+/// assert_eq!(parse_css_color("transparent"), Ok((0, 0, 0, 0)));
+/// assert!(parse_css_color("rgb(1, 2)").is_err());
 /// ```
-pub fn parse_css_color(color: &str) -> (u8, u8, u8, u8) {
-    // Handle "transparent" keyword
-    if color == "transparent" {
-        return (0, 0, 0, 0);
-    }
-
-    if let Some(hex) = color.strip_prefix('#') {
-        match hex.len() {
-            // #RGB -> #RRGGBB
-            3 => {
-                let r = u8::from_str_radix(&hex[0..1], 16).unwrap_or(15);
-                let g = u8::from_str_radix(&hex[1..2], 16).unwrap_or(15);
-                let b = u8::from_str_radix(&hex[2..3], 16).unwrap_or(15);
-                // Expand: F -> FF (multiply by 17)
-                return (r * 17, g * 17, b * 17, 255);
-            }
-            // #RRGGBB
-            6 => {
-                let r = u8::from_str_radix(&hex[0..2], 16).unwrap_or(255);
-                let g = u8::from_str_radix(&hex[2..4], 16).unwrap_or(255);
-                let b = u8::from_str_radix(&hex[4..6], 16).unwrap_or(255);
-                return (r, g, b, 255);
-            }
-            // #RRGGBBAA
-            8 => {
-                let r = u8::from_str_radix(&hex[0..2], 16).unwrap_or(255);
-                let g = u8::from_str_radix(&hex[2..4], 16).unwrap_or(255);
-                let b = u8::from_str_radix(&hex[4..6], 16).unwrap_or(255);
-                let a = u8::from_str_radix(&hex[6..8], 16).unwrap_or(255);
-                return (r, g, b, a);
-            }
-            _ => {}
+pub fn parse_css_color(color: &str) -> Result<(u8, u8, u8, u8), CssColorError> {
+    let css = color.trim();
+    if css.is_empty() {
+        return Err(CssColorError::Empty);
+    }
+
+    if let Some(hex) = css.strip_prefix('#') {
+        return parse_hex(hex).ok_or_else(|| CssColorError::InvalidHex(css.to_string()));
+    }
+
+    if let Some((name, rest)) = css.split_once('(') {
+        let name = name.trim().to_ascii_lowercase();
+        let invalid = |reason: String| CssColorError::InvalidFunction {
+            input: css.to_string(),
+            reason,
+        };
+        let args = rest
+            .strip_suffix(')')
+            .ok_or_else(|| invalid("missing closing parenthesis".into()))?;
+        return match name.as_str() {
+            "rgb" | "rgba" => rgb_function(args).map_err(invalid),
+            "hsl" | "hsla" => hsl_function(args).map_err(invalid),
+            _ => Err(CssColorError::UnsupportedFunction(name)),
+        };
+    }
+
+    named_color(css).ok_or_else(|| CssColorError::UnknownName(css.to_string()))
+}
+
+/// Hex digits after the `#`: RGB, RGBA, RRGGBB or RRGGBBAA
+pub(crate) fn parse_hex(hex: &str) -> Option<(u8, u8, u8, u8)> {
+    if !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return None;
+    }
+    let digit = |i: usize| u8::from_str_radix(&hex[i..i + 1], 16).ok().map(|d| d * 17);
+    let pair = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).ok();
+    match hex.len() {
+        3 => Some((digit(0)?, digit(1)?, digit(2)?, 255)),
+        4 => Some((digit(0)?, digit(1)?, digit(2)?, digit(3)?)),
+        6 => Some((pair(0)?, pair(2)?, pair(4)?, 255)),
+        8 => Some((pair(0)?, pair(2)?, pair(4)?, pair(6)?)),
+        _ => None,
+    }
+}
+
+/// Split function arguments into three channels and an optional alpha
+///
+/// Accepts the legacy `a, b, c[, alpha]` and the modern `a b c[ / alpha]`
+/// forms, but not a mix of the two.
+fn split_args(args: &str) -> Result<([&str; 3], Option<&str>), String> {
+    let (channels, alpha): (Vec<&str>, Option<&str>) = if args.contains(',') {
+        if args.contains('/') {
+            return Err("mixes comma and slash separators".into());
         }
-    } else if color.starts_with("rgba(") && color.ends_with(')') {
-        let inner = &color[5..color.len() - 1];
-        let parts: Vec<&str> = inner.split(',').map(|s| s.trim()).collect();
-        if parts.len() == 4 {
-            let r = parts[0].parse::<u8>().unwrap_or(255);
-            let g = parts[1].parse::<u8>().unwrap_or(255);
-            let b = parts[2].parse::<u8>().unwrap_or(255);
-            let a = (parts[3].parse::<f32>().unwrap_or(1.0) * 255.0) as u8;
-            return (r, g, b, a);
+        let mut parts: Vec<&str> = args.split(',').map(str::trim).collect();
+        if parts.iter().any(|p| p.is_empty()) {
+            return Err("empty component".into());
         }
-    } else if color.starts_with("rgb(") && color.ends_with(')') {
-        let inner = &color[4..color.len() - 1];
-        let parts: Vec<&str> = inner.split(',').map(|s| s.trim()).collect();
-        if parts.len() == 3 {
-            let r = parts[0].parse::<u8>().unwrap_or(255);
-            let g = parts[1].parse::<u8>().unwrap_or(255);
-            let b = parts[2].parse::<u8>().unwrap_or(255);
-            return (r, g, b, 255);
+        let alpha = if parts.len() == 4 { parts.pop() } else { None };
+        (parts, alpha)
+    } else {
+        let (channels, alpha) = match args.split_once('/') {
+            Some((channels, alpha)) => (channels, Some(alpha.trim())),
+            None => (args, None),
+        };
+        if alpha.is_some_and(|a| a.is_empty() || a.contains(char::is_whitespace)) {
+            return Err("expected a single alpha value after '/'".into());
         }
+        (channels.split_whitespace().collect(), alpha)
+    };
+
+    let count = channels.len();
+    let channels: [&str; 3] = channels
+        .try_into()
+        .map_err(|_| format!("expected 3 color components, got {}", count))?;
+    Ok((channels, alpha))
+}
+
+/// A finite number
+fn number(s: &str) -> Result<f64, String> {
+    s.parse::<f64>()
+        .ok()
+        .filter(|v| v.is_finite())
+        .ok_or_else(|| format!("'{}' is not a number", s))
+}
+
+/// A number, or a percentage scaled so that 100% is `full`
+fn number_or_percent(s: &str, full: f64) -> Result<f64, String> {
+    match s.strip_suffix('%') {
+        Some(pct) => Ok(number(pct)? / 100.0 * full),
+        None => number(s),
     }
+}
 
-    // Default: white
-    (255, 255, 255, 255)
+/// Alpha as a 0-1 number or a percentage, clamped and scaled to 0-255
+fn alpha_channel(alpha: Option<&str>) -> Result<u8, String> {
+    match alpha {
+        Some(a) => Ok(to_channel(number_or_percent(a, 1.0)? * 255.0)),
+        None => Ok(255),
+    }
+}
+
+/// Round and clamp into a 0-255 channel
+fn to_channel(value: f64) -> u8 {
+    value.round().clamp(0.0, 255.0) as u8
+}
+
+fn rgb_function(args: &str) -> Result<(u8, u8, u8, u8), String> {
+    let ([r, g, b], alpha) = split_args(args)?;
+    let channel = |s: &str| number_or_percent(s, 255.0).map(to_channel);
+    Ok((channel(r)?, channel(g)?, channel(b)?, alpha_channel(alpha)?))
+}
+
+fn hsl_function(args: &str) -> Result<(u8, u8, u8, u8), String> {
+    let ([h, s, l], alpha) = split_args(args)?;
+
+    let hue = if let Some(deg) = h.strip_suffix("deg") {
+        number(deg)?
+    } else if let Some(grad) = h.strip_suffix("grad") {
+        number(grad)? * 0.9
+    } else if let Some(rad) = h.strip_suffix("rad") {
+        number(rad)?.to_degrees()
+    } else if let Some(turn) = h.strip_suffix("turn") {
+        number(turn)? * 360.0
+    } else {
+        number(h)?
+    };
+    // Saturation and lightness: percentages, or bare numbers on the same scale
+    let fraction =
+        |s: &str| number(s.strip_suffix('%').unwrap_or(s)).map(|v| (v / 100.0).clamp(0.0, 1.0));
+    let (s, l) = (fraction(s)?, fraction(l)?);
+
+    // CSS Color 4 hsl-to-rgb
+    let h = hue.rem_euclid(360.0);
+    let a = s * l.min(1.0 - l);
+    let f = |n: f64| {
+        let k = (n + h / 30.0) % 12.0;
+        l - a * (k - 3.0).min(9.0 - k).clamp(-1.0, 1.0)
+    };
+    Ok((
+        to_channel(f(0.0) * 255.0),
+        to_channel(f(8.0) * 255.0),
+        to_channel(f(4.0) * 255.0),
+        alpha_channel(alpha)?,
+    ))
 }
 
+/// `transparent` or one of the CSS named colors, ignoring case
+fn named_color(name: &str) -> Option<(u8, u8, u8, u8)> {
+    let name = name.to_ascii_lowercase();
+    if name == "transparent" {
+        return Some((0, 0, 0, 0));
+    }
+    let i = NAMED_COLORS
+        .binary_search_by(|(n, _)| n.cmp(&name.as_str()))
+        .ok()?;
+    let rgb = NAMED_COLORS[i].1;
+    Some(((rgb >> 16) as u8, (rgb >> 8) as u8, rgb as u8, 255))
+}
+
+/// CSS named colors as `0xRRGGBB`, sorted by name for binary search
+const NAMED_COLORS: [(&str, u32); 148] = [
+    ("aliceblue", 0xf0f8ff),
+    ("antiquewhite", 0xfaebd7),
+    ("aqua", 0x00ffff),
+    ("aquamarine", 0x7fffd4),
+    ("azure", 0xf0ffff),
+    ("beige", 0xf5f5dc),
+    ("bisque", 0xffe4c4),
+    ("black", 0x000000),
+    ("blanchedalmond", 0xffebcd),
+    ("blue", 0x0000ff),
+    ("blueviolet", 0x8a2be2),
+    ("brown", 0xa52a2a),
+    ("burlywood", 0xdeb887),
+    ("cadetblue", 0x5f9ea0),
+    ("chartreuse", 0x7fff00),
+    ("chocolate", 0xd2691e),
+    ("coral", 0xff7f50),
+    ("cornflowerblue", 0x6495ed),
+    ("cornsilk", 0xfff8dc),
+    ("crimson", 0xdc143c),
+    ("cyan", 0x00ffff),
+    ("darkblue", 0x00008b),
+    ("darkcyan", 0x008b8b),
+    ("darkgoldenrod", 0xb8860b),
+    ("darkgray", 0xa9a9a9),
+    ("darkgreen", 0x006400),
+    ("darkgrey", 0xa9a9a9),
+    ("darkkhaki", 0xbdb76b),
+    ("darkmagenta", 0x8b008b),
+    ("darkolivegreen", 0x556b2f),
+    ("darkorange", 0xff8c00),
+    ("darkorchid", 0x9932cc),
+    ("darkred", 0x8b0000),
+    ("darksalmon", 0xe9967a),
+    ("darkseagreen", 0x8fbc8f),
+    ("darkslateblue", 0x483d8b),
+    ("darkslategray", 0x2f4f4f),
+    ("darkslategrey", 0x2f4f4f),
+    ("darkturquoise", 0x00ced1),
+    ("darkviolet", 0x9400d3),
+    ("deeppink", 0xff1493),
+    ("deepskyblue", 0x00bfff),
+    ("dimgray", 0x696969),
+    ("dimgrey", 0x696969),
+    ("dodgerblue", 0x1e90ff),
+    ("firebrick", 0xb22222),
+    ("floralwhite", 0xfffaf0),
+    ("forestgreen", 0x228b22),
+    ("fuchsia", 0xff00ff),
+    ("gainsboro", 0xdcdcdc),
+    ("ghostwhite", 0xf8f8ff),
+    ("gold", 0xffd700),
+    ("goldenrod", 0xdaa520),
+    ("gray", 0x808080),
+    ("green", 0x008000),
+    ("greenyellow", 0xadff2f),
+    ("grey", 0x808080),
+    ("honeydew", 0xf0fff0),
+    ("hotpink", 0xff69b4),
+    ("indianred", 0xcd5c5c),
+    ("indigo", 0x4b0082),
+    ("ivory", 0xfffff0),
+    ("khaki", 0xf0e68c),
+    ("lavender", 0xe6e6fa),
+    ("lavenderblush", 0xfff0f5),
+    ("lawngreen", 0x7cfc00),
+    ("lemonchiffon", 0xfffacd),
+    ("lightblue", 0xadd8e6),
+    ("lightcoral", 0xf08080),
+    ("lightcyan", 0xe0ffff),
+    ("lightgoldenrodyellow", 0xfafad2),
+    ("lightgray", 0xd3d3d3),
+    ("lightgreen", 0x90ee90),
+    ("lightgrey", 0xd3d3d3),
+    ("lightpink", 0xffb6c1),
+    ("lightsalmon", 0xffa07a),
+    ("lightseagreen", 0x20b2aa),
+    ("lightskyblue", 0x87cefa),
+    ("lightslategray", 0x778899),
+    ("lightslategrey", 0x778899),
+    ("lightsteelblue", 0xb0c4de),
+    ("lightyellow", 0xffffe0),
+    ("lime", 0x00ff00),
+    ("limegreen", 0x32cd32),
+    ("linen", 0xfaf0e6),
+    ("magenta", 0xff00ff),
+    ("maroon", 0x800000),
+    ("mediumaquamarine", 0x66cdaa),
+    ("mediumblue", 0x0000cd),
+    ("mediumorchid", 0xba55d3),
+    ("mediumpurple", 0x9370db),
+    ("mediumseagreen", 0x3cb371),
+    ("mediumslateblue", 0x7b68ee),
+    ("mediumspringgreen", 0x00fa9a),
+    ("mediumturquoise", 0x48d1cc),
+    ("mediumvioletred", 0xc71585),
+    ("midnightblue", 0x191970),
+    ("mintcream", 0xf5fffa),
+    ("mistyrose", 0xffe4e1),
+    ("moccasin", 0xffe4b5),
+    ("navajowhite", 0xffdead),
+    ("navy", 0x000080),
+    ("oldlace", 0xfdf5e6),
+    ("olive", 0x808000),
+    ("olivedrab", 0x6b8e23),
+    ("orange", 0xffa500),
+    ("orangered", 0xff4500),
+    ("orchid", 0xda70d6),
+    ("palegoldenrod", 0xeee8aa),
+    ("palegreen", 0x98fb98),
+    ("paleturquoise", 0xafeeee),
+    ("palevioletred", 0xdb7093),
+    ("papayawhip", 0xffefd5),
+    ("peachpuff", 0xffdab9),
+    ("peru", 0xcd853f),
+    ("pink", 0xffc0cb),
+    ("plum", 0xdda0dd),
+    ("powderblue", 0xb0e0e6),
+    ("purple", 0x800080),
+    ("rebeccapurple", 0x663399),
+    ("red", 0xff0000),
+    ("rosybrown", 0xbc8f8f),
+    ("royalblue", 0x4169e1),
+    ("saddlebrown", 0x8b4513),
+    ("salmon", 0xfa8072),
+    ("sandybrown", 0xf4a460),
+    ("seagreen", 0x2e8b57),
+    ("seashell", 0xfff5ee),
+    ("sienna", 0xa0522d),
+    ("silver", 0xc0c0c0),
+    ("skyblue", 0x87ceeb),
+    ("slateblue", 0x6a5acd),
+    ("slategray", 0x708090),
+    ("slategrey", 0x708090),
+    ("snow", 0xfffafa),
+    ("springgreen", 0x00ff7f),
+    ("steelblue", 0x4682b4),
+    ("tan", 0xd2b48c),
+    ("teal", 0x008080),
+    ("thistle", 0xd8bfd8),
+    ("tomato", 0xff6347),
+    ("turquoise", 0x40e0d0),
+    ("violet", 0xee82ee),
+    ("wheat", 0xf5deb3),
+    ("white", 0xffffff),
+    ("whitesmoke", 0xf5f5f5),
+    ("yellow", 0xffff00),
+    ("yellowgreen", 0x9acd32),
+];
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use proptest::prelude::*;
+
+    fn ok(color: &str) -> (u8, u8, u8, u8) {
+        parse_css_color(color).unwrap_or_else(|e| panic!("{}: {}", color, e))
+    }
 
     #[test]
     fn test_hex_6() {
-        assert_eq!(parse_css_color("#FF0000"), (255, 0, 0, 255));
-        assert_eq!(parse_css_color("#00FF00"), (0, 255, 0, 255));
-        assert_eq!(parse_css_color("#0000FF"), (0, 0, 255, 255));
-        assert_eq!(parse_css_color("#FFFFFF"), (255, 255, 255, 255));
-        assert_eq!(parse_css_color("#000000"), (0, 0, 0, 255));
+        assert_eq!(ok("#FF0000"), (255, 0, 0, 255));
+        assert_eq!(ok("#00FF00"), (0, 255, 0, 255));
+        assert_eq!(ok("#0000FF"), (0, 0, 255, 255));
+        assert_eq!(ok("#FFFFFF"), (255, 255, 255, 255));
+        assert_eq!(ok("#000000"), (0, 0, 0, 255));
     }
 
     #[test]
     fn test_hex_8() {
-        assert_eq!(parse_css_color("#FF000080"), (255, 0, 0, 128));
-        assert_eq!(parse_css_color("#00FF00FF"), (0, 255, 0, 255));
-        assert_eq!(parse_css_color("#0000FF00"), (0, 0, 255, 0));
+        assert_eq!(ok("#FF000080"), (255, 0, 0, 128));
+        assert_eq!(ok("#00FF00FF"), (0, 255, 0, 255));
+        assert_eq!(ok("#0000FF00"), (0, 0, 255, 0));
     }
 
     #[test]
-    fn test_hex_3() {
-        assert_eq!(parse_css_color("#F00"), (255, 0, 0, 255));
-        assert_eq!(parse_css_color("#0F0"), (0, 255, 0, 255));
-        assert_eq!(parse_css_color("#00F"), (0, 0, 255, 255));
-        assert_eq!(parse_css_color("#FFF"), (255, 255, 255, 255));
-        assert_eq!(parse_css_color("#000"), (0, 0, 0, 255));
+    fn test_hex_3_and_4() {
+        assert_eq!(ok("#F00"), (255, 0, 0, 255));
+        assert_eq!(ok("#0F0"), (0, 255, 0, 255));
+        assert_eq!(ok("#00F"), (0, 0, 255, 255));
+        assert_eq!(ok("#FFF"), (255, 255, 255, 255));
+        assert_eq!(ok("#000"), (0, 0, 0, 255));
+        assert_eq!(ok("#F008"), (255, 0, 0, 136));
     }
 
     #[test]
     fn test_rgb() {
-        assert_eq!(parse_css_color("rgb(255, 0, 0)"), (255, 0, 0, 255));
-        assert_eq!(parse_css_color("rgb(100, 150, 200)"), (100, 150, 200, 255));
-        assert_eq!(parse_css_color("rgb(0,0,0)"), (0, 0, 0, 255));
+        assert_eq!(ok("rgb(255, 0, 0)"), (255, 0, 0, 255));
+        assert_eq!(ok("rgb(100, 150, 200)"), (100, 150, 200, 255));
+        assert_eq!(ok("rgb(0,0,0)"), (0, 0, 0, 255));
+        assert_eq!(ok("rgb(100% 50% 0%)"), (255, 128, 0, 255));
+        assert_eq!(ok("RGB( 300 , -5 , 12.6 )"), (255, 0, 13, 255));
     }
 
     #[test]
     fn test_rgba() {
-        assert_eq!(parse_css_color("rgba(255, 0, 0, 1.0)"), (255, 0, 0, 255));
-        assert_eq!(parse_css_color("rgba(255, 0, 0, 0.5)"), (255, 0, 0, 127));
+        assert_eq!(ok("rgba(255, 0, 0, 1.0)"), (255, 0, 0, 255));
+        assert_eq!(ok("rgba(255, 0, 0, 0.5)"), (255, 0, 0, 128));
+        assert_eq!(ok("rgba(100, 150, 200, 0)"), (100, 150, 200, 0));
+        // With and without spaces, modern syntax, percentage alpha
         assert_eq!(
-            parse_css_color("rgba(100, 150, 200, 0)"),
-            (100, 150, 200, 0)
+            ok("rgba(38,166,154,0.5)"),
+            ok("rgba( 38 , 166 , 154 , 0.5 )")
         );
+        assert_eq!(ok("rgb(38 166 154 / 0.5)"), (38, 166, 154, 128));
+        assert_eq!(ok("rgba(38 166 154 / 25%)"), (38, 166, 154, 64));
+        assert_eq!(ok("rgb(1, 2, 3, 2)"), (1, 2, 3, 255));
     }
 
     #[test]
-    fn test_transparent() {
-        assert_eq!(parse_css_color("transparent"), (0, 0, 0, 0));
+    fn test_hsl() {
+        assert_eq!(ok("hsl(0, 100%, 50%)"), (255, 0, 0, 255));
+        assert_eq!(ok("hsl(120deg 100% 25%)"), (0, 128, 0, 255));
+        assert_eq!(ok("hsl(0.5turn 100% 50%)"), (0, 255, 255, 255));
+        assert_eq!(ok("hsl(-120, 100%, 50%)"), (0, 0, 255, 255));
+        assert_eq!(ok("hsla(240, 100%, 50%, 0.5)"), (0, 0, 255, 128));
+        assert_eq!(ok("hsl(0 0% 100%)"), (255, 255, 255, 255));
     }
 
     #[test]
-    fn test_invalid_returns_white() {
-        assert_eq!(parse_css_color("invalid"), (255, 255, 255, 255));
-        assert_eq!(parse_css_color(""), (255, 255, 255, 255));
-        assert_eq!(parse_css_color("#GGG"), (255, 255, 255, 255));
+    fn test_named() {
+        assert_eq!(ok("transparent"), (0, 0, 0, 0));
+        assert_eq!(ok("red"), (255, 0, 0, 255));
+        assert_eq!(ok("  DarkSlateGray "), (47, 79, 79, 255));
+        assert!(NAMED_COLORS.windows(2).all(|w| w[0].0 < w[1].0));
     }
 
     #[test]
-    fn test_lowercase_hex() {
-        assert_eq!(parse_css_color("#ff0000"), (255, 0, 0, 255));
-        assert_eq!(parse_css_color("#aabbcc"), (170, 187, 204, 255));
+    fn test_errors_say_what_is_wrong() {
+        assert_eq!(parse_css_color("  "), Err(CssColorError::Empty));
+        assert_eq!(
+            parse_css_color("#GGG"),
+            Err(CssColorError::InvalidHex("#GGG".into()))
+        );
+        assert!(parse_css_color("#+ff").is_err());
+        assert_eq!(
+            parse_css_color("invalid"),
+            Err(CssColorError::UnknownName("invalid".into()))
+        );
+        assert_eq!(
+            parse_css_color("lab(50% 40 59)"),
+            Err(CssColorError::UnsupportedFunction("lab".into()))
+        );
+
+        let reason = |color: &str| match parse_css_color(color) {
+            Err(CssColorError::InvalidFunction { reason, .. }) => reason,
+            other => panic!("{}: {:?}", color, other),
+        };
+        assert_eq!(reason("rgb(1, 2)"), "expected 3 color components, got 2");
+        assert_eq!(reason("rgb(1, x, 3)"), "'x' is not a number");
+        assert_eq!(reason("rgb(1, 2, 3"), "missing closing parenthesis");
+        assert_eq!(
+            reason("rgb(1, 2, 3 / 1)"),
+            "mixes comma and slash separators"
+        );
+        assert_eq!(reason("rgb(1,,3)"), "empty component");
+        assert_eq!(
+            parse_css_color("rgb(1, 2)").unwrap_err().to_string(),
+            "Invalid color 'rgb(1, 2)': expected 3 color components, got 2"
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn prop_hex_round_trips(r: u8, g: u8, b: u8, a: u8) {
+            let hex = format!("#{:02x}{:02x}{:02x}{:02x}", r, g, b, a);
+            prop_assert_eq!(parse_css_color(&hex), Ok((r, g, b, a)));
+            prop_assert_eq!(parse_css_color(&hex.to_uppercase()), Ok((r, g, b, a)));
+        }
+
+        #[test]
+        fn prop_rgb_syntaxes_agree(r: u8, g: u8, b: u8, a: u8) {
+            let alpha = a as f64 / 255.0;
+            let expected = Ok((r, g, b, a));
+            prop_assert_eq!(parse_css_color(&format!("rgba({},{},{},{})", r, g, b, alpha)), expected.clone());
+            prop_assert_eq!(parse_css_color(&format!("rgba( {} , {} , {} , {} )", r, g, b, alpha)), expected.clone());
+            prop_assert_eq!(parse_css_color(&format!("rgb({} {} {} / {})", r, g, b, alpha)), expected.clone());
+            prop_assert_eq!(
+                parse_css_color(&format!("rgb({}% {}% {}% / {}%)",
+                    r as f64 / 2.55, g as f64 / 2.55, b as f64 / 2.55, alpha * 100.0)),
+                expected
+            );
+        }
+
+        #[test]
+        fn prop_gray_hsl_matches_rgb(l in 0u8..=100) {
+            let (r, g, b, a) = parse_css_color(&format!("hsl(200, 0%, {}%)", l)).unwrap();
+            prop_assert!(r == g && g == b && a == 255);
+            prop_assert_eq!(r, to_channel(l as f64 / 100.0 * 255.0));
+        }
+
+        #[test]
+        fn prop_never_panics(s in "\\PC*") {
+            let _ = parse_css_color(&s);
+        }
+
     }
 }
@@ -0,0 +1,1588 @@
+//! Indicator computation
+//!
+//! Pure numeric routines used to build indicator vectors from price data.
+//! Everything here works on plain `f64` slices and only needs `Vec` for
+//! output, so it is available in `compute-only` builds without the SVG
+//! backend or any rendering types.
+//!
+//! Indicator functions return a vector the same length as the input.
+//! Warm-up positions where the value is not yet defined are filled with
+//! `NaN`, which the renderer treats as a gap. [`resample`] is the exception:
+//! it aggregates bars into a higher timeframe. [`heikin_ashi`] returns one
+//! smoothed bar per input bar, paired with the bar it was computed from.
+//! [`sort_bars`] cleans up out-of-order and duplicate bars from a feed.
+//! [`renko`] and [`line_break`] return one brick per box or line, each
+//! with the bar that completed it.
+//! [`timestamp_to_bar`] maps a time onto the bar axis, past either end too.
+//! [`zigzag`] is only defined at its pivots and is `NaN` in between.
+//! The volume indicators ([`obv`], [`ad_line`], [`cmf`], [`mfi`]) take the
+//! bars themselves; a NaN, infinite or negative volume counts as zero.
+
+use super::types::{Bar, DuplicateBarMerge};
+use std::ops::Range;
+
+/// Extract close prices from bars
+pub fn closes(bars: &[Bar]) -> Vec<f64> {
+    bars.iter().map(|b| b.close).collect()
+}
+
+/// Extract high prices from bars
+pub fn highs(bars: &[Bar]) -> Vec<f64> {
+    bars.iter().map(|b| b.high).collect()
+}
+
+/// Extract low prices from bars
+pub fn lows(bars: &[Bar]) -> Vec<f64> {
+    bars.iter().map(|b| b.low).collect()
+}
+
+/// Simple moving average
+///
+/// # Examples
+///
+/// ```
+/// use zengeld_canvas::core::compute;
+///
+/// let sma = compute::sma(&[1.0, 2.0, 3.0, 4.0], 2);
+/// assert!(sma[0].is_nan());
+/// assert_eq!(&sma[1..], &[1.5, 2.5, 3.5]);
+/// ```
+pub fn sma(values: &[f64], period: usize) -> Vec<f64> {
+    let mut result = vec![f64::NAN; values.len()];
+    if period == 0 {
+        return result;
+    }
+
+    for i in (period - 1)..values.len() {
+        let sum: f64 = values[i + 1 - period..=i].iter().sum();
+        result[i] = sum / period as f64;
+    }
+
+    result
+}
+
+/// Exponential moving average, seeded with the SMA of the first `period` values
+pub fn ema(values: &[f64], period: usize) -> Vec<f64> {
+    let mut result = vec![f64::NAN; values.len()];
+    if period == 0 {
+        return result;
+    }
+    let multiplier = 2.0 / (period as f64 + 1.0);
+
+    if values.len() >= period {
+        let sum: f64 = values[0..period].iter().sum();
+        result[period - 1] = sum / period as f64;
+
+        for i in period..values.len() {
+            result[i] = (values[i] - result[i - 1]) * multiplier + result[i - 1];
+        }
+    }
+
+    result
+}
+
+/// Bollinger Bands as `(upper, middle, lower)`
+///
+/// Uses the population standard deviation over the window.
+pub fn bollinger(values: &[f64], period: usize, multiplier: f64) -> (Vec<f64>, Vec<f64>, Vec<f64>) {
+    let mut upper = vec![f64::NAN; values.len()];
+    let mut middle = vec![f64::NAN; values.len()];
+    let mut lower = vec![f64::NAN; values.len()];
+    if period == 0 {
+        return (upper, middle, lower);
+    }
+
+    for i in (period - 1)..values.len() {
+        let window = &values[i + 1 - period..=i];
+        let mean = window.iter().sum::<f64>() / period as f64;
+        let variance = window.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / period as f64;
+        let stddev = variance.sqrt();
+
+        middle[i] = mean;
+        upper[i] = mean + multiplier * stddev;
+        lower[i] = mean - multiplier * stddev;
+    }
+
+    (upper, middle, lower)
+}
+
+/// Relative Strength Index with Wilder smoothing
+pub fn rsi(values: &[f64], period: usize) -> Vec<f64> {
+    let mut result = vec![f64::NAN; values.len()];
+
+    if period == 0 || values.len() < period + 1 {
+        return result;
+    }
+
+    let mut gains = Vec::with_capacity(values.len() - 1);
+    let mut losses = Vec::with_capacity(values.len() - 1);
+
+    for i in 1..values.len() {
+        let change = values[i] - values[i - 1];
+        if change > 0.0 {
+            gains.push(change);
+            losses.push(0.0);
+        } else {
+            gains.push(0.0);
+            losses.push(-change);
+        }
+    }
+
+    let mut avg_gain: f64 = gains[0..period].iter().sum::<f64>() / period as f64;
+    let mut avg_loss: f64 = losses[0..period].iter().sum::<f64>() / period as f64;
+
+    result[period] = rsi_value(avg_gain, avg_loss);
+
+    for i in (period + 1)..values.len() {
+        avg_gain = (avg_gain * (period as f64 - 1.0) + gains[i - 1]) / period as f64;
+        avg_loss = (avg_loss * (period as f64 - 1.0) + losses[i - 1]) / period as f64;
+        result[i] = rsi_value(avg_gain, avg_loss);
+    }
+
+    result
+}
+
+fn rsi_value(avg_gain: f64, avg_loss: f64) -> f64 {
+    if avg_loss == 0.0 {
+        100.0
+    } else {
+        100.0 - 100.0 / (1.0 + avg_gain / avg_loss)
+    }
+}
+
+/// MACD as `(macd_line, signal_line, histogram)`
+pub fn macd(
+    values: &[f64],
+    fast: usize,
+    slow: usize,
+    signal: usize,
+) -> (Vec<f64>, Vec<f64>, Vec<f64>) {
+    let fast_ema = ema(values, fast);
+    let slow_ema = ema(values, slow);
+
+    let macd_line: Vec<f64> = fast_ema
+        .iter()
+        .zip(slow_ema.iter())
+        .map(|(&f, &s)| {
+            if f.is_nan() || s.is_nan() {
+                f64::NAN
+            } else {
+                f - s
+            }
+        })
+        .collect();
+
+    let mut signal_line = vec![f64::NAN; values.len()];
+    let multiplier = 2.0 / (signal as f64 + 1.0);
+
+    let first_valid = macd_line
+        .iter()
+        .position(|&v| !v.is_nan())
+        .unwrap_or(values.len());
+
+    if signal > 0 && first_valid + signal <= values.len() {
+        let sum: f64 = macd_line[first_valid..(first_valid + signal)]
+            .iter()
+            .filter(|v| !v.is_nan())
+            .sum();
+        signal_line[first_valid + signal - 1] = sum / signal as f64;
+
+        for i in (first_valid + signal)..values.len() {
+            if !macd_line[i].is_nan() && !signal_line[i - 1].is_nan() {
+                signal_line[i] =
+                    (macd_line[i] - signal_line[i - 1]) * multiplier + signal_line[i - 1];
+            }
+        }
+    }
+
+    let histogram: Vec<f64> = macd_line
+        .iter()
+        .zip(signal_line.iter())
+        .map(|(&m, &s)| {
+            if m.is_nan() || s.is_nan() {
+                f64::NAN
+            } else {
+                m - s
+            }
+        })
+        .collect();
+
+    (macd_line, signal_line, histogram)
+}
+
+/// Average Directional Index as `(adx, plus_di, minus_di)`
+///
+/// Follows Wilder: true range and directional movement are smoothed with
+/// `S = S - S / period + x`, seeded with the sum of the first `period`
+/// values. +DI/-DI start at index `period`; ADX is seeded with the mean of
+/// the first `period` DX values and starts at index `2 * period - 1`.
+pub fn adx(
+    highs: &[f64],
+    lows: &[f64],
+    closes: &[f64],
+    period: usize,
+) -> (Vec<f64>, Vec<f64>, Vec<f64>) {
+    let len = highs.len().min(lows.len()).min(closes.len());
+    let mut adx = vec![f64::NAN; len];
+    let mut plus_di = vec![f64::NAN; len];
+    let mut minus_di = vec![f64::NAN; len];
+
+    if period == 0 || len <= period {
+        return (adx, plus_di, minus_di);
+    }
+
+    let mut tr = vec![0.0; len];
+    let mut plus_dm = vec![0.0; len];
+    let mut minus_dm = vec![0.0; len];
+    for i in 1..len {
+        tr[i] = (highs[i] - lows[i])
+            .max((highs[i] - closes[i - 1]).abs())
+            .max((lows[i] - closes[i - 1]).abs());
+
+        let up = highs[i] - highs[i - 1];
+        let down = lows[i - 1] - lows[i];
+        if up > down && up > 0.0 {
+            plus_dm[i] = up;
+        }
+        if down > up && down > 0.0 {
+            minus_dm[i] = down;
+        }
+    }
+
+    let p = period as f64;
+    let mut smooth_tr: f64 = tr[1..=period].iter().sum();
+    let mut smooth_plus: f64 = plus_dm[1..=period].iter().sum();
+    let mut smooth_minus: f64 = minus_dm[1..=period].iter().sum();
+    let mut dx = vec![f64::NAN; len];
+
+    for i in period..len {
+        if i > period {
+            smooth_tr = smooth_tr - smooth_tr / p + tr[i];
+            smooth_plus = smooth_plus - smooth_plus / p + plus_dm[i];
+            smooth_minus = smooth_minus - smooth_minus / p + minus_dm[i];
+        }
+        if smooth_tr == 0.0 {
+            plus_di[i] = 0.0;
+            minus_di[i] = 0.0;
+        } else {
+            plus_di[i] = 100.0 * smooth_plus / smooth_tr;
+            minus_di[i] = 100.0 * smooth_minus / smooth_tr;
+        }
+        let di_sum = plus_di[i] + minus_di[i];
+        dx[i] = if di_sum == 0.0 {
+            0.0
+        } else {
+            100.0 * (plus_di[i] - minus_di[i]).abs() / di_sum
+        };
+    }
+
+    let first = 2 * period - 1;
+    if first < len {
+        let mut value = dx[period..=first].iter().sum::<f64>() / p;
+        adx[first] = value;
+        for i in (first + 1)..len {
+            value = (value * (p - 1.0) + dx[i]) / p;
+            adx[i] = value;
+        }
+    }
+
+    (adx, plus_di, minus_di)
+}
+
+/// Average True Range with Wilder smoothing, seeded with the mean true
+/// range of the first `period` bars; starts at index `period - 1`
+///
+/// The first bar's true range is its high-low range.
+pub fn atr(highs: &[f64], lows: &[f64], closes: &[f64], period: usize) -> Vec<f64> {
+    let len = highs.len().min(lows.len()).min(closes.len());
+    let mut result = vec![f64::NAN; len];
+    if period == 0 || len < period {
+        return result;
+    }
+
+    let tr: Vec<f64> = (0..len)
+        .map(|i| {
+            let range = highs[i] - lows[i];
+            if i == 0 {
+                range
+            } else {
+                range
+                    .max((highs[i] - closes[i - 1]).abs())
+                    .max((lows[i] - closes[i - 1]).abs())
+            }
+        })
+        .collect();
+
+    let p = period as f64;
+    let mut value = tr[..period].iter().sum::<f64>() / p;
+    result[period - 1] = value;
+    for i in period..len {
+        value = (value * (p - 1.0) + tr[i]) / p;
+        result[i] = value;
+    }
+    result
+}
+
+/// Weighted moving average, the latest value weighing `period` and the
+/// oldest 1
+pub fn wma(values: &[f64], period: usize) -> Vec<f64> {
+    let mut result = vec![f64::NAN; values.len()];
+    if period == 0 {
+        return result;
+    }
+    let weights = (period * (period + 1) / 2) as f64;
+    for i in (period - 1)..values.len() {
+        let window = &values[i + 1 - period..=i];
+        let sum: f64 = window
+            .iter()
+            .enumerate()
+            .map(|(w, v)| v * (w + 1) as f64)
+            .sum();
+        result[i] = sum / weights;
+    }
+    result
+}
+
+/// Hull moving average: WMA over `sqrt(period)` of twice the WMA over half
+/// the period minus the WMA over the period
+pub fn hma(values: &[f64], period: usize) -> Vec<f64> {
+    if period == 0 {
+        return vec![f64::NAN; values.len()];
+    }
+    let half = wma(values, (period / 2).max(1));
+    let full = wma(values, period);
+    let raw: Vec<f64> = half.iter().zip(&full).map(|(h, f)| 2.0 * h - f).collect();
+    // Leading NaNs of `raw` would poison every later window
+    let start = raw.iter().position(|v| !v.is_nan()).unwrap_or(raw.len());
+    let mut result = vec![f64::NAN; start];
+    result.extend(wma(&raw[start..], (period as f64).sqrt().round() as usize));
+    result
+}
+
+/// Highest high and lowest low over each `period` bars window
+fn extremes(highs: &[f64], lows: &[f64], period: usize, i: usize) -> (f64, f64) {
+    let highest = highs[i + 1 - period..=i]
+        .iter()
+        .fold(f64::MIN, |a, &b| a.max(b));
+    let lowest = lows[i + 1 - period..=i]
+        .iter()
+        .fold(f64::MAX, |a, &b| a.min(b));
+    (highest, lowest)
+}
+
+/// Stochastic oscillator as `(%K, %D)`: where the close sits in the range
+/// of the last `k` bars, 0 to 100, and its SMA over `d`; %K starts at index
+/// `k - 1`
+///
+/// A window without range sits at 50.
+pub fn stochastic(
+    highs: &[f64],
+    lows: &[f64],
+    closes: &[f64],
+    k: usize,
+    d: usize,
+) -> (Vec<f64>, Vec<f64>) {
+    let len = highs.len().min(lows.len()).min(closes.len());
+    let mut percent_k = vec![f64::NAN; len];
+    if k == 0 {
+        return (percent_k, vec![f64::NAN; len]);
+    }
+    for i in (k - 1)..len {
+        let (highest, lowest) = extremes(highs, lows, k, i);
+        percent_k[i] = if highest > lowest {
+            100.0 * (closes[i] - lowest) / (highest - lowest)
+        } else {
+            50.0
+        };
+    }
+    let start = (k - 1).min(len);
+    let mut percent_d = vec![f64::NAN; start];
+    percent_d.extend(sma(&percent_k[start..], d));
+    (percent_k, percent_d)
+}
+
+/// Williams %R: how far the close sits below the highest high of the last
+/// `period` bars, -100 to 0; starts at index `period - 1`
+///
+/// A window without range sits at -50.
+pub fn williams_r(highs: &[f64], lows: &[f64], closes: &[f64], period: usize) -> Vec<f64> {
+    let len = highs.len().min(lows.len()).min(closes.len());
+    let mut result = vec![f64::NAN; len];
+    if period == 0 {
+        return result;
+    }
+    for i in (period - 1)..len {
+        let (highest, lowest) = extremes(highs, lows, period, i);
+        result[i] = if highest > lowest {
+            -100.0 * (highest - closes[i]) / (highest - lowest)
+        } else {
+            -50.0
+        };
+    }
+    result
+}
+
+/// Commodity Channel Index: distance of the typical price from its SMA
+/// over `period` bars, in units of 0.015 mean deviations; starts at index
+/// `period - 1`
+///
+/// A window without deviation is zero.
+pub fn cci(highs: &[f64], lows: &[f64], closes: &[f64], period: usize) -> Vec<f64> {
+    let len = highs.len().min(lows.len()).min(closes.len());
+    let mut result = vec![f64::NAN; len];
+    if period == 0 {
+        return result;
+    }
+    let typical: Vec<f64> = (0..len)
+        .map(|i| (highs[i] + lows[i] + closes[i]) / 3.0)
+        .collect();
+    for i in (period - 1)..len {
+        let window = &typical[i + 1 - period..=i];
+        let mean = window.iter().sum::<f64>() / period as f64;
+        let deviation = window.iter().map(|v| (v - mean).abs()).sum::<f64>() / period as f64;
+        result[i] = if deviation > 0.0 {
+            (typical[i] - mean) / (0.015 * deviation)
+        } else {
+            0.0
+        };
+    }
+    result
+}
+
+/// Keltner Channels as `(upper, middle, lower)`: the EMA of the closes over
+/// `period` bars, `multiplier` ATRs over the same period either side;
+/// starts at index `period - 1`
+pub fn keltner(
+    highs: &[f64],
+    lows: &[f64],
+    closes: &[f64],
+    period: usize,
+    multiplier: f64,
+) -> (Vec<f64>, Vec<f64>, Vec<f64>) {
+    let middle = ema(closes, period);
+    let range = atr(highs, lows, closes, period);
+    let (upper, lower) = middle
+        .iter()
+        .zip(&range)
+        .map(|(m, r)| (m + multiplier * r, m - multiplier * r))
+        .unzip();
+    (upper, middle, lower)
+}
+
+/// Donchian Channels as `(upper, middle, lower)`: the highest high and
+/// lowest low of the last `period` bars and their midpoint; starts at
+/// index `period - 1`
+pub fn donchian(highs: &[f64], lows: &[f64], period: usize) -> (Vec<f64>, Vec<f64>, Vec<f64>) {
+    let len = highs.len().min(lows.len());
+    let mut upper = vec![f64::NAN; len];
+    let mut middle = vec![f64::NAN; len];
+    let mut lower = vec![f64::NAN; len];
+    if period == 0 {
+        return (upper, middle, lower);
+    }
+    for i in (period - 1)..len {
+        let (highest, lowest) = extremes(highs, lows, period, i);
+        upper[i] = highest;
+        lower[i] = lowest;
+        middle[i] = (highest + lowest) / 2.0;
+    }
+    (upper, middle, lower)
+}
+
+/// Volume of a bar, zero when missing or invalid
+fn volume(bar: &Bar) -> f64 {
+    if bar.volume.is_finite() && bar.volume > 0.0 {
+        bar.volume
+    } else {
+        0.0
+    }
+}
+
+/// Where the close sits in the bar's range, from -1 at the low to 1 at the
+/// high; zero for a bar without range
+fn money_flow_multiplier(bar: &Bar) -> f64 {
+    let range = bar.high - bar.low;
+    let multiplier = ((bar.close - bar.low) - (bar.high - bar.close)) / range;
+    if range > 0.0 && multiplier.is_finite() {
+        multiplier
+    } else {
+        0.0
+    }
+}
+
+/// On-Balance Volume: running total of the volume, added on bars closing
+/// higher than the one before and subtracted on bars closing lower
+///
+/// Starts at zero on the first bar.
+///
+/// # Examples
+///
+/// ```
+/// use zengeld_canvas::core::{Bar, compute};
+///
+/// let bar = |close: f64, volume: f64| Bar::with_volume(0, close, close, close, close, volume);
+/// let obv = compute::obv(&[bar(10.0, 5.0), bar(11.0, 3.0), bar(10.5, 2.0)]);
+/// assert_eq!(obv, [0.0, 3.0, 1.0]);
+/// ```
+pub fn obv(bars: &[Bar]) -> Vec<f64> {
+    let mut total = 0.0;
+    let mut result = Vec::with_capacity(bars.len());
+    for (i, bar) in bars.iter().enumerate() {
+        if i > 0 {
+            let change = bar.close - bars[i - 1].close;
+            if change > 0.0 {
+                total += volume(bar);
+            } else if change < 0.0 {
+                total -= volume(bar);
+            }
+        }
+        result.push(total);
+    }
+    result
+}
+
+/// Accumulation/Distribution line: running total of the volume weighted by
+/// the money flow multiplier, where the close sits in the bar's range
+pub fn ad_line(bars: &[Bar]) -> Vec<f64> {
+    let mut total = 0.0;
+    bars.iter()
+        .map(|bar| {
+            total += money_flow_multiplier(bar) * volume(bar);
+            total
+        })
+        .collect()
+}
+
+/// Chaikin Money Flow: weighted volume over `period` bars divided by their
+/// volume, between -1 and 1; starts at index `period - 1`
+///
+/// Windows without volume are zero.
+pub fn cmf(bars: &[Bar], period: usize) -> Vec<f64> {
+    let mut result = vec![f64::NAN; bars.len()];
+    if period == 0 {
+        return result;
+    }
+    for i in (period - 1)..bars.len() {
+        let window = &bars[i + 1 - period..=i];
+        let flow: f64 = window
+            .iter()
+            .map(|b| money_flow_multiplier(b) * volume(b))
+            .sum();
+        let volume: f64 = window.iter().map(volume).sum();
+        result[i] = if volume > 0.0 { flow / volume } else { 0.0 };
+    }
+    result
+}
+
+/// Money Flow Index: RSI of the typical price weighted by volume, over
+/// `period` price changes; starts at index `period`
+///
+/// Windows without any money flow sit at the neutral 50.
+pub fn mfi(bars: &[Bar], period: usize) -> Vec<f64> {
+    let mut result = vec![f64::NAN; bars.len()];
+    if period == 0 || bars.len() <= period {
+        return result;
+    }
+    let typical: Vec<f64> = bars
+        .iter()
+        .map(|b| (b.high + b.low + b.close) / 3.0)
+        .collect();
+    // Signed flow of each bar against the one before
+    let flows: Vec<(f64, f64)> = (0..bars.len())
+        .map(|i| {
+            let flow = typical[i] * volume(&bars[i]);
+            match i.checked_sub(1).map(|prev| typical[i] - typical[prev]) {
+                Some(change) if change > 0.0 => (flow, 0.0),
+                Some(change) if change < 0.0 => (0.0, flow),
+                _ => (0.0, 0.0),
+            }
+        })
+        .collect();
+    for i in period..bars.len() {
+        let (positive, negative) = flows[i + 1 - period..=i]
+            .iter()
+            .fold((0.0, 0.0), |acc, f| (acc.0 + f.0, acc.1 + f.1));
+        result[i] = if positive + negative > 0.0 {
+            rsi_value(positive, negative)
+        } else {
+            50.0
+        };
+    }
+    result
+}
+
+/// Parabolic SAR as `(sar, directions)`, where a direction is `true` while
+/// long (the SAR trails below price)
+///
+/// Wilder's rules: the acceleration factor starts at `step`, grows by `step`
+/// with every new extreme up to `max_step`, and the SAR never moves into the
+/// previous two bars' range. A bar piercing the SAR flips the trend and
+/// restarts it at the prior extreme. The first bar has no SAR; the initial
+/// trend follows the second bar's midpoint. Bars with a non-finite high or
+/// low stay `NaN`.
+pub fn psar(highs: &[f64], lows: &[f64], step: f64, max_step: f64) -> (Vec<f64>, Vec<bool>) {
+    let len = highs.len().min(lows.len());
+    let mut sar_values = vec![f64::NAN; len];
+    let mut directions = vec![true; len];
+    if len < 2 {
+        return (sar_values, directions);
+    }
+
+    let mut long = highs[1] + lows[1] >= highs[0] + lows[0];
+    let mut sar = if long { lows[0] } else { highs[0] };
+    let mut extreme = if long { highs[0] } else { lows[0] };
+    let mut af = step;
+    // Last two finite bars, most recent first
+    let mut prev = [(highs[0], lows[0]); 2];
+
+    for i in 1..len {
+        let (high, low) = (highs[i], lows[i]);
+        if !high.is_finite() || !low.is_finite() {
+            continue;
+        }
+        if i > 1 {
+            sar += af * (extreme - sar);
+            sar = if long {
+                sar.min(prev[0].1).min(prev[1].1)
+            } else {
+                sar.max(prev[0].0).max(prev[1].0)
+            };
+        }
+
+        if long && low < sar {
+            long = false;
+            sar = extreme;
+            extreme = low;
+            af = step;
+        } else if !long && high > sar {
+            long = true;
+            sar = extreme;
+            extreme = high;
+            af = step;
+        } else if long && high > extreme {
+            extreme = high;
+            af = (af + step).min(max_step);
+        } else if !long && low < extreme {
+            extreme = low;
+            af = (af + step).min(max_step);
+        }
+
+        sar_values[i] = sar;
+        directions[i] = long;
+        prev = [(high, low), prev[0]];
+    }
+    (sar_values, directions)
+}
+
+/// Supertrend as `(supertrend, directions)`, where a direction is `true` in
+/// an uptrend (the line trails below price)
+///
+/// Bands sit `multiplier` [`atr`]s either side of the bar midpoint and only
+/// tighten until the close breaks through one. The line follows the lower
+/// band in an uptrend and the upper band in a downtrend; a close beyond the
+/// line flips it. Starts with the ATR at index `period - 1`.
+pub fn supertrend(
+    highs: &[f64],
+    lows: &[f64],
+    closes: &[f64],
+    period: usize,
+    multiplier: f64,
+) -> (Vec<f64>, Vec<bool>) {
+    let atr = atr(highs, lows, closes, period);
+    let len = atr.len();
+    let mut values = vec![f64::NAN; len];
+    let mut directions = vec![true; len];
+    // (upper band, lower band, uptrend) of the previous bar
+    let mut state: Option<(f64, f64, bool)> = None;
+
+    for i in 0..len {
+        if !atr[i].is_finite() || !closes[i].is_finite() {
+            continue;
+        }
+        let mid = (highs[i] + lows[i]) / 2.0;
+        let (basic_upper, basic_lower) = (mid + multiplier * atr[i], mid - multiplier * atr[i]);
+
+        let (upper, lower, up) = match state {
+            None => (basic_upper, basic_lower, closes[i] >= mid),
+            Some((prev_upper, prev_lower, prev_up)) => {
+                let prev_close = closes[i - 1];
+                let upper = if basic_upper < prev_upper || prev_close > prev_upper {
+                    basic_upper
+                } else {
+                    prev_upper
+                };
+                let lower = if basic_lower > prev_lower || prev_close < prev_lower {
+                    basic_lower
+                } else {
+                    prev_lower
+                };
+                let up = if prev_up {
+                    closes[i] >= lower
+                } else {
+                    closes[i] > upper
+                };
+                (upper, lower, up)
+            }
+        };
+
+        values[i] = if up { lower } else { upper };
+        directions[i] = up;
+        state = Some((upper, lower, up));
+    }
+    (values, directions)
+}
+
+/// Aggregate bars into `interval_secs` buckets aligned to the unix epoch
+/// (4h buckets start at 00:00, 04:00, ... UTC)
+///
+/// Returns each higher-timeframe bar with the index range of its child
+/// bars. A bucket only covers the bars actually present, so the first and
+/// last buckets may be partial. Bars must be sorted by timestamp.
+pub fn resample(bars: &[Bar], interval_secs: i64) -> Vec<(Bar, Range<usize>)> {
+    let mut out: Vec<(Bar, Range<usize>)> = Vec::new();
+    if interval_secs <= 0 {
+        return out;
+    }
+
+    for (i, bar) in bars.iter().enumerate() {
+        let bucket = bar.timestamp.div_euclid(interval_secs) * interval_secs;
+        match out.last_mut() {
+            Some((htf, range)) if htf.timestamp == bucket => {
+                htf.high = htf.high.max(bar.high);
+                htf.low = htf.low.min(bar.low);
+                htf.close = bar.close;
+                htf.volume += bar.volume;
+                range.end = i + 1;
+            }
+            _ => out.push((
+                Bar {
+                    timestamp: bucket,
+                    ..*bar
+                },
+                i..i + 1,
+            )),
+        }
+    }
+
+    out
+}
+
+/// Bars stably sorted by timestamp, one per timestamp
+///
+/// Bars sharing a timestamp are merged in delivery order according to
+/// `merge`, so a replayed bar wins over the one it replays.
+pub fn sort_bars(bars: &[Bar], merge: DuplicateBarMerge) -> Vec<Bar> {
+    let mut sorted = bars.to_vec();
+    sorted.sort_by_key(|b| b.timestamp);
+
+    let mut out: Vec<Bar> = Vec::with_capacity(sorted.len());
+    for bar in sorted {
+        match out.last_mut() {
+            Some(prev) if prev.timestamp == bar.timestamp => match merge {
+                DuplicateBarMerge::KeepLast => *prev = bar,
+                DuplicateBarMerge::Combine => {
+                    prev.high = prev.high.max(bar.high);
+                    prev.low = prev.low.min(bar.low);
+                    prev.close = bar.close;
+                    prev.volume += bar.volume;
+                }
+            },
+            _ => out.push(bar),
+        }
+    }
+    out
+}
+
+/// Whether timestamps strictly increase, i.e. [`sort_bars`] would change nothing
+pub fn bars_are_sorted(bars: &[Bar]) -> bool {
+    bars.windows(2).all(|w| w[0].timestamp < w[1].timestamp)
+}
+
+/// Median spacing between consecutive bars, ignoring non-increasing steps
+///
+/// Robust to weekend and overnight gaps, which would stretch a mean.
+pub fn median_bar_interval(bars: &[Bar]) -> Option<i64> {
+    let mut steps: Vec<i64> = bars
+        .windows(2)
+        .map(|w| w[1].timestamp - w[0].timestamp)
+        .filter(|&step| step > 0)
+        .collect();
+    if steps.is_empty() {
+        return None;
+    }
+    let mid = steps.len() / 2;
+    Some(*steps.select_nth_unstable(mid).1)
+}
+
+/// Fractional bar index of a timestamp on sorted bars
+///
+/// Times between two bars interpolate linearly. Times before the first or
+/// after the last bar are extrapolated with [`median_bar_interval`], so a
+/// scheduled event ten intervals out lands at `len - 1 + 10`. `None` when
+/// there are no bars, or one bar and `timestamp` is not on it.
+pub fn timestamp_to_bar(bars: &[Bar], timestamp: i64) -> Option<f64> {
+    let (first, last) = (bars.first()?, bars.last()?);
+    let extrapolate = |from: &Bar, index: usize| {
+        if timestamp == from.timestamp {
+            return Some(index as f64);
+        }
+        let interval = median_bar_interval(bars)?;
+        Some(index as f64 + (timestamp - from.timestamp) as f64 / interval as f64)
+    };
+    if timestamp <= first.timestamp {
+        return extrapolate(first, 0);
+    }
+    if timestamp >= last.timestamp {
+        return extrapolate(last, bars.len() - 1);
+    }
+
+    let next = bars.partition_point(|b| b.timestamp <= timestamp);
+    let (a, b) = (&bars[next - 1], &bars[next]);
+    let frac = (timestamp - a.timestamp) as f64 / (b.timestamp - a.timestamp) as f64;
+    Some((next - 1) as f64 + frac)
+}
+
+/// A Heikin Ashi bar together with the bar it was computed from
+#[derive(Clone, Copy, Debug)]
+pub struct HeikinAshiBar {
+    /// Smoothed OHLC
+    pub ha: Bar,
+    /// Underlying bar, kept so renderers can show real highs and lows
+    pub original: Bar,
+}
+
+/// Heikin Ashi transform
+///
+/// - HA Close = (Open + High + Low + Close) / 4
+/// - HA Open = (prev HA Open + prev HA Close) / 2, seeded with the first open
+/// - HA High = max(High, HA Open, HA Close)
+/// - HA Low = min(Low, HA Open, HA Close)
+///
+/// Bars with a `NaN` price are passed through unchanged and do not advance
+/// the smoothing.
+pub fn heikin_ashi(bars: &[Bar]) -> Vec<HeikinAshiBar> {
+    let mut ha_open: Option<f64> = None;
+
+    bars.iter()
+        .map(|bar| {
+            if bar.open.is_nan() || bar.high.is_nan() || bar.low.is_nan() || bar.close.is_nan() {
+                return HeikinAshiBar {
+                    ha: *bar,
+                    original: *bar,
+                };
+            }
+
+            let open = ha_open.unwrap_or(bar.open);
+            let close = (bar.open + bar.high + bar.low + bar.close) / 4.0;
+            ha_open = Some((open + close) / 2.0);
+
+            HeikinAshiBar {
+                ha: Bar {
+                    open,
+                    high: bar.high.max(open).max(close),
+                    low: bar.low.min(open).min(close),
+                    close,
+                    ..*bar
+                },
+                original: *bar,
+            }
+        })
+        .collect()
+}
+
+/// A Renko or line-break brick with the bar that completed it
+///
+/// Bricks are spaced evenly whatever the time between them, so the time
+/// axis labels each one with its source bar's timestamp.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Brick {
+    pub open: f64,
+    pub close: f64,
+    /// Index of the source bar that completed the brick
+    pub source: usize,
+    /// Timestamp of that bar
+    pub timestamp: i64,
+}
+
+impl Brick {
+    fn new(open: f64, close: f64, source: usize, bar: &Bar) -> Self {
+        Self {
+            open,
+            close,
+            source,
+            timestamp: bar.timestamp,
+        }
+    }
+
+    /// Whether the brick closes above its open
+    pub fn is_up(&self) -> bool {
+        self.close > self.open
+    }
+
+    /// The brick as a bar spanning open to close, stamped with its source
+    /// bar's time
+    pub fn to_bar(&self) -> Bar {
+        Bar::new(
+            self.timestamp,
+            self.open,
+            self.open.max(self.close),
+            self.open.min(self.close),
+            self.close,
+        )
+    }
+}
+
+/// Renko bricks of `box_size` on the closes
+///
+/// A brick is added each time the close moves a full box past the last
+/// brick; turning around takes two boxes, as the reversal brick starts at
+/// the far edge of the last one. One bar may complete several bricks.
+pub fn renko(bars: &[Bar], box_size: f64) -> Vec<Brick> {
+    let mut bricks = Vec::new();
+    let Some(first) = bars.iter().find(|b| b.close.is_finite()) else {
+        return bricks;
+    };
+    if !(box_size.is_finite() && box_size > 0.0) {
+        return bricks;
+    }
+    let (mut top, mut bottom) = (first.close, first.close);
+    for (i, bar) in bars.iter().enumerate() {
+        while bar.close >= top + box_size {
+            bricks.push(Brick::new(top, top + box_size, i, bar));
+            (bottom, top) = (top, top + box_size);
+        }
+        while bar.close <= bottom - box_size {
+            bricks.push(Brick::new(bottom, bottom - box_size, i, bar));
+            (top, bottom) = (bottom, bottom - box_size);
+        }
+    }
+    bricks
+}
+
+/// Line-break bricks: a new line each time the close passes the last
+/// line's end, turning around only when it passes the far end of the
+/// last `lines` lines (three-line break for `lines = 3`)
+pub fn line_break(bars: &[Bar], lines: usize) -> Vec<Brick> {
+    let mut bricks: Vec<Brick> = Vec::new();
+    let Some(first) = bars.iter().find(|b| b.close.is_finite()) else {
+        return bricks;
+    };
+    let lines = lines.max(1);
+    let reference = first.close;
+    for (i, bar) in bars.iter().enumerate() {
+        let close = bar.close;
+        let Some(last) = bricks.last().copied() else {
+            if close.is_finite() && close != reference {
+                bricks.push(Brick::new(reference, close, i, bar));
+            }
+            continue;
+        };
+        let recent = &bricks[bricks.len().saturating_sub(lines)..];
+        let high = recent
+            .iter()
+            .map(|b| b.open.max(b.close))
+            .fold(f64::MIN, f64::max);
+        let low = recent
+            .iter()
+            .map(|b| b.open.min(b.close))
+            .fold(f64::MAX, f64::min);
+        let brick = if last.is_up() {
+            if close > last.close {
+                Some(Brick::new(last.close, close, i, bar))
+            } else if close < low {
+                Some(Brick::new(last.open, close, i, bar))
+            } else {
+                None
+            }
+        } else if close < last.close {
+            Some(Brick::new(last.close, close, i, bar))
+        } else if close > high {
+            Some(Brick::new(last.open, close, i, bar))
+        } else {
+            None
+        };
+        bricks.extend(brick);
+    }
+    bricks
+}
+
+/// A ZigZag turning point
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ZigZagPivot {
+    /// Bar index of the pivot
+    pub index: usize,
+    /// The bar's high for a swing high, its low for a swing low
+    pub value: f64,
+    /// Whether this is a swing high
+    pub is_high: bool,
+}
+
+/// ZigZag pivots: swing highs and lows separated by a reversal of at least
+/// `deviation` percent
+///
+/// A high is confirmed once a later low falls `deviation`% below it, and a
+/// low once a later high rises `deviation`% above it. The last pivot is the
+/// extreme of the leg still in progress, so it can move as bars arrive.
+/// Nothing is returned until the first `deviation`% move. Bars with a
+/// non-finite high or low are skipped.
+pub fn zigzag_pivots(highs: &[f64], lows: &[f64], deviation: f64) -> Vec<ZigZagPivot> {
+    let threshold = deviation.max(0.0) / 100.0;
+    let mut pivots = Vec::new();
+    // Before the first reversal, both extremes are candidates
+    let mut high: Option<(usize, f64)> = None;
+    let mut low: Option<(usize, f64)> = None;
+    // Once trending: the current leg's extreme and whether it is a high
+    let mut leg: Option<(ZigZagPivot, bool)> = None;
+
+    for (i, (&h, &l)) in highs.iter().zip(lows).enumerate() {
+        if !h.is_finite() || !l.is_finite() {
+            continue;
+        }
+        match &mut leg {
+            None => {
+                if high.is_none_or(|(_, v)| h > v) {
+                    high = Some((i, h));
+                }
+                if low.is_none_or(|(_, v)| l < v) {
+                    low = Some((i, l));
+                }
+                let (Some((hi, hv)), Some((li, lv))) = (high, low) else {
+                    continue;
+                };
+                if hi > li && hv >= lv * (1.0 + threshold) {
+                    pivots.push(ZigZagPivot {
+                        index: li,
+                        value: lv,
+                        is_high: false,
+                    });
+                    leg = Some((
+                        ZigZagPivot {
+                            index: hi,
+                            value: hv,
+                            is_high: true,
+                        },
+                        true,
+                    ));
+                } else if li > hi && lv <= hv * (1.0 - threshold) {
+                    pivots.push(ZigZagPivot {
+                        index: hi,
+                        value: hv,
+                        is_high: true,
+                    });
+                    leg = Some((
+                        ZigZagPivot {
+                            index: li,
+                            value: lv,
+                            is_high: false,
+                        },
+                        false,
+                    ));
+                }
+            }
+            Some((extreme, rising)) => {
+                if *rising {
+                    if h > extreme.value {
+                        *extreme = ZigZagPivot {
+                            index: i,
+                            value: h,
+                            is_high: true,
+                        };
+                    } else if l <= extreme.value * (1.0 - threshold) {
+                        pivots.push(*extreme);
+                        *extreme = ZigZagPivot {
+                            index: i,
+                            value: l,
+                            is_high: false,
+                        };
+                        *rising = false;
+                    }
+                } else if l < extreme.value {
+                    *extreme = ZigZagPivot {
+                        index: i,
+                        value: l,
+                        is_high: false,
+                    };
+                } else if h >= extreme.value * (1.0 + threshold) {
+                    pivots.push(*extreme);
+                    *extreme = ZigZagPivot {
+                        index: i,
+                        value: h,
+                        is_high: true,
+                    };
+                    *rising = true;
+                }
+            }
+        }
+    }
+
+    if let Some((extreme, _)) = leg {
+        pivots.push(extreme);
+    }
+    pivots
+}
+
+/// ZigZag as an indicator vector: the pivot value at each pivot bar of
+/// [`zigzag_pivots`], `NaN` everywhere else
+pub fn zigzag(highs: &[f64], lows: &[f64], deviation: f64) -> Vec<f64> {
+    let mut result = vec![f64::NAN; highs.len().min(lows.len())];
+    for pivot in zigzag_pivots(highs, lows, deviation) {
+        result[pivot.index] = pivot.value;
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn approx(a: f64, b: f64) -> bool {
+        (a - b).abs() < 1e-9
+    }
+
+    #[test]
+    fn test_sma() {
+        let sma = sma(&[2.0, 4.0, 6.0, 8.0, 10.0], 3);
+        assert!(sma[0].is_nan() && sma[1].is_nan());
+        assert_eq!(&sma[2..], &[4.0, 6.0, 8.0]);
+    }
+
+    #[test]
+    fn test_ema_seeded_with_sma() {
+        let ema = ema(&[2.0, 4.0, 6.0, 8.0], 3);
+        assert!(ema[1].is_nan());
+        assert!(approx(ema[2], 4.0));
+        // k = 0.5: (8 - 4) * 0.5 + 4
+        assert!(approx(ema[3], 6.0));
+    }
+
+    #[test]
+    fn test_bollinger_constant_series() {
+        let (upper, middle, lower) = bollinger(&[5.0; 6], 3, 2.0);
+        assert!(upper[1].is_nan());
+        assert!(approx(upper[5], 5.0));
+        assert!(approx(middle[5], 5.0));
+        assert!(approx(lower[5], 5.0));
+    }
+
+    #[test]
+    fn test_rsi_bounds() {
+        let rising: Vec<f64> = (0..20).map(|i| i as f64).collect();
+        let rsi_up = rsi(&rising, 14);
+        assert!(rsi_up[13].is_nan());
+        assert!(approx(rsi_up[14], 100.0));
+
+        let mixed = [1.0, 2.0, 1.0, 2.0, 1.0, 2.0];
+        let rsi_mixed = rsi(&mixed, 2);
+        assert!(rsi_mixed[2..].iter().all(|v| (0.0..=100.0).contains(v)));
+    }
+
+    #[test]
+    fn test_macd_histogram_is_difference() {
+        let values: Vec<f64> = (0..60)
+            .map(|i| 100.0 + (i as f64 * 0.3).sin() * 5.0)
+            .collect();
+        let (line, signal, hist) = macd(&values, 12, 26, 9);
+        assert_eq!(line.len(), values.len());
+        assert!(line[24].is_nan() && !line[25].is_nan());
+        assert!(signal[32].is_nan() && !signal[33].is_nan());
+        for i in 33..values.len() {
+            assert!(approx(hist[i], line[i] - signal[i]));
+        }
+    }
+
+    #[test]
+    fn test_zero_period_is_all_nan() {
+        assert!(sma(&[1.0, 2.0], 0).iter().all(|v| v.is_nan()));
+        assert!(ema(&[1.0, 2.0], 0).iter().all(|v| v.is_nan()));
+        assert!(rsi(&[1.0, 2.0], 0).iter().all(|v| v.is_nan()));
+    }
+
+    fn ohlcv(rows: &[(f64, f64, f64, f64)]) -> Vec<Bar> {
+        rows.iter()
+            .enumerate()
+            .map(|(i, &(high, low, close, volume))| {
+                Bar::with_volume(i as i64, close, high, low, close, volume)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_obv_hand_computed() {
+        let bars = ohlcv(&[
+            (10.5, 9.5, 10.0, 1000.0),
+            (11.5, 10.0, 11.0, 1500.0),   // up: +1500
+            (11.2, 10.4, 10.5, 1200.0),   // down: -1200
+            (11.0, 10.2, 10.5, 800.0),    // unchanged
+            (12.5, 10.5, 12.0, 2000.0),   // up: +2000
+            (12.2, 11.0, 11.5, f64::NAN), // down, no volume
+        ]);
+        assert_eq!(obv(&bars), [0.0, 1500.0, 300.0, 300.0, 2300.0, 2300.0]);
+        assert!(obv(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_money_flow_indicators() {
+        let bars = ohlcv(&[
+            (12.0, 10.0, 12.0, 100.0), // close at the high: multiplier 1
+            (12.0, 10.0, 10.0, 50.0),  // at the low: -1
+            (12.0, 10.0, 11.0, 80.0),  // midpoint: 0
+            (11.0, 11.0, 11.0, 70.0),  // no range: 0
+        ]);
+        assert_eq!(ad_line(&bars), [100.0, 50.0, 50.0, 50.0]);
+
+        let flow = cmf(&bars, 2);
+        assert!(flow[0].is_nan());
+        assert!(approx(flow[1], 50.0 / 150.0));
+        assert!(approx(flow[2], -50.0 / 130.0));
+        assert_eq!(flow[3], 0.0);
+
+        // Typical prices 34/3, 32/3, 11, 11: one falling, one rising bar
+        let index = mfi(&bars, 2);
+        assert!(index[1].is_nan());
+        let (down, up) = (32.0 / 3.0 * 50.0, 11.0 * 80.0);
+        assert!(approx(index[2], 100.0 - 100.0 / (1.0 + up / down)));
+        // Only the rise is left in the window
+        assert_eq!(index[3], 100.0);
+
+        // Without volume nothing flows
+        let quiet = ohlcv(&[(12.0, 10.0, 11.0, 0.0); 4]);
+        assert_eq!(cmf(&quiet, 2)[3], 0.0);
+        assert_eq!(mfi(&quiet, 2)[3], 50.0);
+    }
+
+    #[test]
+    fn test_renko_and_line_break_bricks() {
+        let bars = ohlcv(&[
+            (0.0, 0.0, 10.0, 0.0),
+            (0.0, 0.0, 12.5, 0.0), // two up bricks: 10-11, 11-12
+            (0.0, 0.0, 11.5, 0.0), // within the reversal's two boxes
+            (0.0, 0.0, 9.9, 0.0),  // one down brick, 11-10
+            (0.0, 0.0, 13.0, 0.0), // up again from 11
+        ]);
+        let bricks: Vec<(f64, f64, usize)> = renko(&bars, 1.0)
+            .iter()
+            .map(|b| (b.open, b.close, b.source))
+            .collect();
+        assert_eq!(
+            bricks,
+            [
+                (10.0, 11.0, 1),
+                (11.0, 12.0, 1),
+                (11.0, 10.0, 3),
+                (11.0, 12.0, 4),
+                (12.0, 13.0, 4),
+            ]
+        );
+        assert_eq!(renko(&bars, 1.0)[2].timestamp, bars[3].timestamp);
+        assert!(renko(&bars, 0.0).is_empty());
+
+        let lines: Vec<(f64, f64, usize)> = line_break(&bars, 3)
+            .iter()
+            .map(|b| (b.open, b.close, b.source))
+            .collect();
+        // 11.5 neither extends nor breaks the low of the last lines; 9.9 does
+        assert_eq!(lines, [(10.0, 12.5, 1), (10.0, 9.9, 3), (10.0, 13.0, 4)]);
+    }
+
+    #[test]
+    fn test_atr_constant_range() {
+        let highs = [11.0, 12.0, 13.0, 14.0];
+        let lows = [9.0, 10.0, 11.0, 12.0];
+        let closes = [10.0, 11.0, 12.0, 13.0];
+        let atr = atr(&highs, &lows, &closes, 2);
+        assert!(atr[0].is_nan());
+        // True range is 2 on the first bar, then max(2, |12 - 10|, |10 - 10|)
+        assert_eq!(&atr[1..], &[2.0, 2.0, 2.0]);
+    }
+
+    #[test]
+    fn test_weighted_and_hull_averages() {
+        let values = [1.0, 2.0, 3.0, 4.0, 5.0];
+        let weighted = wma(&values, 3);
+        assert!(weighted[1].is_nan());
+        // (1 + 4 + 9) / 6
+        assert!(approx(weighted[2], 14.0 / 6.0));
+        // A straight line: HMA lands on the latest value
+        let line: Vec<f64> = (0..30).map(f64::from).collect();
+        let hull = hma(&line, 9);
+        let first = hull.iter().position(|v| !v.is_nan()).unwrap();
+        assert_eq!(first, 8 + 2);
+        assert!(
+            hull[first..]
+                .iter()
+                .zip(&line[first..])
+                .all(|(h, v)| approx(*h, *v))
+        );
+    }
+
+    #[test]
+    fn test_range_oscillators_hand_computed() {
+        let highs = [10.0, 12.0, 11.0, 13.0];
+        let lows = [8.0, 9.0, 9.0, 10.0];
+        let closes = [9.0, 11.0, 10.0, 12.0];
+        // Last window: high 13, low 9, close 12
+        let (k, d) = stochastic(&highs, &lows, &closes, 3, 2);
+        assert!(k[1].is_nan());
+        assert!(approx(k[3], 75.0));
+        assert!(approx(d[3], (k[2] + k[3]) / 2.0));
+        assert!(approx(williams_r(&highs, &lows, &closes, 3)[3], -25.0));
+
+        let flat = [5.0; 4];
+        assert_eq!(stochastic(&flat, &flat, &flat, 2, 1).0[3], 50.0);
+        assert_eq!(cci(&flat, &flat, &flat, 2)[3], 0.0);
+        // Last two typical prices 10 and 35/3: a full mean deviation above
+        // the mean is 1 / 0.015
+        assert!(approx(cci(&highs, &lows, &closes, 2)[3], 200.0 / 3.0));
+    }
+
+    #[test]
+    fn test_channels_hand_computed() {
+        let highs = [10.0, 12.0, 11.0, 13.0];
+        let lows = [8.0, 9.0, 9.0, 10.0];
+        let (upper, middle, lower) = donchian(&highs, &lows, 3);
+        assert!(upper[1].is_nan());
+        assert_eq!((upper[3], middle[3], lower[3]), (13.0, 11.0, 9.0));
+
+        // Constant one-point range: ATR 1, channel one multiplier wide
+        let closes = [10.0; 5];
+        let (upper, middle, lower) = keltner(&[10.5; 5], &[9.5; 5], &closes, 3, 2.0);
+        assert_eq!((upper[4], middle[4], lower[4]), (12.0, 10.0, 8.0));
+    }
+
+    #[test]
+    fn test_psar_flips_both_ways() {
+        let highs = [10.0, 11.0, 12.0, 12.5, 10.0, 9.0, 13.0];
+        let lows = [9.0, 10.0, 11.0, 11.5, 8.5, 8.0, 10.0];
+        let (sar, long) = psar(&highs, &lows, 0.02, 0.2);
+
+        assert!(sar[0].is_nan());
+        // Long from the first low; bar 2 is held at the prior two lows
+        assert_eq!(&sar[1..3], &[9.0, 9.0]);
+        // 9 + 0.06 * (12 - 9)
+        assert!(approx(sar[3], 9.18));
+        // Bar 4 pierces 9.4456: short from the 12.5 extreme, held there
+        // by the prior highs on bar 5
+        assert_eq!(&sar[4..6], &[12.5, 12.5]);
+        // Bar 6 pierces 12.32: long again from the 8 extreme
+        assert_eq!(sar[6], 8.0);
+        assert_eq!(&long[1..], &[true, true, true, false, false, true]);
+    }
+
+    #[test]
+    fn test_supertrend_trails_price_and_flips() {
+        // Up, down, then up again
+        let closes: Vec<f64> = (0..60)
+            .map(|i| match i {
+                0..20 => 100.0 + i as f64,
+                20..40 => 120.0 - (i - 20) as f64 * 1.5,
+                _ => 90.0 + (i - 40) as f64 * 1.5,
+            })
+            .collect();
+        let highs: Vec<f64> = closes.iter().map(|c| c + 1.0).collect();
+        let lows: Vec<f64> = closes.iter().map(|c| c - 1.0).collect();
+        let (line, up) = supertrend(&highs, &lows, &closes, 5, 2.0);
+
+        assert!(line[3].is_nan() && line[4].is_finite());
+        for i in 4..60 {
+            assert_eq!(up[i], line[i] < closes[i], "bar {}", i);
+            // The stop only tightens while the trend holds
+            if i > 4 && up[i] == up[i - 1] {
+                assert!(if up[i] {
+                    line[i] >= line[i - 1]
+                } else {
+                    line[i] <= line[i - 1]
+                });
+            }
+        }
+        let flips = (5..60).filter(|&i| up[i] != up[i - 1]).count();
+        assert_eq!(flips, 2);
+    }
+
+    #[test]
+    fn test_zigzag_pivots_at_five_percent() {
+        let bars = [
+            (101.0, 99.0),
+            (104.0, 100.0), // +5.05% off the 99 low: the low is a pivot
+            (106.0, 103.0),
+            (105.0, 101.0), // 101 is only 4.7% below 106
+            (102.0, 100.0), // 5.7% below 106: the high is a pivot
+            (103.0, 98.0),
+            (102.0, 100.0), // 102 is only 4.1% above 98
+            (104.0, 101.0), // 6.1% above 98: the low is a pivot
+            (f64::NAN, f64::NAN),
+            (103.0, 102.0),
+        ];
+        let highs: Vec<f64> = bars.iter().map(|b| b.0).collect();
+        let lows: Vec<f64> = bars.iter().map(|b| b.1).collect();
+
+        let pivots = zigzag_pivots(&highs, &lows, 5.0);
+        let found: Vec<(usize, f64, bool)> = pivots
+            .iter()
+            .map(|p| (p.index, p.value, p.is_high))
+            .collect();
+        // The last pivot is the extreme of the unfinished leg
+        assert_eq!(
+            found,
+            [
+                (0, 99.0, false),
+                (2, 106.0, true),
+                (5, 98.0, false),
+                (7, 104.0, true)
+            ]
+        );
+
+        let values = zigzag(&highs, &lows, 5.0);
+        assert_eq!(values.len(), bars.len());
+        let defined: Vec<(usize, f64)> = values
+            .iter()
+            .enumerate()
+            .filter(|(_, v)| v.is_finite())
+            .map(|(i, &v)| (i, v))
+            .collect();
+        assert_eq!(defined, [(0, 99.0), (2, 106.0), (5, 98.0), (7, 104.0)]);
+
+        // No swing reaches 10%
+        assert!(zigzag_pivots(&highs, &lows, 10.0).is_empty());
+    }
+
+    #[test]
+    fn test_heikin_ashi_keeps_original() {
+        let bars = [
+            Bar::new(0, 10.0, 12.0, 9.0, 11.0),
+            Bar::new(60, f64::NAN, f64::NAN, f64::NAN, f64::NAN),
+            Bar::new(120, 11.0, 20.0, 10.0, 13.0),
+        ];
+        let ha = heikin_ashi(&bars);
+        assert_eq!(ha.len(), 3);
+
+        assert!(approx(ha[0].ha.open, 10.0));
+        assert!(approx(ha[0].ha.close, 10.5));
+        assert!(ha[1].ha.close.is_nan());
+
+        // Gap bar does not advance the smoothing
+        assert!(approx(ha[2].ha.open, 10.25));
+        assert!(approx(ha[2].ha.close, 13.5));
+        assert!(approx(ha[2].ha.high, 20.0));
+        assert!(approx(ha[2].ha.low, 10.0));
+        assert_eq!(ha[2].original.high, 20.0);
+        assert_eq!(ha[2].original.open, 11.0);
+    }
+
+    #[test]
+    fn test_adx_wilder_reference() {
+        let highs = [10.0, 11.0, 12.0, 11.5, 13.0, 14.0, 13.5, 12.0, 12.5, 13.5];
+        let lows = [9.0, 9.5, 10.5, 10.0, 11.0, 12.5, 12.0, 10.5, 11.0, 12.0];
+        let closes = [9.5, 10.5, 11.5, 10.5, 12.5, 13.5, 12.5, 11.0, 12.0, 13.0];
+        let (adx, plus_di, minus_di) = adx(&highs, &lows, &closes, 3);
+
+        // Bar 3: TR sum 4.5, +DM sum 2.0, -DM sum 0.5
+        assert!(plus_di[2].is_nan());
+        assert!(approx(plus_di[3], 200.0 / 4.5));
+        assert!(approx(minus_di[3], 50.0 / 4.5));
+
+        assert!(adx[4].is_nan());
+        assert!(approx(adx[5], 74.88721804511277));
+        assert!(approx(plus_di[9], 39.22566059064575));
+        assert!(approx(minus_di[9], 17.69111205312986));
+        assert!(approx(adx[9], 35.863398001077194));
+    }
+
+    #[test]
+    fn test_sort_bars_merges_duplicates() {
+        let bar = |ts: i64, open: f64, high: f64, low: f64, close: f64| Bar {
+            timestamp: ts,
+            open,
+            high,
+            low,
+            close,
+            volume: ts as f64,
+        };
+        // Out of order, with 200 delivered twice
+        let feed = [
+            bar(100, 1.0, 2.0, 0.5, 1.5),
+            bar(300, 3.0, 4.0, 2.5, 3.5),
+            bar(200, 2.0, 5.0, 1.8, 2.2),
+            bar(200, 2.1, 2.6, 1.0, 2.4),
+        ];
+        assert!(!bars_are_sorted(&feed));
+
+        let last = sort_bars(&feed, DuplicateBarMerge::KeepLast);
+        let ts: Vec<i64> = last.iter().map(|b| b.timestamp).collect();
+        assert_eq!(ts, [100, 200, 300]);
+        assert!(bars_are_sorted(&last));
+        assert_eq!((last[1].open, last[1].high, last[1].close), (2.1, 2.6, 2.4));
+
+        // Later close, widest high/low, first open
+        let combined = sort_bars(&feed, DuplicateBarMerge::Combine);
+        assert_eq!(combined.len(), 3);
+        let merged = combined[1];
+        assert_eq!(merged.open, 2.0);
+        assert_eq!(merged.high, 5.0);
+        assert_eq!(merged.low, 1.0);
+        assert_eq!(merged.close, 2.4);
+        assert_eq!(merged.volume, 400.0);
+        assert_eq!(combined[2].close, 3.5);
+
+        // Already clean input comes back unchanged
+        assert_eq!(sort_bars(&last, DuplicateBarMerge::Combine).len(), 3);
+        assert!(bars_are_sorted(&[]));
+    }
+
+    #[test]
+    fn test_timestamp_to_bar_extrapolates_past_the_ends() {
+        // Hourly bars with one overnight gap: the median stays an hour
+        let bars: Vec<Bar> = [0, 3600, 7200, 36000, 39600]
+            .iter()
+            .map(|&ts| Bar::new(ts, 1.0, 1.0, 1.0, 1.0))
+            .collect();
+        assert_eq!(median_bar_interval(&bars), Some(3600));
+
+        assert_eq!(timestamp_to_bar(&bars, 3600), Some(1.0));
+        assert_eq!(timestamp_to_bar(&bars, 5400), Some(1.5));
+        // Ten hours after the last bar, and one before the first
+        assert_eq!(timestamp_to_bar(&bars, 39600 + 36000), Some(14.0));
+        assert_eq!(timestamp_to_bar(&bars, -3600), Some(-1.0));
+
+        assert_eq!(timestamp_to_bar(&bars[..1], 0), Some(0.0));
+        assert_eq!(timestamp_to_bar(&bars[..1], 60), None);
+        assert_eq!(timestamp_to_bar(&[], 0), None);
+    }
+
+    #[test]
+    fn test_resample_15m_to_4h_partial_edges() {
+        // 96 bars of 15m starting 01:30 UTC: 10 bars before 04:00, five full
+        // 4h buckets, and 6 bars into the next day
+        let day = 1_700_006_400; // 2023-11-15 00:00 UTC
+        let bars: Vec<Bar> = (0..96)
+            .map(|i| {
+                let base = 100.0 + i as f64;
+                Bar {
+                    timestamp: day + 5400 + i * 900,
+                    open: base,
+                    high: base + 2.0,
+                    low: base - 1.0,
+                    close: base + 0.5,
+                    volume: 10.0,
+                }
+            })
+            .collect();
+
+        let htf = resample(&bars, 4 * 3600);
+        let sizes: Vec<usize> = htf.iter().map(|(_, r)| r.len()).collect();
+        assert_eq!(sizes, [10, 16, 16, 16, 16, 16, 6]);
+
+        let (first, range) = &htf[0];
+        assert_eq!(first.timestamp, day);
+        assert_eq!(*range, 0..10);
+        assert_eq!(first.open, 100.0);
+        assert_eq!(first.close, 109.5);
+        assert_eq!(first.high, 111.0);
+        assert_eq!(first.low, 99.0);
+        assert_eq!(first.volume, 100.0);
+
+        assert_eq!(htf[1].0.timestamp, day + 4 * 3600);
+        assert_eq!(htf[1].1, 10..26);
+        assert_eq!(htf[6].0.timestamp, day + 24 * 3600);
+        assert!(resample(&bars, 0).is_empty());
+    }
+}
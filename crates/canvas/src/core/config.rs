@@ -274,6 +274,8 @@ pub struct PriceScaleConfig {
     pub padding: f64,
     /// Tick mark length
     pub tick_length: f64,
+    /// Instrument tick size; snaps grid ticks and sets label precision
+    pub tick_size: Option<f64>,
 }
 
 impl Default for PriceScaleConfig {
@@ -291,6 +293,7 @@ impl Default for PriceScaleConfig {
             width: 80.0,
             padding: 8.0,
             tick_length: 4.0,
+            tick_size: None,
         }
     }
 }
@@ -74,6 +74,184 @@ pub fn catmull_rom_spline(points: &[(f64, f64)], segments_per_curve: usize) -> V
     result
 }
 
+/// Calculate distance from point to line segment
+pub fn point_to_line_distance(px: f64, py: f64, x1: f64, y1: f64, x2: f64, y2: f64) -> f64 {
+    let dx = x2 - x1;
+    let dy = y2 - y1;
+    let len_sq = dx * dx + dy * dy;
+
+    if len_sq < 0.0001 {
+        // Line is a point
+        let ddx = px - x1;
+        let ddy = py - y1;
+        return (ddx * ddx + ddy * ddy).sqrt();
+    }
+
+    // Project point onto line, clamping to segment
+    let t = ((px - x1) * dx + (py - y1) * dy) / len_sq;
+    let t = t.clamp(0.0, 1.0);
+
+    let proj_x = x1 + t * dx;
+    let proj_y = y1 + t * dy;
+
+    let ddx = px - proj_x;
+    let ddy = py - proj_y;
+    (ddx * ddx + ddy * ddy).sqrt()
+}
+
+/// M4 downsampling of a dense series into pixel-wide buckets
+///
+/// `points` are `(x, y)` sorted by x. The span `[x_min, x_max]` is cut into
+/// `buckets` equal columns and each column keeps its first, lowest, highest
+/// and last point, in their original order. That is at most four points per
+/// column, and a line through them draws the same pixels as one through
+/// every point: the extremes set the column's vertical run and the first
+/// and last points where it joins its neighbours.
+///
+/// Points outside the span and non-finite values are dropped. Input that
+/// already fits in four points per column is returned as is, less those.
+///
+/// # Examples
+///
+/// ```
+/// use zengeld_canvas::downsample_m4;
+///
+/// let points: Vec<(f64, f64)> = (0..1000).map(|i| (i as f64, (i % 7) as f64)).collect();
+/// let reduced = downsample_m4(&points, 0.0, 1000.0, 10);
+/// assert!(reduced.len() <= 40);
+/// ```
+pub fn downsample_m4(
+    points: &[(f64, f64)],
+    x_min: f64,
+    x_max: f64,
+    buckets: usize,
+) -> Vec<(f64, f64)> {
+    let visible = points
+        .iter()
+        .copied()
+        .enumerate()
+        .filter(|&(_, (x, y))| x >= x_min && x <= x_max && y.is_finite());
+    let span = x_max - x_min;
+    if buckets == 0 || span <= 0.0 {
+        return Vec::new();
+    }
+    if points.len() <= buckets * 4 {
+        return visible.map(|(_, point)| point).collect();
+    }
+
+    let mut result = Vec::with_capacity(buckets * 4);
+    // Column being filled, with its first, lowest, highest and last point
+    // so far, each tagged with its input index
+    let mut column = None;
+    let mut kept = [(0, (0.0, 0.0)); 4];
+    let flush = |result: &mut Vec<(f64, f64)>, mut kept: [(usize, (f64, f64)); 4]| {
+        kept.sort_by_key(|&(i, _)| i);
+        let mut last = None;
+        for (i, point) in kept {
+            if last != Some(i) {
+                result.push(point);
+                last = Some(i);
+            }
+        }
+    };
+
+    for (i, point) in visible {
+        let bucket = (((point.0 - x_min) * buckets as f64 / span) as usize).min(buckets - 1);
+        if column == Some(bucket) {
+            if point.1 < kept[1].1.1 {
+                kept[1] = (i, point);
+            }
+            if point.1 > kept[2].1.1 {
+                kept[2] = (i, point);
+            }
+            kept[3] = (i, point);
+        } else {
+            if column.is_some() {
+                flush(&mut result, kept);
+            }
+            column = Some(bucket);
+            kept = [(i, point); 4];
+        }
+    }
+    if column.is_some() {
+        flush(&mut result, kept);
+    }
+    result
+}
+
+/// Least-squares line through a set of points, with the spread of its
+/// residuals
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LinearFit {
+    pub slope: f64,
+    pub intercept: f64,
+    /// Standard deviation of the residuals
+    pub std_dev: f64,
+    /// Standard error of the regression (residuals over n - 2 degrees of
+    /// freedom); zero for two points
+    pub std_error: f64,
+}
+
+impl LinearFit {
+    /// Value of the fitted line at `x`
+    pub fn at(&self, x: f64) -> f64 {
+        self.slope * x + self.intercept
+    }
+}
+
+/// Ordinary least-squares fit of `y` on `x`
+///
+/// Non-finite points are skipped. Returns `None` with fewer than two points
+/// left or when they all share one x.
+///
+/// # Examples
+///
+/// ```
+/// use zengeld_canvas::linear_regression;
+///
+/// let fit = linear_regression(&[(0.0, 1.0), (1.0, 3.0), (2.0, 5.0)]).unwrap();
+/// assert_eq!((fit.slope, fit.intercept), (2.0, 1.0));
+/// assert_eq!(fit.std_dev, 0.0);
+/// ```
+pub fn linear_regression(points: &[(f64, f64)]) -> Option<LinearFit> {
+    let points: Vec<(f64, f64)> = points
+        .iter()
+        .copied()
+        .filter(|(x, y)| x.is_finite() && y.is_finite())
+        .collect();
+    if points.len() < 2 {
+        return None;
+    }
+
+    let n = points.len() as f64;
+    let mean_x = points.iter().map(|p| p.0).sum::<f64>() / n;
+    let mean_y = points.iter().map(|p| p.1).sum::<f64>() / n;
+    let (sxx, sxy) = points.iter().fold((0.0, 0.0), |(sxx, sxy), &(x, y)| {
+        let dx = x - mean_x;
+        (sxx + dx * dx, sxy + dx * (y - mean_y))
+    });
+    if sxx == 0.0 {
+        return None;
+    }
+
+    let slope = sxy / sxx;
+    let intercept = mean_y - slope * mean_x;
+    let residuals: f64 = points
+        .iter()
+        .map(|&(x, y)| (y - (slope * x + intercept)).powi(2))
+        .sum();
+    Some(LinearFit {
+        slope,
+        intercept,
+        std_dev: (residuals / n).sqrt(),
+        std_error: if points.len() > 2 {
+            (residuals / (n - 2.0)).sqrt()
+        } else {
+            0.0
+        },
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -129,6 +307,80 @@ mod tests {
         assert!((result[10].1 - 2.0).abs() < 1e-10);
     }
 
+    #[test]
+    fn test_point_to_line_distance() {
+        // Perpendicular distance to a horizontal segment
+        assert!((point_to_line_distance(5.0, 3.0, 0.0, 0.0, 10.0, 0.0) - 3.0).abs() < 1e-10);
+        // Beyond the segment end clamps to the endpoint
+        assert!((point_to_line_distance(13.0, 4.0, 0.0, 0.0, 10.0, 0.0) - 5.0).abs() < 1e-10);
+        // Degenerate segment behaves like a point
+        assert!((point_to_line_distance(3.0, 4.0, 0.0, 0.0, 0.0, 0.0) - 5.0).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_downsample_m4_keeps_column_extremes_and_ends() {
+        // 100k points over 100 columns, a sawtooth with spikes
+        let points: Vec<(f64, f64)> = (0..100_000)
+            .map(|i| {
+                let y = match i {
+                    12_345 => 500.0,
+                    67_890 => -500.0,
+                    _ => (i % 97) as f64,
+                };
+                (i as f64 / 1000.0, y)
+            })
+            .collect();
+        let reduced = downsample_m4(&points, 0.0, 100.0, 100);
+        assert!(reduced.len() <= 400);
+        assert!(reduced.windows(2).all(|w| w[0].0 <= w[1].0));
+
+        for column in 0..100 {
+            let in_column = |&&(x, _): &&(f64, f64)| x >= column as f64 && x < column as f64 + 1.0;
+            let source: Vec<(f64, f64)> = points.iter().filter(in_column).copied().collect();
+            let kept: Vec<(f64, f64)> = reduced.iter().filter(in_column).copied().collect();
+            assert!(kept.len() <= 4, "column {column}");
+            let min = |v: &[(f64, f64)]| v.iter().map(|p| p.1).fold(f64::INFINITY, f64::min);
+            let max = |v: &[(f64, f64)]| v.iter().map(|p| p.1).fold(f64::NEG_INFINITY, f64::max);
+            assert_eq!(min(&kept), min(&source), "column {column}");
+            assert_eq!(max(&kept), max(&source), "column {column}");
+            assert_eq!(kept.first(), source.first(), "column {column}");
+            assert_eq!(kept.last(), source.last(), "column {column}");
+        }
+        assert!(reduced.contains(&(12.345, 500.0)));
+        assert!(reduced.contains(&(67.89, -500.0)));
+    }
+
+    #[test]
+    fn test_downsample_m4_sparse_and_out_of_range() {
+        let points = [(0.0, 1.0), (1.0, f64::NAN), (2.0, 3.0), (9.0, 4.0)];
+        assert_eq!(
+            downsample_m4(&points, 0.0, 5.0, 10),
+            vec![(0.0, 1.0), (2.0, 3.0)]
+        );
+        assert!(downsample_m4(&points, 5.0, 5.0, 10).is_empty());
+    }
+
+    #[test]
+    fn test_linear_regression_residual_spread() {
+        let exact: Vec<(f64, f64)> = (0..20).map(|i| (i as f64, 5.0 - 0.5 * i as f64)).collect();
+        let fit = linear_regression(&exact).unwrap();
+        assert!((fit.slope + 0.5).abs() < 1e-12 && (fit.intercept - 5.0).abs() < 1e-12);
+        assert!(fit.std_dev < 1e-12 && fit.std_error < 1e-12);
+
+        // Alternating +-1 around y = x: the line is unchanged, residuals are 1
+        let noisy: Vec<(f64, f64)> = (0..20)
+            .map(|i| (i as f64, i as f64 + if i % 2 == 0 { 1.0 } else { -1.0 }))
+            .chain([(20.0, f64::NAN)])
+            .collect();
+        let fit = linear_regression(&noisy).unwrap();
+        assert!((fit.at(10.0) - 10.0).abs() < 0.2);
+        assert!((fit.std_dev - 1.0).abs() < 0.01);
+        assert!(fit.std_error > fit.std_dev);
+
+        assert_eq!(linear_regression(&[(1.0, 1.0)]), None);
+        assert_eq!(linear_regression(&[(1.0, 1.0), (1.0, 2.0)]), None);
+    }
+
     #[test]
     fn test_smoothness() {
         let points = vec![(0.0, 0.0), (1.0, 1.0), (2.0, 0.5), (3.0, 1.5)];
@@ -8,19 +8,27 @@
 //! - `ChartConfig` - Global configuration system
 //! - Layout constants (scale dimensions, toolbar sizes)
 //! - Utility functions (crisp rendering, color parsing)
+//! - `compute` - Indicator math (SMA, EMA, Bollinger, RSI, MACD), resampling, Heikin Ashi, Renko/line-break bricks and bar sorting
+//! - `testing` - Deterministic sample bars for examples and doctests
 
 mod color;
+pub mod compute;
 pub mod config;
 mod format;
 mod math;
+pub mod testing;
 pub mod theme;
 mod types;
 
 // Re-export types
 pub use types::{
+    AUTO_MILLIS_THRESHOLD,
     BOTTOM_SIDEBAR_HEIGHT,
     BOTTOM_TOOLBAR_HEIGHT,
     Bar,
+    ChartDensity,
+    ChartSizing,
+    DuplicateBarMerge,
     // Sidebar & toolbar constants
     LEFT_SIDEBAR_WIDTH,
     LEFT_TOOLBAR_WIDTH,
@@ -36,6 +44,7 @@ pub use types::{
     PRICE_SCALE_PADDING_OUTER,
     PRICE_SCALE_TICK_LENGTH,
     PRICE_SCALE_WIDTH,
+    PRICE_SCALE_WIDTH_STEP,
     RIGHT_SIDEBAR_WIDTH,
     RIGHT_TOOLBAR_WIDTH,
     STATUS_BAR_HEIGHT,
@@ -43,14 +52,20 @@ pub use types::{
     TIME_SCALE_HEIGHT,
     TOP_TOOLBAR_HEIGHT,
     Theme,
+    TimeSpacing,
+    TimestampUnit,
     crisp,
     crisp_rect,
 };
 
 // Re-export utility functions
-pub use color::parse_css_color;
+#[cfg(feature = "render")]
+pub(crate) use color::parse_hex;
+pub use color::{CssColorError, parse_css_color};
 pub use format::format_indicator_value;
-pub use math::catmull_rom_spline;
+pub use math::{
+    LinearFit, catmull_rom_spline, downsample_m4, linear_regression, point_to_line_distance,
+};
 
 // Re-export configuration system
 pub use config::{
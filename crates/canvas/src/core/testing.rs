@@ -0,0 +1,90 @@
+//! Deterministic sample data for examples, doctests and benchmarks
+//!
+//! The generator is a fixed LCG, so the same `n` always yields the same
+//! bars on every platform - handy for snapshot-style comparisons.
+
+use super::Bar;
+
+/// First bar timestamp (seconds), bars are spaced one day apart
+const START_TIME: i64 = 1_700_000_000;
+
+/// Generate `n` daily OHLCV bars: a slow sine trend plus deterministic noise
+///
+/// ```
+/// use zengeld_canvas::core::testing::sample_bars;
+///
+/// let bars = sample_bars(100);
+/// assert_eq!(bars.len(), 100);
+/// assert!(bars.iter().all(|b| b.low <= b.open.min(b.close)));
+/// assert_eq!(bars[42].close, sample_bars(100)[42].close);
+/// ```
+pub fn sample_bars(n: usize) -> Vec<Bar> {
+    let mut bars = Vec::with_capacity(n);
+    let mut price = 100.0;
+    let base_volume = 1_000_000.0;
+
+    for i in 0..n {
+        let seed = i as u64;
+        let trend = ((i as f64 / n as f64) * std::f64::consts::PI * 2.0).sin() * 10.0;
+        let noise = pseudo_random(seed) * 4.0 - 2.0;
+
+        price += trend * 0.1 + noise;
+        price = price.max(50.0);
+
+        let volatility = 1.0 + pseudo_random(seed + 1000) * 2.0;
+        let high = price + volatility;
+        let low = price - volatility;
+
+        let open = if pseudo_random(seed + 2000) > 0.5 {
+            low + pseudo_random(seed + 3000) * (high - low)
+        } else {
+            high - pseudo_random(seed + 4000) * (high - low)
+        };
+        let close = if pseudo_random(seed + 5000) > 0.5 {
+            low + pseudo_random(seed + 6000) * (high - low)
+        } else {
+            high - pseudo_random(seed + 7000) * (high - low)
+        };
+
+        bars.push(Bar {
+            timestamp: START_TIME + i as i64 * 86400,
+            open,
+            high,
+            low,
+            close,
+            volume: base_volume * (0.5 + pseudo_random(seed + 8000) * 1.5),
+        });
+    }
+
+    bars
+}
+
+/// Deterministic value in `[0, 1]` for a seed
+fn pseudo_random(seed: u64) -> f64 {
+    let x = seed.wrapping_mul(1103515245).wrapping_add(12345);
+    let x = x.wrapping_mul(1103515245).wrapping_add(12345);
+    ((x >> 16) & 0x7fff) as f64 / 32767.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sample_bars_are_valid_and_deterministic() {
+        let bars = sample_bars(250);
+        assert_eq!(bars.len(), 250);
+        let again = sample_bars(250);
+        assert!(bars.iter().zip(&again).all(|(a, b)| {
+            a.timestamp == b.timestamp && a.close == b.close && a.volume == b.volume
+        }));
+
+        for (i, bar) in bars.iter().enumerate() {
+            assert!(bar.high >= bar.open.max(bar.close));
+            assert!(bar.low <= bar.open.min(bar.close));
+            assert!(bar.volume > 0.0);
+            assert_eq!(bar.timestamp, START_TIME + i as i64 * 86400);
+        }
+        assert!(sample_bars(0).is_empty());
+    }
+}
@@ -252,6 +252,8 @@ pub struct UIEffects {
     pub shadow_dropdown: &'static str,
     pub shadow_floating: &'static str,
     pub hover_scale: f64,
+    /// Blur radius in pixels of the glow around series and drawings (0 = none)
+    pub glow: f64,
 }
 
 impl Default for UITheme {
@@ -410,6 +412,7 @@ impl UITheme {
                 shadow_dropdown: "0 8px 24px rgba(0,0,0,0.4)",
                 shadow_floating: "0 4px 12px rgba(0,0,0,0.3)",
                 hover_scale: 0.97,
+                glow: 0.0,
             },
         }
     }
@@ -563,6 +566,7 @@ impl UITheme {
                 shadow_dropdown: "0 8px 24px rgba(0,0,0,0.15)",
                 shadow_floating: "0 4px 12px rgba(0,0,0,0.1)",
                 hover_scale: 0.97,
+                glow: 0.0,
             },
         }
     }
@@ -716,6 +720,7 @@ impl UITheme {
                 shadow_dropdown: "none",
                 shadow_floating: "none",
                 hover_scale: 1.0,
+                glow: 0.0,
             },
         }
     }
@@ -869,6 +874,7 @@ impl UITheme {
                 shadow_dropdown: "0 0 20px rgba(233,69,96,0.3)",
                 shadow_floating: "0 0 15px rgba(233,69,96,0.2)",
                 hover_scale: 1.02,
+                glow: 3.0,
             },
         }
     }
@@ -930,6 +936,9 @@ impl UITheme {
 /// Runtime-modifiable theme with owned String values
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct RuntimeTheme {
+    /// Version of the JSON layout; files saved before it existed are 1
+    #[serde(default = "legacy_schema_version")]
+    pub schema_version: u32,
     pub name: String,
     pub colors: RuntimeUIColors,
     pub chart: RuntimeChartColors,
@@ -939,6 +948,10 @@ pub struct RuntimeTheme {
     pub effects: RuntimeEffects,
 }
 
+fn legacy_schema_version() -> u32 {
+    1
+}
+
 /// UI element colors (owned)
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct RuntimeUIColors {
@@ -1108,6 +1121,8 @@ pub struct RuntimeEffects {
     pub shadow_dropdown: String,
     pub shadow_floating: String,
     pub hover_scale: f64,
+    #[serde(default)]
+    pub glow: f64,
 }
 
 // =============================================================================
@@ -1117,6 +1132,7 @@ pub struct RuntimeEffects {
 impl From<&UITheme> for RuntimeTheme {
     fn from(theme: &UITheme) -> Self {
         Self {
+            schema_version: RuntimeTheme::CURRENT_SCHEMA_VERSION,
             name: theme.name.to_string(),
             colors: RuntimeUIColors {
                 toolbar_bg: theme.colors.toolbar_bg.to_string(),
@@ -1238,6 +1254,7 @@ impl From<&UITheme> for RuntimeTheme {
                 shadow_dropdown: theme.effects.shadow_dropdown.to_string(),
                 shadow_floating: theme.effects.shadow_floating.to_string(),
                 hover_scale: theme.effects.hover_scale,
+                glow: theme.effects.glow,
             },
         }
     }
@@ -1247,6 +1264,12 @@ impl RuntimeTheme {
     /// Available preset names
     pub const PRESETS: &'static [&'static str] = &["dark", "light", "high_contrast", "cyberpunk"];
 
+    /// Schema version written by [`Self::to_json`]
+    ///
+    /// - 1: unversioned files
+    /// - 2: adds `schema_version`
+    pub const CURRENT_SCHEMA_VERSION: u32 = 2;
+
     /// Create from a preset name
     pub fn from_preset(name: &str) -> Self {
         match name {
@@ -1290,9 +1313,26 @@ impl RuntimeTheme {
         serde_json::to_string_pretty(self).unwrap_or_default()
     }
 
-    /// Deserialize from JSON string
+    /// Deserialize from JSON string, migrating older schema versions
+    ///
+    /// Fields missing from the file, e.g. ones added after it was saved,
+    /// take their value from the preset the theme is named after (dark when
+    /// the name is not a preset), and unknown fields are ignored. The result
+    /// is at [`Self::CURRENT_SCHEMA_VERSION`]. Returns `None` for malformed
+    /// JSON or fields of the wrong type.
     pub fn from_json(json: &str) -> Option<Self> {
-        serde_json::from_str(json).ok()
+        let saved: serde_json::Value = serde_json::from_str(json).ok()?;
+        let preset = saved
+            .get("name")
+            .and_then(serde_json::Value::as_str)
+            .map(|name| name.to_lowercase().replace(' ', "_"))
+            .unwrap_or_default();
+        let mut merged = serde_json::to_value(Self::from_preset(&preset)).ok()?;
+        merge_json(&mut merged, saved);
+
+        let mut theme: Self = serde_json::from_value(merged).ok()?;
+        theme.schema_version = Self::CURRENT_SCHEMA_VERSION;
+        Some(theme)
     }
 
     // === Helper methods ===
@@ -1339,6 +1379,23 @@ impl RuntimeTheme {
     }
 }
 
+/// Overwrite `base` with `patch`, recursing into objects present in both
+fn merge_json(base: &mut serde_json::Value, patch: serde_json::Value) {
+    match (base, patch) {
+        (serde_json::Value::Object(base), serde_json::Value::Object(patch)) => {
+            for (key, value) in patch {
+                match base.get_mut(&key) {
+                    Some(slot) => merge_json(slot, value),
+                    None => {
+                        base.insert(key, value);
+                    }
+                }
+            }
+        }
+        (base, patch) => *base = patch,
+    }
+}
+
 impl Default for RuntimeTheme {
     fn default() -> Self {
         Self::dark()
@@ -1374,6 +1431,37 @@ mod tests {
         assert_eq!(theme.colors.toolbar_bg, restored.colors.toolbar_bg);
     }
 
+    #[test]
+    fn test_json_writes_and_migrates_schema_version() {
+        let json = RuntimeTheme::light().to_json();
+        assert!(json.starts_with(r#"{"schema_version":2,"#));
+
+        // A v1 file: no version, and missing a field added since
+        let mut saved: serde_json::Value = serde_json::from_str(&json).unwrap();
+        let saved_obj = saved.as_object_mut().unwrap();
+        saved_obj.remove("schema_version");
+        saved_obj["chart"]
+            .as_object_mut()
+            .unwrap()
+            .remove("crosshair_line");
+        saved_obj["colors"]["accent"] = "#123456".into();
+
+        let theme = RuntimeTheme::from_json(&saved.to_string()).unwrap();
+        assert_eq!(theme.schema_version, RuntimeTheme::CURRENT_SCHEMA_VERSION);
+        assert_eq!(theme.colors.accent, "#123456");
+        assert_eq!(
+            theme.chart.crosshair_line,
+            RuntimeTheme::light().chart.crosshair_line
+        );
+        assert_ne!(
+            theme.chart.crosshair_line,
+            RuntimeTheme::dark().chart.crosshair_line
+        );
+
+        assert!(RuntimeTheme::from_json("not json").is_none());
+        assert!(RuntimeTheme::from_json(r#"{"name": 5}"#).is_none());
+    }
+
     #[test]
     fn test_color_modification() {
         let mut theme = RuntimeTheme::dark();
@@ -3,6 +3,9 @@
 //! This module contains all fundamental data structures, color constants,
 //! layout constants, and helper functions used throughout the chart library.
 
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+
 // =============================================================================
 // Chart Theme - Color Palette
 // =============================================================================
@@ -87,13 +90,13 @@ impl Theme {
 // Layout Constants
 // =============================================================================
 
-/// Height of the time scale area in pixels (CONSTANT)
+/// Height of the time scale area in pixels at the default density
 pub const TIME_SCALE_HEIGHT: f64 = 30.0;
 
 /// Font size for time scale labels
 pub const TIME_SCALE_FONT_SIZE: f64 = 12.0;
 
-/// Fixed width for price scale (CONSTANT)
+/// Width of the price scale at the default density
 pub const PRICE_SCALE_WIDTH: f64 = 70.0;
 
 /// Max font size for price scale labels (when few digits)
@@ -123,9 +126,108 @@ pub const PRICE_SCALE_PADDING_OUTER: f64 = 5.0;
 /// Constant for label positioning
 pub const PRICE_SCALE_LABEL_OFFSET: f64 = 5.0;
 
-/// Minimum width for price scale (legacy, use PRICE_SCALE_WIDTH)
+/// Narrowest price scale column fitted to its labels
 pub const PRICE_SCALE_MIN_WIDTH: f64 = 50.0;
 
+/// Fitted price scale widths are rounded up to a multiple of this, so
+/// labels growing by a digit rarely move the plot edge
+pub const PRICE_SCALE_WIDTH_STEP: f64 = 10.0;
+
+/// Size class of a chart's scales, grid and glyphs
+///
+/// [`ChartDensity::Normal`] keeps [`PRICE_SCALE_WIDTH`] and
+/// [`TIME_SCALE_HEIGHT`]; the others shrink everything for thumbnails or
+/// grow it for large displays.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChartDensity {
+    /// Small fonts and narrow scales, for thumbnails and dashboards
+    Compact,
+    #[default]
+    Normal,
+    /// Large fonts and wide scales, for 4K and wall displays
+    Comfortable,
+}
+
+/// Scale, grid and glyph sizes of a [`ChartDensity`], in CSS pixels
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ChartSizing {
+    /// Width of the price scale column
+    pub price_scale_width: f64,
+    /// Height of the time scale strip
+    pub time_scale_height: f64,
+    /// Largest price scale label font; time labels are a pixel smaller
+    pub scale_font_size: f64,
+    /// Length of the tick marks on both scales
+    pub tick_length: f64,
+    /// Target distance between grid lines; their count follows the pane size
+    pub grid_spacing: f64,
+    /// Minimum distance between time scale labels
+    pub min_time_label_spacing: f64,
+    /// Glyph size of a signal of size 1.0
+    pub signal_size: f64,
+}
+
+impl ChartSizing {
+    /// Font of the time scale, countdown and signal labels
+    pub fn label_font_size(&self) -> f64 {
+        self.scale_font_size - 1.0
+    }
+
+    /// Number of grid intervals across `extent` pixels, at least two
+    pub fn grid_count(&self, extent: f64) -> usize {
+        ((extent / self.grid_spacing).round() as usize).max(2)
+    }
+}
+
+impl ChartDensity {
+    /// Sizes of this density
+    pub fn sizing(self) -> ChartSizing {
+        match self {
+            Self::Compact => ChartSizing {
+                price_scale_width: 50.0,
+                time_scale_height: 20.0,
+                scale_font_size: 9.0,
+                tick_length: 3.0,
+                grid_spacing: 50.0,
+                min_time_label_spacing: 45.0,
+                signal_size: 9.0,
+            },
+            Self::Normal => ChartSizing {
+                price_scale_width: PRICE_SCALE_WIDTH,
+                time_scale_height: TIME_SCALE_HEIGHT,
+                scale_font_size: 11.0,
+                tick_length: 4.0,
+                grid_spacing: 70.0,
+                min_time_label_spacing: 60.0,
+                signal_size: 12.0,
+            },
+            Self::Comfortable => ChartSizing {
+                price_scale_width: 100.0,
+                time_scale_height: 42.0,
+                scale_font_size: 16.0,
+                tick_length: 6.0,
+                grid_spacing: 110.0,
+                min_time_label_spacing: 90.0,
+                signal_size: 18.0,
+            },
+        }
+    }
+}
+
+impl std::str::FromStr for ChartDensity {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "compact" => Ok(Self::Compact),
+            "normal" => Ok(Self::Normal),
+            "comfortable" => Ok(Self::Comfortable),
+            _ => Err(()),
+        }
+    }
+}
+
 // =============================================================================
 // Sidebar & Toolbar Constants
 // =============================================================================
@@ -226,6 +328,135 @@ impl Bar {
     }
 }
 
+// =============================================================================
+// Timestamp Units
+// =============================================================================
+
+/// Timestamps above this magnitude are taken as milliseconds by
+/// [`TimestampUnit::Auto`] (1e12 s is ~33,000 years; 1e12 ms is 2001)
+pub const AUTO_MILLIS_THRESHOLD: i64 = 1_000_000_000_000;
+
+/// Unit of [`Bar::timestamp`] and other epoch timestamps given to a chart
+///
+/// Everything past ingest works in seconds; [`TimestampUnit::normalize_bars`]
+/// converts input in other units.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TimestampUnit {
+    /// Unix seconds
+    #[default]
+    Seconds,
+    /// Unix milliseconds
+    Milliseconds,
+    /// Milliseconds if any timestamp exceeds [`AUTO_MILLIS_THRESHOLD`],
+    /// seconds otherwise
+    Auto,
+}
+
+impl TimestampUnit {
+    /// Settle `Auto` on a concrete unit for a whole series
+    ///
+    /// An empty series leaves `Auto` as is, so single values are detected
+    /// one by one in [`Self::to_seconds`].
+    pub fn resolve(self, timestamps: impl IntoIterator<Item = i64>) -> Self {
+        if self != Self::Auto {
+            return self;
+        }
+        let mut timestamps = timestamps.into_iter().peekable();
+        if timestamps.peek().is_none() {
+            Self::Auto
+        } else if timestamps.any(|ts| ts.unsigned_abs() > AUTO_MILLIS_THRESHOLD as u64) {
+            Self::Milliseconds
+        } else {
+            Self::Seconds
+        }
+    }
+
+    /// Convert a timestamp in this unit to unix seconds, rounding down
+    pub fn to_seconds(self, ts: i64) -> i64 {
+        match self.resolve([ts]) {
+            Self::Milliseconds => ts.div_euclid(1000),
+            _ => ts,
+        }
+    }
+
+    /// Bars with timestamps in unix seconds, borrowed when already there
+    pub fn normalize_bars(self, bars: &[Bar]) -> Cow<'_, [Bar]> {
+        match self.resolve(bars.iter().map(|b| b.timestamp)) {
+            Self::Milliseconds => Cow::Owned(
+                bars.iter()
+                    .map(|b| Bar {
+                        timestamp: b.timestamp.div_euclid(1000),
+                        ..*b
+                    })
+                    .collect(),
+            ),
+            _ => Cow::Borrowed(bars),
+        }
+    }
+}
+
+/// Horizontal placement of bars
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TimeSpacing {
+    /// One slot per bar, whatever the time between bars
+    #[default]
+    Indexed,
+    /// Bars placed by timestamp in units of the median bar interval, so
+    /// weekend and holiday gaps open up; bars never get closer than one slot
+    Proportional,
+}
+
+impl std::str::FromStr for TimeSpacing {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "indexed" | "index" => Ok(Self::Indexed),
+            "proportional" | "time" => Ok(Self::Proportional),
+            _ => Err(()),
+        }
+    }
+}
+
+/// How bars sharing a timestamp are merged when bars are sorted
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DuplicateBarMerge {
+    /// The bar delivered last replaces the earlier ones
+    #[default]
+    KeepLast,
+    /// One bar spanning all of them: first open, highest high, lowest low,
+    /// the last bar's close and the summed volume
+    Combine,
+}
+
+impl std::str::FromStr for DuplicateBarMerge {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "keep_last" | "last" => Ok(Self::KeepLast),
+            "combine" => Ok(Self::Combine),
+            _ => Err(()),
+        }
+    }
+}
+
+impl std::str::FromStr for TimestampUnit {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "s" | "sec" | "seconds" => Ok(Self::Seconds),
+            "ms" | "millis" | "milliseconds" => Ok(Self::Milliseconds),
+            "auto" => Ok(Self::Auto),
+            _ => Err(()),
+        }
+    }
+}
+
 // =============================================================================
 // Pixel-Perfect Helpers
 // =============================================================================
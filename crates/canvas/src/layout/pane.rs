@@ -533,7 +533,7 @@ impl PaneManager {
         }
 
         // Calculate total separator height
-        let separator_space = self.separator_height * (self.order.len() - 1).max(0) as f64;
+        let separator_space = self.separator_height * (self.order.len() - 1) as f64;
         let available_height = self.total_height - separator_space;
 
         // Calculate total ratio
@@ -27,6 +27,28 @@
 //! └─────────────────────────────────────────────────────────────────────┘
 //! ```
 //!
+//! # Quick Start
+//!
+//! ```
+//! # #[cfg(feature = "render")] {
+//! use zengeld_canvas::api::Chart;
+//! use zengeld_canvas::core::testing::sample_bars;
+//!
+//! let bars = sample_bars(200);
+//! let svg = Chart::new(800, 500)
+//!     .bars(&bars)
+//!     .candlesticks()
+//!     .sma(20, "#2196F3")
+//!     .macd(12, 26, 9)
+//!     .render_svg();
+//! assert!(svg.contains("</svg>"));
+//! # }
+//! ```
+//!
+//! More recipes live in `crates/canvas/examples/`; run one with
+//! `cargo run --example basic_candles` and find its SVG under
+//! `target/chart_examples/`.
+//!
 //! # Modules
 //!
 //! - **core** - Foundational types (Bar, Theme) and utilities (color parsing, math)
@@ -36,6 +58,13 @@
 //! - **layout** - Chart layout (panes, multichart grids)
 //! - **render** - Rendering engine and chart element rendering
 //!
+//! # Features
+//!
+//! - `render` *(default)* - primitives, the render engine and the high-level API
+//! - `compute-only` - build with `default-features = false` to get just the
+//!   math: `Bar`, [`core::compute`], geometry helpers and price/time tick math,
+//!   without the SVG backend or rendering types
+//!
 //! # License
 //!
 //! Licensed under either of Apache License, Version 2.0 or MIT license at your option.
@@ -54,15 +83,18 @@ pub mod coords;
 pub mod model;
 
 /// Interactive primitives (drawing tools)
+#[cfg(feature = "render")]
 pub mod primitives;
 
 /// Layout system (panes, multichart)
 pub mod layout;
 
 /// Rendering engine
+#[cfg(feature = "render")]
 pub mod render;
 
 /// High-level API for chart rendering
+#[cfg(feature = "render")]
 pub mod api;
 
 // =============================================================================
@@ -71,12 +103,18 @@ pub mod api;
 
 // Core types and utilities
 pub use core::{
+    AUTO_MILLIS_THRESHOLD,
     BOTTOM_SIDEBAR_HEIGHT,
     BOTTOM_TOOLBAR_HEIGHT,
     Bar,
+    ChartDensity,
+    ChartSizing,
+    CssColorError,
+    DuplicateBarMerge,
     // Sidebar & toolbar constants
     LEFT_SIDEBAR_WIDTH,
     LEFT_TOOLBAR_WIDTH,
+    LinearFit,
     // Price scale constants
     PRICE_SCALE_BORDER_SIZE,
     PRICE_SCALE_FONT,
@@ -89,6 +127,7 @@ pub use core::{
     PRICE_SCALE_PADDING_OUTER,
     PRICE_SCALE_TICK_LENGTH,
     PRICE_SCALE_WIDTH,
+    PRICE_SCALE_WIDTH_STEP,
     RIGHT_SIDEBAR_WIDTH,
     RIGHT_TOOLBAR_WIDTH,
     STATUS_BAR_HEIGHT,
@@ -96,11 +135,16 @@ pub use core::{
     TIME_SCALE_HEIGHT,
     TOP_TOOLBAR_HEIGHT,
     Theme,
+    TimeSpacing,
+    TimestampUnit,
     catmull_rom_spline,
     crisp,
     crisp_rect,
+    downsample_m4,
     format_indicator_value,
+    linear_regression,
     parse_css_color,
+    point_to_line_distance,
 };
 
 // Configuration system
@@ -132,18 +176,20 @@ pub use core::{
 
 // Coordinate systems
 pub use coords::{
-    DAY, HOUR, MINUTE, NICE_MULTIPLIERS, PriceScale, PriceScaleMode, TickMarkWeight, TimeScale,
-    TimeTick, Viewport, format_price, format_time_by_weight, format_time_full, lwc_nice_number,
-    nice_number, nice_price_step, price_precision,
+    DAY, HOUR, MAX_PRICE_TICKS, MINUTE, NICE_MULTIPLIERS, PriceScale, PriceScaleMode,
+    TickMarkWeight, TimeScale, TimeTick, Viewport, format_countdown, format_price,
+    format_time_by_weight, format_time_full, format_time_pattern, lwc_nice_number, nice_number,
+    nice_price_step, price_precision,
 };
 
 // Model - Series
 pub use model::{
     AreaData, AreaSeriesOptions, AreaStyleOptions, BarData, BarSeriesOptions, BarStyleOptions,
-    BaselineData, BaselineSeriesOptions, BaselineStyleOptions, CandlestickData,
-    CandlestickSeriesOptions, CandlestickStyleOptions, HistogramData, HistogramSeriesOptions,
-    HistogramStyleOptions, LineData, LineSeriesOptions, LineStyleOptions, LineType,
-    PriceLineSource, SeriesData, SeriesOptions, SeriesOptionsCommon, SeriesType, SingleValue,
+    BaselineData, BaselineSeriesOptions, BaselineStyleOptions, CandleWidthMode, CandlestickData,
+    CandlestickSeriesOptions, CandlestickStyleOptions, DenseCandles, HaWickSource, HistogramData,
+    HistogramSeriesOptions, HistogramStyleOptions, LineData, LineSeriesOptions, LineStyleOptions,
+    LineType, PriceLineSource, SeriesData, SeriesOptions, SeriesOptionsCommon, SeriesType,
+    SingleValue,
 };
 
 // Model - Overlays
@@ -158,11 +204,25 @@ pub use model::{
     HorzAlign,
     Legend,
     LegendData,
+    LegendIndicator,
     LegendPosition,
+    LegendValue,
+    LogoPosition,
+    // Volume by price
+    PriceBucket,
+    // Seasonal overlay
+    SeasonalLine,
+    SeasonalPeriod,
+    Source,
     VertAlign,
     Watermark,
+    WatermarkLayout,
     WatermarkLine,
+    WatermarkLogo,
     get_compare_color,
+    point_of_control,
+    seasonal_lines,
+    volume_by_price,
 };
 
 // Model - Annotations
@@ -178,18 +238,32 @@ pub use layout::{MAIN_PANE, Pane, PaneGeometry, PaneId, PaneManager, SubPane};
 pub use layout::{CellBounds, CellId, LayoutCell, MultichartLayout};
 
 // Primitives (Drawing System)
+#[cfg(feature = "render")]
 pub use primitives::{
+    // Regression channel bands
+    BandDeviation,
+    // Composite (data-driven) primitives
+    CompositeOp,
+    CompositePoint,
+    CompositePrimitive,
+    CompositeSpec,
     // Control points (data types only, not UI)
     ControlPoint,
     ControlPointType,
+    CoordExpr,
+    DivergenceType,
     // Icons
     EmojiType,
     ExtendMode,
+    FibLabelMode,
+    FillRule,
     LineStyle as DrawingLineStyle,
     // Styling
     PrimitiveColor,
     PrimitiveData,
+    PrimitiveError,
     PrimitiveFactory,
+    PrimitiveInfo,
     PrimitiveKind,
     PrimitiveMetadata,
     PrimitiveRegistry,
@@ -215,8 +289,6 @@ pub use primitives::{
     execute_ops,
     // Point label generation
     get_point_labels,
-    // Geometry helpers
-    point_to_line_distance,
     render_crisp,
     render_crisp_rect,
     render_primitive_text,
@@ -0,0 +1,1015 @@
+//! Indicator parameters - what a built-in indicator was computed from
+//!
+//! Indicators added through the chart API (SMA, Bollinger, MACD, ...) keep
+//! their inputs, so a settings dialog can read and change them and the
+//! values can be re-derived from the bars without rebuilding the chart.
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+use super::indicators::{Indicator, OUTSIDE_BAND_VECTOR};
+use super::overlays::Source;
+use crate::core::{Bar, compute};
+
+#[cfg(feature = "render")]
+use crate::primitives::{ConfigProperty, PropertyCategory, PropertyValue, SelectOption};
+
+/// Inputs of a built-in indicator
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum IndicatorParams {
+    Sma {
+        period: usize,
+        #[serde(default)]
+        source: Source,
+    },
+    Ema {
+        period: usize,
+        #[serde(default)]
+        source: Source,
+    },
+    Bollinger {
+        period: usize,
+        multiplier: f64,
+        #[serde(default)]
+        source: Source,
+    },
+    Zigzag {
+        deviation: f64,
+    },
+    Psar {
+        step: f64,
+        max_step: f64,
+    },
+    Supertrend {
+        period: usize,
+        multiplier: f64,
+    },
+    Rsi {
+        period: usize,
+        #[serde(default)]
+        source: Source,
+    },
+    Adx {
+        period: usize,
+    },
+    Macd {
+        fast: usize,
+        slow: usize,
+        signal: usize,
+        #[serde(default)]
+        source: Source,
+    },
+    Obv,
+    AdLine,
+    Cmf {
+        period: usize,
+    },
+    Mfi {
+        period: usize,
+    },
+    Stochastic {
+        k: usize,
+        d: usize,
+    },
+    Atr {
+        period: usize,
+    },
+    Cci {
+        period: usize,
+    },
+    WilliamsR {
+        period: usize,
+    },
+    Wma {
+        period: usize,
+        #[serde(default)]
+        source: Source,
+    },
+    Hma {
+        period: usize,
+        #[serde(default)]
+        source: Source,
+    },
+    Keltner {
+        period: usize,
+        multiplier: f64,
+    },
+    Donchian {
+        period: usize,
+    },
+}
+
+impl IndicatorParams {
+    /// Display name the indicator gets for these inputs, e.g. `"SMA 20"`
+    ///
+    /// A source other than close is appended (`"SMA 20 hl2"`).
+    pub fn name(&self) -> String {
+        let name = match *self {
+            Self::Sma { period, .. } => format!("SMA {}", period),
+            Self::Ema { period, .. } => format!("EMA {}", period),
+            Self::Bollinger { period, .. } => format!("BB {}", period),
+            Self::Zigzag { deviation } => format!("ZigZag {}%", deviation),
+            Self::Psar { .. } => "Parabolic SAR".to_string(),
+            Self::Supertrend { period, multiplier } => {
+                format!("Supertrend ({}, {})", period, multiplier)
+            }
+            Self::Rsi { period, .. } => format!("RSI {}", period),
+            Self::Adx { period } => format!("ADX {}", period),
+            Self::Macd {
+                fast, slow, signal, ..
+            } => format!("MACD ({},{},{})", fast, slow, signal),
+            Self::Obv => "OBV".to_string(),
+            Self::AdLine => "A/D Line".to_string(),
+            Self::Cmf { period } => format!("CMF {}", period),
+            Self::Mfi { period } => format!("MFI {}", period),
+            Self::Stochastic { k, d } => format!("Stoch ({},{})", k, d),
+            Self::Atr { period } => format!("ATR {}", period),
+            Self::Cci { period } => format!("CCI {}", period),
+            Self::WilliamsR { period } => format!("Williams %R {}", period),
+            Self::Wma { period, .. } => format!("WMA {}", period),
+            Self::Hma { period, .. } => format!("HMA {}", period),
+            Self::Keltner { period, .. } => format!("Keltner {}", period),
+            Self::Donchian { period } => format!("Donchian {}", period),
+        };
+        match self.source() {
+            Some(source) if source != Source::Close => format!("{} {}", name, source.as_str()),
+            _ => name,
+        }
+    }
+
+    /// Price the indicator is computed on, for those that take one
+    pub fn source(&self) -> Option<Source> {
+        match *self {
+            Self::Sma { source, .. }
+            | Self::Ema { source, .. }
+            | Self::Bollinger { source, .. }
+            | Self::Rsi { source, .. }
+            | Self::Macd { source, .. }
+            | Self::Wma { source, .. }
+            | Self::Hma { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+
+    /// Numeric inputs by name, in dialog order
+    pub fn inputs(&self) -> Vec<(&'static str, f64)> {
+        match *self {
+            Self::Sma { period, .. }
+            | Self::Ema { period, .. }
+            | Self::Rsi { period, .. }
+            | Self::Adx { period }
+            | Self::Cmf { period }
+            | Self::Mfi { period }
+            | Self::Atr { period }
+            | Self::Cci { period }
+            | Self::WilliamsR { period }
+            | Self::Wma { period, .. }
+            | Self::Hma { period, .. }
+            | Self::Donchian { period } => vec![("period", period as f64)],
+            Self::Bollinger {
+                period, multiplier, ..
+            }
+            | Self::Supertrend { period, multiplier }
+            | Self::Keltner { period, multiplier } => {
+                vec![("period", period as f64), ("multiplier", multiplier)]
+            }
+            Self::Zigzag { deviation } => vec![("deviation", deviation)],
+            Self::Psar { step, max_step } => vec![("step", step), ("max_step", max_step)],
+            Self::Macd {
+                fast, slow, signal, ..
+            } => vec![
+                ("fast", fast as f64),
+                ("slow", slow as f64),
+                ("signal", signal as f64),
+            ],
+            Self::Stochastic { k, d } => vec![("k", k as f64), ("d", d as f64)],
+            Self::Obv | Self::AdLine => Vec::new(),
+        }
+    }
+
+    /// Set the numeric input `name`; false if these params have no such
+    /// input. Counts (periods) take the integer part of `value`.
+    pub fn set_input(&mut self, name: &str, value: f64) -> bool {
+        let count = value as usize;
+        match (self, name) {
+            (
+                Self::Sma { period, .. }
+                | Self::Ema { period, .. }
+                | Self::Rsi { period, .. }
+                | Self::Adx { period }
+                | Self::Cmf { period }
+                | Self::Mfi { period }
+                | Self::Atr { period }
+                | Self::Cci { period }
+                | Self::WilliamsR { period }
+                | Self::Wma { period, .. }
+                | Self::Hma { period, .. }
+                | Self::Donchian { period }
+                | Self::Bollinger { period, .. }
+                | Self::Supertrend { period, .. }
+                | Self::Keltner { period, .. },
+                "period",
+            ) => *period = count,
+            (
+                Self::Bollinger { multiplier, .. }
+                | Self::Supertrend { multiplier, .. }
+                | Self::Keltner { multiplier, .. },
+                "multiplier",
+            ) => *multiplier = value,
+            (Self::Stochastic { k, .. }, "k") => *k = count,
+            (Self::Stochastic { d, .. }, "d") => *d = count,
+            (Self::Zigzag { deviation }, "deviation") => *deviation = value,
+            (Self::Psar { step, .. }, "step") => *step = value,
+            (Self::Psar { max_step, .. }, "max_step") => *max_step = value,
+            (Self::Macd { fast, .. }, "fast") => *fast = count,
+            (Self::Macd { slow, .. }, "slow") => *slow = count,
+            (Self::Macd { signal, .. }, "signal") => *signal = count,
+            _ => return false,
+        }
+        true
+    }
+
+    /// Whether the input `name` is a whole number of bars
+    pub fn is_count(name: &str) -> bool {
+        matches!(name, "period" | "fast" | "slow" | "signal" | "k" | "d")
+    }
+
+    /// Set the price source; false if these params take none
+    pub fn set_source(&mut self, value: Source) -> bool {
+        match self {
+            Self::Sma { source, .. }
+            | Self::Ema { source, .. }
+            | Self::Bollinger { source, .. }
+            | Self::Rsi { source, .. }
+            | Self::Macd { source, .. }
+            | Self::Wma { source, .. }
+            | Self::Hma { source, .. } => *source = value,
+            _ => return false,
+        }
+        true
+    }
+}
+
+impl FromStr for IndicatorParams {
+    type Err = ();
+
+    /// Standard inputs for an indicator kind, e.g. `"rsi"` is RSI 14 on close
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let source = Source::Close;
+        match s.to_lowercase().as_str() {
+            "sma" => Ok(Self::Sma { period: 20, source }),
+            "ema" => Ok(Self::Ema { period: 20, source }),
+            "bollinger" | "bb" => Ok(Self::Bollinger {
+                period: 20,
+                multiplier: 2.0,
+                source,
+            }),
+            "zigzag" => Ok(Self::Zigzag { deviation: 5.0 }),
+            "psar" => Ok(Self::Psar {
+                step: 0.02,
+                max_step: 0.2,
+            }),
+            "supertrend" => Ok(Self::Supertrend {
+                period: 10,
+                multiplier: 3.0,
+            }),
+            "rsi" => Ok(Self::Rsi { period: 14, source }),
+            "adx" => Ok(Self::Adx { period: 14 }),
+            "macd" => Ok(Self::Macd {
+                fast: 12,
+                slow: 26,
+                signal: 9,
+                source,
+            }),
+            "obv" => Ok(Self::Obv),
+            "ad" | "ad_line" => Ok(Self::AdLine),
+            "cmf" => Ok(Self::Cmf { period: 20 }),
+            "mfi" => Ok(Self::Mfi { period: 14 }),
+            "stochastic" | "stoch" => Ok(Self::Stochastic { k: 14, d: 3 }),
+            "atr" => Ok(Self::Atr { period: 14 }),
+            "cci" => Ok(Self::Cci { period: 20 }),
+            "williams_r" => Ok(Self::WilliamsR { period: 14 }),
+            "wma" => Ok(Self::Wma { period: 20, source }),
+            "hma" => Ok(Self::Hma { period: 20, source }),
+            "keltner" => Ok(Self::Keltner {
+                period: 20,
+                multiplier: 2.0,
+            }),
+            "donchian" => Ok(Self::Donchian { period: 20 }),
+            _ => Err(()),
+        }
+    }
+}
+
+/// Why an indicator property could not be read or set
+#[derive(Clone, Debug, PartialEq)]
+pub enum IndicatorPropertyError {
+    /// No indicator with this id
+    UnknownIndicator(String),
+    /// The indicator has no property with this name
+    UnknownProperty(String),
+    /// The value has the wrong type or is out of range for the property
+    InvalidValue { name: String, reason: String },
+    /// The indicator (with this id) was built from values rather than
+    /// parameters, so it has no inputs or source to change
+    NotEditable(String),
+}
+
+impl fmt::Display for IndicatorPropertyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownIndicator(id) => write!(f, "Unknown indicator '{}'", id),
+            Self::UnknownProperty(name) => write!(f, "Unknown indicator property '{}'", name),
+            Self::InvalidValue { name, reason } => {
+                write!(f, "Invalid value for '{}': {}", name, reason)
+            }
+            Self::NotEditable(id) => write!(f, "Indicator '{}' has no editable inputs", id),
+        }
+    }
+}
+
+impl std::error::Error for IndicatorPropertyError {}
+
+impl Indicator {
+    /// Built-in indicator for `params` with its default styling, e.g. from
+    /// a config that only names the inputs
+    ///
+    /// The values are empty and computed from the bars on first render.
+    pub fn from_params(id: &str, params: IndicatorParams) -> Self {
+        let mut indicator = match params {
+            IndicatorParams::Sma { period, .. } => Self::sma(id, period as u32, "#2196F3"),
+            IndicatorParams::Ema { period, .. } => Self::ema(id, period as u32, "#FF9800"),
+            IndicatorParams::Bollinger { period, .. } => Self::bollinger(id, period as u32),
+            IndicatorParams::Zigzag { deviation } => Self::zigzag(id, deviation),
+            IndicatorParams::Psar { .. } => Self::psar(id),
+            IndicatorParams::Supertrend { period, multiplier } => {
+                Self::supertrend(id, period as u32, multiplier)
+            }
+            IndicatorParams::Rsi { period, .. } => Self::rsi(id, period as u32),
+            IndicatorParams::Adx { period } => Self::adx(id, period as u32),
+            IndicatorParams::Macd {
+                fast, slow, signal, ..
+            } => Self::macd(id, fast as u32, slow as u32, signal as u32),
+            IndicatorParams::Obv => Self::obv(id),
+            IndicatorParams::AdLine => Self::ad_line(id),
+            IndicatorParams::Cmf { period } => Self::cmf(id, period as u32),
+            IndicatorParams::Mfi { period } => Self::mfi(id, period as u32),
+            IndicatorParams::Stochastic { k, d } => Self::stochastic(id, k as u32, d as u32),
+            IndicatorParams::Atr { period } => Self::atr(id, period as u32),
+            IndicatorParams::Cci { period } => Self::cci(id, period as u32),
+            IndicatorParams::WilliamsR { period } => Self::williams_r(id, period as u32),
+            IndicatorParams::Wma { period, .. } => Self::wma(id, period as u32, "#2196F3"),
+            IndicatorParams::Hma { period, .. } => Self::hma(id, period as u32, "#2196F3"),
+            IndicatorParams::Keltner { period, .. } => Self::keltner(id, period as u32),
+            IndicatorParams::Donchian { period } => Self::donchian(id, period as u32),
+        }
+        .with_params(params);
+        indicator.name = params.name();
+        indicator.needs_recompute = true;
+        indicator
+    }
+
+    /// Record the inputs the values were computed from
+    pub fn with_params(mut self, params: IndicatorParams) -> Self {
+        self.params = Some(params);
+        self
+    }
+
+    /// Re-derive the values from `bars` with the recorded parameters
+    ///
+    /// Indicators without parameters (custom values) keep their values;
+    /// only their outside-band markers are refreshed.
+    pub fn recompute(&mut self, bars: &[Bar]) {
+        self.needs_recompute = false;
+        let Some(params) = self.params else {
+            self.mark_outside_band(bars);
+            return;
+        };
+        let mut set = |index: usize, values: Vec<f64>| {
+            if let Some(vector) = self.vectors.get_mut(index) {
+                vector.values = values;
+            }
+        };
+        match params {
+            IndicatorParams::Sma { period, source } => {
+                set(0, compute::sma(&source.values(bars), period));
+            }
+            IndicatorParams::Ema { period, source } => {
+                set(0, compute::ema(&source.values(bars), period));
+            }
+            IndicatorParams::Bollinger {
+                period,
+                multiplier,
+                source,
+            } => {
+                let bands = compute::bollinger(&source.values(bars), period, multiplier);
+                self.set_bands(bands);
+            }
+            IndicatorParams::Keltner { period, multiplier } => {
+                let highs = compute::highs(bars);
+                let lows = compute::lows(bars);
+                let closes = compute::closes(bars);
+                let bands = compute::keltner(&highs, &lows, &closes, period, multiplier);
+                self.set_bands(bands);
+            }
+            IndicatorParams::Donchian { period } => {
+                let highs = compute::highs(bars);
+                let bands = compute::donchian(&highs, &compute::lows(bars), period);
+                self.set_bands(bands);
+            }
+            IndicatorParams::Zigzag { deviation } => {
+                let highs = compute::highs(bars);
+                set(0, compute::zigzag(&highs, &compute::lows(bars), deviation));
+            }
+            IndicatorParams::Psar { step, max_step } => {
+                let highs = compute::highs(bars);
+                let (values, directions) =
+                    compute::psar(&highs, &compute::lows(bars), step, max_step);
+                set(0, values);
+                if let Some(vector) = self.vectors.first_mut() {
+                    vector.directions = directions;
+                }
+            }
+            IndicatorParams::Supertrend { period, multiplier } => {
+                let closes = compute::closes(bars);
+                let (values, directions) = compute::supertrend(
+                    &compute::highs(bars),
+                    &compute::lows(bars),
+                    &closes,
+                    period,
+                    multiplier,
+                );
+                set(0, values);
+                set(1, closes);
+                if let Some(vector) = self.vectors.first_mut() {
+                    vector.directions = directions;
+                }
+            }
+            IndicatorParams::Rsi { period, source } => {
+                set(0, compute::rsi(&source.values(bars), period));
+            }
+            IndicatorParams::Adx { period } => {
+                let (adx, plus_di, minus_di) = compute::adx(
+                    &compute::highs(bars),
+                    &compute::lows(bars),
+                    &compute::closes(bars),
+                    period,
+                );
+                set(0, adx);
+                set(1, plus_di);
+                set(2, minus_di);
+            }
+            IndicatorParams::Macd {
+                fast,
+                slow,
+                signal,
+                source,
+            } => {
+                let (macd, signal_line, histogram) =
+                    compute::macd(&source.values(bars), fast, slow, signal);
+                set(0, macd);
+                set(1, signal_line);
+                set(2, histogram);
+            }
+            IndicatorParams::Obv => set(0, compute::obv(bars)),
+            IndicatorParams::AdLine => set(0, compute::ad_line(bars)),
+            IndicatorParams::Cmf { period } => set(0, compute::cmf(bars, period)),
+            IndicatorParams::Mfi { period } => set(0, compute::mfi(bars, period)),
+            IndicatorParams::Stochastic { k, d } => {
+                let highs = compute::highs(bars);
+                let lows = compute::lows(bars);
+                let (percent_k, percent_d) =
+                    compute::stochastic(&highs, &lows, &compute::closes(bars), k, d);
+                set(0, percent_k);
+                set(1, percent_d);
+            }
+            IndicatorParams::Atr { period } => {
+                let highs = compute::highs(bars);
+                let lows = compute::lows(bars);
+                set(
+                    0,
+                    compute::atr(&highs, &lows, &compute::closes(bars), period),
+                );
+            }
+            IndicatorParams::Cci { period } => {
+                let highs = compute::highs(bars);
+                let lows = compute::lows(bars);
+                set(
+                    0,
+                    compute::cci(&highs, &lows, &compute::closes(bars), period),
+                );
+            }
+            IndicatorParams::WilliamsR { period } => {
+                let highs = compute::highs(bars);
+                let lows = compute::lows(bars);
+                set(
+                    0,
+                    compute::williams_r(&highs, &lows, &compute::closes(bars), period),
+                );
+            }
+            IndicatorParams::Wma { period, source } => {
+                set(0, compute::wma(&source.values(bars), period));
+            }
+            IndicatorParams::Hma { period, source } => {
+                set(0, compute::hma(&source.values(bars), period));
+            }
+        }
+        self.mark_outside_band(bars);
+    }
+
+    /// Set the `(upper, middle, lower)` lines of a band indicator by vector
+    /// name, as the middle line may be left out
+    fn set_bands(&mut self, (upper, middle, lower): (Vec<f64>, Vec<f64>, Vec<f64>)) {
+        for (name, values) in [("Middle", middle), ("Upper", upper), ("Lower", lower)] {
+            if let Some(vector) = self.vectors.iter_mut().find(|v| v.name == name) {
+                vector.values = values;
+            }
+        }
+    }
+
+    /// Fill the outside-band markers of a band indicator from its upper and
+    /// lower vectors
+    ///
+    /// A bar closing over the upper line gets a dot above its high, one
+    /// closing under the lower line a dot below its low, a tenth of the
+    /// band's width away; other bars get NaN. Does nothing for indicators
+    /// without an [`OUTSIDE_BAND_VECTOR`].
+    pub fn mark_outside_band(&mut self, bars: &[Bar]) {
+        let find = |name: &str| self.vectors.iter().position(|v| v.name == name);
+        let (Some(marks), Some(upper), Some(lower)) =
+            (find(OUTSIDE_BAND_VECTOR), find("Upper"), find("Lower"))
+        else {
+            return;
+        };
+        let (upper, lower) = (&self.vectors[upper].values, &self.vectors[lower].values);
+        let (values, directions) = bars
+            .iter()
+            .enumerate()
+            .map(|(i, bar)| {
+                let (Some(&top), Some(&bottom)) = (upper.get(i), lower.get(i)) else {
+                    return (f64::NAN, true);
+                };
+                let gap = (top - bottom).abs() * 0.1;
+                if bar.close > top {
+                    (bar.high + gap, true)
+                } else if bar.close < bottom {
+                    (bar.low - gap, false)
+                } else {
+                    (f64::NAN, true)
+                }
+            })
+            .unzip();
+        let vector = &mut self.vectors[marks];
+        vector.values = values;
+        vector.directions = directions;
+    }
+}
+
+#[cfg(feature = "render")]
+impl Indicator {
+    /// Settings of this indicator for a dialog: its inputs, each vector's
+    /// colors, the reference levels and visibility
+    ///
+    /// Ids are `period`, `source`, `vectors[0].color`, `levels[1]`, ...;
+    /// pass them back to [`Self::set_property`]. Indicators built from
+    /// values list no inputs, and changing one fails with
+    /// [`IndicatorPropertyError::NotEditable`].
+    pub fn properties(&self) -> Vec<ConfigProperty> {
+        let mut properties = Vec::new();
+        if let Some(params) = &self.params {
+            for (name, value) in params.inputs() {
+                let property = if IndicatorParams::is_count(name) {
+                    ConfigProperty::integer(name, &label(name), value as i32, Some(1), None)
+                } else {
+                    ConfigProperty::number(name, &label(name), value, Some(0.0), None)
+                };
+                properties.push(property.with_category(PropertyCategory::Inputs));
+            }
+            if let Some(source) = params.source() {
+                let options = [
+                    Source::Open,
+                    Source::High,
+                    Source::Low,
+                    Source::Close,
+                    Source::Hl2,
+                    Source::Hlc3,
+                    Source::Ohlc4,
+                ]
+                .iter()
+                .map(|s| SelectOption::new(s.as_str(), s.as_str()))
+                .collect();
+                properties.push(
+                    ConfigProperty::select("source", "Source", source.as_str(), options)
+                        .with_category(PropertyCategory::Inputs),
+                );
+            }
+        }
+
+        for (i, vector) in self.vectors.iter().enumerate() {
+            for (field, color) in vector.style.colors() {
+                let id = format!("vectors[{}].{}", i, field);
+                let name = format!("{} {}", vector.name, label(field).to_lowercase());
+                properties.push(ConfigProperty::color(&id, &name, color).with_order(i as i32));
+            }
+        }
+        for (i, level) in self.levels.iter().enumerate() {
+            let name = level
+                .label
+                .clone()
+                .unwrap_or_else(|| format!("Level {}", i + 1));
+            properties.push(
+                ConfigProperty::number(&format!("levels[{}]", i), &name, level.value, None, None)
+                    .with_category(PropertyCategory::Inputs)
+                    .with_order(100 + i as i32),
+            );
+        }
+        properties.push(
+            ConfigProperty::boolean("visible", "Visible", self.visible)
+                .with_category(PropertyCategory::Visibility),
+        );
+        properties
+    }
+
+    /// Replace the parameters, renaming the indicator if it still carries
+    /// the old default name, and mark the values for recomputation
+    fn change_params(&mut self, params: IndicatorParams) {
+        if let Some(old) = self.params
+            && self.name == old.name()
+        {
+            self.name = params.name();
+        }
+        self.params = Some(params);
+        self.needs_recompute = true;
+    }
+
+    /// Change one setting by its [`Self::properties`] id
+    ///
+    /// Changing an input marks the values stale: the chart re-derives them
+    /// from its bars on the next render, or call [`Self::recompute`].
+    /// Numbers are accepted as integers or floats; colors and the source as
+    /// strings.
+    pub fn set_property(
+        &mut self,
+        name: &str,
+        value: PropertyValue,
+    ) -> Result<(), IndicatorPropertyError> {
+        let invalid = |reason: &str| IndicatorPropertyError::InvalidValue {
+            name: name.to_string(),
+            reason: reason.to_string(),
+        };
+        let number = match value {
+            PropertyValue::Number(n) => Some(n),
+            PropertyValue::Integer(n) => Some(n as f64),
+            _ => None,
+        };
+
+        if name == "visible" {
+            self.visible = value
+                .as_bool()
+                .ok_or_else(|| invalid("expected a boolean"))?;
+            return Ok(());
+        }
+        if name == "source" {
+            let mut params = self
+                .params
+                .ok_or_else(|| IndicatorPropertyError::NotEditable(self.id.clone()))?;
+            let source = value
+                .as_string()
+                .and_then(|s| s.parse().ok())
+                .ok_or_else(|| invalid("expected open, high, low, close, hl2, hlc3 or ohlc4"))?;
+            if !params.set_source(source) {
+                return Err(IndicatorPropertyError::UnknownProperty(name.to_string()));
+            }
+            self.change_params(params);
+            return Ok(());
+        }
+        if let Some(index) = indexed(name, "levels") {
+            let level = self
+                .levels
+                .get_mut(index)
+                .ok_or_else(|| IndicatorPropertyError::UnknownProperty(name.to_string()))?;
+            level.value = number
+                .filter(|n| n.is_finite())
+                .ok_or_else(|| invalid("expected a number"))?;
+            return Ok(());
+        }
+        if let Some((vector, field)) = name
+            .strip_prefix("vectors[")
+            .and_then(|s| s.split_once("]."))
+        {
+            let color = self
+                .vectors
+                .get_mut(vector.parse::<usize>().unwrap_or(usize::MAX))
+                .and_then(|vector| vector.style.color_mut(field))
+                .ok_or_else(|| IndicatorPropertyError::UnknownProperty(name.to_string()))?;
+            let value = value
+                .as_string()
+                .ok_or_else(|| invalid("expected a color"))?;
+            crate::core::parse_css_color(value).map_err(|e| invalid(&e.to_string()))?;
+            *color = value.to_string();
+            return Ok(());
+        }
+
+        let mut params = self
+            .params
+            .ok_or_else(|| IndicatorPropertyError::NotEditable(self.id.clone()))?;
+        if !params.inputs().iter().any(|(n, _)| *n == name) {
+            return Err(IndicatorPropertyError::UnknownProperty(name.to_string()));
+        }
+        let value = number.ok_or_else(|| invalid("expected a number"))?;
+        if IndicatorParams::is_count(name) {
+            if value.fract() != 0.0 || value < 1.0 {
+                return Err(invalid("expected a whole number of at least 1"));
+            }
+        } else if !(value.is_finite() && value >= 0.0) {
+            return Err(invalid("expected a non-negative number"));
+        }
+        params.set_input(name, value);
+        self.change_params(params);
+        Ok(())
+    }
+}
+
+/// `"max_step"` as `"Max step"`
+#[cfg(feature = "render")]
+fn label(name: &str) -> String {
+    let spaced = name.replace('_', " ");
+    let mut chars = spaced.chars();
+    chars
+        .next()
+        .map(|first| first.to_uppercase().chain(chars).collect())
+        .unwrap_or_default()
+}
+
+/// Index in `"{field}[{index}]"`
+#[cfg(feature = "render")]
+fn indexed(name: &str, field: &str) -> Option<usize> {
+    name.strip_prefix(field)?
+        .strip_prefix('[')?
+        .strip_suffix(']')?
+        .parse()
+        .ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::testing::sample_bars;
+    use crate::model::{BandLine, BandStyle, IndicatorVector, VectorStyle};
+
+    #[test]
+    fn test_recompute_matches_compute() {
+        let bars = sample_bars(120);
+        let mut macd = Indicator::macd("macd", 12, 26, 9);
+        macd.recompute(&bars);
+        let (line, signal, histogram) = compute::macd(&compute::closes(&bars), 12, 26, 9);
+        assert_eq!(macd.vectors[2].values.len(), histogram.len());
+        for (vector, expected) in macd.vectors.iter().zip([line, signal, histogram]) {
+            let same = vector
+                .values
+                .iter()
+                .zip(&expected)
+                .all(|(a, b)| a == b || (a.is_nan() && b.is_nan()));
+            assert!(same, "{} differs", vector.name);
+        }
+    }
+
+    #[test]
+    fn test_from_params_builds_preset_with_inputs() {
+        let mut params: IndicatorParams = "bb".parse().unwrap();
+        assert!(params.set_input("multiplier", 2.5));
+        let mut bands = Indicator::from_params("bands", params);
+        assert_eq!((bands.id.as_str(), bands.name.as_str()), ("bands", "BB 20"));
+        assert_eq!(bands.vectors.len(), 3);
+        assert!(bands.needs_recompute);
+
+        let bars = sample_bars(60);
+        bands.recompute(&bars);
+        let (upper, _, _) = compute::bollinger(&compute::closes(&bars), 20, 2.5);
+        assert_eq!(bands.vectors[1].values[30], upper[30]);
+        assert!("fisher".parse::<IndicatorParams>().is_err());
+    }
+
+    #[test]
+    fn test_presets_recompute_from_their_params() {
+        let bars = sample_bars(80);
+        let (highs, lows, closes) = (
+            compute::highs(&bars),
+            compute::lows(&bars),
+            compute::closes(&bars),
+        );
+        let cases = [
+            (
+                Indicator::stochastic("st", 14, 3),
+                "%D",
+                compute::stochastic(&highs, &lows, &closes, 14, 3).1,
+            ),
+            (
+                Indicator::atr("atr", 14),
+                "ATR",
+                compute::atr(&highs, &lows, &closes, 14),
+            ),
+            (
+                Indicator::cci("cci", 20),
+                "CCI",
+                compute::cci(&highs, &lows, &closes, 20),
+            ),
+            (
+                Indicator::williams_r("wr", 14),
+                "%R",
+                compute::williams_r(&highs, &lows, &closes, 14),
+            ),
+            (
+                Indicator::wma("wma", 20, "#fff"),
+                "WMA",
+                compute::wma(&closes, 20),
+            ),
+            (
+                Indicator::hma("hma", 20, "#fff"),
+                "HMA",
+                compute::hma(&closes, 20),
+            ),
+            (
+                Indicator::keltner("kc", 20),
+                "Upper",
+                compute::keltner(&highs, &lows, &closes, 20, 2.0).0,
+            ),
+            (
+                Indicator::donchian("dc", 20),
+                "Lower",
+                compute::donchian(&highs, &lows, 20).2,
+            ),
+        ];
+        for (mut indicator, vector, expected) in cases {
+            let params = indicator.params.expect("preset keeps its params");
+            assert_eq!(indicator.name, params.name());
+            indicator.recompute(&bars);
+            let values = &indicator
+                .vectors
+                .iter()
+                .find(|v| v.name == vector)
+                .unwrap()
+                .values;
+            assert_eq!(values[60], expected[60], "{}", indicator.id);
+        }
+    }
+
+    #[cfg(feature = "render")]
+    #[test]
+    fn test_value_indicators_are_not_editable() {
+        let mut ichimoku = Indicator::ichimoku("ichimoku");
+        assert!(ichimoku.properties().iter().all(|p| p.id != "period"));
+        assert_eq!(
+            ichimoku.set_property("period", PropertyValue::Integer(9)),
+            Err(IndicatorPropertyError::NotEditable("ichimoku".into()))
+        );
+        let mut stochastic = Indicator::stochastic("st", 14, 3);
+        stochastic
+            .set_property("k", PropertyValue::Integer(5))
+            .unwrap();
+        assert_eq!(stochastic.name, "Stoch (5,3)");
+    }
+
+    #[test]
+    fn test_recompute_leaves_custom_values() {
+        let mut custom = Indicator::new("custom", "Custom")
+            .add_vector(IndicatorVector::new("Line", VectorStyle::line("#fff", 1.0)));
+        custom.vectors[0].values = vec![1.0, 2.0];
+        custom.recompute(&sample_bars(10));
+        assert_eq!(custom.vectors[0].values, vec![1.0, 2.0]);
+    }
+
+    #[test]
+    fn test_outside_band_markers_match_hand_count() {
+        // Band 90..110; outside: 111 and 120 above, 89 and 85 below
+        let closes = [100.0, 111.0, 105.0, 89.0, 120.0, 90.0, 85.0, 110.0];
+        let bars: Vec<Bar> = closes
+            .iter()
+            .enumerate()
+            .map(|(i, &c)| Bar::new(i as i64, c, c + 1.0, c - 1.0, c))
+            .collect();
+        let style = BandStyle::keltner()
+            .show_middle(false)
+            .outside_markers(true);
+        let mut keltner = Indicator::keltner_styled("kc", 20, &style);
+        assert_eq!(keltner.vectors.len(), 3);
+        // A hand-set band, kept by recompute
+        keltner.params = None;
+        keltner.vectors[0].values = vec![110.0; closes.len()];
+        keltner.vectors[1].values = vec![90.0; closes.len()];
+        keltner.recompute(&bars);
+
+        let marks = &keltner.vectors[2];
+        assert_eq!(marks.name, OUTSIDE_BAND_VECTOR);
+        let dots: Vec<(usize, bool)> = marks
+            .values
+            .iter()
+            .zip(&marks.directions)
+            .enumerate()
+            .filter(|(_, (v, _))| v.is_finite())
+            .map(|(i, (_, &up))| (i, up))
+            .collect();
+        assert_eq!(dots, [(1, true), (3, false), (4, true), (6, false)]);
+        // A tenth of the band's width past the wick
+        assert_eq!(marks.values[1], 112.0 + 2.0);
+        assert_eq!(marks.values[3], 88.0 - 2.0);
+    }
+
+    #[test]
+    fn test_band_presets_keep_default_look() {
+        let bands = Indicator::bollinger("bb", 20);
+        let names: Vec<&str> = bands.vectors.iter().map(|v| v.name.as_str()).collect();
+        assert_eq!(names, ["Middle", "Upper", "Lower"]);
+        assert_eq!(bands.fills[0].upper_vector, 1);
+        assert_eq!(bands.fills[0].alpha, 0.1);
+
+        let donchian = Indicator::donchian("dc", 20);
+        assert_eq!(donchian.vectors.len(), 2);
+        assert_eq!(donchian.fills[0].color, "#2196F3");
+
+        // Without the middle line Bollinger still fills upper and lower
+        let style = BandStyle::bollinger()
+            .show_middle(false)
+            .upper(BandLine::new("#00f").dashed())
+            .no_fill();
+        let mut bands =
+            Indicator::bollinger_styled("bb", 20, &style).with_params(IndicatorParams::Bollinger {
+                period: 20,
+                multiplier: 2.0,
+                source: Source::Close,
+            });
+        let bars = sample_bars(60);
+        bands.recompute(&bars);
+        let (upper, _, _) = compute::bollinger(&compute::closes(&bars), 20, 2.0);
+        assert_eq!(bands.vectors[0].values[30], upper[30]);
+        assert!(bands.fills.is_empty());
+        assert!(matches!(
+            bands.vectors[0].style,
+            VectorStyle::Line { dashed: true, .. }
+        ));
+    }
+
+    #[cfg(feature = "render")]
+    #[test]
+    fn test_set_property_changes_inputs_and_name() {
+        let mut rsi = Indicator::rsi("rsi", 14);
+        let ids: Vec<String> = rsi.properties().into_iter().map(|p| p.id).collect();
+        assert!(ids.contains(&"period".to_string()));
+        assert!(ids.contains(&"source".to_string()));
+        assert!(ids.contains(&"vectors[0].color".to_string()));
+        assert!(ids.contains(&"levels[0]".to_string()));
+
+        rsi.set_property("period", PropertyValue::Integer(21))
+            .unwrap();
+        rsi.set_property("source", PropertyValue::String("hl2".into()))
+            .unwrap();
+        assert_eq!(
+            rsi.params,
+            Some(IndicatorParams::Rsi {
+                period: 21,
+                source: Source::Hl2
+            })
+        );
+        assert_eq!(rsi.name, "RSI 21 hl2");
+        assert!(rsi.needs_recompute);
+
+        rsi.set_property("levels[0]", PropertyValue::Number(75.0))
+            .unwrap();
+        assert_eq!(rsi.levels[0].value, 75.0);
+        rsi.set_property("vectors[0].color", PropertyValue::Color("#ff0000".into()))
+            .unwrap();
+        assert_eq!(rsi.vectors[0].style.primary_color(), "#ff0000");
+    }
+
+    #[cfg(feature = "render")]
+    #[test]
+    fn test_set_property_rejects_bad_values() {
+        let mut sma = Indicator::sma("sma", 20, "#2196F3");
+        assert_eq!(
+            sma.set_property("multiplier", PropertyValue::Number(2.0)),
+            Err(IndicatorPropertyError::UnknownProperty("multiplier".into()))
+        );
+        assert!(matches!(
+            sma.set_property("period", PropertyValue::Number(2.5)),
+            Err(IndicatorPropertyError::InvalidValue { .. })
+        ));
+        assert!(matches!(
+            sma.set_property("period", PropertyValue::Integer(0)),
+            Err(IndicatorPropertyError::InvalidValue { .. })
+        ));
+        assert!(matches!(
+            sma.set_property("vectors[0].color", PropertyValue::String("nope".into())),
+            Err(IndicatorPropertyError::InvalidValue { .. })
+        ));
+        assert_eq!(
+            sma.params,
+            Some(IndicatorParams::Sma {
+                period: 20,
+                source: Source::Close
+            })
+        );
+        assert!(!sma.needs_recompute);
+    }
+}
@@ -28,7 +28,12 @@
 //! // Later: macd.set_vectors(vec![macd_line, signal_line, histogram]);
 //! ```
 
+use super::indicator_params::IndicatorParams;
+use super::overlays::Source;
+use crate::core::compute;
 use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::str::FromStr;
 
 // =============================================================================
 // Placement
@@ -47,6 +52,15 @@ pub enum IndicatorPlacement {
     OverlayBottom {
         #[serde(default = "default_overlay_bottom_height")]
         height_ratio: f64,
+        /// Opacity applied to the whole strip (0.0-1.0)
+        #[serde(default = "default_overlay_bottom_opacity")]
+        opacity: f64,
+        /// Shrink the price area so the main series never overlaps the strip
+        #[serde(default)]
+        reserve_space: bool,
+        /// Cap on the strip height in pixels
+        #[serde(default)]
+        max_height: Option<f64>,
     },
     /// Separate sub-pane below main chart (e.g., RSI, MACD)
     SubPane {
@@ -61,6 +75,9 @@ fn default_height_ratio() -> f64 {
 fn default_overlay_bottom_height() -> f64 {
     0.2
 }
+fn default_overlay_bottom_opacity() -> f64 {
+    1.0
+}
 
 impl IndicatorPlacement {
     pub fn overlay() -> Self {
@@ -69,6 +86,9 @@ impl IndicatorPlacement {
     pub fn overlay_bottom(height_ratio: f64) -> Self {
         Self::OverlayBottom {
             height_ratio: height_ratio.clamp(0.1, 0.5),
+            opacity: default_overlay_bottom_opacity(),
+            reserve_space: false,
+            max_height: None,
         }
     }
     pub fn subpane(height_ratio: f64) -> Self {
@@ -89,10 +109,68 @@ impl IndicatorPlacement {
     pub fn height_ratio(&self) -> f64 {
         match self {
             Self::Overlay => 0.0,
-            Self::OverlayBottom { height_ratio } => *height_ratio,
+            Self::OverlayBottom { height_ratio, .. } => *height_ratio,
             Self::SubPane { height_ratio } => *height_ratio,
         }
     }
+
+    /// Set the strip opacity (OverlayBottom only)
+    pub fn with_opacity(mut self, value: f64) -> Self {
+        if let Self::OverlayBottom { opacity, .. } = &mut self {
+            *opacity = value.clamp(0.0, 1.0);
+        }
+        self
+    }
+
+    /// Reserve the strip below the price area (OverlayBottom only)
+    pub fn with_reserved_space(mut self, reserve: bool) -> Self {
+        if let Self::OverlayBottom { reserve_space, .. } = &mut self {
+            *reserve_space = reserve;
+        }
+        self
+    }
+
+    /// Cap the strip height in pixels (OverlayBottom only)
+    pub fn with_max_height(mut self, pixels: f64) -> Self {
+        if let Self::OverlayBottom { max_height, .. } = &mut self {
+            *max_height = Some(pixels.max(0.0));
+        }
+        self
+    }
+
+    /// Strip opacity, 1.0 for anything but OverlayBottom
+    pub fn opacity(&self) -> f64 {
+        match self {
+            Self::OverlayBottom { opacity, .. } => *opacity,
+            _ => 1.0,
+        }
+    }
+
+    /// Whether this OverlayBottom takes its strip away from the price area
+    pub fn reserves_space(&self) -> bool {
+        matches!(
+            self,
+            Self::OverlayBottom {
+                reserve_space: true,
+                ..
+            }
+        )
+    }
+
+    /// OverlayBottom strip height in pixels for a main pane of `main_height`
+    pub fn strip_height(&self, main_height: f64) -> f64 {
+        match self {
+            Self::OverlayBottom {
+                height_ratio,
+                max_height,
+                ..
+            } => {
+                let height = main_height * height_ratio;
+                max_height.map_or(height, |max| height.min(max))
+            }
+            _ => 0.0,
+        }
+    }
 }
 
 // =============================================================================
@@ -130,6 +208,57 @@ impl IndicatorRange {
     }
 }
 
+/// A value scale of its own inside a subpane, for vectors whose magnitudes
+/// differ too much to share the pane's (e.g. an oscillator line over a
+/// small histogram)
+///
+/// Vectors join it with [`IndicatorVector::with_scale_group`]; the others
+/// and the levels stay on the indicator's `range`, and each edge of a fill
+/// follows its own vector.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct ScaleGroup {
+    /// Id vectors refer to in `scale_group`
+    pub id: String,
+    /// Range fitted to the group's vectors
+    #[serde(default)]
+    pub range: IndicatorRange,
+    /// Label the group's scale as a secondary axis, in a column the price
+    /// scale widens by to the right of the pane's own labels
+    #[serde(default)]
+    pub axis: bool,
+}
+
+impl ScaleGroup {
+    pub fn new(id: &str, range: IndicatorRange) -> Self {
+        Self {
+            id: id.to_string(),
+            range,
+            axis: false,
+        }
+    }
+
+    /// Show the group's scale as a secondary axis
+    pub fn with_axis(mut self) -> Self {
+        self.axis = true;
+        self
+    }
+}
+
+// =============================================================================
+// Volume Split
+// =============================================================================
+
+/// How a buy/sell volume split is drawn
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum VolumeSplitMode {
+    /// Sell volume at the base, buy volume stacked on top of it
+    #[default]
+    Stacked,
+    /// Buy volume above zero, sell volume mirrored below
+    Mirrored,
+}
+
 // =============================================================================
 // Reference Level (horizontal lines like overbought/oversold)
 // =============================================================================
@@ -185,6 +314,196 @@ impl IndicatorLevel {
     }
 }
 
+// =============================================================================
+// Indicator Fill (shaded band between two vectors)
+// =============================================================================
+
+/// Shaded region between two vectors of the same indicator
+///
+/// Used for band indicators (Bollinger, Keltner, Donchian) where the area
+/// between the outer lines is filled. Bars where either vector is NaN break
+/// the fill into separate polygons.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct IndicatorFill {
+    /// Index of the upper boundary vector
+    pub upper_vector: usize,
+    /// Index of the lower boundary vector
+    pub lower_vector: usize,
+    pub color: String,
+    #[serde(default = "default_indicator_fill_alpha")]
+    pub alpha: f64,
+    /// Color where the lower vector rises above the upper one, e.g. after a
+    /// Supertrend flip (`color` when unset)
+    #[serde(default)]
+    pub crossed_color: Option<String>,
+}
+
+fn default_indicator_fill_alpha() -> f64 {
+    0.1
+}
+
+impl IndicatorFill {
+    pub fn new(upper_vector: usize, lower_vector: usize, color: &str) -> Self {
+        Self {
+            upper_vector,
+            lower_vector,
+            color: color.to_string(),
+            alpha: default_indicator_fill_alpha(),
+            crossed_color: None,
+        }
+    }
+    pub fn with_alpha(mut self, alpha: f64) -> Self {
+        self.alpha = alpha;
+        self
+    }
+    pub fn with_crossed_color(mut self, color: &str) -> Self {
+        self.crossed_color = Some(color.to_string());
+        self
+    }
+}
+
+// =============================================================================
+// Band Style (Bollinger, Keltner, Donchian)
+// =============================================================================
+
+/// Name of the vector holding outside-band markers
+pub const OUTSIDE_BAND_VECTOR: &str = "Outside";
+
+/// One line of a band indicator
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct BandLine {
+    pub color: String,
+    pub width: f64,
+    #[serde(default)]
+    pub dashed: bool,
+}
+
+impl BandLine {
+    pub fn new(color: &str) -> Self {
+        Self {
+            color: color.to_string(),
+            width: 1.0,
+            dashed: false,
+        }
+    }
+    pub fn width(mut self, width: f64) -> Self {
+        self.width = width;
+        self
+    }
+    pub fn dashed(mut self) -> Self {
+        self.dashed = true;
+        self
+    }
+
+    fn style(&self) -> VectorStyle {
+        VectorStyle::Line {
+            color: self.color.clone(),
+            width: self.width,
+            dashed: self.dashed,
+        }
+    }
+}
+
+/// Look of a band indicator: its lines, the fill between the outer ones
+/// and the markers on bars closing outside the band
+///
+/// The presets reproduce the default look of each indicator; see
+/// [`Indicator::bollinger_styled`].
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct BandStyle {
+    pub show_middle: bool,
+    pub middle: BandLine,
+    pub upper: BandLine,
+    pub lower: BandLine,
+    /// Shade the area between the upper and lower lines
+    pub fill: bool,
+    pub fill_color: String,
+    #[serde(default = "default_indicator_fill_alpha")]
+    pub fill_alpha: f64,
+    /// Dot above bars closing over the upper line and below bars closing
+    /// under the lower one
+    #[serde(default)]
+    pub outside_markers: bool,
+    #[serde(default = "default_up_color")]
+    pub above_color: String,
+    #[serde(default = "default_down_color")]
+    pub below_color: String,
+}
+
+impl BandStyle {
+    fn preset(middle: &str, band: &str, fill: &str) -> Self {
+        Self {
+            show_middle: true,
+            middle: BandLine::new(middle),
+            upper: BandLine::new(band),
+            lower: BandLine::new(band),
+            fill: true,
+            fill_color: fill.to_string(),
+            fill_alpha: default_indicator_fill_alpha(),
+            outside_markers: false,
+            above_color: default_up_color(),
+            below_color: default_down_color(),
+        }
+    }
+
+    /// Default Bollinger Bands look
+    pub fn bollinger() -> Self {
+        Self::preset("#2196F3", "#2196F380", "#2196F3")
+    }
+
+    /// Default Keltner Channels look
+    pub fn keltner() -> Self {
+        Self::preset("#FF9800", "#FF980080", "#FF9800")
+    }
+
+    /// Default Donchian Channels look, without a middle line
+    pub fn donchian() -> Self {
+        Self {
+            show_middle: false,
+            upper: BandLine::new("#4CAF50"),
+            lower: BandLine::new("#F44336"),
+            ..Self::preset("#2196F3", "#2196F3", "#2196F3")
+        }
+    }
+
+    pub fn show_middle(mut self, show: bool) -> Self {
+        self.show_middle = show;
+        self
+    }
+    pub fn middle(mut self, line: BandLine) -> Self {
+        self.middle = line;
+        self
+    }
+    pub fn upper(mut self, line: BandLine) -> Self {
+        self.upper = line;
+        self
+    }
+    pub fn lower(mut self, line: BandLine) -> Self {
+        self.lower = line;
+        self
+    }
+    /// Turn the fill off
+    pub fn no_fill(mut self) -> Self {
+        self.fill = false;
+        self
+    }
+    pub fn fill(mut self, color: &str, alpha: f64) -> Self {
+        self.fill = true;
+        self.fill_color = color.to_string();
+        self.fill_alpha = alpha;
+        self
+    }
+    pub fn outside_markers(mut self, enabled: bool) -> Self {
+        self.outside_markers = enabled;
+        self
+    }
+    pub fn marker_colors(mut self, above: &str, below: &str) -> Self {
+        self.above_color = above.to_string();
+        self.below_color = below.to_string();
+        self
+    }
+}
+
 // =============================================================================
 // Vector Style - style for each line/component of a multi-vector indicator
 // =============================================================================
@@ -225,12 +544,28 @@ pub enum VectorStyle {
         radius: f64,
         #[serde(default)]
         filled: bool,
+        /// Color of dots whose direction is down (Parabolic SAR above price)
+        #[serde(default)]
+        down_color: Option<String>,
     },
     /// Step line (discrete values)
     Step {
         color: String,
         #[serde(default = "default_line_width")]
         width: f64,
+        /// Color of runs whose direction is down. When set, the line breaks
+        /// where the direction flips instead of joining both sides.
+        #[serde(default)]
+        down_color: Option<String>,
+    },
+    /// Straight segments between the defined values, however long the
+    /// `NaN` run between them (ZigZag pivots)
+    Pivots {
+        color: String,
+        #[serde(default = "default_line_width")]
+        width: f64,
+        #[serde(default)]
+        labels: PivotLabels,
     },
     /// Cloud/fill between this vector and another
     Cloud {
@@ -245,6 +580,31 @@ pub enum VectorStyle {
     Hidden,
 }
 
+/// Text drawn next to each point of a `Pivots` vector
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PivotLabels {
+    #[default]
+    None,
+    /// The pivot's value
+    Price,
+    /// Percentage change from the previous pivot
+    Change,
+}
+
+impl FromStr for PivotLabels {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "none" => Ok(Self::None),
+            "price" => Ok(Self::Price),
+            "change" | "percent" => Ok(Self::Change),
+            _ => Err(()),
+        }
+    }
+}
+
 fn default_line_width() -> f64 {
     1.0
 }
@@ -315,12 +675,38 @@ impl VectorStyle {
             color: color.to_string(),
             radius,
             filled: true,
+            down_color: None,
+        }
+    }
+    /// Dots colored by the vector's directions
+    pub fn dots_directional(up: &str, down: &str, radius: f64) -> Self {
+        Self::Dots {
+            color: up.to_string(),
+            radius,
+            filled: true,
+            down_color: Some(down.to_string()),
         }
     }
     pub fn step(color: &str, width: f64) -> Self {
         Self::Step {
             color: color.to_string(),
             width,
+            down_color: None,
+        }
+    }
+    /// Step line colored by the vector's directions, broken at flips
+    pub fn step_directional(up: &str, down: &str, width: f64) -> Self {
+        Self::Step {
+            color: up.to_string(),
+            width,
+            down_color: Some(down.to_string()),
+        }
+    }
+    pub fn pivots(color: &str, width: f64, labels: PivotLabels) -> Self {
+        Self::Pivots {
+            color: color.to_string(),
+            width,
+            labels,
         }
     }
     pub fn cloud(color_above: &str, color_below: &str, fill_to: usize) -> Self {
@@ -342,10 +728,63 @@ impl VectorStyle {
             Self::Histogram { up_color, .. } => up_color,
             Self::Dots { color, .. } => color,
             Self::Step { color, .. } => color,
+            Self::Pivots { color, .. } => color,
             Self::Cloud { color_above, .. } => color_above,
             Self::Hidden => "#000000",
         }
     }
+
+    /// Every color this style draws with, labelled by field name
+    pub fn colors(&self) -> Vec<(&'static str, &str)> {
+        match self {
+            Self::Line { color, .. } | Self::Area { color, .. } | Self::Pivots { color, .. } => {
+                vec![("color", color)]
+            }
+            Self::Histogram {
+                up_color,
+                down_color,
+                ..
+            } => vec![("up_color", up_color), ("down_color", down_color)],
+            Self::Dots {
+                color, down_color, ..
+            }
+            | Self::Step {
+                color, down_color, ..
+            } => {
+                let mut colors = vec![("color", color.as_str())];
+                colors.extend(down_color.as_deref().map(|c| ("down_color", c)));
+                colors
+            }
+            Self::Cloud {
+                color_above,
+                color_below,
+                ..
+            } => vec![("color_above", color_above), ("color_below", color_below)],
+            Self::Hidden => Vec::new(),
+        }
+    }
+
+    /// Mutable access to the color named `field` in [`Self::colors`]
+    pub fn color_mut(&mut self, field: &str) -> Option<&mut String> {
+        match (self, field) {
+            (
+                Self::Line { color, .. }
+                | Self::Area { color, .. }
+                | Self::Pivots { color, .. }
+                | Self::Dots { color, .. }
+                | Self::Step { color, .. },
+                "color",
+            ) => Some(color),
+            (Self::Histogram { up_color, .. }, "up_color") => Some(up_color),
+            (Self::Histogram { down_color, .. }, "down_color") => Some(down_color),
+            (Self::Dots { down_color, .. } | Self::Step { down_color, .. }, "down_color") => {
+                down_color.as_mut()
+            }
+            (Self::Cloud { color_above, .. }, "color_above") => Some(color_above),
+            (Self::Cloud { color_below, .. }, "color_below") => Some(color_below),
+            _ => None,
+        }
+    }
 }
 
 // =============================================================================
@@ -357,6 +796,9 @@ impl VectorStyle {
 pub struct IndicatorVector {
     /// Name of this vector (e.g., "Upper", "Middle", "Lower" for Bollinger)
     pub name: String,
+    /// Short label for legends and tooltips (falls back to `name`)
+    #[serde(default)]
+    pub label: Option<String>,
     /// Visual style
     pub style: VectorStyle,
     /// The actual values (one per bar)
@@ -369,6 +811,13 @@ pub struct IndicatorVector {
     /// Whether to show in legend
     #[serde(default = "default_true")]
     pub show_in_legend: bool,
+    /// SMA period applied to `values` before rendering; `values` stay raw
+    #[serde(default)]
+    pub smoothing: Option<usize>,
+    /// Id of the subpane [`ScaleGroup`] the vector is scaled by, instead
+    /// of the indicator's range
+    #[serde(default)]
+    pub scale_group: Option<String>,
 }
 
 fn default_true() -> bool {
@@ -379,10 +828,13 @@ impl IndicatorVector {
     pub fn new(name: &str, style: VectorStyle) -> Self {
         Self {
             name: name.to_string(),
+            label: None,
             style,
             values: Vec::new(),
             directions: Vec::new(),
             show_in_legend: true,
+            smoothing: None,
+            scale_group: None,
         }
     }
 
@@ -396,11 +848,44 @@ impl IndicatorVector {
         self
     }
 
+    pub fn with_label(mut self, label: &str) -> Self {
+        self.label = Some(label.to_string());
+        self
+    }
+
+    /// Label for legends and tooltips
+    pub fn display_label(&self) -> &str {
+        self.label.as_deref().unwrap_or(&self.name)
+    }
+
     pub fn hide_from_legend(mut self) -> Self {
         self.show_in_legend = false;
         self
     }
 
+    /// Scale the vector by the indicator's [`ScaleGroup`] with this id
+    pub fn with_scale_group(mut self, id: &str) -> Self {
+        self.scale_group = Some(id.to_string());
+        self
+    }
+
+    /// Smooth the rendered line with an SMA of `period` bars
+    pub fn with_smoothing(mut self, period: usize) -> Self {
+        self.smoothing = Some(period);
+        self
+    }
+
+    /// Values as rendered: the SMA of `values` when smoothing is set
+    ///
+    /// Warm-up NaNs carry through, so the smoothed line starts
+    /// `period - 1` bars after the first raw value.
+    pub fn display_values(&self) -> Cow<'_, [f64]> {
+        match self.smoothing {
+            Some(period) if period > 1 => Cow::Owned(compute::sma(&self.values, period)),
+            _ => Cow::Borrowed(&self.values),
+        }
+    }
+
     pub fn value_at(&self, index: usize) -> Option<f64> {
         self.values.get(index).copied().filter(|v| !v.is_nan())
     }
@@ -408,6 +893,19 @@ impl IndicatorVector {
     pub fn direction_at(&self, index: usize) -> Option<bool> {
         self.directions.get(index).copied()
     }
+
+    /// Color the vector is drawn with at a bar: a histogram's falling
+    /// bars use `down_color`, everything else the style's primary color
+    pub fn color_at(&self, index: usize) -> &str {
+        match &self.style {
+            VectorStyle::Histogram { down_color, .. }
+                if self.direction_at(index) == Some(false) =>
+            {
+                down_color
+            }
+            style => style.primary_color(),
+        }
+    }
 }
 
 // =============================================================================
@@ -434,12 +932,31 @@ pub struct Indicator {
     /// Vector components (each with its own style and values)
     #[serde(default)]
     pub vectors: Vec<IndicatorVector>,
+    /// Shaded regions between vector pairs (rendered behind the lines)
+    #[serde(default)]
+    pub fills: Vec<IndicatorFill>,
+    /// Independent value scales some vectors use in a subpane
+    #[serde(default)]
+    pub scale_groups: Vec<ScaleGroup>,
     /// Whether visible
     #[serde(default = "default_true")]
     pub visible: bool,
     /// Precision for display
     #[serde(default = "default_precision")]
     pub precision: u8,
+    /// Legend group; grouped indicators share a legend header and can be
+    /// hidden together
+    #[serde(default)]
+    pub group: Option<String>,
+    /// Whether an overlay's values stretch the automatic price range
+    #[serde(default = "default_true")]
+    pub autoscale: bool,
+    /// Inputs the values were computed from, for built-in indicators
+    #[serde(default)]
+    pub params: Option<IndicatorParams>,
+    /// Whether `params` changed since the values were last computed
+    #[serde(skip)]
+    pub needs_recompute: bool,
 }
 
 fn default_precision() -> u8 {
@@ -456,8 +973,14 @@ impl Indicator {
             range: IndicatorRange::Auto,
             levels: Vec::new(),
             vectors: Vec::new(),
+            fills: Vec::new(),
+            scale_groups: Vec::new(),
             visible: true,
             precision: 2,
+            group: None,
+            autoscale: true,
+            params: None,
+            needs_recompute: false,
         }
     }
 
@@ -505,11 +1028,82 @@ impl Indicator {
         self
     }
 
+    /// Fill the area between two vectors (by index)
+    pub fn add_fill(mut self, fill: IndicatorFill) -> Self {
+        self.fills.push(fill);
+        self
+    }
+
+    /// Add an independent scale for the vectors naming it, see
+    /// [`ScaleGroup`]
+    pub fn add_scale_group(mut self, group: ScaleGroup) -> Self {
+        self.scale_groups.push(group);
+        self
+    }
+
+    /// Scale group a vector is drawn against, `None` for the indicator's
+    /// own range (also when it names a group the indicator lacks)
+    pub fn scale_group_of(&self, vector: &IndicatorVector) -> Option<&ScaleGroup> {
+        let id = vector.scale_group.as_deref()?;
+        self.scale_groups.iter().find(|group| group.id == id)
+    }
+
+    /// Keep an overlay's values out of the automatic price range, so a
+    /// far-off projection does not squash the bars
+    pub fn with_autoscale(mut self, autoscale: bool) -> Self {
+        self.autoscale = autoscale;
+        self
+    }
+
     pub fn precision(mut self, p: u8) -> Self {
         self.precision = p;
         self
     }
 
+    /// Put the indicator in a legend group
+    pub fn group(mut self, group: &str) -> Self {
+        self.group = Some(group.to_string());
+        self
+    }
+
+    /// Copy with smoothed vectors' values replaced by their rendered SMA,
+    /// or `None` when no vector is smoothed
+    pub fn smoothed(&self) -> Option<Indicator> {
+        if !self
+            .vectors
+            .iter()
+            .any(|v| matches!(v.display_values(), Cow::Owned(_)))
+        {
+            return None;
+        }
+        let mut indicator = self.clone();
+        for vector in &mut indicator.vectors {
+            vector.values = vector.display_values().into_owned();
+            vector.smoothing = None;
+        }
+        Some(indicator)
+    }
+
+    /// Keep only the first `len` bars of every vector, e.g. to replay the
+    /// indicator up to a bar
+    pub fn truncate(&mut self, len: usize) {
+        for vector in &mut self.vectors {
+            vector.values.truncate(len);
+            vector.directions.truncate(len);
+        }
+    }
+
+    /// Drop the first `count` bars of every vector, e.g. when the chart
+    /// starts later in the data
+    pub fn drop_first(&mut self, count: usize) {
+        for vector in &mut self.vectors {
+            vector.values.drain(..count.min(vector.values.len()));
+            vector
+                .directions
+                .drain(..count.min(vector.directions.len()));
+        }
+    }
+
     // =========================================================================
     // Single-line convenience (creates one vector)
     // =========================================================================
@@ -606,6 +1200,21 @@ impl Indicator {
                 .unwrap_or(true)
     }
 
+    /// Labeled value of every legend-visible vector at a bar
+    ///
+    /// Missing or warm-up values are returned as `NaN` so tooltips can
+    /// render a placeholder.
+    pub fn values_at(&self, bar_index: usize) -> Vec<(String, f64)> {
+        self.vectors
+            .iter()
+            .filter(|v| v.show_in_legend)
+            .map(|v| {
+                let value = v.values.get(bar_index).copied().unwrap_or(f64::NAN);
+                (v.display_label().to_string(), value)
+            })
+            .collect()
+    }
+
     /// Calculate range across all vectors
     pub fn calculate_range(&self) -> (f64, f64) {
         match &self.range {
@@ -651,6 +1260,10 @@ impl Indicator {
     /// Simple Moving Average
     pub fn sma(id: &str, period: u32, color: &str) -> Self {
         Self::new(id, &format!("SMA {}", period))
+            .with_params(IndicatorParams::Sma {
+                period: period as usize,
+                source: Source::Close,
+            })
             .overlay()
             .range(IndicatorRange::Price)
             .add_vector(IndicatorVector::new("SMA", VectorStyle::line(color, 1.0)))
@@ -659,6 +1272,10 @@ impl Indicator {
     /// Exponential Moving Average
     pub fn ema(id: &str, period: u32, color: &str) -> Self {
         Self::new(id, &format!("EMA {}", period))
+            .with_params(IndicatorParams::Ema {
+                period: period as usize,
+                source: Source::Close,
+            })
             .overlay()
             .range(IndicatorRange::Price)
             .add_vector(IndicatorVector::new("EMA", VectorStyle::line(color, 1.0)))
@@ -666,21 +1283,20 @@ impl Indicator {
 
     /// Bollinger Bands (3 vectors: middle, upper, lower)
     pub fn bollinger(id: &str, period: u32) -> Self {
+        Self::bollinger_styled(id, period, &BandStyle::bollinger())
+    }
+
+    /// Bollinger Bands with their lines, fill and markers set by `style`
+    pub fn bollinger_styled(id: &str, period: u32, style: &BandStyle) -> Self {
         Self::new(id, &format!("BB {}", period))
+            .with_params(IndicatorParams::Bollinger {
+                period: period as usize,
+                multiplier: 2.0,
+                source: Source::Close,
+            })
             .overlay()
             .range(IndicatorRange::Price)
-            .add_vector(IndicatorVector::new(
-                "Middle",
-                VectorStyle::line("#2196F3", 1.0),
-            ))
-            .add_vector(IndicatorVector::new(
-                "Upper",
-                VectorStyle::line("#2196F380", 1.0),
-            ))
-            .add_vector(IndicatorVector::new(
-                "Lower",
-                VectorStyle::line("#2196F380", 1.0),
-            ))
+            .band(style)
     }
 
     /// Bollinger Bands with cloud fill
@@ -694,51 +1310,83 @@ impl Indicator {
             ))
             .add_vector(IndicatorVector::new(
                 "Upper",
-                VectorStyle::cloud("#2196F320", "#2196F320", 2),
+                VectorStyle::line("#2196F380", 1.0),
             ))
             .add_vector(
                 IndicatorVector::new("Lower", VectorStyle::line("#2196F380", 1.0))
                     .hide_from_legend(),
             )
+            .add_fill(IndicatorFill::new(1, 2, "#2196F3").with_alpha(0.2))
     }
 
     /// Keltner Channels (3 vectors)
     pub fn keltner(id: &str, period: u32) -> Self {
+        Self::keltner_styled(id, period, &BandStyle::keltner())
+    }
+
+    /// Keltner Channels with their lines, fill and markers set by `style`
+    pub fn keltner_styled(id: &str, period: u32, style: &BandStyle) -> Self {
         Self::new(id, &format!("Keltner {}", period))
+            .with_params(IndicatorParams::Keltner {
+                period: period as usize,
+                multiplier: 2.0,
+            })
             .overlay()
             .range(IndicatorRange::Price)
-            .add_vector(IndicatorVector::new(
-                "Middle",
-                VectorStyle::line("#FF9800", 1.0),
-            ))
-            .add_vector(IndicatorVector::new(
-                "Upper",
-                VectorStyle::line("#FF980080", 1.0),
-            ))
-            .add_vector(IndicatorVector::new(
-                "Lower",
-                VectorStyle::line("#FF980080", 1.0),
-            ))
+            .band(style)
     }
 
     /// Donchian Channels (2 vectors: upper, lower + fill)
     pub fn donchian(id: &str, period: u32) -> Self {
+        Self::donchian_styled(id, period, &BandStyle::donchian())
+    }
+
+    /// Donchian Channels with their lines, fill and markers set by `style`
+    pub fn donchian_styled(id: &str, period: u32, style: &BandStyle) -> Self {
         Self::new(id, &format!("Donchian {}", period))
+            .with_params(IndicatorParams::Donchian {
+                period: period as usize,
+            })
             .overlay()
             .range(IndicatorRange::Price)
-            .add_vector(IndicatorVector::new(
-                "Upper",
-                VectorStyle::line("#4CAF50", 1.0),
-            ))
-            .add_vector(IndicatorVector::new(
-                "Lower",
-                VectorStyle::line("#F44336", 1.0),
-            ))
+            .band(style)
+    }
+
+    /// Add the vectors and fill of a band: middle (when shown), upper,
+    /// lower, then the outside markers (when enabled)
+    fn band(mut self, style: &BandStyle) -> Self {
+        if style.show_middle {
+            self = self.add_vector(IndicatorVector::new("Middle", style.middle.style()));
+        }
+        let upper = self.vectors.len();
+        self = self
+            .add_vector(IndicatorVector::new("Upper", style.upper.style()))
+            .add_vector(IndicatorVector::new("Lower", style.lower.style()));
+        if style.fill {
+            self = self.add_fill(
+                IndicatorFill::new(upper, upper + 1, &style.fill_color)
+                    .with_alpha(style.fill_alpha),
+            );
+        }
+        if style.outside_markers {
+            self = self.add_vector(
+                IndicatorVector::new(
+                    OUTSIDE_BAND_VECTOR,
+                    VectorStyle::dots_directional(&style.above_color, &style.below_color, 3.0),
+                )
+                .hide_from_legend(),
+            );
+        }
+        self
     }
 
     /// RSI (1 vector + levels)
     pub fn rsi(id: &str, period: u32) -> Self {
         Self::new(id, &format!("RSI {}", period))
+            .with_params(IndicatorParams::Rsi {
+                period: period as usize,
+                source: Source::Close,
+            })
             .subpane(0.15)
             .fixed_range(0.0, 100.0)
             .add_level(
@@ -767,6 +1415,10 @@ impl Indicator {
     /// Stochastic (2 vectors: %K, %D)
     pub fn stochastic(id: &str, k: u32, d: u32) -> Self {
         Self::new(id, &format!("Stoch ({},{})", k, d))
+            .with_params(IndicatorParams::Stochastic {
+                k: k as usize,
+                d: d as usize,
+            })
             .subpane(0.15)
             .fixed_range(0.0, 100.0)
             .add_level(IndicatorLevel::new(80.0, "#ef5350"))
@@ -784,6 +1436,12 @@ impl Indicator {
     /// MACD (3 vectors: MACD line, Signal line, Histogram)
     pub fn macd(id: &str, fast: u32, slow: u32, signal: u32) -> Self {
         Self::new(id, &format!("MACD ({},{},{})", fast, slow, signal))
+            .with_params(IndicatorParams::Macd {
+                fast: fast as usize,
+                slow: slow as usize,
+                signal: signal as usize,
+                source: Source::Close,
+            })
             .subpane(0.2)
             .range(IndicatorRange::Symmetric)
             .add_level(IndicatorLevel::new(0.0, "#787b86").dotted())
@@ -795,7 +1453,9 @@ impl Indicator {
                 "Signal",
                 VectorStyle::line("#FF9800", 1.0),
             ))
-            .add_vector(IndicatorVector::new("Histogram", VectorStyle::histogram()))
+            .add_vector(
+                IndicatorVector::new("Histogram", VectorStyle::histogram()).with_label("Hist"),
+            )
     }
 
     /// MACD default (12, 26, 9)
@@ -819,9 +1479,118 @@ impl Indicator {
             .add_vector(IndicatorVector::new("Volume", VectorStyle::histogram()))
     }
 
+    /// Volume split into aggressive buy and sell volume
+    ///
+    /// `splits` holds `(buy, sell)` per bar. Stacked mode draws the bar total in
+    /// the up color with the sell part over it in the down color, so the buy part
+    /// shows on top. Mirrored mode draws buy above zero and sell below it.
+    /// The name carries the last bar's buy/sell values for the legend.
+    pub fn volume_split(id: &str, splits: &[(f64, f64)], mode: VolumeSplitMode) -> Self {
+        Self::split_volume(id, splits, mode, None)
+    }
+
+    /// Stacked [`Self::volume_split`] over the bars' total `volumes`
+    ///
+    /// Bars missing either side of their split draw their total volume
+    /// instead, colored by `directions` like [`Self::volume`].
+    pub fn volume_breakdown(
+        id: &str,
+        volumes: &[f64],
+        directions: &[bool],
+        splits: &[(f64, f64)],
+    ) -> Self {
+        Self::split_volume(
+            id,
+            splits,
+            VolumeSplitMode::Stacked,
+            Some((volumes, directions)),
+        )
+    }
+
+    fn split_volume(
+        id: &str,
+        splits: &[(f64, f64)],
+        mode: VolumeSplitMode,
+        fallback: Option<(&[f64], &[bool])>,
+    ) -> Self {
+        let up = default_up_color();
+        let down = default_down_color();
+        // Zero sides are left as gaps so they don't draw a 1px sliver
+        let side = |v: f64| if v > 0.0 { v } else { f64::NAN };
+
+        let (upper, lower): (Vec<f64>, Vec<f64>) = match mode {
+            VolumeSplitMode::Stacked => splits
+                .iter()
+                .enumerate()
+                .map(|(i, &(buy, sell))| match fallback {
+                    Some((volumes, _)) if buy.is_nan() || sell.is_nan() => {
+                        (volumes.get(i).copied().unwrap_or(f64::NAN), f64::NAN)
+                    }
+                    _ => (side(buy.max(0.0) + sell.max(0.0)), side(sell)),
+                })
+                .unzip(),
+            VolumeSplitMode::Mirrored => splits
+                .iter()
+                .map(|&(buy, sell)| (side(buy), -side(sell)))
+                .unzip(),
+        };
+        // Split bars are up colored; fallback bars follow the price
+        let directions: Vec<bool> = match fallback {
+            Some((_, directions)) => splits
+                .iter()
+                .enumerate()
+                .map(|(i, &(buy, sell))| {
+                    !(buy.is_nan() || sell.is_nan()) || directions.get(i).copied().unwrap_or(true)
+                })
+                .collect(),
+            None => Vec::new(),
+        };
+
+        let name = match splits.last() {
+            Some(&(buy, sell)) if !buy.is_nan() && !sell.is_nan() => format!(
+                "Volume B {} S {}",
+                crate::core::format_indicator_value(buy),
+                crate::core::format_indicator_value(sell)
+            ),
+            _ => "Volume".to_string(),
+        };
+
+        // Stacked bars grow from zero, so the range spans 0..max total
+        let max_total = upper
+            .iter()
+            .copied()
+            .filter(|v| !v.is_nan())
+            .fold(0.0, f64::max);
+        let range = match mode {
+            VolumeSplitMode::Stacked if max_total > 0.0 => IndicatorRange::fixed(0.0, max_total),
+            VolumeSplitMode::Stacked => IndicatorRange::Auto,
+            VolumeSplitMode::Mirrored => IndicatorRange::Symmetric,
+        };
+        let upper_name = match mode {
+            VolumeSplitMode::Stacked => "Total",
+            VolumeSplitMode::Mirrored => "Buy",
+        };
+
+        Self::new(id, &name)
+            .overlay_bottom(0.2)
+            .range(range)
+            .add_vector(
+                IndicatorVector::new(upper_name, VectorStyle::histogram_colored(&up, &down))
+                    .with_values(upper)
+                    .with_directions(directions),
+            )
+            .add_vector(
+                IndicatorVector::new("Sell", VectorStyle::histogram_colored(&down, &down))
+                    .with_values(lower),
+            )
+    }
+
     /// ATR
     pub fn atr(id: &str, period: u32) -> Self {
         Self::new(id, &format!("ATR {}", period))
+            .with_params(IndicatorParams::Atr {
+                period: period as usize,
+            })
             .subpane(0.12)
             .range(IndicatorRange::Auto)
             .add_vector(IndicatorVector::new(
@@ -833,6 +1602,9 @@ impl Indicator {
     /// ADX (3 vectors: ADX, +DI, -DI)
     pub fn adx(id: &str, period: u32) -> Self {
         Self::new(id, &format!("ADX {}", period))
+            .with_params(IndicatorParams::Adx {
+                period: period as usize,
+            })
             .subpane(0.15)
             .fixed_range(0.0, 100.0)
             .add_level(IndicatorLevel::new(25.0, "#787b86").dotted())
@@ -853,6 +1625,9 @@ impl Indicator {
     /// CCI
     pub fn cci(id: &str, period: u32) -> Self {
         Self::new(id, &format!("CCI {}", period))
+            .with_params(IndicatorParams::Cci {
+                period: period as usize,
+            })
             .subpane(0.15)
             .range(IndicatorRange::Symmetric)
             .add_level(IndicatorLevel::new(100.0, "#ef5350"))
@@ -867,6 +1642,9 @@ impl Indicator {
     /// Williams %R
     pub fn williams_r(id: &str, period: u32) -> Self {
         Self::new(id, &format!("Williams %R {}", period))
+            .with_params(IndicatorParams::WilliamsR {
+                period: period as usize,
+            })
             .subpane(0.15)
             .fixed_range(-100.0, 0.0)
             .add_level(IndicatorLevel::new(-20.0, "#ef5350"))
@@ -907,25 +1685,43 @@ impl Indicator {
     /// Parabolic SAR (dots)
     pub fn psar(id: &str) -> Self {
         Self::new(id, "Parabolic SAR")
+            .with_params(IndicatorParams::Psar {
+                step: 0.02,
+                max_step: 0.2,
+            })
             .overlay()
             .range(IndicatorRange::Price)
             .add_vector(IndicatorVector::new(
                 "SAR",
-                VectorStyle::dots("#FF9800", 2.0),
+                VectorStyle::dots_directional("#26a69a", "#ef5350", 2.0),
             ))
     }
 
-    /// Supertrend (2 vectors: line + direction for coloring)
+    /// Supertrend (2 vectors: line colored by its directions, and the close
+    /// it is filled against by [`Self::with_supertrend_fill`])
     pub fn supertrend(id: &str, period: u32, multiplier: f64) -> Self {
         Self::new(id, &format!("Supertrend ({}, {})", period, multiplier))
+            .with_params(IndicatorParams::Supertrend {
+                period: period as usize,
+                multiplier,
+            })
             .overlay()
             .range(IndicatorRange::Price)
             .add_vector(IndicatorVector::new(
                 "Supertrend",
-                VectorStyle::line("#2196F3", 2.0),
+                VectorStyle::step_directional("#26a69a", "#ef5350", 2.0),
             ))
-            // Direction vector: 1 = bullish (green), -1 = bearish (red)
-            .add_vector(IndicatorVector::new("Direction", VectorStyle::hidden()).hide_from_legend())
+            .add_vector(IndicatorVector::new("Close", VectorStyle::hidden()).hide_from_legend())
+    }
+
+    /// Translucent fill between a Supertrend line and the close, red while
+    /// the line is above price and green while below
+    pub fn with_supertrend_fill(self) -> Self {
+        self.add_fill(
+            IndicatorFill::new(0, 1, "#ef5350")
+                .with_crossed_color("#26a69a")
+                .with_alpha(0.1),
+        )
     }
 
     /// VWAP
@@ -981,6 +1777,10 @@ impl Indicator {
     /// Weighted Moving Average
     pub fn wma(id: &str, period: u32, color: &str) -> Self {
         Self::new(id, &format!("WMA {}", period))
+            .with_params(IndicatorParams::Wma {
+                period: period as usize,
+                source: Source::Close,
+            })
             .overlay()
             .range(IndicatorRange::Price)
             .add_vector(IndicatorVector::new("WMA", VectorStyle::line(color, 1.0)))
@@ -989,6 +1789,10 @@ impl Indicator {
     /// Hull Moving Average
     pub fn hma(id: &str, period: u32, color: &str) -> Self {
         Self::new(id, &format!("HMA {}", period))
+            .with_params(IndicatorParams::Hma {
+                period: period as usize,
+                source: Source::Close,
+            })
             .overlay()
             .range(IndicatorRange::Price)
             .add_vector(IndicatorVector::new("HMA", VectorStyle::line(color, 1.5)))
@@ -1198,6 +2002,9 @@ impl Indicator {
     /// Money Flow Index (MFI)
     pub fn mfi(id: &str, period: u32) -> Self {
         Self::new(id, &format!("MFI {}", period))
+            .with_params(IndicatorParams::Mfi {
+                period: period as usize,
+            })
             .subpane(0.15)
             .fixed_range(0.0, 100.0)
             .add_level(IndicatorLevel::new(80.0, "#ef5350").with_label("Overbought"))
@@ -1215,6 +2022,7 @@ impl Indicator {
     /// On-Balance Volume (OBV)
     pub fn obv(id: &str) -> Self {
         Self::new(id, "OBV")
+            .with_params(IndicatorParams::Obv)
             .subpane(0.12)
             .range(IndicatorRange::Auto)
             .add_vector(IndicatorVector::new(
@@ -1226,6 +2034,7 @@ impl Indicator {
     /// Accumulation/Distribution Line
     pub fn ad_line(id: &str) -> Self {
         Self::new(id, "A/D Line")
+            .with_params(IndicatorParams::AdLine)
             .subpane(0.12)
             .range(IndicatorRange::Auto)
             .add_vector(IndicatorVector::new(
@@ -1237,6 +2046,9 @@ impl Indicator {
     /// Chaikin Money Flow (CMF)
     pub fn cmf(id: &str, period: u32) -> Self {
         Self::new(id, &format!("CMF {}", period))
+            .with_params(IndicatorParams::Cmf {
+                period: period as usize,
+            })
             .subpane(0.15)
             .fixed_range(-1.0, 1.0)
             .add_level(IndicatorLevel::new(0.0, "#787b86").dotted())
@@ -1481,14 +2293,15 @@ impl Indicator {
             ))
     }
 
-    /// ZigZag
+    /// ZigZag (values from `compute::zigzag`: defined only at pivots)
     pub fn zigzag(id: &str, deviation: f64) -> Self {
         Self::new(id, &format!("ZigZag {}%", deviation))
+            .with_params(IndicatorParams::Zigzag { deviation })
             .overlay()
             .range(IndicatorRange::Price)
             .add_vector(IndicatorVector::new(
                 "ZigZag",
-                VectorStyle::line("#FF9800", 2.0),
+                VectorStyle::pivots("#FF9800", 2.0, PivotLabels::None),
             ))
     }
 
@@ -1528,6 +2341,7 @@ impl Indicator {
                 "Lower",
                 VectorStyle::line("#ef5350", 1.0),
             ))
+            .add_fill(IndicatorFill::new(0, 1, "#2196F3"))
     }
 
     /// Average True Range Bands
@@ -2009,6 +2823,24 @@ impl Default for StrategyTheme {
     }
 }
 
+impl StrategyTheme {
+    /// Resolve a color that may name a theme slot
+    ///
+    /// `"primary"`, `"secondary"`, `"buy"`, `"sell"`, `"profit"` and `"loss"`
+    /// map to this theme's colors; anything else is returned unchanged.
+    pub fn resolve<'a>(&'a self, color: &'a str) -> &'a str {
+        match color {
+            "primary" => &self.primary,
+            "secondary" => &self.secondary,
+            "buy" => &self.buy,
+            "sell" => &self.sell,
+            "profit" => &self.profit,
+            "loss" => &self.loss,
+            _ => color,
+        }
+    }
+}
+
 impl Strategy {
     pub fn new(id: &str, name: &str) -> Self {
         Self {
@@ -2023,6 +2855,11 @@ impl Strategy {
         }
     }
 
+    /// Parse a strategy from its JSON form
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+
     pub fn with_description(mut self, desc: &str) -> Self {
         self.description = desc.to_string();
         self
@@ -2072,6 +2909,91 @@ impl Strategy {
             primitive.visible = visible;
         }
     }
+
+    /// Replace theme slot names in colors with concrete colors
+    ///
+    /// Uses the strategy's own theme when set, otherwise `fallback`.
+    /// Primitives without a color get the theme's primary color.
+    pub fn resolve_colors(&mut self, fallback: &StrategyTheme) {
+        let theme = self.theme.clone().unwrap_or_else(|| fallback.clone());
+        let resolve = |color: &mut String| {
+            let resolved = theme.resolve(color).to_string();
+            *color = resolved;
+        };
+
+        for indicator in &mut self.indicators {
+            for vector in &mut indicator.vectors {
+                match &mut vector.style {
+                    VectorStyle::Line { color, .. }
+                    | VectorStyle::Area { color, .. }
+                    | VectorStyle::Pivots { color, .. } => resolve(color),
+                    VectorStyle::Dots {
+                        color, down_color, ..
+                    }
+                    | VectorStyle::Step {
+                        color, down_color, ..
+                    } => {
+                        resolve(color);
+                        if let Some(down) = down_color {
+                            resolve(down);
+                        }
+                    }
+                    VectorStyle::Histogram {
+                        up_color,
+                        down_color,
+                        ..
+                    } => {
+                        resolve(up_color);
+                        resolve(down_color);
+                    }
+                    VectorStyle::Cloud {
+                        color_above,
+                        color_below,
+                        ..
+                    } => {
+                        resolve(color_above);
+                        resolve(color_below);
+                    }
+                    VectorStyle::Hidden => {}
+                }
+            }
+            for fill in &mut indicator.fills {
+                resolve(&mut fill.color);
+                if let Some(crossed) = &mut fill.crossed_color {
+                    resolve(crossed);
+                }
+            }
+        }
+
+        for signal in &mut self.signals {
+            match &mut signal.visual {
+                SignalVisual::Arrow { color, .. }
+                | SignalVisual::Dot { color, .. }
+                | SignalVisual::Flag { color, .. } => resolve(color),
+                SignalVisual::Label {
+                    color, background, ..
+                } => {
+                    resolve(color);
+                    if let Some(bg) = background {
+                        resolve(bg);
+                    }
+                }
+                SignalVisual::Primitive { color, .. } => {
+                    if let Some(c) = color {
+                        resolve(c);
+                    }
+                }
+                SignalVisual::Emoji { .. } => {}
+            }
+        }
+
+        for primitive in &mut self.primitives {
+            match &mut primitive.color {
+                Some(c) => resolve(c),
+                None => primitive.color = Some(theme.primary.clone()),
+            }
+        }
+    }
 }
 
 // =============================================================================
@@ -2199,4 +3121,99 @@ mod tests {
         assert_eq!(parsed.id, "test");
         assert_eq!(parsed.vector_count(), 3);
     }
+
+    #[test]
+    fn test_volume_split_one_side_zero() {
+        let splits = [(100.0, 0.0), (0.0, 50.0), (30.0, 20.0)];
+
+        let stacked = Indicator::volume_split("vol", &splits, VolumeSplitMode::Stacked);
+        assert_eq!(stacked.vector_count(), 2);
+        assert_eq!(stacked.vectors[0].values[..2], [100.0, 50.0]);
+        assert!(stacked.vectors[1].values[0].is_nan());
+        assert_eq!(stacked.vectors[1].values[1], 50.0);
+        assert_eq!(stacked.range, IndicatorRange::fixed(0.0, 100.0));
+        assert_eq!(stacked.name, "Volume B 30.00 S 20.00");
+
+        let mirrored = Indicator::volume_split("vol", &splits, VolumeSplitMode::Mirrored);
+        assert!(mirrored.vectors[0].values[1].is_nan());
+        assert_eq!(mirrored.vectors[1].values[1], -50.0);
+        assert_eq!(mirrored.range, IndicatorRange::Symmetric);
+    }
+
+    #[test]
+    fn test_values_at_labels() {
+        let mut macd = Indicator::macd_default("macd");
+        macd.set_all_values(vec![
+            vec![f64::NAN, 1.5],
+            vec![f64::NAN, 1.0],
+            vec![f64::NAN, 0.5],
+        ]);
+
+        let values = macd.values_at(1);
+        let labels: Vec<&str> = values.iter().map(|(l, _)| l.as_str()).collect();
+        assert_eq!(labels, ["MACD", "Signal", "Hist"]);
+        assert_eq!(values[2].1, 0.5);
+
+        assert!(macd.values_at(0).iter().all(|(_, v)| v.is_nan()));
+        assert_eq!(macd.values_at(99).len(), 3);
+    }
+
+    #[test]
+    fn test_strategy_resolve_colors() {
+        let fallback = StrategyTheme {
+            buy: "#00ff00".to_string(),
+            ..StrategyTheme::default()
+        };
+        let mut strategy = Strategy::new("s", "S")
+            .add_signal(Signal::new(1.0, 100.0, SignalVisual::arrow_up("buy")))
+            .add_signal(Signal::new(2.0, 100.0, SignalVisual::dot("#123456", 3.0)))
+            .add_primitive(StrategyPrimitive::horizontal_line(0.0, 100.0));
+        strategy.resolve_colors(&fallback);
+
+        let colors: Vec<&str> = strategy
+            .signals
+            .iter()
+            .map(|s| match &s.visual {
+                SignalVisual::Arrow { color, .. } | SignalVisual::Dot { color, .. } => {
+                    color.as_str()
+                }
+                _ => unreachable!(),
+            })
+            .collect();
+        assert_eq!(colors, ["#00ff00", "#123456"]);
+        assert_eq!(strategy.primitives[0].color.as_deref(), Some("#2196F3"));
+
+        // The strategy's own theme wins over the fallback
+        let mut themed = Strategy::new("t", "T")
+            .with_theme(StrategyTheme::default())
+            .add_signal(Signal::new(1.0, 100.0, SignalVisual::arrow_up("buy")));
+        themed.resolve_colors(&fallback);
+        match &themed.signals[0].visual {
+            SignalVisual::Arrow { color, .. } => assert_eq!(color, "#26a69a"),
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn test_strategy_from_json() {
+        let json = r#"{
+            "id": "bt",
+            "name": "Backtest",
+            "signals": [
+                {"bar": 3, "price": 101.5, "visual": {"type": "arrow", "direction": "up", "color": "buy"}}
+            ],
+            "primitives": [
+                {"primitive_type": "trend_line", "points": [[1, 100], [5, 105]], "color": null}
+            ]
+        }"#;
+        let strategy = Strategy::from_json(json).unwrap();
+        assert!(strategy.visible);
+        assert_eq!(strategy.signals.len(), 1);
+        assert_eq!(
+            strategy.primitives[0].points,
+            vec![(1.0, 100.0), (5.0, 105.0)]
+        );
+
+        assert!(Strategy::from_json("{}").is_err());
+    }
 }
@@ -10,6 +10,7 @@
 //! - `annotations` - Data-point annotations (Markers, Price Lines)
 
 pub mod annotations;
+pub mod indicator_params;
 pub mod indicators;
 pub mod overlays;
 pub mod series;
@@ -29,9 +30,12 @@ pub use series::{
     BaselineData,
     BaselineSeriesOptions,
     BaselineStyleOptions,
+    CandleWidthMode,
     CandlestickData,
     CandlestickSeriesOptions,
     CandlestickStyleOptions,
+    DenseCandles,
+    HaWickSource,
     HistogramData,
     HistogramSeriesOptions,
     HistogramStyleOptions,
@@ -66,11 +70,25 @@ pub use overlays::{
     // Legend
     Legend,
     LegendData,
+    LegendIndicator,
     LegendPosition,
+    LegendValue,
+    LogoPosition,
+    // Volume by price
+    PriceBucket,
+    // Seasonal
+    SeasonalLine,
+    SeasonalPeriod,
+    Source,
     VertAlign,
     Watermark,
+    WatermarkLayout,
     WatermarkLine,
+    WatermarkLogo,
     get_compare_color,
+    point_of_control,
+    seasonal_lines,
+    volume_by_price,
 };
 
 // =============================================================================
@@ -84,10 +102,15 @@ pub use annotations::{
 // =============================================================================
 // Indicators re-exports
 // =============================================================================
+pub use indicator_params::{IndicatorParams, IndicatorPropertyError};
 pub use indicators::{
     ArrowDirection,
+    // Band indicators
+    BandLine,
+    BandStyle,
     // Core types
     Indicator,
+    IndicatorFill,
     IndicatorLevel,
     IndicatorPlacement,
     IndicatorRange,
@@ -96,6 +119,9 @@ pub use indicators::{
     IndicatorStyle,
     // Vector/style
     IndicatorVector,
+    OUTSIDE_BAND_VECTOR,
+    PivotLabels,
+    ScaleGroup,
     // Signals
     Signal,
     SignalVisual,
@@ -104,4 +130,5 @@ pub use indicators::{
     StrategyPrimitive,
     StrategyTheme,
     VectorStyle,
+    VolumeSplitMode,
 };
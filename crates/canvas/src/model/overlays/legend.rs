@@ -3,6 +3,7 @@
 //! Shows OHLC values, change, and percentage change for the bar
 //! under the cursor or the last bar.
 
+use crate::model::SeriesType;
 use serde::{Deserialize, Serialize};
 
 // =============================================================================
@@ -25,6 +26,20 @@ pub enum LegendPosition {
     BottomRight,
 }
 
+impl std::str::FromStr for LegendPosition {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().replace(['_', '-', ' '], "").as_str() {
+            "topleft" => Ok(Self::TopLeft),
+            "topright" => Ok(Self::TopRight),
+            "bottomleft" => Ok(Self::BottomLeft),
+            "bottomright" => Ok(Self::BottomRight),
+            _ => Err(()),
+        }
+    }
+}
+
 // =============================================================================
 // Legend Configuration
 // =============================================================================
@@ -126,24 +141,62 @@ impl Legend {
 // =============================================================================
 
 /// Data to display in legend
-#[derive(Clone, Debug)]
+///
+/// [`from_bar`](Self::from_bar) fills just the OHLC part; the chart
+/// renderer's `legend_data` also fills the bar position and the legend's
+/// indicator entries.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
 pub struct LegendData {
+    /// Index of the bar the values are taken from
+    pub index: usize,
+    pub timestamp: i64,
     pub open: f64,
     pub high: f64,
     pub low: f64,
     pub close: f64,
+    pub volume: f64,
     pub prev_close: Option<f64>,
+    /// Series title, if the chart has one
+    pub series_name: Option<String>,
+    pub series_type: SeriesType,
+    /// Indicator entries in legend order (grouped members follow each other)
+    pub indicators: Vec<LegendIndicator>,
+}
+
+/// One indicator row of the legend
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct LegendIndicator {
+    pub id: String,
+    pub name: String,
+    /// Group header the entry is listed under
+    pub group: Option<String>,
+    /// `false` for an entry toggled off, drawn greyed out
+    pub visible: bool,
+    pub values: Vec<LegendValue>,
+}
+
+/// Value of one indicator vector at the legend's bar
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct LegendValue {
+    pub label: String,
+    /// `None` where the vector has no value (warm-up or gap)
+    pub value: Option<f64>,
+    /// CSS color the vector is drawn with at this bar
+    pub color: String,
 }
 
 impl LegendData {
     /// Create legend data from bar
     pub fn from_bar(bar: &crate::Bar, prev_close: Option<f64>) -> Self {
         Self {
+            timestamp: bar.timestamp,
             open: bar.open,
             high: bar.high,
             low: bar.low,
             close: bar.close,
+            volume: bar.volume,
             prev_close,
+            ..Default::default()
         }
     }
 
@@ -210,6 +263,7 @@ mod tests {
             low: 98.0,
             close: 103.0,
             prev_close: Some(100.0),
+            ..Default::default()
         };
 
         assert_eq!(data.change(), Some(3.0));
@@ -224,6 +278,7 @@ mod tests {
             low: 98.0,
             close: 103.0,
             prev_close: Some(100.0),
+            ..Default::default()
         };
 
         let legend = Legend::default();
@@ -244,6 +299,7 @@ mod tests {
             low: 95.0,
             close: 97.0,
             prev_close: Some(100.0),
+            ..Default::default()
         };
 
         let legend = Legend::default();
@@ -5,14 +5,23 @@
 //! - Legend for OHLC display
 //! - Watermark for branding
 //! - Compare for symbol comparison overlays
+//! - Seasonal for overlaying periods of the same series
+//! - Volume by price along the right edge of the main pane
 
 pub mod compare;
 pub mod grid;
 pub mod legend;
+pub mod seasonal;
+pub mod volume_by_price;
 pub mod watermark;
 
 // Re-exports
 pub use compare::{COMPARE_COLORS, CompareOverlay, CompareSeries, get_compare_color};
 pub use grid::{GridLineOptions, GridOptions};
-pub use legend::{Legend, LegendData, LegendPosition};
-pub use watermark::{FontStyle, HorzAlign, VertAlign, Watermark, WatermarkLine};
+pub use legend::{Legend, LegendData, LegendIndicator, LegendPosition, LegendValue};
+pub use seasonal::{SeasonalLine, SeasonalPeriod, Source, seasonal_lines};
+pub use volume_by_price::{PriceBucket, point_of_control, volume_by_price};
+pub use watermark::{
+    FontStyle, HorzAlign, LogoPosition, VertAlign, Watermark, WatermarkLayout, WatermarkLine,
+    WatermarkLogo,
+};
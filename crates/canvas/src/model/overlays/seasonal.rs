@@ -0,0 +1,280 @@
+//! Seasonal Overlay - one line per period on a shared in-period x axis
+//!
+//! Bars are split by calendar period (each year, or each day for intraday
+//! data). Every period becomes a line whose x is the position within the
+//! period (0.0 at the start, 1.0 at the end) and whose y is the percentage
+//! change from the period's first value, so years can be compared on a
+//! common January-December axis.
+
+use crate::Bar;
+use crate::coords::time_scale::{civil_from_days, days_from_civil};
+use crate::coords::{DAY, HOUR, format_time_pattern};
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+/// Period that splits the series into overlaid lines
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SeasonalPeriod {
+    /// One line per calendar year (UTC), on a Jan-Dec axis
+    #[default]
+    Year,
+    /// One line per day (UTC), on a 00:00-24:00 axis
+    Day,
+}
+
+impl SeasonalPeriod {
+    /// Start and end timestamps of the period containing `ts`
+    pub fn bounds(&self, ts: i64) -> (i64, i64) {
+        match self {
+            Self::Year => {
+                let (year, _, _) = civil_from_days(ts.div_euclid(DAY));
+                (
+                    days_from_civil(year, 1, 1) * DAY,
+                    days_from_civil(year + 1, 1, 1) * DAY,
+                )
+            }
+            Self::Day => {
+                let start = ts.div_euclid(DAY) * DAY;
+                (start, start + DAY)
+            }
+        }
+    }
+
+    /// Legend label of the period starting at `start` ("2024", "2024-03-05")
+    pub fn label(&self, start: i64) -> String {
+        match self {
+            Self::Year => format_time_pattern(start, "%Y"),
+            Self::Day => format_time_pattern(start, "%Y-%m-%d"),
+        }
+    }
+
+    /// Axis ticks as (position within the period, label)
+    ///
+    /// Years are labeled by month name, days every three hours.
+    pub fn ticks(&self) -> Vec<(f64, String)> {
+        match self {
+            Self::Year => {
+                // Month starts in a common year; leap years shift by < 1 day
+                (1..=12)
+                    .map(|month| {
+                        let day = days_from_civil(1970, month, 1);
+                        (day as f64 / 365.0, format_time_pattern(day * DAY, "%b"))
+                    })
+                    .collect()
+            }
+            Self::Day => (0..8)
+                .map(|i| {
+                    let secs = i * 3 * HOUR;
+                    (secs as f64 / DAY as f64, format_time_pattern(secs, "%H:%M"))
+                })
+                .collect(),
+        }
+    }
+}
+
+impl FromStr for SeasonalPeriod {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "year" | "yearly" => Ok(Self::Year),
+            "day" | "daily" => Ok(Self::Day),
+            _ => Err(()),
+        }
+    }
+}
+
+/// Bar value a seasonal line or a price indicator follows
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Source {
+    Open,
+    High,
+    Low,
+    #[default]
+    Close,
+    /// (high + low) / 2
+    Hl2,
+    /// (high + low + close) / 3
+    Hlc3,
+    /// (open + high + low + close) / 4
+    Ohlc4,
+}
+
+impl Source {
+    pub fn value(&self, bar: &Bar) -> f64 {
+        match self {
+            Self::Open => bar.open,
+            Self::High => bar.high,
+            Self::Low => bar.low,
+            Self::Close => bar.close,
+            Self::Hl2 => (bar.high + bar.low) / 2.0,
+            Self::Hlc3 => (bar.high + bar.low + bar.close) / 3.0,
+            Self::Ohlc4 => (bar.open + bar.high + bar.low + bar.close) / 4.0,
+        }
+    }
+
+    /// This source's value for every bar
+    pub fn values(&self, bars: &[Bar]) -> Vec<f64> {
+        bars.iter().map(|bar| self.value(bar)).collect()
+    }
+
+    /// Lowercase name, as parsed and serialized
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Open => "open",
+            Self::High => "high",
+            Self::Low => "low",
+            Self::Close => "close",
+            Self::Hl2 => "hl2",
+            Self::Hlc3 => "hlc3",
+            Self::Ohlc4 => "ohlc4",
+        }
+    }
+}
+
+impl FromStr for Source {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "open" => Ok(Self::Open),
+            "high" => Ok(Self::High),
+            "low" => Ok(Self::Low),
+            "close" => Ok(Self::Close),
+            "hl2" => Ok(Self::Hl2),
+            "hlc3" => Ok(Self::Hlc3),
+            "ohlc4" => Ok(Self::Ohlc4),
+            _ => Err(()),
+        }
+    }
+}
+
+/// One period of a seasonal overlay
+#[derive(Clone, Debug, PartialEq)]
+pub struct SeasonalLine {
+    /// Period label for the legend
+    pub label: String,
+    /// (position within period 0.0-1.0, % change from the period's first value)
+    pub points: Vec<(f64, f64)>,
+    /// Whether this is the latest period
+    pub current: bool,
+}
+
+/// Split bars into one normalized line per period, oldest first
+///
+/// Non-finite values are skipped, as are periods whose first value is zero.
+/// Bars must be sorted by timestamp.
+pub fn seasonal_lines(bars: &[Bar], period: SeasonalPeriod, source: Source) -> Vec<SeasonalLine> {
+    let mut lines: Vec<SeasonalLine> = Vec::new();
+    let mut current: Option<(i64, i64, f64)> = None;
+
+    for bar in bars {
+        let value = source.value(bar);
+        if !value.is_finite() {
+            continue;
+        }
+
+        let (start, end, base) = match current {
+            Some((start, end, base)) if bar.timestamp < end => (start, end, base),
+            _ => {
+                let (start, end) = period.bounds(bar.timestamp);
+                lines.push(SeasonalLine {
+                    label: period.label(start),
+                    points: Vec::new(),
+                    current: false,
+                });
+                (start, end, value)
+            }
+        };
+        current = Some((start, end, base));
+
+        if base == 0.0 {
+            continue;
+        }
+        let position = (bar.timestamp - start) as f64 / (end - start) as f64;
+        let change = (value / base - 1.0) * 100.0;
+        if let Some(line) = lines.last_mut() {
+            line.points.push((position, change));
+        }
+    }
+
+    lines.retain(|line| !line.points.is_empty());
+    if let Some(last) = lines.last_mut() {
+        last.current = true;
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bar(timestamp: i64, close: f64) -> Bar {
+        Bar {
+            timestamp,
+            open: close,
+            high: close,
+            low: close,
+            close,
+            volume: 0.0,
+        }
+    }
+
+    #[test]
+    fn test_source_values() {
+        let bar = Bar {
+            timestamp: 0,
+            open: 1.0,
+            high: 4.0,
+            low: 0.0,
+            close: 3.0,
+            volume: 0.0,
+        };
+        let value = |s: &str| s.parse::<Source>().unwrap().value(&bar);
+        assert_eq!(value("hl2"), 2.0);
+        assert_eq!(value("HLC3"), 7.0 / 3.0);
+        assert_eq!(value("ohlc4"), 2.0);
+        assert_eq!(Source::Ohlc4.as_str(), "ohlc4");
+        assert!("hl3".parse::<Source>().is_err());
+    }
+
+    #[test]
+    fn test_year_bounds_and_leap_years() {
+        // 2024-03-01 00:00 UTC
+        let (start, end) = SeasonalPeriod::Year.bounds(1_709_251_200);
+        assert_eq!(start, 1_704_067_200); // 2024-01-01
+        assert_eq!(end - start, 366 * DAY);
+        assert_eq!(SeasonalPeriod::Year.label(start), "2024");
+
+        let ticks = SeasonalPeriod::Year.ticks();
+        assert_eq!(ticks.len(), 12);
+        assert_eq!(ticks[0], (0.0, "Jan".to_string()));
+        assert_eq!(ticks[11].1, "Dec");
+    }
+
+    #[test]
+    fn test_seasonal_lines_normalize_per_period() {
+        let jan_2023 = 1_672_531_200;
+        let jan_2024 = 1_704_067_200;
+        let bars = [
+            bar(jan_2023, 100.0),
+            bar(jan_2023 + 100 * DAY, 110.0),
+            bar(jan_2024, 50.0),
+            bar(jan_2024 + 183 * DAY, f64::NAN),
+            bar(jan_2024 + 183 * DAY, 40.0),
+        ];
+
+        let lines = seasonal_lines(&bars, SeasonalPeriod::Year, Source::Close);
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0].label, "2023");
+        assert!(!lines[0].current && lines[1].current);
+        assert_eq!(lines[0].points[0], (0.0, 0.0));
+        assert!((lines[0].points[1].0 - 100.0 / 365.0).abs() < 1e-12);
+        assert!((lines[0].points[1].1 - 10.0).abs() < 1e-9);
+        assert_eq!(lines[1].points.len(), 2);
+        assert!((lines[1].points[1].0 - 0.5).abs() < 1e-12);
+        assert!((lines[1].points[1].1 + 20.0).abs() < 1e-9);
+    }
+}
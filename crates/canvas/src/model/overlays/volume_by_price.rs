@@ -0,0 +1,163 @@
+//! Volume by Price - whole-chart volume histogram along the price axis
+//!
+//! Every drawn bar adds its volume to the price bucket its source price
+//! (close, typical price, ...) falls in. Unlike the volume profile drawing
+//! tools, which cover a time range picked by the user, this always covers
+//! the bars on screen.
+
+use super::seasonal::Source;
+use crate::Bar;
+
+/// One price bucket of a volume-by-price histogram
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct PriceBucket {
+    /// Bottom of the bucket's price range
+    pub low: f64,
+    /// Top of the bucket's price range
+    pub high: f64,
+    /// Volume of bars closing at or above their open
+    pub up_volume: f64,
+    /// Volume of bars closing below their open
+    pub down_volume: f64,
+}
+
+impl PriceBucket {
+    /// Up and down volume together
+    pub fn volume(&self) -> f64 {
+        self.up_volume + self.down_volume
+    }
+
+    /// Middle of the bucket's price range
+    pub fn mid(&self) -> f64 {
+        (self.low + self.high) / 2.0
+    }
+}
+
+/// Split the `source` price range of `bars` into `buckets` equal buckets,
+/// lowest first, and add each bar's volume to the bucket of its price
+///
+/// Bars with a non-finite price or a non-finite or non-positive volume are
+/// skipped. Returns no buckets when no bar is left or `buckets` is zero; a
+/// flat range gets a single bucket.
+pub fn volume_by_price(bars: &[Bar], source: Source, buckets: usize) -> Vec<PriceBucket> {
+    let points: Vec<(f64, &Bar)> = bars
+        .iter()
+        .map(|bar| (source.value(bar), bar))
+        .filter(|(price, bar)| price.is_finite() && bar.volume.is_finite() && bar.volume > 0.0)
+        .collect();
+    if points.is_empty() || buckets == 0 {
+        return Vec::new();
+    }
+
+    let (low, high) = points
+        .iter()
+        .fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), &(p, _)| {
+            (lo.min(p), hi.max(p))
+        });
+    let buckets = if high > low { buckets } else { 1 };
+    let step = (high - low) / buckets as f64;
+
+    let mut result: Vec<PriceBucket> = (0..buckets)
+        .map(|i| PriceBucket {
+            low: low + step * i as f64,
+            high: if i + 1 == buckets {
+                high
+            } else {
+                low + step * (i + 1) as f64
+            },
+            ..Default::default()
+        })
+        .collect();
+    for (price, bar) in points {
+        let index = if step > 0.0 {
+            (((price - low) / step) as usize).min(buckets - 1)
+        } else {
+            0
+        };
+        let bucket = &mut result[index];
+        if bar.close >= bar.open {
+            bucket.up_volume += bar.volume;
+        } else {
+            bucket.down_volume += bar.volume;
+        }
+    }
+    result
+}
+
+/// Index of the bucket with the most volume (the point of control), the
+/// lowest one on ties
+pub fn point_of_control(buckets: &[PriceBucket]) -> Option<usize> {
+    buckets
+        .iter()
+        .enumerate()
+        .filter(|(_, b)| b.volume() > 0.0)
+        .fold(None, |best: Option<(usize, f64)>, (i, b)| match best {
+            Some((_, volume)) if volume >= b.volume() => best,
+            _ => Some((i, b.volume())),
+        })
+        .map(|(i, _)| i)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bar(open: f64, close: f64, volume: f64) -> Bar {
+        Bar {
+            timestamp: 0,
+            open,
+            high: open.max(close) + 1.0,
+            low: open.min(close) - 1.0,
+            close,
+            volume,
+        }
+    }
+
+    #[test]
+    fn test_bucket_sums_equal_total_volume() {
+        let bars: Vec<Bar> = (0..200)
+            .map(|i| {
+                let close = 100.0 + (i as f64 * 0.37).sin() * 10.0;
+                bar(close - (i % 3) as f64 + 1.0, close, 10.0 + (i % 7) as f64)
+            })
+            .chain([bar(100.0, 101.0, f64::NAN), bar(100.0, f64::NAN, 5.0)])
+            .collect();
+        let total: f64 = bars[..200].iter().map(|b| b.volume).sum();
+        let up: f64 = bars[..200]
+            .iter()
+            .filter(|b| b.close >= b.open)
+            .map(|b| b.volume)
+            .sum();
+
+        for source in [Source::Close, Source::Hlc3] {
+            let buckets = volume_by_price(&bars, source, 24);
+            assert_eq!(buckets.len(), 24);
+            let sum: f64 = buckets.iter().map(PriceBucket::volume).sum();
+            assert!((sum - total).abs() < 1e-9);
+            let up_sum: f64 = buckets.iter().map(|b| b.up_volume).sum();
+            assert!((up_sum - up).abs() < 1e-9);
+            // Buckets tile the price range without gaps
+            assert!(buckets.windows(2).all(|w| w[0].high == w[1].low));
+        }
+    }
+
+    #[test]
+    fn test_point_of_control_and_flat_range() {
+        let bars = [
+            bar(9.0, 10.0, 5.0),
+            bar(21.0, 20.0, 7.0),
+            bar(19.0, 20.0, 4.0),
+        ];
+        let buckets = volume_by_price(&bars, Source::Close, 2);
+        assert_eq!(buckets[0].up_volume, 5.0);
+        assert_eq!((buckets[1].up_volume, buckets[1].down_volume), (4.0, 7.0));
+        assert_eq!(point_of_control(&buckets), Some(1));
+        assert_eq!(buckets[1].mid(), 17.5);
+
+        let flat = volume_by_price(&bars[1..], Source::Close, 10);
+        assert_eq!(flat.len(), 1);
+        assert_eq!(flat[0].volume(), 11.0);
+        assert!(volume_by_price(&[], Source::Close, 10).is_empty());
+        assert_eq!(point_of_control(&[]), None);
+    }
+}
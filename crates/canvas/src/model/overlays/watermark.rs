@@ -88,6 +88,48 @@ impl WatermarkLine {
     }
 }
 
+// =============================================================================
+// Watermark Logo
+// =============================================================================
+
+/// Which side of the text the logo row sits on
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+#[derive(Default)]
+pub enum LogoPosition {
+    #[default]
+    Above,
+    Below,
+}
+
+/// Small image drawn as its own row of the watermark block
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct WatermarkLogo {
+    /// Image source, typically a `data:image/png;base64,...` URI
+    pub src: String,
+    pub width: f64,
+    pub height: f64,
+    #[serde(default)]
+    pub position: LogoPosition,
+}
+
+impl WatermarkLogo {
+    /// Logo above the text
+    pub fn new(src: impl Into<String>, width: f64, height: f64) -> Self {
+        Self {
+            src: src.into(),
+            width,
+            height,
+            position: LogoPosition::Above,
+        }
+    }
+
+    pub fn below(mut self) -> Self {
+        self.position = LogoPosition::Below;
+        self
+    }
+}
+
 // =============================================================================
 // Watermark
 // =============================================================================
@@ -113,12 +155,43 @@ pub struct Watermark {
     /// Padding from edges (pixels)
     #[serde(default = "default_padding")]
     pub padding: f64,
+
+    /// Space between rows (pixels); `None` = 20% of the upper row's height
+    #[serde(default)]
+    pub line_gap: Option<f64>,
+
+    /// Opacity applied on top of each line's color and to the logo
+    #[serde(default = "default_opacity")]
+    pub opacity: f64,
+
+    /// Optional image row above or below the text
+    #[serde(default)]
+    pub logo: Option<WatermarkLogo>,
 }
 
 fn default_padding() -> f64 {
     20.0
 }
 
+fn default_opacity() -> f64 {
+    1.0
+}
+
+/// Placement of a watermark block inside the area it is drawn in
+///
+/// All coordinates are top-left corners relative to that area.
+#[derive(Clone, Debug)]
+pub struct WatermarkLayout<'a> {
+    pub x: f64,
+    pub y: f64,
+    /// Widest row
+    pub width: f64,
+    /// All rows plus the gaps between them
+    pub height: f64,
+    pub lines: Vec<(f64, f64, &'a WatermarkLine)>,
+    pub logo: Option<(f64, f64, &'a WatermarkLogo)>,
+}
+
 impl Default for Watermark {
     fn default() -> Self {
         Self {
@@ -127,6 +200,9 @@ impl Default for Watermark {
             vert_align: VertAlign::Center,
             lines: vec![],
             padding: default_padding(),
+            line_gap: None,
+            opacity: default_opacity(),
+            logo: None,
         }
     }
 }
@@ -140,6 +216,9 @@ impl Watermark {
             vert_align: VertAlign::Center,
             lines: vec![WatermarkLine::new(text, "rgba(171, 71, 188, 0.3)", 48.0)],
             padding: 20.0,
+            line_gap: None,
+            opacity: default_opacity(),
+            logo: None,
         }
     }
 
@@ -151,6 +230,9 @@ impl Watermark {
             vert_align: VertAlign::Center,
             lines,
             padding: 20.0,
+            line_gap: None,
+            opacity: default_opacity(),
+            logo: None,
         }
     }
 
@@ -161,6 +243,24 @@ impl Watermark {
         self
     }
 
+    /// Set the space between rows
+    pub fn with_line_gap(mut self, gap: f64) -> Self {
+        self.line_gap = Some(gap.max(0.0));
+        self
+    }
+
+    /// Set the overall opacity
+    pub fn with_opacity(mut self, opacity: f64) -> Self {
+        self.opacity = opacity.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Add an image row above or below the text
+    pub fn with_logo(mut self, logo: WatermarkLogo) -> Self {
+        self.logo = Some(logo);
+        self
+    }
+
     /// Calculate rendering positions for all lines
     ///
     /// Returns (x, y, &WatermarkLine) for each line with alignment applied.
@@ -174,48 +274,81 @@ impl Watermark {
     where
         F: Fn(&str, &str) -> f64, // (text, font) -> width
     {
-        if !self.visible || self.lines.is_empty() {
-            return vec![];
-        }
+        self.layout(chart_width, chart_height, measure_text)
+            .map(|layout| layout.lines)
+            .unwrap_or_default()
+    }
 
-        let mut positions = Vec::new();
-        let line_spacing = 1.2; // CSS line-height equivalent
+    /// Lay out the logo and lines as one block aligned inside an area
+    ///
+    /// The whole block is aligned vertically; each row is aligned
+    /// horizontally on its own, so centered rows of different widths
+    /// share a center line. Returns `None` when there is nothing to draw.
+    pub fn layout<F>(
+        &self,
+        area_width: f64,
+        area_height: f64,
+        measure_text: F,
+    ) -> Option<WatermarkLayout<'_>>
+    where
+        F: Fn(&str, &str) -> f64, // (text, font) -> width
+    {
+        if !self.visible || (self.lines.is_empty() && self.logo.is_none()) {
+            return None;
+        }
 
-        // Calculate total block height
-        let total_height: f64 = self
+        // Rows top to bottom as (width, height, line index or logo)
+        let mut rows: Vec<(f64, f64, Option<usize>)> = self
             .lines
             .iter()
             .enumerate()
             .map(|(i, line)| {
-                let h = line.font_size;
-                if i > 0 { h * line_spacing } else { h }
+                let width = measure_text(&line.text, &line.css_font());
+                (width, line.font_size, Some(i))
             })
-            .sum();
+            .collect();
+        if let Some(logo) = &self.logo {
+            let row = (logo.width, logo.height, None);
+            match logo.position {
+                LogoPosition::Above => rows.insert(0, row),
+                LogoPosition::Below => rows.push(row),
+            }
+        }
 
-        // Starting Y position of block
-        let block_y = match self.vert_align {
+        let gap_after = |height: f64| self.line_gap.unwrap_or(height * 0.2);
+        let gaps: f64 = rows[..rows.len() - 1].iter().map(|r| gap_after(r.1)).sum();
+        let height = rows.iter().map(|r| r.1).sum::<f64>() + gaps;
+        let width = rows.iter().map(|r| r.0).fold(0.0, f64::max);
+
+        let align_x = |w: f64| match self.horz_align {
+            HorzAlign::Left => self.padding,
+            HorzAlign::Center => (area_width - w) / 2.0,
+            HorzAlign::Right => area_width - w - self.padding,
+        };
+        let y = match self.vert_align {
             VertAlign::Top => self.padding,
-            VertAlign::Center => (chart_height - total_height) / 2.0,
-            VertAlign::Bottom => chart_height - total_height - self.padding,
+            VertAlign::Center => (area_height - height) / 2.0,
+            VertAlign::Bottom => area_height - height - self.padding,
         };
 
-        let mut current_y = block_y;
-
-        for line in &self.lines {
-            let text_width = measure_text(&line.text, &line.css_font());
-
-            // X position for this line
-            let x = match self.horz_align {
-                HorzAlign::Left => self.padding,
-                HorzAlign::Center => (chart_width - text_width) / 2.0,
-                HorzAlign::Right => chart_width - text_width - self.padding,
-            };
-
-            positions.push((x, current_y, line));
-            current_y += line.font_size * line_spacing;
+        let mut layout = WatermarkLayout {
+            x: align_x(width),
+            y,
+            width,
+            height,
+            lines: Vec::with_capacity(self.lines.len()),
+            logo: None,
+        };
+        let mut current_y = y;
+        for (row_width, row_height, line) in rows {
+            let x = align_x(row_width);
+            match line {
+                Some(i) => layout.lines.push((x, current_y, &self.lines[i])),
+                None => layout.logo = self.logo.as_ref().map(|logo| (x, current_y, logo)),
+            }
+            current_y += row_height + gap_after(row_height);
         }
-
-        positions
+        Some(layout)
     }
 }
 
@@ -278,4 +411,35 @@ mod tests {
         // Second line should be below first
         assert!(positions[1].1 > positions[0].1);
     }
+
+    #[test]
+    fn test_layout_stacks_logo_and_lines_as_one_block() {
+        let lines = vec![
+            WatermarkLine::new("AAAA", "#fff", 40.0),
+            WatermarkLine::new("BB", "#fff", 20.0),
+        ];
+        let wm = Watermark::multi_line(lines)
+            .with_line_gap(10.0)
+            .with_logo(WatermarkLogo::new("data:image/png;base64,AAAA", 30.0, 30.0));
+        let measure = |text: &str, _font: &str| text.len() as f64 * 25.0;
+
+        // Rows: logo 30, gap 10, line 40, gap 10, line 20
+        let layout = wm.layout(800.0, 600.0, measure).unwrap();
+        assert_eq!((layout.width, layout.height), (100.0, 110.0));
+        assert_eq!((layout.x, layout.y), (350.0, 245.0));
+        let (logo_x, logo_y, _) = layout.logo.unwrap();
+        assert_eq!((logo_x, logo_y), (385.0, 245.0));
+        assert_eq!((layout.lines[0].0, layout.lines[0].1), (350.0, 285.0));
+        assert_eq!((layout.lines[1].0, layout.lines[1].1), (375.0, 335.0));
+
+        // Logo below, block in the bottom-right corner
+        let wm = Watermark {
+            logo: wm.logo.clone().map(WatermarkLogo::below),
+            ..wm.with_alignment(HorzAlign::Right, VertAlign::Bottom)
+        };
+        let layout = wm.layout(800.0, 600.0, measure).unwrap();
+        assert_eq!(layout.y + layout.height, 580.0);
+        assert_eq!(layout.lines[1].0, 800.0 - 50.0 - 20.0);
+        assert_eq!(layout.logo.unwrap().1, 580.0 - 30.0);
+    }
 }
@@ -1,4 +1,4 @@
-//! Series enums: LineType, PriceLineSource
+//! Series enums: LineType, PriceLineSource, HaWickSource, CandleWidthMode
 //!
 //! Note: LineStyle is re-exported from price_line module to avoid duplication
 
@@ -16,11 +16,70 @@ pub enum LineType {
 }
 
 /// Source of price for the price line
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum PriceLineSource {
     #[default]
-    LastBar, // Last bar in data
-    LastVisible, // Last visible bar
+    LastBar, // Last bar in data, no line when its close is missing
+    LastVisible, // Last bar with a close
+}
+
+/// Which high/low a Heikin Ashi wick spans
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HaWickSource {
+    /// Smoothed HA high/low
+    #[default]
+    Smoothed,
+    /// Real high/low of the underlying bar
+    Original,
+}
+
+impl std::str::FromStr for HaWickSource {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "smoothed" | "ha" => Ok(Self::Smoothed),
+            "original" | "real" => Ok(Self::Original),
+            _ => Err(()),
+        }
+    }
+}
+
+/// What candles collapse to once bars are packed past the density threshold
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DenseCandles {
+    /// Keep the bodies, drop the wicks
+    CullWicks,
+    /// One high-low line per body-width column, merging the bars in it
+    #[default]
+    HighLow,
+}
+
+impl std::str::FromStr for DenseCandles {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "cull_wicks" | "cull" | "bodies" => Ok(Self::CullWicks),
+            "high_low" | "highlow" | "lines" => Ok(Self::HighLow),
+            _ => Err(()),
+        }
+    }
+}
+
+/// How wide candle bodies are drawn
+#[derive(Clone, Copy, Debug, PartialEq, Default, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CandleWidthMode {
+    /// A share of the bar spacing, so bodies grow and shrink with zoom
+    #[default]
+    RatioOfSpacing,
+    /// A fixed width in pixels, narrowed to the bar spacing where bodies
+    /// would overlap
+    FixedPixels(f64),
 }
 
 #[cfg(test)]
@@ -27,7 +27,9 @@ pub use data::{
     AreaData, BarData, BaselineData, CandlestickData, HistogramData, LineData, SeriesData,
     SingleValue,
 };
-pub use enums::{LineStyle, LineType, PriceLineSource};
+pub use enums::{
+    CandleWidthMode, DenseCandles, HaWickSource, LineStyle, LineType, PriceLineSource,
+};
 pub use options::{
     AreaSeriesOptions, AreaStyleOptions, BarSeriesOptions, BarStyleOptions, BaselineSeriesOptions,
     BaselineStyleOptions, CandlestickSeriesOptions, CandlestickStyleOptions,
@@ -69,6 +71,48 @@ pub enum SeriesType {
     Columns,
 }
 
+impl SeriesType {
+    /// Name as used in serialized configs, e.g. `"hollow_candlestick"`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Candlestick => "candlestick",
+            Self::HollowCandlestick => "hollow_candlestick",
+            Self::HeikinAshi => "heikin_ashi",
+            Self::Bar => "bar",
+            Self::HlcArea => "hlc_area",
+            Self::Line => "line",
+            Self::StepLine => "step_line",
+            Self::LineWithMarkers => "line_with_markers",
+            Self::Area => "area",
+            Self::Baseline => "baseline",
+            Self::Histogram => "histogram",
+            Self::Columns => "columns",
+        }
+    }
+}
+
+impl std::str::FromStr for SeriesType {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().replace('-', "_").as_str() {
+            "candlestick" | "candles" => Ok(Self::Candlestick),
+            "hollow_candlestick" => Ok(Self::HollowCandlestick),
+            "heikin_ashi" => Ok(Self::HeikinAshi),
+            "bar" | "ohlc" => Ok(Self::Bar),
+            "hlc_area" => Ok(Self::HlcArea),
+            "line" => Ok(Self::Line),
+            "step_line" => Ok(Self::StepLine),
+            "line_with_markers" => Ok(Self::LineWithMarkers),
+            "area" => Ok(Self::Area),
+            "baseline" => Ok(Self::Baseline),
+            "histogram" => Ok(Self::Histogram),
+            "columns" => Ok(Self::Columns),
+            _ => Err(()),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -79,6 +123,22 @@ mod tests {
         assert_ne!(SeriesType::Line, SeriesType::Area);
     }
 
+    #[test]
+    fn test_series_type_as_str_matches_serde() {
+        for series_type in [
+            SeriesType::Candlestick,
+            SeriesType::HollowCandlestick,
+            SeriesType::HeikinAshi,
+            SeriesType::HlcArea,
+            SeriesType::LineWithMarkers,
+            SeriesType::Columns,
+        ] {
+            let json = serde_json::to_string(&series_type).unwrap();
+            assert_eq!(json, format!("\"{}\"", series_type.as_str()));
+            assert_eq!(series_type.as_str().parse(), Ok(series_type));
+        }
+    }
+
     #[test]
     fn test_single_value() {
         let val = SingleValue::new(1699920000, 100.0);
@@ -1,13 +1,16 @@
 //! Style options for all series types
 
-use super::enums::{LineStyle, LineType, PriceLineSource};
+use super::enums::{
+    CandleWidthMode, DenseCandles, HaWickSource, LineStyle, LineType, PriceLineSource,
+};
 
 // =============================================================================
 // Common Options for All Series
 // =============================================================================
 
 /// Base structure with options common to all series
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
 pub struct SeriesOptionsCommon {
     /// Last value label visibility
     pub last_value_visible: bool,
@@ -65,6 +68,24 @@ pub struct CandlestickStyleOptions {
     // Element visibility
     pub wick_visible: bool,
     pub border_visible: bool,
+
+    /// Fixed wick width in pixels (`None` = scale with bar width)
+    pub wick_width: Option<f64>,
+    /// Wick width as a fraction of the body width, at least 1px
+    /// (`None` = 15% clamped to 1..=4px; a fixed `wick_width` wins)
+    pub wick_width_ratio: Option<f64>,
+
+    /// Bars per pixel beyond which candles collapse to `dense_mode`
+    /// (`None` = always draw full candles)
+    pub density_threshold: Option<f64>,
+    /// What candles collapse to past `density_threshold`
+    pub dense_mode: DenseCandles,
+
+    /// Heikin Ashi only: whether wicks span the smoothed or the real range
+    pub ha_wick_source: HaWickSource,
+
+    /// Body width: a share of the bar spacing or fixed pixels
+    pub candle_width: CandleWidthMode,
 }
 
 impl Default for CandlestickStyleOptions {
@@ -80,10 +101,50 @@ impl Default for CandlestickStyleOptions {
             wick_color: "#737375".to_string(),
             wick_visible: true,
             border_visible: true,
+            wick_width: None,
+            wick_width_ratio: None,
+            density_threshold: None,
+            dense_mode: DenseCandles::HighLow,
+            ha_wick_source: HaWickSource::Smoothed,
+            candle_width: CandleWidthMode::RatioOfSpacing,
         }
     }
 }
 
+impl CandlestickStyleOptions {
+    /// Wick width for a given candle body width
+    ///
+    /// Unless overridden, wicks are 15% of the body width, clamped to
+    /// 1..=4 pixels so they stay visible on narrow bars and thin on wide ones.
+    /// A ratio scales without the upper bound but never drops below 1px.
+    pub fn wick_width_for(&self, bar_width: f64) -> f64 {
+        self.wick_width
+            .unwrap_or_else(|| match self.wick_width_ratio {
+                Some(ratio) => (bar_width * ratio).max(1.0),
+                None => (bar_width * 0.15).clamp(1.0, 4.0),
+            })
+    }
+
+    /// Body width for bars `spacing` apart, from the spacing-based
+    /// `bar_width` or the fixed width, whichever the mode picks
+    ///
+    /// Fixed bodies are narrowed to the spacing so neighbours never
+    /// overlap, and stay at least 1px wide.
+    pub fn body_width_for(&self, bar_width: f64, spacing: f64) -> f64 {
+        match self.candle_width {
+            CandleWidthMode::RatioOfSpacing => bar_width,
+            CandleWidthMode::FixedPixels(width) => width.min(spacing).max(1.0),
+        }
+    }
+
+    /// Fallback for bars packed `bars_per_pixel` deep, if past the threshold
+    pub fn dense_mode_at(&self, bars_per_pixel: f64) -> Option<DenseCandles> {
+        self.density_threshold
+            .filter(|&threshold| bars_per_pixel > threshold)
+            .map(|_| self.dense_mode)
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct CandlestickSeriesOptions {
     pub common: SeriesOptionsCommon,
@@ -98,8 +159,13 @@ pub struct CandlestickSeriesOptions {
 pub struct BarStyleOptions {
     pub up_color: String,
     pub down_color: String,
-    pub open_visible: bool,
+    /// Draw the open tick on the left of the high-low line
+    pub show_open_tick: bool,
+    /// Draw the close tick on the right of the high-low line
+    pub show_close_tick: bool,
     pub thin_bars: bool,
+    /// Length of the open and close ticks in pixels (None = half the bar width)
+    pub tick_length: Option<f64>,
 }
 
 impl Default for BarStyleOptions {
@@ -107,12 +173,22 @@ impl Default for BarStyleOptions {
         Self {
             up_color: "#26a69a".to_string(),
             down_color: "#ef5350".to_string(),
-            open_visible: true,
+            show_open_tick: true,
+            show_close_tick: true,
             thin_bars: true,
+            tick_length: None,
         }
     }
 }
 
+impl BarStyleOptions {
+    /// Tick length for a given bar width: the configured length, or half
+    /// the bar width so the ticks span it
+    pub fn tick_length_for(&self, bar_width: f64) -> f64 {
+        self.tick_length.unwrap_or(bar_width / 2.0)
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct BarSeriesOptions {
     pub common: SeriesOptionsCommon,
@@ -181,6 +257,11 @@ pub struct AreaStyleOptions {
     pub relative_gradient: bool,
     pub invert_filled_area: bool,
 
+    // Fill base (None = pane bottom); values under it fill with
+    // `below_color` when set, else with the same gradient
+    pub base_value: Option<f64>,
+    pub below_color: Option<String>,
+
     // Line (inherits from LineStyleOptions)
     pub line_color: String,
     pub line_style: LineStyle,
@@ -205,6 +286,8 @@ impl Default for AreaStyleOptions {
             bottom_color: "rgba(40, 221, 100, 0)".to_string(),
             relative_gradient: false,
             invert_filled_area: false,
+            base_value: None,
+            below_color: None,
             line_color: "#33D778".to_string(),
             line_style: LineStyle::Solid,
             line_width: 3,
@@ -119,6 +119,7 @@ pub fn metadata() -> PrimitiveMetadata {
             let (b, p) = points.first().copied().unwrap_or((0.0, 0.0));
             Box::new(AnchoredText::new(b, p, color))
         },
+        point_range: 1..=2,
         supports_text: true,
         has_levels: false,
         has_points_config: false,
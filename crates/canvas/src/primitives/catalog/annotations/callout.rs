@@ -154,6 +154,7 @@ pub fn metadata() -> PrimitiveMetadata {
             let (b2, p2) = points.get(1).copied().unwrap_or((b1 + 5.0, p1 + 10.0));
             Box::new(Callout::new(b1, p1, b2, p2, color))
         },
+        point_range: 1..=2,
         supports_text: true,
         has_levels: false,
         has_points_config: false,
@@ -100,8 +100,9 @@ impl Primitive for PriceLabel {
         let default_text = format!("{:.2}", self.price);
         let label_text = self.get_custom_text().unwrap_or(&default_text);
         let font_size = self.get_font_size();
-        let char_width = font_size * 0.65;
-        let text_width = label_text.len() as f64 * char_width;
+        ctx.set_font(&format!("{}px sans-serif", font_size as i32));
+        // Padded 4px either side
+        let text_width = ctx.measure_text(label_text) + 8.0;
 
         // Draw horizontal dashed line if enabled
         if self.show_line {
@@ -127,7 +128,6 @@ impl Primitive for PriceLabel {
 
         // Draw text
         ctx.set_fill_color("#000000");
-        ctx.set_font(&format!("{}px sans-serif", font_size as i32));
         ctx.fill_text(label_text, x - text_width / 2.0 + 4.0, y + font_size * 0.3);
 
         let _ = is_selected;
@@ -155,6 +155,7 @@ pub fn metadata() -> PrimitiveMetadata {
             let (b, p) = points.first().copied().unwrap_or((0.0, 0.0));
             Box::new(PriceLabel::new(b, p, color))
         },
+        point_range: 1..=1,
         supports_text: false,
         has_levels: false,
         has_points_config: false,
@@ -116,8 +116,8 @@ impl Primitive for PriceNote {
         } else {
             text_content.to_string()
         };
-        let char_width = font_size * 0.6;
-        let text_width = label_text.len() as f64 * char_width;
+        ctx.set_font(&format!("{}px sans-serif", font_size as i32));
+        let text_width = ctx.measure_text(&label_text);
 
         ctx.set_fill_color(&self.data.color.stroke);
         ctx.fill_rect(
@@ -128,7 +128,6 @@ impl Primitive for PriceNote {
         );
 
         ctx.set_fill_color("#000000");
-        ctx.set_font(&format!("{}px sans-serif", font_size as i32));
         ctx.fill_text(
             &label_text,
             chart_width - text_width - 4.0,
@@ -160,6 +159,7 @@ pub fn metadata() -> PrimitiveMetadata {
             let (b, p) = points.first().copied().unwrap_or((0.0, 0.0));
             Box::new(PriceNote::new(b, p, color))
         },
+        point_range: 1..=1,
         supports_text: false,
         has_levels: false,
         has_points_config: false,
@@ -150,6 +150,7 @@ pub fn metadata() -> PrimitiveMetadata {
             let (b, p) = points.first().copied().unwrap_or((0.0, 0.0));
             Box::new(Table::new(b, p, color))
         },
+        point_range: 1..=1,
         supports_text: true,
         has_levels: false,
         has_points_config: false,
@@ -4,6 +4,7 @@ use super::super::{
     LineStyle, Primitive, PrimitiveColor, PrimitiveData, PrimitiveKind, PrimitiveMetadata,
     RenderContext, TextAlign, TextAnchor, crisp,
 };
+use super::head::arrow_head_path;
 use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -17,6 +18,8 @@ pub struct ArrowLine {
     pub arrow_start: bool,
     #[serde(default = "default_true")]
     pub arrow_end: bool,
+    /// Head length in pixels at the default 2px line width; heads grow and
+    /// shrink with the width
     #[serde(default = "default_arrow_size")]
     pub arrow_size: f64,
 }
@@ -46,6 +49,24 @@ impl ArrowLine {
             arrow_size: 12.0,
         }
     }
+
+    /// Draw heads at the start and/or end of the line
+    pub fn with_heads(mut self, start: bool, end: bool) -> Self {
+        self.arrow_start = start;
+        self.arrow_end = end;
+        self
+    }
+
+    /// Head length in pixels at the default 2px line width
+    pub fn with_arrow_size(mut self, size: f64) -> Self {
+        self.arrow_size = size;
+        self
+    }
+
+    /// Head length in pixels for the current line width
+    pub fn head_length(&self) -> f64 {
+        self.arrow_size * self.data.width.max(0.5) / 2.0
+    }
 }
 
 impl Primitive for ArrowLine {
@@ -100,60 +121,41 @@ impl Primitive for ArrowLine {
             LineStyle::SparseDotted => ctx.set_line_dash(&[2.0, 8.0]),
         }
 
-        // Draw line
-        ctx.begin_path();
-        ctx.move_to(crisp(x1, dpr), crisp(y1, dpr));
-        ctx.line_to(crisp(x2, dpr), crisp(y2, dpr));
-        ctx.stroke();
-        ctx.set_line_dash(&[]);
-
-        // Calculate direction
         let dx = x2 - x1;
         let dy = y2 - y1;
-        let len = (dx * dx + dy * dy).sqrt();
-        if len > 0.0 {
-            let nx = dx / len;
-            let ny = dy / len;
-
-            ctx.set_fill_color(&self.data.color.stroke);
-
-            // Draw arrowhead at end
+        let len = dx.hypot(dy);
+        let head = self.head_length();
+        let heads = self.arrow_start as u8 + self.arrow_end as u8;
+
+        // End the shaft halfway into each head so a wide stroke cannot
+        // blunt the tip; short arrows keep the full shaft
+        let (mut sx, mut sy, mut ex, mut ey) = (x1, y1, x2, y2);
+        if len > head * heads as f64 {
+            let (tx, ty) = (dx / len * head / 2.0, dy / len * head / 2.0);
+            if self.arrow_start {
+                sx += tx;
+                sy += ty;
+            }
             if self.arrow_end {
-                let ax = x2;
-                let ay = y2;
-                let s = self.arrow_size;
-                ctx.begin_path();
-                ctx.move_to(crisp(ax, dpr), crisp(ay, dpr));
-                ctx.line_to(
-                    crisp(ax - nx * s - ny * s * 0.4, dpr),
-                    crisp(ay - ny * s + nx * s * 0.4, dpr),
-                );
-                ctx.line_to(
-                    crisp(ax - nx * s + ny * s * 0.4, dpr),
-                    crisp(ay - ny * s - nx * s * 0.4, dpr),
-                );
-                ctx.close_path();
-                ctx.fill();
+                ex -= tx;
+                ey -= ty;
             }
+        }
+        ctx.begin_path();
+        ctx.move_to(crisp(sx, dpr), crisp(sy, dpr));
+        ctx.line_to(crisp(ex, dpr), crisp(ey, dpr));
+        ctx.stroke();
+        ctx.set_line_dash(&[]);
 
-            // Draw arrowhead at start
-            if self.arrow_start {
-                let ax = x1;
-                let ay = y1;
-                let s = self.arrow_size;
-                ctx.begin_path();
-                ctx.move_to(crisp(ax, dpr), crisp(ay, dpr));
-                ctx.line_to(
-                    crisp(ax + nx * s - ny * s * 0.4, dpr),
-                    crisp(ay + ny * s + nx * s * 0.4, dpr),
-                );
-                ctx.line_to(
-                    crisp(ax + nx * s + ny * s * 0.4, dpr),
-                    crisp(ay + ny * s - nx * s * 0.4, dpr),
-                );
-                ctx.close_path();
-                ctx.fill();
-            }
+        // Filled heads along the line's direction, not snapped so their
+        // orientation stays exact
+        ctx.set_fill_color(&self.data.color.stroke);
+        let half_width = head * 0.4;
+        if self.arrow_end && arrow_head_path(ctx, (x2, y2), (dx, dy), head, half_width) {
+            ctx.fill();
+        }
+        if self.arrow_start && arrow_head_path(ctx, (x1, y1), (-dx, -dy), head, half_width) {
+            ctx.fill();
         }
 
         let _ = is_selected;
@@ -220,6 +222,7 @@ pub fn metadata() -> PrimitiveMetadata {
             let (b2, p2) = points.get(1).copied().unwrap_or((b1 + 10.0, p1));
             Box::new(ArrowLine::new(b1, p1, b2, p2, color))
         },
+        point_range: 2..=2,
         supports_text: true,
         has_levels: false,
         has_points_config: false,
@@ -4,6 +4,7 @@ use super::super::{
     Primitive, PrimitiveColor, PrimitiveData, PrimitiveKind, PrimitiveMetadata, RenderContext,
     TextAlign, TextAnchor, crisp,
 };
+use super::head::arrow_head_path;
 use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Default)]
@@ -21,7 +22,15 @@ pub struct ArrowMarker {
     pub bar: f64,
     pub price: f64,
     pub direction: ArrowDirection,
+    /// Length of the marker in pixels
     pub size: f64,
+    /// Heads at both ends of a shaft
+    #[serde(default)]
+    pub both_ends: bool,
+    /// Head length in pixels. Unset, a single-headed marker is all head and
+    /// a double-headed one uses 40% of `size` per head.
+    #[serde(default)]
+    pub head_size: Option<f64>,
 }
 
 impl ArrowMarker {
@@ -38,8 +47,39 @@ impl ArrowMarker {
             price,
             direction: ArrowDirection::Up,
             size: 20.0,
+            both_ends: false,
+            head_size: None,
         }
     }
+
+    /// Point the marker in `direction`
+    pub fn with_direction(mut self, direction: ArrowDirection) -> Self {
+        self.direction = direction;
+        self
+    }
+
+    /// Draw heads at both ends
+    pub fn with_both_ends(mut self, both_ends: bool) -> Self {
+        self.both_ends = both_ends;
+        self
+    }
+
+    /// Head length in pixels
+    pub fn with_head_size(mut self, head_size: f64) -> Self {
+        self.head_size = Some(head_size);
+        self
+    }
+
+    /// Head length in pixels, at most what fits in the marker
+    pub fn head_length(&self) -> f64 {
+        let max = if self.both_ends {
+            self.size / 2.0
+        } else {
+            self.size
+        };
+        let default = if self.both_ends { 0.4 } else { 1.0 } * self.size;
+        self.head_size.unwrap_or(default).clamp(0.0, max)
+    }
 }
 
 impl Primitive for ArrowMarker {
@@ -73,40 +113,40 @@ impl Primitive for ArrowMarker {
     }
     fn render(&self, ctx: &mut dyn RenderContext, is_selected: bool) {
         let dpr = ctx.dpr();
-        let x = ctx.bar_to_x(self.bar);
-        let y = ctx.price_to_y(self.price);
+        let x = crisp(ctx.bar_to_x(self.bar), dpr);
+        let y = crisp(ctx.price_to_y(self.price), dpr);
         let s = self.size;
+        let (dx, dy) = match self.direction {
+            ArrowDirection::Up => (0.0, -1.0),
+            ArrowDirection::Down => (0.0, 1.0),
+            ArrowDirection::Left => (-1.0, 0.0),
+            ArrowDirection::Right => (1.0, 0.0),
+        };
+        let tip = (x + dx * s / 2.0, y + dy * s / 2.0);
+        let tail = (x - dx * s / 2.0, y - dy * s / 2.0);
+        let head = self.head_length();
 
         ctx.set_fill_color(&self.data.color.stroke);
         ctx.set_stroke_color(&self.data.color.stroke);
         ctx.set_stroke_width(self.data.width);
 
-        ctx.begin_path();
-        match self.direction {
-            ArrowDirection::Up => {
-                ctx.move_to(crisp(x, dpr), crisp(y - s / 2.0, dpr));
-                ctx.line_to(crisp(x - s / 3.0, dpr), crisp(y + s / 2.0, dpr));
-                ctx.line_to(crisp(x + s / 3.0, dpr), crisp(y + s / 2.0, dpr));
-            }
-            ArrowDirection::Down => {
-                ctx.move_to(crisp(x, dpr), crisp(y + s / 2.0, dpr));
-                ctx.line_to(crisp(x - s / 3.0, dpr), crisp(y - s / 2.0, dpr));
-                ctx.line_to(crisp(x + s / 3.0, dpr), crisp(y - s / 2.0, dpr));
-            }
-            ArrowDirection::Left => {
-                ctx.move_to(crisp(x - s / 2.0, dpr), crisp(y, dpr));
-                ctx.line_to(crisp(x + s / 2.0, dpr), crisp(y - s / 3.0, dpr));
-                ctx.line_to(crisp(x + s / 2.0, dpr), crisp(y + s / 3.0, dpr));
-            }
-            ArrowDirection::Right => {
-                ctx.move_to(crisp(x + s / 2.0, dpr), crisp(y, dpr));
-                ctx.line_to(crisp(x - s / 2.0, dpr), crisp(y - s / 3.0, dpr));
-                ctx.line_to(crisp(x - s / 2.0, dpr), crisp(y + s / 3.0, dpr));
-            }
+        // Shaft between the heads when they don't fill the marker
+        let tail_head = if self.both_ends { head } else { 0.0 };
+        if head + tail_head < s {
+            ctx.begin_path();
+            ctx.move_to(tail.0 + dx * tail_head / 2.0, tail.1 + dy * tail_head / 2.0);
+            ctx.line_to(tip.0 - dx * head / 2.0, tip.1 - dy * head / 2.0);
+            ctx.stroke();
+        }
+
+        if arrow_head_path(ctx, tip, (dx, dy), head, head / 3.0) {
+            ctx.fill();
+            ctx.stroke();
+        }
+        if self.both_ends && arrow_head_path(ctx, tail, (-dx, -dy), head, head / 3.0) {
+            ctx.fill();
+            ctx.stroke();
         }
-        ctx.close_path();
-        ctx.fill();
-        ctx.stroke();
 
         let _ = is_selected;
     }
@@ -147,6 +187,7 @@ pub fn metadata() -> PrimitiveMetadata {
             let (bar, price) = points.first().copied().unwrap_or((0.0, 0.0));
             Box::new(ArrowMarker::new(bar, price, color))
         },
+        point_range: 1..=2,
         supports_text: true,
         has_levels: false,
         has_points_config: false,
@@ -0,0 +1,48 @@
+//! Arrowhead geometry shared by the arrow primitives
+
+use super::super::RenderContext;
+
+/// Corners of a triangular arrowhead: the tip, then the two base corners
+///
+/// The head points along `dir` (any length) with its tip at `tip`. `length`
+/// runs from the tip to the base, which is `2 * half_width` wide. Returns
+/// `None` when `dir` has no direction.
+pub fn arrow_head(
+    tip: (f64, f64),
+    dir: (f64, f64),
+    length: f64,
+    half_width: f64,
+) -> Option<[(f64, f64); 3]> {
+    let len = dir.0.hypot(dir.1);
+    if !len.is_finite() || len == 0.0 {
+        return None;
+    }
+    let (nx, ny) = (dir.0 / len, dir.1 / len);
+    let (bx, by) = (tip.0 - nx * length, tip.1 - ny * length);
+    Some([
+        tip,
+        (bx - ny * half_width, by + nx * half_width),
+        (bx + ny * half_width, by - nx * half_width),
+    ])
+}
+
+/// Add a closed arrowhead path to `ctx`, ready to fill
+///
+/// Returns `false` (and adds nothing) when `dir` has no direction.
+pub fn arrow_head_path(
+    ctx: &mut dyn RenderContext,
+    tip: (f64, f64),
+    dir: (f64, f64),
+    length: f64,
+    half_width: f64,
+) -> bool {
+    let Some([a, b, c]) = arrow_head(tip, dir, length, half_width) else {
+        return false;
+    };
+    ctx.begin_path();
+    ctx.move_to(a.0, a.1);
+    ctx.line_to(b.0, b.1);
+    ctx.line_to(c.0, c.1);
+    ctx.close_path();
+    true
+}
@@ -4,8 +4,10 @@ pub mod arrow_down;
 pub mod arrow_line;
 pub mod arrow_marker;
 pub mod arrow_up;
+pub mod head;
 
 pub use arrow_down::ArrowDown;
 pub use arrow_line::ArrowLine;
 pub use arrow_marker::ArrowMarker;
 pub use arrow_up::ArrowUp;
+pub use head::{arrow_head, arrow_head_path};
@@ -1,7 +1,8 @@
 //! Brush - freehand drawing
 
 use super::super::{
-    Primitive, PrimitiveColor, PrimitiveData, PrimitiveKind, PrimitiveMetadata, RenderContext,
+    FillRule, Primitive, PrimitiveColor, PrimitiveData, PrimitiveKind, PrimitiveMetadata,
+    RenderContext,
 };
 use serde::{Deserialize, Serialize};
 
@@ -11,10 +12,23 @@ pub struct Brush {
     pub points: Vec<(f64, f64)>,
     #[serde(default = "default_size")]
     pub brush_size: f64,
+    /// Opacity of the painted stroke (0.0 - 1.0)
+    #[serde(default = "default_opacity")]
+    pub fill_opacity: f64,
+    /// Extra opacity applied on top of `fill_opacity`
+    #[serde(default = "default_opacity")]
+    pub stroke_opacity: f64,
+    /// How the enclosed area is filled where the stroke crosses itself
+    /// (only drawn when the brush has a fill color)
+    #[serde(default)]
+    pub fill_rule: FillRule,
 }
 fn default_size() -> f64 {
     3.0
 }
+fn default_opacity() -> f64 {
+    1.0
+}
 
 impl Brush {
     pub fn new(points: Vec<(f64, f64)>, color: &str) -> Self {
@@ -28,6 +42,38 @@ impl Brush {
             },
             points,
             brush_size: 3.0,
+            fill_opacity: 1.0,
+            stroke_opacity: 1.0,
+            fill_rule: FillRule::NonZero,
+        }
+    }
+
+    /// Begin a smooth path through two or more screen points
+    fn trace(ctx: &mut dyn RenderContext, screen_pts: &[(f64, f64)]) {
+        ctx.begin_path();
+        if screen_pts.len() == 2 {
+            // Two points - draw a line
+            ctx.move_to(screen_pts[0].0, screen_pts[0].1);
+            ctx.line_to(screen_pts[1].0, screen_pts[1].1);
+        } else {
+            // 3+ points - use quadratic bezier through midpoints for smooth curves
+            ctx.move_to(screen_pts[0].0, screen_pts[0].1);
+
+            // First segment: line to midpoint of first two points
+            let mid_x = (screen_pts[0].0 + screen_pts[1].0) / 2.0;
+            let mid_y = (screen_pts[0].1 + screen_pts[1].1) / 2.0;
+            ctx.line_to(mid_x, mid_y);
+
+            // Middle segments: quadratic curves through points, ending at midpoints
+            for i in 1..screen_pts.len() - 1 {
+                let next_mid_x = (screen_pts[i].0 + screen_pts[i + 1].0) / 2.0;
+                let next_mid_y = (screen_pts[i].1 + screen_pts[i + 1].1) / 2.0;
+                ctx.quadratic_curve_to(screen_pts[i].0, screen_pts[i].1, next_mid_x, next_mid_y);
+            }
+
+            // Last segment: line to final point
+            let last = screen_pts.last().unwrap();
+            ctx.line_to(last.0, last.1);
         }
     }
 }
@@ -68,7 +114,11 @@ impl Primitive for Brush {
 
         let _dpr = ctx.dpr();
 
+        let opacity = self.fill_opacity * self.stroke_opacity;
         ctx.set_stroke_color(&self.data.color.stroke);
+        ctx.set_stroke_opacity(opacity);
+        ctx.set_fill_color(&self.data.color.stroke);
+        ctx.set_fill_opacity(opacity);
         ctx.set_stroke_width(self.data.width);
         ctx.set_line_cap("round");
         ctx.set_line_join("round");
@@ -80,39 +130,53 @@ impl Primitive for Brush {
             .map(|&(bar, price)| (ctx.bar_to_x(bar), ctx.price_to_y(price)))
             .collect();
 
-        // Draw smooth curve using quadratic bezier interpolation
-        ctx.begin_path();
         if screen_pts.len() == 1 {
             // Single point - just draw a dot
             let (x, y) = screen_pts[0];
+            ctx.begin_path();
             ctx.arc(x, y, self.data.width / 2.0, 0.0, std::f64::consts::TAU);
             ctx.fill();
+            ctx.reset_alpha();
             return;
-        } else if screen_pts.len() == 2 {
-            // Two points - draw a line
-            ctx.move_to(screen_pts[0].0, screen_pts[0].1);
-            ctx.line_to(screen_pts[1].0, screen_pts[1].1);
-        } else {
-            // 3+ points - use quadratic bezier through midpoints for smooth curves
-            ctx.move_to(screen_pts[0].0, screen_pts[0].1);
+        }
 
-            // First segment: line to midpoint of first two points
-            let mid_x = (screen_pts[0].0 + screen_pts[1].0) / 2.0;
-            let mid_y = (screen_pts[0].1 + screen_pts[1].1) / 2.0;
-            ctx.line_to(mid_x, mid_y);
+        // Area enclosed by the stroke, closed back to the first point
+        if let Some(fill) = &self.data.color.fill
+            && screen_pts.len() >= 3
+        {
+            ctx.set_fill_color(fill);
+            ctx.set_fill_rule(self.fill_rule);
+            Self::trace(ctx, &screen_pts);
+            ctx.close_path();
+            ctx.fill();
+        }
 
-            // Middle segments: quadratic curves through points, ending at midpoints
-            for i in 1..screen_pts.len() - 1 {
-                let next_mid_x = (screen_pts[i].0 + screen_pts[i + 1].0) / 2.0;
-                let next_mid_y = (screen_pts[i].1 + screen_pts[i + 1].1) / 2.0;
-                ctx.quadratic_curve_to(screen_pts[i].0, screen_pts[i].1, next_mid_x, next_mid_y);
-            }
+        // Draw smooth curve using quadratic bezier interpolation
+        Self::trace(ctx, &screen_pts);
+        ctx.stroke();
+        ctx.reset_alpha();
+    }
 
-            // Last segment: line to final point
-            let last = screen_pts.last().unwrap();
-            ctx.line_to(last.0, last.1);
+    fn opacity(&self) -> Option<(f64, f64)> {
+        Some((self.fill_opacity, self.stroke_opacity))
+    }
+    fn set_opacity(&mut self, fill: Option<f64>, stroke: Option<f64>) -> bool {
+        if let Some(fill) = fill {
+            self.fill_opacity = fill.clamp(0.0, 1.0);
         }
-        ctx.stroke();
+        if let Some(stroke) = stroke {
+            self.stroke_opacity = stroke.clamp(0.0, 1.0);
+        }
+        true
+    }
+
+    fn fill_rule(&self) -> Option<FillRule> {
+        Some(self.fill_rule)
+    }
+
+    fn set_fill_rule(&mut self, rule: FillRule) -> bool {
+        self.fill_rule = rule;
+        true
     }
 
     fn to_json(&self) -> String {
@@ -129,6 +193,7 @@ pub fn metadata() -> PrimitiveMetadata {
         display_name: "Brush",
         kind: PrimitiveKind::Annotation,
         factory: |points, color| Box::new(Brush::new(points.to_vec(), color)),
+        point_range: 2..=usize::MAX,
         supports_text: true,
         has_levels: false,
         has_points_config: false,
@@ -11,14 +11,21 @@ pub struct Highlighter {
     pub points: Vec<(f64, f64)>,
     #[serde(default = "default_size")]
     pub brush_size: f64,
-    #[serde(default = "default_opacity")]
-    pub opacity: f64,
+    /// Opacity of the highlighted band (its wide stroke is the filled area)
+    #[serde(default = "default_fill_opacity", alias = "opacity")]
+    pub fill_opacity: f64,
+    /// Extra opacity applied on top of `fill_opacity`
+    #[serde(default = "default_stroke_opacity")]
+    pub stroke_opacity: f64,
 }
 fn default_size() -> f64 {
     20.0
 }
-fn default_opacity() -> f64 {
-    0.4
+fn default_fill_opacity() -> f64 {
+    0.25
+}
+fn default_stroke_opacity() -> f64 {
+    1.0
 }
 
 impl Highlighter {
@@ -33,7 +40,8 @@ impl Highlighter {
             },
             points,
             brush_size: 20.0,
-            opacity: 0.4,
+            fill_opacity: 0.25,
+            stroke_opacity: 1.0,
         }
     }
 }
@@ -74,10 +82,11 @@ impl Primitive for Highlighter {
 
         let _dpr = ctx.dpr();
 
-        // Parse the color and apply opacity
-        let color_with_opacity = apply_opacity(&self.data.color.stroke, self.opacity);
-
-        ctx.set_stroke_color(&color_with_opacity);
+        let opacity = self.fill_opacity * self.stroke_opacity;
+        ctx.set_stroke_color(&self.data.color.stroke);
+        ctx.set_stroke_opacity(opacity);
+        ctx.set_fill_color(&self.data.color.stroke);
+        ctx.set_fill_opacity(opacity);
         ctx.set_stroke_width(self.data.width);
         ctx.set_line_cap("round");
         ctx.set_line_join("round");
@@ -96,6 +105,7 @@ impl Primitive for Highlighter {
             let (x, y) = screen_pts[0];
             ctx.arc(x, y, self.data.width / 2.0, 0.0, std::f64::consts::TAU);
             ctx.fill();
+            ctx.reset_alpha();
             return;
         } else if screen_pts.len() == 2 {
             // Two points - draw a line
@@ -122,6 +132,20 @@ impl Primitive for Highlighter {
             ctx.line_to(last.0, last.1);
         }
         ctx.stroke();
+        ctx.reset_alpha();
+    }
+
+    fn opacity(&self) -> Option<(f64, f64)> {
+        Some((self.fill_opacity, self.stroke_opacity))
+    }
+    fn set_opacity(&mut self, fill: Option<f64>, stroke: Option<f64>) -> bool {
+        if let Some(fill) = fill {
+            self.fill_opacity = fill.clamp(0.0, 1.0);
+        }
+        if let Some(stroke) = stroke {
+            self.stroke_opacity = stroke.clamp(0.0, 1.0);
+        }
+        true
     }
 
     fn to_json(&self) -> String {
@@ -132,53 +156,13 @@ impl Primitive for Highlighter {
     }
 }
 
-fn apply_opacity(color: &str, opacity: f64) -> String {
-    // Handle hex colors (#RGB or #RRGGBB)
-    if let Some(hex) = color.strip_prefix('#') {
-        let (r, g, b) = if hex.len() == 3 {
-            // #RGB format
-            let r = u8::from_str_radix(&hex[0..1], 16).unwrap_or(0) * 17;
-            let g = u8::from_str_radix(&hex[1..2], 16).unwrap_or(0) * 17;
-            let b = u8::from_str_radix(&hex[2..3], 16).unwrap_or(0) * 17;
-            (r, g, b)
-        } else if hex.len() == 6 {
-            // #RRGGBB format
-            let r = u8::from_str_radix(&hex[0..2], 16).unwrap_or(0);
-            let g = u8::from_str_radix(&hex[2..4], 16).unwrap_or(0);
-            let b = u8::from_str_radix(&hex[4..6], 16).unwrap_or(0);
-            (r, g, b)
-        } else {
-            return color.to_string();
-        };
-        return format!("rgba({}, {}, {}, {})", r, g, b, opacity);
-    }
-
-    // If already rgba, replace alpha
-    if color.starts_with("rgba(") {
-        if let Some(close_paren) = color.rfind(')') {
-            if let Some(last_comma) = color[..close_paren].rfind(',') {
-                return format!("{}, {})", &color[..last_comma], opacity);
-            }
-        }
-    }
-
-    // If rgb, convert to rgba
-    if let Some(rgb_inner) = color.strip_prefix("rgb(") {
-        if let Some(rgb_inner) = rgb_inner.strip_suffix(')') {
-            return format!("rgba({}, {})", rgb_inner, opacity);
-        }
-    }
-
-    // Default: return original color
-    color.to_string()
-}
-
 pub fn metadata() -> PrimitiveMetadata {
     PrimitiveMetadata {
         type_id: "highlighter",
         display_name: "Highlighter",
         kind: PrimitiveKind::Annotation,
         factory: |points, color| Box::new(Highlighter::new(points.to_vec(), color)),
+        point_range: 2..=usize::MAX,
         supports_text: true,
         has_levels: false,
         has_points_config: false,
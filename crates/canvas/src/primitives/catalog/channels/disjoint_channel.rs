@@ -360,6 +360,7 @@ pub fn metadata() -> PrimitiveMetadata {
         display_name: "Disjoint Channel",
         kind: PrimitiveKind::Channel,
         factory: create_disjoint_channel,
+        point_range: 2..=4,
         supports_text: true,
         has_levels: false,
         has_points_config: false,
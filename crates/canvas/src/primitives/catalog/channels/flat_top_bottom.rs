@@ -297,6 +297,7 @@ pub fn metadata() -> PrimitiveMetadata {
         display_name: "Flat Top/Bottom",
         kind: PrimitiveKind::Channel,
         factory: create_flat_top_bottom,
+        point_range: 2..=3,
         supports_text: true,
         has_levels: false,
         has_points_config: false,
@@ -298,6 +298,7 @@ pub fn metadata() -> PrimitiveMetadata {
         display_name: "Parallel Channel",
         kind: PrimitiveKind::Channel,
         factory: create_parallel_channel,
+        point_range: 2..=3,
         supports_text: true,
         has_levels: false,
         has_points_config: false,
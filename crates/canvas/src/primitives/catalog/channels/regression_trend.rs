@@ -1,12 +1,14 @@
 //! Regression Trend primitive
 //!
-//! A linear regression channel with a center line calculated from price data
-//! and parallel lines at standard deviation distances.
+//! A linear regression channel: a least-squares line through the closes of
+//! the bars between the two anchors, and parallel bands a multiple of the
+//! residuals' standard deviation (or standard error) away from it.
 
 use super::super::{
-    LineStyle, Primitive, PrimitiveColor, PrimitiveData, PrimitiveKind, PrimitiveMetadata,
-    RenderContext, TextAlign, TextAnchor, crisp,
+    BandDeviation, LineStyle, Primitive, PrimitiveColor, PrimitiveData, PrimitiveKind,
+    PrimitiveMetadata, RenderContext, TextAlign, TextAnchor, crisp,
 };
+use crate::core::{Bar, LinearFit, linear_regression};
 use serde::{Deserialize, Serialize};
 
 /// Regression Trend - linear regression channel
@@ -22,9 +24,12 @@ pub struct RegressionTrend {
     pub bar2: f64,
     /// End price (for anchor)
     pub price2: f64,
-    /// Standard deviation multiplier for channel width
+    /// Band distance from the center line, in multiples of `deviation`
     #[serde(default = "default_std_dev")]
     pub std_dev_mult: f64,
+    /// Residual measure the bands are offset by
+    #[serde(default)]
+    pub deviation: BandDeviation,
     /// Use Upper Pearson's channel (fitted to highs/lows)
     #[serde(default)]
     pub use_upper_deviation: bool,
@@ -70,6 +75,7 @@ impl RegressionTrend {
             bar2,
             price2,
             std_dev_mult: 2.0,
+            deviation: BandDeviation::StdDev,
             use_upper_deviation: false,
             show_center: true,
             fill: true,
@@ -78,9 +84,8 @@ impl RegressionTrend {
         }
     }
 
-    /// Get the regression line slope and intercept
-    /// In a real implementation, this would compute from actual price data
-    /// For now, we use the anchor points as the regression line
+    /// Slope and intercept of the line through the two anchors, drawn when
+    /// there are no bars to fit
     pub fn regression_params(&self) -> (f64, f64) {
         // slope = (price2 - price1) / (bar2 - bar1)
         let slope = if (self.bar2 - self.bar1).abs() > 0.001 {
@@ -93,20 +98,54 @@ impl RegressionTrend {
         (slope, intercept)
     }
 
-    /// Get price on the regression line at a given bar
+    /// Get price on the anchor line at a given bar
     pub fn price_at_bar(&self, bar: f64) -> f64 {
         let (slope, intercept) = self.regression_params();
         slope * bar + intercept
     }
 
-    /// Get the channel offset based on std_dev_mult
-    /// In a real implementation, this would be calculated from actual std deviation
-    /// For now, we estimate as a percentage of the price range
+    /// Estimated band offset for the anchor line, a share of its price range
     pub fn channel_offset(&self) -> f64 {
         let price_range = (self.price2 - self.price1).abs();
         // Estimate std dev as ~15% of price range, multiply by std_dev_mult
         (price_range * 0.15).max(self.price1 * 0.02) * self.std_dev_mult / 2.0
     }
+
+    /// Least-squares fit of the closes between the anchors, with the upper
+    /// and lower band offsets
+    ///
+    /// Bands sit `std_dev_mult` deviations from the line, or with
+    /// `use_upper_deviation` at the furthest high above and low below it.
+    /// Returns None when fewer than two bars with a close lie in the range.
+    pub fn fit(&self, bars: &[Bar]) -> Option<(LinearFit, f64, f64)> {
+        let first = self.bar1.min(self.bar2).round().max(0.0) as usize;
+        let last =
+            (self.bar1.max(self.bar2).round().max(0.0) as usize).min(bars.len().checked_sub(1)?);
+        let range = bars.get(first..=last)?;
+        let points: Vec<(f64, f64)> = range
+            .iter()
+            .enumerate()
+            .map(|(i, bar)| ((first + i) as f64, bar.close))
+            .collect();
+        let fit = linear_regression(&points)?;
+
+        if self.use_upper_deviation {
+            // f64::max skips the NaN of a missing high or low
+            let (mut upper, mut lower) = (0.0_f64, 0.0_f64);
+            for (i, bar) in range.iter().enumerate() {
+                let center = fit.at((first + i) as f64);
+                upper = upper.max(bar.high - center);
+                lower = lower.max(center - bar.low);
+            }
+            return Some((fit, upper, lower));
+        }
+        let deviation = match self.deviation {
+            BandDeviation::StdDev => fit.std_dev,
+            BandDeviation::StdError => fit.std_error,
+        };
+        let offset = deviation * self.std_dev_mult;
+        Some((fit, offset, offset))
+    }
 }
 
 impl Primitive for RegressionTrend {
@@ -150,18 +189,39 @@ impl Primitive for RegressionTrend {
         self.price2 += price_delta;
     }
 
+    fn band(&self) -> Option<(f64, BandDeviation)> {
+        Some((self.std_dev_mult, self.deviation))
+    }
+
+    fn set_band(&mut self, multiplier: Option<f64>, deviation: Option<BandDeviation>) -> bool {
+        if let Some(multiplier) = multiplier {
+            self.std_dev_mult = multiplier.max(0.0);
+        }
+        if let Some(deviation) = deviation {
+            self.deviation = deviation;
+        }
+        true
+    }
+
     fn render(&self, ctx: &mut dyn RenderContext, _is_selected: bool) {
         let dpr = ctx.dpr();
         let x1 = ctx.bar_to_x(self.bar1);
-        let y1 = ctx.price_to_y(self.price1);
         let x2 = ctx.bar_to_x(self.bar2);
-        let y2 = ctx.price_to_y(self.price2);
 
-        let offset = self.channel_offset();
-        let upper_y1 = ctx.price_to_y(self.price1 + offset);
-        let upper_y2 = ctx.price_to_y(self.price2 + offset);
-        let lower_y1 = ctx.price_to_y(self.price1 - offset);
-        let lower_y2 = ctx.price_to_y(self.price2 - offset);
+        // Fit the bars when the platform provides them, else follow the anchors
+        let (center1, center2, upper, lower) = match self.fit(ctx.bars()) {
+            Some((fit, upper, lower)) => (fit.at(self.bar1), fit.at(self.bar2), upper, lower),
+            None => {
+                let offset = self.channel_offset();
+                (self.price1, self.price2, offset, offset)
+            }
+        };
+        let y1 = ctx.price_to_y(center1);
+        let y2 = ctx.price_to_y(center2);
+        let upper_y1 = ctx.price_to_y(center1 + upper);
+        let upper_y2 = ctx.price_to_y(center2 + upper);
+        let lower_y1 = ctx.price_to_y(center1 - lower);
+        let lower_y2 = ctx.price_to_y(center2 - lower);
 
         // Fill if enabled
         if self.fill {
@@ -294,6 +354,7 @@ pub fn metadata() -> PrimitiveMetadata {
         display_name: "Regression Trend",
         kind: PrimitiveKind::Channel,
         factory: create_regression_trend,
+        point_range: 2..=2,
         supports_text: true,
         has_levels: false,
         has_points_config: false,
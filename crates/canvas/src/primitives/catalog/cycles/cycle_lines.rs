@@ -174,6 +174,7 @@ pub fn metadata() -> PrimitiveMetadata {
             let (b2, _) = points.get(1).copied().unwrap_or((b1 + 20.0, 0.0));
             Box::new(CycleLines::new(b1, b2, color))
         },
+        point_range: 2..=2,
         supports_text: true,
         has_levels: false,
         has_points_config: false,
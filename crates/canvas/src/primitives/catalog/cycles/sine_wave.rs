@@ -172,6 +172,7 @@ pub fn metadata() -> PrimitiveMetadata {
             let (b2, p2) = points.get(1).copied().unwrap_or((b1 + 40.0, p1));
             Box::new(SineWave::new(b1, p1, b2, p2, color))
         },
+        point_range: 2..=2,
         supports_text: true,
         has_levels: false,
         has_points_config: false,
@@ -175,6 +175,7 @@ pub fn metadata() -> PrimitiveMetadata {
             let (b2, p2) = points.get(1).copied().unwrap_or((b + 20.0, p + p * 0.05));
             Box::new(TimeCycles::new(b, p, (b2 - b).abs(), (p2 - p).abs(), color))
         },
+        point_range: 2..=2,
         supports_text: true,
         has_levels: false,
         has_points_config: false,
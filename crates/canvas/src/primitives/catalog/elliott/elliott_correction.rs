@@ -151,6 +151,7 @@ pub fn metadata() -> PrimitiveMetadata {
             }
             Box::new(ElliottCorrection::new(arr, color))
         },
+        point_range: 4..=4,
         supports_text: true,
         has_levels: false,
         has_points_config: true,
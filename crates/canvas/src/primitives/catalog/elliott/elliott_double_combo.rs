@@ -142,6 +142,7 @@ pub fn metadata() -> PrimitiveMetadata {
             }
             Box::new(ElliottDoubleCombo::new(arr, color))
         },
+        point_range: 7..=7,
         supports_text: true,
         has_levels: false,
         has_points_config: true,
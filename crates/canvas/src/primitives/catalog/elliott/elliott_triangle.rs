@@ -162,6 +162,7 @@ pub fn metadata() -> PrimitiveMetadata {
             }
             Box::new(ElliottTriangle::new(arr, color))
         },
+        point_range: 6..=6,
         supports_text: true,
         has_levels: false,
         has_points_config: true,
@@ -122,6 +122,7 @@ pub fn metadata() -> PrimitiveMetadata {
         display_name: "Elliott Triple Combo",
         kind: PrimitiveKind::Pattern,
         factory: |points, color| Box::new(ElliottTripleCombo::new(points.to_vec(), color)),
+        point_range: 2..=usize::MAX,
         supports_text: true,
         has_levels: false,
         has_points_config: true,
@@ -352,38 +352,40 @@ impl Primitive for CustomEvent {
             }
             CustomEventStyle::Label { text, background } => {
                 if !text.is_empty() {
+                    ctx.set_font("12px sans-serif");
+                    let text_width = ctx.measure_text(text);
                     if *background {
-                        let text_width = (text.len() as f64 * 7.0) + 8.0;
+                        let box_width = text_width + 8.0;
                         let text_height = 16.0;
                         ctx.set_global_alpha(0.8);
                         ctx.fill_rect(
-                            crisp(x - text_width / 2.0, dpr),
+                            crisp(x - box_width / 2.0, dpr),
                             crisp(y - text_height / 2.0, dpr),
-                            text_width,
+                            box_width,
                             text_height,
                         );
                         ctx.set_global_alpha(1.0);
                         ctx.set_fill_color("#FFFFFF");
                     }
-                    ctx.set_font("12px sans-serif");
-                    ctx.fill_text(text, x - (text.len() as f64 * 3.5), y + 4.0);
+                    ctx.fill_text(text, x - text_width / 2.0, y + 4.0);
                 }
             }
             CustomEventStyle::Badge { text } => {
                 if !text.is_empty() {
-                    let text_width = (text.len() as f64 * 7.0) + 12.0;
+                    ctx.set_font("11px sans-serif");
+                    let text_width = ctx.measure_text(text);
+                    let box_width = text_width + 12.0;
                     let text_height = 18.0;
                     // Draw as regular rect (rounded rect not available in RenderContext)
                     ctx.fill_rect(
-                        crisp(x - text_width / 2.0, dpr),
+                        crisp(x - box_width / 2.0, dpr),
                         crisp(y - text_height / 2.0, dpr),
-                        text_width,
+                        box_width,
                         text_height,
                     );
 
                     ctx.set_fill_color("#FFFFFF");
-                    ctx.set_font("11px sans-serif");
-                    ctx.fill_text(text, x - (text.len() as f64 * 3.0), y + 4.0);
+                    ctx.fill_text(text, x - text_width / 2.0, y + 4.0);
                 }
             }
             CustomEventStyle::Path { path_data: _ } => {
@@ -431,6 +433,7 @@ pub fn metadata() -> PrimitiveMetadata {
             let (b, p) = points.first().copied().unwrap_or((0.0, 0.0));
             Box::new(CustomEvent::new(b, p, "custom", color))
         },
+        point_range: 1..=1,
         supports_text: true,
         has_levels: false,
         has_points_config: false,
@@ -5,6 +5,7 @@ use super::super::{
     TextAnchor, crisp,
 };
 use serde::{Deserialize, Serialize};
+use std::str::FromStr;
 
 /// Type of divergence
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
@@ -66,7 +67,26 @@ impl DivergenceType {
     }
 }
 
+impl FromStr for DivergenceType {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "regular_bullish" | "bullish" => Ok(Self::RegularBullish),
+            "regular_bearish" | "bearish" => Ok(Self::RegularBearish),
+            "hidden_bullish" => Ok(Self::HiddenBullish),
+            "hidden_bearish" => Ok(Self::HiddenBearish),
+            _ => Err(()),
+        }
+    }
+}
+
 /// Divergence event primitive
+///
+/// Both pivots are usually on one pane. With a point placed on an
+/// indicator's subpane (see `PrimitiveConfig::indicator_divergence`), the
+/// chart resolves each point on its own pane and the line links a price
+/// swing to the matching oscillator swing.
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Divergence {
     pub data: PrimitiveData,
@@ -183,6 +203,11 @@ impl Primitive for Divergence {
         self.price2 += pd;
     }
 
+    fn set_divergence_type(&mut self, kind: DivergenceType) -> bool {
+        self.divergence_type = kind;
+        true
+    }
+
     fn render(&self, ctx: &mut dyn RenderContext, _is_selected: bool) {
         let dpr = ctx.dpr();
         let x1 = ctx.bar_to_x(self.bar1);
@@ -283,6 +308,7 @@ pub fn metadata() -> PrimitiveMetadata {
             event.data.color = PrimitiveColor::new(color);
             Box::new(event)
         },
+        point_range: 2..=2,
         supports_text: true,
         has_levels: false,
         has_points_config: false,
@@ -303,7 +303,7 @@ impl Primitive for TrendEvent {
             } else {
                 y + s / 2.0 + 10.0
             };
-            ctx.fill_text(label, x - (label.len() as f64 * 3.0), label_y);
+            ctx.fill_text(label, x - ctx.measure_text(label) / 2.0, label_y);
         } else if matches!(self.event_type, TrendEventType::CHoCH | TrendEventType::BoS) {
             // Draw structure break marker
             ctx.begin_path();
@@ -313,7 +313,9 @@ impl Primitive for TrendEvent {
 
             // Draw label badge
             let label = self.event_type.short_label();
-            let badge_width = (label.len() as f64 * 6.0) + 8.0;
+            ctx.set_font("10px sans-serif");
+            let text_width = ctx.measure_text(label);
+            let badge_width = text_width + 8.0;
             let badge_height = 14.0;
             let badge_y = y - badge_height - 4.0;
 
@@ -325,8 +327,7 @@ impl Primitive for TrendEvent {
             );
 
             ctx.set_fill_color("#FFFFFF");
-            ctx.set_font("10px sans-serif");
-            ctx.fill_text(label, x - (label.len() as f64 * 2.5), badge_y + 10.0);
+            ctx.fill_text(label, x - text_width / 2.0, badge_y + 10.0);
         } else if matches!(self.event_type, TrendEventType::Reversal) {
             // Draw curved arrow for reversal
             ctx.begin_path();
@@ -404,6 +405,7 @@ pub fn metadata() -> PrimitiveMetadata {
             event.data.color = PrimitiveColor::new(color);
             Box::new(event)
         },
+        point_range: 1..=1,
         supports_text: true,
         has_levels: false,
         has_points_config: false,
@@ -304,6 +304,7 @@ pub fn metadata() -> PrimitiveMetadata {
             event.data.color = PrimitiveColor::new(color);
             Box::new(event)
         },
+        point_range: 1..=1,
         supports_text: true,
         has_levels: false,
         has_points_config: false,
@@ -4,8 +4,8 @@
 //! Arcs emanate from the second point at Fib ratios of the distance.
 
 use super::super::{
-    EllipseParams, LineStyle, Primitive, PrimitiveColor, PrimitiveData, PrimitiveKind,
-    PrimitiveMetadata, RenderContext, TextAlign, TextAnchor, config::FibLevelConfig, crisp,
+    LineStyle, Primitive, PrimitiveColor, PrimitiveData, PrimitiveKind, PrimitiveMetadata,
+    RenderContext, TextAlign, TextAnchor, config::FibLevelConfig, crisp,
 };
 use crate::Viewport;
 use serde::{Deserialize, Serialize};
@@ -137,10 +137,8 @@ impl Primitive for FibArcs {
         let x2 = ctx.bar_to_x(self.bar2);
         let y2 = ctx.price_to_y(self.price2);
 
-        // Calculate base radii from data coordinates for ellipse behavior
-        let base_rx = (x2 - x1).abs().max(1.0);
-        let base_ry = (y2 - y1).abs().max(1.0);
-
+        // Arcs are circles on screen, sized by the baseline's length
+        let base_distance = (x2 - x1).hypot(y2 - y1).max(1.0);
         let baseline_angle = (y2 - y1).atan2(x2 - x1);
 
         ctx.set_stroke_color(&self.data.color.stroke);
@@ -159,26 +157,21 @@ impl Primitive for FibArcs {
         ctx.line_to(crisp(x2, dpr), crisp(y2, dpr));
         ctx.stroke();
 
-        // Draw elliptical arcs at each level, centered at point 2
+        // Concentric arcs at each level, centered at point 2
         for &level in &self.levels {
-            let rx = base_rx * level;
-            let ry = base_ry * level;
+            let radius = base_distance * level;
             ctx.begin_path();
             if self.full_circle {
-                ctx.ellipse(EllipseParams::full(x2, y2, rx, ry));
+                ctx.arc(x2, y2, radius, 0.0, 2.0 * PI);
             } else {
-                // Semi-ellipse facing away from point 1
-                let start_angle = baseline_angle - PI / 2.0;
-                let end_angle = baseline_angle + PI / 2.0;
-                ctx.ellipse(EllipseParams::new(
+                // Semicircle facing away from point 1
+                ctx.arc(
                     x2,
                     y2,
-                    rx,
-                    ry,
-                    0.0,
-                    start_angle,
-                    end_angle,
-                ));
+                    radius,
+                    baseline_angle - PI / 2.0,
+                    baseline_angle + PI / 2.0,
+                );
             }
             ctx.stroke();
         }
@@ -256,6 +249,7 @@ pub fn metadata() -> PrimitiveMetadata {
         display_name: "Fib Arcs",
         kind: PrimitiveKind::Fibonacci,
         factory: create_fib_arcs,
+        point_range: 2..=2,
         supports_text: true,
         has_levels: true,
         has_points_config: false,
@@ -274,6 +274,7 @@ pub fn metadata() -> PrimitiveMetadata {
         display_name: "Fib Channel",
         kind: PrimitiveKind::Fibonacci,
         factory: create_fib_channel,
+        point_range: 3..=3,
         supports_text: true,
         has_levels: true,
         has_points_config: false,
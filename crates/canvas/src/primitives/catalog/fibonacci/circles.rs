@@ -225,6 +225,7 @@ pub fn metadata() -> PrimitiveMetadata {
         display_name: "Fib Circles",
         kind: PrimitiveKind::Fibonacci,
         factory: create_fib_circles,
+        point_range: 2..=2,
         supports_text: true,
         has_levels: true,
         has_points_config: false,
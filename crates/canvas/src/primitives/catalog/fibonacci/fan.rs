@@ -244,6 +244,7 @@ pub fn metadata() -> PrimitiveMetadata {
         display_name: "Fib Fan",
         kind: PrimitiveKind::Fibonacci,
         factory: create_fib_fan,
+        point_range: 2..=2,
         supports_text: true,
         has_levels: true,
         has_points_config: false,
@@ -4,9 +4,10 @@
 //! Standard levels: 0%, 23.6%, 38.2%, 50%, 61.8%, 78.6%, 100%
 
 use super::super::{
-    LineStyle, Primitive, PrimitiveColor, PrimitiveData, PrimitiveKind, PrimitiveMetadata,
-    RenderContext, TextAlign, TextAnchor, config::FibLevelConfig, crisp,
+    FibLabelMode, LineStyle, Primitive, PrimitiveColor, PrimitiveData, PrimitiveKind,
+    PrimitiveMetadata, RenderContext, TextAlign, TextAnchor, config::FibLevelConfig, crisp,
 };
+use crate::coords::format_price;
 use serde::{Deserialize, Serialize};
 
 /// Standard Fibonacci retracement levels
@@ -84,6 +85,9 @@ pub struct FibRetracement {
     /// Fill opacity (0.0 to 1.0)
     #[serde(default = "default_fill_opacity")]
     pub fill_opacity: f64,
+    /// Label drawn above each level line (unset = no labels)
+    #[serde(default)]
+    pub label_mode: Option<FibLabelMode>,
 }
 
 fn default_true() -> bool {
@@ -139,6 +143,7 @@ impl FibRetracement {
             extend_right: true,
             show_fill: false,
             fill_opacity: 0.1,
+            label_mode: None,
         }
     }
 
@@ -147,6 +152,38 @@ impl FibRetracement {
         self.price1 + (self.price2 - self.price1) * level
     }
 
+    /// Label of a level in the current label mode, `None` without labels
+    ///
+    /// Prices get four significant digits of the move between the anchors;
+    /// the percent is the level's distance from point 1 along that move.
+    pub fn level_label(&self, level: f64) -> Option<String> {
+        let ratio = trimmed(level, 3);
+        let range = self.price2 - self.price1;
+        Some(match self.label_mode? {
+            FibLabelMode::RatioOnly => ratio,
+            FibLabelMode::RatioPrice => {
+                let step = if range != 0.0 {
+                    range.abs() / 10_000.0
+                } else {
+                    0.01
+                };
+                format!(
+                    "{}  {}",
+                    ratio,
+                    format_price(self.price_at_level(level), step)
+                )
+            }
+            FibLabelMode::RatioPercent => {
+                let percent = if range != 0.0 {
+                    (self.price_at_level(level) - self.price1) / range * 100.0
+                } else {
+                    0.0
+                };
+                format!("{}  {}%", ratio, trimmed(percent, 2))
+            }
+        })
+    }
+
     /// Get all level prices (only visible levels)
     pub fn level_prices(&self) -> Vec<(f64, f64)> {
         self.level_configs
@@ -294,6 +331,22 @@ impl Primitive for FibRetracement {
         }
         ctx.set_line_dash(&[]);
 
+        // Level labels, left-aligned just above each line
+        if self.label_mode.is_some() {
+            use super::super::render::{TextAlign, TextBaseline};
+            ctx.set_font("11px sans-serif");
+            ctx.set_text_align(TextAlign::Left);
+            ctx.set_text_baseline(TextBaseline::Bottom);
+            for cfg in self.level_configs.iter().filter(|cfg| cfg.visible) {
+                let Some(label) = self.level_label(cfg.level) else {
+                    continue;
+                };
+                let y = ctx.price_to_y(self.price_at_level(cfg.level));
+                ctx.set_fill_color(cfg.color.as_deref().unwrap_or(&self.data.color.stroke));
+                ctx.fill_text(&label, left_x + 4.0, y - 2.0);
+            }
+        }
+
         // Draw connecting line from point 1 to point 2
         let y1 = ctx.price_to_y(self.price1);
         let y2 = ctx.price_to_y(self.price2);
@@ -360,6 +413,11 @@ impl Primitive for FibRetracement {
         true
     }
 
+    fn set_fib_labels(&mut self, mode: FibLabelMode) -> bool {
+        self.label_mode = Some(mode);
+        true
+    }
+
     fn to_json(&self) -> String {
         serde_json::to_string(self).unwrap_or_default()
     }
@@ -369,6 +427,20 @@ impl Primitive for FibRetracement {
     }
 }
 
+/// `value` with up to `decimals` digits and no trailing zeros: `0.618`, `1`
+fn trimmed(value: f64, decimals: usize) -> String {
+    let text = format!("{:.*}", decimals, value);
+    let text = if text.contains('.') {
+        text.trim_end_matches('0').trim_end_matches('.')
+    } else {
+        &text
+    };
+    match text {
+        "-0" => "0".to_string(),
+        text => text.to_string(),
+    }
+}
+
 // Note: Configurable is now implemented via blanket impl in config.rs
 // This provides base configuration (color, width, style, coordinates) automatically.
 // Custom properties (show_prices, extend_left, etc.) could be added via a
@@ -390,6 +462,7 @@ pub fn metadata() -> PrimitiveMetadata {
         display_name: "Fib Retracement",
         kind: PrimitiveKind::Fibonacci,
         factory: create_fib_retracement,
+        point_range: 2..=2,
         supports_text: true,
         has_levels: true,
         has_points_config: false,
@@ -206,6 +206,7 @@ pub fn metadata() -> PrimitiveMetadata {
         display_name: "Speed Resistance",
         kind: PrimitiveKind::Fibonacci,
         factory: create_fib_speed_resistance,
+        point_range: 2..=2,
         supports_text: true,
         has_levels: true,
         has_points_config: false,
@@ -245,6 +245,7 @@ pub fn metadata() -> PrimitiveMetadata {
         display_name: "Fib Spiral",
         kind: PrimitiveKind::Fibonacci,
         factory: create_fib_spiral,
+        point_range: 2..=2,
         supports_text: true,
         has_levels: true,
         has_points_config: false,
@@ -159,6 +159,7 @@ pub fn metadata() -> PrimitiveMetadata {
         display_name: "Fib Time Zones",
         kind: PrimitiveKind::Fibonacci,
         factory: create_fib_time_zones,
+        point_range: 1..=2,
         supports_text: true,
         has_levels: true,
         has_points_config: false,
@@ -235,6 +235,7 @@ pub fn metadata() -> PrimitiveMetadata {
         display_name: "Fib Extension",
         kind: PrimitiveKind::Fibonacci,
         factory: create_fib_trend_extension,
+        point_range: 3..=3,
         supports_text: true,
         has_levels: true,
         has_points_config: false,
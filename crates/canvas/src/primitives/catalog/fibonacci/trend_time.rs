@@ -199,6 +199,7 @@ pub fn metadata() -> PrimitiveMetadata {
         display_name: "Fib Trend Time",
         kind: PrimitiveKind::Fibonacci,
         factory: create_fib_trend_time,
+        point_range: 2..=3,
         supports_text: true,
         has_levels: true,
         has_points_config: false,
@@ -263,6 +263,7 @@ pub fn metadata() -> PrimitiveMetadata {
         display_name: "Fib Wedge",
         kind: PrimitiveKind::Fibonacci,
         factory: create_fib_wedge,
+        point_range: 2..=3,
         supports_text: true,
         has_levels: true,
         has_points_config: false,
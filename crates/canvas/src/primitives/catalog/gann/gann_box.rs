@@ -257,6 +257,7 @@ pub fn metadata() -> PrimitiveMetadata {
         display_name: "Gann Box",
         kind: PrimitiveKind::Gann,
         factory: create_gann_box,
+        point_range: 2..=2,
         supports_text: true,
         has_levels: true,
         has_points_config: false,
@@ -287,6 +287,7 @@ pub fn metadata() -> PrimitiveMetadata {
         display_name: "Gann Fan",
         kind: PrimitiveKind::Gann,
         factory: create_gann_fan,
+        point_range: 2..=2,
         supports_text: true,
         has_levels: true,
         has_points_config: false,
@@ -268,6 +268,7 @@ pub fn metadata() -> PrimitiveMetadata {
         display_name: "Gann Square",
         kind: PrimitiveKind::Gann,
         factory: create_gann_square,
+        point_range: 2..=2,
         supports_text: true,
         has_levels: true,
         has_points_config: false,
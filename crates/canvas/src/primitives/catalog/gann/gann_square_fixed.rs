@@ -270,6 +270,7 @@ pub fn metadata() -> PrimitiveMetadata {
         display_name: "Gann Square Fixed",
         kind: PrimitiveKind::Gann,
         factory: create_gann_square_fixed,
+        point_range: 1..=2,
         supports_text: true,
         has_levels: true,
         has_points_config: false,
@@ -180,6 +180,7 @@ pub fn metadata() -> PrimitiveMetadata {
         display_name: "Cross Line",
         kind: PrimitiveKind::Line,
         factory: create_cross_line,
+        point_range: 1..=1,
         supports_text: true,
         has_levels: false,
         has_points_config: false,
@@ -227,6 +227,7 @@ pub fn metadata() -> PrimitiveMetadata {
         display_name: "Extended Line",
         kind: PrimitiveKind::Line,
         factory: create_extended_line,
+        point_range: 2..=2,
         supports_text: true,
         has_levels: false,
         has_points_config: false,
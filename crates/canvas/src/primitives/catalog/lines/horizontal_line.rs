@@ -158,6 +158,7 @@ pub fn metadata() -> PrimitiveMetadata {
         display_name: "Horizontal Line",
         kind: PrimitiveKind::Line,
         factory: create_horizontal_line,
+        point_range: 1..=1,
         supports_text: true,
         has_levels: false,
         has_points_config: false,
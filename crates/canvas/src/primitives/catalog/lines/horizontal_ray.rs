@@ -165,6 +165,7 @@ pub fn metadata() -> PrimitiveMetadata {
         display_name: "Horizontal Ray",
         kind: PrimitiveKind::Line,
         factory: create_horizontal_ray,
+        point_range: 1..=2,
         supports_text: true,
         has_levels: false,
         has_points_config: false,
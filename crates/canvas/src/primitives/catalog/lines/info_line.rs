@@ -292,6 +292,7 @@ pub fn metadata() -> PrimitiveMetadata {
         display_name: "Info Line",
         kind: PrimitiveKind::Line,
         factory: create_info_line,
+        point_range: 2..=2,
         supports_text: true,
         has_levels: false,
         has_points_config: false,
@@ -231,6 +231,7 @@ pub fn metadata() -> PrimitiveMetadata {
         display_name: "Ray",
         kind: PrimitiveKind::Line,
         factory: create_ray,
+        point_range: 2..=2,
         supports_text: true,
         has_levels: false,
         has_points_config: false,
@@ -305,6 +305,7 @@ pub fn metadata() -> PrimitiveMetadata {
         display_name: "Trend Angle",
         kind: PrimitiveKind::Line,
         factory: create_trend_angle,
+        point_range: 2..=2,
         supports_text: true,
         has_levels: false,
         has_points_config: false,
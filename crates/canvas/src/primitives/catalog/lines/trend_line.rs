@@ -272,6 +272,7 @@ pub fn metadata() -> PrimitiveMetadata {
         display_name: "Trend Line",
         kind: PrimitiveKind::Line,
         factory: create_trend_line,
+        point_range: 2..=2,
         supports_text: true,
         has_levels: false,
         has_points_config: false,
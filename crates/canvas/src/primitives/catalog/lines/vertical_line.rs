@@ -164,6 +164,7 @@ pub fn metadata() -> PrimitiveMetadata {
         display_name: "Vertical Line",
         kind: PrimitiveKind::Line,
         factory: create_vertical_line,
+        point_range: 1..=1,
         supports_text: true,
         has_levels: false,
         has_points_config: false,
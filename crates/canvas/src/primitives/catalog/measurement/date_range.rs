@@ -136,6 +136,7 @@ pub fn metadata() -> PrimitiveMetadata {
             let (b2, _) = points.get(1).copied().unwrap_or((b1 + 20.0, p));
             Box::new(DateRange::new(b1, b2, p, color))
         },
+        point_range: 2..=2,
         supports_text: false,
         has_levels: false,
         has_points_config: false,
@@ -144,6 +144,7 @@ pub fn metadata() -> PrimitiveMetadata {
             let (_, p2) = points.get(1).copied().unwrap_or((b, p1 + 10.0));
             Box::new(PriceRange::new(b, p1, p2, color))
         },
+        point_range: 2..=2,
         supports_text: false,
         has_levels: false,
         has_points_config: false,
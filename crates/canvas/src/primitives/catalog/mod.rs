@@ -60,9 +60,9 @@ pub use volume::*;
 // Re-export core types for primitives to use via super::super
 // This maintains backward compatibility with existing primitive imports
 pub use super::core::{
-    ControlPoint, ControlPointType, ExtendMode, LineStyle, Primitive, PrimitiveColor,
-    PrimitiveData, PrimitiveKind, PrimitiveText, SyncMode, TextAlign, TextAnchor,
-    normalize_text_rotation, point_to_line_distance,
+    BandDeviation, ControlPoint, ControlPointType, ExtendMode, FibLabelMode, FillRule, LineStyle,
+    Primitive, PrimitiveColor, PrimitiveData, PrimitiveKind, PrimitiveText, SyncMode, TextAlign,
+    TextAnchor, normalize_text_rotation, point_to_line_distance,
 };
 
 // Re-export render module and its types (for super::super::render::X usage)
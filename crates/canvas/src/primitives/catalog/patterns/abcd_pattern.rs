@@ -176,6 +176,7 @@ pub fn metadata() -> PrimitiveMetadata {
             }
             Box::new(AbcdPattern::new(arr, color))
         },
+        point_range: 4..=4,
         supports_text: true,
         has_levels: false,
         has_points_config: true,
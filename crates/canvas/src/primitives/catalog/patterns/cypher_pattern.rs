@@ -180,6 +180,7 @@ pub fn metadata() -> PrimitiveMetadata {
             }
             Box::new(CypherPattern::new(arr, color))
         },
+        point_range: 5..=5,
         supports_text: true,
         has_levels: false,
         has_points_config: true,
@@ -186,6 +186,7 @@ pub fn metadata() -> PrimitiveMetadata {
             }
             Box::new(HeadShoulders::new(arr, color))
         },
+        point_range: 7..=7,
         supports_text: true,
         has_levels: false,
         has_points_config: true,
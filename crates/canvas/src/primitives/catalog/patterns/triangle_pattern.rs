@@ -245,6 +245,7 @@ pub fn metadata() -> PrimitiveMetadata {
             let (_, p2b) = points.get(3).copied().unwrap_or((b2, 93.0));
             Box::new(TrianglePattern::new(b1, p1t, p1b, b2, p2t, p2b, color))
         },
+        point_range: 3..=4,
         supports_text: true,
         has_levels: false,
         has_points_config: true,
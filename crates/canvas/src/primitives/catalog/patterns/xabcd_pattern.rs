@@ -180,6 +180,7 @@ pub fn metadata() -> PrimitiveMetadata {
             }
             Box::new(XabcdPattern::new(arr, color))
         },
+        point_range: 5..=5,
         supports_text: true,
         has_levels: false,
         has_points_config: true,
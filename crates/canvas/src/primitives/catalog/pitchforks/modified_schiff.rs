@@ -315,6 +315,7 @@ pub fn metadata() -> PrimitiveMetadata {
         display_name: "Modified Schiff",
         kind: PrimitiveKind::Channel,
         factory: create_modified_schiff,
+        point_range: 3..=3,
         supports_text: true,
         has_levels: true,
         has_points_config: false,
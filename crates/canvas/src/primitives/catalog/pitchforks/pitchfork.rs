@@ -402,6 +402,7 @@ pub fn metadata() -> PrimitiveMetadata {
         display_name: "Pitchfork",
         kind: PrimitiveKind::Channel,
         factory: create_pitchfork,
+        point_range: 3..=3,
         supports_text: true,
         has_levels: true,
         has_points_config: false,
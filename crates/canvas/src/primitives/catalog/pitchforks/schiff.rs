@@ -394,6 +394,7 @@ pub fn metadata() -> PrimitiveMetadata {
         display_name: "Schiff Pitchfork",
         kind: PrimitiveKind::Channel,
         factory: create_schiff_pitchfork,
+        point_range: 3..=3,
         supports_text: true,
         has_levels: true,
         has_points_config: false,
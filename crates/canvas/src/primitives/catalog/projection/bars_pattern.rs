@@ -167,6 +167,7 @@ pub fn metadata() -> PrimitiveMetadata {
             let (b3, _) = points.get(2).copied().unwrap_or((b2 + 10.0, 0.0));
             Box::new(BarsPattern::new(b1, b2, b3, color))
         },
+        point_range: 2..=3,
         supports_text: true,
         has_levels: false,
         has_points_config: false,
@@ -0,0 +1,263 @@
+//! Forecast Path - projected median with a confidence cone
+//!
+//! Drawn from values a model supplies for the bars after `start_bar`
+//! instead of from dragged anchor points.
+
+use super::super::{
+    LineStyle, Primitive, PrimitiveColor, PrimitiveData, PrimitiveKind, PrimitiveMetadata,
+    RenderContext, crisp,
+};
+use crate::primitives::core::{render_selection_halo, render_selection_handles};
+use serde::{Deserialize, Serialize};
+
+/// Dashed median line inside a shaded band between upper and lower bounds
+///
+/// Value `k` of each series sits on bar `start_bar + 1 + k`, so the cone
+/// covers one bar slot per value, usually in the right offset past the
+/// last bar.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ForecastPath {
+    pub data: PrimitiveData,
+    /// Bar the projection starts after, usually the last one
+    pub start_bar: f64,
+    pub median: Vec<f64>,
+    pub upper: Vec<f64>,
+    pub lower: Vec<f64>,
+    /// Cone fill opacity
+    #[serde(default = "default_fill_opacity")]
+    pub fill_opacity: f64,
+    /// Median line opacity (0.0 - 1.0)
+    #[serde(default = "default_stroke_opacity")]
+    pub stroke_opacity: f64,
+}
+
+fn default_fill_opacity() -> f64 {
+    0.15
+}
+
+fn default_stroke_opacity() -> f64 {
+    1.0
+}
+
+impl ForecastPath {
+    /// Create a forecast path; the series are cut to the shortest of them
+    pub fn new(
+        start_bar: f64,
+        median: Vec<f64>,
+        upper: Vec<f64>,
+        lower: Vec<f64>,
+        color: &str,
+    ) -> Self {
+        let mut path = Self {
+            data: PrimitiveData {
+                type_id: "forecast_path".to_string(),
+                display_name: "Forecast Path".to_string(),
+                color: PrimitiveColor::new(color),
+                width: 1.5,
+                style: LineStyle::Dashed,
+                ..Default::default()
+            },
+            start_bar,
+            median,
+            upper,
+            lower,
+            fill_opacity: default_fill_opacity(),
+            stroke_opacity: default_stroke_opacity(),
+        };
+        let len = path.len();
+        path.median.truncate(len);
+        path.upper.truncate(len);
+        path.lower.truncate(len);
+        path
+    }
+
+    /// Build from control points: medians, then upper, then lower bounds
+    ///
+    /// The first point's bar is the first projected bar; the others only
+    /// give their prices. Points past a multiple of three are ignored.
+    pub fn from_points(points: &[(f64, f64)], color: &str) -> Self {
+        let mut path = Self::new(0.0, Vec::new(), Vec::new(), Vec::new(), color);
+        path.set_points(points);
+        path
+    }
+
+    /// Number of projected bars
+    pub fn len(&self) -> usize {
+        self.median
+            .len()
+            .min(self.upper.len())
+            .min(self.lower.len())
+    }
+
+    /// True when nothing is projected
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Bar of projected value `k`
+    fn bar(&self, k: usize) -> f64 {
+        self.start_bar + 1.0 + k as f64
+    }
+}
+
+impl Primitive for ForecastPath {
+    fn type_id(&self) -> &'static str {
+        "forecast_path"
+    }
+    fn display_name(&self) -> &str {
+        &self.data.display_name
+    }
+    fn kind(&self) -> PrimitiveKind {
+        PrimitiveKind::Trading
+    }
+    fn data(&self) -> &PrimitiveData {
+        &self.data
+    }
+    fn data_mut(&mut self) -> &mut PrimitiveData {
+        &mut self.data
+    }
+    fn points(&self) -> Vec<(f64, f64)> {
+        let len = self.len();
+        [&self.median, &self.upper, &self.lower]
+            .into_iter()
+            .flat_map(|values| {
+                values[..len]
+                    .iter()
+                    .enumerate()
+                    .map(|(k, &price)| (self.bar(k), price))
+            })
+            .collect()
+    }
+    fn set_points(&mut self, pts: &[(f64, f64)]) {
+        let len = pts.len() / 3;
+        if let Some(&(bar, _)) = pts.first() {
+            self.start_bar = bar - 1.0;
+        }
+        let prices = |part: usize| {
+            pts[part * len..(part + 1) * len]
+                .iter()
+                .map(|p| p.1)
+                .collect()
+        };
+        self.median = prices(0);
+        self.upper = prices(1);
+        self.lower = prices(2);
+    }
+    fn translate(&mut self, bd: f64, pd: f64) {
+        self.start_bar += bd;
+        for value in self
+            .median
+            .iter_mut()
+            .chain(&mut self.upper)
+            .chain(&mut self.lower)
+        {
+            *value += pd;
+        }
+    }
+
+    fn render(&self, ctx: &mut dyn RenderContext, _is_selected: bool) {
+        let len = self.len();
+        if len < 2 {
+            return;
+        }
+        let dpr = ctx.dpr();
+        let xs: Vec<f64> = (0..len).map(|k| ctx.bar_to_x(self.bar(k))).collect();
+        let edge = |values: &[f64], ctx: &dyn RenderContext| -> Vec<(f64, f64)> {
+            xs.iter()
+                .zip(values)
+                .map(|(&x, &price)| (x, ctx.price_to_y(price)))
+                .collect()
+        };
+        let upper = edge(&self.upper, ctx);
+        let lower = edge(&self.lower, ctx);
+        let median = edge(&self.median, ctx);
+
+        // Cone: along the upper bound, back along the lower one
+        let fill_color = self
+            .data
+            .color
+            .fill
+            .as_deref()
+            .unwrap_or(&self.data.color.stroke);
+        ctx.set_fill_color(fill_color);
+        ctx.set_fill_opacity(self.fill_opacity);
+        ctx.begin_path();
+        ctx.move_to(upper[0].0, upper[0].1);
+        for &(x, y) in upper.iter().skip(1).chain(lower.iter().rev()) {
+            ctx.line_to(x, y);
+        }
+        ctx.close_path();
+        ctx.fill();
+
+        // Median on top
+        ctx.set_stroke_opacity(self.stroke_opacity);
+        ctx.set_stroke_color(&self.data.color.stroke);
+        ctx.set_stroke_width(self.data.width);
+        match self.data.style {
+            LineStyle::Solid => ctx.set_line_dash(&[]),
+            LineStyle::Dashed => ctx.set_line_dash(&[8.0, 4.0]),
+            LineStyle::Dotted => ctx.set_line_dash(&[2.0, 2.0]),
+            LineStyle::LargeDashed => ctx.set_line_dash(&[12.0, 6.0]),
+            LineStyle::SparseDotted => ctx.set_line_dash(&[2.0, 8.0]),
+        }
+        ctx.begin_path();
+        ctx.move_to(crisp(median[0].0, dpr), crisp(median[0].1, dpr));
+        for &(x, y) in median.iter().skip(1) {
+            ctx.line_to(crisp(x, dpr), crisp(y, dpr));
+        }
+        ctx.stroke();
+        ctx.set_line_dash(&[]);
+        ctx.reset_alpha();
+    }
+
+    /// Halo along the median with handles at its ends, rather than one
+    /// handle per projected value
+    fn render_selection(&self, ctx: &mut dyn RenderContext) {
+        let median: Vec<(f64, f64)> = self.points()[..self.len()]
+            .iter()
+            .map(|&(bar, price)| (ctx.bar_to_x(bar), ctx.price_to_y(price)))
+            .collect();
+        let ends: Vec<(f64, f64)> = median
+            .first()
+            .into_iter()
+            .chain(median.last())
+            .copied()
+            .collect();
+        render_selection_halo(ctx, &median, &self.data.color.stroke, self.data.width);
+        render_selection_handles(ctx, &ends, &self.data.color.stroke);
+    }
+
+    fn opacity(&self) -> Option<(f64, f64)> {
+        Some((self.fill_opacity, self.stroke_opacity))
+    }
+
+    fn set_opacity(&mut self, fill: Option<f64>, stroke: Option<f64>) -> bool {
+        if let Some(fill) = fill {
+            self.fill_opacity = fill.clamp(0.0, 1.0);
+        }
+        if let Some(stroke) = stroke {
+            self.stroke_opacity = stroke.clamp(0.0, 1.0);
+        }
+        true
+    }
+
+    fn to_json(&self) -> String {
+        serde_json::to_string(self).unwrap_or_default()
+    }
+    fn clone_box(&self) -> Box<dyn Primitive> {
+        Box::new(self.clone())
+    }
+}
+
+pub fn metadata() -> PrimitiveMetadata {
+    PrimitiveMetadata {
+        type_id: "forecast_path",
+        display_name: "Forecast Path",
+        kind: PrimitiveKind::Trading,
+        factory: |points, color| Box::new(ForecastPath::from_points(points, color)),
+        point_range: 3..=usize::MAX,
+        supports_text: false,
+        has_levels: false,
+        has_points_config: false,
+    }
+}
@@ -149,6 +149,7 @@ pub fn metadata() -> PrimitiveMetadata {
             let (b2, p2) = points.get(1).copied().unwrap_or((b1 + 20.0, p1 + 10.0));
             Box::new(Projection::new(b1, p1, b2, p2, color))
         },
+        point_range: 2..=2,
         supports_text: true,
         has_levels: false,
         has_points_config: false,
@@ -2,6 +2,7 @@
 
 pub mod bars_pattern;
 pub mod forecast;
+pub mod forecast_path;
 pub mod general;
 pub mod long_position;
 pub mod price_projection;
@@ -9,6 +10,7 @@ pub mod short_position;
 
 pub use bars_pattern::BarsPattern;
 pub use forecast::Forecast;
+pub use forecast_path::ForecastPath;
 pub use general::Projection;
 pub use long_position::LongPosition;
 pub use price_projection::PriceProjection;
@@ -188,6 +188,7 @@ pub fn metadata() -> PrimitiveMetadata {
             let (b3, p3) = points.get(2).copied().unwrap_or((b2 + 10.0, p2 + 5.0));
             Box::new(PriceProjection::new(b1, p1, b2, p2, b3, p3, color))
         },
+        point_range: 3..=3,
         supports_text: true,
         has_levels: false,
         has_points_config: false,
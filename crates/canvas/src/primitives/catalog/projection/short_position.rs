@@ -201,6 +201,7 @@ pub fn metadata() -> PrimitiveMetadata {
             let (_, target) = points.get(2).copied().unwrap_or((b, entry - 10.0));
             Box::new(ShortPosition::new(b, entry, stop, target, color))
         },
+        point_range: 3..=3,
         supports_text: true,
         has_levels: false,
         has_points_config: false,
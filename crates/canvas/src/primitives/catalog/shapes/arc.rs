@@ -206,6 +206,7 @@ pub fn metadata() -> PrimitiveMetadata {
         display_name: "Arc",
         kind: PrimitiveKind::Shape,
         factory: create_arc,
+        point_range: 1..=3,
         supports_text: true,
         has_levels: false,
         has_points_config: false,
@@ -28,6 +28,9 @@ pub struct Circle {
     /// Fill opacity (0.0 - 1.0)
     #[serde(default = "default_fill_opacity")]
     pub fill_opacity: f64,
+    /// Border opacity (0.0 - 1.0)
+    #[serde(default = "default_stroke_opacity")]
+    pub stroke_opacity: f64,
 }
 
 fn default_true() -> bool {
@@ -38,6 +41,10 @@ fn default_fill_opacity() -> f64 {
     0.2
 }
 
+fn default_stroke_opacity() -> f64 {
+    1.0
+}
+
 impl Circle {
     /// Create a new circle
     pub fn new(
@@ -61,6 +68,7 @@ impl Circle {
             radius_price,
             fill: true,
             fill_opacity: 0.2,
+            stroke_opacity: 1.0,
         }
     }
 
@@ -137,14 +145,20 @@ impl Primitive for Circle {
 
         // Fill if enabled
         if self.fill {
-            let alpha_hex = (self.fill_opacity * 255.0) as u8;
-            let fill_color = format!("{}{:02x}", &self.data.color.stroke[..7], alpha_hex);
-            ctx.set_fill_color(&fill_color);
+            let fill_color = self
+                .data
+                .color
+                .fill
+                .as_deref()
+                .unwrap_or(&self.data.color.stroke);
+            ctx.set_fill_color(fill_color);
+            ctx.set_fill_opacity(self.fill_opacity);
             ctx.begin_path();
             ctx.ellipse(EllipseParams::full(cx, cy, rx, ry));
             ctx.fill();
         }
 
+        ctx.set_stroke_opacity(self.stroke_opacity);
         ctx.set_stroke_color(&self.data.color.stroke);
         ctx.set_stroke_width(self.data.width);
         match self.data.style {
@@ -159,6 +173,7 @@ impl Primitive for Circle {
         ctx.ellipse(EllipseParams::full(cx, cy, rx, ry));
         ctx.stroke();
         ctx.set_line_dash(&[]);
+        ctx.reset_alpha();
     }
 
     fn text_anchor(&self, ctx: &dyn RenderContext) -> Option<TextAnchor> {
@@ -189,6 +204,20 @@ impl Primitive for Circle {
         Some(TextAnchor::new(x, y, &self.data.color.stroke))
     }
 
+    fn opacity(&self) -> Option<(f64, f64)> {
+        Some((self.fill_opacity, self.stroke_opacity))
+    }
+
+    fn set_opacity(&mut self, fill: Option<f64>, stroke: Option<f64>) -> bool {
+        if let Some(fill) = fill {
+            self.fill_opacity = fill.clamp(0.0, 1.0);
+        }
+        if let Some(stroke) = stroke {
+            self.stroke_opacity = stroke.clamp(0.0, 1.0);
+        }
+        true
+    }
+
     fn to_json(&self) -> String {
         serde_json::to_string(self).unwrap_or_default()
     }
@@ -231,6 +260,7 @@ pub fn metadata() -> PrimitiveMetadata {
         display_name: "Circle",
         kind: PrimitiveKind::Shape,
         factory: create_circle,
+        point_range: 1..=2,
         supports_text: true,
         has_levels: false,
         has_points_config: false,
@@ -215,6 +215,7 @@ pub fn metadata() -> PrimitiveMetadata {
         display_name: "Curve",
         kind: PrimitiveKind::Shape,
         factory: create_curve,
+        point_range: 2..=3,
         supports_text: true,
         has_levels: false,
         has_points_config: false,
@@ -267,6 +267,7 @@ pub fn metadata() -> PrimitiveMetadata {
         display_name: "Double Curve",
         kind: PrimitiveKind::Shape,
         factory: create_double_curve,
+        point_range: 2..=4,
         supports_text: true,
         has_levels: false,
         has_points_config: false,
@@ -27,6 +27,9 @@ pub struct Ellipse {
     /// Fill opacity (0.0 - 1.0)
     #[serde(default = "default_fill_opacity")]
     pub fill_opacity: f64,
+    /// Border opacity (0.0 - 1.0)
+    #[serde(default = "default_stroke_opacity")]
+    pub stroke_opacity: f64,
 }
 
 fn default_true() -> bool {
@@ -37,6 +40,10 @@ fn default_fill_opacity() -> f64 {
     0.2
 }
 
+fn default_stroke_opacity() -> f64 {
+    1.0
+}
+
 impl Ellipse {
     /// Create a new ellipse
     pub fn new(
@@ -60,6 +67,7 @@ impl Ellipse {
             radius_price,
             fill: true,
             fill_opacity: 0.2,
+            stroke_opacity: 1.0,
         }
     }
 
@@ -133,13 +141,19 @@ impl Primitive for Ellipse {
 
         // Fill if enabled
         if self.fill {
-            let alpha_hex = (self.fill_opacity * 255.0) as u8;
-            let fill_color = format!("{}{:02x}", &self.data.color.stroke[..7], alpha_hex);
-            ctx.set_fill_color(&fill_color);
+            let fill_color = self
+                .data
+                .color
+                .fill
+                .as_deref()
+                .unwrap_or(&self.data.color.stroke);
+            ctx.set_fill_color(fill_color);
+            ctx.set_fill_opacity(self.fill_opacity);
             ctx.ellipse(EllipseParams::full(cx, cy, rx, ry));
             ctx.fill();
         }
 
+        ctx.set_stroke_opacity(self.stroke_opacity);
         ctx.set_stroke_color(&self.data.color.stroke);
         ctx.set_stroke_width(self.data.width);
         match self.data.style {
@@ -154,6 +168,7 @@ impl Primitive for Ellipse {
         ctx.ellipse(EllipseParams::full(cx, cy, rx, ry));
         ctx.stroke();
         ctx.set_line_dash(&[]);
+        ctx.reset_alpha();
     }
 
     fn text_anchor(&self, ctx: &dyn RenderContext) -> Option<TextAnchor> {
@@ -184,6 +199,20 @@ impl Primitive for Ellipse {
         Some(TextAnchor::new(x, y, &self.data.color.stroke))
     }
 
+    fn opacity(&self) -> Option<(f64, f64)> {
+        Some((self.fill_opacity, self.stroke_opacity))
+    }
+
+    fn set_opacity(&mut self, fill: Option<f64>, stroke: Option<f64>) -> bool {
+        if let Some(fill) = fill {
+            self.fill_opacity = fill.clamp(0.0, 1.0);
+        }
+        if let Some(stroke) = stroke {
+            self.stroke_opacity = stroke.clamp(0.0, 1.0);
+        }
+        true
+    }
+
     fn to_json(&self) -> String {
         serde_json::to_string(self).unwrap_or_default()
     }
@@ -224,6 +253,7 @@ pub fn metadata() -> PrimitiveMetadata {
         display_name: "Ellipse",
         kind: PrimitiveKind::Shape,
         factory: create_ellipse,
+        point_range: 1..=2,
         supports_text: true,
         has_levels: false,
         has_points_config: false,
@@ -3,8 +3,8 @@
 //! A freeform path that can contain straight and curved segments.
 
 use super::super::{
-    LineStyle, Primitive, PrimitiveColor, PrimitiveData, PrimitiveKind, PrimitiveMetadata,
-    RenderContext, TextAlign, TextAnchor, crisp,
+    FillRule, LineStyle, Primitive, PrimitiveColor, PrimitiveData, PrimitiveKind,
+    PrimitiveMetadata, RenderContext, TextAlign, TextAnchor, crisp,
 };
 use serde::{Deserialize, Serialize};
 
@@ -21,6 +21,9 @@ pub struct Path {
     /// Close the path
     #[serde(default)]
     pub closed: bool,
+    /// How a closed path that crosses itself is filled
+    #[serde(default)]
+    pub fill_rule: FillRule,
 }
 
 impl Path {
@@ -37,6 +40,7 @@ impl Path {
             points_data: points,
             smooth: false,
             closed: false,
+            fill_rule: FillRule::NonZero,
         }
     }
 
@@ -57,6 +61,39 @@ impl Path {
         let n = self.points_data.len() as f64;
         (sum.0 / n, sum.1 / n)
     }
+
+    /// Begin a path through the screen points, smoothed if enabled
+    fn trace(&self, ctx: &mut dyn RenderContext, screen_points: &[(f64, f64)], dpr: f64) {
+        ctx.begin_path();
+        if self.smooth && screen_points.len() >= 3 {
+            // Smooth path using quadratic curves through points
+            ctx.move_to(screen_points[0].0, screen_points[0].1);
+            for i in 1..screen_points.len() - 1 {
+                let (x0, y0) = screen_points[i - 1];
+                let (x1, y1) = screen_points[i];
+                let (x2, y2) = screen_points[i + 1];
+                let cp_x = x1;
+                let cp_y = y1;
+                let end_x = (x1 + x2) / 2.0;
+                let end_y = (y1 + y2) / 2.0;
+                if i == 1 {
+                    ctx.line_to((x0 + x1) / 2.0, (y0 + y1) / 2.0);
+                }
+                ctx.quadratic_curve_to(cp_x, cp_y, end_x, end_y);
+            }
+            let last = screen_points.last().unwrap();
+            ctx.line_to(last.0, last.1);
+        } else {
+            // Straight lines
+            ctx.move_to(
+                crisp(screen_points[0].0, dpr),
+                crisp(screen_points[0].1, dpr),
+            );
+            for (x, y) in screen_points.iter().skip(1) {
+                ctx.line_to(crisp(*x, dpr), crisp(*y, dpr));
+            }
+        }
+    }
 }
 
 impl Primitive for Path {
@@ -107,6 +144,18 @@ impl Primitive for Path {
             .map(|(b, p)| (ctx.bar_to_x(*b), ctx.price_to_y(*p)))
             .collect();
 
+        // A closed path with a fill color is filled under its outline;
+        // a path ending where it started counts as closed
+        let closed = self.closed || self.points_data.first() == self.points_data.last();
+        let fill_color = self.data.color.fill.as_deref().filter(|_| closed);
+        if let Some(fill_color) = fill_color {
+            ctx.set_fill_color(fill_color);
+            ctx.set_fill_rule(self.fill_rule);
+            self.trace(ctx, &screen_points, dpr);
+            ctx.close_path();
+            ctx.fill();
+        }
+
         ctx.set_stroke_color(&self.data.color.stroke);
         ctx.set_stroke_width(self.data.width);
         match self.data.style {
@@ -117,35 +166,7 @@ impl Primitive for Path {
             LineStyle::SparseDotted => ctx.set_line_dash(&[2.0, 8.0]),
         }
 
-        ctx.begin_path();
-        if self.smooth && screen_points.len() >= 3 {
-            // Smooth path using quadratic curves through points
-            ctx.move_to(screen_points[0].0, screen_points[0].1);
-            for i in 1..screen_points.len() - 1 {
-                let (x0, y0) = screen_points[i - 1];
-                let (x1, y1) = screen_points[i];
-                let (x2, y2) = screen_points[i + 1];
-                let cp_x = x1;
-                let cp_y = y1;
-                let end_x = (x1 + x2) / 2.0;
-                let end_y = (y1 + y2) / 2.0;
-                if i == 1 {
-                    ctx.line_to((x0 + x1) / 2.0, (y0 + y1) / 2.0);
-                }
-                ctx.quadratic_curve_to(cp_x, cp_y, end_x, end_y);
-            }
-            let last = screen_points.last().unwrap();
-            ctx.line_to(last.0, last.1);
-        } else {
-            // Straight lines
-            ctx.move_to(
-                crisp(screen_points[0].0, dpr),
-                crisp(screen_points[0].1, dpr),
-            );
-            for (x, y) in screen_points.iter().skip(1) {
-                ctx.line_to(crisp(*x, dpr), crisp(*y, dpr));
-            }
-        }
+        self.trace(ctx, &screen_points, dpr);
         if self.closed {
             ctx.close_path();
         }
@@ -153,6 +174,15 @@ impl Primitive for Path {
         ctx.set_line_dash(&[]);
     }
 
+    fn fill_rule(&self) -> Option<FillRule> {
+        Some(self.fill_rule)
+    }
+
+    fn set_fill_rule(&mut self, rule: FillRule) -> bool {
+        self.fill_rule = rule;
+        true
+    }
+
     fn text_anchor(&self, ctx: &dyn RenderContext) -> Option<TextAnchor> {
         let text = self.data.text.as_ref()?;
         if text.content.is_empty() {
@@ -229,6 +259,7 @@ pub fn metadata() -> PrimitiveMetadata {
         display_name: "Path",
         kind: PrimitiveKind::Shape,
         factory: create_path,
+        point_range: 2..=usize::MAX,
         supports_text: true,
         has_levels: false,
         has_points_config: false,
@@ -3,8 +3,8 @@
 //! A series of connected straight line segments.
 
 use super::super::{
-    LineStyle, Primitive, PrimitiveColor, PrimitiveData, PrimitiveKind, PrimitiveMetadata,
-    RenderContext, TextAlign, TextAnchor, crisp,
+    FillRule, LineStyle, Primitive, PrimitiveColor, PrimitiveData, PrimitiveKind,
+    PrimitiveMetadata, RenderContext, TextAlign, TextAnchor, crisp,
 };
 use serde::{Deserialize, Serialize};
 
@@ -24,12 +24,22 @@ pub struct Polyline {
     /// Fill opacity
     #[serde(default = "default_fill_opacity")]
     pub fill_opacity: f64,
+    /// Border opacity (0.0 - 1.0)
+    #[serde(default = "default_stroke_opacity")]
+    pub stroke_opacity: f64,
+    /// How a closed outline that crosses itself is filled
+    #[serde(default)]
+    pub fill_rule: FillRule,
 }
 
 fn default_fill_opacity() -> f64 {
     0.2
 }
 
+fn default_stroke_opacity() -> f64 {
+    1.0
+}
+
 impl Polyline {
     /// Create a new polyline
     pub fn new(points: Vec<(f64, f64)>, color: &str) -> Self {
@@ -45,6 +55,8 @@ impl Polyline {
             closed: false,
             fill: false,
             fill_opacity: 0.2,
+            stroke_opacity: 1.0,
+            fill_rule: FillRule::NonZero,
         }
     }
 
@@ -125,9 +137,15 @@ impl Primitive for Polyline {
 
         // Fill if closed and fill enabled
         if self.closed && self.fill && screen_points.len() >= 3 {
-            let alpha_hex = (self.fill_opacity * 255.0) as u8;
-            let fill_color = format!("{}{:02x}", &self.data.color.stroke[..7], alpha_hex);
-            ctx.set_fill_color(&fill_color);
+            let fill_color = self
+                .data
+                .color
+                .fill
+                .as_deref()
+                .unwrap_or(&self.data.color.stroke);
+            ctx.set_fill_color(fill_color);
+            ctx.set_fill_opacity(self.fill_opacity);
+            ctx.set_fill_rule(self.fill_rule);
             ctx.begin_path();
             ctx.move_to(screen_points[0].0, screen_points[0].1);
             for (x, y) in screen_points.iter().skip(1) {
@@ -138,6 +156,7 @@ impl Primitive for Polyline {
         }
 
         // Draw stroke
+        ctx.set_stroke_opacity(self.stroke_opacity);
         ctx.set_stroke_color(&self.data.color.stroke);
         ctx.set_stroke_width(self.data.width);
         match self.data.style {
@@ -161,6 +180,7 @@ impl Primitive for Polyline {
         }
         ctx.stroke();
         ctx.set_line_dash(&[]);
+        ctx.reset_alpha();
     }
 
     fn text_anchor(&self, ctx: &dyn RenderContext) -> Option<TextAnchor> {
@@ -216,6 +236,29 @@ impl Primitive for Polyline {
         Some(TextAnchor::new(x, y, &self.data.color.stroke))
     }
 
+    fn opacity(&self) -> Option<(f64, f64)> {
+        Some((self.fill_opacity, self.stroke_opacity))
+    }
+
+    fn set_opacity(&mut self, fill: Option<f64>, stroke: Option<f64>) -> bool {
+        if let Some(fill) = fill {
+            self.fill_opacity = fill.clamp(0.0, 1.0);
+        }
+        if let Some(stroke) = stroke {
+            self.stroke_opacity = stroke.clamp(0.0, 1.0);
+        }
+        true
+    }
+
+    fn fill_rule(&self) -> Option<FillRule> {
+        Some(self.fill_rule)
+    }
+
+    fn set_fill_rule(&mut self, rule: FillRule) -> bool {
+        self.fill_rule = rule;
+        true
+    }
+
     fn to_json(&self) -> String {
         serde_json::to_string(self).unwrap_or_default()
     }
@@ -239,6 +282,7 @@ pub fn metadata() -> PrimitiveMetadata {
         display_name: "Polyline",
         kind: PrimitiveKind::Shape,
         factory: create_polyline,
+        point_range: 2..=usize::MAX,
         supports_text: true,
         has_levels: false,
         has_points_config: false,
@@ -24,9 +24,13 @@ pub struct Rectangle {
     /// Fill the rectangle
     #[serde(default = "default_true")]
     pub fill: bool,
-    /// Fill opacity (0.0 - 1.0)
-    #[serde(default = "default_fill_opacity")]
-    pub fill_opacity: f64,
+    /// Fill opacity (0.0 - 1.0); when unset, 0.15 with an explicit fill
+    /// color and 0.2 when the fill follows the stroke color
+    #[serde(default)]
+    pub fill_opacity: Option<f64>,
+    /// Border opacity (0.0 - 1.0)
+    #[serde(default = "default_stroke_opacity")]
+    pub stroke_opacity: f64,
     /// Border radius for rounded corners (0 = sharp)
     #[serde(default)]
     pub border_radius: f64,
@@ -36,8 +40,8 @@ fn default_true() -> bool {
     true
 }
 
-fn default_fill_opacity() -> f64 {
-    0.2
+fn default_stroke_opacity() -> f64 {
+    1.0
 }
 
 impl Rectangle {
@@ -56,11 +60,22 @@ impl Rectangle {
             bar2,
             price2,
             fill: true,
-            fill_opacity: 0.2,
+            fill_opacity: None,
+            stroke_opacity: 1.0,
             border_radius: 0.0,
         }
     }
 
+    /// Effective fill opacity
+    pub fn effective_fill_opacity(&self) -> f64 {
+        let default = if self.data.color.fill.is_some() {
+            0.15
+        } else {
+            0.2
+        };
+        self.fill_opacity.unwrap_or(default)
+    }
+
     /// Get normalized corners (min/max)
     pub fn normalized(&self) -> (f64, f64, f64, f64) {
         let min_bar = self.bar1.min(self.bar2);
@@ -146,13 +161,19 @@ impl Primitive for Rectangle {
 
         // Fill if enabled
         if self.fill {
-            let alpha_hex = (self.fill_opacity * 255.0) as u8;
-            let fill_color = format!("{}{:02x}", &self.data.color.stroke[..7], alpha_hex);
-            ctx.set_fill_color(&fill_color);
+            let fill_color = self
+                .data
+                .color
+                .fill
+                .as_deref()
+                .unwrap_or(&self.data.color.stroke);
+            ctx.set_fill_color(fill_color);
+            ctx.set_fill_opacity(self.effective_fill_opacity());
             ctx.fill_rect(min_x, min_y, width, height);
         }
 
         // Set stroke style
+        ctx.set_stroke_opacity(self.stroke_opacity);
         ctx.set_stroke_color(&self.data.color.stroke);
         ctx.set_stroke_width(self.data.width);
 
@@ -167,6 +188,7 @@ impl Primitive for Rectangle {
         // Draw rectangle border
         ctx.stroke_rect(crisp(min_x, dpr), crisp(min_y, dpr), width, height);
         ctx.set_line_dash(&[]);
+        ctx.reset_alpha();
     }
 
     fn text_anchor(&self, ctx: &dyn RenderContext) -> Option<TextAnchor> {
@@ -201,6 +223,20 @@ impl Primitive for Rectangle {
         Some(TextAnchor::new(x, y, &self.data.color.stroke))
     }
 
+    fn opacity(&self) -> Option<(f64, f64)> {
+        Some((self.effective_fill_opacity(), self.stroke_opacity))
+    }
+
+    fn set_opacity(&mut self, fill: Option<f64>, stroke: Option<f64>) -> bool {
+        if let Some(fill) = fill {
+            self.fill_opacity = Some(fill.clamp(0.0, 1.0));
+        }
+        if let Some(stroke) = stroke {
+            self.stroke_opacity = stroke.clamp(0.0, 1.0);
+        }
+        true
+    }
+
     fn to_json(&self) -> String {
         serde_json::to_string(self).unwrap_or_default()
     }
@@ -229,6 +265,7 @@ pub fn metadata() -> PrimitiveMetadata {
         display_name: "Rectangle",
         kind: PrimitiveKind::Shape,
         factory: create_rectangle,
+        point_range: 2..=2,
         supports_text: true,
         has_levels: false,
         has_points_config: false,
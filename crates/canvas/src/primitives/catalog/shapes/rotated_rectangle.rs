@@ -29,6 +29,9 @@ pub struct RotatedRectangle {
     /// Fill opacity
     #[serde(default = "default_fill_opacity")]
     pub fill_opacity: f64,
+    /// Border opacity (0.0 - 1.0)
+    #[serde(default = "default_stroke_opacity")]
+    pub stroke_opacity: f64,
 }
 
 fn default_true() -> bool {
@@ -39,6 +42,10 @@ fn default_fill_opacity() -> f64 {
     0.2
 }
 
+fn default_stroke_opacity() -> f64 {
+    1.0
+}
+
 impl RotatedRectangle {
     /// Create a new rotated rectangle
     pub fn new(
@@ -64,6 +71,7 @@ impl RotatedRectangle {
             rotation,
             fill: true,
             fill_opacity: 0.2,
+            stroke_opacity: 1.0,
         }
     }
 
@@ -181,9 +189,14 @@ impl Primitive for RotatedRectangle {
 
         // Fill if enabled
         if self.fill {
-            let alpha_hex = (self.fill_opacity * 255.0) as u8;
-            let fill_color = format!("{}{:02x}", &self.data.color.stroke[..7], alpha_hex);
-            ctx.set_fill_color(&fill_color);
+            let fill_color = self
+                .data
+                .color
+                .fill
+                .as_deref()
+                .unwrap_or(&self.data.color.stroke);
+            ctx.set_fill_color(fill_color);
+            ctx.set_fill_opacity(self.fill_opacity);
             ctx.begin_path();
             ctx.move_to(screen_corners[0].0, screen_corners[0].1);
             for (x, y) in screen_corners.iter().skip(1) {
@@ -194,6 +207,7 @@ impl Primitive for RotatedRectangle {
         }
 
         // Draw stroke
+        ctx.set_stroke_opacity(self.stroke_opacity);
         ctx.set_stroke_color(&self.data.color.stroke);
         ctx.set_stroke_width(self.data.width);
         match self.data.style {
@@ -215,6 +229,7 @@ impl Primitive for RotatedRectangle {
         ctx.close_path();
         ctx.stroke();
         ctx.set_line_dash(&[]);
+        ctx.reset_alpha();
 
         let _ = is_selected;
     }
@@ -259,6 +274,20 @@ impl Primitive for RotatedRectangle {
         Some(TextAnchor::new(x, y, &self.data.color.stroke))
     }
 
+    fn opacity(&self) -> Option<(f64, f64)> {
+        Some((self.fill_opacity, self.stroke_opacity))
+    }
+
+    fn set_opacity(&mut self, fill: Option<f64>, stroke: Option<f64>) -> bool {
+        if let Some(fill) = fill {
+            self.fill_opacity = fill.clamp(0.0, 1.0);
+        }
+        if let Some(stroke) = stroke {
+            self.stroke_opacity = stroke.clamp(0.0, 1.0);
+        }
+        true
+    }
+
     fn to_json(&self) -> String {
         serde_json::to_string(self).unwrap_or_default()
     }
@@ -296,6 +325,7 @@ pub fn metadata() -> PrimitiveMetadata {
         display_name: "Rotated Rectangle",
         kind: PrimitiveKind::Shape,
         factory: create_rotated_rectangle,
+        point_range: 1..=3,
         supports_text: true,
         has_levels: false,
         has_points_config: false,
@@ -31,6 +31,9 @@ pub struct Triangle {
     /// Fill opacity (0.0 - 1.0)
     #[serde(default = "default_fill_opacity")]
     pub fill_opacity: f64,
+    /// Border opacity (0.0 - 1.0)
+    #[serde(default = "default_stroke_opacity")]
+    pub stroke_opacity: f64,
 }
 
 fn default_true() -> bool {
@@ -41,6 +44,10 @@ fn default_fill_opacity() -> f64 {
     0.2
 }
 
+fn default_stroke_opacity() -> f64 {
+    1.0
+}
+
 impl Triangle {
     /// Create a new triangle
     pub fn new(
@@ -68,6 +75,7 @@ impl Triangle {
             price3,
             fill: true,
             fill_opacity: 0.2,
+            stroke_opacity: 1.0,
         }
     }
 
@@ -143,9 +151,14 @@ impl Primitive for Triangle {
         let y3 = ctx.price_to_y(self.price3);
 
         if self.fill {
-            let alpha_hex = (self.fill_opacity * 255.0) as u8;
-            let fill_color = format!("{}{:02x}", &self.data.color.stroke[..7], alpha_hex);
-            ctx.set_fill_color(&fill_color);
+            let fill_color = self
+                .data
+                .color
+                .fill
+                .as_deref()
+                .unwrap_or(&self.data.color.stroke);
+            ctx.set_fill_color(fill_color);
+            ctx.set_fill_opacity(self.fill_opacity);
             ctx.begin_path();
             ctx.move_to(x1, y1);
             ctx.line_to(x2, y2);
@@ -154,6 +167,7 @@ impl Primitive for Triangle {
             ctx.fill();
         }
 
+        ctx.set_stroke_opacity(self.stroke_opacity);
         ctx.set_stroke_color(&self.data.color.stroke);
         ctx.set_stroke_width(self.data.width);
         match self.data.style {
@@ -171,6 +185,7 @@ impl Primitive for Triangle {
         ctx.close_path();
         ctx.stroke();
         ctx.set_line_dash(&[]);
+        ctx.reset_alpha();
     }
 
     fn text_anchor(&self, ctx: &dyn RenderContext) -> Option<TextAnchor> {
@@ -212,6 +227,20 @@ impl Primitive for Triangle {
         Some(TextAnchor::new(x, y, &self.data.color.stroke))
     }
 
+    fn opacity(&self) -> Option<(f64, f64)> {
+        Some((self.fill_opacity, self.stroke_opacity))
+    }
+
+    fn set_opacity(&mut self, fill: Option<f64>, stroke: Option<f64>) -> bool {
+        if let Some(fill) = fill {
+            self.fill_opacity = fill.clamp(0.0, 1.0);
+        }
+        if let Some(stroke) = stroke {
+            self.stroke_opacity = stroke.clamp(0.0, 1.0);
+        }
+        true
+    }
+
     fn to_json(&self) -> String {
         serde_json::to_string(self).unwrap_or_default()
     }
@@ -246,6 +275,7 @@ pub fn metadata() -> PrimitiveMetadata {
         display_name: "Triangle",
         kind: PrimitiveKind::Shape,
         factory: create_triangle,
+        point_range: 3..=3,
         supports_text: true,
         has_levels: false,
         has_points_config: false,
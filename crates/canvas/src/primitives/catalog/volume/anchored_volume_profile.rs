@@ -142,6 +142,7 @@ pub fn metadata() -> PrimitiveMetadata {
             let (b, _) = points.first().copied().unwrap_or((0.0, 0.0));
             Box::new(AnchoredVolumeProfile::new(b, color))
         },
+        point_range: 1..=2,
         supports_text: true,
         has_levels: false,
         has_points_config: false,
@@ -0,0 +1,738 @@
+//! Composite Primitive - data-driven drawing tools
+//!
+//! A `CompositeSpec` describes a tool as a list of drawing ops (lines,
+//! rects, texts, arcs) whose coordinates are affine expressions of the
+//! tool's control points, e.g. `"p0.x"` or `"lerp(p0.y, p1.y, 0.5)"`.
+//! Specs are plain JSON, so hosts can define tools without Rust code, and
+//! are registered on a `PrimitiveRegistry` under their own type ID.
+//!
+//! Expression grammar: numbers, `pN.x` / `pN.y`, `+ - * /` (products and
+//! divisors must keep the result affine), parentheses and
+//! `lerp(a, b, t)` with constant `t`.
+
+use super::core::{
+    Primitive, PrimitiveColor, PrimitiveData, PrimitiveKind, RenderContext, RenderTextAlign,
+    TextAlign, TextBaseline, crisp,
+};
+use super::registry::PrimitiveError;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fmt;
+use std::str::FromStr;
+use std::sync::{Arc, Mutex, OnceLock};
+
+// =============================================================================
+// Expressions
+// =============================================================================
+
+/// Control point coordinate referenced by an expression
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct PointRef {
+    point: usize,
+    y: bool,
+}
+
+/// `constant + sum(coef * coordinate)`
+#[derive(Clone, Debug, Default, PartialEq)]
+struct Affine {
+    constant: f64,
+    terms: Vec<(PointRef, f64)>,
+}
+
+impl Affine {
+    fn constant(value: f64) -> Self {
+        Self {
+            constant: value,
+            terms: Vec::new(),
+        }
+    }
+
+    fn is_constant(&self) -> bool {
+        self.terms.is_empty()
+    }
+
+    fn scale(mut self, k: f64) -> Self {
+        self.constant *= k;
+        for (_, coef) in &mut self.terms {
+            *coef *= k;
+        }
+        self
+    }
+
+    fn add(mut self, other: Affine) -> Self {
+        self.constant += other.constant;
+        for (r, coef) in other.terms {
+            match self.terms.iter_mut().find(|(t, _)| *t == r) {
+                Some((_, c)) => *c += coef,
+                None => self.terms.push((r, coef)),
+            }
+        }
+        self
+    }
+}
+
+/// Affine coordinate expression over control points
+///
+/// Serialized as its source text; numbers are accepted on input.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CoordExpr {
+    source: String,
+    affine: Affine,
+}
+
+impl CoordExpr {
+    /// Evaluate against (bar, price) control points; missing points count as 0
+    pub fn eval(&self, points: &[(f64, f64)]) -> f64 {
+        self.affine
+            .terms
+            .iter()
+            .fold(self.affine.constant, |acc, (r, coef)| {
+                let (x, y) = points.get(r.point).copied().unwrap_or_default();
+                acc + coef * if r.y { y } else { x }
+            })
+    }
+
+    /// Highest control point index referenced, if any
+    pub fn max_point(&self) -> Option<usize> {
+        self.affine.terms.iter().map(|(r, _)| r.point).max()
+    }
+
+    /// Expression source text
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+}
+
+impl FromStr for CoordExpr {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parser = Parser {
+            chars: s.chars().filter(|c| !c.is_whitespace()).collect(),
+            pos: 0,
+        };
+        let affine = parser.expr()?;
+        if parser.pos < parser.chars.len() {
+            return Err(format!(
+                "unexpected '{}' in \"{}\"",
+                parser.chars[parser.pos], s
+            ));
+        }
+        Ok(Self {
+            source: s.trim().to_string(),
+            affine,
+        })
+    }
+}
+
+impl fmt::Display for CoordExpr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.source)
+    }
+}
+
+impl Serialize for CoordExpr {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.source)
+    }
+}
+
+impl<'de> Deserialize<'de> for CoordExpr {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Raw {
+            Text(String),
+            Number(f64),
+        }
+        match Raw::deserialize(deserializer)? {
+            Raw::Text(s) => s.parse().map_err(serde::de::Error::custom),
+            Raw::Number(n) => Ok(Self {
+                source: n.to_string(),
+                affine: Affine::constant(n),
+            }),
+        }
+    }
+}
+
+struct Parser {
+    chars: Vec<char>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        if self.peek() == Some(c) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, c: char) -> Result<(), String> {
+        if self.eat(c) {
+            Ok(())
+        } else {
+            Err(format!("expected '{}' at position {}", c, self.pos))
+        }
+    }
+
+    fn expr(&mut self) -> Result<Affine, String> {
+        let mut acc = self.term()?;
+        loop {
+            if self.eat('+') {
+                acc = acc.add(self.term()?);
+            } else if self.eat('-') {
+                acc = acc.add(self.term()?.scale(-1.0));
+            } else {
+                return Ok(acc);
+            }
+        }
+    }
+
+    fn term(&mut self) -> Result<Affine, String> {
+        let mut acc = self.unary()?;
+        loop {
+            if self.eat('*') {
+                let rhs = self.unary()?;
+                acc = match (acc.is_constant(), rhs.is_constant()) {
+                    (_, true) => acc.scale(rhs.constant),
+                    (true, false) => rhs.scale(acc.constant),
+                    _ => return Err("product of two point coordinates is not affine".into()),
+                };
+            } else if self.eat('/') {
+                let rhs = self.unary()?;
+                if !rhs.is_constant() || rhs.constant == 0.0 {
+                    return Err("divisor must be a non-zero constant".into());
+                }
+                acc = acc.scale(1.0 / rhs.constant);
+            } else {
+                return Ok(acc);
+            }
+        }
+    }
+
+    fn unary(&mut self) -> Result<Affine, String> {
+        if self.eat('-') {
+            return Ok(self.unary()?.scale(-1.0));
+        }
+        self.atom()
+    }
+
+    fn atom(&mut self) -> Result<Affine, String> {
+        if self.eat('(') {
+            let inner = self.expr()?;
+            self.expect(')')?;
+            return Ok(inner);
+        }
+        match self.peek() {
+            Some(c) if c.is_ascii_digit() || c == '.' => self.number().map(Affine::constant),
+            Some(c) if c.is_ascii_alphabetic() => {
+                let start = self.pos;
+                while self.peek().is_some_and(|c| c.is_ascii_alphanumeric()) {
+                    self.pos += 1;
+                }
+                let ident: String = self.chars[start..self.pos].iter().collect();
+                if ident == "lerp" {
+                    self.lerp()
+                } else {
+                    self.point(&ident)
+                }
+            }
+            Some(c) => Err(format!("unexpected '{}' at position {}", c, self.pos)),
+            None => Err("unexpected end of expression".into()),
+        }
+    }
+
+    fn number(&mut self) -> Result<f64, String> {
+        let start = self.pos;
+        while self.peek().is_some_and(|c| c.is_ascii_digit() || c == '.') {
+            self.pos += 1;
+        }
+        let text: String = self.chars[start..self.pos].iter().collect();
+        text.parse()
+            .map_err(|_| format!("invalid number \"{}\"", text))
+    }
+
+    fn point(&mut self, ident: &str) -> Result<Affine, String> {
+        let point = ident
+            .strip_prefix('p')
+            .and_then(|n| n.parse().ok())
+            .ok_or_else(|| format!("unknown name \"{}\"", ident))?;
+        self.expect('.')?;
+        let y = match self.peek() {
+            Some('x') => false,
+            Some('y') => true,
+            _ => return Err(format!("expected .x or .y after {}", ident)),
+        };
+        self.pos += 1;
+        Ok(Affine {
+            constant: 0.0,
+            terms: vec![(PointRef { point, y }, 1.0)],
+        })
+    }
+
+    fn lerp(&mut self) -> Result<Affine, String> {
+        self.expect('(')?;
+        let a = self.expr()?;
+        self.expect(',')?;
+        let b = self.expr()?;
+        self.expect(',')?;
+        let t = self.expr()?;
+        self.expect(')')?;
+        if !t.is_constant() {
+            return Err("lerp factor must be a constant".into());
+        }
+        Ok(a.scale(1.0 - t.constant).add(b.scale(t.constant)))
+    }
+}
+
+// =============================================================================
+// Spec
+// =============================================================================
+
+/// A point given as x (bar) and y (price) expressions
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CompositePoint {
+    pub x: CoordExpr,
+    pub y: CoordExpr,
+}
+
+impl CompositePoint {
+    fn eval(&self, points: &[(f64, f64)]) -> (f64, f64) {
+        (self.x.eval(points), self.y.eval(points))
+    }
+
+    fn max_point(&self) -> Option<usize> {
+        self.x.max_point().max(self.y.max_point())
+    }
+}
+
+/// One drawing op of a composite; `color` overrides the primitive's color
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum CompositeOp {
+    Line {
+        from: CompositePoint,
+        to: CompositePoint,
+        #[serde(default)]
+        dashed: bool,
+        #[serde(default)]
+        color: Option<String>,
+    },
+    Rect {
+        from: CompositePoint,
+        to: CompositePoint,
+        /// Fill opacity; `None` draws the outline only
+        #[serde(default)]
+        fill_opacity: Option<f64>,
+        #[serde(default)]
+        color: Option<String>,
+    },
+    Text {
+        at: CompositePoint,
+        text: String,
+        #[serde(default = "default_align")]
+        align: TextAlign,
+        #[serde(default)]
+        color: Option<String>,
+    },
+    /// Circular arc around `center` through the radius of `edge`,
+    /// angles in degrees
+    Arc {
+        center: CompositePoint,
+        edge: CompositePoint,
+        #[serde(default)]
+        start_angle: f64,
+        #[serde(default = "default_end_angle")]
+        end_angle: f64,
+        #[serde(default)]
+        color: Option<String>,
+    },
+}
+
+fn default_align() -> TextAlign {
+    TextAlign::Center
+}
+
+fn default_end_angle() -> f64 {
+    360.0
+}
+
+fn default_kind() -> PrimitiveKind {
+    PrimitiveKind::Shape
+}
+
+impl CompositeOp {
+    fn anchors(&self) -> Vec<&CompositePoint> {
+        match self {
+            Self::Line { from, to, .. } | Self::Rect { from, to, .. } => vec![from, to],
+            Self::Text { at, .. } => vec![at],
+            Self::Arc { center, edge, .. } => vec![center, edge],
+        }
+    }
+}
+
+/// Declarative description of a custom drawing tool
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CompositeSpec {
+    /// Type ID used in `PrimitiveConfig`
+    pub type_id: String,
+    /// Display name for UI (defaults to `type_id`)
+    #[serde(default)]
+    pub display_name: String,
+    /// Toolbar category
+    #[serde(default = "default_kind")]
+    pub kind: PrimitiveKind,
+    /// Number of control points
+    pub points: usize,
+    /// Drawing ops, in paint order
+    pub ops: Vec<CompositeOp>,
+}
+
+impl CompositeSpec {
+    /// Parse and validate a spec from JSON
+    pub fn from_json(json: &str) -> Result<Self, PrimitiveError> {
+        let spec: Self = serde_json::from_str(json)
+            .map_err(|e| PrimitiveError::InvalidComposite(e.to_string()))?;
+        spec.validate()?;
+        Ok(spec)
+    }
+
+    /// Serialize to JSON
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).unwrap_or_default()
+    }
+
+    /// Check that the spec has points and ops, and only references its points
+    pub fn validate(&self) -> Result<(), PrimitiveError> {
+        let invalid = |msg: String| Err(PrimitiveError::InvalidComposite(msg));
+        if self.type_id.is_empty() {
+            return invalid("type_id is empty".into());
+        }
+        if self.points == 0 || self.ops.is_empty() {
+            return invalid(format!(
+                "{} needs at least one point and one op",
+                self.type_id
+            ));
+        }
+        let max = self
+            .ops
+            .iter()
+            .flat_map(|op| op.anchors())
+            .filter_map(|p| p.max_point())
+            .max();
+        if let Some(max) = max.filter(|&max| max >= self.points) {
+            return invalid(format!(
+                "{} references p{} but has {} points",
+                self.type_id, max, self.points
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Intern a type ID or display name for `&'static str` metadata
+///
+/// Each distinct string is leaked once, however often it is registered.
+pub(crate) fn intern(s: &str) -> &'static str {
+    static NAMES: OnceLock<Mutex<HashSet<&'static str>>> = OnceLock::new();
+    let mut names = NAMES
+        .get_or_init(Default::default)
+        .lock()
+        .unwrap_or_else(|e| e.into_inner());
+    match names.get(s) {
+        Some(name) => name,
+        None => {
+            let name: &'static str = Box::leak(s.to_string().into_boxed_str());
+            names.insert(name);
+            name
+        }
+    }
+}
+
+// =============================================================================
+// Primitive
+// =============================================================================
+
+/// Primitive drawn from a `CompositeSpec`
+#[derive(Clone, Debug)]
+pub struct CompositePrimitive {
+    /// Common primitive data
+    pub data: PrimitiveData,
+    type_id: &'static str,
+    spec: Arc<CompositeSpec>,
+    points: Vec<(f64, f64)>,
+}
+
+impl CompositePrimitive {
+    /// Create from a spec and its control points
+    pub fn new(spec: Arc<CompositeSpec>, points: &[(f64, f64)], color: &str) -> Self {
+        let display_name = if spec.display_name.is_empty() {
+            spec.type_id.clone()
+        } else {
+            spec.display_name.clone()
+        };
+        Self {
+            data: PrimitiveData {
+                type_id: spec.type_id.clone(),
+                display_name,
+                color: PrimitiveColor::new(color),
+                width: 1.5,
+                ..Default::default()
+            },
+            type_id: intern(&spec.type_id),
+            spec,
+            points: points.to_vec(),
+        }
+    }
+
+    /// The spec this primitive draws
+    pub fn spec(&self) -> &CompositeSpec {
+        &self.spec
+    }
+
+    /// Screen-space bounding box of the control points and all op anchors
+    pub fn bounds(&self, ctx: &dyn RenderContext) -> Option<(f64, f64, f64, f64)> {
+        let anchors = self
+            .spec
+            .ops
+            .iter()
+            .flat_map(|op| op.anchors())
+            .map(|p| p.eval(&self.points));
+        self.points
+            .iter()
+            .copied()
+            .chain(anchors)
+            .map(|(bar, price)| (ctx.bar_to_x(bar), ctx.price_to_y(price)))
+            .fold(None, |acc, (x, y)| match acc {
+                None => Some((x, y, x, y)),
+                Some((x0, y0, x1, y1)) => Some((x0.min(x), y0.min(y), x1.max(x), y1.max(y))),
+            })
+    }
+
+    /// Whether a screen position is within `tolerance` pixels of a control
+    /// point or inside the bounding box
+    pub fn hit_test(&self, ctx: &dyn RenderContext, x: f64, y: f64, tolerance: f64) -> bool {
+        let near_point = self.points.iter().any(|&(bar, price)| {
+            (ctx.bar_to_x(bar) - x).hypot(ctx.price_to_y(price) - y) <= tolerance
+        });
+        near_point
+            || self.bounds(ctx).is_some_and(|(x0, y0, x1, y1)| {
+                x >= x0 - tolerance
+                    && x <= x1 + tolerance
+                    && y >= y0 - tolerance
+                    && y <= y1 + tolerance
+            })
+    }
+
+    fn to_screen(&self, ctx: &dyn RenderContext, p: &CompositePoint) -> (f64, f64) {
+        let (bar, price) = p.eval(&self.points);
+        (ctx.bar_to_x(bar), ctx.price_to_y(price))
+    }
+}
+
+/// Built-in factory for registry metadata; it has no spec to draw,
+/// so composites are created through `PrimitiveRegistry::create`
+pub(crate) fn create_unbound(points: &[(f64, f64)], color: &str) -> Box<dyn Primitive> {
+    let spec = CompositeSpec {
+        type_id: "composite".to_string(),
+        display_name: "Composite".to_string(),
+        kind: PrimitiveKind::Shape,
+        points: points.len(),
+        ops: Vec::new(),
+    };
+    Box::new(CompositePrimitive::new(Arc::new(spec), points, color))
+}
+
+impl Primitive for CompositePrimitive {
+    fn type_id(&self) -> &'static str {
+        self.type_id
+    }
+
+    fn display_name(&self) -> &str {
+        &self.data.display_name
+    }
+
+    fn kind(&self) -> PrimitiveKind {
+        self.spec.kind
+    }
+
+    fn data(&self) -> &PrimitiveData {
+        &self.data
+    }
+
+    fn data_mut(&mut self) -> &mut PrimitiveData {
+        &mut self.data
+    }
+
+    fn points(&self) -> Vec<(f64, f64)> {
+        self.points.clone()
+    }
+
+    fn set_points(&mut self, points: &[(f64, f64)]) {
+        for (slot, &p) in self.points.iter_mut().zip(points) {
+            *slot = p;
+        }
+    }
+
+    fn translate(&mut self, bar_delta: f64, price_delta: f64) {
+        for (bar, price) in &mut self.points {
+            *bar += bar_delta;
+            *price += price_delta;
+        }
+    }
+
+    fn render(&self, ctx: &mut dyn RenderContext, _is_selected: bool) {
+        let dpr = ctx.dpr();
+        let stroke = &self.data.color.stroke;
+        ctx.set_stroke_width(self.data.width);
+
+        for op in &self.spec.ops {
+            match op {
+                CompositeOp::Line {
+                    from,
+                    to,
+                    dashed,
+                    color,
+                } => {
+                    let (x1, y1) = self.to_screen(ctx, from);
+                    let (x2, y2) = self.to_screen(ctx, to);
+                    ctx.set_stroke_color(color.as_deref().unwrap_or(stroke));
+                    if *dashed {
+                        ctx.set_line_dash(&[8.0, 4.0]);
+                    } else {
+                        ctx.set_line_style(self.data.style);
+                    }
+                    ctx.begin_path();
+                    ctx.move_to(crisp(x1, dpr), crisp(y1, dpr));
+                    ctx.line_to(crisp(x2, dpr), crisp(y2, dpr));
+                    ctx.stroke();
+                }
+                CompositeOp::Rect {
+                    from,
+                    to,
+                    fill_opacity,
+                    color,
+                } => {
+                    let (x1, y1) = self.to_screen(ctx, from);
+                    let (x2, y2) = self.to_screen(ctx, to);
+                    let (x, y, w, h) = (x1.min(x2), y1.min(y2), (x2 - x1).abs(), (y2 - y1).abs());
+                    let color = color.as_deref().unwrap_or(stroke);
+                    if let Some(opacity) = fill_opacity {
+                        ctx.set_fill_color(color);
+                        ctx.set_fill_opacity(*opacity);
+                        ctx.fill_rect(x, y, w, h);
+                        ctx.reset_alpha();
+                    }
+                    ctx.set_stroke_color(color);
+                    ctx.set_line_style(self.data.style);
+                    ctx.stroke_rect(x, y, w, h);
+                }
+                CompositeOp::Text {
+                    at,
+                    text,
+                    align,
+                    color,
+                } => {
+                    let (x, y) = self.to_screen(ctx, at);
+                    ctx.set_fill_color(color.as_deref().unwrap_or(stroke));
+                    ctx.set_font("12px sans-serif");
+                    ctx.set_text_align(match align {
+                        TextAlign::Start => RenderTextAlign::Left,
+                        TextAlign::Center => RenderTextAlign::Center,
+                        TextAlign::End => RenderTextAlign::Right,
+                    });
+                    ctx.set_text_baseline(TextBaseline::Middle);
+                    ctx.fill_text(text, x, y);
+                }
+                CompositeOp::Arc {
+                    center,
+                    edge,
+                    start_angle,
+                    end_angle,
+                    color,
+                } => {
+                    let (cx, cy) = self.to_screen(ctx, center);
+                    let (ex, ey) = self.to_screen(ctx, edge);
+                    ctx.set_stroke_color(color.as_deref().unwrap_or(stroke));
+                    ctx.set_line_style(self.data.style);
+                    ctx.begin_path();
+                    ctx.arc(
+                        cx,
+                        cy,
+                        (ex - cx).hypot(ey - cy),
+                        start_angle.to_radians(),
+                        end_angle.to_radians(),
+                    );
+                    ctx.stroke();
+                }
+            }
+        }
+        ctx.set_line_dash(&[]);
+    }
+
+    fn to_json(&self) -> String {
+        serde_json::json!({
+            "type_id": self.type_id,
+            "points": self.points,
+            "data": self.data,
+        })
+        .to_string()
+    }
+
+    fn clone_box(&self) -> Box<dyn Primitive> {
+        Box::new(self.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn expr(s: &str) -> CoordExpr {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_affine_expressions() {
+        let points = [(10.0, 100.0), (30.0, 120.0)];
+        assert_eq!(expr("p0.x").eval(&points), 10.0);
+        assert_eq!(expr("lerp(p0.y, p1.y, 0.5)").eval(&points), 110.0);
+        assert_eq!(expr("(p0.x + p1.x) / 2 - 1").eval(&points), 19.0);
+        assert_eq!(expr("2 * p1.x - -p0.x").eval(&points), 70.0);
+        assert_eq!(expr("lerp(p0.y, p1.y, 0.25)").max_point(), Some(1));
+
+        assert!("p0.x * p1.x".parse::<CoordExpr>().is_err());
+        assert!("lerp(p0.y, p1.y, p0.x)".parse::<CoordExpr>().is_err());
+        assert!("q0.x".parse::<CoordExpr>().is_err());
+        assert!("p0.z".parse::<CoordExpr>().is_err());
+    }
+
+    #[test]
+    fn test_spec_json_round_trip_and_validation() {
+        let json = r#"{
+            "type_id": "sweep",
+            "points": 2,
+            "ops": [
+                {"op": "line", "from": {"x": "p0.x", "y": "p0.y"}, "to": {"x": "p1.x", "y": 100}},
+                {"op": "text", "at": {"x": "p1.x", "y": "p1.y"}, "text": "S"}
+            ]
+        }"#;
+        let spec = CompositeSpec::from_json(json).unwrap();
+        assert_eq!(spec.kind, PrimitiveKind::Shape);
+        assert_eq!(CompositeSpec::from_json(&spec.to_json()).unwrap(), spec);
+
+        let bad = json.replace("\"points\": 2", "\"points\": 1");
+        let err = CompositeSpec::from_json(&bad).unwrap_err();
+        assert_eq!(
+            err,
+            PrimitiveError::InvalidComposite("sweep references p1 but has 1 points".to_string())
+        );
+    }
+}
@@ -383,6 +383,20 @@ impl ConfigProperty {
         }
     }
 
+    /// Create an integer property
+    pub fn integer(id: &str, name: &str, value: i32, min: Option<i32>, max: Option<i32>) -> Self {
+        Self {
+            id: id.to_string(),
+            name: name.to_string(),
+            prop_type: PropertyType::Integer { min, max },
+            value: PropertyValue::Integer(value),
+            category: PropertyCategory::Style,
+            order: 0,
+            readonly: false,
+            tooltip: None,
+        }
+    }
+
     /// Create a boolean property
     pub fn boolean(id: &str, name: &str, value: bool) -> Self {
         Self {
@@ -572,6 +586,26 @@ impl<T: Primitive> Configurable for T {
         let data = self.data();
         let mut props = data.base_properties();
 
+        // Add opacity properties for filled shapes and brushes
+        if let Some((fill, stroke)) = self.opacity() {
+            props.push(
+                ConfigProperty::number("fill_opacity", "Fill Opacity", fill, Some(0.0), Some(1.0))
+                    .with_category(PropertyCategory::Style)
+                    .with_order(3),
+            );
+            props.push(
+                ConfigProperty::number(
+                    "stroke_opacity",
+                    "Line Opacity",
+                    stroke,
+                    Some(0.0),
+                    Some(1.0),
+                )
+                .with_category(PropertyCategory::Style)
+                .with_order(4),
+            );
+        }
+
         // Add text properties if primitive has text
         props.extend(data.text_properties());
 
@@ -598,6 +632,16 @@ impl<T: Primitive> Configurable for T {
             return true;
         }
 
+        // Handle opacity properties
+        if let Some(opacity) = value.as_number() {
+            let opacity = opacity.clamp(0.0, 1.0);
+            match id {
+                "fill_opacity" => return self.set_opacity(Some(opacity), None),
+                "stroke_opacity" => return self.set_opacity(None, Some(opacity)),
+                _ => {}
+            }
+        }
+
         // Handle coordinate properties (point1, point2, etc.)
         if let Some(suffix) = id.strip_prefix("point") {
             if let Some((bar, price)) = value.as_coordinate() {
@@ -975,6 +1019,8 @@ pub fn localized_property_label(id: &str, lang: Language) -> Option<&'static str
             // Style properties
             "stroke_color" => Some("Stroke Color"),
             "fill_color" => Some("Fill Color"),
+            "fill_opacity" => Some("Fill Opacity"),
+            "stroke_opacity" => Some("Line Opacity"),
             "width" => Some("Width"),
             "line_style" => Some("Line Style"),
             "visible" => Some("Visible"),
@@ -1001,6 +1047,8 @@ pub fn localized_property_label(id: &str, lang: Language) -> Option<&'static str
             // Style properties
             "stroke_color" => Some("Цвет линии"),
             "fill_color" => Some("Цвет заливки"),
+            "fill_opacity" => Some("Прозрачность заливки"),
+            "stroke_opacity" => Some("Прозрачность линии"),
             "width" => Some("Толщина"),
             "line_style" => Some("Стиль линии"),
             "visible" => Some("Видимость"),
@@ -18,12 +18,15 @@ pub use config::{
     TimeframeVisibilityConfig,
 };
 pub use render::{
-    RenderContext, RenderOp, RenderOps, TextAlign as RenderTextAlign, TextBaseline, crisp,
-    crisp_rect, execute_ops, measure_primitive_text, render_primitive_text,
-    render_primitive_text_rotated, render_text_with_background,
+    RenderContext, RenderOp, RenderOps, SELECTION_HALO_OPACITY, SELECTION_HALO_SPREAD,
+    SELECTION_HANDLE_RADIUS, TextAlign as RenderTextAlign, TextBaseline, crisp, crisp_rect,
+    execute_ops, measure_primitive_text, render_primitive_text, render_primitive_text_rotated,
+    render_selection_bounds, render_selection_halo, render_selection_handles,
+    render_text_with_background,
 };
 pub use traits::{Primitive, PrimitiveData, PrimitiveKind, SyncMode};
 pub use types::{
-    ControlPoint, ControlPointType, ExtendMode, LineStyle, PrimitiveColor, PrimitiveText,
-    TextAlign, TextAnchor, normalize_text_rotation, point_to_line_distance,
+    BandDeviation, ControlPoint, ControlPointType, ExtendMode, FibLabelMode, FillRule, LineStyle,
+    PrimitiveColor, PrimitiveText, TextAlign, TextAnchor, normalize_text_rotation,
+    point_to_line_distance,
 };
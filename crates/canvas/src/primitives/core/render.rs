@@ -3,6 +3,8 @@
 //! This module provides a `RenderContext` trait that abstracts away
 //! platform-specific rendering (Canvas2D, egui, etc.)
 
+use crate::core::Bar;
+
 /// Parameters for drawing an ellipse
 #[derive(Clone, Copy, Debug, Default)]
 pub struct EllipseParams {
@@ -71,6 +73,12 @@ pub trait RenderContext {
     /// Set fill style
     fn set_fill_color(&mut self, color: &str);
 
+    /// Fill rule for subsequent fills
+    /// Default implementation ignores it (platform fills with nonzero)
+    fn set_fill_rule(&mut self, rule: super::FillRule) {
+        let _ = rule;
+    }
+
     /// Path operations
     fn begin_path(&mut self);
     fn move_to(&mut self, x: f64, y: f64);
@@ -120,9 +128,23 @@ pub trait RenderContext {
         }
     }
 
-    /// Device pixel ratio for crisp rendering
+    /// Device pixel ratio for crisp rendering, the larger of
+    /// [`Self::dpr_xy`] when the axes differ
     fn dpr(&self) -> f64;
 
+    /// Horizontal and vertical device pixel ratios, for snapping each axis
+    /// on its own. Default implementation uses `dpr()` for both.
+    fn dpr_xy(&self) -> (f64, f64) {
+        (self.dpr(), self.dpr())
+    }
+
+    /// Bars of the chart, indexed like `bar_to_x`, for primitives fitted to
+    /// price data. Default implementation has none; those primitives then
+    /// fall back to their anchor points.
+    fn bars(&self) -> &[Bar] {
+        &[]
+    }
+
     /// Save/restore state
     fn save(&mut self);
     fn restore(&mut self);
@@ -155,6 +177,18 @@ pub trait RenderContext {
     /// Global alpha (transparency)
     fn set_global_alpha(&mut self, alpha: f64);
 
+    /// Opacity applied to subsequent fills, on top of the fill color's alpha
+    /// Default implementation falls back to global alpha
+    fn set_fill_opacity(&mut self, opacity: f64) {
+        self.set_global_alpha(opacity.clamp(0.0, 1.0));
+    }
+
+    /// Opacity applied to subsequent strokes, on top of the stroke color's alpha
+    /// Default implementation falls back to global alpha
+    fn set_stroke_opacity(&mut self, opacity: f64) {
+        self.set_global_alpha(opacity.clamp(0.0, 1.0));
+    }
+
     /// Line cap and join
     fn set_line_cap(&mut self, cap: &str);
     fn set_line_join(&mut self, join: &str);
@@ -192,9 +226,12 @@ pub trait RenderContext {
         self.set_global_alpha(alpha.clamp(0.0, 1.0));
     }
 
-    /// Reset global alpha to 1.0 (should be called after using set_fill_color_alpha)
+    /// Reset global alpha and fill/stroke opacity to 1.0
+    /// (should be called after using set_fill_color_alpha or the opacity setters)
     fn reset_alpha(&mut self) {
         self.set_global_alpha(1.0);
+        self.set_fill_opacity(1.0);
+        self.set_stroke_opacity(1.0);
     }
 }
 
@@ -381,6 +418,99 @@ pub fn render_text_with_background(
     render_primitive_text(ctx, text, x, y, fallback_color);
 }
 
+/// How far the selection halo extends past each side of the line, in pixels
+pub const SELECTION_HALO_SPREAD: f64 = 3.0;
+
+/// Opacity of the selection halo
+pub const SELECTION_HALO_OPACITY: f64 = 0.25;
+
+/// Radius of the control-point handles drawn on a selected primitive
+pub const SELECTION_HANDLE_RADIUS: f64 = 4.0;
+
+/// Stroke a translucent halo along `points` (screen coordinates), wider
+/// than a line of `width` by [`SELECTION_HALO_SPREAD`] on each side
+pub fn render_selection_halo(
+    ctx: &mut dyn RenderContext,
+    points: &[(f64, f64)],
+    color: &str,
+    width: f64,
+) {
+    let Some(&(x0, y0)) = points.first() else {
+        return;
+    };
+    if points.len() < 2 {
+        return;
+    }
+    ctx.set_stroke_color(color);
+    ctx.set_stroke_width(width + SELECTION_HALO_SPREAD * 2.0);
+    ctx.set_stroke_opacity(SELECTION_HALO_OPACITY);
+    ctx.set_line_dash(&[]);
+    ctx.set_line_cap("round");
+    ctx.set_line_join("round");
+    ctx.begin_path();
+    ctx.move_to(x0, y0);
+    for &(x, y) in &points[1..] {
+        ctx.line_to(x, y);
+    }
+    ctx.stroke();
+    ctx.reset_alpha();
+}
+
+/// Stroke a translucent halo around the box holding `points` (screen
+/// coordinates) and their handles, as wide as [`render_selection_halo`]
+pub fn render_selection_bounds(
+    ctx: &mut dyn RenderContext,
+    points: &[(f64, f64)],
+    color: &str,
+    width: f64,
+) {
+    let Some(&(x0, y0)) = points.first() else {
+        return;
+    };
+    let (min, max) = points
+        .iter()
+        .fold(((x0, y0), (x0, y0)), |(min, max), &(x, y)| {
+            ((min.0.min(x), min.1.min(y)), (max.0.max(x), max.1.max(y)))
+        });
+    let pad = SELECTION_HANDLE_RADIUS + SELECTION_HALO_SPREAD;
+    ctx.set_stroke_color(color);
+    ctx.set_stroke_width(width + SELECTION_HALO_SPREAD * 2.0);
+    ctx.set_stroke_opacity(SELECTION_HALO_OPACITY);
+    ctx.set_line_dash(&[]);
+    ctx.stroke_rect(
+        min.0 - pad,
+        min.1 - pad,
+        max.0 - min.0 + 2.0 * pad,
+        max.1 - min.1 + 2.0 * pad,
+    );
+    ctx.reset_alpha();
+}
+
+/// Draw a control-point handle (white dot, `color` outline) at each point
+pub fn render_selection_handles(ctx: &mut dyn RenderContext, points: &[(f64, f64)], color: &str) {
+    ctx.set_line_dash(&[]);
+    ctx.set_stroke_width(1.5);
+    ctx.set_fill_color("#FFFFFF");
+    ctx.set_stroke_color(color);
+    for &(x, y) in points {
+        // Backends may consume the path on fill, so trace it for each pass
+        for stroke in [false, true] {
+            ctx.begin_path();
+            ctx.ellipse(EllipseParams::full(
+                x,
+                y,
+                SELECTION_HANDLE_RADIUS,
+                SELECTION_HANDLE_RADIUS,
+            ));
+            if stroke {
+                ctx.stroke();
+            } else {
+                ctx.fill();
+            }
+        }
+    }
+}
+
 /// Helper to make crisp rectangles
 #[inline]
 pub fn crisp_rect(x: f64, y: f64, w: f64, h: f64, dpr: f64) -> (f64, f64, f64, f64) {
@@ -6,8 +6,14 @@
 use super::config::{
     ConfigProperty, PropertyCategory, PropertyValue, SelectOption, TimeframeVisibilityConfig,
 };
-use super::render::{RenderContext, crisp};
-use super::types::{LineStyle, PrimitiveColor, PrimitiveText, TextAlign, TextAnchor};
+use super::render::{
+    RenderContext, crisp, render_selection_bounds, render_selection_halo, render_selection_handles,
+};
+use super::types::{
+    BandDeviation, FibLabelMode, FillRule, LineStyle, PrimitiveColor, PrimitiveText, TextAlign,
+    TextAnchor,
+};
+use crate::primitives::catalog::DivergenceType;
 use serde::{Deserialize, Serialize};
 
 /// Category of primitive for toolbar organization
@@ -469,7 +475,31 @@ pub trait Primitive: Send + Sync {
 
         ctx.set_line_dash(&[]);
 
-        let _ = is_selected; // Selection rendering handled by UI layer
+        let _ = is_selected; // Drawn on top by render_selection()
+    }
+
+    /// Draw the selected appearance on top of the rendered primitive
+    ///
+    /// Renderers call this after `render(ctx, true)`. The default strokes a
+    /// translucent halo in the line color, then a handle on each control
+    /// point. Lines and patterns are drawn through their control points, so
+    /// the halo traces them; other kinds get a halo around the box of the
+    /// points, since e.g. a rectangle's corners don't outline it. Override
+    /// when neither fits the drawn shape.
+    fn render_selection(&self, ctx: &mut dyn RenderContext) {
+        let data = self.data();
+        let screen_points: Vec<(f64, f64)> = self
+            .points()
+            .iter()
+            .map(|(bar, price)| (ctx.bar_to_x(*bar), ctx.price_to_y(*price)))
+            .collect();
+        match self.kind() {
+            PrimitiveKind::Line | PrimitiveKind::Pattern => {
+                render_selection_halo(ctx, &screen_points, &data.color.stroke, data.width)
+            }
+            _ => render_selection_bounds(ctx, &screen_points, &data.color.stroke, data.width),
+        }
+        render_selection_handles(ctx, &screen_points, &data.color.stroke);
     }
 
     /// Get text anchor point for centralized text rendering
@@ -499,6 +529,62 @@ pub trait Primitive: Send + Sync {
         false
     }
 
+    // =========================================================================
+    // Opacity (for filled shapes and brushes)
+    // =========================================================================
+
+    /// Get `(fill_opacity, stroke_opacity)`, each 0.0 - 1.0
+    /// Returns None for primitives without opacity controls
+    ///
+    /// Opacity multiplies the alpha of the color itself. It is independent of
+    /// `z_order`: the z-order decides which primitive is drawn on top, the
+    /// opacity decides how much of the candles and primitives beneath show through.
+    fn opacity(&self) -> Option<(f64, f64)> {
+        None
+    }
+
+    /// Set fill and/or stroke opacity (`None` leaves that side unchanged)
+    /// Returns true if the primitive supports opacity
+    fn set_opacity(&mut self, _fill: Option<f64>, _stroke: Option<f64>) -> bool {
+        false
+    }
+
+    /// Fill rule for the primitive's filled area
+    /// Returns None for primitives that don't fill freeform outlines
+    fn fill_rule(&self) -> Option<FillRule> {
+        None
+    }
+
+    /// Set the fill rule
+    /// Returns true if the primitive supports choosing one
+    fn set_fill_rule(&mut self, _rule: FillRule) -> bool {
+        false
+    }
+
+    /// Get `(multiplier, deviation)` of the bands around a fitted line
+    /// Returns None for primitives without deviation bands
+    fn band(&self) -> Option<(f64, BandDeviation)> {
+        None
+    }
+
+    /// Label each Fibonacci level with `mode`
+    /// Returns true if the primitive draws Fibonacci levels with labels
+    fn set_fib_labels(&mut self, _mode: FibLabelMode) -> bool {
+        false
+    }
+
+    /// Set the divergence kind, which picks the dash and arrow direction
+    /// Returns true if the primitive marks a divergence
+    fn set_divergence_type(&mut self, _kind: DivergenceType) -> bool {
+        false
+    }
+
+    /// Set the band multiplier and/or deviation (`None` leaves it unchanged)
+    /// Returns true if the primitive has deviation bands
+    fn set_band(&mut self, _multiplier: Option<f64>, _deviation: Option<BandDeviation>) -> bool {
+        false
+    }
+
     // =========================================================================
     // Serialization
     // =========================================================================
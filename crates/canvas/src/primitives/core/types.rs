@@ -243,6 +243,68 @@ pub enum ExtendMode {
     Both,
 }
 
+/// Fill rule for closed shapes whose outline crosses itself
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FillRule {
+    /// Everything the outline winds around is filled
+    #[default]
+    NonZero,
+    /// Regions enclosed an even number of times stay empty, e.g. the
+    /// center of a self-intersecting star
+    EvenOdd,
+}
+
+/// Residual measure a regression channel's bands are offset by
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BandDeviation {
+    /// Standard deviation of the residuals
+    #[default]
+    StdDev,
+    /// Standard error of the regression
+    StdError,
+}
+
+impl std::str::FromStr for BandDeviation {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "std_dev" | "stddev" | "deviation" => Ok(Self::StdDev),
+            "std_error" | "stderror" | "error" => Ok(Self::StdError),
+            _ => Err(()),
+        }
+    }
+}
+
+/// What each Fibonacci level is labeled with
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FibLabelMode {
+    /// The ratio alone, e.g. `0.618`
+    #[default]
+    RatioOnly,
+    /// The ratio and the level's price, e.g. `0.618  123.45`
+    RatioPrice,
+    /// The ratio and the level's distance along the move between the
+    /// anchors, e.g. `0.618  61.8%`
+    RatioPercent,
+}
+
+impl std::str::FromStr for FibLabelMode {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "ratio" | "ratio_only" => Ok(Self::RatioOnly),
+            "ratio_price" | "price" => Ok(Self::RatioPrice),
+            "ratio_percent" | "percent" => Ok(Self::RatioPercent),
+            _ => Err(()),
+        }
+    }
+}
+
 // =============================================================================
 // Control Points (Handles)
 // =============================================================================
@@ -324,27 +386,5 @@ impl ControlPoint {
 // Geometry Utilities
 // =============================================================================
 
-/// Calculate distance from point to line segment
-pub fn point_to_line_distance(px: f64, py: f64, x1: f64, y1: f64, x2: f64, y2: f64) -> f64 {
-    let dx = x2 - x1;
-    let dy = y2 - y1;
-    let len_sq = dx * dx + dy * dy;
-
-    if len_sq < 0.0001 {
-        // Line is a point
-        let ddx = px - x1;
-        let ddy = py - y1;
-        return (ddx * ddx + ddy * ddy).sqrt();
-    }
-
-    // Project point onto line, clamping to segment
-    let t = ((px - x1) * dx + (py - y1) * dy) / len_sq;
-    let t = t.clamp(0.0, 1.0);
-
-    let proj_x = x1 + t * dx;
-    let proj_y = y1 + t * dy;
-
-    let ddx = px - proj_x;
-    let ddy = py - proj_y;
-    (ddx * ddx + ddy * ddy).sqrt()
-}
+// Lives in core so it is available without the rendering stack
+pub use crate::core::point_to_line_distance;
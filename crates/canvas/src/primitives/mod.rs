@@ -16,6 +16,7 @@
 //! │   ├── render.rs       # RenderContext, RenderOp, rendering utilities
 //! │   └── config.rs       # Configurable trait, property system
 //! ├── registry.rs         # PrimitiveRegistry (factory)
+//! ├── composite.rs        # CompositePrimitive (data-driven tools)
 //! ├── signals/            # Strategy signals system
 //! │   ├── types.rs        # SignalType, SystemSignal, StrategySignalConfig
 //! │   └── manager.rs      # SignalManager
@@ -48,6 +49,7 @@
 // =============================================================================
 
 pub mod catalog;
+mod composite;
 pub mod core;
 mod registry;
 pub mod signals;
@@ -59,10 +61,13 @@ pub mod utils;
 // =============================================================================
 
 pub use core::{
+    BandDeviation,
     // Control points (data types)
     ControlPoint,
     ControlPointType,
     ExtendMode,
+    FibLabelMode,
+    FillRule,
     LineStyle,
     // Core trait
     Primitive as PrimitiveTrait,
@@ -85,7 +90,8 @@ pub use core::{
 pub use core::render::{
     EllipseParams, RenderContext, RenderOp, RenderOps, TextBaseline, crisp as render_crisp,
     crisp_rect as render_crisp_rect, execute_ops, render_primitive_text,
-    render_primitive_text_rotated, render_text_with_background,
+    render_primitive_text_rotated, render_selection_bounds, render_selection_halo,
+    render_selection_handles, render_text_with_background,
 };
 
 // Configuration exports
@@ -99,7 +105,15 @@ pub use core::config::{
 // Registry exports
 // =============================================================================
 
-pub use registry::{PrimitiveFactory, PrimitiveMetadata, PrimitiveRegistry};
+pub use registry::{
+    PrimitiveError, PrimitiveFactory, PrimitiveInfo, PrimitiveMetadata, PrimitiveRegistry,
+};
+
+// =============================================================================
+// Composite (data-driven) primitives
+// =============================================================================
+
+pub use composite::{CompositeOp, CompositePoint, CompositePrimitive, CompositeSpec, CoordExpr};
 
 // =============================================================================
 // Signals exports
@@ -172,7 +186,7 @@ pub use catalog::cycles::{CycleLines, SineWave, TimeCycles};
 
 // Projection
 pub use catalog::projection::{
-    BarsPattern, Forecast, LongPosition, PriceProjection, Projection, ShortPosition,
+    BarsPattern, Forecast, ForecastPath, LongPosition, PriceProjection, Projection, ShortPosition,
 };
 
 // Volume
@@ -3,9 +3,13 @@
 //! This allows adding new primitives without modifying DrawingManager.
 //! Each primitive type registers itself with metadata and a factory function.
 
+use super::composite::{self, CompositePrimitive, CompositeSpec};
 use super::core::{Primitive, PrimitiveKind};
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use std::sync::{OnceLock, RwLock};
+use std::fmt;
+use std::ops::RangeInclusive;
+use std::sync::{Arc, OnceLock};
 
 /// Factory function type for creating primitives
 pub type PrimitiveFactory = fn(points: &[(f64, f64)], color: &str) -> Box<dyn Primitive>;
@@ -21,6 +25,8 @@ pub struct PrimitiveMetadata {
     pub kind: PrimitiveKind,
     /// Factory function
     pub factory: PrimitiveFactory,
+    /// Accepted number of control points (`usize::MAX` = no upper bound)
+    pub point_range: RangeInclusive<usize>,
     /// Whether this primitive supports text labels (shows "Text" tab in settings)
     pub supports_text: bool,
     /// Whether this primitive has configurable levels (Fibonacci, Gann, Pitchfork - shows "Levels" tab)
@@ -29,58 +35,230 @@ pub struct PrimitiveMetadata {
     pub has_points_config: bool,
 }
 
-/// Global primitive registry
+impl PrimitiveMetadata {
+    /// Serializable summary for tool palettes
+    pub fn info(&self) -> PrimitiveInfo {
+        let (min, max) = (*self.point_range.start(), *self.point_range.end());
+        PrimitiveInfo {
+            type_id: self.type_id.to_string(),
+            name: self.display_name.to_string(),
+            category: self.kind,
+            min_points: min,
+            max_points: (max != usize::MAX).then_some(max),
+            supports_text: self.supports_text,
+            has_levels: self.has_levels,
+        }
+    }
+}
+
+/// Primitive type as shown in a drawing-tool palette
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PrimitiveInfo {
+    pub type_id: String,
+    /// Display name
+    pub name: String,
+    /// Toolbar category
+    pub category: PrimitiveKind,
+    /// Control points needed to place the tool
+    pub min_points: usize,
+    /// Most control points accepted (`None` = no limit)
+    pub max_points: Option<usize>,
+    pub supports_text: bool,
+    pub has_levels: bool,
+}
+
+/// Errors from creating a primitive through the registry
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PrimitiveError {
+    /// No primitive registered under this type ID
+    UnknownType(String),
+
+    /// Control point count outside the primitive's `point_range`
+    PointCount {
+        type_id: String,
+        expected: RangeInclusive<usize>,
+        got: usize,
+    },
+
+    /// Composite spec that failed to parse or references missing points
+    InvalidComposite(String),
+}
+
+impl fmt::Display for PrimitiveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PrimitiveError::UnknownType(type_id) => {
+                write!(f, "Unknown primitive type: {}", type_id)
+            }
+            PrimitiveError::PointCount {
+                type_id,
+                expected,
+                got,
+            } => {
+                let (min, max) = (*expected.start(), *expected.end());
+                write!(f, "Primitive {} expects ", type_id)?;
+                if min == max {
+                    write!(f, "{}", min)?;
+                } else if max == usize::MAX {
+                    write!(f, "at least {}", min)?;
+                } else {
+                    write!(f, "{} to {}", min, max)?;
+                }
+                write!(f, " points, got {}", got)
+            }
+            PrimitiveError::InvalidComposite(msg) => write!(f, "Invalid composite: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for PrimitiveError {}
+
+/// Primitive registry
+///
+/// Maps type IDs to metadata and factories. Iteration follows registration
+/// order, so toolbars and snapshot output are stable across runs.
 ///
-/// Use `PrimitiveRegistry::global()` to access.
+/// Rendering uses the immutable built-in registry from
+/// `PrimitiveRegistry::global()` unless a chart carries its own instance
+/// (see `ChartConfig::registry`), which is how custom primitives are added.
+#[derive(Clone, Default)]
 pub struct PrimitiveRegistry {
-    primitives: HashMap<&'static str, PrimitiveMetadata>,
+    primitives: Vec<PrimitiveMetadata>,
+    index: HashMap<&'static str, usize>,
     by_kind: HashMap<PrimitiveKind, Vec<&'static str>>,
+    composites: HashMap<&'static str, Arc<CompositeSpec>>,
 }
 
 impl PrimitiveRegistry {
     /// Create a new empty registry
     pub fn new() -> Self {
-        Self {
-            primitives: HashMap::new(),
-            by_kind: HashMap::new(),
-        }
+        Self::default()
+    }
+
+    /// Create a registry with all built-in primitives
+    pub fn with_defaults() -> Self {
+        let mut registry = Self::new();
+        registry.register_builtins();
+        registry
     }
 
-    /// Get the global registry instance
-    pub fn global() -> &'static RwLock<PrimitiveRegistry> {
-        static REGISTRY: OnceLock<RwLock<PrimitiveRegistry>> = OnceLock::new();
-        REGISTRY.get_or_init(|| {
-            let mut registry = PrimitiveRegistry::new();
-            // Register built-in primitives
-            registry.register_builtins();
-            RwLock::new(registry)
-        })
+    /// Get the shared built-in registry, built once on first use
+    pub fn global() -> &'static PrimitiveRegistry {
+        static REGISTRY: OnceLock<PrimitiveRegistry> = OnceLock::new();
+        REGISTRY.get_or_init(PrimitiveRegistry::with_defaults)
     }
 
     /// Register a primitive type
-    pub fn register(&mut self, metadata: PrimitiveMetadata) {
+    ///
+    /// `type_id` and `factory` override the corresponding metadata fields,
+    /// so a built-in's metadata can be reused for a custom primitive.
+    /// Registering an existing type ID replaces it in place.
+    pub fn register(
+        &mut self,
+        type_id: &'static str,
+        factory: PrimitiveFactory,
+        metadata: PrimitiveMetadata,
+    ) -> &mut Self {
+        self.add(PrimitiveMetadata {
+            type_id,
+            factory,
+            ..metadata
+        });
+        self.composites.remove(type_id);
+        self
+    }
+
+    /// Register a data-driven composite primitive under its spec's type ID
+    ///
+    /// The metadata's `factory` can't carry the spec, so create composites
+    /// through `create`. Registering an existing type ID replaces it.
+    pub fn register_composite(&mut self, spec: CompositeSpec) -> Result<&mut Self, PrimitiveError> {
+        spec.validate()?;
+        let type_id = composite::intern(&spec.type_id);
+        let display_name = if spec.display_name.is_empty() {
+            type_id
+        } else {
+            composite::intern(&spec.display_name)
+        };
+        self.add(PrimitiveMetadata {
+            type_id,
+            display_name,
+            kind: spec.kind,
+            factory: composite::create_unbound,
+            point_range: spec.points..=spec.points,
+            supports_text: false,
+            has_levels: false,
+            has_points_config: false,
+        });
+        self.composites.insert(type_id, Arc::new(spec));
+        Ok(self)
+    }
+
+    fn add(&mut self, metadata: PrimitiveMetadata) {
         let type_id = metadata.type_id;
         let kind = metadata.kind;
 
-        self.primitives.insert(type_id, metadata);
-        self.by_kind.entry(kind).or_default().push(type_id);
+        match self.index.get(type_id) {
+            Some(&i) => {
+                let old_kind = self.primitives[i].kind;
+                self.primitives[i] = metadata;
+                if old_kind != kind {
+                    if let Some(ids) = self.by_kind.get_mut(&old_kind) {
+                        ids.retain(|id| *id != type_id);
+                    }
+                    self.by_kind.entry(kind).or_default().push(type_id);
+                }
+            }
+            None => {
+                self.index.insert(type_id, self.primitives.len());
+                self.primitives.push(metadata);
+                self.by_kind.entry(kind).or_default().push(type_id);
+            }
+        }
     }
 
     /// Get metadata for a primitive type
     pub fn get(&self, type_id: &str) -> Option<&PrimitiveMetadata> {
-        self.primitives.get(type_id)
+        self.index.get(type_id).map(|&i| &self.primitives[i])
+    }
+
+    /// Check that `count` control points are valid for a primitive type
+    pub fn validate(&self, type_id: &str, count: usize) -> Result<(), PrimitiveError> {
+        let meta = self
+            .get(type_id)
+            .ok_or_else(|| PrimitiveError::UnknownType(type_id.to_string()))?;
+        if meta.point_range.contains(&count) {
+            Ok(())
+        } else {
+            Err(PrimitiveError::PointCount {
+                type_id: type_id.to_string(),
+                expected: meta.point_range.clone(),
+                got: count,
+            })
+        }
     }
 
     /// Create a primitive by type ID
+    ///
+    /// Fails if the type is unknown or the point count is outside the
+    /// primitive's `point_range`.
     pub fn create(
         &self,
         type_id: &str,
         points: &[(f64, f64)],
         color: Option<&str>,
-    ) -> Option<Box<dyn Primitive>> {
-        let meta = self.primitives.get(type_id)?;
+    ) -> Result<Box<dyn Primitive>, PrimitiveError> {
+        self.validate(type_id, points.len())?;
         let color = color.unwrap_or("#2196F3"); // Default blue color
-        Some((meta.factory)(points, color))
+        if let Some(spec) = self.composites.get(type_id) {
+            return Ok(Box::new(CompositePrimitive::new(
+                spec.clone(),
+                points,
+                color,
+            )));
+        }
+        let meta = &self.primitives[self.index[type_id]];
+        Ok((meta.factory)(points, color))
     }
 
     /// Get all primitive type IDs in a category
@@ -88,31 +266,37 @@ impl PrimitiveRegistry {
         self.by_kind.get(&kind).map(|v| v.as_slice()).unwrap_or(&[])
     }
 
-    /// Get all registered primitive types
+    /// Metadata of all registered primitives, in registration order
+    pub fn list_metadata(&self) -> Vec<PrimitiveMetadata> {
+        self.primitives.clone()
+    }
+
+    /// Palette entries for all registered primitives, in registration order
+    pub fn list_info(&self) -> Vec<PrimitiveInfo> {
+        self.primitives
+            .iter()
+            .map(PrimitiveMetadata::info)
+            .collect()
+    }
+
+    /// Get all registered primitive types, in registration order
     pub fn all(&self) -> impl Iterator<Item = &PrimitiveMetadata> {
-        self.primitives.values()
+        self.primitives.iter()
     }
 
     /// Check if primitive type has configurable levels (Fibonacci, Gann, Pitchfork)
     pub fn has_levels(&self, type_id: &str) -> bool {
-        self.primitives
-            .get(type_id)
-            .map(|m| m.has_levels)
-            .unwrap_or(false)
+        self.get(type_id).map(|m| m.has_levels).unwrap_or(false)
     }
 
     /// Check if primitive type supports text
     pub fn supports_text(&self, type_id: &str) -> bool {
-        self.primitives
-            .get(type_id)
-            .map(|m| m.supports_text)
-            .unwrap_or(false)
+        self.get(type_id).map(|m| m.supports_text).unwrap_or(false)
     }
 
     /// Check if primitive type has configurable control points (Elliott, Patterns)
     pub fn has_points_config(&self, type_id: &str) -> bool {
-        self.primitives
-            .get(type_id)
+        self.get(type_id)
             .map(|m| m.has_points_config)
             .unwrap_or(false)
     }
@@ -168,7 +352,7 @@ impl PrimitiveRegistry {
                             .and_then(|d| d.get("color"))
                             .and_then(|c| c.get("stroke"))
                             .and_then(|s| s.as_str());
-                        return self.create(type_id, &points, color);
+                        return self.create(type_id, &points, color).ok();
                     }
                 }
                 None
@@ -179,140 +363,143 @@ impl PrimitiveRegistry {
     /// Register all built-in primitives
     fn register_builtins(&mut self) {
         // Lines
-        self.register(super::catalog::lines::trend_line::metadata());
-        self.register(super::catalog::lines::horizontal_line::metadata());
-        self.register(super::catalog::lines::vertical_line::metadata());
-        self.register(super::catalog::lines::ray::metadata());
-        self.register(super::catalog::lines::extended_line::metadata());
-        self.register(super::catalog::lines::info_line::metadata());
-        self.register(super::catalog::lines::trend_angle::metadata());
-        self.register(super::catalog::lines::horizontal_ray::metadata());
-        self.register(super::catalog::lines::cross_line::metadata());
+        self.add(super::catalog::lines::trend_line::metadata());
+        self.add(super::catalog::lines::horizontal_line::metadata());
+        self.add(super::catalog::lines::vertical_line::metadata());
+        self.add(super::catalog::lines::ray::metadata());
+        self.add(super::catalog::lines::extended_line::metadata());
+        self.add(super::catalog::lines::info_line::metadata());
+        self.add(super::catalog::lines::trend_angle::metadata());
+        self.add(super::catalog::lines::horizontal_ray::metadata());
+        self.add(super::catalog::lines::cross_line::metadata());
 
         // Channels
-        self.register(super::catalog::channels::parallel_channel::metadata());
-        self.register(super::catalog::channels::regression_trend::metadata());
-        self.register(super::catalog::channels::flat_top_bottom::metadata());
-        self.register(super::catalog::channels::disjoint_channel::metadata());
+        self.add(super::catalog::channels::parallel_channel::metadata());
+        self.add(super::catalog::channels::regression_trend::metadata());
+        self.add(super::catalog::channels::flat_top_bottom::metadata());
+        self.add(super::catalog::channels::disjoint_channel::metadata());
 
         // Shapes
-        self.register(super::catalog::shapes::rectangle::metadata());
-        self.register(super::catalog::shapes::circle::metadata());
-        self.register(super::catalog::shapes::ellipse::metadata());
-        self.register(super::catalog::shapes::triangle::metadata());
-        self.register(super::catalog::shapes::arc::metadata());
-        self.register(super::catalog::shapes::polyline::metadata());
-        self.register(super::catalog::shapes::path::metadata());
-        self.register(super::catalog::shapes::rotated_rectangle::metadata());
-        self.register(super::catalog::shapes::curve::metadata());
-        self.register(super::catalog::shapes::double_curve::metadata());
+        self.add(super::catalog::shapes::rectangle::metadata());
+        self.add(super::catalog::shapes::circle::metadata());
+        self.add(super::catalog::shapes::ellipse::metadata());
+        self.add(super::catalog::shapes::triangle::metadata());
+        self.add(super::catalog::shapes::arc::metadata());
+        self.add(super::catalog::shapes::polyline::metadata());
+        self.add(super::catalog::shapes::path::metadata());
+        self.add(super::catalog::shapes::rotated_rectangle::metadata());
+        self.add(super::catalog::shapes::curve::metadata());
+        self.add(super::catalog::shapes::double_curve::metadata());
 
         // Fibonacci
-        self.register(super::catalog::fibonacci::retracement::metadata());
-        self.register(super::catalog::fibonacci::trend_extension::metadata());
-        self.register(super::catalog::fibonacci::channel::metadata());
-        self.register(super::catalog::fibonacci::time_zones::metadata());
-        self.register(super::catalog::fibonacci::speed_resistance::metadata());
-        self.register(super::catalog::fibonacci::trend_time::metadata());
-        self.register(super::catalog::fibonacci::circles::metadata());
-        self.register(super::catalog::fibonacci::spiral::metadata());
-        self.register(super::catalog::fibonacci::arcs::metadata());
-        self.register(super::catalog::fibonacci::wedge::metadata());
-        self.register(super::catalog::fibonacci::fan::metadata());
+        self.add(super::catalog::fibonacci::retracement::metadata());
+        self.add(super::catalog::fibonacci::trend_extension::metadata());
+        self.add(super::catalog::fibonacci::channel::metadata());
+        self.add(super::catalog::fibonacci::time_zones::metadata());
+        self.add(super::catalog::fibonacci::speed_resistance::metadata());
+        self.add(super::catalog::fibonacci::trend_time::metadata());
+        self.add(super::catalog::fibonacci::circles::metadata());
+        self.add(super::catalog::fibonacci::spiral::metadata());
+        self.add(super::catalog::fibonacci::arcs::metadata());
+        self.add(super::catalog::fibonacci::wedge::metadata());
+        self.add(super::catalog::fibonacci::fan::metadata());
 
         // Pitchforks
-        self.register(super::catalog::pitchforks::pitchfork::metadata());
-        self.register(super::catalog::pitchforks::schiff::metadata());
-        self.register(super::catalog::pitchforks::modified_schiff::metadata());
-        self.register(super::catalog::pitchforks::inside_pitchfork::metadata());
+        self.add(super::catalog::pitchforks::pitchfork::metadata());
+        self.add(super::catalog::pitchforks::schiff::metadata());
+        self.add(super::catalog::pitchforks::modified_schiff::metadata());
+        self.add(super::catalog::pitchforks::inside_pitchfork::metadata());
 
         // Gann
-        self.register(super::catalog::gann::gann_box::metadata());
-        self.register(super::catalog::gann::gann_square_fixed::metadata());
-        self.register(super::catalog::gann::gann_square::metadata());
-        self.register(super::catalog::gann::gann_fan::metadata());
+        self.add(super::catalog::gann::gann_box::metadata());
+        self.add(super::catalog::gann::gann_square_fixed::metadata());
+        self.add(super::catalog::gann::gann_square::metadata());
+        self.add(super::catalog::gann::gann_fan::metadata());
 
         // Arrows
-        self.register(super::catalog::arrows::arrow_marker::metadata());
-        self.register(super::catalog::arrows::arrow_line::metadata());
-        self.register(super::catalog::arrows::arrow_up::metadata());
-        self.register(super::catalog::arrows::arrow_down::metadata());
+        self.add(super::catalog::arrows::arrow_marker::metadata());
+        self.add(super::catalog::arrows::arrow_line::metadata());
+        self.add(super::catalog::arrows::arrow_up::metadata());
+        self.add(super::catalog::arrows::arrow_down::metadata());
 
         // Annotations
-        self.register(super::catalog::annotations::text::metadata());
-        self.register(super::catalog::annotations::anchored_text::metadata());
-        self.register(super::catalog::annotations::note::metadata());
-        self.register(super::catalog::annotations::price_note::metadata());
-        self.register(super::catalog::annotations::signpost::metadata());
-        self.register(super::catalog::annotations::callout::metadata());
-        self.register(super::catalog::annotations::comment::metadata());
-        self.register(super::catalog::annotations::price_label::metadata());
-        self.register(super::catalog::annotations::sign::metadata());
-        self.register(super::catalog::annotations::flag::metadata());
-        self.register(super::catalog::annotations::table::metadata());
+        self.add(super::catalog::annotations::text::metadata());
+        self.add(super::catalog::annotations::anchored_text::metadata());
+        self.add(super::catalog::annotations::note::metadata());
+        self.add(super::catalog::annotations::price_note::metadata());
+        self.add(super::catalog::annotations::signpost::metadata());
+        self.add(super::catalog::annotations::callout::metadata());
+        self.add(super::catalog::annotations::comment::metadata());
+        self.add(super::catalog::annotations::price_label::metadata());
+        self.add(super::catalog::annotations::sign::metadata());
+        self.add(super::catalog::annotations::flag::metadata());
+        self.add(super::catalog::annotations::table::metadata());
 
         // Patterns
-        self.register(super::catalog::patterns::xabcd_pattern::metadata());
-        self.register(super::catalog::patterns::cypher_pattern::metadata());
-        self.register(super::catalog::patterns::head_shoulders::metadata());
-        self.register(super::catalog::patterns::abcd_pattern::metadata());
-        self.register(super::catalog::patterns::triangle_pattern::metadata());
-        self.register(super::catalog::patterns::three_drives::metadata());
+        self.add(super::catalog::patterns::xabcd_pattern::metadata());
+        self.add(super::catalog::patterns::cypher_pattern::metadata());
+        self.add(super::catalog::patterns::head_shoulders::metadata());
+        self.add(super::catalog::patterns::abcd_pattern::metadata());
+        self.add(super::catalog::patterns::triangle_pattern::metadata());
+        self.add(super::catalog::patterns::three_drives::metadata());
 
         // Elliott
-        self.register(super::catalog::elliott::elliott_impulse::metadata());
-        self.register(super::catalog::elliott::elliott_correction::metadata());
-        self.register(super::catalog::elliott::elliott_triangle::metadata());
-        self.register(super::catalog::elliott::elliott_double_combo::metadata());
-        self.register(super::catalog::elliott::elliott_triple_combo::metadata());
+        self.add(super::catalog::elliott::elliott_impulse::metadata());
+        self.add(super::catalog::elliott::elliott_correction::metadata());
+        self.add(super::catalog::elliott::elliott_triangle::metadata());
+        self.add(super::catalog::elliott::elliott_double_combo::metadata());
+        self.add(super::catalog::elliott::elliott_triple_combo::metadata());
 
         // Cycles
-        self.register(super::catalog::cycles::cycle_lines::metadata());
-        self.register(super::catalog::cycles::time_cycles::metadata());
-        self.register(super::catalog::cycles::sine_wave::metadata());
+        self.add(super::catalog::cycles::cycle_lines::metadata());
+        self.add(super::catalog::cycles::time_cycles::metadata());
+        self.add(super::catalog::cycles::sine_wave::metadata());
 
         // Projection
-        self.register(super::catalog::projection::long_position::metadata());
-        self.register(super::catalog::projection::short_position::metadata());
-        self.register(super::catalog::projection::forecast::metadata());
-        self.register(super::catalog::projection::bars_pattern::metadata());
-        self.register(super::catalog::projection::price_projection::metadata());
-        self.register(super::catalog::projection::general::metadata());
+        self.add(super::catalog::projection::long_position::metadata());
+        self.add(super::catalog::projection::short_position::metadata());
+        self.add(super::catalog::projection::forecast::metadata());
+        self.add(super::catalog::projection::forecast_path::metadata());
+        self.add(super::catalog::projection::bars_pattern::metadata());
+        self.add(super::catalog::projection::price_projection::metadata());
+        self.add(super::catalog::projection::general::metadata());
 
         // Volume
-        self.register(super::catalog::volume::anchored_vwap::metadata());
-        self.register(super::catalog::volume::fixed_volume_profile::metadata());
-        self.register(super::catalog::volume::anchored_volume_profile::metadata());
+        self.add(super::catalog::volume::anchored_vwap::metadata());
+        self.add(super::catalog::volume::fixed_volume_profile::metadata());
+        self.add(super::catalog::volume::anchored_volume_profile::metadata());
 
         // Measurement
-        self.register(super::catalog::measurement::price_range::metadata());
-        self.register(super::catalog::measurement::date_range::metadata());
-        self.register(super::catalog::measurement::price_date_range::metadata());
+        self.add(super::catalog::measurement::price_range::metadata());
+        self.add(super::catalog::measurement::date_range::metadata());
+        self.add(super::catalog::measurement::price_date_range::metadata());
 
         // Brushes
-        self.register(super::catalog::brushes::brush::metadata());
-        self.register(super::catalog::brushes::highlighter::metadata());
+        self.add(super::catalog::brushes::brush::metadata());
+        self.add(super::catalog::brushes::highlighter::metadata());
 
         // Icons
-        self.register(super::catalog::icons::image::metadata());
-        self.register(super::catalog::icons::emoji::metadata());
+        self.add(super::catalog::icons::image::metadata());
+        self.add(super::catalog::icons::emoji::metadata());
 
         // Events (strategy-generated markers)
-        self.register(super::catalog::events::crossover_metadata());
-        self.register(super::catalog::events::breakdown_metadata());
-        self.register(super::catalog::events::divergence_metadata());
-        self.register(super::catalog::events::pattern_match_metadata());
-        self.register(super::catalog::events::zone_event_metadata());
-        self.register(super::catalog::events::volume_event_metadata());
-        self.register(super::catalog::events::trend_event_metadata());
-        self.register(super::catalog::events::momentum_event_metadata());
-        self.register(super::catalog::events::custom_event_metadata());
+        self.add(super::catalog::events::crossover_metadata());
+        self.add(super::catalog::events::breakdown_metadata());
+        self.add(super::catalog::events::divergence_metadata());
+        self.add(super::catalog::events::pattern_match_metadata());
+        self.add(super::catalog::events::zone_event_metadata());
+        self.add(super::catalog::events::volume_event_metadata());
+        self.add(super::catalog::events::trend_event_metadata());
+        self.add(super::catalog::events::momentum_event_metadata());
+        self.add(super::catalog::events::custom_event_metadata());
     }
 }
 
-impl Default for PrimitiveRegistry {
-    fn default() -> Self {
-        Self::new()
+impl fmt::Debug for PrimitiveRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list()
+            .entries(self.primitives.iter().map(|m| m.type_id))
+            .finish()
     }
 }
 
@@ -345,3 +532,90 @@ macro_rules! define_primitive {
         }
     };
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_registration_order_is_stable() {
+        let defaults = PrimitiveRegistry::with_defaults();
+        let ids: Vec<_> = defaults.all().map(|m| m.type_id).collect();
+        assert_eq!(
+            &ids[..3],
+            ["trend_line", "horizontal_line", "vertical_line"]
+        );
+        assert_eq!(
+            ids,
+            PrimitiveRegistry::global()
+                .all()
+                .map(|m| m.type_id)
+                .collect::<Vec<_>>()
+        );
+
+        // Re-registering replaces in place without duplicating
+        let mut registry = defaults.clone();
+        let trend = super::super::catalog::lines::trend_line::metadata();
+        registry.register("trend_line", trend.factory, trend.clone());
+        assert_eq!(registry.all().count(), ids.len());
+        assert_eq!(registry.all().next().map(|m| m.type_id), Some("trend_line"));
+        let lines = registry.by_kind(PrimitiveKind::Line);
+        assert_eq!(lines.iter().filter(|id| **id == "trend_line").count(), 1);
+    }
+
+    #[test]
+    fn test_create_validates_point_count() {
+        let registry = PrimitiveRegistry::global();
+        let points = [(0.0, 100.0), (10.0, 110.0)];
+
+        let err = registry.create("triangle", &points, None).err().unwrap();
+        assert_eq!(
+            err,
+            PrimitiveError::PointCount {
+                type_id: "triangle".to_string(),
+                expected: 3..=3,
+                got: 2,
+            }
+        );
+        assert_eq!(
+            err.to_string(),
+            "Primitive triangle expects 3 points, got 2"
+        );
+
+        let third = [(0.0, 100.0), (10.0, 110.0), (5.0, 90.0)];
+        assert!(registry.create("triangle", &third, None).is_ok());
+        assert_eq!(
+            registry.validate("polyline", 1).unwrap_err().to_string(),
+            "Primitive polyline expects at least 2 points, got 1"
+        );
+        assert_eq!(
+            registry.validate("nope", 1),
+            Err(PrimitiveError::UnknownType("nope".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_list_metadata_for_palette() {
+        let registry = PrimitiveRegistry::global();
+        let metadata = registry.list_metadata();
+        assert_eq!(metadata.len(), registry.all().count());
+
+        let info = registry.list_info();
+        let find = |id: &str| info.iter().find(|i| i.type_id == id).unwrap();
+        let trend = find("trend_line");
+        assert_eq!(trend.name, "Trend Line");
+        assert_eq!(trend.category, PrimitiveKind::Line);
+        assert_eq!((trend.min_points, trend.max_points), (2, Some(2)));
+
+        let fib = find("fib_retracement");
+        assert_eq!(fib.category, PrimitiveKind::Fibonacci);
+        assert_eq!((fib.min_points, fib.max_points), (2, Some(2)));
+        assert!(fib.has_levels);
+
+        let polyline = find("polyline");
+        assert_eq!((polyline.min_points, polyline.max_points), (2, None));
+
+        let json = serde_json::to_string(fib).unwrap();
+        assert!(json.contains(r#""category":"Fibonacci""#));
+    }
+}
@@ -4,12 +4,14 @@
 //! Indicators are rendered based on their VectorStyle (line, area, histogram, etc.)
 
 use super::super::engine::{
-    Color, FillStyle, LineStyle, Point, Rect, RenderBatch, RenderCommand, TextStyle,
-    crisp_bar_width, crisp_coord, crisp_rect,
+    Color, FillStyle, FontWeight, LineCap, LineJoin, LineStyle, Path, Point, Rect, RenderBackend,
+    RenderBatch, RenderCommand, SvgBackend, TextAlign, TextBaseline, TextStyle, crisp_bar_width,
+    crisp_coord, crisp_rect, estimate_text_width,
 };
+use crate::core::format_indicator_value;
 use crate::model::indicators::{
-    ArrowDirection, Indicator, IndicatorLevel, IndicatorVector, Signal, SignalVisual, Strategy,
-    VectorStyle,
+    ArrowDirection, Indicator, IndicatorLevel, IndicatorVector, PivotLabels, Signal, SignalVisual,
+    Strategy, VectorStyle,
 };
 
 // =============================================================================
@@ -65,6 +67,9 @@ pub fn render_indicator(
         visible_range,
     );
 
+    // Fills between vector pairs sit behind the lines
+    render_indicator_fills(batch, indicator, bar_to_x, price_to_y, dpr, visible_range);
+
     // Render each vector
     for (i, vector) in indicator.vectors.iter().enumerate() {
         render_indicator_vector(
@@ -83,6 +88,50 @@ pub fn render_indicator(
     }
 }
 
+/// Render the shaded fills declared in `indicator.fills`
+///
+/// Each fill becomes one polygon per run of bars where both boundary
+/// vectors are finite, so warm-up NaNs and gaps split the band. Runs are
+/// split again where the vectors cross, for `crossed_color`.
+pub fn render_indicator_fills(
+    batch: &mut RenderBatch,
+    indicator: &Indicator,
+    bar_to_x: impl Fn(usize) -> f64 + Copy,
+    price_to_y: impl Fn(f64) -> f64 + Copy,
+    dpr: f64,
+    visible_range: Option<(usize, usize)>,
+) {
+    for fill in &indicator.fills {
+        let (Some(upper), Some(lower)) = (
+            indicator.vectors.get(fill.upper_vector),
+            indicator.vectors.get(fill.lower_vector),
+        ) else {
+            continue;
+        };
+
+        let len = upper.values.len().min(lower.values.len());
+        let (start, end) = visible_range.unwrap_or((0, len));
+        let start = start.saturating_sub(1).min(len);
+        let end = (end + 1).min(len);
+
+        render_vector_cloud(
+            batch,
+            VectorCloudParams {
+                values1: &upper.values,
+                values2: &lower.values,
+                color_above: &fill.color,
+                color_below: fill.crossed_color.as_deref().unwrap_or(&fill.color),
+                fill_alpha: fill.alpha,
+                bar_to_x,
+                price_to_y,
+                dpr,
+                start,
+                end,
+            },
+        );
+    }
+}
+
 /// Render reference levels (horizontal lines like overbought/oversold)
 fn render_indicator_levels(
     batch: &mut RenderBatch,
@@ -215,12 +264,15 @@ fn render_indicator_vector<F1, F2>(
             color,
             radius,
             filled,
+            down_color,
         } => {
             render_vector_dots(
                 batch,
                 VectorDotsParams {
                     values: &vector.values,
+                    directions: &vector.directions,
                     color,
+                    down_color: down_color.as_deref(),
                     radius: *radius,
                     filled: *filled,
                     bar_to_x,
@@ -231,13 +283,39 @@ fn render_indicator_vector<F1, F2>(
                 },
             );
         }
-        VectorStyle::Step { color, width } => {
+        VectorStyle::Step {
+            color,
+            width,
+            down_color,
+        } => {
             render_vector_step(
                 batch,
                 VectorStepParams {
+                    values: &vector.values,
+                    directions: &vector.directions,
+                    color,
+                    down_color: down_color.as_deref(),
+                    width: *width,
+                    bar_to_x,
+                    price_to_y,
+                    dpr,
+                    start,
+                    end,
+                },
+            );
+        }
+        VectorStyle::Pivots {
+            color,
+            width,
+            labels,
+        } => {
+            render_vector_pivots(
+                batch,
+                VectorPivotsParams {
                     values: &vector.values,
                     color,
                     width: *width,
+                    labels: *labels,
                     bar_to_x,
                     price_to_y,
                     dpr,
@@ -357,7 +435,9 @@ where
     F2: Fn(f64) -> f64,
 {
     values: &'a [f64],
+    directions: &'a [bool],
     color: &'a str,
+    down_color: Option<&'a str>,
     radius: f64,
     filled: bool,
     bar_to_x: F1,
@@ -367,6 +447,23 @@ where
     end: usize,
 }
 
+/// Parameters for vector pivots rendering
+struct VectorPivotsParams<'a, F1, F2>
+where
+    F1: Fn(usize) -> f64,
+    F2: Fn(f64) -> f64,
+{
+    values: &'a [f64],
+    color: &'a str,
+    width: f64,
+    labels: PivotLabels,
+    bar_to_x: F1,
+    price_to_y: F2,
+    dpr: f64,
+    start: usize,
+    end: usize,
+}
+
 /// Parameters for vector step rendering
 struct VectorStepParams<'a, F1, F2>
 where
@@ -374,7 +471,9 @@ where
     F2: Fn(f64) -> f64,
 {
     values: &'a [f64],
+    directions: &'a [bool],
     color: &'a str,
+    down_color: Option<&'a str>,
     width: f64,
     bar_to_x: F1,
     price_to_y: F2,
@@ -450,12 +549,12 @@ where
                 let dash = if dashed { Some(vec![6.0, 4.0]) } else { None };
                 batch.push(RenderCommand::Polyline {
                     points: std::mem::take(&mut points),
-                    style: LineStyle {
+                    style: smooth(LineStyle {
                         color: parse_color(color),
                         width,
                         dash,
                         ..Default::default()
-                    },
+                    }),
                 });
             }
             points.clear();
@@ -472,12 +571,12 @@ where
         let dash = if dashed { Some(vec![6.0, 4.0]) } else { None };
         batch.push(RenderCommand::Polyline {
             points,
-            style: LineStyle {
+            style: smooth(LineStyle {
                 color: parse_color(color),
                 width,
                 dash,
                 ..Default::default()
-            },
+            }),
         });
     }
 }
@@ -539,11 +638,11 @@ where
         if line_width > 0.0 {
             batch.push(RenderCommand::Polyline {
                 points: line_points,
-                style: LineStyle {
+                style: smooth(LineStyle {
                     color: parse_color(color),
                     width: line_width,
                     ..Default::default()
-                },
+                }),
             });
         }
     }
@@ -581,11 +680,20 @@ fn render_vector_histogram<F1, F2>(
         let x = bar_to_x(i);
         let y = price_to_y(value);
 
+        // At least 1px, growing away from zero by the value's sign
+        let height = (y - zero_y).abs();
+        let top = if height >= 1.0 {
+            y.min(zero_y)
+        } else if value >= 0.0 {
+            zero_y - 1.0
+        } else {
+            zero_y
+        };
         let (rx, ry, rw, rh) = crisp_rect(
             x - crisp_width / 2.0,
-            y.min(zero_y),
+            top,
             crisp_width,
-            (y - zero_y).abs().max(1.0),
+            height.max(1.0),
             dpr,
         );
 
@@ -609,7 +717,9 @@ where
 {
     let VectorDotsParams {
         values,
+        directions,
         color,
+        down_color,
         radius,
         filled,
         bar_to_x,
@@ -619,13 +729,12 @@ where
         end,
     } = params;
 
-    let color = parse_color(color);
-
     for (idx, &value) in values[start..end].iter().enumerate() {
         let i = start + idx;
         if value.is_nan() || value.is_infinite() {
             continue;
         }
+        let color = parse_color(direction_color(color, down_color, directions, i));
 
         let x = crisp_coord(bar_to_x(i), dpr);
         let y = crisp_coord(price_to_y(value), dpr);
@@ -650,6 +759,136 @@ where
     }
 }
 
+fn render_vector_pivots<F1, F2>(batch: &mut RenderBatch, params: VectorPivotsParams<'_, F1, F2>)
+where
+    F1: Fn(usize) -> f64,
+    F2: Fn(f64) -> f64,
+{
+    let VectorPivotsParams {
+        values,
+        color,
+        width,
+        labels,
+        bar_to_x,
+        price_to_y,
+        dpr,
+        start,
+        end,
+    } = params;
+
+    // Reach out to the pivots either side of the range, so the segments
+    // crossing its edges are drawn
+    let start = values[..start]
+        .iter()
+        .rposition(|v| v.is_finite())
+        .unwrap_or(start);
+    let end = values[end..]
+        .iter()
+        .position(|v| v.is_finite())
+        .map_or(end, |i| end + i + 1);
+
+    let points: Vec<Point> = (start..end)
+        .filter(|&i| values[i].is_finite())
+        .map(|i| {
+            Point::new(
+                crisp_coord(bar_to_x(i), dpr),
+                crisp_coord(price_to_y(values[i]), dpr),
+            )
+        })
+        .collect();
+    if points.len() >= 2 {
+        batch.push(RenderCommand::Polyline {
+            points,
+            style: smooth(LineStyle {
+                color: parse_color(color),
+                width,
+                ..Default::default()
+            }),
+        });
+    }
+
+    for (i, text, is_high) in pivot_labels(values, labels) {
+        if i < start || i >= end {
+            continue;
+        }
+        batch.push(RenderCommand::Text {
+            text,
+            pos: Point::new(bar_to_x(i), pivot_label_y(price_to_y(values[i]), is_high)),
+            style: pivot_label_style(parse_color(color), is_high),
+        });
+    }
+}
+
+/// Label text of each pivot as (bar index, text, whether it is a swing high)
+///
+/// A pivot is a high when it is above the previous one, or above the next
+/// one for the first pivot. The first pivot has no change to show.
+fn pivot_labels(values: &[f64], labels: PivotLabels) -> Vec<(usize, String, bool)> {
+    if labels == PivotLabels::None {
+        return Vec::new();
+    }
+    let pivots: Vec<(usize, f64)> = values
+        .iter()
+        .enumerate()
+        .filter(|(_, v)| v.is_finite())
+        .map(|(i, &v)| (i, v))
+        .collect();
+
+    pivots
+        .iter()
+        .enumerate()
+        .filter_map(|(n, &(i, v))| {
+            let prev = n.checked_sub(1).map(|p| pivots[p].1);
+            let is_high = match (prev, pivots.get(n + 1)) {
+                (Some(prev), _) => v > prev,
+                (None, Some(&(_, next))) => v > next,
+                (None, None) => true,
+            };
+            let text = match labels {
+                PivotLabels::Price => format_indicator_value(v),
+                PivotLabels::Change => {
+                    let prev = prev.filter(|&p| p != 0.0)?;
+                    format!("{:+.2}%", (v / prev - 1.0) * 100.0)
+                }
+                PivotLabels::None => return None,
+            };
+            Some((i, text, is_high))
+        })
+        .collect()
+}
+
+/// Labels sit above swing highs and below swing lows
+fn pivot_label_y(y: f64, is_high: bool) -> f64 {
+    if is_high { y - 4.0 } else { y + 4.0 }
+}
+
+fn pivot_label_style(color: Color, is_high: bool) -> TextStyle {
+    TextStyle {
+        font_size: 10.0,
+        color,
+        align: TextAlign::Center,
+        baseline: if is_high {
+            TextBaseline::Bottom
+        } else {
+            TextBaseline::Top
+        },
+        ..Default::default()
+    }
+}
+
+/// `down_color` for indices whose direction is down, `color` otherwise
+fn direction_color<'a>(
+    color: &'a str,
+    down_color: Option<&'a str>,
+    directions: &[bool],
+    index: usize,
+) -> &'a str {
+    match down_color {
+        Some(down) if directions.get(index) == Some(&false) => down,
+        _ => color,
+    }
+}
+
 fn render_vector_step<F1, F2>(batch: &mut RenderBatch, params: VectorStepParams<'_, F1, F2>)
 where
     F1: Fn(usize) -> f64,
@@ -657,7 +896,9 @@ where
 {
     let VectorStepParams {
         values,
+        directions,
         color,
+        down_color,
         width,
         bar_to_x,
         price_to_y,
@@ -666,53 +907,53 @@ where
         end,
     } = params;
 
+    let flush = |batch: &mut RenderBatch, points: &mut Vec<Point>, color: &str| {
+        if points.len() >= 2 {
+            batch.push(RenderCommand::Polyline {
+                points: std::mem::take(points),
+                style: LineStyle {
+                    color: parse_color(color),
+                    width,
+                    ..Default::default()
+                },
+            });
+        }
+        points.clear();
+    };
+
     let mut points = Vec::new();
     let mut prev_y = 0.0;
-    let mut started = false;
+    let mut run_color = color;
+    let mut run_direction = None;
 
     for (idx, &value) in values[start..end].iter().enumerate() {
         let i = start + idx;
         if value.is_nan() || value.is_infinite() {
             // Break on invalid values
-            if points.len() >= 2 {
-                batch.push(RenderCommand::Polyline {
-                    points: std::mem::take(&mut points),
-                    style: LineStyle {
-                        color: parse_color(color),
-                        width,
-                        ..Default::default()
-                    },
-                });
-            }
-            points.clear();
-            started = false;
+            flush(batch, &mut points, run_color);
             continue;
         }
 
+        // Break where the direction flips, so each run keeps its own color
+        let direction = directions.get(i).copied();
+        if down_color.is_some() && direction != run_direction {
+            flush(batch, &mut points, run_color);
+            run_direction = direction;
+            run_color = direction_color(color, down_color, directions, i);
+        }
+
         let x = crisp_coord(bar_to_x(i), dpr);
         let y = crisp_coord(price_to_y(value), dpr);
 
-        if !started {
-            points.push(Point::new(x, y));
-            started = true;
-        } else {
+        if !points.is_empty() {
             // Step: horizontal then vertical
             points.push(Point::new(x, prev_y));
-            points.push(Point::new(x, y));
         }
+        points.push(Point::new(x, y));
         prev_y = y;
     }
 
-    if points.len() >= 2 {
-        batch.push(RenderCommand::Polyline {
-            points,
-            style: LineStyle {
-                color: parse_color(color),
-                width,
-                ..Default::default()
-            },
-        });
-    }
+    flush(batch, &mut points, run_color);
 }
 
 fn render_vector_cloud<F1, F2>(batch: &mut RenderBatch, params: VectorCloudParams<'_, F1, F2>)
@@ -801,30 +1042,276 @@ where
         return;
     }
 
-    // Build polygon: forward path (values1) + backward path (values2, reversed)
-    let mut points = Vec::with_capacity((end - start) * 2);
+    let point = |values: &[f64], i: usize| {
+        Point::new(
+            crisp_coord(bar_to_x(i), dpr),
+            crisp_coord(price_to_y(values[i]), dpr),
+        )
+    };
+    let above = |i: usize| values1[i] > values2[i];
+
+    // One polygon per run where the same vector is on top, closed at the
+    // crossing point either side so adjacent runs meet
+    let mut run_start = start;
+    let mut entry: Option<Point> = None;
+    for i in start..end {
+        let crossing = (i + 1 < end && above(i + 1) != above(i)).then(|| {
+            let (a1, a2) = (point(values1, i), point(values2, i));
+            let (b1, b2) = (point(values1, i + 1), point(values2, i + 1));
+            let (da, db) = (a1.y - a2.y, b1.y - b2.y);
+            let t = if da == db { 0.5 } else { da / (da - db) };
+            Point::new(a1.x + (b1.x - a1.x) * t, a1.y + (b1.y - a1.y) * t)
+        });
+        if crossing.is_none() && i + 1 < end {
+            continue;
+        }
 
-    for (idx, &v1) in values1[start..end].iter().enumerate() {
-        let i = start + idx;
-        let x = crisp_coord(bar_to_x(i), dpr);
-        let y = crisp_coord(price_to_y(v1), dpr);
-        points.push(Point::new(x, y));
+        // Forward along values1, back along values2
+        let mut points = Vec::with_capacity((i + 1 - run_start) * 2 + 2);
+        points.extend(entry);
+        points.extend((run_start..=i).map(|j| point(values1, j)));
+        points.extend(crossing);
+        points.extend((run_start..=i).rev().map(|j| point(values2, j)));
+
+        let color = if above(i) { color_above } else { color_below };
+        batch.push(RenderCommand::FillPolygon {
+            points,
+            style: FillStyle::Solid(parse_color_with_alpha(color, fill_alpha)),
+        });
+
+        run_start = i + 1;
+        entry = crossing;
     }
+}
 
-    for i in (start..end).rev() {
-        let x = crisp_coord(bar_to_x(i), dpr);
-        let y = crisp_coord(price_to_y(values2[i]), dpr);
-        points.push(Point::new(x, y));
+// =============================================================================
+// Backend Vector Rendering
+// =============================================================================
+
+/// Geometry of the pane an indicator vector is drawn into
+///
+/// Shared by the single-chart and multichart renderers so that both draw
+/// every `VectorStyle` the same way.
+#[derive(Clone, Copy, Debug)]
+pub struct VectorContext {
+    /// Plot area of the pane; columns and dots outside it horizontally are culled
+    pub pane: Rect,
+    /// Distance between adjacent bar slots, which sizes histogram columns
+    pub bar_spacing: f64,
+    /// Y that histogram columns grow from
+    pub zero_y: f64,
+    /// Y that area fills extend to
+    pub area_base_y: f64,
+}
+
+impl VectorContext {
+    /// Context for a pane whose value range is `range`, mapped by `value_to_y`
+    pub fn new(
+        pane: Rect,
+        bar_spacing: f64,
+        range: (f64, f64),
+        value_to_y: impl Fn(f64) -> f64,
+    ) -> Self {
+        let (zero, area_base) = value_baselines(range.0, range.1);
+        Self {
+            pane,
+            bar_spacing,
+            zero_y: value_to_y(zero),
+            area_base_y: value_to_y(area_base),
+        }
     }
 
-    // Determine if above or below (check first point)
-    let is_above = values1[start] > values2[start];
-    let color = if is_above { color_above } else { color_below };
+    /// Whether a mark centred at `x` and `half_width` wide each side can show
+    fn in_pane(&self, x: f64, half_width: f64) -> bool {
+        x + half_width >= self.pane.x && x - half_width <= self.pane.x + self.pane.width
+    }
+}
 
-    batch.push(RenderCommand::FillPolygon {
-        points,
-        style: FillStyle::Solid(parse_color_with_alpha(color, fill_alpha)),
-    });
+/// Values that histogram bars and area fills start from in a pane whose
+/// value range is `min..max`, as `(histogram, area)`
+///
+/// Histograms grow from zero, clamped to the nearest pane edge when the
+/// range sits entirely above or below zero. Areas fill to zero when the
+/// range crosses it and down to the pane bottom otherwise.
+fn value_baselines(min: f64, max: f64) -> (f64, f64) {
+    let (lo, hi) = (min.min(max), max.max(min));
+    let zero = 0.0_f64.max(lo).min(hi);
+    let area = if zero == 0.0 { 0.0 } else { lo };
+    (zero, area)
+}
+
+/// Top and height of a histogram column, at least 1px tall so zero values
+/// still show on the side of the baseline their sign points to
+fn histogram_bar_span(value: f64, y: f64, zero_y: f64) -> (f64, f64) {
+    let height = (zero_y - y).abs();
+    if height >= 1.0 {
+        (y.min(zero_y), height)
+    } else if value >= 0.0 {
+        (zero_y - 1.0, 1.0)
+    } else {
+        (zero_y, 1.0)
+    }
+}
+
+/// Round joins and caps, so thick indicator lines have no miter spikes
+fn smooth(style: LineStyle) -> LineStyle {
+    style.with_join(LineJoin::Round).with_cap(LineCap::Round)
+}
+
+/// Draw one indicator vector straight to an SVG backend by its `VectorStyle`
+///
+/// Non-finite values are skipped: lines, areas and steps join the values
+/// either side of a gap. `Pivots` does too, and can label each point.
+/// `Cloud` vectors are drawn by the indicator's fills instead.
+pub fn render_vector(
+    backend: &mut SvgBackend,
+    ctx: &VectorContext,
+    vector: &IndicatorVector,
+    bar_to_x: &impl Fn(usize) -> f64,
+    value_to_y: &impl Fn(f64) -> f64,
+) {
+    let points = || -> Vec<Point> {
+        vector
+            .values
+            .iter()
+            .enumerate()
+            .filter(|&(_, v)| v.is_finite())
+            .map(|(i, &v)| Point::new(bar_to_x(i), value_to_y(v)))
+            .collect()
+    };
+
+    match &vector.style {
+        VectorStyle::Line {
+            color,
+            width,
+            dashed,
+        } => {
+            let points = points();
+            if points.len() >= 2 {
+                let c = Color::from_css(color).unwrap_or(Color::WHITE);
+                let style = if *dashed {
+                    LineStyle::dashed(c, *width, 4.0, 4.0)
+                } else {
+                    LineStyle::solid(c, *width)
+                };
+                backend.polyline(&points, &smooth(style));
+            }
+        }
+        VectorStyle::Histogram {
+            up_color,
+            down_color,
+            bar_width_ratio,
+        } => {
+            let up = Color::from_css(up_color).unwrap_or(Color::rgb(38, 166, 154));
+            let down = Color::from_css(down_color).unwrap_or(Color::rgb(239, 83, 80));
+            let bar_width = (ctx.bar_spacing * bar_width_ratio).max(1.0);
+
+            for (i, &v) in vector.values.iter().enumerate() {
+                let x = bar_to_x(i);
+                if !v.is_finite() || !ctx.in_pane(x, bar_width / 2.0) {
+                    continue;
+                }
+                let y = value_to_y(v);
+
+                // Use directions vector if available, otherwise fallback to value sign
+                let is_up = vector.direction_at(i).unwrap_or(v >= 0.0);
+                let color = if is_up { up } else { down };
+                let (top, height) = histogram_bar_span(v, y, ctx.zero_y);
+                backend.crisp_fill_rect(
+                    Rect::new(x - bar_width / 2.0, top, bar_width, height),
+                    color,
+                );
+            }
+        }
+        VectorStyle::Area {
+            color,
+            fill_alpha,
+            line_width,
+        } => {
+            let points = points();
+            if points.len() >= 2 {
+                let c = Color::from_css(color).unwrap_or(Color::WHITE);
+                // Fill to the zero line or pane bottom, then the line on top
+                let mut fill_pts = points.clone();
+                fill_pts.push(Point::new(points[points.len() - 1].x, ctx.area_base_y));
+                fill_pts.push(Point::new(points[0].x, ctx.area_base_y));
+                backend.fill_path(
+                    &Path::polygon(&fill_pts),
+                    &FillStyle::solid(c.with_alpha(*fill_alpha)),
+                );
+                backend.polyline(&points, &smooth(LineStyle::solid(c, *line_width)));
+            }
+        }
+        VectorStyle::Dots {
+            color,
+            radius,
+            filled,
+            down_color,
+        } => {
+            for (i, &v) in vector.values.iter().enumerate() {
+                let center = Point::new(bar_to_x(i), value_to_y(v));
+                if !v.is_finite() || !ctx.in_pane(center.x, *radius) {
+                    continue;
+                }
+                let color = direction_color(color, down_color.as_deref(), &vector.directions, i);
+                let c = Color::from_css(color).unwrap_or(Color::WHITE);
+                if *filled {
+                    backend.fill_circle(center, *radius, c);
+                } else {
+                    backend.stroke_circle(center, *radius, &LineStyle::solid(c, 1.0));
+                }
+            }
+        }
+        VectorStyle::Step {
+            color,
+            width,
+            down_color,
+        } => {
+            let style_at = |i: usize| {
+                let color = direction_color(color, down_color.as_deref(), &vector.directions, i);
+                LineStyle::solid(Color::from_css(color).unwrap_or(Color::WHITE), *width)
+            };
+            let defined: Vec<usize> = (0..vector.values.len())
+                .filter(|&i| vector.values[i].is_finite())
+                .collect();
+            // Horizontal to the next value's bar, then vertical to it
+            for pair in defined.windows(2) {
+                let (a, b) = (pair[0], pair[1]);
+                // A flip jumps to the other side of price rather than joining
+                if down_color.is_some() && vector.direction_at(a) != vector.direction_at(b) {
+                    continue;
+                }
+                let style = style_at(b);
+                let from = Point::new(bar_to_x(a), value_to_y(vector.values[a]));
+                let to = Point::new(bar_to_x(b), value_to_y(vector.values[b]));
+                let corner = Point::new(to.x, from.y);
+                backend.crisp_line(from, corner, &style);
+                if corner.y != to.y {
+                    backend.crisp_line(corner, to, &style);
+                }
+            }
+        }
+        VectorStyle::Pivots {
+            color,
+            width,
+            labels,
+        } => {
+            let c = Color::from_css(color).unwrap_or(Color::WHITE);
+            let points = points();
+            if points.len() >= 2 {
+                backend.polyline(&points, &smooth(LineStyle::solid(c, *width)));
+            }
+            for (i, text, is_high) in pivot_labels(&vector.values, *labels) {
+                let y = pivot_label_y(value_to_y(vector.values[i]), is_high);
+                backend.text(
+                    &text,
+                    Point::new(bar_to_x(i), y),
+                    &pivot_label_style(c, is_high),
+                );
+            }
+        }
+        VectorStyle::Cloud { .. } | VectorStyle::Hidden => {}
+    }
 }
 
 // =============================================================================
@@ -947,7 +1434,7 @@ fn render_label(
     background: Option<&str>,
     _dpr: f64,
 ) {
-    let text_width = (text.len() as f64 * 7.0) + 8.0;
+    let text_width = estimate_text_width(text, 12.0, FontWeight::Normal) + 8.0;
     let text_height = 16.0;
 
     // Background
@@ -41,4 +41,7 @@ pub use overlays::{render_grid, render_legend, render_watermark};
 pub use annotations::{render_markers, render_price_lines};
 
 // Re-exports - Indicator and signal rendering
-pub use indicators::{render_indicator, render_signals, render_strategy};
+pub use indicators::{
+    StrategyParams, VectorContext, render_indicator, render_indicator_fills, render_signals,
+    render_strategy, render_vector,
+};
@@ -4,9 +4,9 @@
 
 use super::super::engine::{
     Color, FontWeight, LineStyle as RenderLineStyle, Point, Rect, RenderBatch, RenderCommand,
-    TextAlign, TextBaseline, TextStyle, crisp_coord,
+    TextAlign, TextBaseline, TextStyle, crisp_coord, estimate_text_width,
 };
-use crate::model::overlays::{GridOptions, Legend, LegendData, Watermark};
+use crate::model::overlays::{FontStyle, GridOptions, Legend, LegendData, Watermark};
 
 // =============================================================================
 // Grid Rendering
@@ -144,9 +144,7 @@ pub fn render_legend(
     let price_step = 0.01; // Could be passed as parameter
     let text = data.format(legend, price_step);
 
-    // Measure text width (approximate - would need actual measurement in real implementation)
-    let char_width = legend.font_size * 0.6; // Approximate monospace width
-    let text_width = text.len() as f64 * char_width;
+    let text_width = estimate_text_width(&text, legend.font_size, FontWeight::Normal);
 
     // Calculate position based on legend settings
     let (x, y) = legend.calc_position(chart_rect.width, chart_rect.height, text_width);
@@ -195,13 +193,15 @@ pub fn render_legend(
 
 /// Render watermark
 ///
-/// Displays multi-line text watermark with configurable alignment and styling.
-/// Supports center, corner, and edge positioning.
+/// Displays the logo row and text lines as one block, aligned within
+/// `chart_rect` - pass the main pane, not the whole canvas, so the block
+/// is not pushed off center by the scales. Each line keeps its own font
+/// size, weight and color; the watermark's opacity applies on top.
 ///
 /// # Arguments
 /// * `batch` - Render batch to append commands to
 /// * `watermark` - Watermark configuration (lines, alignment, visibility)
-/// * `chart_rect` - Chart bounds for position calculation
+/// * `chart_rect` - Area the block is aligned in
 /// * `dpr` - Device pixel ratio (currently unused but kept for consistency)
 pub fn render_watermark(
     batch: &mut RenderBatch,
@@ -209,51 +209,57 @@ pub fn render_watermark(
     chart_rect: Rect,
     _dpr: f64,
 ) {
-    if !watermark.visible || watermark.lines.is_empty() {
-        return;
-    }
-
-    // Simple text width measurement function (approximate)
+    // Text width from the line's CSS font (size and weight)
     let measure_text = |text: &str, font: &str| -> f64 {
-        // Extract font size from CSS font string
         let font_size = font
             .split_whitespace()
-            .find_map(|part| {
-                if part.ends_with("px") {
-                    part.trim_end_matches("px").parse::<f64>().ok()
-                } else {
-                    None
-                }
-            })
+            .find_map(|part| part.strip_suffix("px")?.parse::<f64>().ok())
             .unwrap_or(48.0);
+        let weight = if font.contains("bold") {
+            FontWeight::Bold
+        } else {
+            FontWeight::Normal
+        };
+        estimate_text_width(text, font_size, weight)
+    };
 
-        text.len() as f64 * font_size * 0.6 // Approximate width
+    let Some(layout) = watermark.layout(chart_rect.width, chart_rect.height, measure_text) else {
+        return;
     };
+    let opacity = watermark.opacity.clamp(0.0, 1.0);
 
-    // Calculate positions for all lines
-    let positions = watermark.calc_positions(chart_rect.width, chart_rect.height, measure_text);
+    if let Some((x, y, logo)) = layout.logo {
+        if opacity < 1.0 {
+            batch.push(RenderCommand::SetAlpha { alpha: opacity });
+        }
+        batch.push(RenderCommand::Image {
+            id: logo.src.clone(),
+            src: None,
+            dst: Rect::new(chart_rect.x + x, chart_rect.y + y, logo.width, logo.height),
+        });
+        if opacity < 1.0 {
+            batch.push(RenderCommand::SetAlpha { alpha: 1.0 });
+        }
+    }
 
-    // Render each line
-    for (x, y, line) in positions {
+    for (x, y, line) in layout.lines {
         let color = Color::from_css(&line.color).unwrap_or(Color::rgba(171, 71, 188, 77)); // Default watermark color with alpha
 
         let text_style = TextStyle {
             font_family: line.font_family.clone(),
             font_size: line.font_size,
             font_weight: match line.font_style {
-                crate::model::overlays::watermark::FontStyle::Bold
-                | crate::model::overlays::watermark::FontStyle::BoldItalic => FontWeight::Bold,
+                FontStyle::Bold | FontStyle::BoldItalic => FontWeight::Bold,
                 _ => FontWeight::Normal,
             },
-            color,
+            color: color.with_alpha(opacity),
             align: TextAlign::Left, // Position is pre-calculated
             baseline: TextBaseline::Top,
         };
 
         let pos = Point::new(chart_rect.x + x, chart_rect.y + y);
 
-        // Check if italic (would need TextStyle enhancement for italic support)
-        // For now, render as normal text
+        // Italic would need TextStyle support; it renders upright for now
         batch.push(RenderCommand::Text {
             text: line.text.clone(),
             pos,
@@ -265,7 +271,7 @@ pub fn render_watermark(
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::WatermarkLine;
+    use crate::{WatermarkLine, WatermarkLogo};
 
     #[test]
     fn test_render_grid_horizontal() {
@@ -305,6 +311,7 @@ mod tests {
             low: 49800.0,
             close: 50100.0,
             prev_close: Some(50000.0),
+            ..Default::default()
         };
         let chart_rect = Rect::new(0.0, 0.0, 800.0, 600.0);
 
@@ -341,6 +348,119 @@ mod tests {
         assert_eq!(batch.len(), 2); // Two text commands
     }
 
+    /// One line per command: `text <x>,<y> <size> <text>` or `image <x>,<y> <w>x<h>`
+    fn watermark_snapshot(watermark: &Watermark, rect: Rect) -> Vec<String> {
+        let mut batch = RenderBatch::new();
+        render_watermark(&mut batch, watermark, rect, 1.0);
+        batch
+            .commands()
+            .iter()
+            .filter_map(|cmd| match cmd {
+                RenderCommand::Text { text, pos, style } => Some(format!(
+                    "text {:.1},{:.1} {} {}",
+                    pos.x, pos.y, style.font_size, text
+                )),
+                RenderCommand::Image { dst, .. } => Some(format!(
+                    "image {:.1},{:.1} {}x{}",
+                    dst.x, dst.y, dst.width, dst.height
+                )),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_watermark_corner_snapshots() {
+        use crate::model::overlays::{HorzAlign, VertAlign};
+
+        // Main pane of an 800x600 chart, offset as if inside a layout
+        let pane = Rect::new(10.0, 30.0, 730.0, 500.0);
+        let one = Watermark::multi_line(vec![WatermarkLine::new("BTCUSD", "#787b86", 48.0)]);
+        let three = Watermark::multi_line(vec![
+            WatermarkLine::new("BTCUSD", "#787b86", 48.0),
+            WatermarkLine::new("1D", "#787b86", 24.0),
+            WatermarkLine::new("Binance", "#787b86", 16.0),
+        ])
+        .with_line_gap(6.0);
+
+        let snapshots: [(HorzAlign, VertAlign, &[&str], &[&str]); 4] = [
+            (
+                HorzAlign::Left,
+                VertAlign::Top,
+                &["text 30.0,50.0 48 BTCUSD"],
+                &[
+                    "text 30.0,50.0 48 BTCUSD",
+                    "text 30.0,104.0 24 1D",
+                    "text 30.0,134.0 16 Binance",
+                ],
+            ),
+            (
+                HorzAlign::Right,
+                VertAlign::Top,
+                &["text 522.7,50.0 48 BTCUSD"],
+                &[
+                    "text 522.7,50.0 48 BTCUSD",
+                    "text 689.3,104.0 24 1D",
+                    "text 662.2,134.0 16 Binance",
+                ],
+            ),
+            (
+                HorzAlign::Left,
+                VertAlign::Bottom,
+                &["text 30.0,462.0 48 BTCUSD"],
+                &[
+                    "text 30.0,410.0 48 BTCUSD",
+                    "text 30.0,464.0 24 1D",
+                    "text 30.0,494.0 16 Binance",
+                ],
+            ),
+            (
+                HorzAlign::Right,
+                VertAlign::Bottom,
+                &["text 522.7,462.0 48 BTCUSD"],
+                &[
+                    "text 522.7,410.0 48 BTCUSD",
+                    "text 689.3,464.0 24 1D",
+                    "text 662.2,494.0 16 Binance",
+                ],
+            ),
+        ];
+        for (horz, vert, expected_one, expected_three) in snapshots {
+            let snapshot =
+                |wm: &Watermark| watermark_snapshot(&wm.clone().with_alignment(horz, vert), pane);
+            assert_eq!(snapshot(&one), expected_one, "{:?} {:?}", horz, vert);
+            assert_eq!(snapshot(&three), expected_three, "{:?} {:?}", horz, vert);
+        }
+    }
+
+    #[test]
+    fn test_watermark_logo_and_opacity() {
+        let logo = WatermarkLogo::new("data:image/png;base64,iVBORw0KGgo=", 32.0, 32.0);
+        let watermark = Watermark::multi_line(vec![WatermarkLine::new("ACME", "#ffffff", 20.0)])
+            .with_line_gap(8.0)
+            .with_opacity(0.5)
+            .with_logo(logo);
+        let pane = Rect::new(0.0, 0.0, 400.0, 300.0);
+
+        // Block is 32 + 8 + 20 = 60 tall, centered in the pane
+        assert_eq!(
+            watermark_snapshot(&watermark, pane),
+            ["image 184.0,120.0 32x32", "text 171.1,160.0 20 ACME"]
+        );
+
+        let mut batch = RenderBatch::new();
+        render_watermark(&mut batch, &watermark, pane, 1.0);
+        assert!(matches!(
+            batch.commands()[0],
+            RenderCommand::SetAlpha { alpha } if alpha == 0.5
+        ));
+        let text_alpha = batch.commands().iter().find_map(|cmd| match cmd {
+            RenderCommand::Text { style, .. } => Some(style.color.a),
+            _ => None,
+        });
+        assert_eq!(text_alpha, Some(127));
+    }
+
     #[test]
     fn test_grid_respects_visibility() {
         let mut batch = RenderBatch::new();
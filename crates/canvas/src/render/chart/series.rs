@@ -6,13 +6,14 @@
 
 use super::super::engine::{
     Color, FillStyle, LineStyle, Path, PathBuilder, Point, Rect, RenderBatch, RenderCommand,
-    crisp_bar_width, crisp_coord, crisp_rect,
+    crisp_bar_width, crisp_coord, crisp_rect, crisp_stroke_coord,
 };
 use crate::core::catmull_rom_spline;
+use crate::core::compute::heikin_ashi;
 use crate::model::series::{
     AreaData, AreaStyleOptions, BarData, BarStyleOptions, BaselineData, BaselineStyleOptions,
-    CandlestickData, CandlestickStyleOptions, HistogramData, HistogramStyleOptions, LineData,
-    LineStyleOptions, LineType,
+    CandlestickData, CandlestickStyleOptions, DenseCandles, HaWickSource, HistogramData,
+    HistogramStyleOptions, LineData, LineStyleOptions, LineType,
 };
 
 // =============================================================================
@@ -29,6 +30,9 @@ use crate::model::series::{
 /// * `price_to_y` - Function to convert price to Y coordinate
 /// * `bar_width` - Base width of each candlestick
 /// * `dpr` - Device pixel ratio for crisp rendering
+///
+/// Past `options.density_threshold` bars per pixel, candles either lose
+/// their wicks or collapse to high-low lines (see [`DenseCandles`]).
 pub fn render_candlesticks(
     batch: &mut RenderBatch,
     data: &[CandlestickData],
@@ -42,7 +46,24 @@ pub fn render_candlesticks(
         return;
     }
 
-    let crisp_width = crisp_bar_width(bar_width, dpr);
+    let density = bars_per_pixel(&bar_to_x, data.len());
+    let crisp_width = crisp_bar_width(options.body_width_for(bar_width, 1.0 / density), dpr);
+    let wick_width = crisp_bar_width(options.wick_width_for(crisp_width), dpr);
+
+    let dense_mode = options.dense_mode_at(density);
+    if dense_mode == Some(DenseCandles::HighLow) {
+        render_high_low_columns(
+            batch,
+            data,
+            options,
+            &bar_to_x,
+            &price_to_y,
+            crisp_width,
+            dpr,
+        );
+        return;
+    }
+    let wick_visible = options.wick_visible && dense_mode.is_none();
 
     for (i, candle) in data.iter().enumerate() {
         let bar = &candle.bar;
@@ -94,22 +115,23 @@ pub fn render_candlesticks(
         };
 
         // Draw wick (vertical line from high to low)
-        if options.wick_visible {
-            let wick_x = crisp_coord(x, dpr);
+        if wick_visible {
+            let wick_x = crisp_stroke_coord(x, wick_width, dpr);
             let wick_y1 = crisp_coord(high_y, dpr);
             let wick_y2 = crisp_coord(low_y, dpr);
 
             batch.push(RenderCommand::Line {
                 from: Point::new(wick_x, wick_y1),
                 to: Point::new(wick_x, wick_y2),
-                style: LineStyle::solid(wick_color, 1.0),
+                style: LineStyle::solid(wick_color, wick_width),
             });
         }
 
         // Draw body (rectangle from open to close)
         let body_top = open_y.min(close_y);
         let body_bottom = open_y.max(close_y);
-        let body_height = (body_bottom - body_top).max(1.0 / dpr); // Minimum 1 device pixel
+        // A doji (open == close) still gets a 1px line so it stays visible
+        let body_height = (body_bottom - body_top).max(1.0);
 
         let (rect_x, rect_y, rect_w, rect_h) = crisp_rect(
             x - crisp_width / 2.0,
@@ -137,6 +159,95 @@ pub fn render_candlesticks(
     }
 }
 
+/// Bars per pixel, from the spacing of the first two bars (0 for one bar)
+fn bars_per_pixel(bar_to_x: &impl Fn(usize) -> f64, len: usize) -> f64 {
+    if len < 2 {
+        return 0.0;
+    }
+    let spacing = (bar_to_x(1) - bar_to_x(0)).abs();
+    if spacing > 0.0 {
+        1.0 / spacing
+    } else {
+        f64::INFINITY
+    }
+}
+
+/// Bars merged into one high-low column
+struct HighLowColumn<'a> {
+    index: i64,
+    open: f64,
+    high: f64,
+    low: f64,
+    close: f64,
+    color: Option<&'a str>,
+}
+
+/// Dense candles as high-low lines, one per body-width column
+///
+/// Past the density threshold bodies sit at their 1px minimum width and
+/// overlap their neighbours, so bars sharing a column merge into a single
+/// line over their combined range. The line takes the column's net move
+/// (first open to last close) for its color, or the custom color of the
+/// bar that closes it, and the series reads like an OHLC line chart.
+fn render_high_low_columns(
+    batch: &mut RenderBatch,
+    data: &[CandlestickData],
+    options: &CandlestickStyleOptions,
+    bar_to_x: &impl Fn(usize) -> f64,
+    price_to_y: &impl Fn(f64) -> f64,
+    column_width: f64,
+    dpr: f64,
+) {
+    let mut flush = |column: &HighLowColumn| {
+        let color = match column.color {
+            Some(color) => parse_color(color),
+            None if column.close >= column.open => parse_color(&options.up_color),
+            None => parse_color(&options.down_color),
+        };
+        let center = (column.index as f64 + 0.5) * column_width;
+        let x = crisp_stroke_coord(center, column_width, dpr);
+        batch.push(RenderCommand::Line {
+            from: Point::new(x, crisp_coord(price_to_y(column.high), dpr)),
+            to: Point::new(x, crisp_coord(price_to_y(column.low), dpr)),
+            style: LineStyle::solid(color, column_width),
+        });
+    };
+
+    let mut current: Option<HighLowColumn> = None;
+    for (i, candle) in data.iter().enumerate() {
+        let bar = &candle.bar;
+        if bar.open.is_nan() || bar.high.is_nan() || bar.low.is_nan() || bar.close.is_nan() {
+            continue;
+        }
+
+        let index = (bar_to_x(i) / column_width).floor() as i64;
+        match current.as_mut() {
+            Some(column) if column.index == index => {
+                column.high = column.high.max(bar.high);
+                column.low = column.low.min(bar.low);
+                column.close = bar.close;
+                column.color = candle.color.as_deref();
+            }
+            _ => {
+                if let Some(column) = &current {
+                    flush(column);
+                }
+                current = Some(HighLowColumn {
+                    index,
+                    open: bar.open,
+                    high: bar.high,
+                    low: bar.low,
+                    close: bar.close,
+                    color: candle.color.as_deref(),
+                });
+            }
+        }
+    }
+    if let Some(column) = &current {
+        flush(column);
+    }
+}
+
 // =============================================================================
 // Line Series
 // =============================================================================
@@ -276,7 +387,8 @@ fn build_curved_line_path(points: &[Point], _dpr: f64) -> Path {
 /// * `options` - Styling options for the area
 /// * `bar_to_x` - Function to convert bar index to X coordinate
 /// * `price_to_y` - Function to convert price to Y coordinate
-/// * `chart_bottom` - Y coordinate of chart bottom (for fill)
+/// * `chart_bottom` - Y coordinate of chart bottom (fill base unless
+///   `options.base_value` is set)
 /// * `dpr` - Device pixel ratio for crisp rendering
 pub fn render_area(
     batch: &mut RenderBatch,
@@ -306,28 +418,6 @@ pub fn render_area(
         return;
     }
 
-    // Create fill path
-    let mut builder = PathBuilder::new();
-
-    if options.invert_filled_area {
-        // Fill above line (to top)
-        builder.move_to(Point::new(points[0].x, 0.0));
-        for point in &points {
-            builder.line_to(*point);
-        }
-        builder.line_to(Point::new(points[points.len() - 1].x, 0.0));
-    } else {
-        // Fill below line (to bottom) - standard
-        builder.move_to(Point::new(points[0].x, chart_bottom));
-        for point in &points {
-            builder.line_to(*point);
-        }
-        builder.line_to(Point::new(points[points.len() - 1].x, chart_bottom));
-    }
-
-    builder.close();
-    let fill_path = builder.build();
-
     // Create gradient fill
     let gradient = FillStyle::LinearGradient {
         start: Point::new(0.0, 0.0),
@@ -338,12 +428,43 @@ pub fn render_area(
         ],
     };
 
-    // Draw fill
-    batch.push(RenderCommand::FillPath {
-        path: fill_path,
-        style: gradient,
+    let base_y = options.base_value.map_or(chart_bottom, &price_to_y);
+    let below_color = options.below_color.as_deref().filter(|_| {
+        // Only a custom base has anything to split
+        options.base_value.is_some() && !options.invert_filled_area
     });
 
+    if let Some(below_color) = below_color {
+        let (above, below) = area_fill_zones(&points, base_y);
+        batch.push(RenderCommand::FillPath {
+            path: Path::polygon(&above),
+            style: gradient,
+        });
+        batch.push(RenderCommand::FillPath {
+            path: Path::polygon(&below),
+            style: FillStyle::solid(parse_color(below_color)),
+        });
+    } else {
+        // Fill above line (to top) when inverted, else down to the base
+        let edge_y = if options.invert_filled_area {
+            0.0
+        } else {
+            base_y
+        };
+        let mut builder = PathBuilder::new();
+        builder.move_to(Point::new(points[0].x, edge_y));
+        for point in &points {
+            builder.line_to(*point);
+        }
+        builder.line_to(Point::new(points[points.len() - 1].x, edge_y));
+        builder.close();
+
+        batch.push(RenderCommand::FillPath {
+            path: builder.build(),
+            style: gradient,
+        });
+    }
+
     // Draw line
     if options.line_visible {
         let line_color = parse_color(&options.line_color);
@@ -376,6 +497,37 @@ pub fn render_area(
     }
 }
 
+/// Fill polygons above and below a horizontal base at `base_y`
+///
+/// The line is clamped to the base on each side, with a point inserted
+/// wherever it crosses, and each polygon is closed along the base. A side
+/// the line never reaches comes back flat.
+pub fn area_fill_zones(points: &[Point], base_y: f64) -> (Vec<Point>, Vec<Point>) {
+    let mut line = Vec::with_capacity(points.len() * 2);
+    for (i, &point) in points.iter().enumerate() {
+        if let Some(&prev) = i.checked_sub(1).and_then(|j| points.get(j)) {
+            if (prev.y - base_y) * (point.y - base_y) < 0.0 {
+                line.push(calculate_intersection(prev, point, base_y));
+            }
+        }
+        line.push(point);
+    }
+
+    // Y grows downward: above the base means a smaller y
+    let zone = |clamp: fn(f64, f64) -> f64| -> Vec<Point> {
+        let mut polygon: Vec<Point> = line
+            .iter()
+            .map(|p| Point::new(p.x, clamp(p.y, base_y)))
+            .collect();
+        if let (Some(first), Some(last)) = (line.first(), line.last()) {
+            polygon.push(Point::new(last.x, base_y));
+            polygon.push(Point::new(first.x, base_y));
+        }
+        polygon
+    };
+    (zone(f64::min), zone(f64::max))
+}
+
 // =============================================================================
 // Bar Series (OHLC bars)
 // =============================================================================
@@ -403,7 +555,7 @@ pub fn render_bars(
         return;
     }
 
-    let tick_width = bar_width * 0.3; // Tick extends 30% of bar width
+    let tick_width = options.tick_length_for(bar_width);
 
     for (i, bar_data) in data.iter().enumerate() {
         let bar = &bar_data.bar;
@@ -441,7 +593,7 @@ pub fn render_bars(
         });
 
         // Draw open tick (left)
-        if options.open_visible {
+        if options.show_open_tick {
             batch.push(RenderCommand::Line {
                 from: Point::new(x - tick_width, open_y),
                 to: Point::new(x, open_y),
@@ -450,11 +602,13 @@ pub fn render_bars(
         }
 
         // Draw close tick (right)
-        batch.push(RenderCommand::Line {
-            from: Point::new(x, close_y),
-            to: Point::new(x + tick_width, close_y),
-            style,
-        });
+        if options.show_close_tick {
+            batch.push(RenderCommand::Line {
+                from: Point::new(x, close_y),
+                to: Point::new(x + tick_width, close_y),
+                style,
+            });
+        }
     }
 }
 
@@ -809,6 +963,7 @@ pub fn render_hollow_candles(
     }
 
     let crisp_width = crisp_bar_width(bar_width, dpr);
+    let wick_width = crisp_bar_width(options.wick_width_for(crisp_width), dpr);
 
     for (i, candle) in data.iter().enumerate() {
         let bar = &candle.bar;
@@ -846,18 +1001,18 @@ pub fn render_hollow_candles(
 
         // Draw wick
         if options.wick_visible {
-            let wick_x = crisp_coord(x, dpr);
+            let wick_x = crisp_stroke_coord(x, wick_width, dpr);
             batch.push(RenderCommand::Line {
                 from: Point::new(wick_x, crisp_coord(high_y, dpr)),
                 to: Point::new(wick_x, crisp_coord(low_y, dpr)),
-                style: LineStyle::solid(wick_color, 1.0),
+                style: LineStyle::solid(wick_color, wick_width),
             });
         }
 
         // Draw body
         let body_top = open_y.min(close_y);
         let body_bottom = open_y.max(close_y);
-        let body_height = (body_bottom - body_top).max(1.0 / dpr);
+        let body_height = (body_bottom - body_top).max(1.0);
 
         let (rect_x, rect_y, rect_w, rect_h) = crisp_rect(
             x - crisp_width / 2.0,
@@ -897,6 +1052,9 @@ pub fn render_hollow_candles(
 /// - HA High = max(High, HA Open, HA Close)
 /// - HA Low = min(Low, HA Open, HA Close)
 ///
+/// With `options.ha_wick_source` set to [`HaWickSource::Original`] the wick
+/// spans the underlying bar's real high/low while the body stays smoothed.
+///
 /// # Arguments
 /// * `batch` - RenderBatch to push commands to
 /// * `data` - Candlestick data points (raw OHLC, will be converted to HA)
@@ -918,31 +1076,32 @@ pub fn render_heikin_ashi(
         return;
     }
 
-    let crisp_width = crisp_bar_width(bar_width, dpr);
+    let spacing = 1.0 / bars_per_pixel(&bar_to_x, data.len());
+    let crisp_width = crisp_bar_width(options.body_width_for(bar_width, spacing), dpr);
+    let wick_width = crisp_bar_width(options.wick_width_for(crisp_width), dpr);
 
-    // Calculate Heikin Ashi values
-    let mut ha_open = data[0].bar.open;
-    let mut ha_close;
+    let bars: Vec<_> = data.iter().map(|candle| candle.bar).collect();
 
-    for (i, candle) in data.iter().enumerate() {
+    for (i, (candle, ha_bar)) in data.iter().zip(heikin_ashi(&bars)).enumerate() {
         let bar = &candle.bar;
 
         if bar.open.is_nan() || bar.high.is_nan() || bar.low.is_nan() || bar.close.is_nan() {
             continue;
         }
 
-        // Calculate HA values
-        ha_close = (bar.open + bar.high + bar.low + bar.close) / 4.0;
-        let ha_high = bar.high.max(ha_open).max(ha_close);
-        let ha_low = bar.low.min(ha_open).min(ha_close);
+        let ha = &ha_bar.ha;
+        let wick_bar = match options.ha_wick_source {
+            HaWickSource::Smoothed => ha,
+            HaWickSource::Original => &ha_bar.original,
+        };
 
         let x = bar_to_x(i);
-        let open_y = price_to_y(ha_open);
-        let high_y = price_to_y(ha_high);
-        let low_y = price_to_y(ha_low);
-        let close_y = price_to_y(ha_close);
+        let open_y = price_to_y(ha.open);
+        let high_y = price_to_y(wick_bar.high);
+        let low_y = price_to_y(wick_bar.low);
+        let close_y = price_to_y(ha.close);
 
-        let is_bullish = ha_close >= ha_open;
+        let is_bullish = ha.close >= ha.open;
 
         // Determine colors
         let body_color = if let Some(ref color) = candle.color {
@@ -963,18 +1122,18 @@ pub fn render_heikin_ashi(
 
         // Draw wick
         if options.wick_visible {
-            let wick_x = crisp_coord(x, dpr);
+            let wick_x = crisp_stroke_coord(x, wick_width, dpr);
             batch.push(RenderCommand::Line {
                 from: Point::new(wick_x, crisp_coord(high_y, dpr)),
                 to: Point::new(wick_x, crisp_coord(low_y, dpr)),
-                style: LineStyle::solid(wick_color, 1.0),
+                style: LineStyle::solid(wick_color, wick_width),
             });
         }
 
         // Draw body
         let body_top = open_y.min(close_y);
         let body_bottom = open_y.max(close_y);
-        let body_height = (body_bottom - body_top).max(1.0 / dpr);
+        let body_height = (body_bottom - body_top).max(1.0);
 
         let (rect_x, rect_y, rect_w, rect_h) = crisp_rect(
             x - crisp_width / 2.0,
@@ -988,9 +1147,6 @@ pub fn render_heikin_ashi(
             rect: Rect::new(rect_x, rect_y, rect_w, rect_h),
             color: body_color,
         });
-
-        // Update HA open for next bar
-        ha_open = (ha_open + ha_close) / 2.0;
     }
 }
 
@@ -1295,6 +1451,218 @@ fn create_line_style(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::core::Bar;
+
+    fn candle(open: f64, high: f64, low: f64, close: f64) -> CandlestickData {
+        CandlestickData {
+            bar: Bar::new(0, open, high, low, close),
+            color: None,
+            border_color: None,
+            wick_color: None,
+        }
+    }
+
+    fn render_one(data: &CandlestickData, bar_width: f64) -> RenderBatch {
+        let mut batch = RenderBatch::new();
+        render_candlesticks(
+            &mut batch,
+            std::slice::from_ref(data),
+            &CandlestickStyleOptions::default(),
+            |_| 50.0,
+            |p| 200.0 - p,
+            bar_width,
+            1.0,
+        );
+        batch
+    }
+
+    fn wick_width(batch: &RenderBatch) -> f64 {
+        batch
+            .iter()
+            .find_map(|cmd| match cmd {
+                RenderCommand::Line { style, .. } => Some(style.width),
+                _ => None,
+            })
+            .unwrap()
+    }
+
+    #[test]
+    fn test_doji_body_is_visible() {
+        let batch = render_one(&candle(100.0, 105.0, 95.0, 100.0), 8.0);
+        let body = batch
+            .iter()
+            .find_map(|cmd| match cmd {
+                RenderCommand::FillRect { rect, .. } => Some(*rect),
+                _ => None,
+            })
+            .unwrap();
+        assert_eq!(body.height, 1.0);
+        assert_eq!(body.width, 8.0);
+    }
+
+    #[test]
+    fn test_wick_width_scales_with_bar_width() {
+        let data = candle(100.0, 105.0, 95.0, 102.0);
+        assert_eq!(wick_width(&render_one(&data, 4.0)), 1.0);
+        assert_eq!(wick_width(&render_one(&data, 20.0)), 3.0);
+        assert_eq!(wick_width(&render_one(&data, 100.0)), 4.0);
+
+        let fixed = CandlestickStyleOptions {
+            wick_width: Some(2.0),
+            ..Default::default()
+        };
+        assert_eq!(fixed.wick_width_for(100.0), 2.0);
+    }
+
+    #[test]
+    fn test_wick_width_ratio_keeps_one_pixel() {
+        let ratio = CandlestickStyleOptions {
+            wick_width_ratio: Some(0.5),
+            ..Default::default()
+        };
+        assert_eq!(ratio.wick_width_for(20.0), 10.0);
+        assert_eq!(ratio.wick_width_for(1.0), 1.0);
+
+        let fixed = CandlestickStyleOptions {
+            wick_width: Some(2.0),
+            ..ratio
+        };
+        assert_eq!(fixed.wick_width_for(20.0), 2.0);
+    }
+
+    fn render_dense(data: &[CandlestickData], options: &CandlestickStyleOptions) -> RenderBatch {
+        // Four bars per pixel; bodies fall back to their 1px minimum
+        let mut batch = RenderBatch::new();
+        render_candlesticks(
+            &mut batch,
+            data,
+            options,
+            |i| 10.0 + i as f64 * 0.25,
+            |p| 200.0 - p,
+            1.0,
+            1.0,
+        );
+        batch
+    }
+
+    #[test]
+    fn test_dense_candles_cull_wicks() {
+        let data = vec![candle(100.0, 105.0, 95.0, 102.0); 4];
+        let lines = |batch: &RenderBatch| {
+            batch
+                .iter()
+                .filter(|cmd| matches!(cmd, RenderCommand::Line { .. }))
+                .count()
+        };
+        let cull = |threshold| CandlestickStyleOptions {
+            density_threshold: Some(threshold),
+            dense_mode: DenseCandles::CullWicks,
+            ..Default::default()
+        };
+
+        let sparse = render_dense(&data, &cull(8.0));
+        assert_eq!(lines(&sparse), 4);
+
+        let dense = render_dense(&data, &cull(2.0));
+        assert_eq!(lines(&dense), 0);
+        let bodies = dense
+            .iter()
+            .filter(|cmd| matches!(cmd, RenderCommand::FillRect { .. }))
+            .count();
+        assert_eq!(bodies, 4);
+    }
+
+    #[test]
+    fn test_dense_candles_collapse_to_high_low_lines() {
+        let data = [
+            // Column 10: opens 100, closes 103, range 96..106
+            candle(100.0, 104.0, 98.0, 102.0),
+            candle(102.0, 106.0, 97.0, 101.0),
+            candle(101.0, 103.0, 99.0, 100.0),
+            candle(100.0, 105.0, 96.0, 103.0),
+            // Column 11: opens 103, closes 91, range 90..102
+            candle(103.0, 102.0, 95.0, 96.0),
+            candle(96.0, 97.0, 90.0, 92.0),
+            candle(92.0, 94.0, 91.0, 93.0),
+            candle(93.0, 95.0, 90.0, 91.0),
+        ];
+        let options = CandlestickStyleOptions {
+            density_threshold: Some(2.0),
+            ..Default::default()
+        };
+        let snapshot: Vec<_> = render_dense(&data, &options)
+            .iter()
+            .map(|cmd| match cmd {
+                RenderCommand::Line { from, to, style } => {
+                    (from.x, from.y, to.x, to.y, style.width, style.color)
+                }
+                other => panic!("unexpected {other:?}"),
+            })
+            .collect();
+
+        // One solid high-low line per pixel column, colored by its net move
+        let up = parse_color(&options.up_color);
+        let down = parse_color(&options.down_color);
+        assert_eq!(
+            snapshot,
+            [
+                (10.5, 94.5, 10.5, 104.5, 1.0, up),
+                (11.5, 98.5, 11.5, 110.5, 1.0, down),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_heikin_ashi_original_wicks() {
+        // Second bar gaps down: its HA open (100.625) sits above the real high
+        let data = [
+            candle(100.0, 110.0, 90.0, 105.0),
+            candle(80.0, 85.0, 70.0, 75.0),
+        ];
+        let render = |source| {
+            let options = CandlestickStyleOptions {
+                ha_wick_source: source,
+                ..Default::default()
+            };
+            let mut batch = RenderBatch::new();
+            render_heikin_ashi(
+                &mut batch,
+                &data,
+                &options,
+                |i| 50.0 + i as f64 * 20.0,
+                |p| 200.0 - p,
+                10.0,
+                1.0,
+            );
+            let wick = batch
+                .iter()
+                .filter_map(|cmd| match cmd {
+                    RenderCommand::Line { from, to, .. } => Some((from.y, to.y)),
+                    _ => None,
+                })
+                .nth(1)
+                .unwrap();
+            let body = batch
+                .iter()
+                .filter_map(|cmd| match cmd {
+                    RenderCommand::FillRect { rect, .. } => Some(*rect),
+                    _ => None,
+                })
+                .nth(1)
+                .unwrap();
+            (wick, body)
+        };
+
+        let (wick, body) = render(HaWickSource::Original);
+        assert_eq!(wick, (crisp_coord(115.0, 1.0), crisp_coord(130.0, 1.0)));
+        // Body still spans HA open 100.625 to HA close 77.5
+        assert!((body.y - 99.375).abs() <= 1.0);
+        assert!((body.y + body.height - 122.5).abs() <= 1.0);
+
+        let (wick, smoothed_body) = render(HaWickSource::Smoothed);
+        assert_eq!(wick, (crisp_coord(99.375, 1.0), crisp_coord(130.0, 1.0)));
+        assert_eq!(body, smoothed_body);
+    }
 
     #[test]
     fn test_parse_color() {
@@ -6,6 +6,7 @@
 use super::batch::RenderBatch;
 use super::commands::RenderCommand;
 use super::path::{Path, PathBuilder};
+use super::text_metrics::estimate_text_width;
 use super::types::{Color, FillStyle, LineStyle, Point, Rect, TextStyle, Transform2D};
 
 /// Result type for rendering operations
@@ -105,12 +106,30 @@ pub trait RenderBackend {
     /// Begin a new frame
     fn begin_frame(&mut self, width: f64, height: f64, dpr: f64);
 
+    /// Begin a new frame with independent horizontal and vertical pixel
+    /// ratios
+    ///
+    /// Backends with non-square device pixels override this; the default
+    /// begins the frame at the larger ratio.
+    fn begin_frame_xy(&mut self, width: f64, height: f64, dpr_x: f64, dpr_y: f64) {
+        self.begin_frame(width, height, dpr_x.max(dpr_y));
+    }
+
     /// End the current frame
     fn end_frame(&mut self);
 
-    /// Get current device pixel ratio
+    /// Get current device pixel ratio, the larger of [`Self::dpr_xy`] when
+    /// the axes differ
     fn dpr(&self) -> f64;
 
+    /// Get horizontal and vertical device pixel ratios
+    ///
+    /// Backends with non-square device pixels override this; the default
+    /// uses `dpr()` for both axes.
+    fn dpr_xy(&self) -> (f64, f64) {
+        (self.dpr(), self.dpr())
+    }
+
     /// Get current canvas size
     fn size(&self) -> (f64, f64);
 
@@ -208,7 +227,22 @@ pub trait RenderBackend {
     }
 
     /// Measure text dimensions
-    fn measure_text(&self, text: &str, style: &TextStyle) -> TextMetrics;
+    ///
+    /// Defaults to the sans-serif width table in [`super::text_metrics`];
+    /// backends with access to real font metrics should override this.
+    fn measure_text(&self, text: &str, style: &TextStyle) -> TextMetrics {
+        let width = text
+            .lines()
+            .map(|line| estimate_text_width(line, style.font_size, style.font_weight))
+            .fold(0.0, f64::max);
+
+        TextMetrics {
+            width,
+            height: style.font_size,
+            ascent: style.font_size * 0.8,
+            descent: style.font_size * 0.2,
+        }
+    }
 
     // =========================================================================
     // Images
@@ -248,6 +282,11 @@ pub trait RenderBackend {
     /// Set global alpha (affects all subsequent drawing)
     fn set_alpha(&mut self, alpha: f64);
 
+    /// Glow subsequent drawing with a blur of `radius` pixels (0 = off)
+    ///
+    /// A visual effect only: backends without filters ignore it.
+    fn set_glow(&mut self, _radius: f64) {}
+
     /// Save current state (transform, clip, alpha) to stack
     fn save(&mut self);
 
@@ -569,16 +608,6 @@ impl RenderBackend for NullBackend {
 
     fn text(&mut self, _text: &str, _pos: Point, _style: &TextStyle) {}
 
-    fn measure_text(&self, text: &str, _style: &TextStyle) -> TextMetrics {
-        // Rough approximation
-        TextMetrics {
-            width: text.len() as f64 * 7.0,
-            height: 14.0,
-            ascent: 11.0,
-            descent: 3.0,
-        }
-    }
-
     fn image(&mut self, _id: &str, _src: Option<Rect>, _dst: Rect) {}
     fn image_info(&self, _id: &str) -> Option<ImageInfo> {
         None
@@ -13,7 +13,7 @@ use serde::{Deserialize, Serialize};
 /// - Self-contained (no external state needed)
 /// - Serializable (for WASM/PyO3 export)
 /// - Efficient (minimal allocations)
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub enum RenderCommand {
     // =========================================================================
     // Shape commands (most common, optimized paths)
@@ -395,6 +395,46 @@ impl RenderCommand {
         }
     }
 
+    /// Variant name, e.g. `"FillRect"`, for stats and debugging
+    pub fn name(&self) -> &'static str {
+        match self {
+            RenderCommand::FillPath { .. } => "FillPath",
+            RenderCommand::StrokePath { .. } => "StrokePath",
+            RenderCommand::FillRect { .. } => "FillRect",
+            RenderCommand::StrokeRect { .. } => "StrokeRect",
+            RenderCommand::Line { .. } => "Line",
+            RenderCommand::Polyline { .. } => "Polyline",
+            RenderCommand::FillCircle { .. } => "FillCircle",
+            RenderCommand::StrokeCircle { .. } => "StrokeCircle",
+            RenderCommand::FillEllipse { .. } => "FillEllipse",
+            RenderCommand::StrokeEllipse { .. } => "StrokeEllipse",
+            RenderCommand::StrokeArc { .. } => "StrokeArc",
+            RenderCommand::Text { .. } => "Text",
+            RenderCommand::TextRotated { .. } => "TextRotated",
+            RenderCommand::TextWithBackground { .. } => "TextWithBackground",
+            RenderCommand::Image { .. } => "Image",
+            RenderCommand::PushClip { .. } => "PushClip",
+            RenderCommand::PopClip => "PopClip",
+            RenderCommand::PushTransform { .. } => "PushTransform",
+            RenderCommand::PopTransform => "PopTransform",
+            RenderCommand::PushLayer { .. } => "PushLayer",
+            RenderCommand::PopLayer => "PopLayer",
+            RenderCommand::SetAlpha { .. } => "SetAlpha",
+            RenderCommand::Save => "Save",
+            RenderCommand::Restore => "Restore",
+            RenderCommand::QuadraticCurveTo { .. } => "QuadraticCurveTo",
+            RenderCommand::BezierCurveTo { .. } => "BezierCurveTo",
+            RenderCommand::FillPolygon { .. } => "FillPolygon",
+            RenderCommand::StrokePolygon { .. } => "StrokePolygon",
+            RenderCommand::FillRoundedRect { .. } => "FillRoundedRect",
+            RenderCommand::StrokeRoundedRect { .. } => "StrokeRoundedRect",
+            RenderCommand::FillArc { .. } => "FillArc",
+            RenderCommand::Candlestick { .. } => "Candlestick",
+            RenderCommand::HistogramBar { .. } => "HistogramBar",
+            RenderCommand::GridLine { .. } => "GridLine",
+        }
+    }
+
     /// Check if this command affects state (needs push/pop)
     pub fn is_state_command(&self) -> bool {
         matches!(
@@ -9,6 +9,11 @@
 //! - The coordinate must be at a half-pixel boundary (e.g., 10.5, not 10.0)
 //! - This centers the 1px stroke on the pixel boundary
 //!
+//! More generally a stroke covering an odd number of device pixels is
+//! centered on a half-pixel, and one covering an even number on a whole
+//! pixel. At dpr 2 a 1px logical line is 2 device pixels wide, so it lands
+//! on whole device pixels (multiples of 0.5 in logical units).
+//!
 //! For a filled rectangle:
 //! - Coordinates should be at exact pixel boundaries
 //! - Width/height should be whole pixel counts
@@ -35,26 +40,114 @@ pub fn crisp_coord(coord: f64, dpr: f64) -> f64 {
     (coord * dpr).floor() / dpr + 0.5 / dpr
 }
 
+/// Align a coordinate for a crisp stroke of `stroke_width` logical pixels
+///
+/// Odd device-pixel widths get the half-pixel offset of [`crisp_coord`];
+/// even widths snap to the nearest whole device pixel.
+#[inline]
+pub fn crisp_stroke_coord(coord: f64, stroke_width: f64, dpr: f64) -> f64 {
+    if stroke_device_pixels(stroke_width, dpr) % 2 == 1 {
+        crisp_coord(coord, dpr)
+    } else {
+        (coord * dpr).round() / dpr
+    }
+}
+
+/// Stroke width in whole device pixels (at least 1)
+#[inline]
+fn stroke_device_pixels(stroke_width: f64, dpr: f64) -> i64 {
+    ((stroke_width * dpr).round() as i64).max(1)
+}
+
 /// Align both coordinates for a crisp horizontal or vertical line
 #[inline]
 pub fn crisp_line_coords(x1: f64, y1: f64, x2: f64, y2: f64, dpr: f64) -> (f64, f64, f64, f64) {
+    crisp_line_coords_xy(x1, y1, x2, y2, dpr, dpr)
+}
+
+/// Align a line with independent horizontal and vertical pixel densities
+///
+/// X coordinates snap to `dpr_x` and Y coordinates to `dpr_y`, so a
+/// horizontal line is positioned by `dpr_y` and a vertical one by `dpr_x`.
+#[inline]
+pub fn crisp_line_coords_xy(
+    x1: f64,
+    y1: f64,
+    x2: f64,
+    y2: f64,
+    dpr_x: f64,
+    dpr_y: f64,
+) -> (f64, f64, f64, f64) {
     // For horizontal lines, align Y; for vertical lines, align X
     let is_horizontal = (y2 - y1).abs() < 0.001;
     let is_vertical = (x2 - x1).abs() < 0.001;
 
     if is_horizontal {
-        let y = crisp_coord(y1, dpr);
-        ((x1 * dpr).floor() / dpr, y, (x2 * dpr).ceil() / dpr, y)
+        let y = crisp_coord(y1, dpr_y);
+        (
+            (x1 * dpr_x).floor() / dpr_x,
+            y,
+            (x2 * dpr_x).ceil() / dpr_x,
+            y,
+        )
     } else if is_vertical {
-        let x = crisp_coord(x1, dpr);
-        (x, (y1 * dpr).floor() / dpr, x, (y2 * dpr).ceil() / dpr)
+        let x = crisp_coord(x1, dpr_x);
+        (
+            x,
+            (y1 * dpr_y).floor() / dpr_y,
+            x,
+            (y2 * dpr_y).ceil() / dpr_y,
+        )
     } else {
         // Diagonal line - align start point
         (
-            crisp_coord(x1, dpr),
-            crisp_coord(y1, dpr),
-            crisp_coord(x2, dpr),
-            crisp_coord(y2, dpr),
+            crisp_coord(x1, dpr_x),
+            crisp_coord(y1, dpr_y),
+            crisp_coord(x2, dpr_x),
+            crisp_coord(y2, dpr_y),
+        )
+    }
+}
+
+/// Like [`crisp_line_coords_xy`], but honoring the stroke width
+///
+/// The line's position snaps with [`crisp_stroke_coord`], so even-width
+/// strokes sit on whole device pixels instead of half ones.
+#[inline]
+pub fn crisp_stroke_coords_xy(
+    x1: f64,
+    y1: f64,
+    x2: f64,
+    y2: f64,
+    stroke_width: f64,
+    dpr_x: f64,
+    dpr_y: f64,
+) -> (f64, f64, f64, f64) {
+    let is_horizontal = (y2 - y1).abs() < 0.001;
+    let is_vertical = (x2 - x1).abs() < 0.001;
+
+    if is_horizontal {
+        let y = crisp_stroke_coord(y1, stroke_width, dpr_y);
+        (
+            (x1 * dpr_x).floor() / dpr_x,
+            y,
+            (x2 * dpr_x).ceil() / dpr_x,
+            y,
+        )
+    } else if is_vertical {
+        let x = crisp_stroke_coord(x1, stroke_width, dpr_x);
+        (
+            x,
+            (y1 * dpr_y).floor() / dpr_y,
+            x,
+            (y2 * dpr_y).ceil() / dpr_y,
+        )
+    } else {
+        (
+            crisp_stroke_coord(x1, stroke_width, dpr_x),
+            crisp_stroke_coord(y1, stroke_width, dpr_y),
+            crisp_stroke_coord(x2, stroke_width, dpr_x),
+            crisp_stroke_coord(y2, stroke_width, dpr_y),
         )
     }
 }
@@ -116,10 +209,11 @@ pub fn crisp_bar_width(base_width: f64, dpr: f64) -> f64 {
 /// Calculate crisp stroke offset for centered lines
 ///
 /// When drawing a stroked rectangle or line, the stroke is centered on the path.
-/// For a 1px stroke to be crisp, we need to offset by 0.5 device pixels.
+/// A stroke covering an odd number of device pixels needs a 0.5 device pixel
+/// offset to be crisp; an even one needs none.
 #[inline]
 pub fn stroke_offset(stroke_width: f64, dpr: f64) -> f64 {
-    if stroke_width <= 1.0 / dpr {
+    if stroke_device_pixels(stroke_width, dpr) % 2 == 1 {
         0.5 / dpr
     } else {
         0.0
@@ -164,6 +258,22 @@ mod tests {
         assert_eq!(h, 1.0);
     }
 
+    #[test]
+    fn test_crisp_stroke_odd_even_widths() {
+        // 1px at dpr 1 and 3px at dpr 1 are odd: half-pixel
+        assert_eq!(crisp_stroke_coord(10.3, 1.0, 1.0), 10.5);
+        assert_eq!(crisp_stroke_coord(10.3, 3.0, 1.0), 10.5);
+        // 2px at dpr 1 and 1px at dpr 2 are even: whole device pixel
+        assert_eq!(crisp_stroke_coord(10.3, 2.0, 1.0), 10.0);
+        assert_eq!(crisp_stroke_coord(10.3, 1.0, 2.0), 10.5);
+        assert_eq!(crisp_stroke_coord(10.1, 1.0, 2.0), 10.0);
+        assert_eq!(stroke_offset(1.0, 2.0), 0.0);
+        assert_eq!(stroke_offset(1.5, 2.0), 0.25);
+
+        let (x1, y1, x2, y2) = crisp_stroke_coords_xy(0.2, 20.3, 99.7, 20.3, 1.0, 2.0, 2.0);
+        assert_eq!((x1, y1, x2, y2), (0.0, 20.5, 100.0, 20.5));
+    }
+
     #[test]
     fn test_crisp_bar_width() {
         assert_eq!(crisp_bar_width(5.3, 1.0), 5.0);
@@ -0,0 +1,208 @@
+//! Render diff - command deltas between two frames
+//!
+//! Frames are compared element by element (see
+//! [`SvgBackend::begin_element`](super::SvgBackend::begin_element)): the
+//! main series, each indicator and primitive, the scales and so on. A
+//! command's id is its element and its position in it (`"series#41"`), so
+//! appending to an element adds commands at its end instead of shifting the
+//! ones before.
+
+use super::commands::RenderCommand;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Commands of one frame grouped by element id, in draw order
+pub type FrameElements = Vec<(String, Vec<RenderCommand>)>;
+
+/// One command change between two frames
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum CommandDelta {
+    /// A command with no counterpart in the previous frame
+    Add { id: String, command: RenderCommand },
+    /// A command that is no longer drawn
+    Remove { id: String },
+    /// A command drawn differently than before
+    Update { id: String, command: RenderCommand },
+}
+
+impl CommandDelta {
+    /// Stable id of the command, `"<element>#<position>"`
+    pub fn id(&self) -> &str {
+        match self {
+            Self::Add { id, .. } | Self::Remove { id } | Self::Update { id, .. } => id,
+        }
+    }
+
+    /// Id of the element the command belongs to
+    pub fn element(&self) -> &str {
+        split_id(self.id()).0
+    }
+}
+
+/// Split `"<element>#<position>"`; element ids may contain `#` themselves
+fn split_id(id: &str) -> (&str, usize) {
+    match id.rsplit_once('#') {
+        Some((element, position)) => (element, position.parse().unwrap_or(0)),
+        None => (id, 0),
+    }
+}
+
+/// Commands to send to bring a frame up to date with a newer one
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct RenderDiff {
+    /// Element ids of the new frame, in draw order
+    pub order: Vec<String>,
+    /// Changes in the new frame's draw order, then removed elements
+    pub deltas: Vec<CommandDelta>,
+}
+
+impl RenderDiff {
+    /// Deltas turning the `prev` frame into `next`
+    pub fn between(
+        prev: &[(String, Vec<RenderCommand>)],
+        next: &[(String, Vec<RenderCommand>)],
+    ) -> Self {
+        let old: HashMap<&str, &[RenderCommand]> = prev
+            .iter()
+            .map(|(id, commands)| (id.as_str(), commands.as_slice()))
+            .collect();
+        let mut deltas = Vec::new();
+
+        for (element, commands) in next {
+            let before = old.get(element.as_str()).copied().unwrap_or_default();
+            for (i, command) in commands.iter().enumerate() {
+                let id = || format!("{}#{}", element, i);
+                match before.get(i) {
+                    Some(old) if old == command => {}
+                    Some(_) => deltas.push(CommandDelta::Update {
+                        id: id(),
+                        command: command.clone(),
+                    }),
+                    None => deltas.push(CommandDelta::Add {
+                        id: id(),
+                        command: command.clone(),
+                    }),
+                }
+            }
+            for i in commands.len()..before.len() {
+                deltas.push(CommandDelta::Remove {
+                    id: format!("{}#{}", element, i),
+                });
+            }
+        }
+
+        let kept: Vec<&str> = next.iter().map(|(id, _)| id.as_str()).collect();
+        for (element, commands) in prev.iter().filter(|(id, _)| !kept.contains(&id.as_str())) {
+            for i in 0..commands.len() {
+                deltas.push(CommandDelta::Remove {
+                    id: format!("{}#{}", element, i),
+                });
+            }
+        }
+
+        Self {
+            order: next.iter().map(|(id, _)| id.clone()).collect(),
+            deltas,
+        }
+    }
+
+    /// True when both frames draw the same commands
+    pub fn is_empty(&self) -> bool {
+        self.deltas.is_empty()
+    }
+
+    /// Number of changed commands
+    pub fn len(&self) -> usize {
+        self.deltas.len()
+    }
+
+    /// Ids of the elements with changes, in order of first change
+    pub fn elements(&self) -> Vec<&str> {
+        let mut elements: Vec<&str> = Vec::new();
+        for delta in &self.deltas {
+            if !elements.contains(&delta.element()) {
+                elements.push(delta.element());
+            }
+        }
+        elements
+    }
+
+    /// Bring the previous frame's elements up to date, as a receiver of
+    /// the diff would
+    pub fn apply(&self, frame: &mut FrameElements) {
+        let mut slots: HashMap<String, Vec<Option<RenderCommand>>> = frame
+            .drain(..)
+            .map(|(id, commands)| (id, commands.into_iter().map(Some).collect()))
+            .collect();
+        for delta in &self.deltas {
+            let (element, position) = split_id(delta.id());
+            let commands = slots.entry(element.to_string()).or_default();
+            if commands.len() <= position {
+                commands.resize(position + 1, None);
+            }
+            commands[position] = match delta {
+                CommandDelta::Add { command, .. } | CommandDelta::Update { command, .. } => {
+                    Some(command.clone())
+                }
+                CommandDelta::Remove { .. } => None,
+            };
+        }
+        frame.extend(self.order.iter().map(|id| {
+            let commands = slots.remove(id).unwrap_or_default();
+            (id.clone(), commands.into_iter().flatten().collect())
+        }));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::render::engine::{Color, Rect};
+
+    fn rect(x: f64) -> RenderCommand {
+        RenderCommand::FillRect {
+            rect: Rect::new(x, 0.0, 4.0, 10.0),
+            color: Color::rgb(38, 166, 154),
+        }
+    }
+
+    #[test]
+    fn test_diff_keys_commands_by_element_position() {
+        let prev: FrameElements = vec![
+            ("series".into(), vec![rect(0.0), rect(8.0)]),
+            ("primitive:a".into(), vec![rect(50.0)]),
+            ("time_scale".into(), vec![rect(90.0), rect(95.0)]),
+        ];
+        let next: FrameElements = vec![
+            ("series".into(), vec![rect(0.0), rect(8.0), rect(16.0)]),
+            ("time_scale".into(), vec![rect(91.0)]),
+        ];
+
+        let diff = RenderDiff::between(&prev, &next);
+        assert_eq!(
+            diff.deltas,
+            vec![
+                CommandDelta::Add {
+                    id: "series#2".into(),
+                    command: rect(16.0),
+                },
+                CommandDelta::Update {
+                    id: "time_scale#0".into(),
+                    command: rect(91.0),
+                },
+                CommandDelta::Remove {
+                    id: "time_scale#1".into(),
+                },
+                CommandDelta::Remove {
+                    id: "primitive:a#0".into(),
+                },
+            ]
+        );
+        assert_eq!(diff.elements(), ["series", "time_scale", "primitive:a"]);
+        assert!(RenderDiff::between(&next, &next).is_empty());
+
+        let mut frame = prev;
+        diff.apply(&mut frame);
+        assert_eq!(frame, next);
+    }
+}
@@ -0,0 +1,172 @@
+//! SVG fragments - replaceable parts of a rendered document
+//!
+//! A document rendered with groups marked (see
+//! [`SvgBackend::mark_groups`](super::SvgBackend::mark_groups)) wraps the
+//! parts that change on their own, like the last bar or the price tag, in
+//! `<g id="zc-...">` elements. Between two such documents only the groups
+//! whose markup changed need to be sent, and a client swaps the inner
+//! markup of just those nodes.
+
+use serde::{Deserialize, Serialize};
+use std::ops::Range;
+
+/// Prefix of replaceable group ids, so other `<g id>`s are never matched
+pub const FRAGMENT_ID_PREFIX: &str = "zc-";
+
+/// New markup for one group, or for the whole document
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SvgFragment {
+    /// Id of the group whose inner markup `svg` replaces; `None` when
+    /// `svg` is a whole new document
+    pub id: Option<String>,
+    pub svg: String,
+}
+
+impl SvgFragment {
+    /// Fragments turning the `prev` document into `next`
+    ///
+    /// Changed groups are sent on their own as long as everything outside
+    /// them is unchanged; otherwise the whole of `next` is one fragment.
+    pub fn between(prev: &str, next: &str) -> Vec<Self> {
+        let (old, new) = (groups(prev), groups(next));
+        let same_ids = old.len() == new.len() && old.iter().zip(&new).all(|(a, b)| a.0 == b.0);
+        if !same_ids || outside(prev, &old).ne(outside(next, &new)) {
+            return vec![Self {
+                id: None,
+                svg: next.to_string(),
+            }];
+        }
+        old.iter()
+            .zip(&new)
+            .filter(|(a, b)| prev[a.1.clone()] != next[b.1.clone()])
+            .map(|(_, (id, inner))| Self {
+                id: Some(id.to_string()),
+                svg: next[inner.clone()].to_string(),
+            })
+            .collect()
+    }
+
+    /// Apply fragments to a document, as a client swapping group markup
+    /// would; fragments for groups the document lacks are skipped
+    pub fn apply(svg: &mut String, fragments: &[SvgFragment]) {
+        for fragment in fragments {
+            match &fragment.id {
+                None => fragment.svg.clone_into(svg),
+                Some(id) => {
+                    let inner = groups(svg)
+                        .into_iter()
+                        .find(|(group, _)| group == id)
+                        .map(|(_, inner)| inner);
+                    if let Some(inner) = inner {
+                        svg.replace_range(inner, &fragment.svg);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Replaceable groups of a document: id and byte range of the inner markup
+fn groups(svg: &str) -> Vec<(&str, Range<usize>)> {
+    let open = format!(r#"<g id="{}"#, FRAGMENT_ID_PREFIX);
+    let mut groups = Vec::new();
+    let mut pos = 0;
+    while let Some(found) = svg[pos..].find(&open) {
+        let id_start = pos + found + r#"<g id=""#.len();
+        let Some(id_len) = svg[id_start..].find('"') else {
+            break;
+        };
+        let Some(tag_len) = svg[id_start..].find('>') else {
+            break;
+        };
+        let start = id_start + tag_len + 1;
+        let end = closing_tag(svg, start);
+        groups.push((&svg[id_start..id_start + id_len], start..end));
+        pos = (end + "</g>".len()).min(svg.len());
+    }
+    groups
+}
+
+/// Position of the `</g>` closing a group whose content starts at `start`
+fn closing_tag(svg: &str, start: usize) -> usize {
+    // Walk opening and closing tags in document order
+    let mut depth = 0;
+    let mut pos = start;
+    loop {
+        let next_open = svg[pos..]
+            .match_indices("<g")
+            .map(|(i, _)| pos + i)
+            .find(|&i| matches!(svg.as_bytes().get(i + 2), Some(b' ' | b'>')));
+        let Some(next_close) = svg[pos..].find("</g>").map(|i| pos + i) else {
+            return svg.len();
+        };
+        match next_open {
+            Some(open) if open < next_close => {
+                depth += 1;
+                pos = open + 2;
+            }
+            _ if depth == 0 => return next_close,
+            _ => {
+                depth -= 1;
+                pos = next_close + "</g>".len();
+            }
+        }
+    }
+}
+
+/// Markup outside the groups, in order
+fn outside<'a>(svg: &'a str, groups: &[(&str, Range<usize>)]) -> impl Iterator<Item = &'a str> {
+    let starts = std::iter::once(0).chain(groups.iter().map(|(_, inner)| inner.end));
+    let ends = groups
+        .iter()
+        .map(|(_, inner)| inner.start)
+        .chain(std::iter::once(svg.len()));
+    starts.zip(ends).map(move |(start, end)| &svg[start..end])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn doc(bar: &str, tag: &str, scale: &str) -> String {
+        format!(
+            "<svg>\n<g clip-path=\"url(#clip0)\">\n<g id=\"zc-last-bar\">\n{}\n</g>\n</g>\n\
+             <g id=\"zc-last-price\">\n<g><rect/></g>\n{}\n</g>\n{}\n</svg>",
+            bar, tag, scale
+        )
+    }
+
+    #[test]
+    fn test_fragments_swap_changed_groups() {
+        let prev = doc("<rect y=\"10\"/>", "<text>101</text>", "<text>100</text>");
+        let next = doc("<rect y=\"8\"/>", "<text>102</text>", "<text>100</text>");
+
+        let fragments = SvgFragment::between(&prev, &next);
+        assert_eq!(
+            fragments,
+            vec![
+                SvgFragment {
+                    id: Some("zc-last-bar".into()),
+                    svg: "\n<rect y=\"8\"/>\n".into(),
+                },
+                SvgFragment {
+                    id: Some("zc-last-price".into()),
+                    svg: "\n<g><rect/></g>\n<text>102</text>\n".into(),
+                },
+            ]
+        );
+        let mut svg = prev.clone();
+        SvgFragment::apply(&mut svg, &fragments);
+        assert_eq!(svg, next);
+        assert!(SvgFragment::between(&next, &next).is_empty());
+
+        // A change outside the groups resends the document
+        let moved = doc("<rect y=\"8\"/>", "<text>102</text>", "<text>105</text>");
+        let fragments = SvgFragment::between(&next, &moved);
+        assert_eq!(fragments.len(), 1);
+        assert_eq!(fragments[0].id, None);
+        let mut svg = prev;
+        SvgFragment::apply(&mut svg, &fragments);
+        assert_eq!(svg, moved);
+    }
+}
@@ -9,23 +9,33 @@
 //! - `path` - Path construction and manipulation
 //! - `commands` - Atomic render commands (RenderCommand enum)
 //! - `batch` - Command batching with O(1) bounds tracking
+//! - `diff` - Command deltas between two recorded frames
+//! - `fragment` - Replaceable groups of rendered SVG documents
 //! - `backend` - RenderBackend trait for platform abstraction
 //! - `crisp` - Pixel-perfect rendering utilities
 //! - `coords` - Coordinate system conversion
+//! - `text_metrics` - Sans-serif advance widths for text measurement
+//! - `raster_backend` - RGBA rasterizer (`raster` feature)
 
 pub mod backend;
 pub mod batch;
 pub mod commands;
 pub mod coords;
 pub mod crisp;
+pub mod diff;
+pub mod fragment;
 pub mod path;
+#[cfg(feature = "raster")]
+pub mod raster_backend;
 pub mod svg_backend;
+pub mod text_metrics;
 pub mod types;
 
 // Re-exports - Core types
 pub use types::{Color, Point, Rect, Transform2D};
 pub use types::{
-    FillStyle, FontWeight, LineCap, LineJoin, LineStyle, TextAlign, TextBaseline, TextStyle,
+    FillRule, FillStyle, FontWeight, LineCap, LineJoin, LineStyle, TextAlign, TextBaseline,
+    TextStyle,
 };
 
 // Re-exports - Path
@@ -37,17 +47,28 @@ pub use commands::RenderCommand;
 // Re-exports - Batch
 pub use batch::{RenderBatch, RenderQueue, layers};
 
+// Re-exports - Frame diffs
+pub use diff::{CommandDelta, FrameElements, RenderDiff};
+pub use fragment::{FRAGMENT_ID_PREFIX, SvgFragment};
+
 // Re-exports - Backend
 pub use backend::{ImageInfo, NullBackend, RenderBackend, RenderError, RenderResult, TextMetrics};
 
 // Re-exports - Crisp rendering
 pub use crisp::{
-    crisp_bar_width, crisp_coord, crisp_line_coords, crisp_point, crisp_rect, crisp_rect_struct,
-    stroke_offset,
+    crisp_bar_width, crisp_coord, crisp_line_coords, crisp_line_coords_xy, crisp_point, crisp_rect,
+    crisp_rect_struct, crisp_stroke_coord, crisp_stroke_coords_xy, stroke_offset,
 };
 
 // Re-exports - Coordinate conversion
 pub use coords::{CoordSystem, snap_point_to_pixel, snap_rect_to_pixel, snap_to_pixel};
 
+// Re-exports - Text measurement
+pub use text_metrics::{baseline_shift_em, char_advance, estimate_text_width};
+
 // Re-exports - SVG backend
-pub use svg_backend::SvgBackend;
+pub use svg_backend::{PrecisionCategory, SvgBackend, SvgPrecision};
+
+// Re-exports - Raster backend
+#[cfg(feature = "raster")]
+pub use raster_backend::RasterBackend;
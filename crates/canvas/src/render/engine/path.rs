@@ -3,7 +3,7 @@
 //! Immutable paths that can be reused for multiple draw operations.
 //! Optimized for minimal allocations in hot rendering paths.
 
-use super::types::{Point, Rect};
+use super::types::{FillRule, Point, Rect};
 use serde::{Deserialize, Serialize};
 
 /// Path command (subpath segment)
@@ -39,11 +39,28 @@ pub enum PathCommand {
     Close,
 }
 
+/// Signed angle an arc turns through, canvas style: clockwise arcs run
+/// forward from `start` to `end`, counterclockwise ones backward, wrapping
+/// around at most one full turn
+pub(crate) fn arc_sweep(start: f64, end: f64, ccw: bool) -> f64 {
+    use std::f64::consts::TAU;
+
+    let mut sweep = end - start;
+    if !ccw && sweep < 0.0 {
+        sweep = sweep.rem_euclid(TAU);
+    } else if ccw && sweep > 0.0 {
+        sweep -= TAU;
+    }
+    sweep.clamp(-TAU, TAU)
+}
+
 /// Immutable path (can be stored and reused)
-#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
 pub struct Path {
     commands: Vec<PathCommand>,
     bounds: Rect,
+    #[serde(default)]
+    fill_rule: FillRule,
 }
 
 impl Path {
@@ -69,12 +86,52 @@ impl Path {
         self.bounds
     }
 
+    /// Fill rule used when this path is filled
+    #[inline]
+    pub fn fill_rule(&self) -> FillRule {
+        self.fill_rule
+    }
+
+    /// Set the fill rule used when this path is filled
+    pub fn with_fill_rule(mut self, fill_rule: FillRule) -> Self {
+        self.fill_rule = fill_rule;
+        self
+    }
+
     /// Check if path is empty
     #[inline]
     pub fn is_empty(&self) -> bool {
         self.commands.is_empty()
     }
 
+    /// True when no point, radius or angle is NaN or infinite
+    pub fn is_finite(&self) -> bool {
+        self.commands.iter().all(|cmd| match *cmd {
+            PathCommand::MoveTo(p) | PathCommand::LineTo(p) => p.is_finite(),
+            PathCommand::QuadTo { control, end } => control.is_finite() && end.is_finite(),
+            PathCommand::CubicTo { c1, c2, end } => {
+                c1.is_finite() && c2.is_finite() && end.is_finite()
+            }
+            PathCommand::Arc {
+                center,
+                radius,
+                start,
+                end,
+                ..
+            } => center.is_finite() && [radius, start, end].iter().all(|v| v.is_finite()),
+            PathCommand::Ellipse {
+                center,
+                rx,
+                ry,
+                rotation,
+                start,
+                end,
+                ..
+            } => center.is_finite() && [rx, ry, rotation, start, end].iter().all(|v| v.is_finite()),
+            PathCommand::Close => true,
+        })
+    }
+
     // =========================================================================
     // Factory methods for common shapes
     // =========================================================================
@@ -222,6 +279,7 @@ impl PathBuilder {
         Path {
             commands: self.commands,
             bounds,
+            fill_rule: FillRule::NonZero,
         }
     }
 
@@ -365,6 +423,12 @@ impl PathBuilder {
         let max_r = rx.max(ry);
         self.update_bounds(Point::new(center.x - max_r, center.y - max_r));
         self.update_bounds(Point::new(center.x + max_r, center.y + max_r));
+        let (sin_r, cos_r) = rotation.sin_cos();
+        let (x, y) = (rx * end.cos(), ry * end.sin());
+        self.current = Point::new(
+            center.x + x * cos_r - y * sin_r,
+            center.y + x * sin_r + y * cos_r,
+        );
         self
     }
 
@@ -0,0 +1,554 @@
+//! Raster Render Backend
+//!
+//! Rasterizes render commands into an RGBA pixel buffer with tiny-skia.
+//! Used for PNG export and for uploading charts as GPU textures.
+//!
+//! Text and images are not drawn: tiny-skia has no font or image decoding
+//! support, so labels only appear in the SVG output.
+
+use super::backend::{ImageInfo, RenderBackend, RenderError, RenderResult};
+use super::path::{Path, PathCommand, arc_sweep};
+use super::types::{
+    Color, FillRule, FillStyle, LineCap, LineJoin, LineStyle, Point, Rect, TextStyle, Transform2D,
+};
+use tiny_skia as sk;
+
+/// Saved state for save/restore: stack depths and global alpha
+#[derive(Clone, Copy, Debug)]
+struct RasterState {
+    transforms: usize,
+    clips: usize,
+    alpha: f64,
+}
+
+/// Raster render backend
+///
+/// Draws into a pixmap of `width * dpr_x` by `height * dpr_y` device pixels.
+/// Pixels are RGBA8 with premultiplied alpha, row-major from the top-left
+/// corner, with a stride of `4 * width` bytes.
+pub struct RasterBackend {
+    /// Target pixmap
+    pixmap: sk::Pixmap,
+    /// Logical width
+    width: f64,
+    /// Logical height
+    height: f64,
+    /// Horizontal device pixel ratio
+    dpr_x: f64,
+    /// Vertical device pixel ratio
+    dpr_y: f64,
+    /// Pushed transforms, each already combined with the one below
+    transforms: Vec<sk::Transform>,
+    /// Pushed clips, each already intersected with the one below
+    clips: Vec<sk::Mask>,
+    /// Layers drawn offscreen and composited with their opacity on pop
+    layers: Vec<(sk::Pixmap, f64)>,
+    /// State stack (for save/restore)
+    state_stack: Vec<RasterState>,
+    /// Global alpha
+    alpha: f64,
+}
+
+impl RasterBackend {
+    /// Create a new raster backend
+    pub fn new(width: u32, height: u32, dpr: f64) -> RenderResult<Self> {
+        Self::with_dpr_xy(width, height, dpr, dpr)
+    }
+
+    /// Create a backend with independent horizontal and vertical pixel
+    /// densities
+    pub fn with_dpr_xy(width: u32, height: u32, dpr_x: f64, dpr_y: f64) -> RenderResult<Self> {
+        let pixmap = Self::new_pixmap(width as f64 * dpr_x, height as f64 * dpr_y)?;
+        Ok(Self {
+            pixmap,
+            width: width as f64,
+            height: height as f64,
+            dpr_x,
+            dpr_y,
+            transforms: Vec::new(),
+            clips: Vec::new(),
+            layers: Vec::new(),
+            state_stack: Vec::new(),
+            alpha: 1.0,
+        })
+    }
+
+    fn new_pixmap(width: f64, height: f64) -> RenderResult<sk::Pixmap> {
+        sk::Pixmap::new(width.round() as u32, height.round() as u32).ok_or_else(|| {
+            RenderError::Platform(format!("invalid raster size {}x{}", width, height))
+        })
+    }
+
+    /// Pixel buffer size in device pixels
+    pub fn pixel_size(&self) -> (u32, u32) {
+        (self.pixmap.width(), self.pixmap.height())
+    }
+
+    /// Premultiplied RGBA8 pixels, row-major from the top-left corner
+    pub fn pixels(&self) -> &[u8] {
+        self.pixmap.data()
+    }
+
+    /// Take the premultiplied RGBA8 pixel buffer
+    ///
+    /// Layers still open are composited first.
+    pub fn into_rgba(mut self) -> Vec<u8> {
+        while !self.layers.is_empty() {
+            self.pop_layer();
+        }
+        self.pixmap.take()
+    }
+
+    /// Encode the frame as PNG
+    pub fn encode_png(&self) -> RenderResult<Vec<u8>> {
+        self.pixmap
+            .encode_png()
+            .map_err(|err| RenderError::Platform(err.to_string()))
+    }
+
+    /// Current logical-to-device transform
+    fn transform(&self) -> sk::Transform {
+        self.transforms
+            .last()
+            .copied()
+            .unwrap_or_else(|| sk::Transform::from_scale(self.dpr_x as f32, self.dpr_y as f32))
+    }
+
+    fn target(&mut self) -> &mut sk::Pixmap {
+        match self.layers.last_mut() {
+            Some((layer, _)) => layer,
+            None => &mut self.pixmap,
+        }
+    }
+
+    fn solid_paint(&self, color: Color) -> sk::Paint<'static> {
+        let mut paint = sk::Paint::default();
+        let alpha = (color.a as f64 * self.alpha.clamp(0.0, 1.0)).round() as u8;
+        paint.set_color_rgba8(color.r, color.g, color.b, alpha);
+        paint.anti_alias = true;
+        paint
+    }
+
+    /// Paint for a fill style; gradient coordinates are relative to the
+    /// path bounds, as in the SVG backend
+    fn fill_paint(&self, style: &FillStyle, bounds: Rect) -> sk::Paint<'static> {
+        let to_stops = |stops: &[(f64, Color)]| -> Vec<sk::GradientStop> {
+            stops
+                .iter()
+                .map(|(offset, color)| {
+                    let alpha = (color.a as f64 * self.alpha.clamp(0.0, 1.0)).round() as u8;
+                    sk::GradientStop::new(
+                        *offset as f32,
+                        sk::Color::from_rgba8(color.r, color.g, color.b, alpha),
+                    )
+                })
+                .collect()
+        };
+        let at = |p: &Point| {
+            sk::Point::from_xy(
+                (bounds.x + p.x * bounds.width) as f32,
+                (bounds.y + p.y * bounds.height) as f32,
+            )
+        };
+        let shader = match style {
+            FillStyle::Solid(color) => return self.solid_paint(*color),
+            FillStyle::LinearGradient { start, end, stops } => sk::LinearGradient::new(
+                at(start),
+                at(end),
+                to_stops(stops),
+                sk::SpreadMode::Pad,
+                sk::Transform::identity(),
+            ),
+            FillStyle::RadialGradient {
+                center,
+                radius,
+                stops,
+            } => sk::RadialGradient::new(
+                at(center),
+                at(center),
+                (radius * bounds.width.max(bounds.height)) as f32,
+                to_stops(stops),
+                sk::SpreadMode::Pad,
+                sk::Transform::identity(),
+            ),
+        };
+        match shader {
+            Some(shader) => sk::Paint {
+                shader,
+                anti_alias: true,
+                ..Default::default()
+            },
+            // Degenerate gradient: fall back to its first stop
+            None => {
+                let first = match style {
+                    FillStyle::LinearGradient { stops, .. }
+                    | FillStyle::RadialGradient { stops, .. } => stops.first(),
+                    FillStyle::Solid(_) => None,
+                };
+                self.solid_paint(first.map_or(Color::TRANSPARENT, |(_, c)| *c))
+            }
+        }
+    }
+
+    fn stroke(style: &LineStyle) -> sk::Stroke {
+        sk::Stroke {
+            width: style.width as f32,
+            line_cap: match style.cap {
+                LineCap::Butt => sk::LineCap::Butt,
+                LineCap::Round => sk::LineCap::Round,
+                LineCap::Square => sk::LineCap::Square,
+            },
+            line_join: match style.join {
+                LineJoin::Miter => sk::LineJoin::Miter,
+                LineJoin::Round => sk::LineJoin::Round,
+                LineJoin::Bevel => sk::LineJoin::Bevel,
+            },
+            dash: style
+                .dash
+                .as_ref()
+                .filter(|dash| !dash.is_empty())
+                .and_then(|dash| {
+                    // tiny-skia wants an even number of intervals, like SVG's
+                    // dasharray repeating an odd list
+                    let mut intervals: Vec<f32> = dash.iter().map(|d| *d as f32).collect();
+                    if intervals.len() % 2 == 1 {
+                        intervals.extend_from_within(..);
+                    }
+                    sk::StrokeDash::new(intervals, 0.0)
+                }),
+            ..Default::default()
+        }
+    }
+
+    /// Convert a path, flattening arcs and ellipses into line segments
+    fn to_sk_path(path: &Path) -> Option<sk::Path> {
+        let mut builder = sk::PathBuilder::new();
+        let mut open = false;
+        // Continue the open subpath, or start one (arcs join the current
+        // point like canvas arcs do)
+        fn point_to(builder: &mut sk::PathBuilder, open: &mut bool, p: Point) {
+            if *open {
+                builder.line_to(p.x as f32, p.y as f32);
+            } else {
+                builder.move_to(p.x as f32, p.y as f32);
+                *open = true;
+            }
+        }
+
+        for cmd in path.commands() {
+            match cmd {
+                PathCommand::MoveTo(p) => {
+                    builder.move_to(p.x as f32, p.y as f32);
+                    open = true;
+                }
+                PathCommand::LineTo(p) => point_to(&mut builder, &mut open, *p),
+                PathCommand::QuadTo { control, end } => {
+                    if !open {
+                        point_to(&mut builder, &mut open, *control);
+                    }
+                    builder.quad_to(
+                        control.x as f32,
+                        control.y as f32,
+                        end.x as f32,
+                        end.y as f32,
+                    );
+                }
+                PathCommand::CubicTo { c1, c2, end } => {
+                    if !open {
+                        point_to(&mut builder, &mut open, *c1);
+                    }
+                    builder.cubic_to(
+                        c1.x as f32,
+                        c1.y as f32,
+                        c2.x as f32,
+                        c2.y as f32,
+                        end.x as f32,
+                        end.y as f32,
+                    );
+                }
+                PathCommand::Arc {
+                    center,
+                    radius,
+                    start,
+                    end,
+                    ccw,
+                } => {
+                    for p in arc_points(*center, *radius, *radius, 0.0, *start, *end, *ccw) {
+                        point_to(&mut builder, &mut open, p);
+                    }
+                }
+                PathCommand::Ellipse {
+                    center,
+                    rx,
+                    ry,
+                    rotation,
+                    start,
+                    end,
+                    ccw,
+                } => {
+                    for p in arc_points(*center, *rx, *ry, *rotation, *start, *end, *ccw) {
+                        point_to(&mut builder, &mut open, p);
+                    }
+                }
+                PathCommand::Close => {
+                    builder.close();
+                    open = false;
+                }
+            }
+        }
+
+        builder.finish()
+    }
+}
+
+/// Points along an elliptical arc, about two pixels apart
+fn arc_points(
+    center: Point,
+    rx: f64,
+    ry: f64,
+    rotation: f64,
+    start: f64,
+    end: f64,
+    ccw: bool,
+) -> impl Iterator<Item = Point> {
+    let sweep = arc_sweep(start, end, ccw);
+    let segments = (sweep.abs() * rx.max(ry) / 2.0).ceil().clamp(4.0, 256.0) as usize;
+    let (sin_r, cos_r) = rotation.sin_cos();
+
+    (0..=segments).map(move |i| {
+        let t = start + sweep * i as f64 / segments as f64;
+        let (x, y) = (rx * t.cos(), ry * t.sin());
+        Point::new(
+            center.x + x * cos_r - y * sin_r,
+            center.y + x * sin_r + y * cos_r,
+        )
+    })
+}
+
+impl RenderBackend for RasterBackend {
+    fn begin_frame(&mut self, width: f64, height: f64, dpr: f64) {
+        self.begin_frame_xy(width, height, dpr, dpr);
+    }
+
+    fn begin_frame_xy(&mut self, width: f64, height: f64, dpr_x: f64, dpr_y: f64) {
+        self.width = width;
+        self.height = height;
+        self.dpr_x = dpr_x;
+        self.dpr_y = dpr_y;
+        if let Ok(pixmap) = Self::new_pixmap(width * dpr_x, height * dpr_y) {
+            self.pixmap = pixmap;
+        }
+        self.transforms.clear();
+        self.clips.clear();
+        self.layers.clear();
+        self.state_stack.clear();
+        self.alpha = 1.0;
+    }
+
+    fn end_frame(&mut self) {
+        while !self.layers.is_empty() {
+            self.pop_layer();
+        }
+    }
+
+    fn dpr(&self) -> f64 {
+        self.dpr_x.max(self.dpr_y)
+    }
+
+    fn dpr_xy(&self) -> (f64, f64) {
+        (self.dpr_x, self.dpr_y)
+    }
+
+    fn size(&self) -> (f64, f64) {
+        (self.width, self.height)
+    }
+
+    fn clear(&mut self, color: Color) {
+        self.target()
+            .fill(sk::Color::from_rgba8(color.r, color.g, color.b, color.a));
+    }
+
+    fn clear_rect(&mut self, rect: Rect) {
+        let mut paint = self.solid_paint(Color::TRANSPARENT);
+        paint.blend_mode = sk::BlendMode::Clear;
+        if let Some(rect) = sk::Rect::from_xywh(
+            rect.x as f32,
+            rect.y as f32,
+            rect.width as f32,
+            rect.height as f32,
+        ) {
+            let transform = self.transform();
+            self.target().fill_rect(rect, &paint, transform, None);
+        }
+    }
+
+    fn fill_path(&mut self, path: &Path, style: &FillStyle) {
+        let Some(sk_path) = Self::to_sk_path(path) else {
+            return;
+        };
+        let paint = self.fill_paint(style, path.bounds());
+        let rule = match path.fill_rule() {
+            FillRule::NonZero => sk::FillRule::Winding,
+            FillRule::EvenOdd => sk::FillRule::EvenOdd,
+        };
+        let transform = self.transform();
+        let (target, clip) = match self.layers.last_mut() {
+            Some((layer, _)) => (layer, self.clips.last()),
+            None => (&mut self.pixmap, self.clips.last()),
+        };
+        target.fill_path(&sk_path, &paint, rule, transform, clip);
+    }
+
+    fn stroke_path(&mut self, path: &Path, style: &LineStyle) {
+        let Some(sk_path) = Self::to_sk_path(path) else {
+            return;
+        };
+        let paint = self.solid_paint(style.color);
+        let stroke = Self::stroke(style);
+        let transform = self.transform();
+        let (target, clip) = match self.layers.last_mut() {
+            Some((layer, _)) => (layer, self.clips.last()),
+            None => (&mut self.pixmap, self.clips.last()),
+        };
+        target.stroke_path(&sk_path, &paint, &stroke, transform, clip);
+    }
+
+    fn text(&mut self, _text: &str, _pos: Point, _style: &TextStyle) {
+        // No font rasterizer
+    }
+
+    fn image(&mut self, _id: &str, _src: Option<Rect>, _dst: Rect) {
+        // No image decoder
+    }
+
+    fn image_info(&self, _id: &str) -> Option<ImageInfo> {
+        None
+    }
+
+    fn preload_image(&mut self, _id: &str, _url: &str) {}
+
+    fn push_clip(&mut self, rect: Rect) {
+        let transform = self.transform();
+        let Some(clip_path) = Self::to_sk_path(&Path::rect(rect)) else {
+            // Empty clip rect: nothing inside it is visible
+            if let Some(mask) = sk::Mask::new(self.pixmap.width(), self.pixmap.height()) {
+                self.clips.push(mask);
+            }
+            return;
+        };
+        let mask = match self.clips.last() {
+            Some(current) => {
+                let mut mask = current.clone();
+                mask.intersect_path(&clip_path, sk::FillRule::Winding, true, transform);
+                Some(mask)
+            }
+            None => sk::Mask::new(self.pixmap.width(), self.pixmap.height()).map(|mut mask| {
+                mask.fill_path(&clip_path, sk::FillRule::Winding, true, transform);
+                mask
+            }),
+        };
+        if let Some(mask) = mask {
+            self.clips.push(mask);
+        }
+    }
+
+    fn pop_clip(&mut self) {
+        self.clips.pop();
+    }
+
+    fn push_transform(&mut self, transform: Transform2D) {
+        let local = sk::Transform::from_row(
+            transform.a as f32,
+            transform.b as f32,
+            transform.c as f32,
+            transform.d as f32,
+            transform.e as f32,
+            transform.f as f32,
+        );
+        let combined = self.transform().pre_concat(local);
+        self.transforms.push(combined);
+    }
+
+    fn pop_transform(&mut self) {
+        self.transforms.pop();
+    }
+
+    fn push_layer(&mut self, opacity: f64) {
+        if let Some(layer) = sk::Pixmap::new(self.pixmap.width(), self.pixmap.height()) {
+            self.layers.push((layer, opacity));
+        }
+    }
+
+    fn pop_layer(&mut self) {
+        let Some((layer, opacity)) = self.layers.pop() else {
+            return;
+        };
+        let paint = sk::PixmapPaint {
+            opacity: opacity.clamp(0.0, 1.0) as f32,
+            ..Default::default()
+        };
+        self.target().draw_pixmap(
+            0,
+            0,
+            layer.as_ref(),
+            &paint,
+            sk::Transform::identity(),
+            None,
+        );
+    }
+
+    fn set_alpha(&mut self, alpha: f64) {
+        self.alpha = alpha;
+    }
+
+    fn save(&mut self) {
+        self.state_stack.push(RasterState {
+            transforms: self.transforms.len(),
+            clips: self.clips.len(),
+            alpha: self.alpha,
+        });
+    }
+
+    fn restore(&mut self) {
+        if let Some(state) = self.state_stack.pop() {
+            self.transforms.truncate(state.transforms);
+            self.clips.truncate(state.clips);
+            self.alpha = state.alpha;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pixel(backend: &RasterBackend, x: u32, y: u32) -> [u8; 4] {
+        let i = ((y * backend.pixel_size().0 + x) * 4) as usize;
+        backend.pixels()[i..i + 4].try_into().unwrap()
+    }
+
+    #[test]
+    fn test_raster_fill_clip_and_premultiplied_alpha() {
+        let mut backend = RasterBackend::new(40, 20, 2.0).unwrap();
+        backend.begin_frame(40.0, 20.0, 2.0);
+        assert_eq!(backend.pixel_size(), (80, 40));
+
+        backend.clear(Color::rgb(0, 0, 255));
+        backend.push_clip(Rect::new(0.0, 0.0, 20.0, 20.0));
+        backend.fill_rect(Rect::new(0.0, 0.0, 40.0, 20.0), Color::rgb(255, 0, 0));
+        backend.pop_clip();
+        backend.fill_rect(
+            Rect::new(30.0, 0.0, 10.0, 10.0),
+            Color::rgba(255, 255, 255, 128),
+        );
+        backend.end_frame();
+
+        // Clipped to the left half
+        assert_eq!(pixel(&backend, 10, 10), [255, 0, 0, 255]);
+        assert_eq!(pixel(&backend, 50, 30), [0, 0, 255, 255]);
+        // Half-transparent white over opaque blue stays opaque
+        let [r, g, b, a] = pixel(&backend, 70, 5);
+        assert_eq!(a, 255);
+        assert!((127..=129).contains(&r) && (127..=129).contains(&g) && b == 255);
+    }
+}
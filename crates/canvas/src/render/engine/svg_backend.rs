@@ -3,12 +3,107 @@
 //! Generates SVG output for headless chart rendering.
 //! Produces high-quality vector graphics suitable for print and scaling.
 
-use super::backend::{ImageInfo, RenderBackend, TextMetrics};
-use super::path::{Path, PathCommand};
+use super::backend::{ImageInfo, RenderBackend};
+use super::batch::RenderBatch;
+use super::commands::RenderCommand;
+use super::crisp::{crisp_stroke_coord, crisp_stroke_coords_xy};
+use super::diff::FrameElements;
+use super::fragment::FRAGMENT_ID_PREFIX;
+use super::path::{Path, PathCommand, arc_sweep};
+use super::text_metrics::baseline_shift_em;
 use super::types::{
-    Color, FillStyle, LineStyle, Point, Rect, TextAlign, TextBaseline, TextStyle, Transform2D,
+    Color, FillRule, FillStyle, LineCap, LineJoin, LineStyle, Point, Rect, TextAlign, TextStyle,
+    Transform2D,
 };
-use std::fmt::Write;
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Write};
+
+/// Escape XML special characters in text content and attribute values
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Kind of element being drawn, which picks its coordinate precision
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum PrecisionCategory {
+    /// Grid lines and price/time scales
+    Axis,
+    /// Main series and indicators
+    Series,
+    /// Drawings and curved lines, where rounding shows as kinks
+    Curve,
+    /// Everything else: legend, overlays, labels
+    #[default]
+    Other,
+}
+
+/// Decimal places written for coordinates, per [`PrecisionCategory`]
+///
+/// Only positions and sizes are affected; stroke widths, opacities and
+/// transforms keep their fixed precision.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SvgPrecision {
+    pub axis: u8,
+    pub series: u8,
+    pub curves: u8,
+    pub other: u8,
+}
+
+impl Default for SvgPrecision {
+    fn default() -> Self {
+        Self::uniform(2)
+    }
+}
+
+impl SvgPrecision {
+    /// The same number of decimals everywhere
+    pub fn uniform(decimals: u8) -> Self {
+        Self {
+            axis: decimals,
+            series: decimals,
+            curves: decimals,
+            other: decimals,
+        }
+    }
+
+    /// Whole pixels for axes and grid, one decimal for series, two for
+    /// curves and the rest
+    ///
+    /// Crisp 1px grid lines lose their half-pixel offset at zero decimals,
+    /// so they may draw two device pixels wide.
+    pub fn compact() -> Self {
+        Self {
+            axis: 0,
+            series: 1,
+            curves: 2,
+            other: 2,
+        }
+    }
+
+    /// Decimals for a category
+    pub fn decimals(&self, category: PrecisionCategory) -> usize {
+        let decimals = match category {
+            PrecisionCategory::Axis => self.axis,
+            PrecisionCategory::Series => self.series,
+            PrecisionCategory::Curve => self.curves,
+            PrecisionCategory::Other => self.other,
+        };
+        decimals as usize
+    }
+}
+
+/// A coordinate written with a fixed number of decimals
+#[derive(Clone, Copy)]
+struct Coord(f64, usize);
+
+impl fmt::Display for Coord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:.*}", self.1, self.0)
+    }
+}
 
 /// SVG render backend
 ///
@@ -20,8 +115,10 @@ pub struct SvgBackend {
     width: f64,
     /// Current height
     height: f64,
-    /// Device pixel ratio
-    dpr: f64,
+    /// Horizontal device pixel ratio
+    dpr_x: f64,
+    /// Vertical device pixel ratio
+    dpr_y: f64,
     /// State stack (for save/restore)
     state_stack: Vec<SvgState>,
     /// Current state
@@ -30,6 +127,20 @@ pub struct SvgBackend {
     defs: String,
     /// Next gradient ID
     next_gradient_id: u32,
+    /// Accessible name and description, emitted as `<title>`/`<desc>`
+    accessible: Option<(Option<String>, String)>,
+    /// Draw calls recorded for replay on another backend
+    recording: Option<RenderBatch>,
+    /// Recorded command index where each element starts
+    elements: Vec<(usize, String)>,
+    /// Wrap replaceable parts in `<g id>` groups
+    groups: bool,
+    /// Elements left out for NaN or infinite geometry
+    non_finite: usize,
+    /// Coordinate decimals per category
+    precision: SvgPrecision,
+    /// Category of what is being drawn
+    category: PrecisionCategory,
 }
 
 #[derive(Clone, Debug, Default)]
@@ -37,16 +148,25 @@ struct SvgState {
     transform: Option<Transform2D>,
     clip_path: Option<String>,
     alpha: f64,
+    /// Id of the filter applied to drawn elements
+    filter: Option<String>,
 }
 
 impl SvgBackend {
     /// Create a new SVG backend
     pub fn new(width: u32, height: u32, dpr: f64) -> Self {
+        Self::with_dpr_xy(width, height, dpr, dpr)
+    }
+
+    /// Create a backend with independent horizontal and vertical pixel
+    /// densities (e.g. 2x by 3x for a printer with non-square dots)
+    pub fn with_dpr_xy(width: u32, height: u32, dpr_x: f64, dpr_y: f64) -> Self {
         Self {
             content: String::with_capacity(65536),
             width: width as f64,
             height: height as f64,
-            dpr,
+            dpr_x,
+            dpr_y,
             state_stack: Vec::new(),
             state: SvgState {
                 alpha: 1.0,
@@ -54,70 +174,400 @@ impl SvgBackend {
             },
             defs: String::new(),
             next_gradient_id: 0,
+            accessible: None,
+            recording: None,
+            elements: Vec::new(),
+            groups: false,
+            non_finite: 0,
+            precision: SvgPrecision::default(),
+            category: PrecisionCategory::default(),
         }
     }
 
+    /// Set the coordinate decimals of each element category
+    pub fn set_precision(&mut self, precision: SvgPrecision) {
+        self.precision = precision;
+    }
+
+    /// Mark what the following draw calls are, until the next call
+    pub fn set_category(&mut self, category: PrecisionCategory) {
+        self.category = category;
+    }
+
+    /// Format a coordinate at the current category's precision
+    fn coord(&self, value: f64) -> Coord {
+        Coord(value, self.precision.decimals(self.category))
+    }
+
+    /// Also record every draw call as a [`RenderCommand`], so the frame can
+    /// be replayed on another backend (e.g. a rasterizer)
+    pub fn record_commands(&mut self) {
+        self.recording = Some(RenderBatch::new());
+    }
+
+    /// Take the commands recorded since [`Self::record_commands`]
+    pub fn take_commands(&mut self) -> RenderBatch {
+        self.recording.take().unwrap_or_default()
+    }
+
+    /// Start a named element: commands recorded from here on belong to
+    /// `id` until the next element starts. Does nothing unless recording.
+    pub fn begin_element(&mut self, id: &str) {
+        if let Some(recording) = &self.recording {
+            self.elements.push((recording.len(), id.to_string()));
+        }
+    }
+
+    /// Take the recorded commands grouped by element, in draw order
+    ///
+    /// Commands before the first element go to `"frame"`, and an element
+    /// started more than once gets all of its commands in one entry.
+    pub fn take_elements(&mut self) -> FrameElements {
+        let commands = self.take_commands().into_commands();
+        let mut starts = std::mem::take(&mut self.elements);
+        starts.insert(0, (0, "frame".to_string()));
+
+        let mut frame: FrameElements = Vec::new();
+        for (i, (start, id)) in starts.iter().enumerate() {
+            let end = starts.get(i + 1).map_or(commands.len(), |(next, _)| *next);
+            let part = &commands[*start..end];
+            match frame.iter_mut().find(|(existing, _)| existing == id) {
+                Some((_, element)) => element.extend_from_slice(part),
+                None if part.is_empty() && i == 0 => {}
+                None => frame.push((id.clone(), part.to_vec())),
+            }
+        }
+        frame
+    }
+
+    /// Wrap parts of the frame that change on their own, like the last bar
+    /// or the price tag, in `<g id="zc-...">` groups so they can be
+    /// replaced one by one (see [`SvgFragment`](super::SvgFragment))
+    pub fn mark_groups(&mut self) {
+        self.groups = true;
+    }
+
+    /// Whether [`Self::mark_groups`] is on
+    pub fn groups_marked(&self) -> bool {
+        self.groups
+    }
+
+    /// Open a replaceable group, closed by [`Self::end_group`]; does
+    /// nothing unless groups are marked
+    ///
+    /// Characters other than letters, digits, `-`, `_`, `:` and `.` in
+    /// `id` become `_`.
+    pub fn begin_group(&mut self, id: &str) {
+        if self.groups {
+            let id: String = id
+                .chars()
+                .map(|c| match c {
+                    'a'..='z' | 'A'..='Z' | '0'..='9' | '-' | '_' | ':' | '.' => c,
+                    _ => '_',
+                })
+                .collect();
+            writeln!(self.content, r#"<g id="{}{}">"#, FRAGMENT_ID_PREFIX, id).unwrap();
+        }
+    }
+
+    /// Close the group opened by [`Self::begin_group`]
+    pub fn end_group(&mut self) {
+        if self.groups {
+            writeln!(self.content, "</g>").unwrap();
+        }
+    }
+
+    /// Number of elements left out of the document so far because a
+    /// coordinate or size was NaN or infinite, which browsers refuse to
+    /// parse
+    pub fn non_finite_elements(&self) -> usize {
+        self.non_finite
+    }
+
+    /// Count an element with NaN or infinite geometry; true when it is to
+    /// be skipped
+    fn skip_non_finite(&mut self, finite: bool) -> bool {
+        if !finite {
+            self.non_finite += 1;
+        }
+        !finite
+    }
+
+    fn record(&mut self, cmd: impl FnOnce() -> RenderCommand) {
+        if let Some(recording) = &mut self.recording {
+            recording.push(cmd());
+        }
+    }
+
+    /// Set independent horizontal and vertical pixel densities
+    ///
+    /// `begin_frame` resets both axes to its single `dpr`; start the frame
+    /// with [`RenderBackend::begin_frame_xy`] to keep them apart.
+    pub fn set_dpr_xy(&mut self, dpr_x: f64, dpr_y: f64) {
+        self.dpr_x = dpr_x;
+        self.dpr_y = dpr_y;
+    }
+
+    /// Draw a line snapped to device pixels on each axis
+    ///
+    /// Horizontal lines snap with the vertical density and vertical lines
+    /// with the horizontal one. Odd device-pixel stroke widths sit on half
+    /// pixels, even ones on whole pixels.
+    pub fn crisp_line(&mut self, from: Point, to: Point, style: &LineStyle) {
+        let (x1, y1, x2, y2) = crisp_stroke_coords_xy(
+            from.x,
+            from.y,
+            to.x,
+            to.y,
+            style.width,
+            self.dpr_x,
+            self.dpr_y,
+        );
+        self.line(Point::new(x1, y1), Point::new(x2, y2), style);
+    }
+
+    /// Snap a rectangle's edges to the nearest device pixels (at least one
+    /// pixel in size)
+    pub fn snap_rect(&self, rect: Rect) -> Rect {
+        let x1 = (rect.x * self.dpr_x).round() / self.dpr_x;
+        let y1 = (rect.y * self.dpr_y).round() / self.dpr_y;
+        let x2 = ((rect.x + rect.width) * self.dpr_x).round() / self.dpr_x;
+        let y2 = ((rect.y + rect.height) * self.dpr_y).round() / self.dpr_y;
+        Rect::new(
+            x1,
+            y1,
+            (x2 - x1).max(1.0 / self.dpr_x),
+            (y2 - y1).max(1.0 / self.dpr_y),
+        )
+    }
+
+    /// Fill a rectangle snapped to device pixels
+    pub fn crisp_fill_rect(&mut self, rect: Rect, color: Color) {
+        let rect = self.snap_rect(rect);
+        self.fill_rect(rect, color);
+    }
+
+    /// Stroke a rectangle with each edge snapped like [`Self::crisp_line`]
+    pub fn crisp_stroke_rect(&mut self, rect: Rect, style: &LineStyle) {
+        let x1 = crisp_stroke_coord(rect.x, style.width, self.dpr_x);
+        let y1 = crisp_stroke_coord(rect.y, style.width, self.dpr_y);
+        let x2 = crisp_stroke_coord(rect.x + rect.width, style.width, self.dpr_x);
+        let y2 = crisp_stroke_coord(rect.y + rect.height, style.width, self.dpr_y);
+        self.stroke_rect(Rect::new(x1, y1, x2 - x1, y2 - y1), style);
+    }
+
+    /// Clip to a rectangle snapped to device pixels, so content clipped
+    /// at the edge ends on a pixel boundary instead of a blurred half pixel
+    pub fn push_crisp_clip(&mut self, rect: Rect) {
+        let rect = self.snap_rect(rect);
+        self.push_clip(rect);
+    }
+
+    /// Give the document an accessible name and text description
+    ///
+    /// They are written as `<title>` and `<desc>` right inside `<svg>`, which
+    /// is marked `role="img"` and points at them with `aria-labelledby` and
+    /// `aria-describedby`.
+    pub fn set_description(&mut self, title: Option<&str>, description: &str) {
+        self.accessible = Some((title.map(str::to_string), description.to_string()));
+    }
+
+    /// Start a group whose opacity pulses between 1 and `min_opacity`
+    /// every `period_secs`; close it with `pop_layer`
+    ///
+    /// Recorded commands see a plain layer at full opacity.
+    pub fn push_pulse_layer(&mut self, min_opacity: f64, period_secs: f64) {
+        self.record(|| RenderCommand::PushLayer { opacity: 1.0 });
+        writeln!(
+            self.content,
+            r#"<g><animate attributeName="opacity" values="1;{:.2};1" dur="{:.2}s" repeatCount="indefinite"/>"#,
+            min_opacity, period_secs
+        )
+        .unwrap();
+    }
+
     /// Get the SVG document as a string
+    ///
+    /// The document keeps the CSS size. With different horizontal and
+    /// vertical densities the viewBox spans the device buffer
+    /// (`width * dpr_x` by `height * dpr_y`) and the content is scaled into
+    /// it.
     pub fn to_svg(&self) -> String {
         let mut svg = String::with_capacity(self.content.len() + 512);
+        let anisotropic = self.dpr_x != self.dpr_y;
+        let (view_width, view_height, aspect) = if anisotropic {
+            (
+                self.width * self.dpr_x,
+                self.height * self.dpr_y,
+                r#" preserveAspectRatio="none""#,
+            )
+        } else {
+            (self.width, self.height, "")
+        };
+        let (open, close) = if anisotropic {
+            (
+                format!(r#"<g transform="scale({},{})">"#, self.dpr_x, self.dpr_y) + "\n",
+                "</g>\n",
+            )
+        } else {
+            (String::new(), "")
+        };
+        let (aria, accessible) = match &self.accessible {
+            Some((title, desc)) => {
+                let mut aria = String::from(r#" role="img""#);
+                let mut elements = String::new();
+                if let Some(title) = title {
+                    aria.push_str(r#" aria-labelledby="chart-title""#);
+                    writeln!(
+                        elements,
+                        r#"<title id="chart-title">{}</title>"#,
+                        escape_xml(title)
+                    )
+                    .unwrap();
+                }
+                aria.push_str(r#" aria-describedby="chart-desc""#);
+                writeln!(
+                    elements,
+                    r#"<desc id="chart-desc">{}</desc>"#,
+                    escape_xml(desc)
+                )
+                .unwrap();
+                (aria, elements)
+            }
+            None => (String::new(), String::new()),
+        };
 
         writeln!(
             svg,
             r#"<?xml version="1.0" encoding="UTF-8"?>
 <svg xmlns="http://www.w3.org/2000/svg"
      xmlns:xlink="http://www.w3.org/1999/xlink"
-     width="{}" height="{}" viewBox="0 0 {} {}">
-<defs>
+     width="{}" height="{}" viewBox="0 0 {} {}"{}{}>
+{}<defs>
 {}
 </defs>
-{}</svg>"#,
-            self.width, self.height, self.width, self.height, self.defs, self.content
+{}{}{}</svg>"#,
+            self.width,
+            self.height,
+            view_width,
+            view_height,
+            aspect,
+            aria,
+            accessible,
+            self.defs,
+            open,
+            self.content,
+            close
         )
         .unwrap();
 
         svg
     }
 
-    /// Convert color to CSS string
-    fn color_to_css(color: Color) -> String {
+    /// Paint attributes for a color, e.g. `fill="#26a69a" fill-opacity="0.25"`
+    ///
+    /// Alpha goes into a separate opacity attribute instead of an `rgba()`
+    /// value, so translucent paints share the same color string.
+    fn paint_attrs(color_attr: &str, opacity_attr: &str, color: Color) -> String {
+        let mut attrs = format!(
+            r##"{}="#{:02x}{:02x}{:02x}""##,
+            color_attr, color.r, color.g, color.b
+        );
         if color.a < 255 {
-            format!(
-                "rgba({},{},{},{})",
-                color.r,
-                color.g,
-                color.b,
+            write!(
+                attrs,
+                r#" {}="{:.2}""#,
+                opacity_attr,
                 color.a as f64 / 255.0
             )
-        } else {
-            format!("#{:02x}{:02x}{:02x}", color.r, color.g, color.b)
+            .unwrap();
         }
+        attrs
+    }
+
+    fn fill_paint(color: Color) -> String {
+        Self::paint_attrs("fill", "fill-opacity", color)
     }
 
     /// Convert path to SVG path data
-    fn path_to_d(path: &Path) -> String {
+    fn path_to_d(&self, path: &Path) -> String {
+        let c = |value: f64| self.coord(value);
         let mut d = String::new();
 
+        // Elliptical arc as `A` segments, joined to an open subpath by a
+        // line like a canvas arc. SVG draws nothing for an arc whose ends
+        // meet, so turns past a half circle are split in two.
+        let arc = |d: &mut String,
+                   open: bool,
+                   center: Point,
+                   (rx, ry): (f64, f64),
+                   rotation: f64,
+                   (start, end, ccw): (f64, f64, bool)| {
+            let (sin_r, cos_r) = rotation.sin_cos();
+            let at = |t: f64| {
+                let (x, y) = (rx * t.cos(), ry * t.sin());
+                (
+                    center.x + x * cos_r - y * sin_r,
+                    center.y + x * sin_r + y * cos_r,
+                )
+            };
+            let (x, y) = at(start);
+            write!(d, "{}{} {} ", if open { 'L' } else { 'M' }, c(x), c(y)).unwrap();
+
+            let sweep = arc_sweep(start, end, ccw);
+            let pieces = if sweep == 0.0 {
+                0
+            } else if sweep.abs() > std::f64::consts::PI {
+                2
+            } else {
+                1
+            };
+            for i in 1..=pieces {
+                let (x, y) = at(start + sweep * i as f64 / pieces as f64);
+                write!(
+                    d,
+                    "A{} {} {:.2} 0 {} {} {} ",
+                    c(rx),
+                    c(ry),
+                    rotation.to_degrees(),
+                    u8::from(sweep > 0.0),
+                    c(x),
+                    c(y)
+                )
+                .unwrap();
+            }
+        };
+
+        let mut open = false;
         for cmd in path.commands() {
             match cmd {
                 PathCommand::MoveTo(p) => {
-                    write!(d, "M{:.2} {:.2} ", p.x, p.y).unwrap();
+                    write!(d, "M{} {} ", c(p.x), c(p.y)).unwrap();
                 }
                 PathCommand::LineTo(p) => {
-                    write!(d, "L{:.2} {:.2} ", p.x, p.y).unwrap();
+                    write!(d, "L{} {} ", c(p.x), c(p.y)).unwrap();
                 }
                 PathCommand::QuadTo { control, end } => {
                     write!(
                         d,
-                        "Q{:.2} {:.2} {:.2} {:.2} ",
-                        control.x, control.y, end.x, end.y
+                        "Q{} {} {} {} ",
+                        c(control.x),
+                        c(control.y),
+                        c(end.x),
+                        c(end.y)
                     )
                     .unwrap();
                 }
                 PathCommand::CubicTo { c1, c2, end } => {
                     write!(
                         d,
-                        "C{:.2} {:.2} {:.2} {:.2} {:.2} {:.2} ",
-                        c1.x, c1.y, c2.x, c2.y, end.x, end.y
+                        "C{} {} {} {} {} {} ",
+                        c(c1.x),
+                        c(c1.y),
+                        c(c2.x),
+                        c(c2.y),
+                        c(end.x),
+                        c(end.y)
                     )
                     .unwrap();
                 }
@@ -127,25 +577,14 @@ impl SvgBackend {
                     start,
                     end,
                     ccw,
-                } => {
-                    // Convert arc to SVG arc
-                    let start_x = center.x + radius * start.cos();
-                    let start_y = center.y + radius * start.sin();
-                    let end_x = center.x + radius * end.cos();
-                    let end_y = center.y + radius * end.sin();
-                    let large_arc = if (end - start).abs() > std::f64::consts::PI {
-                        1
-                    } else {
-                        0
-                    };
-                    let sweep = if *ccw { 0 } else { 1 };
-                    write!(
-                        d,
-                        "M{:.2} {:.2} A{:.2} {:.2} 0 {} {} {:.2} {:.2} ",
-                        start_x, start_y, radius, radius, large_arc, sweep, end_x, end_y
-                    )
-                    .unwrap();
-                }
+                } => arc(
+                    &mut d,
+                    open,
+                    *center,
+                    (*radius, *radius),
+                    0.0,
+                    (*start, *end, *ccw),
+                ),
                 PathCommand::Ellipse {
                     center,
                     rx,
@@ -154,49 +593,32 @@ impl SvgBackend {
                     start,
                     end,
                     ccw,
-                } => {
-                    // Simplified ellipse to arc conversion
-                    let cos_r = rotation.cos();
-                    let sin_r = rotation.sin();
-                    let start_x = center.x + rx * start.cos() * cos_r - ry * start.sin() * sin_r;
-                    let start_y = center.y + rx * start.cos() * sin_r + ry * start.sin() * cos_r;
-                    let end_x = center.x + rx * end.cos() * cos_r - ry * end.sin() * sin_r;
-                    let end_y = center.y + rx * end.cos() * sin_r + ry * end.sin() * cos_r;
-                    let large_arc = if (end - start).abs() > std::f64::consts::PI {
-                        1
-                    } else {
-                        0
-                    };
-                    let sweep = if *ccw { 0 } else { 1 };
-                    write!(
-                        d,
-                        "M{:.2} {:.2} A{:.2} {:.2} {:.2} {} {} {:.2} {:.2} ",
-                        start_x,
-                        start_y,
-                        rx,
-                        ry,
-                        rotation.to_degrees(),
-                        large_arc,
-                        sweep,
-                        end_x,
-                        end_y
-                    )
-                    .unwrap();
-                }
+                } => arc(
+                    &mut d,
+                    open,
+                    *center,
+                    (*rx, *ry),
+                    *rotation,
+                    (*start, *end, *ccw),
+                ),
                 PathCommand::Close => {
                     d.push_str("Z ");
                 }
             }
+            open = !matches!(cmd, PathCommand::Close);
         }
 
         d.trim_end().to_string()
     }
 
     /// Convert line style to SVG attributes
-    fn line_style_attrs(style: &LineStyle) -> String {
+    ///
+    /// Open strokes (lines, polylines and paths) always state their cap and
+    /// join; closed shapes only when they differ from SVG's butt/miter.
+    fn line_style_attrs(style: &LineStyle, open: bool) -> String {
         let mut attrs = format!(
-            r#"stroke="{}" stroke-width="{:.2}""#,
-            Self::color_to_css(style.color),
+            r#"{} stroke-width="{:.2}""#,
+            Self::paint_attrs("stroke", "stroke-opacity", style.color),
             style.width
         );
 
@@ -207,24 +629,11 @@ impl SvgBackend {
             }
         }
 
-        match style.cap {
-            super::types::LineCap::Butt => {}
-            super::types::LineCap::Round => {
-                attrs.push_str(r#" stroke-linecap="round""#);
-            }
-            super::types::LineCap::Square => {
-                attrs.push_str(r#" stroke-linecap="square""#);
-            }
+        if open || style.cap != LineCap::Butt {
+            write!(attrs, r#" stroke-linecap="{}""#, style.cap.as_str()).unwrap();
         }
-
-        match style.join {
-            super::types::LineJoin::Miter => {}
-            super::types::LineJoin::Round => {
-                attrs.push_str(r#" stroke-linejoin="round""#);
-            }
-            super::types::LineJoin::Bevel => {
-                attrs.push_str(r#" stroke-linejoin="bevel""#);
-            }
+        if open || style.join != LineJoin::Miter {
+            write!(attrs, r#" stroke-linejoin="{}""#, style.join.as_str()).unwrap();
         }
 
         attrs
@@ -233,9 +642,7 @@ impl SvgBackend {
     /// Get fill attribute for FillStyle
     fn fill_attr(&mut self, style: &FillStyle) -> String {
         match style {
-            FillStyle::Solid(color) => {
-                format!(r#"fill="{}""#, Self::color_to_css(*color))
-            }
+            FillStyle::Solid(color) => Self::fill_paint(*color),
             FillStyle::LinearGradient { start, end, stops } => {
                 let id = self.next_gradient_id;
                 self.next_gradient_id += 1;
@@ -252,9 +659,9 @@ impl SvgBackend {
                 for (offset, color) in stops {
                     write!(
                         gradient,
-                        r#"<stop offset="{:.0}%" stop-color="{}"/>"#,
+                        r#"<stop offset="{:.0}%" {}/>"#,
                         offset * 100.0,
-                        Self::color_to_css(*color)
+                        Self::paint_attrs("stop-color", "stop-opacity", *color)
                     )
                     .unwrap();
                 }
@@ -283,9 +690,9 @@ impl SvgBackend {
                 for (offset, color) in stops {
                     write!(
                         gradient,
-                        r#"<stop offset="{:.0}%" stop-color="{}"/>"#,
+                        r#"<stop offset="{:.0}%" {}/>"#,
                         offset * 100.0,
-                        Self::color_to_css(*color)
+                        Self::paint_attrs("stop-color", "stop-opacity", *color)
                     )
                     .unwrap();
                 }
@@ -310,21 +717,29 @@ impl SvgBackend {
         }
     }
 
-    /// Get opacity attribute
-    fn opacity_attr(&self) -> String {
+    /// Get opacity and filter attributes
+    fn effect_attrs(&self) -> String {
+        let mut attrs = String::new();
         if self.state.alpha < 1.0 {
-            format!(r#" opacity="{:.2}""#, self.state.alpha)
-        } else {
-            String::new()
+            write!(attrs, r#" opacity="{:.2}""#, self.state.alpha).unwrap();
         }
+        if let Some(filter) = &self.state.filter {
+            write!(attrs, r#" filter="url(#{})""#, filter).unwrap();
+        }
+        attrs
     }
 }
 
 impl RenderBackend for SvgBackend {
     fn begin_frame(&mut self, width: f64, height: f64, dpr: f64) {
+        self.begin_frame_xy(width, height, dpr, dpr);
+    }
+
+    fn begin_frame_xy(&mut self, width: f64, height: f64, dpr_x: f64, dpr_y: f64) {
         self.width = width;
         self.height = height;
-        self.dpr = dpr;
+        self.dpr_x = dpr_x;
+        self.dpr_y = dpr_y;
         self.content.clear();
         self.defs.clear();
         self.state_stack.clear();
@@ -333,6 +748,11 @@ impl RenderBackend for SvgBackend {
             ..Default::default()
         };
         self.next_gradient_id = 0;
+        self.category = PrecisionCategory::default();
+        if let Some(recording) = &mut self.recording {
+            recording.clear();
+        }
+        self.elements.clear();
     }
 
     fn end_frame(&mut self) {
@@ -340,7 +760,11 @@ impl RenderBackend for SvgBackend {
     }
 
     fn dpr(&self) -> f64 {
-        self.dpr
+        self.dpr_x.max(self.dpr_y)
+    }
+
+    fn dpr_xy(&self) -> (f64, f64) {
+        (self.dpr_x, self.dpr_y)
     }
 
     fn size(&self) -> (f64, f64) {
@@ -348,111 +772,172 @@ impl RenderBackend for SvgBackend {
     }
 
     fn clear(&mut self, color: Color) {
+        let rect = Rect::new(0.0, 0.0, self.width, self.height);
+        self.record(|| RenderCommand::FillRect { rect, color });
         writeln!(
             self.content,
-            r#"<rect x="0" y="0" width="{}" height="{}" fill="{}"/>"#,
+            r#"<rect x="0" y="0" width="{}" height="{}" {}/>"#,
             self.width,
             self.height,
-            Self::color_to_css(color)
+            Self::fill_paint(color)
         )
         .unwrap();
     }
 
     fn clear_rect(&mut self, rect: Rect) {
+        if self.skip_non_finite(rect.is_finite()) {
+            return;
+        }
         // SVG doesn't have clear_rect, but we can draw a rect with background
         writeln!(
             self.content,
-            r#"<rect x="{:.2}" y="{:.2}" width="{:.2}" height="{:.2}" fill="none"/>"#,
-            rect.x, rect.y, rect.width, rect.height
+            r#"<rect x="{}" y="{}" width="{}" height="{}" fill="none"/>"#,
+            self.coord(rect.x),
+            self.coord(rect.y),
+            self.coord(rect.width),
+            self.coord(rect.height)
         )
         .unwrap();
     }
 
     fn fill_path(&mut self, path: &Path, style: &FillStyle) {
-        let d = Self::path_to_d(path);
+        if self.skip_non_finite(path.is_finite()) {
+            return;
+        }
+        self.record(|| RenderCommand::FillPath {
+            path: path.clone(),
+            style: style.clone(),
+        });
+        let d = self.path_to_d(path);
         let fill = self.fill_attr(style);
+        let rule = match path.fill_rule() {
+            FillRule::NonZero => "",
+            FillRule::EvenOdd => r#" fill-rule="evenodd""#,
+        };
         let transform = self.transform_attr();
-        let opacity = self.opacity_attr();
+        let effects = self.effect_attrs();
 
         writeln!(
             self.content,
-            r#"<path d="{}" {} stroke="none"{}{}/>""#,
-            d, fill, transform, opacity
+            r#"<path d="{}" {}{} stroke="none"{}{}/>""#,
+            d, fill, rule, transform, effects
         )
         .unwrap();
     }
 
     fn stroke_path(&mut self, path: &Path, style: &LineStyle) {
-        let d = Self::path_to_d(path);
-        let stroke = Self::line_style_attrs(style);
+        if self.skip_non_finite(path.is_finite() && style.width.is_finite()) {
+            return;
+        }
+        self.record(|| RenderCommand::StrokePath {
+            path: path.clone(),
+            style: style.clone(),
+        });
+        let d = self.path_to_d(path);
+        let stroke = Self::line_style_attrs(style, true);
         let transform = self.transform_attr();
-        let opacity = self.opacity_attr();
+        let effects = self.effect_attrs();
 
         writeln!(
             self.content,
             r#"<path d="{}" {} fill="none"{}{}/>""#,
-            d, stroke, transform, opacity
+            d, stroke, transform, effects
         )
         .unwrap();
     }
 
     fn fill_rect(&mut self, rect: Rect, color: Color) {
+        if self.skip_non_finite(rect.is_finite()) {
+            return;
+        }
+        self.record(|| RenderCommand::FillRect { rect, color });
         let transform = self.transform_attr();
-        let opacity = self.opacity_attr();
+        let effects = self.effect_attrs();
 
         writeln!(
             self.content,
-            r#"<rect x="{:.2}" y="{:.2}" width="{:.2}" height="{:.2}" fill="{}"{}{}/>""#,
-            rect.x,
-            rect.y,
-            rect.width,
-            rect.height,
-            Self::color_to_css(color),
+            r#"<rect x="{}" y="{}" width="{}" height="{}" {}{}{}/>""#,
+            self.coord(rect.x),
+            self.coord(rect.y),
+            self.coord(rect.width),
+            self.coord(rect.height),
+            Self::fill_paint(color),
             transform,
-            opacity
+            effects
         )
         .unwrap();
     }
 
     fn stroke_rect(&mut self, rect: Rect, style: &LineStyle) {
-        let stroke = Self::line_style_attrs(style);
+        if self.skip_non_finite(rect.is_finite() && style.width.is_finite()) {
+            return;
+        }
+        self.record(|| RenderCommand::StrokeRect {
+            rect,
+            style: style.clone(),
+        });
+        let stroke = Self::line_style_attrs(style, false);
         let transform = self.transform_attr();
-        let opacity = self.opacity_attr();
+        let effects = self.effect_attrs();
 
         writeln!(
             self.content,
-            r#"<rect x="{:.2}" y="{:.2}" width="{:.2}" height="{:.2}" {} fill="none"{}{}/>""#,
-            rect.x, rect.y, rect.width, rect.height, stroke, transform, opacity
+            r#"<rect x="{}" y="{}" width="{}" height="{}" {} fill="none"{}{}/>""#,
+            self.coord(rect.x),
+            self.coord(rect.y),
+            self.coord(rect.width),
+            self.coord(rect.height),
+            stroke,
+            transform,
+            effects
         )
         .unwrap();
     }
 
     fn line(&mut self, from: Point, to: Point, style: &LineStyle) {
-        let stroke = Self::line_style_attrs(style);
+        if self.skip_non_finite(from.is_finite() && to.is_finite() && style.width.is_finite()) {
+            return;
+        }
+        self.record(|| RenderCommand::Line {
+            from,
+            to,
+            style: style.clone(),
+        });
+        let stroke = Self::line_style_attrs(style, true);
         let transform = self.transform_attr();
-        let opacity = self.opacity_attr();
+        let effects = self.effect_attrs();
 
         writeln!(
             self.content,
-            r#"<line x1="{:.2}" y1="{:.2}" x2="{:.2}" y2="{:.2}" {}{}{}/>""#,
-            from.x, from.y, to.x, to.y, stroke, transform, opacity
+            r#"<line x1="{}" y1="{}" x2="{}" y2="{}" {}{}{}/>""#,
+            self.coord(from.x),
+            self.coord(from.y),
+            self.coord(to.x),
+            self.coord(to.y),
+            stroke,
+            transform,
+            effects
         )
         .unwrap();
     }
 
     fn polyline(&mut self, points: &[Point], style: &LineStyle) {
-        if points.len() < 2 {
+        if points.len() < 2 || self.skip_non_finite(points.iter().all(|p| p.is_finite())) {
             return;
         }
+        self.record(|| RenderCommand::Polyline {
+            points: points.to_vec(),
+            style: style.clone(),
+        });
 
         let pts: Vec<String> = points
             .iter()
-            .map(|p| format!("{:.2},{:.2}", p.x, p.y))
+            .map(|p| format!("{},{}", self.coord(p.x), self.coord(p.y)))
             .collect();
 
-        let stroke = Self::line_style_attrs(style);
+        let stroke = Self::line_style_attrs(style, true);
         let transform = self.transform_attr();
-        let opacity = self.opacity_attr();
+        let effects = self.effect_attrs();
 
         writeln!(
             self.content,
@@ -460,42 +945,76 @@ impl RenderBackend for SvgBackend {
             pts.join(" "),
             stroke,
             transform,
-            opacity
+            effects
         )
         .unwrap();
     }
 
     fn fill_circle(&mut self, center: Point, radius: f64, color: Color) {
+        if self.skip_non_finite(center.is_finite() && radius.is_finite()) {
+            return;
+        }
+        self.record(|| RenderCommand::FillCircle {
+            center,
+            radius,
+            color,
+        });
         let transform = self.transform_attr();
-        let opacity = self.opacity_attr();
+        let effects = self.effect_attrs();
 
         writeln!(
             self.content,
-            r#"<circle cx="{:.2}" cy="{:.2}" r="{:.2}" fill="{}" stroke="none"{}{}/>""#,
-            center.x,
-            center.y,
-            radius,
-            Self::color_to_css(color),
+            r#"<circle cx="{}" cy="{}" r="{}" {} stroke="none"{}{}/>""#,
+            self.coord(center.x),
+            self.coord(center.y),
+            self.coord(radius),
+            Self::fill_paint(color),
             transform,
-            opacity
+            effects
         )
         .unwrap();
     }
 
     fn stroke_circle(&mut self, center: Point, radius: f64, style: &LineStyle) {
-        let stroke = Self::line_style_attrs(style);
+        if self.skip_non_finite(center.is_finite() && radius.is_finite() && style.width.is_finite())
+        {
+            return;
+        }
+        self.record(|| RenderCommand::StrokeCircle {
+            center,
+            radius,
+            style: style.clone(),
+        });
+        let stroke = Self::line_style_attrs(style, false);
         let transform = self.transform_attr();
-        let opacity = self.opacity_attr();
+        let effects = self.effect_attrs();
 
         writeln!(
             self.content,
-            r#"<circle cx="{:.2}" cy="{:.2}" r="{:.2}" {} fill="none"{}{}/>""#,
-            center.x, center.y, radius, stroke, transform, opacity
+            r#"<circle cx="{}" cy="{}" r="{}" {} fill="none"{}{}/>""#,
+            self.coord(center.x),
+            self.coord(center.y),
+            self.coord(radius),
+            stroke,
+            transform,
+            effects
         )
         .unwrap();
     }
 
     fn fill_ellipse(&mut self, center: Point, rx: f64, ry: f64, rotation: f64, color: Color) {
+        if self
+            .skip_non_finite(center.is_finite() && [rx, ry, rotation].iter().all(|v| v.is_finite()))
+        {
+            return;
+        }
+        self.record(|| RenderCommand::FillEllipse {
+            center,
+            rx,
+            ry,
+            rotation,
+            color,
+        });
         let mut transform = self.transform_attr();
         if rotation != 0.0 {
             write!(
@@ -507,13 +1026,20 @@ impl RenderBackend for SvgBackend {
             )
             .unwrap();
         }
-        let opacity = self.opacity_attr();
+        let effects = self.effect_attrs();
 
         writeln!(
             self.content,
-            r#"<ellipse cx="{:.2}" cy="{:.2}" rx="{:.2}" ry="{:.2}" fill="{}" stroke="none"{}{}/>""#,
-            center.x, center.y, rx, ry, Self::color_to_css(color), transform, opacity
-        ).unwrap();
+            r#"<ellipse cx="{}" cy="{}" rx="{}" ry="{}" {} stroke="none"{}{}/>""#,
+            self.coord(center.x),
+            self.coord(center.y),
+            self.coord(rx),
+            self.coord(ry),
+            Self::fill_paint(color),
+            transform,
+            effects
+        )
+        .unwrap();
     }
 
     fn stroke_ellipse(
@@ -524,7 +1050,22 @@ impl RenderBackend for SvgBackend {
         rotation: f64,
         style: &LineStyle,
     ) {
-        let stroke = Self::line_style_attrs(style);
+        if self.skip_non_finite(
+            center.is_finite()
+                && [rx, ry, rotation, style.width]
+                    .iter()
+                    .all(|v| v.is_finite()),
+        ) {
+            return;
+        }
+        self.record(|| RenderCommand::StrokeEllipse {
+            center,
+            rx,
+            ry,
+            rotation,
+            style: style.clone(),
+        });
+        let stroke = Self::line_style_attrs(style, false);
         let mut transform = self.transform_attr();
         if rotation != 0.0 {
             write!(
@@ -536,32 +1077,48 @@ impl RenderBackend for SvgBackend {
             )
             .unwrap();
         }
-        let opacity = self.opacity_attr();
+        let effects = self.effect_attrs();
 
         writeln!(
             self.content,
-            r#"<ellipse cx="{:.2}" cy="{:.2}" rx="{:.2}" ry="{:.2}" {} fill="none"{}{}/>""#,
-            center.x, center.y, rx, ry, stroke, transform, opacity
+            r#"<ellipse cx="{}" cy="{}" rx="{}" ry="{}" {} fill="none"{}{}/>""#,
+            self.coord(center.x),
+            self.coord(center.y),
+            self.coord(rx),
+            self.coord(ry),
+            stroke,
+            transform,
+            effects
         )
         .unwrap();
     }
 
     fn text(&mut self, text: &str, pos: Point, style: &TextStyle) {
+        if self.skip_non_finite(pos.is_finite() && style.font_size.is_finite()) {
+            return;
+        }
+        self.record(|| RenderCommand::Text {
+            text: text.to_string(),
+            pos,
+            style: style.clone(),
+        });
         let anchor = match style.align {
             TextAlign::Left => "start",
             TextAlign::Center => "middle",
             TextAlign::Right => "end",
         };
 
-        let baseline = match style.baseline {
-            TextBaseline::Top => "hanging",
-            TextBaseline::Middle => "central",
-            TextBaseline::Bottom => "text-after-edge",
-            TextBaseline::Alphabetic => "alphabetic",
+        // A dy shift rather than dominant-baseline, which PDF converters
+        // and several rasterizers ignore, leaving labels on the baseline
+        let shift = baseline_shift_em(style.baseline);
+        let dy = if shift == 0.0 {
+            String::new()
+        } else {
+            format!(r#" dy="{}em""#, shift)
         };
 
         let transform = self.transform_attr();
-        let opacity = self.opacity_attr();
+        let effects = self.effect_attrs();
 
         let font_weight = match style.font_weight {
             super::types::FontWeight::Bold => r#" font-weight="bold""#,
@@ -569,60 +1126,63 @@ impl RenderBackend for SvgBackend {
             super::types::FontWeight::Normal => "",
         };
 
-        // Escape XML special characters
-        let escaped = text
-            .replace('&', "&amp;")
-            .replace('<', "&lt;")
-            .replace('>', "&gt;")
-            .replace('"', "&quot;");
+        let escaped = escape_xml(text);
 
         writeln!(
             self.content,
-            r#"<text x="{:.2}" y="{:.2}" fill="{}" font-family="{}" font-size="{:.1}" text-anchor="{}" dominant-baseline="{}"{}{}{}>{}</text>"#,
-            pos.x, pos.y,
-            Self::color_to_css(style.color),
+            r#"<text x="{}" y="{}"{} {} font-family="{}" font-size="{:.1}" text-anchor="{}"{}{}{}>{}</text>"#,
+            self.coord(pos.x), self.coord(pos.y),
+            dy,
+            Self::fill_paint(style.color),
             style.font_family,
             style.font_size,
             anchor,
-            baseline,
             font_weight,
             transform,
-            opacity,
+            effects,
             escaped
         ).unwrap();
     }
 
-    fn measure_text(&self, text: &str, style: &TextStyle) -> TextMetrics {
-        // Approximate text measurement (SVG is typically rendered client-side)
-        let char_width = style.font_size * 0.6;
-        let width = text.len() as f64 * char_width;
-
-        TextMetrics {
-            width,
-            height: style.font_size,
-            ascent: style.font_size * 0.8,
-            descent: style.font_size * 0.2,
-        }
-    }
-
     fn image(&mut self, id: &str, src: Option<Rect>, dst: Rect) {
+        if self.skip_non_finite(dst.is_finite()) {
+            return;
+        }
+        self.record(|| RenderCommand::Image {
+            id: id.to_string(),
+            src,
+            dst,
+        });
         // SVG xlink:href for images
+        let href = escape_xml(id);
         let transform = self.transform_attr();
-        let opacity = self.opacity_attr();
+        let effects = self.effect_attrs();
 
         if let Some(_src_rect) = src {
             // Clip to source rectangle (would need clipPath)
             writeln!(
                 self.content,
-                r#"<image x="{:.2}" y="{:.2}" width="{:.2}" height="{:.2}" href="{}"{}{}/>""#,
-                dst.x, dst.y, dst.width, dst.height, id, transform, opacity
+                r#"<image x="{}" y="{}" width="{}" height="{}" href="{}"{}{}/>""#,
+                self.coord(dst.x),
+                self.coord(dst.y),
+                self.coord(dst.width),
+                self.coord(dst.height),
+                href,
+                transform,
+                effects
             )
             .unwrap();
         } else {
             writeln!(
                 self.content,
-                r#"<image x="{:.2}" y="{:.2}" width="{:.2}" height="{:.2}" href="{}"{}{}/>""#,
-                dst.x, dst.y, dst.width, dst.height, id, transform, opacity
+                r#"<image x="{}" y="{}" width="{}" height="{}" href="{}"{}{}/>""#,
+                self.coord(dst.x),
+                self.coord(dst.y),
+                self.coord(dst.width),
+                self.coord(dst.height),
+                href,
+                transform,
+                effects
             )
             .unwrap();
         }
@@ -638,25 +1198,47 @@ impl RenderBackend for SvgBackend {
     }
 
     fn push_clip(&mut self, rect: Rect) {
+        // Still open the group pop_clip closes, clipping everything away
+        let rect = if self.skip_non_finite(rect.is_finite()) {
+            Rect::ZERO
+        } else {
+            rect
+        };
+        self.record(|| RenderCommand::PushClip { rect });
         let clip_id = format!("clip{}", self.next_gradient_id);
         self.next_gradient_id += 1;
 
         writeln!(
             self.defs,
-            r#"<clipPath id="{}"><rect x="{:.2}" y="{:.2}" width="{:.2}" height="{:.2}"/></clipPath>"#,
-            clip_id, rect.x, rect.y, rect.width, rect.height
-        ).unwrap();
+            r#"<clipPath id="{}"><rect x="{}" y="{}" width="{}" height="{}"/></clipPath>"#,
+            clip_id,
+            self.coord(rect.x),
+            self.coord(rect.y),
+            self.coord(rect.width),
+            self.coord(rect.height)
+        )
+        .unwrap();
 
         writeln!(self.content, r#"<g clip-path="url(#{})">"#, clip_id).unwrap();
         self.state.clip_path = Some(clip_id);
     }
 
     fn pop_clip(&mut self) {
+        self.record(|| RenderCommand::PopClip);
         writeln!(self.content, "</g>").unwrap();
         self.state.clip_path = None;
     }
 
     fn push_transform(&mut self, transform: Transform2D) {
+        // Identity rather than nothing, as pop_transform closes the group
+        let t = transform;
+        let finite = [t.a, t.b, t.c, t.d, t.e, t.f].iter().all(|v| v.is_finite());
+        let transform = if self.skip_non_finite(finite) {
+            Transform2D::IDENTITY
+        } else {
+            transform
+        };
+        self.record(|| RenderCommand::PushTransform { transform });
         writeln!(
             self.content,
             r#"<g transform="matrix({:.4},{:.4},{:.4},{:.4},{:.2},{:.2})">"#,
@@ -667,28 +1249,58 @@ impl RenderBackend for SvgBackend {
     }
 
     fn pop_transform(&mut self) {
+        self.record(|| RenderCommand::PopTransform);
         writeln!(self.content, "</g>").unwrap();
         self.state.transform = None;
     }
 
     fn push_layer(&mut self, opacity: f64) {
+        let opacity = if self.skip_non_finite(opacity.is_finite()) {
+            1.0
+        } else {
+            opacity
+        };
+        self.record(|| RenderCommand::PushLayer { opacity });
         writeln!(self.content, r#"<g opacity="{:.2}">"#, opacity).unwrap();
     }
 
     fn pop_layer(&mut self) {
+        self.record(|| RenderCommand::PopLayer);
         writeln!(self.content, "</g>").unwrap();
     }
 
     fn set_alpha(&mut self, alpha: f64) {
+        self.record(|| RenderCommand::SetAlpha { alpha });
         self.state.alpha = alpha;
     }
 
+    fn set_glow(&mut self, radius: f64) {
+        if radius <= 0.0 {
+            self.state.filter = None;
+            return;
+        }
+        // One filter per radius, over the whole canvas: a bounding-box
+        // region would be empty for straight vertical and horizontal lines
+        let id = format!("glow{}", (radius * 100.0).round() as u32);
+        if !self.defs.contains(&format!(r#"<filter id="{}""#, id)) {
+            writeln!(
+                self.defs,
+                r#"<filter id="{}" filterUnits="userSpaceOnUse" x="0" y="0" width="{}" height="{}"><feGaussianBlur in="SourceGraphic" stdDeviation="{:.2}" result="blur"/><feMerge><feMergeNode in="blur"/><feMergeNode in="SourceGraphic"/></feMerge></filter>"#,
+                id, self.width, self.height, radius
+            )
+            .unwrap();
+        }
+        self.state.filter = Some(id);
+    }
+
     fn save(&mut self) {
+        self.record(|| RenderCommand::Save);
         self.state_stack.push(self.state.clone());
         writeln!(self.content, "<g>").unwrap();
     }
 
     fn restore(&mut self) {
+        self.record(|| RenderCommand::Restore);
         if let Some(state) = self.state_stack.pop() {
             self.state = state;
         }
@@ -714,6 +1326,165 @@ mod tests {
         assert!(svg.contains("#ff0000"));
     }
 
+    #[test]
+    fn test_text_alignment_positions_label_at_anchor() {
+        use super::super::text_metrics::{CAP_HEIGHT, DESCENT, UNITS_PER_EM};
+        use super::super::types::TextBaseline;
+
+        let attr = |element: &str, name: &str| -> String {
+            let start = element.find(&format!(" {}=\"", name)).unwrap() + name.len() + 3;
+            let end = start + element[start..].find('"').unwrap();
+            element[start..end].to_string()
+        };
+        let render = |align: TextAlign, baseline: TextBaseline| {
+            let mut backend = SvgBackend::new(200, 100, 1.0);
+            backend.begin_frame(200.0, 100.0, 1.0);
+            let style = TextStyle {
+                font_size: 10.0,
+                align,
+                baseline,
+                ..TextStyle::default()
+            };
+            backend.text("1234", Point::new(100.0, 50.0), &style);
+            backend.end_frame();
+            let svg = backend.to_svg();
+            svg.lines()
+                .find(|l| l.starts_with("<text"))
+                .unwrap()
+                .to_string()
+        };
+
+        let label = render(TextAlign::Center, TextBaseline::Middle);
+        assert_eq!(attr(&label, "text-anchor"), "middle");
+        assert_eq!(attr(&label, "dy"), "0.359em");
+        assert!(!label.contains("dominant-baseline"));
+
+        // The element stays at the anchor; the digits' cap band is
+        // centered on it once shifted by dy
+        let (x, y): (f64, f64) = (
+            attr(&label, "x").parse().unwrap(),
+            attr(&label, "y").parse().unwrap(),
+        );
+        assert_eq!((x, y), (100.0, 50.0));
+        let dy: f64 = attr(&label, "dy").trim_end_matches("em").parse().unwrap();
+        let baseline = y + dy * 10.0;
+        let cap_top = baseline - CAP_HEIGHT / UNITS_PER_EM * 10.0;
+        assert!(((cap_top + baseline) / 2.0 - 50.0).abs() < 1e-9);
+
+        let top = render(TextAlign::Right, TextBaseline::Top);
+        assert_eq!(attr(&top, "text-anchor"), "end");
+        assert_eq!(attr(&top, "dy"), "0.718em");
+        let bottom = render(TextAlign::Left, TextBaseline::Bottom);
+        assert_eq!(attr(&bottom, "text-anchor"), "start");
+        assert_eq!(
+            attr(&bottom, "dy"),
+            format!("{}em", -DESCENT / UNITS_PER_EM)
+        );
+        let alphabetic = render(TextAlign::Left, TextBaseline::Alphabetic);
+        assert!(!alphabetic.contains(" dy="));
+    }
+
+    #[test]
+    fn test_svg_independent_axis_dpr() {
+        let mut backend = SvgBackend::new(400, 300, 1.0);
+        backend.begin_frame_xy(400.0, 300.0, 2.0, 3.0);
+        assert_eq!(backend.dpr_xy(), (2.0, 3.0));
+
+        let style = LineStyle::solid(Color::rgb(0, 255, 0), 1.0);
+        // Horizontal: y snaps to thirds, x ends to halves
+        backend.crisp_line(Point::new(10.2, 50.2), Point::new(90.7, 50.2), &style);
+        // Vertical: x snaps to halves, y ends to thirds
+        backend.crisp_line(Point::new(40.2, 10.2), Point::new(40.2, 90.5), &style);
+        backend.end_frame();
+
+        let svg = backend.to_svg();
+        assert!(svg.contains(
+            r#"width="400" height="300" viewBox="0 0 800 900" preserveAspectRatio="none""#
+        ));
+        assert!(svg.contains(r#"<g transform="scale(2,3)">"#));
+        // floor(50.2 * 3) / 3 + 0.5 / 3 = 50.1667
+        assert!(svg.contains(r#"<line x1="10.00" y1="50.17" x2="91.00" y2="50.17""#));
+        // 1px at dpr_x 2 covers two device pixels: round(40.2 * 2) / 2 = 40.0
+        assert!(svg.contains(r#"<line x1="40.00" y1="10.00" x2="40.00" y2="90.67""#));
+    }
+
+    #[test]
+    fn test_precision_per_category() {
+        use super::super::path::PathBuilder;
+        use crate::core::catmull_rom_spline;
+
+        let mut backend = SvgBackend::new(400, 300, 1.0);
+        backend.begin_frame(400.0, 300.0, 1.0);
+        backend.set_precision(SvgPrecision::compact());
+        let style = LineStyle::solid(Color::rgb(42, 46, 57), 1.0);
+
+        backend.set_category(PrecisionCategory::Axis);
+        backend.crisp_line(Point::new(0.0, 80.3), Point::new(400.0, 80.3), &style);
+        backend.crisp_line(Point::new(120.7, 0.0), Point::new(120.7, 300.0), &style);
+
+        backend.set_category(PrecisionCategory::Curve);
+        let spline = catmull_rom_spline(&[(10.0, 200.0), (60.0, 150.0), (110.0, 190.0)], 3);
+        let mut builder = PathBuilder::new();
+        builder.move_to(Point::new(spline[0].0, spline[0].1));
+        for &(x, y) in &spline[1..] {
+            builder.line_to(Point::new(x, y));
+        }
+        backend.stroke_path(&builder.build(), &style);
+        backend.end_frame();
+
+        let svg = backend.to_svg();
+        // Crisp 1px lines sit on half pixels: 80.5 and 120.5 round to even
+        assert!(
+            svg.contains(r#"<line x1="0" y1="80" x2="400" y2="80""#),
+            "{svg}"
+        );
+        assert!(
+            svg.contains(r#"<line x1="120" y1="0" x2="120" y2="300""#),
+            "{svg}"
+        );
+
+        let d = svg.split(r#"<path d=""#).nth(1).unwrap();
+        let d = &d[..d.find('"').unwrap()];
+        let numbers: Vec<&str> = d.split([' ', 'M', 'L']).filter(|n| !n.is_empty()).collect();
+        assert_eq!(numbers.len(), spline.len() * 2);
+        for n in numbers {
+            assert_eq!(n.split('.').nth(1).map(str::len), Some(2), "{d}");
+        }
+    }
+
+    #[test]
+    fn test_default_precision_is_two_decimals() {
+        let mut backend = SvgBackend::new(100, 100, 1.0);
+        backend.begin_frame(100.0, 100.0, 1.0);
+        backend.set_category(PrecisionCategory::Axis);
+        backend.fill_rect(Rect::new(1.0, 2.5, 3.0, 4.0), Color::rgb(255, 0, 0));
+        assert!(
+            backend
+                .to_svg()
+                .contains(r#"<rect x="1.00" y="2.50" width="3.00" height="4.00""#)
+        );
+    }
+
+    #[test]
+    fn test_glow_filter_shared_until_cleared() {
+        let mut backend = SvgBackend::new(100, 100, 1.0);
+        backend.begin_frame(100.0, 100.0, 1.0);
+        let style = LineStyle::solid(Color::rgb(0, 255, 245), 2.0);
+        backend.set_glow(3.0);
+        backend.line(Point::new(10.0, 0.0), Point::new(10.0, 100.0), &style);
+        backend.line(Point::new(20.0, 0.0), Point::new(20.0, 100.0), &style);
+        backend.set_glow(0.0);
+        backend.line(Point::new(30.0, 0.0), Point::new(30.0, 100.0), &style);
+
+        let svg = backend.to_svg();
+        assert_eq!(svg.matches(r#"<filter id="glow300""#).count(), 1);
+        assert!(svg.contains(r#"<feGaussianBlur in="SourceGraphic" stdDeviation="3.00""#));
+        let lines: Vec<&str> = svg.lines().filter(|l| l.starts_with("<line")).collect();
+        assert!(lines[0].contains(r#"filter="url(#glow300)""#));
+        assert!(lines[1].contains(r#"filter="url(#glow300)""#));
+        assert!(!lines[2].contains("filter="));
+    }
+
     #[test]
     fn test_svg_line() {
         let mut backend = SvgBackend::new(400, 300, 1.0);
@@ -729,4 +1500,74 @@ mod tests {
         assert!(svg.contains("<line"));
         assert!(svg.contains("stroke="));
     }
+
+    #[test]
+    fn test_svg_skips_non_finite_geometry() {
+        let mut backend = SvgBackend::new(400, 300, 1.0);
+        backend.begin_frame(400.0, 300.0, 1.0);
+        let style = LineStyle::solid(Color::rgb(0, 255, 0), 1.0);
+        backend.line(Point::new(0.0, f64::NAN), Point::new(100.0, 100.0), &style);
+        backend.fill_rect(Rect::new(0.0, 0.0, f64::INFINITY, 10.0), Color::BLACK);
+        backend.polyline(&[Point::new(0.0, 0.0), Point::new(f64::NAN, 1.0)], &style);
+        backend.push_clip(Rect::new(f64::NAN, 0.0, 10.0, 10.0));
+        backend.fill_circle(Point::new(5.0, 5.0), 2.0, Color::BLACK);
+        backend.pop_clip();
+        backend.end_frame();
+
+        let svg = backend.to_svg();
+        assert_eq!(backend.non_finite_elements(), 4);
+        assert!(!svg.contains("NaN") && !svg.contains("inf"));
+        assert!(!svg.contains("<line") && !svg.contains("<polyline"));
+        assert!(svg.contains("<circle"));
+    }
+
+    #[test]
+    fn test_svg_open_strokes_emit_cap_and_join() {
+        let mut backend = SvgBackend::new(400, 300, 1.0);
+        backend.begin_frame(400.0, 300.0, 1.0);
+        let zigzag = [
+            Point::new(0.0, 0.0),
+            Point::new(50.0, 100.0),
+            Point::new(100.0, 0.0),
+        ];
+        let style = LineStyle::solid(Color::rgb(0, 255, 0), 4.0);
+        backend.polyline(
+            &zigzag,
+            &style
+                .clone()
+                .with_join(LineJoin::Round)
+                .with_cap(LineCap::Round),
+        );
+        backend.polyline(&zigzag, &style);
+        backend.stroke_rect(Rect::new(0.0, 0.0, 10.0, 10.0), &style);
+        backend.end_frame();
+
+        let svg = backend.to_svg();
+        let lines: Vec<&str> = svg.lines().filter(|l| l.contains("stroke=")).collect();
+        assert!(lines[0].contains(r#"stroke-linecap="round" stroke-linejoin="round""#));
+        assert!(lines[1].contains(r#"stroke-linecap="butt" stroke-linejoin="miter""#));
+        // Closed shapes keep SVG's defaults implicit
+        assert!(!lines[2].contains("stroke-linejoin"));
+    }
+
+    #[test]
+    fn test_svg_translucent_paint_uses_opacity_attrs() {
+        let mut backend = SvgBackend::new(400, 300, 1.0);
+        backend.begin_frame(400.0, 300.0, 1.0);
+        let teal = Color::rgb(38, 166, 154);
+        backend.fill_rect(Rect::new(0.0, 0.0, 10.0, 10.0), teal.with_alpha(0.25));
+        backend.line(
+            Point::new(0.0, 0.0),
+            Point::new(10.0, 10.0),
+            &LineStyle::solid(teal.with_alpha(0.5), 1.0),
+        );
+        backend.fill_rect(Rect::new(0.0, 0.0, 10.0, 10.0), teal);
+        backend.end_frame();
+
+        let svg = backend.to_svg();
+        assert!(!svg.contains("rgba"));
+        assert!(svg.contains(r##"fill="#26a69a" fill-opacity="0.25""##));
+        assert!(svg.contains(r##"stroke="#26a69a" stroke-opacity="0.50""##));
+        assert!(svg.contains(r##"fill="#26a69a"/>"##));
+    }
 }
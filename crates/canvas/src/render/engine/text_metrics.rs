@@ -0,0 +1,117 @@
+//! Text width estimation for backends without font access
+//!
+//! SVG is laid out by the viewer, so the backend cannot ask a font how wide
+//! a string is. Instead we use per-character advance widths of a standard
+//! sans-serif (Helvetica/Arial metrics, 1000 units per em), scaled by font
+//! size and weight. This is close enough to size label boxes within a few
+//! percent of what browsers render.
+
+use super::types::{FontWeight, TextBaseline};
+
+/// Font design units per em for [`char_advance`]
+pub const UNITS_PER_EM: f64 = 1000.0;
+
+/// Height of capitals and digits above the baseline, in font units
+pub const CAP_HEIGHT: f64 = 718.0;
+
+/// Depth of descenders below the baseline, in font units
+pub const DESCENT: f64 = 207.0;
+
+/// Advance used for characters missing from the table
+pub const DEFAULT_ADVANCE: u16 = 556;
+
+/// Advance widths for ASCII 0x20..=0x7E
+const ASCII_ADVANCE: [u16; 95] = [
+    278, 278, 355, 556, 556, 889, 667, 191, // space ! " # $ % & '
+    333, 333, 389, 584, 278, 333, 278, 278, // ( ) * + , - . /
+    556, 556, 556, 556, 556, 556, 556, 556, // 0-7
+    556, 556, 278, 278, 584, 584, 584, 556, // 8 9 : ; < = > ?
+    1015, 667, 667, 722, 722, 667, 611, 778, // @ A-G
+    722, 278, 500, 667, 556, 833, 722, 778, // H-O
+    667, 778, 722, 667, 611, 722, 667, 944, // P-W
+    667, 667, 611, 278, 278, 278, 469, 556, // X Y Z [ \ ] ^ _
+    333, 556, 556, 500, 556, 556, 278, 556, // ` a-g
+    556, 222, 222, 500, 222, 833, 556, 556, // h-o
+    556, 556, 333, 500, 278, 556, 500, 722, // p-w
+    500, 500, 500, 334, 260, 334, 584, // x y z { | } ~
+];
+
+/// Advance width of one character in font units (1000 per em)
+pub fn char_advance(c: char) -> u16 {
+    match c {
+        ' '..='~' => ASCII_ADVANCE[c as usize - 0x20],
+        '\u{00A0}' => 278,
+        '°' => 400,
+        '±' | '×' | '÷' | '−' | '≈' | '≠' | '≤' | '≥' => 584,
+        '€' | '£' | '¥' | '¢' => 556,
+        '•' | '·' => 350,
+        '–' => 556,
+        '—' | '…' | '‰' => 1000,
+        '‘' | '’' => 222,
+        '“' | '”' => 333,
+        '←' | '↑' | '→' | '↓' => 1000,
+        '▲' | '▼' | '◆' | '●' | '■' => 722,
+        // CJK, Hangul and fullwidth forms are square
+        '\u{2E80}'..='\u{9FFF}' | '\u{AC00}'..='\u{D7AF}' | '\u{FF00}'..='\u{FF60}' => 1000,
+        // Combining marks take no space
+        '\u{0300}'..='\u{036F}' => 0,
+        _ => DEFAULT_ADVANCE,
+    }
+}
+
+/// Width multiplier for a font weight
+pub fn weight_factor(weight: FontWeight) -> f64 {
+    match weight {
+        FontWeight::Normal => 1.0,
+        FontWeight::Bold => 1.08,
+        FontWeight::Light => 0.96,
+    }
+}
+
+/// Estimated rendered width of a single line of text in pixels
+pub fn estimate_text_width(text: &str, font_size: f64, weight: FontWeight) -> f64 {
+    let units: u32 = text.chars().map(|c| char_advance(c) as u32).sum();
+    units as f64 / UNITS_PER_EM * font_size * weight_factor(weight)
+}
+
+/// Offset from a text anchor point down to the alphabetic baseline, in ems
+///
+/// `Top` hangs capitals and digits from the anchor, `Middle` centers them
+/// on it and `Bottom` keeps descenders above it. Backends that only draw
+/// from the baseline (SVG viewers without `dominant-baseline` support)
+/// shift by this to put labels where the caller anchored them.
+pub fn baseline_shift_em(baseline: TextBaseline) -> f64 {
+    match baseline {
+        TextBaseline::Top => CAP_HEIGHT / UNITS_PER_EM,
+        TextBaseline::Middle => CAP_HEIGHT / 2.0 / UNITS_PER_EM,
+        TextBaseline::Bottom => -DESCENT / UNITS_PER_EM,
+        TextBaseline::Alphabetic => 0.0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_estimate_matches_sans_serif_advances() {
+        // Helvetica: "Hi" = 722 + 222
+        assert!((estimate_text_width("Hi", 10.0, FontWeight::Normal) - 9.44).abs() < 1e-9);
+        assert!(
+            estimate_text_width("MMMM", 12.0, FontWeight::Normal)
+                > estimate_text_width("iiii", 12.0, FontWeight::Normal) * 3.0
+        );
+        assert!(
+            estimate_text_width("Bold", 12.0, FontWeight::Bold)
+                > estimate_text_width("Bold", 12.0, FontWeight::Normal)
+        );
+
+        // 40-character label at 12px; a flat 0.6em per char would give 288px
+        let label = "Liquidity sweep above 1.0845 (Asia high)";
+        assert_eq!(label.chars().count(), 40);
+        let width = estimate_text_width(label, 12.0, FontWeight::Normal);
+        assert!((width - 218.772).abs() < 1e-9);
+        assert_eq!(char_advance('漢'), 1000);
+        assert_eq!(char_advance('\u{1F600}'), DEFAULT_ADVANCE);
+    }
+}
@@ -3,7 +3,9 @@
 //! Minimal, zero-copy types optimized for high-frequency rendering.
 //! All types are `Copy` where possible to avoid allocation.
 
+use crate::core::{CssColorError, parse_css_color, parse_hex};
 use serde::{Deserialize, Serialize};
+use std::str::FromStr;
 
 // =============================================================================
 // Geometry Types (all Copy for zero-cost passing)
@@ -19,6 +21,15 @@ pub struct Color {
     pub a: u8,
 }
 
+impl FromStr for Color {
+    type Err = CssColorError;
+
+    fn from_str(css: &str) -> Result<Self, Self::Err> {
+        let (r, g, b, a) = parse_css_color(css)?;
+        Ok(Self::rgba(r, g, b, a))
+    }
+}
+
 impl Color {
     pub const TRANSPARENT: Color = Color {
         r: 0,
@@ -49,65 +60,18 @@ impl Color {
         Self { r, g, b, a }
     }
 
-    /// Create from hex string (#RGB, #RRGGBB, #RRGGBBAA)
+    /// Create from hex string (#RGB, #RGBA, #RRGGBB, #RRGGBBAA), `#` optional
     pub fn from_hex(hex: &str) -> Option<Self> {
-        let hex = hex.trim_start_matches('#');
-        match hex.len() {
-            3 => {
-                let r = u8::from_str_radix(&hex[0..1], 16).ok()? * 17;
-                let g = u8::from_str_radix(&hex[1..2], 16).ok()? * 17;
-                let b = u8::from_str_radix(&hex[2..3], 16).ok()? * 17;
-                Some(Self::rgb(r, g, b))
-            }
-            6 => {
-                let r = u8::from_str_radix(&hex[0..2], 16).ok()?;
-                let g = u8::from_str_radix(&hex[2..4], 16).ok()?;
-                let b = u8::from_str_radix(&hex[4..6], 16).ok()?;
-                Some(Self::rgb(r, g, b))
-            }
-            8 => {
-                let r = u8::from_str_radix(&hex[0..2], 16).ok()?;
-                let g = u8::from_str_radix(&hex[2..4], 16).ok()?;
-                let b = u8::from_str_radix(&hex[4..6], 16).ok()?;
-                let a = u8::from_str_radix(&hex[6..8], 16).ok()?;
-                Some(Self::rgba(r, g, b, a))
-            }
-            _ => None,
-        }
+        let (r, g, b, a) = parse_hex(hex.trim_start_matches('#'))?;
+        Some(Self::rgba(r, g, b, a))
     }
 
-    /// Parse CSS color string (hex, rgb(), rgba())
+    /// Parse CSS color string (hex, rgb(), rgba(), hsl(), hsla(), named)
+    ///
+    /// Same rules as [`parse_css_color`]; use `str::parse::<Color>()` to
+    /// learn why a string was rejected.
     pub fn from_css(css: &str) -> Option<Self> {
-        let css = css.trim();
-
-        if css.starts_with('#') {
-            return Self::from_hex(css);
-        }
-
-        if css.starts_with("rgba(") && css.ends_with(')') {
-            let inner = &css[5..css.len() - 1];
-            let parts: Vec<&str> = inner.split(',').map(|s| s.trim()).collect();
-            if parts.len() == 4 {
-                let r = parts[0].parse().ok()?;
-                let g = parts[1].parse().ok()?;
-                let b = parts[2].parse().ok()?;
-                let a = (parts[3].parse::<f64>().ok()? * 255.0) as u8;
-                return Some(Self::rgba(r, g, b, a));
-            }
-        }
-
-        if css.starts_with("rgb(") && css.ends_with(')') {
-            let inner = &css[4..css.len() - 1];
-            let parts: Vec<&str> = inner.split(',').map(|s| s.trim()).collect();
-            if parts.len() == 3 {
-                let r = parts[0].parse().ok()?;
-                let g = parts[1].parse().ok()?;
-                let b = parts[2].parse().ok()?;
-                return Some(Self::rgb(r, g, b));
-            }
-        }
-
-        None
+        css.parse().ok()
     }
 
     /// Convert to CSS string
@@ -161,6 +125,23 @@ impl Point {
         (dx * dx + dy * dy).sqrt()
     }
 
+    /// True when neither coordinate is NaN or infinite
+    #[inline]
+    pub fn is_finite(self) -> bool {
+        self.x.is_finite() && self.y.is_finite()
+    }
+
+    /// Distance to the closest point of segment `a`-`b`
+    pub fn distance_to_segment(self, a: Point, b: Point) -> f64 {
+        let (dx, dy) = (b.x - a.x, b.y - a.y);
+        let len_sq = dx * dx + dy * dy;
+        if len_sq == 0.0 {
+            return self.distance_to(a);
+        }
+        let t = (((self.x - a.x) * dx + (self.y - a.y) * dy) / len_sq).clamp(0.0, 1.0);
+        self.distance_to(Point::new(a.x + t * dx, a.y + t * dy))
+    }
+
     #[inline]
     pub fn lerp(self, other: Point, t: f64) -> Point {
         Point {
@@ -231,6 +212,14 @@ impl Rect {
         }
     }
 
+    /// True when no coordinate or side is NaN or infinite
+    #[inline]
+    pub fn is_finite(&self) -> bool {
+        [self.x, self.y, self.width, self.height]
+            .iter()
+            .all(|v| v.is_finite())
+    }
+
     #[inline]
     pub fn from_points(p1: Point, p2: Point) -> Self {
         let x = p1.x.min(p2.x);
@@ -418,6 +407,54 @@ pub enum LineJoin {
     Bevel,
 }
 
+impl LineCap {
+    /// Name as used by SVG and canvas `lineCap`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Butt => "butt",
+            Self::Round => "round",
+            Self::Square => "square",
+        }
+    }
+}
+
+impl std::str::FromStr for LineCap {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "butt" => Ok(Self::Butt),
+            "round" => Ok(Self::Round),
+            "square" => Ok(Self::Square),
+            _ => Err(()),
+        }
+    }
+}
+
+impl LineJoin {
+    /// Name as used by SVG and canvas `lineJoin`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Miter => "miter",
+            Self::Round => "round",
+            Self::Bevel => "bevel",
+        }
+    }
+}
+
+impl std::str::FromStr for LineJoin {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "miter" => Ok(Self::Miter),
+            "round" => Ok(Self::Round),
+            "bevel" => Ok(Self::Bevel),
+            _ => Err(()),
+        }
+    }
+}
+
 /// Line style configuration
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct LineStyle {
@@ -492,6 +529,18 @@ impl LineStyle {
     }
 }
 
+/// Which regions of a self-intersecting path count as inside when filled
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FillRule {
+    /// Inside where the winding number is non-zero (SVG and canvas default)
+    #[default]
+    NonZero,
+    /// Inside where a ray crosses an odd number of edges, so overlapping
+    /// loops leave holes
+    EvenOdd,
+}
+
 /// Fill style
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub enum FillStyle {
@@ -703,10 +752,24 @@ mod tests {
         );
         assert_eq!(
             Color::from_css("rgba(0, 255, 0, 0.5)"),
-            Some(Color::rgba(0, 255, 0, 127))
+            Some(Color::rgba(0, 255, 0, 128))
+        );
+        assert_eq!(Color::from_css("teal"), Some(Color::rgb(0, 128, 128)));
+        assert_eq!(Color::from_css("rgb(1, 2)"), None);
+        assert_eq!(
+            "#12".parse::<Color>(),
+            Err(CssColorError::InvalidHex("#12".into()))
         );
     }
 
+    proptest::proptest! {
+        #[test]
+        fn prop_to_css_round_trips(r: u8, g: u8, b: u8, a: u8) {
+            let color = Color::rgba(r, g, b, a);
+            proptest::prop_assert_eq!(color.to_css().parse::<Color>(), Ok(color));
+        }
+    }
+
     #[test]
     fn test_rect_operations() {
         let r1 = Rect::new(0.0, 0.0, 100.0, 100.0);
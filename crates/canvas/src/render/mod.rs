@@ -45,7 +45,8 @@ pub mod chart;
 // Core types
 pub use engine::{Color, Point, Rect, Transform2D};
 pub use engine::{
-    FillStyle, FontWeight, LineCap, LineJoin, LineStyle, TextAlign, TextBaseline, TextStyle,
+    FillRule, FillStyle, FontWeight, LineCap, LineJoin, LineStyle, TextAlign, TextBaseline,
+    TextStyle,
 };
 
 // Path
@@ -57,15 +58,22 @@ pub use engine::RenderCommand;
 // Batch
 pub use engine::{RenderBatch, RenderQueue, layers};
 
+// Frame diffs
+pub use engine::{CommandDelta, FRAGMENT_ID_PREFIX, FrameElements, RenderDiff, SvgFragment};
+
 // Backend
 pub use engine::{
-    ImageInfo, NullBackend, RenderBackend, RenderError, RenderResult, SvgBackend, TextMetrics,
+    ImageInfo, NullBackend, PrecisionCategory, RenderBackend, RenderError, RenderResult,
+    SvgBackend, SvgPrecision, TextMetrics,
 };
 
+// Text measurement
+pub use engine::{char_advance, estimate_text_width};
+
 // Crisp rendering
 pub use engine::{
-    crisp_bar_width, crisp_coord, crisp_line_coords, crisp_point, crisp_rect, crisp_rect_struct,
-    stroke_offset,
+    crisp_bar_width, crisp_coord, crisp_line_coords, crisp_line_coords_xy, crisp_point, crisp_rect,
+    crisp_rect_struct, crisp_stroke_coord, crisp_stroke_coords_xy, stroke_offset,
 };
 
 // Coordinate conversion
@@ -87,4 +95,7 @@ pub use chart::{render_grid, render_legend, render_watermark};
 pub use chart::{render_markers, render_price_lines};
 
 // Indicator and signal rendering
-pub use chart::{render_indicator, render_signals, render_strategy};
+pub use chart::{
+    StrategyParams, VectorContext, render_indicator, render_indicator_fills, render_signals,
+    render_strategy, render_vector,
+};